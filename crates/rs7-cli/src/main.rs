@@ -1,10 +1,12 @@
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use colored::Colorize;
-use rs7_core::Version;
-use rs7_parser::parse_message;
+use rs7_core::builders::ack::{build_ack, build_ack_with_text, AckCode};
+use rs7_core::{Message, Version};
+use rs7_parser::{parse_message, parse_message_bytes};
 use rs7_terser::Terser;
 use rs7_validator::Validator;
+use rs7_xml::{XmlDecoder, XmlEncoder, XmlEncoderConfig};
 use serde_json::json;
 use std::fs;
 use std::io::{self, Read};
@@ -63,6 +65,11 @@ enum Commands {
         /// Output format: text, json
         #[arg(short, long, default_value = "text")]
         format: String,
+
+        /// With --format json, emit numbers as JSON numbers, date/times as
+        /// ISO 8601 strings and absent values as null
+        #[arg(long)]
+        typed: bool,
     },
 
     /// Convert message to different formats
@@ -71,13 +78,21 @@ enum Commands {
         #[arg(value_name = "FILE")]
         input: String,
 
-        /// Output format: json, fhir
+        /// Output format: json, xml, fhir
         #[arg(short, long, required = true)]
         to: String,
 
+        /// Input format: auto, hl7, xml (auto detects XML by a leading '<')
+        #[arg(long, default_value = "auto", value_parser = ["auto", "hl7", "xml"])]
+        from: String,
+
         /// Pretty-print output
         #[arg(short, long)]
         pretty: bool,
+
+        /// Include the HL7 v2 namespace in XML output
+        #[arg(long)]
+        namespace: bool,
     },
 
     /// Display message information
@@ -86,6 +101,25 @@ enum Commands {
         #[arg(value_name = "FILE")]
         input: String,
     },
+
+    /// Generate an acknowledgment (ACK) for a message
+    Ack {
+        /// Input file path (use '-' for stdin)
+        #[arg(value_name = "FILE")]
+        input: String,
+
+        /// Acknowledgment code: AA, AE, AR
+        #[arg(short, long, default_value = "AA", value_parser = ["AA", "AE", "AR"])]
+        code: String,
+
+        /// Text message for MSA-3
+        #[arg(short, long)]
+        text: Option<String>,
+
+        /// Output format: hl7, json
+        #[arg(short, long, default_value = "hl7")]
+        format: String,
+    },
 }
 
 fn main() -> Result<()> {
@@ -106,9 +140,22 @@ fn main() -> Result<()> {
             input,
             paths,
             format,
-        } => extract_command(&input, &paths, &format)?,
-        Commands::Convert { input, to, pretty } => convert_command(&input, &to, pretty)?,
+            typed,
+        } => extract_command(&input, &paths, &format, typed)?,
+        Commands::Convert {
+            input,
+            to,
+            from,
+            pretty,
+            namespace,
+        } => convert_command(&input, &to, &from, pretty, namespace)?,
         Commands::Info { input } => info_command(&input)?,
+        Commands::Ack {
+            input,
+            code,
+            text,
+            format,
+        } => ack_command(&input, &code, text.as_deref(), &format)?,
     }
 
     Ok(())
@@ -126,6 +173,19 @@ fn read_input(input: &str) -> Result<String> {
     }
 }
 
+/// Read raw bytes from a file or stdin, leaving decoding to MSH-18
+fn read_input_bytes(input: &str) -> Result<Vec<u8>> {
+    if input == "-" {
+        let mut buffer = Vec::new();
+        io::stdin()
+            .read_to_end(&mut buffer)
+            .context("Failed to read from stdin")?;
+        Ok(buffer)
+    } else {
+        fs::read(input).context(format!("Failed to read file: {}", input))
+    }
+}
+
 fn parse_command(input: &str, format: &str, detailed: bool) -> Result<()> {
     let content = read_input(input)?;
     let message = parse_message(&content).context("Failed to parse HL7 message")?;
@@ -271,7 +331,7 @@ fn validate_command(input: &str, version: Option<&str>, format: &str) -> Result<
     Ok(())
 }
 
-fn extract_command(input: &str, paths: &[String], format: &str) -> Result<()> {
+fn extract_command(input: &str, paths: &[String], format: &str, typed: bool) -> Result<()> {
     let content = read_input(input)?;
     let message = parse_message(&content).context("Failed to parse HL7 message")?;
     let terser = Terser::new(&message);
@@ -280,8 +340,12 @@ fn extract_command(input: &str, paths: &[String], format: &str) -> Result<()> {
         "json" => {
             let mut results = serde_json::Map::new();
             for path in paths {
-                let value = terser.get(path).ok().flatten().unwrap_or("").to_string();
-                results.insert(path.clone(), json!(value));
+                let value = if typed {
+                    typed_value(&terser, path)
+                } else {
+                    json!(terser.get(path).ok().flatten().unwrap_or(""))
+                };
+                results.insert(path.clone(), value);
             }
             println!("{}", serde_json::to_string_pretty(&results)?);
         }
@@ -300,9 +364,56 @@ fn extract_command(input: &str, paths: &[String], format: &str) -> Result<()> {
     Ok(())
 }
 
-fn convert_command(input: &str, to: &str, pretty: bool) -> Result<()> {
+/// Convert an extracted value to the closest JSON type
+///
+/// Absent or empty values become `null`. Values starting with a full
+/// YYYYMMDD date that the Terser reads as a date/time become ISO 8601 strings
+/// at their own precision, then values the Terser reads as integers or
+/// decimals become numbers when they print back to exactly the same text, so
+/// identifiers such as `007` and values such as `1.50` or
+/// `12345678901234567890` stay strings.
+fn typed_value(terser: &Terser, path: &str) -> serde_json::Value {
+    let value = match terser.get(path).ok().flatten().map(str::trim) {
+        Some(v) if !v.is_empty() => v,
+        _ => return serde_json::Value::Null,
+    };
+
+    // Checked first, as a YYYYMMDD date is also an integer
+    let date_like = value.len() >= 8 && value.as_bytes()[..8].iter().all(u8::is_ascii_digit);
+    if date_like && let Ok(Some(timestamp)) = terser.get_timestamp(path) {
+        return json!(timestamp.to_iso8601());
+    }
+
+    if let Ok(Some(int)) = terser.get_int(path)
+        && int.to_string() == value
+    {
+        return json!(int);
+    }
+    if value.contains('.')
+        && let Ok(Some(float)) = terser.get_f64(path)
+        && float.is_finite()
+        && float.to_string() == value
+    {
+        return json!(float);
+    }
+
+    json!(value)
+}
+
+fn convert_command(input: &str, to: &str, from: &str, pretty: bool, namespace: bool) -> Result<()> {
     let content = read_input(input)?;
-    let message = parse_message(&content).context("Failed to parse HL7 message")?;
+    let is_xml = match from {
+        "xml" => true,
+        "hl7" => false,
+        _ => content.trim_start().starts_with('<'),
+    };
+    let message = if is_xml {
+        XmlDecoder::new()
+            .decode(&content)
+            .context("Failed to decode HL7 XML message")?
+    } else {
+        parse_message(&content).context("Failed to parse HL7 message")?
+    };
 
     match to {
         "json" => {
@@ -314,12 +425,7 @@ fn convert_command(input: &str, to: &str, pretty: bool) -> Result<()> {
                 "sending_facility": message.get_sending_facility(),
                 "receiving_application": message.get_receiving_application(),
                 "receiving_facility": message.get_receiving_facility(),
-                "segments": message.segments.iter().map(|seg| {
-                    json!({
-                        "id": seg.id,
-                        "fields": seg.fields.iter().map(|f| f.encode(&message.delimiters)).collect::<Vec<_>>(),
-                    })
-                }).collect::<Vec<_>>(),
+                "segments": segments_json(&message),
             });
 
             if pretty {
@@ -328,10 +434,21 @@ fn convert_command(input: &str, to: &str, pretty: bool) -> Result<()> {
                 println!("{}", serde_json::to_string(&json)?);
             }
         }
+        "xml" => {
+            let config = XmlEncoderConfig {
+                pretty_print: pretty,
+                include_namespace: namespace,
+                ..Default::default()
+            };
+            let xml = XmlEncoder::with_config(config)
+                .encode(&message)
+                .context("Failed to encode message as XML")?;
+            println!("{}", xml);
+        }
         "fhir" => {
             #[cfg(feature = "fhir")]
             {
-                use rs7_fhir::converter::MessageConverter;
+                use rs7_fhir::converters::MessageConverter;
                 let converter = MessageConverter::new();
                 match converter.convert(&message) {
                     Ok(bundle) => {
@@ -363,8 +480,8 @@ fn convert_command(input: &str, to: &str, pretty: bool) -> Result<()> {
 }
 
 fn info_command(input: &str) -> Result<()> {
-    let content = read_input(input)?;
-    let message = parse_message(&content).context("Failed to parse HL7 message")?;
+    let content = read_input_bytes(input)?;
+    let message = parse_message_bytes(&content).context("Failed to parse HL7 message")?;
 
     println!("{}", "HL7 Message Information".bold().green());
     println!("{}", "=".repeat(50).bright_black());
@@ -378,28 +495,95 @@ fn info_command(input: &str) -> Result<()> {
     println!("  Receiving Application: {}", message.get_receiving_application().unwrap_or("N/A").cyan());
     println!("  Receiving Facility:   {}", message.get_receiving_facility().unwrap_or("N/A").cyan());
 
-    println!("\n{}", "Message Structure:".bold());
-    println!("  Total Segments:       {}", message.segments.len().to_string().yellow());
-
-    // Count segment types
-    let mut segment_counts = std::collections::HashMap::new();
-    for segment in &message.segments {
-        *segment_counts.entry(&segment.id).or_insert(0) += 1;
+    println!("\n{}", "Encoding:".bold());
+    let delimiters = &message.delimiters;
+    println!("  Field Separator:      {}", delimiters.field_separator.to_string().cyan());
+    println!("  Component Separator:  {}", delimiters.component_separator.to_string().cyan());
+    println!("  Repetition Separator: {}", delimiters.repetition_separator.to_string().cyan());
+    println!("  Escape Character:     {}", delimiters.escape_character.to_string().cyan());
+    println!("  Subcomponent Separator: {}", delimiters.subcomponent_separator.to_string().cyan());
+    if let Some(truncation) = delimiters.truncation_character {
+        println!("  Truncation Character: {}", truncation.to_string().cyan());
+    }
+    let charset = message
+        .get_msh()
+        .and_then(|msh| msh.get_field_value(18))
+        .filter(|value| !value.is_empty());
+    match charset {
+        Some(value) => println!("  Character Set:        {}", value.cyan()),
+        None => println!("  Character Set:        {}", "Not specified (ASCII/UTF-8)".bright_black()),
+    }
+    let non_ascii = content.iter().filter(|b| !b.is_ascii()).count();
+    if non_ascii > 0 {
+        println!("  Non-ASCII Content:    {}", format!("Yes ({} bytes)", non_ascii).yellow());
+    } else {
+        println!("  Non-ASCII Content:    {}", "No".cyan());
     }
 
-    println!("  Segment Types:        {}", segment_counts.len().to_string().yellow());
+    let stats = message.stats();
+    println!("\n{}", "Message Structure:".bold());
+    println!("  Total Segments:       {}", stats.segment_count.to_string().yellow());
+    println!("  Segment Types:        {}", stats.segments.len().to_string().yellow());
+    println!("  Max Field Count:      {}", stats.max_field_count.to_string().yellow());
+    println!("  Max Nesting Depth:    {}", stats.max_depth.to_string().yellow());
     println!("\n  Segment Breakdown:");
-    let mut sorted_segments: Vec<_> = segment_counts.iter().collect();
-    sorted_segments.sort_by(|a, b| a.0.cmp(b.0));
-    for (id, count) in sorted_segments {
-        println!("    {} x {}", count.to_string().bright_white(), id.bright_cyan());
+    for (id, segment) in &stats.segments {
+        println!(
+            "    {} x {} ({} bytes)",
+            segment.count.to_string().bright_white(),
+            id.bright_cyan(),
+            segment.bytes
+        );
     }
 
-    // Calculate message size
-    let encoded = message.encode();
     println!("\n{}", "Size Information:".bold());
-    println!("  Encoded Size:         {} bytes", encoded.len().to_string().yellow());
-    println!("  Average Segment Size: {} bytes", (encoded.len() / message.segments.len()).to_string().yellow());
+    println!("  Encoded Size:         {} bytes", stats.total_bytes.to_string().yellow());
+    println!("  Average Segment Size: {} bytes", stats.average_segment_bytes().to_string().yellow());
+
+    Ok(())
+}
+
+fn ack_command(input: &str, code: &str, text: Option<&str>, format: &str) -> Result<()> {
+    let content = read_input(input)?;
+    let message = parse_message(&content).context("Failed to parse HL7 message")?;
+
+    let code = match code {
+        "AE" => AckCode::Error,
+        "AR" => AckCode::Reject,
+        _ => AckCode::Accept,
+    };
+    let ack = match text {
+        Some(text) => build_ack_with_text(&message, code, text),
+        None => build_ack(&message, code),
+    }
+    .context("Failed to build ACK")?;
+
+    match format {
+        "json" => {
+            let json = json!({
+                "ack_code": code.as_str(),
+                "control_id": ack.get_control_id(),
+                "acknowledged_control_id": message.get_control_id(),
+                "encoded": ack.encode(),
+                "segments": segments_json(&ack),
+            });
+            println!("{}", serde_json::to_string_pretty(&json)?);
+        }
+        _ => println!("{}", ack.encode()),
+    }
 
     Ok(())
 }
+
+fn segments_json(message: &Message) -> Vec<serde_json::Value> {
+    message
+        .segments
+        .iter()
+        .map(|seg| {
+            json!({
+                "id": seg.id,
+                "fields": seg.fields.iter().map(|f| f.encode(&message.delimiters)).collect::<Vec<_>>(),
+            })
+        })
+        .collect()
+}
@@ -47,4 +47,8 @@ pub enum ConformanceError {
     /// Invalid binding strength
     #[error("Invalid binding strength: {0}")]
     InvalidBindingStrength(String),
+
+    /// Error serializing a validation report
+    #[error("Serialization error: {0}")]
+    SerializationError(#[from] serde_json::Error),
 }
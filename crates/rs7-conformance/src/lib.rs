@@ -10,6 +10,9 @@
 //! - **Usage Validation**: Enforce R (Required), RE (Required if Known), O (Optional), X (Not Used)
 //! - **Cardinality Validation**: Check min/max occurrence constraints
 //! - **Length Validation**: Enforce maximum field lengths
+//! - **Value Set Bindings**: Check coded values against `TableRegistry` tables by binding strength
+//! - **Co-constraints**: Cross-field `IF ... THEN` conditions evaluated per message
+//! - **Component Validation**: Usage and length of individual components, reported as e.g. `PID-5.1`
 //! - **Integration**: Seamless integration with rs7-validator
 //!
 //! ## Quick Start
@@ -50,10 +53,7 @@
 //!
 //! Future releases will add:
 //! - Conditional predicates (C usage codes)
-//! - Component-level validation
 //! - Data type flavors
-//! - Value set bindings
-//! - Co-constraints
 
 pub mod error;
 pub mod predicate;
@@ -69,6 +69,6 @@ pub use profile::{
     ProfileMetadata, SegmentProfile, Usage, ValueSetBinding,
 };
 pub use validator::{
-    ConformanceErrorType, ConformanceValidationResult, ConformanceValidator, Severity,
-    ValidationLocation,
+    ConformanceErrorType, ConformanceValidationResult, ConformanceValidator,
+    NistReportEntry, NistValidationReport, Severity, ValidationLocation,
 };
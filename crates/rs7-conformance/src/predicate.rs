@@ -7,10 +7,13 @@
 //! ## Supported Condition Types
 //!
 //! - **IS VALUED**: Check if a field has a non-empty value
-//! - **IS NOT VALUED**: Check if a field is empty or missing
+//! - **IS NOT VALUED** / **NOT VALUED**: Check if a field is empty or missing
 //! - **Equality**: Compare field value to literal (e.g., "PID-8 = 'M'")
 //! - **Comparison**: Numeric comparisons (>, <, >=, <=, !=)
+//! - **Membership**: `IN (...)` / `NOT IN (...)` against a list of literals
 //! - **Boolean Logic**: AND, OR operators for complex conditions
+//! - **Implication**: `[IF] <condition> THEN <condition>` for co-constraints;
+//!   `SHALL BE VALUED` / `SHALL NOT BE VALUED` read as `IS VALUED` / `IS NOT VALUED`
 //!
 //! ## Example Conditions
 //!
@@ -19,7 +22,15 @@
 //! PID-8 = 'M'
 //! PID-7 IS NOT VALUED
 //! PID-8 IS VALUED AND PV1-2 = 'I'
+//! IF PID-30 = 'Y' THEN PID-29 SHALL BE VALUED
+//! MSH-9-1 = "ADT" AND PID-5-1 != "DOE"
+//! PID-3-4-1 NOT VALUED
+//! PID-8 IN ('M', 'F', 'U')
 //! ```
+//!
+//! Paths are Terser paths, so a condition can address any component or
+//! subcomponent (`PID-5-1`, `PID-3(2)-4-1`). Literals may be quoted with
+//! single or double quotes.
 
 use crate::error::{ConformanceError, Result};
 use rs7_core::Message;
@@ -36,6 +47,8 @@ pub enum Condition {
     Equals(String, String),
     /// Field does not equal literal value
     NotEquals(String, String),
+    /// Field equals one of the literal values (e.g., "PID-8 IN ('M', 'F')")
+    In(String, Vec<String>),
     /// Numeric greater than comparison
     GreaterThan(String, f64),
     /// Numeric less than comparison
@@ -50,6 +63,8 @@ pub enum Condition {
     Or(Box<Condition>, Box<Condition>),
     /// Logical NOT of a condition
     Not(Box<Condition>),
+    /// Implication: when the first condition holds, the second must too
+    Implies(Box<Condition>, Box<Condition>),
 }
 
 impl Condition {
@@ -90,6 +105,13 @@ impl Condition {
                     true
                 }
             }
+            Condition::In(path, values) => {
+                if let Ok(Some(value)) = terser.get(path) {
+                    values.iter().any(|expected| value.trim() == expected.trim())
+                } else {
+                    false
+                }
+            }
             Condition::GreaterThan(path, threshold) => {
                 if let Ok(Some(value)) = terser.get(path) {
                     if let Ok(num) = value.trim().parse::<f64>() {
@@ -141,6 +163,30 @@ impl Condition {
                 left.evaluate_with_terser(terser) || right.evaluate_with_terser(terser)
             }
             Condition::Not(inner) => !inner.evaluate_with_terser(terser),
+            Condition::Implies(antecedent, consequent) => {
+                !antecedent.evaluate_with_terser(terser) || consequent.evaluate_with_terser(terser)
+            }
+        }
+    }
+
+    /// The field path a violation of this condition is best reported at
+    ///
+    /// For an implication this is the path of the consequent, e.g. `PID-29`
+    /// for `PID-30 = 'Y' THEN PID-29 IS VALUED`.
+    pub fn primary_path(&self) -> &str {
+        match self {
+            Condition::IsValued(path)
+            | Condition::IsNotValued(path)
+            | Condition::Equals(path, _)
+            | Condition::NotEquals(path, _)
+            | Condition::In(path, _)
+            | Condition::GreaterThan(path, _)
+            | Condition::LessThan(path, _)
+            | Condition::GreaterThanOrEqual(path, _)
+            | Condition::LessThanOrEqual(path, _) => path,
+            Condition::And(left, _) | Condition::Or(left, _) => left.primary_path(),
+            Condition::Not(inner) => inner.primary_path(),
+            Condition::Implies(_, consequent) => consequent.primary_path(),
         }
     }
 }
@@ -163,9 +209,25 @@ impl PredicateParser {
     /// assert_eq!(cond, Condition::Equals("PID-8".to_string(), "M".to_string()));
     /// ```
     pub fn parse(input: &str) -> Result<Condition> {
-        let trimmed = input.trim();
+        let mut trimmed = input.trim();
+
+        // Handle IF ... THEN (lowest precedence)
+        if trimmed.to_uppercase().starts_with("IF ") {
+            trimmed = trimmed[3..].trim_start();
+            if Self::find_operator(trimmed, " THEN ").is_none() {
+                return Err(ConformanceError::InvalidPredicate(format!(
+                    "IF without THEN: {}",
+                    input.trim()
+                )));
+            }
+        }
+        if let Some(then_pos) = Self::find_operator(trimmed, " THEN ") {
+            let antecedent = Self::parse(&trimmed[..then_pos])?;
+            let consequent = Self::parse(&trimmed[then_pos + 6..])?;
+            return Ok(Condition::Implies(Box::new(antecedent), Box::new(consequent)));
+        }
 
-        // Handle OR (lowest precedence)
+        // Handle OR
         if let Some(or_pos) = Self::find_operator(trimmed, " OR ") {
             let left = Self::parse(&trimmed[..or_pos])?;
             let right = Self::parse(&trimmed[or_pos + 4..])?;
@@ -199,6 +261,35 @@ impl PredicateParser {
             return Ok(Condition::IsNotValued(path));
         }
 
+        // Handle NOT VALUED
+        if let Some(pos) = trimmed.to_uppercase().find(" NOT VALUED") {
+            let path = trimmed[..pos].trim().to_string();
+            Self::validate_path(&path)?;
+            return Ok(Condition::IsNotValued(path));
+        }
+
+        // Handle SHALL BE VALUED / SHALL NOT BE VALUED
+        if let Some(pos) = trimmed.to_uppercase().find(" SHALL BE VALUED") {
+            let path = trimmed[..pos].trim().to_string();
+            Self::validate_path(&path)?;
+            return Ok(Condition::IsValued(path));
+        }
+        if let Some(pos) = trimmed.to_uppercase().find(" SHALL NOT BE VALUED") {
+            let path = trimmed[..pos].trim().to_string();
+            Self::validate_path(&path)?;
+            return Ok(Condition::IsNotValued(path));
+        }
+
+        // Handle NOT IN / IN
+        if let Some(pos) = Self::find_operator(trimmed, " NOT IN") {
+            let (path, values) = Self::parse_membership(&trimmed[..pos], &trimmed[pos + 7..])?;
+            return Ok(Condition::Not(Box::new(Condition::In(path, values))));
+        }
+        if let Some(pos) = Self::find_operator(trimmed, " IN") {
+            let (path, values) = Self::parse_membership(&trimmed[..pos], &trimmed[pos + 3..])?;
+            return Ok(Condition::In(path, values));
+        }
+
         // Handle comparison operators
         if let Some((path, op, value)) = Self::parse_comparison(trimmed)? {
             Self::validate_path(&path)?;
@@ -235,18 +326,28 @@ impl PredicateParser {
     }
 
     /// Find operator position, ignoring quoted strings
+    ///
+    /// Word operators ending in a letter (e.g. ` IN`) only match when followed
+    /// by a space or an opening parenthesis.
     fn find_operator(input: &str, operator: &str) -> Option<usize> {
         let upper = input.to_uppercase();
-        let mut in_quotes = false;
+        let mut quote = None;
         let mut i = 0;
 
         while i < input.len() {
-            if input.as_bytes()[i] == b'\'' {
-                in_quotes = !in_quotes;
+            match (input.as_bytes()[i], quote) {
+                (c @ (b'\'' | b'"'), None) => quote = Some(c),
+                (c, Some(open)) if c == open => quote = None,
+                _ => {}
             }
 
-            if !in_quotes && upper[i..].starts_with(operator) {
-                return Some(i);
+            if quote.is_none() && upper.is_char_boundary(i) && upper[i..].starts_with(operator) {
+                let next = upper.as_bytes().get(i + operator.len()).copied();
+                let word_end = !operator.ends_with(|c: char| c.is_ascii_alphabetic())
+                    || matches!(next, Some(b' ' | b'('));
+                if word_end {
+                    return Some(i);
+                }
             }
 
             i += 1;
@@ -255,6 +356,45 @@ impl PredicateParser {
         None
     }
 
+    /// Parse the path and value list of an `IN (...)` condition
+    fn parse_membership(path: &str, list: &str) -> Result<(String, Vec<String>)> {
+        let path = path.trim().to_string();
+        Self::validate_path(&path)?;
+
+        let list = list.trim();
+        let Some(inner) = list.strip_prefix('(').and_then(|l| l.strip_suffix(')')) else {
+            return Err(ConformanceError::InvalidPredicate(format!(
+                "IN expects a parenthesized list: {}",
+                list
+            )));
+        };
+
+        let values: Vec<String> = inner
+            .split(',')
+            .map(|value| Self::unquote(value.trim()).to_string())
+            .collect();
+        if values.iter().all(|value| value.is_empty()) {
+            return Err(ConformanceError::InvalidPredicate(format!(
+                "Empty IN list for {}",
+                path
+            )));
+        }
+
+        Ok((path, values))
+    }
+
+    /// Strip matching single or double quotes from a literal
+    fn unquote(value: &str) -> &str {
+        if value.len() >= 2
+            && ((value.starts_with('\'') && value.ends_with('\''))
+                || (value.starts_with('"') && value.ends_with('"')))
+        {
+            &value[1..value.len() - 1]
+        } else {
+            value
+        }
+    }
+
     /// Parse comparison expression (path op value)
     fn parse_comparison(input: &str) -> Result<Option<(String, String, String)>> {
         // Try operators in order of length (to match >= before =, etc.)
@@ -266,13 +406,7 @@ impl PredicateParser {
                 let value_part = input[op_pos + op.len()..].trim();
 
                 // Remove quotes if present
-                let value = if value_part.starts_with('\'') && value_part.ends_with('\'') {
-                    value_part[1..value_part.len() - 1].to_string()
-                } else if value_part.starts_with('"') && value_part.ends_with('"') {
-                    value_part[1..value_part.len() - 1].to_string()
-                } else {
-                    value_part.to_string()
-                };
+                let value = Self::unquote(value_part).to_string();
 
                 return Ok(Some((path, op.to_string(), value)));
             }
@@ -289,13 +423,12 @@ impl PredicateParser {
             ));
         }
 
-        // Basic validation: should contain at least one dash
-        if !path.contains('-') {
-            return Err(ConformanceError::InvalidPredicate(format!(
-                "Invalid field path: {}",
-                path
-            )));
-        }
+        // Paths are resolved by the Terser, which parses them before looking
+        // anything up, so an empty message is enough to check the syntax
+        let empty = Message::default();
+        Terser::new(&empty).get(path).map_err(|e| {
+            ConformanceError::InvalidPredicate(format!("Invalid field path {}: {}", path, e))
+        })?;
 
         Ok(())
     }
@@ -465,6 +598,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_implies() {
+        let expected = Condition::Implies(
+            Box::new(Condition::Equals("PID-30".to_string(), "Y".to_string())),
+            Box::new(Condition::IsValued("PID-29".to_string())),
+        );
+
+        assert_eq!(
+            PredicateParser::parse("IF PID-30 = 'Y' THEN PID-29 SHALL BE VALUED").unwrap(),
+            expected
+        );
+        assert_eq!(
+            PredicateParser::parse("PID-30 = 'Y' THEN PID-29 IS VALUED").unwrap(),
+            expected
+        );
+        assert_eq!(expected.primary_path(), "PID-29");
+
+        assert!(PredicateParser::parse("IF PID-30 = 'Y'").is_err());
+    }
+
+    #[test]
+    fn test_evaluate_implies() {
+        let message = create_test_message();
+
+        // Antecedent true, consequent true
+        let cond = PredicateParser::parse("IF PID-8 = 'M' THEN PID-7 IS VALUED").unwrap();
+        assert!(cond.evaluate(&message));
+
+        // Antecedent true, consequent false
+        let cond = PredicateParser::parse("IF PID-8 = 'M' THEN PID-6 SHALL BE VALUED").unwrap();
+        assert!(!cond.evaluate(&message));
+
+        // Antecedent false
+        let cond = PredicateParser::parse("IF PID-8 = 'F' THEN PID-6 IS VALUED").unwrap();
+        assert!(cond.evaluate(&message));
+    }
+
     #[test]
     fn test_parse_not() {
         let result = PredicateParser::parse("NOT PID-8 IS VALUED");
@@ -553,6 +723,69 @@ mod tests {
         assert!(condition.evaluate(&message));
     }
 
+    #[test]
+    fn test_parse_component_conditions() {
+        assert_eq!(
+            PredicateParser::parse("PID-5-1 = \"DOE\"").unwrap(),
+            Condition::Equals("PID-5-1".to_string(), "DOE".to_string())
+        );
+        assert_eq!(
+            PredicateParser::parse("PID-3(2)-4-1 NOT VALUED").unwrap(),
+            Condition::IsNotValued("PID-3(2)-4-1".to_string())
+        );
+        assert_eq!(
+            PredicateParser::parse("PID-8 IN ('M', \"F\", U)").unwrap(),
+            Condition::In(
+                "PID-8".to_string(),
+                vec!["M".to_string(), "F".to_string(), "U".to_string()]
+            )
+        );
+        assert_eq!(
+            PredicateParser::parse("PID-8 NOT IN('M','F')").unwrap(),
+            Condition::Not(Box::new(Condition::In(
+                "PID-8".to_string(),
+                vec!["M".to_string(), "F".to_string()]
+            )))
+        );
+
+        // Double-quoted literals may contain operator keywords
+        match PredicateParser::parse("PID-5-1 = \"A AND B\" OR PID-8 IS VALUED").unwrap() {
+            Condition::Or(left, _) => assert_eq!(
+                *left,
+                Condition::Equals("PID-5-1".to_string(), "A AND B".to_string())
+            ),
+            other => panic!("Expected Or condition, got {:?}", other),
+        }
+
+        assert!(PredicateParser::parse("PID-8 IN 'M'").is_err());
+        assert!(PredicateParser::parse("PID-8 IN ()").is_err());
+        assert!(PredicateParser::parse("PID-x-1 IS VALUED").is_err());
+    }
+
+    #[test]
+    fn test_evaluate_component_conditions() {
+        let message = rs7_parser::parse_message(
+            "MSH|^~\\&|APP|FAC|APP|FAC|20240101||ADT^A01|1|P|2.5\r\
+             PID|1||123^^^HOSP&1.2.3&ISO~456^^^CLINIC||DOE^JOHN||19800101|M",
+        )
+        .unwrap();
+
+        let holds = |condition: &str| PredicateParser::parse(condition).unwrap().evaluate(&message);
+
+        assert!(holds("MSH-9-1 = \"ADT\""));
+        assert!(holds("MSH-9-2 != 'A04'"));
+        assert!(holds("PID-5-1 = \"DOE\" AND PID-5-2 = 'JOHN'"));
+        assert!(!holds("PID-5-1 = 'JOHN'"));
+        assert!(holds("PID-3-4-2 = '1.2.3'"));
+        assert!(holds("PID-3(1)-4-1 = 'CLINIC'"));
+        assert!(holds("PID-3(1)-4-2 NOT VALUED"));
+        assert!(holds("PID-8 IN ('M', 'F')"));
+        assert!(!holds("PID-8 NOT IN ('M', 'F')"));
+        assert!(!holds("PID-9 IN ('M', 'F')"));
+        assert!(holds("PID-9 NOT IN ('M', 'F')"));
+        assert!(holds("IF MSH-9-1 = 'ADT' THEN PID-5-1 IS VALUED"));
+    }
+
     #[test]
     fn test_invalid_path() {
         let result = PredicateParser::parse("INVALID IS VALUED");
@@ -145,7 +145,7 @@ pub struct ComponentProfile {
     pub datatype: Option<String>,
     /// Maximum length
     pub length: Option<usize>,
-    /// HL7 table ID
+    /// HL7 table ID the component value is validated against
     pub table_id: Option<String>,
 }
 
@@ -1,24 +1,47 @@
 //! Conformance profile validator
 
+pub mod report;
 pub mod result;
 
+pub use report::{NistReportEntry, NistValidationReport};
 pub use result::{
     ConformanceErrorType, ConformanceValidationError, ConformanceValidationInfo,
     ConformanceValidationResult, ConformanceValidationWarning, Severity, ValidationLocation,
 };
 
-use crate::profile::{ConditionalUsage, ConformanceProfile, FieldProfile, SegmentProfile, Usage};
+use crate::profile::{
+    BindingStrength, ComponentProfile, ConditionalUsage, ConformanceProfile, FieldProfile,
+    SegmentProfile, Usage, ValueSetBinding,
+};
 use rs7_core::{Message, Segment};
+use rs7_validator::{TableRegistry, VocabularyValidation};
 
 /// Conformance profile validator
 pub struct ConformanceValidator {
     profile: ConformanceProfile,
+    /// Source of value-set contents, keyed by `value_set_id`
+    tables: TableRegistry,
 }
 
 impl ConformanceValidator {
     /// Create a new conformance validator
+    ///
+    /// Value-set bindings and component tables are resolved against the
+    /// standard HL7 tables; use
+    /// [`ConformanceValidator::with_table_registry`] to supply site tables.
     pub fn new(profile: ConformanceProfile) -> Self {
-        Self { profile }
+        Self {
+            profile,
+            tables: TableRegistry::new(),
+        }
+    }
+
+    /// Resolve value-set bindings and component tables against a custom table registry
+    ///
+    /// A binding's `value_set_id` is looked up as a table ID.
+    pub fn with_table_registry(mut self, tables: TableRegistry) -> Self {
+        self.tables = tables;
+        self
     }
 
     /// Get the profile
@@ -32,15 +55,82 @@ impl ConformanceValidator {
 
         // Validate message structure
         self.validate_message_structure(message, &mut result);
+        self.validate_segment_order(message, &mut result);
 
         // Validate each segment in the profile
         for segment_profile in &self.profile.message.segments {
             self.validate_segment(message, segment_profile, &mut result);
         }
 
+        // Validate cross-field co-constraints
+        self.validate_co_constraints(message, &mut result);
+
         result
     }
 
+    /// Validate the message-level co-constraints of the profile
+    ///
+    /// A violation is reported at the field the constraint requires (the
+    /// consequent of an `IF ... THEN` condition), with the co-constraint id
+    /// as the rule. Conditions that cannot be parsed produce a warning.
+    fn validate_co_constraints(&self, message: &Message, result: &mut ConformanceValidationResult) {
+        let Some(co_constraints) = &self.profile.message.co_constraints else {
+            return;
+        };
+
+        for co_constraint in co_constraints {
+            let condition = match crate::predicate::PredicateParser::parse(&co_constraint.condition) {
+                Ok(condition) => condition,
+                Err(e) => {
+                    result.add_warning(ConformanceValidationWarning {
+                        location: ValidationLocation::segment(String::new()),
+                        error_type: None,
+                        message: format!("Co-constraint {} was not checked: {}", co_constraint.id, e),
+                        rule: Some(co_constraint.id.clone()),
+                    });
+                    continue;
+                }
+            };
+
+            if condition.evaluate(message) {
+                continue;
+            }
+
+            result.add_error(
+                ConformanceValidationError::new(
+                    Self::location_from_path(condition.primary_path()),
+                    ConformanceErrorType::CoConstraintViolation,
+                    format!("Co-constraint {} violated: {}", co_constraint.id, co_constraint.description),
+                )
+                .with_rule(co_constraint.id.clone()),
+            );
+        }
+    }
+
+    /// Build a location from a terser path such as `PID-29` or `OBX(2)-5-1`
+    ///
+    /// Terser segment indices are 1-based; the location's index is 0-based.
+    fn location_from_path(path: &str) -> ValidationLocation {
+        let mut parts = path.split('-');
+        let segment_part = parts.next().unwrap_or_default();
+        let (segment, segment_index) = match segment_part.split_once('(') {
+            Some((segment, index)) => (
+                segment,
+                index.trim_end_matches(')').parse::<usize>().ok().filter(|i| *i > 0).map(|i| i - 1),
+            ),
+            None => (segment_part, None),
+        };
+        let field = parts.next().and_then(|f| f.split('(').next()?.parse().ok());
+        let component = parts.next().and_then(|c| c.parse().ok());
+
+        ValidationLocation {
+            segment: segment.to_string(),
+            segment_index,
+            field,
+            component,
+        }
+    }
+
     /// Validate overall message structure
     fn validate_message_structure(&self, _message: &Message, result: &mut ConformanceValidationResult) {
         // Check message type matches profile
@@ -58,6 +148,72 @@ impl ConformanceValidator {
         // This would be added in a future enhancement
     }
 
+    /// Validate that segments appear in the order the profile lists them
+    ///
+    /// The message is walked alongside the profile's segment list, remembering
+    /// the furthest profile position reached. Optional segments may be skipped
+    /// and a segment may repeat in place. A segment the profile places before
+    /// that position is reported as a [`ConformanceErrorType::StructuralError`],
+    /// unless it is a repeating segment that already occurred: profile segment
+    /// groups are flattened, so such a segment is taken to start a new
+    /// repetition of its group (e.g. `OBR OBX OBR OBX`). Segments the profile
+    /// does not define are ignored.
+    fn validate_segment_order(&self, message: &Message, result: &mut ConformanceValidationResult) {
+        let profile_segments = &self.profile.message.segments;
+        let mut position = 0;
+        let mut occurrences: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+
+        for segment in &message.segments {
+            let occurrence = *occurrences
+                .entry(segment.id.as_str())
+                .and_modify(|count| *count += 1)
+                .or_insert(0);
+
+            let mut candidates = profile_segments
+                .iter()
+                .enumerate()
+                .filter(|(_, profile)| profile.name == segment.id);
+
+            // Segments the profile does not define are not ordered
+            let Some((index, _)) = candidates.clone().next_back() else {
+                continue;
+            };
+
+            // The first matching profile entry at or after the current position
+            if let Some((index, _)) = candidates.clone().find(|(index, _)| *index >= position) {
+                position = index;
+                continue;
+            }
+
+            // A repeated occurrence of a repeating segment starts a new group repetition
+            if occurrence > 0
+                && let Some((index, _)) =
+                    candidates.find(|(_, profile)| profile.cardinality.max.is_none_or(|max| max > 1))
+            {
+                position = index;
+                continue;
+            }
+
+            let expected_before = &profile_segments[position].name;
+            result.add_error(
+                ConformanceValidationError::new(
+                    ValidationLocation {
+                        segment: segment.id.clone(),
+                        segment_index: (occurrence > 0).then_some(occurrence),
+                        field: None,
+                        component: None,
+                    },
+                    ConformanceErrorType::StructuralError,
+                    format!(
+                        "Segment {} is out of order: the profile places it before {}",
+                        segment.id, expected_before
+                    ),
+                )
+                .with_rule(format!("{} order={}", segment.id, index + 1)),
+            );
+        }
+    }
+
     /// Validate a segment according to its profile
     fn validate_segment(
         &self,
@@ -116,6 +272,7 @@ impl ConformanceValidator {
                 if occurrence_count == 0 {
                     result.add_warning(ConformanceValidationWarning {
                         location: ValidationLocation::segment(segment_name.to_string()),
+                        error_type: Some(ConformanceErrorType::RequiredIfKnownMissing),
                         message: format!(
                             "Required if known segment {} is missing",
                             segment_name
@@ -244,6 +401,257 @@ impl ConformanceValidator {
                 field_profile,
                 result,
             );
+
+            // Validate value-set binding
+            if let Some(binding) = &field_profile.value_set {
+                self.validate_value_set(&segment.id, segment_index, field_position, field, binding, result);
+            }
+
+            // Validate components of composite fields
+            if let Some(components) = &field_profile.components {
+                for (rep_index, repetition) in field.repetitions.iter().enumerate() {
+                    if repetition.is_empty() {
+                        continue;
+                    }
+                    for component_profile in components {
+                        self.validate_component(
+                            &segment.id,
+                            segment_index,
+                            field_position,
+                            rep_index,
+                            repetition,
+                            component_profile,
+                            message,
+                            result,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Validate the coded value of each repetition against a value-set binding
+    ///
+    /// The code is the first component (e.g. CWE-1). Misses are errors for
+    /// `Required` bindings and warnings for `Extensible`/`Preferred` ones;
+    /// `Example` bindings are not checked.
+    fn validate_value_set(
+        &self,
+        segment_id: &str,
+        segment_index: usize,
+        field_position: usize,
+        field: &rs7_core::Field,
+        binding: &ValueSetBinding,
+        result: &mut ConformanceValidationResult,
+    ) {
+        if binding.strength == BindingStrength::Example {
+            return;
+        }
+
+        let location = ValidationLocation {
+            segment: segment_id.to_string(),
+            segment_index: (segment_index > 0).then_some(segment_index),
+            field: Some(field_position),
+            component: None,
+        };
+        let rule = format!(
+            "{}-{} value_set={} ({:?})",
+            segment_id, field_position, binding.value_set_id, binding.strength
+        );
+
+        if self.tables.get_table(&binding.value_set_id).is_none() {
+            // Report a configuration problem once rather than for every occurrence
+            if !result.warnings.iter().any(|w| w.rule.as_deref() == Some(rule.as_str())) {
+                result.add_warning(ConformanceValidationWarning {
+                    location,
+                    error_type: None,
+                    message: format!(
+                        "Value set {} is not known; binding was not checked",
+                        binding.value_set_id
+                    ),
+                    rule: Some(rule),
+                });
+            }
+            return;
+        }
+
+        for repetition in &field.repetitions {
+            let Some(code) = repetition.get_component(0).and_then(|c| c.value()).filter(|c| !c.is_empty()) else {
+                continue;
+            };
+
+            let VocabularyValidation::Invalid { reason } = self.tables.validate(&binding.value_set_id, code) else {
+                continue;
+            };
+
+            let message = format!("Value '{}' is not in value set {}: {}", code, binding.value_set_id, reason);
+            if binding.strength == BindingStrength::Required && !self.tables.is_suggest_only(&binding.value_set_id) {
+                result.add_error(
+                    ConformanceValidationError::new(
+                        location.clone(),
+                        ConformanceErrorType::ValueNotInValueSet,
+                        message,
+                    )
+                    .with_rule(rule.clone()),
+                );
+            } else {
+                result.add_warning(ConformanceValidationWarning {
+                    location: location.clone(),
+                    error_type: Some(ConformanceErrorType::ValueNotInValueSet),
+                    message,
+                    rule: Some(rule.clone()),
+                });
+            }
+        }
+    }
+
+    /// Validate a component within one repetition of a field
+    ///
+    /// Only called for repetitions that carry a value; a missing field is
+    /// reported by the field-level usage check instead.
+    #[allow(clippy::too_many_arguments)]
+    fn validate_component(
+        &self,
+        segment_id: &str,
+        segment_index: usize,
+        field_position: usize,
+        rep_index: usize,
+        repetition: &rs7_core::Repetition,
+        component_profile: &ComponentProfile,
+        message: &Message,
+        result: &mut ConformanceValidationResult,
+    ) {
+        let position = component_profile.position;
+        let component = position
+            .checked_sub(1)
+            .and_then(|index| repetition.get_component(index))
+            .filter(|c| !c.is_empty());
+
+        let location = ValidationLocation {
+            segment: segment_id.to_string(),
+            segment_index: (segment_index > 0).then_some(segment_index),
+            field: Some(field_position),
+            component: Some(position),
+        };
+        let rule_path = format!("{}-{}.{}", segment_id, field_position, position);
+        let component_name = component_profile
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("Component {}", position));
+        let repetition_note = if rep_index > 0 {
+            format!(" (repetition {})", rep_index + 1)
+        } else {
+            String::new()
+        };
+
+        // Resolve conditional usage against the message
+        let usage = match &component_profile.usage {
+            ConditionalUsage::Conditional(predicate) => {
+                match crate::predicate::PredicateEvaluator::evaluate(predicate, message) {
+                    Ok(usage) => ConditionalUsage::from_usage(usage),
+                    Err(_) => ConditionalUsage::Optional,
+                }
+            }
+            usage => usage.clone(),
+        };
+
+        match usage {
+            ConditionalUsage::Required if component.is_none() => {
+                result.add_error(
+                    ConformanceValidationError::new(
+                        location.clone(),
+                        ConformanceErrorType::RequiredElementMissing,
+                        format!("Required component {} is missing{}", component_name, repetition_note),
+                    )
+                    .with_rule(format!("{} usage=R", rule_path)),
+                );
+            }
+            ConditionalUsage::RequiredIfKnown if component.is_none() => {
+                result.add_warning(ConformanceValidationWarning {
+                    location: location.clone(),
+                    error_type: Some(ConformanceErrorType::RequiredIfKnownMissing),
+                    message: format!(
+                        "Required if known component {} is missing{}",
+                        component_name, repetition_note
+                    ),
+                    rule: Some(format!("{} usage=RE", rule_path)),
+                });
+            }
+            ConditionalUsage::NotUsed if component.is_some() => {
+                result.add_error(
+                    ConformanceValidationError::new(
+                        location.clone(),
+                        ConformanceErrorType::NotUsedElementPresent,
+                        format!(
+                            "Component {} is marked as not used but is present{}",
+                            component_name, repetition_note
+                        ),
+                    )
+                    .with_rule(format!("{} usage=X", rule_path)),
+                );
+            }
+            _ => {}
+        }
+
+        // Validate component length
+        if let (Some(component), Some(max_length)) = (component, component_profile.length) {
+            let actual_length = component.encode(&rs7_core::Delimiters::default()).len();
+            if actual_length > max_length {
+                result.add_error(
+                    ConformanceValidationError::new(
+                        location.clone(),
+                        ConformanceErrorType::ExceedsMaxLength,
+                        format!(
+                            "Component {} has length {}, but maximum is {}{}",
+                            component_name, actual_length, max_length, repetition_note
+                        ),
+                    )
+                    .with_rule(format!("{} length<={}", rule_path, max_length)),
+                );
+            }
+        }
+
+        // Validate the component value against its table
+        if let (Some(component), Some(table_id)) = (component, &component_profile.table_id) {
+            let rule = format!("{} table={}", rule_path, table_id);
+
+            if self.tables.get_table(table_id).is_none() {
+                // Report a configuration problem once rather than for every occurrence
+                if !result.warnings.iter().any(|w| w.rule.as_deref() == Some(rule.as_str())) {
+                    result.add_warning(ConformanceValidationWarning {
+                        location,
+                        error_type: None,
+                        message: format!("Table {} is not known; component {} was not checked", table_id, component_name),
+                        rule: Some(rule),
+                    });
+                }
+                return;
+            }
+
+            let Some(value) = component.value().filter(|v| !v.is_empty()) else {
+                return;
+            };
+            let VocabularyValidation::Invalid { reason } = self.tables.validate(table_id, value) else {
+                return;
+            };
+
+            let message = format!(
+                "Component {} value '{}' is not in table {}: {}{}",
+                component_name, value, table_id, reason, repetition_note
+            );
+            if self.tables.is_suggest_only(table_id) {
+                result.add_warning(ConformanceValidationWarning {
+                    location,
+                    error_type: Some(ConformanceErrorType::InvalidCode),
+                    message,
+                    rule: Some(rule),
+                });
+            } else {
+                result.add_error(
+                    ConformanceValidationError::new(location, ConformanceErrorType::InvalidCode, message)
+                        .with_rule(rule),
+                );
+            }
         }
     }
 
@@ -294,6 +702,7 @@ impl ConformanceValidator {
                         .unwrap_or_else(|| format!("Field {}", field_position));
                     result.add_warning(ConformanceValidationWarning {
                         location,
+                        error_type: Some(ConformanceErrorType::RequiredIfKnownMissing),
                         message: format!("Required if known field {} is missing", field_name),
                         rule: Some(format!("{}-{} usage=RE", segment_id, field_position)),
                     });
@@ -471,7 +880,7 @@ impl ConformanceValidator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::profile::{Cardinality, MessageProfile, ProfileMetadata};
+    use crate::profile::{Cardinality, CoConstraint, MessageProfile, ProfileMetadata};
     use rs7_core::Version;
 
     fn create_test_profile() -> ConformanceProfile {
@@ -519,5 +928,312 @@ mod tests {
         assert_eq!(validator.profile().metadata.name, "Test Profile");
     }
 
-    // More tests would go here
+    fn create_component_profile() -> ConformanceProfile {
+        let metadata = ProfileMetadata::new(
+            "Component Profile".to_string(),
+            "1.0".to_string(),
+            Version::V2_5,
+        );
+
+        let mut message = MessageProfile::new("ADT".to_string(), "A01".to_string());
+
+        let mut pid = SegmentProfile::new("PID".to_string(), Usage::Required, Cardinality::one());
+        let mut id_number = ComponentProfile::new(1, ConditionalUsage::Required);
+        id_number.length = Some(6);
+        pid.add_field(
+            FieldProfile::new(3, Usage::Required, Cardinality::one_or_more()).with_components(vec![
+                id_number,
+                ComponentProfile::new(2, ConditionalUsage::NotUsed),
+                ComponentProfile::new(4, ConditionalUsage::RequiredIfKnown),
+            ]),
+        );
+        message.add_segment(pid);
+
+        ConformanceProfile::new(metadata, message)
+    }
+
+    fn create_value_set_profile(strength: BindingStrength, value_set_id: &str) -> ConformanceProfile {
+        let metadata = ProfileMetadata::new("Value Sets".to_string(), "1.0".to_string(), Version::V2_5);
+        let mut message = MessageProfile::new("ADT".to_string(), "A01".to_string());
+
+        let mut pid = SegmentProfile::new("PID".to_string(), Usage::Required, Cardinality::one());
+        pid.add_field(
+            FieldProfile::new(8, Usage::Optional, Cardinality::zero_or_one())
+                .with_value_set(ValueSetBinding::new(value_set_id.to_string(), strength)),
+        );
+        message.add_segment(pid);
+
+        ConformanceProfile::new(metadata, message)
+    }
+
+    #[test]
+    fn test_value_set_binding_strength() {
+        let valid = parse("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5\rPID||||||||F");
+        let invalid = parse("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5\rPID||||||||Q");
+
+        let required = ConformanceValidator::new(create_value_set_profile(BindingStrength::Required, "0001"));
+        assert!(required.validate(&valid).is_valid());
+        let result = required.validate(&invalid);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].error_type, ConformanceErrorType::ValueNotInValueSet);
+        assert_eq!(result.errors[0].location, ValidationLocation::field("PID".to_string(), 8));
+
+        for strength in [BindingStrength::Extensible, BindingStrength::Preferred] {
+            let validator = ConformanceValidator::new(create_value_set_profile(strength, "0001"));
+            let result = validator.validate(&invalid);
+            assert!(result.is_valid());
+            assert_eq!(result.warnings.len(), 1);
+        }
+
+        let example = ConformanceValidator::new(create_value_set_profile(BindingStrength::Example, "0001"));
+        let result = example.validate(&invalid);
+        assert!(result.is_valid());
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_value_set_custom_registry() {
+        let mut table = rs7_validator::Hl7Table::new("LOCAL_SEX", "Local sex", "Site codes");
+        table.add_value("Q", "Queried", false);
+        let mut tables = TableRegistry::new();
+        tables.add_table(table);
+
+        let validator = ConformanceValidator::new(create_value_set_profile(BindingStrength::Required, "LOCAL_SEX"))
+            .with_table_registry(tables);
+        let message = parse("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5\rPID||||||||Q");
+        assert!(validator.validate(&message).is_valid());
+    }
+
+    #[test]
+    fn test_unknown_value_set_warns() {
+        let validator = ConformanceValidator::new(create_value_set_profile(BindingStrength::Required, "NOPE"));
+        let message = parse("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5\rPID||||||||Q");
+
+        let result = validator.validate(&message);
+        assert!(result.is_valid());
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].message.contains("NOPE"));
+    }
+
+    fn create_death_profile() -> ConformanceProfile {
+        let metadata = ProfileMetadata::new("Death".to_string(), "1.0".to_string(), Version::V2_5);
+        let mut message = MessageProfile::new("ADT".to_string(), "A01".to_string()).with_co_constraints(vec![
+            CoConstraint::new(
+                "DEATH-1".to_string(),
+                "Death date is required when the patient is deceased".to_string(),
+                "IF PID-30 = 'Y' THEN PID-29 SHALL BE VALUED".to_string(),
+            ),
+        ]);
+        message.add_segment(SegmentProfile::new("PID".to_string(), Usage::Required, Cardinality::one()));
+
+        ConformanceProfile::new(metadata, message)
+    }
+
+    #[test]
+    fn test_co_constraint_death_indicator() {
+        let validator = ConformanceValidator::new(create_death_profile());
+        let msh = "MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5\r";
+
+        // PID-5 followed by PID-29 (death date) and PID-30 (death indicator)
+        let pid = |death_date: &str, indicator: &str| {
+            parse(&format!("{}PID|||123||Doe^John{}{}|{}", msh, "|".repeat(24), death_date, indicator))
+        };
+
+        // Deceased with a death date
+        assert!(validator.validate(&pid("20240101", "Y")).is_valid());
+
+        // Not deceased, no death date
+        assert!(validator.validate(&pid("", "N")).is_valid());
+
+        // Deceased without a death date
+        let message = pid("", "Y");
+        let result = validator.validate(&message);
+        assert!(!result.is_valid());
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].error_type, ConformanceErrorType::CoConstraintViolation);
+        assert_eq!(result.errors[0].rule.as_deref(), Some("DEATH-1"));
+        assert_eq!(result.errors[0].location, ValidationLocation::field("PID".to_string(), 29));
+    }
+
+    #[test]
+    fn test_location_from_path_segment_index() {
+        let location = ConformanceValidator::location_from_path("OBX(2)-5-1");
+        assert_eq!(location.segment, "OBX");
+        assert_eq!(location.segment_index, Some(1));
+        assert_eq!(location.field, Some(5));
+        assert_eq!(location.component, Some(1));
+        assert_eq!(location.to_string(), "OBX(2)-5.1");
+
+        assert_eq!(ConformanceValidator::location_from_path("OBX(1)-5").segment_index, Some(0));
+        assert_eq!(ConformanceValidator::location_from_path("PID-29").segment_index, None);
+    }
+
+    #[test]
+    fn test_invalid_co_constraint_warns() {
+        let mut profile = create_death_profile();
+        profile.message.co_constraints = Some(vec![CoConstraint::new(
+            "BAD".to_string(),
+            "Unparseable".to_string(),
+            "IF PID-30 = 'Y'".to_string(),
+        )]);
+        let validator = ConformanceValidator::new(profile);
+        let message = parse("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5\rPID|||123");
+
+        let result = validator.validate(&message);
+        assert!(result.is_valid());
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].rule.as_deref(), Some("BAD"));
+    }
+
+    fn parse(hl7: &str) -> Message {
+        rs7_parser::parse_message(hl7).unwrap()
+    }
+
+    #[test]
+    fn test_component_validation_valid() {
+        let validator = ConformanceValidator::new(create_component_profile());
+        let message = parse("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5\rPID|||123456^^^HOSP");
+
+        let result = validator.validate(&message);
+        assert!(result.is_valid(), "{:?}", result.errors);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_component_usage_violations() {
+        let validator = ConformanceValidator::new(create_component_profile());
+        let message = parse("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5\rPID|||^CHK~123^^^HOSP");
+
+        let result = validator.validate(&message);
+        assert!(!result.is_valid());
+
+        let missing = result
+            .errors
+            .iter()
+            .find(|e| e.error_type == ConformanceErrorType::RequiredElementMissing)
+            .unwrap();
+        assert_eq!(missing.location, ValidationLocation::component("PID".to_string(), 3, 1));
+        assert_eq!(missing.location.to_string(), "PID-3.1");
+
+        let not_used = result
+            .errors
+            .iter()
+            .find(|e| e.error_type == ConformanceErrorType::NotUsedElementPresent)
+            .unwrap();
+        assert_eq!(not_used.location.component, Some(2));
+
+        // CX-4 is missing from the first repetition only
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].location.component, Some(4));
+    }
+
+    fn create_order_profile() -> ConformanceProfile {
+        let metadata = ProfileMetadata::new("Order".to_string(), "1.0".to_string(), Version::V2_5);
+        let mut message = MessageProfile::new("ORU".to_string(), "R01".to_string());
+
+        for (name, usage, cardinality) in [
+            ("MSH", Usage::Required, Cardinality::one()),
+            ("EVN", Usage::Optional, Cardinality::zero_or_one()),
+            ("PID", Usage::Required, Cardinality::one()),
+            ("NK1", Usage::Optional, Cardinality::zero_or_more()),
+            ("OBR", Usage::Required, Cardinality::one_or_more()),
+            ("OBX", Usage::Optional, Cardinality::zero_or_more()),
+        ] {
+            message.add_segment(SegmentProfile::new(name.to_string(), usage, cardinality));
+        }
+
+        ConformanceProfile::new(metadata, message)
+    }
+
+    fn order_errors(hl7: &str) -> Vec<ConformanceValidationError> {
+        let validator = ConformanceValidator::new(create_order_profile());
+        validator
+            .validate(&parse(hl7))
+            .errors
+            .into_iter()
+            .filter(|e| e.error_type == ConformanceErrorType::StructuralError)
+            .collect()
+    }
+
+    #[test]
+    fn test_segment_order_valid() {
+        // Optional EVN and NK1 skipped, repeating OBX, repeated OBR/OBX group, unknown ZPI
+        assert!(order_errors(
+            "MSH|^~\\&|APP|FAC|||20240101||ORU^R01|1|P|2.5\rPID|1\rZPI|1\rOBR|1\rOBX|1\rOBX|2\rOBR|2\rOBX|1"
+        )
+        .is_empty());
+        assert!(order_errors(
+            "MSH|^~\\&|APP|FAC|||20240101||ORU^R01|1|P|2.5\rEVN|A01\rPID|1\rNK1|1\rNK1|2\rOBR|1"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_segment_order_violations() {
+        // EVN after PID
+        let errors = order_errors("MSH|^~\\&|APP|FAC|||20240101||ORU^R01|1|P|2.5\rPID|1\rEVN|A01\rOBR|1");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].location.segment, "EVN");
+        assert_eq!(errors[0].message, "Segment EVN is out of order: the profile places it before PID");
+        assert_eq!(errors[0].rule.as_deref(), Some("EVN order=2"));
+
+        // OBX before any OBR, then OBR; a first NK1 after OBR is not a repetition
+        let errors = order_errors(
+            "MSH|^~\\&|APP|FAC|||20240101||ORU^R01|1|P|2.5\rPID|1\rOBX|1\rOBR|1\rNK1|1",
+        );
+        let segments: Vec<&str> = errors.iter().map(|e| e.location.segment.as_str()).collect();
+        assert_eq!(segments, vec!["OBR", "NK1"]);
+    }
+
+    #[test]
+    fn test_component_length() {
+        let validator = ConformanceValidator::new(create_component_profile());
+        let message = parse("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5\rPID|||1234567^^^HOSP");
+
+        let result = validator.validate(&message);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].error_type, ConformanceErrorType::ExceedsMaxLength);
+        assert_eq!(result.errors[0].rule.as_deref(), Some("PID-3.1 length<=6"));
+    }
+
+    fn create_component_table_profile(table_id: &str) -> ConformanceProfile {
+        let metadata = ProfileMetadata::new("Component Tables".to_string(), "1.0".to_string(), Version::V2_5);
+        let mut message = MessageProfile::new("ADT".to_string(), "A01".to_string());
+
+        let mut pid = SegmentProfile::new("PID".to_string(), Usage::Required, Cardinality::one());
+        let mut identifier_type = ComponentProfile::new(5, ConditionalUsage::Optional);
+        identifier_type.table_id = Some(table_id.to_string());
+        pid.add_field(
+            FieldProfile::new(3, Usage::Required, Cardinality::one_or_more()).with_components(vec![identifier_type]),
+        );
+        message.add_segment(pid);
+
+        ConformanceProfile::new(metadata, message)
+    }
+
+    #[test]
+    fn test_component_table() {
+        let mut table = rs7_validator::Hl7Table::new("ZID", "Identifier type", "Site codes");
+        table.add_value("MR", "Medical record number", false);
+        let mut tables = TableRegistry::new();
+        tables.add_table(table);
+        let validator =
+            ConformanceValidator::new(create_component_table_profile("ZID")).with_table_registry(tables);
+
+        let valid = parse("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5\rPID|||123^^^HOSP^MR");
+        assert!(validator.validate(&valid).is_valid());
+
+        let invalid = parse("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5\rPID|||123^^^HOSP^XX~456^^^HOSP^MR");
+        let result = validator.validate(&invalid);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].error_type, ConformanceErrorType::InvalidCode);
+        assert_eq!(result.errors[0].location.component, Some(5));
+        assert_eq!(result.errors[0].rule.as_deref(), Some("PID-3.5 table=ZID"));
+
+        // Unknown tables are reported once and not checked
+        let validator = ConformanceValidator::new(create_component_table_profile("ZZZ"));
+        let result = validator.validate(&invalid);
+        assert!(result.is_valid());
+        assert_eq!(result.warnings.len(), 1);
+    }
 }
@@ -0,0 +1,232 @@
+//! Validation report export in the NIST HL7 v2 validation report layout
+//!
+//! The NIST validation tools group their detections first by classification
+//! (`Error`, `Warning`, `Informational`) and then by category (`Usage`,
+//! `Cardinality`, `Length`, ...), each detection giving the element path, a
+//! description and the assertion that failed. [`NistValidationReport`]
+//! reproduces that layout so conformance results can be fed to the same
+//! dashboards and QA tooling.
+//!
+//! ```json
+//! {
+//!   "detections": {
+//!     "Error": {
+//!       "Usage": [
+//!         {
+//!           "path": "PID[1]-3[1]",
+//!           "description": "Required field PID-3 is missing",
+//!           "category": "Usage",
+//!           "classification": "Error",
+//!           "assertion": "PID-3 usage=R"
+//!         }
+//!       ]
+//!     }
+//!   }
+//! }
+//! ```
+
+use super::result::{ConformanceErrorType, ConformanceValidationResult, Severity, ValidationLocation};
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Category used for entries without a conformance error type
+const OTHER_CATEGORY: &str = "Other";
+
+/// Validation report structured like the NIST HL7 v2 validation report
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NistValidationReport {
+    /// Detections keyed by classification, then by category
+    pub detections: BTreeMap<String, BTreeMap<String, Vec<NistReportEntry>>>,
+}
+
+/// A single detection in a [`NistValidationReport`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NistReportEntry {
+    /// Element path, e.g. `PID[1]-5[1].1`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Human-readable description
+    pub description: String,
+    /// Category, e.g. `Usage` or `Cardinality`
+    pub category: String,
+    /// Classification: `Error`, `Warning` or `Informational`
+    pub classification: String,
+    /// Profile rule the element was checked against
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assertion: Option<String>,
+}
+
+impl NistValidationReport {
+    /// Build a report from a conformance validation result
+    ///
+    /// Errors are classified by their severity, so an error downgraded to a
+    /// warning is reported as one.
+    pub fn from_result(result: &ConformanceValidationResult) -> Self {
+        let mut report = Self::default();
+
+        for error in &result.errors {
+            report.add(NistReportEntry::new(
+                Some(&error.location),
+                &error.message,
+                error.error_type.nist_category(),
+                error.severity,
+                error.rule.as_deref(),
+            ));
+        }
+        for warning in &result.warnings {
+            report.add(NistReportEntry::new(
+                Some(&warning.location),
+                &warning.message,
+                warning.error_type.as_ref().map_or(OTHER_CATEGORY, ConformanceErrorType::nist_category),
+                Severity::Warning,
+                warning.rule.as_deref(),
+            ));
+        }
+        for info in &result.info {
+            report.add(NistReportEntry::new(
+                info.location.as_ref(),
+                &info.message,
+                OTHER_CATEGORY,
+                Severity::Info,
+                None,
+            ));
+        }
+
+        report
+    }
+
+    /// Add an entry under its classification and category
+    pub fn add(&mut self, entry: NistReportEntry) {
+        self.detections
+            .entry(entry.classification.clone())
+            .or_default()
+            .entry(entry.category.clone())
+            .or_default()
+            .push(entry);
+    }
+
+    /// Iterate over all entries
+    pub fn entries(&self) -> impl Iterator<Item = &NistReportEntry> {
+        self.detections.values().flat_map(|categories| categories.values().flatten())
+    }
+
+    /// Count the entries with a classification
+    pub fn count(&self, classification: &str) -> usize {
+        self.detections
+            .get(classification)
+            .map_or(0, |categories| categories.values().map(Vec::len).sum())
+    }
+
+    /// Serialize the report as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl NistReportEntry {
+    fn new(
+        location: Option<&ValidationLocation>,
+        description: &str,
+        category: &str,
+        severity: Severity,
+        assertion: Option<&str>,
+    ) -> Self {
+        Self {
+            path: location.and_then(nist_path),
+            description: description.to_string(),
+            category: category.to_string(),
+            classification: nist_classification(severity).to_string(),
+            assertion: assertion.map(str::to_string),
+        }
+    }
+}
+
+/// NIST classification for a severity
+fn nist_classification(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "Error",
+        Severity::Warning => "Warning",
+        Severity::Info => "Informational",
+    }
+}
+
+/// Format a location as a NIST path, e.g. `PID[1]-5[1].1`
+///
+/// Occurrences are 1-based; field repetitions are not tracked by
+/// [`ValidationLocation`], so fields are reported as their first repetition.
+fn nist_path(location: &ValidationLocation) -> Option<String> {
+    if location.segment.is_empty() {
+        return None;
+    }
+
+    let mut path = format!("{}[{}]", location.segment, location.segment_index.unwrap_or(0) + 1);
+    if let Some(field) = location.field {
+        path.push_str(&format!("-{}[1]", field));
+        if let Some(component) = location.component {
+            path.push_str(&format!(".{}", component));
+        }
+    }
+
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validator::{ConformanceValidationError, ConformanceValidationInfo, ConformanceValidationWarning};
+
+    #[test]
+    fn test_nist_report() {
+        let mut result = ConformanceValidationResult::new();
+        result.add_error(
+            ConformanceValidationError::new(
+                ValidationLocation::field("PID".to_string(), 3),
+                ConformanceErrorType::RequiredElementMissing,
+                "Required field PID-3 is missing".to_string(),
+            )
+            .with_rule("PID-3 usage=R".to_string()),
+        );
+        result.add_error(
+            ConformanceValidationError::new(
+                ValidationLocation::segment_indexed("OBX".to_string(), 1),
+                ConformanceErrorType::ExceedsMaximumOccurrences,
+                "Too many OBX segments".to_string(),
+            )
+            .with_severity(Severity::Warning),
+        );
+        result.add_warning(ConformanceValidationWarning {
+            location: ValidationLocation::component("PID".to_string(), 5, 2),
+            error_type: Some(ConformanceErrorType::RequiredIfKnownMissing),
+            message: "Required if known component is missing".to_string(),
+            rule: Some("PID-5.2 usage=RE".to_string()),
+        });
+        result.add_info(ConformanceValidationInfo {
+            location: None,
+            message: "Validating against profile: Test 1.0".to_string(),
+        });
+
+        let report = result.to_nist_report();
+        assert_eq!(report.count("Error"), 1);
+        assert_eq!(report.count("Warning"), 2);
+        assert_eq!(report.count("Informational"), 1);
+        assert_eq!(report.entries().count(), 4);
+
+        let usage = &report.detections["Error"]["Usage"][0];
+        assert_eq!(usage.path.as_deref(), Some("PID[1]-3[1]"));
+        assert_eq!(usage.assertion.as_deref(), Some("PID-3 usage=R"));
+
+        let cardinality = &report.detections["Warning"]["Cardinality"][0];
+        assert_eq!(cardinality.path.as_deref(), Some("OBX[2]"));
+        assert_eq!(
+            report.detections["Warning"]["Usage"][0].path.as_deref(),
+            Some("PID[1]-5[1].2")
+        );
+        assert_eq!(report.detections["Informational"]["Other"][0].path, None);
+
+        let json = report.to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["detections"]["Error"]["Usage"][0]["classification"], "Error");
+        assert!(value["detections"]["Informational"]["Other"][0].get("path").is_none());
+    }
+}
@@ -1,7 +1,15 @@
 //! Validation result types
+//!
+//! All result types implement serde's `Serialize` and `Deserialize`; see
+//! [`ConformanceValidationResult::to_json`] and
+//! [`ConformanceValidationResult::to_nist_report`] for ready-made exports.
+
+use super::report::NistValidationReport;
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
 
 /// Result of conformance profile validation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConformanceValidationResult {
     /// Whether the message is valid according to the profile
     is_valid: bool,
@@ -49,6 +57,28 @@ impl ConformanceValidationResult {
     pub fn total_issues(&self) -> usize {
         self.errors.len() + self.warnings.len()
     }
+
+    /// Serialize the result as pretty-printed JSON
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs7_conformance::ConformanceValidationResult;
+    ///
+    /// let result = ConformanceValidationResult::new();
+    /// let json = result.to_json().unwrap();
+    /// assert!(json.contains("\"is_valid\": true"));
+    /// ```
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Convert the result to a report structured like the NIST HL7 v2 validation report
+    ///
+    /// See [`NistValidationReport`].
+    pub fn to_nist_report(&self) -> NistValidationReport {
+        NistValidationReport::from_result(self)
+    }
 }
 
 impl Default for ConformanceValidationResult {
@@ -58,7 +88,7 @@ impl Default for ConformanceValidationResult {
 }
 
 /// Conformance validation error
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConformanceValidationError {
     /// Location in the message (e.g., "PID-3", "PV1")
     pub location: ValidationLocation,
@@ -69,6 +99,7 @@ pub struct ConformanceValidationError {
     /// Severity level
     pub severity: Severity,
     /// Profile rule that was violated (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub rule: Option<String>,
 }
 
@@ -102,35 +133,44 @@ impl ConformanceValidationError {
 }
 
 /// Conformance validation warning
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConformanceValidationWarning {
     /// Location in the message
     pub location: ValidationLocation,
+    /// Kind of issue, when the warning is a softened conformance error
+    /// (e.g. a missing RE element)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_type: Option<ConformanceErrorType>,
     /// Human-readable warning message
     pub message: String,
     /// Profile rule reference (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub rule: Option<String>,
 }
 
 /// Conformance validation info message
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConformanceValidationInfo {
     /// Location in the message
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub location: Option<ValidationLocation>,
     /// Human-readable info message
     pub message: String,
 }
 
 /// Location within an HL7 message
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ValidationLocation {
     /// Segment ID (e.g., "PID", "MSH")
     pub segment: String,
     /// Segment occurrence index (0-based, for repeating segments)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub segment_index: Option<usize>,
     /// Field position (1-based)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub field: Option<usize>,
     /// Component position (1-based)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub component: Option<usize>,
 }
 
@@ -202,7 +242,7 @@ impl std::fmt::Display for ValidationLocation {
 }
 
 /// Type of conformance error
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ConformanceErrorType {
     /// Required element is missing
     RequiredElementMissing,
@@ -224,6 +264,10 @@ pub enum ConformanceErrorType {
     InvalidCode,
     /// Value not in value set
     ValueNotInValueSet,
+    /// Cross-field co-constraint is violated
+    CoConstraintViolation,
+    /// Segment appears out of the profile-defined order
+    StructuralError,
 }
 
 impl ConformanceErrorType {
@@ -240,12 +284,29 @@ impl ConformanceErrorType {
             Self::DataTypeMismatch => "Data type does not match profile",
             Self::InvalidCode => "Invalid code from vocabulary",
             Self::ValueNotInValueSet => "Value not in value set",
+            Self::CoConstraintViolation => "Co-constraint violated",
+            Self::StructuralError => "Segment is out of the profile-defined order",
+        }
+    }
+
+    /// Get the NIST validation report category for this error type
+    pub fn nist_category(&self) -> &'static str {
+        match self {
+            Self::RequiredElementMissing | Self::RequiredIfKnownMissing => "Usage",
+            Self::NotUsedElementPresent => "X-Usage",
+            Self::BelowMinimumOccurrences | Self::ExceedsMaximumOccurrences => "Cardinality",
+            Self::ExceedsMaxLength => "Length",
+            Self::InvalidDataTypeFormat | Self::DataTypeMismatch => "Format",
+            Self::InvalidCode | Self::ValueNotInValueSet => "Code Not Found",
+            Self::CoConstraintViolation => "Constraint Failure",
+            Self::StructuralError => "Segment Order",
         }
     }
 }
 
 /// Severity level for validation issues
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
 pub enum Severity {
     /// Informational only
     Info,
@@ -308,6 +369,39 @@ mod tests {
         assert_eq!(result.total_issues(), 1);
     }
 
+    #[test]
+    fn test_to_json() {
+        let mut result = ConformanceValidationResult::new();
+        result.add_error(
+            ConformanceValidationError::new(
+                ValidationLocation::field("PID".to_string(), 3),
+                ConformanceErrorType::RequiredElementMissing,
+                "Patient ID is required".to_string(),
+            )
+            .with_rule("PID-3 usage=R".to_string()),
+        );
+        result.add_warning(ConformanceValidationWarning {
+            location: ValidationLocation::component("PID".to_string(), 5, 2),
+            error_type: Some(ConformanceErrorType::RequiredIfKnownMissing),
+            message: "Required if known component is missing".to_string(),
+            rule: None,
+        });
+
+        let json = result.to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["is_valid"], false);
+        assert_eq!(value["errors"][0]["severity"], "ERROR");
+        assert_eq!(value["errors"][0]["error_type"], "RequiredElementMissing");
+        assert_eq!(value["errors"][0]["location"]["field"], 3);
+        assert_eq!(value["errors"][0]["rule"], "PID-3 usage=R");
+        assert!(value["warnings"][0].get("rule").is_none());
+
+        let parsed: ConformanceValidationResult = serde_json::from_str(&json).unwrap();
+        assert!(!parsed.is_valid());
+        assert_eq!(parsed.errors[0].location, result.errors[0].location);
+        assert_eq!(parsed.warnings[0].error_type, Some(ConformanceErrorType::RequiredIfKnownMissing));
+    }
+
     #[test]
     fn test_severity_ordering() {
         assert!(Severity::Info < Severity::Warning);
@@ -33,7 +33,7 @@
 //! ```
 
 use crate::{error::{Error, Result}, message::Message, segment::Segment, field::Field};
-use chrono::NaiveDateTime;
+use chrono::{FixedOffset, NaiveDateTime};
 
 /// File Header Segment (FHS) - marks the beginning of a file containing batches
 ///
@@ -55,6 +55,8 @@ pub struct FileHeader {
     pub receiving_facility: Option<String>,
     /// FHS-7: File creation date/time
     pub creation_datetime: Option<NaiveDateTime>,
+    /// FHS-7: Time zone offset of the creation date/time, when present
+    pub creation_offset: Option<FixedOffset>,
     /// FHS-8: File security
     pub security: Option<String>,
     /// FHS-9: File name/ID
@@ -82,6 +84,7 @@ impl FileHeader {
             receiving_application: None,
             receiving_facility: None,
             creation_datetime: None,
+            creation_offset: None,
             security: None,
             file_name_id: None,
             comment: None,
@@ -115,9 +118,14 @@ impl FileHeader {
         fhs.add_field(Field::from_value(self.receiving_facility.as_deref().unwrap_or("")));
 
         // FHS-7: Creation date/time
-        let datetime_str = self.creation_datetime
+        let mut datetime_str = self.creation_datetime
             .map(|dt| crate::types::format_timestamp(&dt))
             .unwrap_or_default();
+        if let Some(offset) = self.creation_offset
+            && !datetime_str.is_empty()
+        {
+            datetime_str.push_str(&format_offset(offset));
+        }
         fhs.add_field(Field::from_value(&datetime_str));
 
         // FHS-8: Security
@@ -175,6 +183,8 @@ pub struct BatchHeader {
     pub receiving_facility: Option<String>,
     /// BHS-7: Batch creation date/time
     pub creation_datetime: Option<NaiveDateTime>,
+    /// BHS-7: Time zone offset of the creation date/time, when present
+    pub creation_offset: Option<FixedOffset>,
     /// BHS-8: Batch security
     pub security: Option<String>,
     /// BHS-9: Batch name/ID/type
@@ -202,6 +212,7 @@ impl BatchHeader {
             receiving_application: None,
             receiving_facility: None,
             creation_datetime: None,
+            creation_offset: None,
             security: None,
             batch_name_id_type: None,
             comment: None,
@@ -235,9 +246,14 @@ impl BatchHeader {
         bhs.add_field(Field::from_value(self.receiving_facility.as_deref().unwrap_or("")));
 
         // BHS-7: Creation date/time
-        let datetime_str = self.creation_datetime
+        let mut datetime_str = self.creation_datetime
             .map(|dt| crate::types::format_timestamp(&dt))
             .unwrap_or_default();
+        if let Some(offset) = self.creation_offset
+            && !datetime_str.is_empty()
+        {
+            datetime_str.push_str(&format_offset(offset));
+        }
         bhs.add_field(Field::from_value(&datetime_str));
 
         // BHS-8: Security
@@ -275,6 +291,14 @@ impl Default for BatchHeader {
     }
 }
 
+/// Format a time zone offset as `+/-HHMM`
+fn format_offset(offset: FixedOffset) -> String {
+    let seconds = offset.local_minus_utc();
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let minutes = seconds.abs() / 60;
+    format!("{}{:02}{:02}", sign, minutes / 60, minutes % 60)
+}
+
 /// File Trailer Segment (FTS) - marks the end of a file and provides file summary
 ///
 /// The FTS segment indicates the number of batches contained in the file and
@@ -453,6 +477,11 @@ impl Batch {
     }
 
     /// Encode the batch to HL7 format with the specified separator
+    ///
+    /// The separator is used between every segment, including those of the
+    /// contained messages. BTS-1 is written with the actual message count, so
+    /// the output passes [`Batch::validate`] even if `trailer.message_count`
+    /// is stale or unset.
     pub fn encode_with_separator(&self, separator: &str) -> String {
         let mut result = Vec::new();
         let delimiters = crate::Delimiters::default();
@@ -462,16 +491,20 @@ impl Batch {
 
         // Add all messages
         for message in &self.messages {
-            result.push(message.encode());
+            result.push(message.encode_with_separator(separator));
         }
 
-        // Add BTS
-        result.push(self.trailer.to_segment().encode(&delimiters));
+        // Add BTS with the recomputed message count
+        let mut trailer = self.trailer.clone();
+        trailer.message_count = Some(self.messages.len());
+        result.push(trailer.to_segment().encode(&delimiters));
 
         result.join(separator)
     }
 
     /// Encode the batch to standard HL7 format (carriage return separator)
+    ///
+    /// Parsing the result with `parse_batch` yields a batch that validates.
     pub fn encode(&self) -> String {
         self.encode_with_separator("\r")
     }
@@ -559,6 +592,9 @@ impl File {
     }
 
     /// Encode the file to HL7 format with the specified separator
+    ///
+    /// FTS-1 and each batch's BTS-1 are written with the actual counts, so the
+    /// output passes [`File::validate`].
     pub fn encode_with_separator(&self, separator: &str) -> String {
         let mut result = Vec::new();
         let delimiters = crate::Delimiters::default();
@@ -571,13 +607,17 @@ impl File {
             result.push(batch.encode_with_separator(separator));
         }
 
-        // Add FTS
-        result.push(self.trailer.to_segment().encode(&delimiters));
+        // Add FTS with the recomputed batch count
+        let mut trailer = self.trailer.clone();
+        trailer.batch_count = Some(self.batches.len());
+        result.push(trailer.to_segment().encode(&delimiters));
 
         result.join(separator)
     }
 
     /// Encode the file to standard HL7 format (carriage return separator)
+    ///
+    /// Parsing the result with `parse_file` yields a file that validates.
     pub fn encode(&self) -> String {
         self.encode_with_separator("\r")
     }
@@ -609,6 +649,18 @@ mod tests {
         assert_eq!(sending_app, "SENDER");
     }
 
+    #[test]
+    fn test_batch_header_creation_offset() {
+        let mut header = BatchHeader::new();
+        header.creation_datetime = chrono::NaiveDate::from_ymd_opt(2024, 3, 15)
+            .unwrap()
+            .and_hms_opt(14, 30, 0);
+        header.creation_offset = FixedOffset::west_opt(5 * 3600);
+
+        let segment = header.to_segment();
+        assert_eq!(segment.get_field_value(7), Some("20240315143000-0500"));
+    }
+
     #[test]
     fn test_batch_message_count_validation() {
         let mut batch = Batch::new();
@@ -702,6 +754,28 @@ mod tests {
         assert!(encoded.contains("FTS|1"));
     }
 
+    #[test]
+    fn test_encode_recomputes_counts() {
+        let mut batch = Batch::new();
+        batch.trailer.message_count = Some(5);
+        batch.trailer.comment = Some("END".to_string());
+        let msg = AdtBuilder::a01(Version::V2_5)
+            .patient_id("12345")
+            .build()
+            .unwrap()
+            .with_segment_terminator(crate::message::SegmentTerminator::Lf);
+        batch.add_message(msg);
+
+        let encoded = batch.encode();
+        assert!(!encoded.contains('\n'));
+        assert!(encoded.ends_with("\rBTS|1|END"));
+
+        let mut file = File::new();
+        file.add_batch(batch);
+        file.add_batch(Batch::new());
+        assert!(file.encode().ends_with("\rBTS|0|\rFTS|2|"));
+    }
+
     #[test]
     fn test_file_total_message_count() {
         let mut file = File::new();
@@ -61,10 +61,13 @@ use crate::{
     field::Field,
     message::Message,
     segment::Segment,
-    types::format_timestamp,
     Version,
 };
-use chrono::Local;
+use super::{
+    control_id::{ControlIdGenerator, TimestampGenerator},
+    TimestampPrecision,
+};
+use std::sync::Arc;
 
 /// Acknowledgment codes for Original Mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -165,6 +168,8 @@ pub struct ErrorInfo {
 pub struct AckBuilder {
     /// Version from the original message
     version: Version,
+    /// Original MSH-12 value, echoed verbatim in the ACK when present
+    original_version_id: Option<String>,
     /// Original message control ID (for MSA-2)
     original_control_id: String,
     /// Original sending application (becomes receiving app in ACK)
@@ -189,6 +194,10 @@ pub struct AckBuilder {
     sending_facility_override: Option<String>,
     /// Custom message control ID
     control_id_override: Option<String>,
+    /// Generator for the control ID when no override is set
+    control_id_generator: Arc<dyn ControlIdGenerator>,
+    /// Precision of the MSH-7 timestamp
+    timestamp_precision: TimestampPrecision,
     /// Processing ID (P, D, T)
     processing_id: String,
 }
@@ -224,6 +233,10 @@ impl AckBuilder {
 
         // Extract values from MSH, with defaults
         let version = message.get_version().unwrap_or(Version::V2_5);
+        let original_version_id = msh
+            .and_then(|m| m.get_field_value(12))
+            .filter(|v| !v.is_empty())
+            .map(|v| v.to_string());
         let original_control_id = message.get_control_id().unwrap_or("").to_string();
         let original_sending_app = message.get_sending_application().unwrap_or("").to_string();
         let original_sending_facility = message.get_sending_facility().unwrap_or("").to_string();
@@ -241,6 +254,7 @@ impl AckBuilder {
 
         Self {
             version,
+            original_version_id,
             original_control_id,
             original_sending_app,
             original_sending_facility,
@@ -253,6 +267,8 @@ impl AckBuilder {
             sending_app_override: None,
             sending_facility_override: None,
             control_id_override: None,
+            control_id_generator: Arc::new(TimestampGenerator),
+            timestamp_precision: TimestampPrecision::default(),
             processing_id,
         }
     }
@@ -269,6 +285,7 @@ impl AckBuilder {
     ) -> Self {
         Self {
             version,
+            original_version_id: None,
             original_control_id: original_control_id.to_string(),
             original_sending_app: original_sending_app.to_string(),
             original_sending_facility: original_sending_facility.to_string(),
@@ -281,10 +298,61 @@ impl AckBuilder {
             sending_app_override: None,
             sending_facility_override: None,
             control_id_override: None,
+            control_id_generator: Arc::new(TimestampGenerator),
+            timestamp_precision: TimestampPrecision::default(),
             processing_id: "P".to_string(),
         }
     }
 
+    /// Create an ACK builder for a version, to be filled via [`responding_to`](Self::responding_to)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rs7_core::builders::ack::{AckBuilder, AckCode};
+    /// use rs7_core::Version;
+    /// use rs7_parser::parse_message;
+    ///
+    /// let incoming = parse_message("MSH|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315||ADT^A01|MSG001|P|2.5").unwrap();
+    /// let ack = AckBuilder::with_version(Version::V2_5)
+    ///     .responding_to(&incoming)
+    ///     .ack_code(AckCode::Error)
+    ///     .text_message("Patient not found")
+    ///     .error_code("204", "Unknown Key Identifier")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(ack.segment("MSA").unwrap().get_field_value(2), Some("MSG001"));
+    /// assert!(ack.segment("ERR").is_some());
+    /// ```
+    pub fn with_version(version: Version) -> Self {
+        Self::new(version, "", "", "")
+    }
+
+    /// Take the addressing details from the message being acknowledged
+    ///
+    /// Applications and facilities are swapped, MSA-2 references the original
+    /// MSH-10, and MSH-11 is copied from the original. MSH-12 keeps this
+    /// builder's version. The ACK code, text, errors and overrides already
+    /// set on this builder are kept.
+    pub fn responding_to(self, message: &Message) -> Self {
+        let original = Self::for_message(message);
+        Self {
+            version: self.version,
+            original_version_id: self.original_version_id,
+            ack_code: self.ack_code,
+            text_message: self.text_message,
+            error_code: self.error_code,
+            errors: self.errors,
+            sending_app_override: self.sending_app_override,
+            sending_facility_override: self.sending_facility_override,
+            control_id_override: self.control_id_override,
+            control_id_generator: self.control_id_generator,
+            timestamp_precision: self.timestamp_precision,
+            ..original
+        }
+    }
+
     /// Set acknowledgment to Accept (AA)
     ///
     /// Indicates the message was successfully received and processed.
@@ -409,6 +477,18 @@ impl AckBuilder {
         self
     }
 
+    /// Set the generator for the control ID used when none is set explicitly
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.control_id_generator = generator;
+        self
+    }
+
+    /// Set the precision of the MSH-7 timestamp
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.timestamp_precision = precision;
+        self
+    }
+
     /// Set the processing ID (P=Production, D=Debugging, T=Training)
     pub fn processing_id(mut self, id: &str) -> Self {
         self.processing_id = id.to_string();
@@ -421,6 +501,10 @@ impl AckBuilder {
     ///
     /// The complete ACK message with MSH, MSA, and optionally ERR segments.
     ///
+    /// An AE or AR acknowledgment with an [`error_code`](Self::error_code)
+    /// but no explicit errors (see [`add_error`](Self::add_error)) gets a
+    /// single ERR segment carrying that code and the text message.
+    ///
     /// # Errors
     ///
     /// Returns an error if required fields are missing or invalid.
@@ -441,6 +525,13 @@ impl AckBuilder {
             message.add_segment(err);
         }
 
+        if self.errors.is_empty()
+            && self.ack_code != AckCode::Accept
+            && let Some(error_code) = &self.error_code
+        {
+            message.add_segment(self.build_default_err(error_code));
+        }
+
         Ok(message)
     }
 
@@ -476,7 +567,7 @@ impl AckBuilder {
         msh.add_field(Field::from_value(&self.original_sending_facility));
 
         // MSH-7: Date/Time of Message
-        let timestamp = format_timestamp(&Local::now().naive_local());
+        let timestamp = self.timestamp_precision.now();
         msh.add_field(Field::from_value(&timestamp));
 
         // MSH-8: Security (empty)
@@ -489,14 +580,18 @@ impl AckBuilder {
         let control_id = self
             .control_id_override
             .clone()
-            .unwrap_or_else(super::generate_control_id);
+            .unwrap_or_else(|| self.control_id_generator.next_id());
         msh.add_field(Field::from_value(&control_id));
 
         // MSH-11: Processing ID
         msh.add_field(Field::from_value(&self.processing_id));
 
-        // MSH-12: Version ID
-        msh.add_field(Field::from_value(self.version.as_str()));
+        // MSH-12: Version ID (copied from the original so the ACK matches)
+        let version_id = self
+            .original_version_id
+            .as_deref()
+            .unwrap_or(self.version.as_str());
+        msh.add_field(Field::from_value(version_id));
 
         Ok(msh)
     }
@@ -532,6 +627,33 @@ impl AckBuilder {
         Ok(msa)
     }
 
+    /// Build the ERR segment for an AE/AR acknowledgment from its error code
+    fn build_default_err(&self, error_code: &str) -> Segment {
+        let mut err = Segment::new("ERR");
+
+        // ERR-1, ERR-2: No location is known
+        err.add_field(Field::from_value(""));
+        err.add_field(Field::from_value(""));
+
+        // ERR-3: HL7 Error Code
+        err.add_field(Field::from_value(format!("{}^HL70357", error_code)));
+
+        // ERR-4: Severity
+        err.add_field(Field::from_value(ErrorSeverity::Error.as_str()));
+
+        // ERR-5 to ERR-7: Empty
+        for _ in 5..=7 {
+            err.add_field(Field::from_value(""));
+        }
+
+        // ERR-8: User Message
+        if let Some(ref text) = self.text_message {
+            err.add_field(Field::from_value(text));
+        }
+
+        err
+    }
+
     /// Build an ERR segment for error details
     fn build_err(&self, error: &ErrorInfo) -> Result<Segment> {
         let mut err = Segment::new("ERR");
@@ -694,6 +816,34 @@ impl AckBuilder {
     }
 }
 
+/// Build an acknowledgment for `original` with the given code
+///
+/// Sending and receiving application/facility are swapped, a fresh control
+/// ID is generated, MSA-2 echoes the original MSH-10 and MSH-12 is copied
+/// from the original message.
+///
+/// # Example
+///
+/// ```rust
+/// use rs7_core::builders::ack::{build_ack, AckCode};
+/// use rs7_parser::parse_message;
+///
+/// let msg = parse_message("MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5").unwrap();
+/// let ack = build_ack(&msg, AckCode::Accept).unwrap();
+/// assert_eq!(ack.segment("MSA").unwrap().get_field_value(2), Some("123"));
+/// ```
+pub fn build_ack(original: &Message, code: AckCode) -> Result<Message> {
+    AckBuilder::for_message(original).ack_code(code).build()
+}
+
+/// Build an acknowledgment like [`build_ack`], with `text` in MSA-3
+pub fn build_ack_with_text(original: &Message, code: AckCode, text: &str) -> Result<Message> {
+    AckBuilder::for_message(original)
+        .ack_code(code)
+        .text_message(text)
+        .build()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -793,6 +943,67 @@ mod tests {
         assert_eq!(err.get_field_value(4), Some("E"));
     }
 
+    #[test]
+    fn test_error_ack_gets_default_err_segment() {
+        let incoming = create_test_message();
+        let ack = AckBuilder::for_message(&incoming)
+            .error("Invalid patient ID")
+            .error_code("204", "Unknown Key Identifier")
+            .build()
+            .unwrap();
+
+        let err = ack.segment("ERR").unwrap();
+        assert_eq!(
+            err.get_field_value(3),
+            Some("204^Unknown Key Identifier^HL70357")
+        );
+        assert_eq!(err.get_field_value(4), Some("E"));
+        assert_eq!(err.get_field_value(8), Some("Invalid patient ID"));
+
+        // Without error details there is nothing to report in ERR
+        let nack = AckBuilder::reject_message(&incoming, "Unsupported").unwrap();
+        assert!(nack.segment("ERR").is_none());
+
+        // Explicit errors replace the default ERR
+        let ack = AckBuilder::for_message(&incoming)
+            .error("Validation failed")
+            .add_error("PID", Some(5), "101", "Required field missing", ErrorSeverity::Error)
+            .build()
+            .unwrap();
+        assert_eq!(ack.segments.iter().filter(|s| s.id == "ERR").count(), 1);
+        assert_eq!(ack.segment("ERR").unwrap().get_field_value(1), Some("PID^1^5"));
+
+        // Accepts carry no ERR
+        let ack = AckBuilder::accept_message(&incoming).unwrap();
+        assert!(ack.segment("ERR").is_none());
+    }
+
+    #[test]
+    fn test_with_version_responding_to() {
+        let incoming = create_test_message();
+        let ack = AckBuilder::with_version(Version::V2_3)
+            .ack_code(AckCode::Reject)
+            .text_message("Duplicate")
+            .control_id("ACK001")
+            .responding_to(&incoming)
+            .build()
+            .unwrap();
+
+        let msh = ack.get_msh().unwrap();
+        assert_eq!(msh.get_field_value(3), Some("RecApp"));
+        assert_eq!(msh.get_field_value(4), Some("RecFac"));
+        assert_eq!(msh.get_field_value(5), Some("SendApp"));
+        assert_eq!(msh.get_field_value(6), Some("SendFac"));
+        assert_eq!(msh.get_field_value(10), Some("ACK001"));
+        assert_eq!(msh.get_field_value(12), Some("2.3"));
+
+        let msa = ack.segment("MSA").unwrap();
+        assert_eq!(msa.get_field_value(1), Some("AR"));
+        assert_eq!(msa.get_field_value(2), Some("MSG001"));
+        assert_eq!(msa.get_field_value(3), Some("Duplicate"));
+        assert!(ack.segment("ERR").is_none());
+    }
+
     #[test]
     fn test_commit_ack() {
         let incoming = create_test_message();
@@ -829,6 +1040,31 @@ mod tests {
         assert_eq!(msh.get_field_value(4), Some("MyFacility"));
     }
 
+    #[test]
+    fn test_build_ack() {
+        let mut incoming = create_test_message();
+        incoming.segments[0].set_field_value(12, "2.5.1").unwrap();
+
+        let ack = build_ack(&incoming, AckCode::Reject).unwrap();
+        let msh = ack.get_msh().unwrap();
+        assert_eq!(msh.get_field_value(3), Some("RecApp"));
+        assert_eq!(msh.get_field_value(4), Some("RecFac"));
+        assert_eq!(msh.get_field_value(5), Some("SendApp"));
+        assert_eq!(msh.get_field_value(6), Some("SendFac"));
+        assert_ne!(msh.get_field_value(10), Some("MSG001"));
+        assert_eq!(msh.get_field_value(12), Some("2.5.1"));
+
+        let msa = ack.segment("MSA").unwrap();
+        assert_eq!(msa.get_field_value(1), Some("AR"));
+        assert_eq!(msa.get_field_value(2), Some("MSG001"));
+        assert_eq!(msa.get_field_value(3), Some(""));
+
+        let ack = build_ack_with_text(&incoming, AckCode::Error, "Unknown patient").unwrap();
+        let msa = ack.segment("MSA").unwrap();
+        assert_eq!(msa.get_field_value(1), Some("AE"));
+        assert_eq!(msa.get_field_value(3), Some("Unknown patient"));
+    }
+
     #[test]
     fn test_ack_code_strings() {
         assert_eq!(AckCode::Accept.as_str(), "AA");
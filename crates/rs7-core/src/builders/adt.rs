@@ -1,6 +1,7 @@
 //! ADT (Admit/Discharge/Transfer) message builders
 
-use super::{generate_control_id, MessageBuilder};
+use super::{control_id::ControlIdGenerator, MessageBuilder, TimestampPrecision};
+use std::sync::Arc;
 use crate::{
     error::Result,
     field::{Component, Field, Repetition},
@@ -180,6 +181,18 @@ impl AdtA01Builder {
         self
     }
 
+    /// Set the generator for the control ID used when none is set explicitly
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base.set_control_id_generator(generator);
+        self
+    }
+
+    /// Set the precision of generated timestamps (MSH-7, EVN-2)
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base.set_timestamp_precision(precision);
+        self
+    }
+
     /// Set processing ID (defaults to "P" for Production)
     pub fn processing_id(mut self, id: &str) -> Self {
         self.processing_id = id.to_string();
@@ -236,7 +249,7 @@ impl AdtA01Builder {
 
     /// Build the message
     pub fn build(mut self) -> Result<Message> {
-        let control_id = self.control_id.unwrap_or_else(generate_control_id);
+        let control_id = self.control_id.unwrap_or_else(|| self.base.next_control_id());
 
         // Create MSH segment
         let msh = self.base.create_msh(
@@ -378,6 +391,16 @@ impl AdtA02Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base = self.base.control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base = self.base.timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.base = self.base.patient_id(id);
         self
@@ -435,6 +458,16 @@ impl AdtA03Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base = self.base.control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base = self.base.timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.base = self.base.patient_id(id);
         self
@@ -487,6 +520,16 @@ impl AdtA04Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base = self.base.control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base = self.base.timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.base = self.base.patient_id(id);
         self
@@ -544,6 +587,16 @@ impl AdtA08Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base = self.base.control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base = self.base.timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.base = self.base.patient_id(id);
         self
@@ -601,6 +654,16 @@ impl AdtA05Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base = self.base.control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base = self.base.timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.base = self.base.patient_id(id);
         self
@@ -663,6 +726,16 @@ impl AdtA06Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base = self.base.control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base = self.base.timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.base = self.base.patient_id(id);
         self
@@ -720,6 +793,16 @@ impl AdtA07Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base = self.base.control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base = self.base.timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.base = self.base.patient_id(id);
         self
@@ -772,6 +855,16 @@ impl AdtA11Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base = self.base.control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base = self.base.timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.base = self.base.patient_id(id);
         self
@@ -819,6 +912,16 @@ impl AdtA12Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base = self.base.control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base = self.base.timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.base = self.base.patient_id(id);
         self
@@ -866,6 +969,16 @@ impl AdtA13Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base = self.base.control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base = self.base.timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.base = self.base.patient_id(id);
         self
@@ -913,6 +1026,16 @@ impl AdtA09Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base = self.base.control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base = self.base.timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.base = self.base.patient_id(id);
         self
@@ -970,6 +1093,16 @@ impl AdtA10Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base = self.base.control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base = self.base.timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.base = self.base.patient_id(id);
         self
@@ -1027,6 +1160,16 @@ impl AdtA17Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base = self.base.control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base = self.base.timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.base = self.base.patient_id(id);
         self
@@ -1074,6 +1217,16 @@ impl AdtA28Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base = self.base.control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base = self.base.timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.base = self.base.patient_id(id);
         self
@@ -1131,6 +1284,16 @@ impl AdtA31Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base = self.base.control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base = self.base.timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.base = self.base.patient_id(id);
         self
@@ -1188,6 +1351,16 @@ impl AdtA40Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base = self.base.control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base = self.base.timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.base = self.base.patient_id(id);
         self
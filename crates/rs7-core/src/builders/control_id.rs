@@ -0,0 +1,146 @@
+//! Message control ID generation
+//!
+//! Builders fill MSH-10 from a [`ControlIdGenerator`] when no explicit
+//! control ID is set. The default, [`TimestampGenerator`], derives IDs from
+//! the wall clock and can repeat when several messages are built within the
+//! same millisecond. Use [`SequentialGenerator`] for reproducible IDs in tests
+//! or [`UuidGenerator`] (feature `uuid`) for collision-free IDs in production.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rs7_core::builders::adt::AdtBuilder;
+//! use rs7_core::builders::control_id::SequentialGenerator;
+//! use rs7_core::Version;
+//! use std::sync::Arc;
+//!
+//! let ids = Arc::new(SequentialGenerator::new("TEST"));
+//! let first = AdtBuilder::a01(Version::V2_5)
+//!     .control_id_generator(ids.clone())
+//!     .patient_id("123")
+//!     .build()
+//!     .unwrap();
+//! let second = AdtBuilder::a01(Version::V2_5)
+//!     .control_id_generator(ids)
+//!     .patient_id("456")
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(first.get_control_id(), Some("TEST1"));
+//! assert_eq!(second.get_control_id(), Some("TEST2"));
+//! ```
+
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Source of message control IDs (MSH-10)
+///
+/// Implementations are shared between builders, possibly across threads, so
+/// they must be `Send + Sync` and should not hand out the same ID twice.
+pub trait ControlIdGenerator: Send + Sync + Debug {
+    /// Produce the next control ID
+    fn next_id(&self) -> String;
+}
+
+/// Control IDs from the wall clock: `MSG` followed by Unix milliseconds
+///
+/// This is the default. IDs repeat if two messages are built within the same
+/// millisecond.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TimestampGenerator;
+
+impl ControlIdGenerator for TimestampGenerator {
+    fn next_id(&self) -> String {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis();
+        format!("MSG{}", timestamp)
+    }
+}
+
+/// Control IDs from a counter: the prefix followed by 1, 2, 3, ...
+#[derive(Debug, Default)]
+pub struct SequentialGenerator {
+    prefix: String,
+    next: AtomicU64,
+}
+
+impl SequentialGenerator {
+    /// Create a generator whose first ID is `{prefix}1`
+    pub fn new(prefix: &str) -> Self {
+        Self::starting_at(prefix, 1)
+    }
+
+    /// Create a generator whose first ID is `{prefix}{start}`
+    pub fn starting_at(prefix: &str, start: u64) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+            next: AtomicU64::new(start),
+        }
+    }
+}
+
+impl ControlIdGenerator for SequentialGenerator {
+    fn next_id(&self) -> String {
+        format!("{}{}", self.prefix, self.next.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Control IDs from random (v4) UUIDs, in hyphenated form
+///
+/// The 36-character IDs exceed the 20-character MSH-10 length of HL7 v2.3
+/// to v2.5; check that receivers accept them.
+#[cfg(feature = "uuid")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UuidGenerator;
+
+#[cfg(feature = "uuid")]
+impl ControlIdGenerator for UuidGenerator {
+    fn next_id(&self) -> String {
+        uuid::Uuid::new_v4().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generators() {
+        assert!(TimestampGenerator.next_id().starts_with("MSG"));
+
+        let sequential = SequentialGenerator::starting_at("ID", 41);
+        assert_eq!(sequential.next_id(), "ID41");
+        assert_eq!(sequential.next_id(), "ID42");
+
+        let ack = crate::builders::ack::AckBuilder::with_version(crate::Version::V2_5)
+            .control_id_generator(std::sync::Arc::new(SequentialGenerator::new("ACK")))
+            .build()
+            .unwrap();
+        assert_eq!(ack.get_control_id(), Some("ACK1"));
+
+        // Query tags and IDs come from the same generator as MSH-10
+        let qbp = crate::builders::qbp::QbpQ22Builder::new(crate::Version::V2_5)
+            .control_id_generator(std::sync::Arc::new(SequentialGenerator::new("Q")))
+            .build()
+            .unwrap();
+        assert_eq!(qbp.get_control_id(), Some("Q1"));
+        assert_eq!(qbp.segment("QPD").unwrap().get_field_value(2), Some("Q2"));
+
+        let qry = crate::builders::qry::QryA19Builder::new(crate::Version::V2_5)
+            .control_id_generator(std::sync::Arc::new(SequentialGenerator::new("Q")))
+            .build()
+            .unwrap();
+        assert_eq!(qry.get_control_id(), Some("Q1"));
+        assert_eq!(qry.segment("QRD").unwrap().get_field_value(4), Some("Q2"));
+
+        #[cfg(feature = "uuid")]
+        {
+            let id = UuidGenerator.next_id();
+            assert_eq!(id.len(), 36);
+            assert_ne!(id, UuidGenerator.next_id());
+        }
+    }
+}
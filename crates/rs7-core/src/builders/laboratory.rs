@@ -1,6 +1,7 @@
 //! Laboratory message builders (OUL, OML)
 
-use super::{generate_control_id, MessageBuilder};
+use super::{control_id::ControlIdGenerator, MessageBuilder, TimestampPrecision};
+use std::sync::Arc;
 use crate::{error::Result, field::Field, message::Message, segment::Segment, Version};
 
 /// Builder for OUL^R21 - Unsolicited Laboratory Observation
@@ -53,6 +54,16 @@ impl OulR21Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base.set_control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base.set_timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.patient_id = Some(id.to_string());
         self
@@ -74,7 +85,7 @@ impl OulR21Builder {
     }
 
     pub fn build(mut self) -> Result<Message> {
-        let control_id = self.control_id.unwrap_or_else(generate_control_id);
+        let control_id = self.control_id.unwrap_or_else(|| self.base.next_control_id());
 
         let msh = self.base.create_msh(
             &self.sending_app,
@@ -178,6 +189,16 @@ impl OmlO21Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base.set_control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base.set_timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.patient_id = Some(id.to_string());
         self
@@ -204,7 +225,7 @@ impl OmlO21Builder {
     }
 
     pub fn build(mut self) -> Result<Message> {
-        let control_id = self.control_id.unwrap_or_else(generate_control_id);
+        let control_id = self.control_id.unwrap_or_else(|| self.base.next_control_id());
 
         let msh = self.base.create_msh(
             &self.sending_app,
@@ -6,6 +6,7 @@
 pub mod ack;
 pub mod adt;
 pub mod batch;
+pub mod control_id;
 pub mod dft;
 pub mod fields;
 pub mod laboratory;
@@ -27,7 +28,41 @@ use crate::{
     types::format_timestamp,
     Version,
 };
-use chrono::Local;
+use chrono::{Local, NaiveDateTime};
+use control_id::{ControlIdGenerator, TimestampGenerator};
+use std::sync::Arc;
+
+/// Precision of timestamps generated by builders (MSH-7, EVN-2, QRD-1)
+///
+/// Some receivers require millisecond timestamps while others reject anything
+/// beyond minutes. Timestamps supplied explicitly, such as an EVN recorded
+/// date/time, are used as given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimestampPrecision {
+    /// YYYYMMDDHHMM
+    Minute,
+    /// YYYYMMDDHHMMSS
+    #[default]
+    Second,
+    /// YYYYMMDDHHMMSS.sss
+    Millisecond,
+}
+
+impl TimestampPrecision {
+    /// Format a timestamp at this precision
+    pub fn format(&self, dt: &NaiveDateTime) -> String {
+        match self {
+            TimestampPrecision::Minute => dt.format("%Y%m%d%H%M").to_string(),
+            TimestampPrecision::Second => format_timestamp(dt),
+            TimestampPrecision::Millisecond => dt.format("%Y%m%d%H%M%S%.3f").to_string(),
+        }
+    }
+
+    /// Format the current local time at this precision
+    pub fn now(&self) -> String {
+        self.format(&Local::now().naive_local())
+    }
+}
 
 /// Base message builder with common functionality
 pub struct MessageBuilder {
@@ -35,6 +70,8 @@ pub struct MessageBuilder {
     version: Version,
     message_type: String,
     trigger_event: String,
+    control_id_generator: Arc<dyn ControlIdGenerator>,
+    timestamp_precision: TimestampPrecision,
 }
 
 impl MessageBuilder {
@@ -45,9 +82,36 @@ impl MessageBuilder {
             version,
             message_type: message_type.to_string(),
             trigger_event: trigger_event.to_string(),
+            control_id_generator: Arc::new(TimestampGenerator),
+            timestamp_precision: TimestampPrecision::default(),
         }
     }
 
+    /// Set the generator used for control IDs that are not set explicitly
+    pub fn set_control_id_generator(&mut self, generator: Arc<dyn ControlIdGenerator>) {
+        self.control_id_generator = generator;
+    }
+
+    /// Get the next control ID from the builder's generator
+    pub fn next_control_id(&self) -> String {
+        self.control_id_generator.next_id()
+    }
+
+    /// Set the precision of the timestamps this builder generates
+    pub fn set_timestamp_precision(&mut self, precision: TimestampPrecision) {
+        self.timestamp_precision = precision;
+    }
+
+    /// Get the precision of the timestamps this builder generates
+    pub fn timestamp_precision(&self) -> TimestampPrecision {
+        self.timestamp_precision
+    }
+
+    /// Format the current time at the builder's timestamp precision
+    pub fn timestamp_now(&self) -> String {
+        self.timestamp_precision.now()
+    }
+
     /// Create MSH segment with basic fields
     pub fn create_msh(
         &self,
@@ -83,7 +147,7 @@ impl MessageBuilder {
         msh.add_field(Field::from_value(receiving_facility));
 
         // MSH-7: Date/Time of Message
-        let timestamp = format_timestamp(&Local::now().naive_local());
+        let timestamp = self.timestamp_now();
         msh.add_field(Field::from_value(&timestamp));
 
         // MSH-8: Security (empty)
@@ -120,7 +184,7 @@ impl MessageBuilder {
         let timestamp = if let Some(dt) = recorded_datetime {
             dt.to_string()
         } else {
-            format_timestamp(&Local::now().naive_local())
+            self.timestamp_now()
         };
         evn.add_field(Field::from_value(&timestamp));
 
@@ -133,12 +197,44 @@ impl MessageBuilder {
     }
 }
 
-/// Generate a message control ID (simple implementation)
+/// Generate a message control ID from the wall clock
+///
+/// See [`TimestampGenerator`]; builders can use another
+/// [`ControlIdGenerator`] instead.
 pub fn generate_control_id() -> String {
-    use std::time::{SystemTime, UNIX_EPOCH};
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_millis();
-    format!("MSG{}", timestamp)
+    TimestampGenerator.next_id()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_timestamp_precision() {
+        let dt = NaiveDate::from_ymd_opt(2024, 3, 15)
+            .unwrap()
+            .and_hms_milli_opt(14, 30, 5, 42)
+            .unwrap();
+        assert_eq!(TimestampPrecision::Minute.format(&dt), "202403151430");
+        assert_eq!(TimestampPrecision::Second.format(&dt), "20240315143005");
+        assert_eq!(TimestampPrecision::Millisecond.format(&dt), "20240315143005.042");
+
+        let message = adt::AdtBuilder::a01(Version::V2_5)
+            .timestamp_precision(TimestampPrecision::Millisecond)
+            .patient_id("123")
+            .build()
+            .unwrap();
+        let msh_7 = message.segment("MSH").unwrap().get_field_value(7).unwrap();
+        let evn_2 = message.segment("EVN").unwrap().get_field_value(2).unwrap();
+        assert_eq!(msh_7.len(), 18);
+        assert_eq!(msh_7.as_bytes()[14], b'.');
+        assert_eq!(evn_2.len(), 18);
+
+        let ack = ack::AckBuilder::with_version(Version::V2_5)
+            .timestamp_precision(TimestampPrecision::Minute)
+            .build()
+            .unwrap();
+        assert_eq!(ack.segment("MSH").unwrap().get_field_value(7).unwrap().len(), 12);
+    }
 }
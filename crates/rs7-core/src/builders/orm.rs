@@ -1,6 +1,7 @@
 //! ORM (Order Message) message builders
 
-use super::{generate_control_id, MessageBuilder};
+use super::{control_id::ControlIdGenerator, MessageBuilder, TimestampPrecision};
+use std::sync::Arc;
 use crate::{
     error::Result,
     field::Field,
@@ -69,6 +70,18 @@ impl OrmO01Builder {
         self
     }
 
+    /// Set the generator for the control ID used when none is set explicitly
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base.set_control_id_generator(generator);
+        self
+    }
+
+    /// Set the precision of generated timestamps (MSH-7, EVN-2)
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base.set_timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.patient_id = Some(id.to_string());
         self
@@ -96,7 +109,7 @@ impl OrmO01Builder {
 
     /// Build the message
     pub fn build(mut self) -> Result<Message> {
-        let control_id = self.control_id.unwrap_or_else(generate_control_id);
+        let control_id = self.control_id.unwrap_or_else(|| self.base.next_control_id());
 
         // Create MSH segment
         let msh = self.base.create_msh(
@@ -1,6 +1,7 @@
 //! ORU (Observation Result) message builders
 
-use super::{generate_control_id, MessageBuilder};
+use super::{control_id::ControlIdGenerator, MessageBuilder, TimestampPrecision};
+use std::sync::Arc;
 use crate::{
     error::Result,
     field::Field,
@@ -24,6 +25,46 @@ pub struct OruR01Builder {
     order_control: String,
     filler_order_number: Option<String>,
     observations: Vec<Observation>,
+    orders: Vec<OrderGroup>,
+}
+
+/// A typed OBX observation value
+///
+/// The variant determines OBX-2 (value type) and how OBX-5 and OBX-6 are filled.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObxValue {
+    /// Numeric value (NM) with units for OBX-6; use an empty string for no units
+    Numeric(f64, String),
+    /// Text value (ST)
+    Text(String),
+    /// Coded value (CE): code, text and coding system
+    Coded(String, String, String),
+}
+
+impl ObxValue {
+    /// HL7 value type written to OBX-2
+    pub fn value_type(&self) -> &'static str {
+        match self {
+            ObxValue::Numeric(..) => "NM",
+            ObxValue::Text(_) => "ST",
+            ObxValue::Coded(..) => "CE",
+        }
+    }
+}
+
+/// An OBR with the typed observations reported under it
+struct OrderGroup {
+    universal_service_id: Option<[String; 3]>,
+    observations: Vec<(String, ObxValue)>,
+}
+
+impl OrderGroup {
+    fn new(universal_service_id: Option<[String; 3]>) -> Self {
+        Self {
+            universal_service_id,
+            observations: Vec::new(),
+        }
+    }
 }
 
 pub struct Observation {
@@ -51,6 +92,7 @@ impl OruR01Builder {
             order_control: "RE".to_string(),
             filler_order_number: None,
             observations: Vec::new(),
+            orders: vec![OrderGroup::new(None)],
         }
     }
 
@@ -79,6 +121,18 @@ impl OruR01Builder {
         self
     }
 
+    /// Set the generator for the control ID used when none is set explicitly
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base.set_control_id_generator(generator);
+        self
+    }
+
+    /// Set the precision of generated timestamps (MSH-7, EVN-2)
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base.set_timestamp_precision(precision);
+        self
+    }
+
     pub fn patient_id(mut self, id: &str) -> Self {
         self.patient_id = Some(id.to_string());
         self
@@ -100,9 +154,60 @@ impl OruR01Builder {
         self
     }
 
+    /// Start a new OBR with the given universal service ID (OBR-4)
+    ///
+    /// Typed observations added afterwards are reported under this OBR. The
+    /// first call names the message's initial OBR instead of adding one, as
+    /// long as no typed observations were added before it.
+    pub fn add_order(mut self, code: &str, text: &str, system: &str) -> Self {
+        let service_id = Some([code.to_string(), text.to_string(), system.to_string()]);
+        match self.orders.as_mut_slice() {
+            [only] if only.universal_service_id.is_none() && only.observations.is_empty() => {
+                only.universal_service_id = service_id;
+            }
+            _ => self.orders.push(OrderGroup::new(service_id)),
+        }
+        self
+    }
+
+    /// Add a typed observation to the current OBR
+    ///
+    /// `id` is the observation identifier written to OBX-3. OBX-1 is numbered
+    /// within each OBR, OBX-2 follows the value type, and OBX-11 is set to
+    /// "F" (final).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rs7_core::builders::oru::{ObxValue, OruR01Builder};
+    /// use rs7_core::Version;
+    ///
+    /// let message = OruR01Builder::new(Version::V2_5)
+    ///     .sending_application("LAB")
+    ///     .control_id("MSG001")
+    ///     .patient_id("12345")
+    ///     .add_order("CBC", "Complete Blood Count", "L")
+    ///     .add_typed_observation("WBC", ObxValue::Numeric(7.5, "10*3/uL".into()))
+    ///     .add_typed_observation("COMMENT", ObxValue::Text("Normal".into()))
+    ///     .add_order("UA", "Urinalysis", "L")
+    ///     .add_typed_observation("COLOR", ObxValue::Coded("Y".into(), "Yellow".into(), "L".into()))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let encoded = message.encode();
+    /// assert!(encoded.contains("\rOBX|1|NM|WBC||7.5|10*3/uL|||||F"));
+    /// assert!(encoded.contains("\rOBR|2|||UA^Urinalysis^L\rOBX|1|CE|COLOR||Y^Yellow^L"));
+    /// ```
+    pub fn add_typed_observation(mut self, id: &str, value: ObxValue) -> Self {
+        if let Some(order) = self.orders.last_mut() {
+            order.observations.push((id.to_string(), value));
+        }
+        self
+    }
+
     /// Build the message
     pub fn build(mut self) -> Result<Message> {
-        let control_id = self.control_id.unwrap_or_else(generate_control_id);
+        let control_id = self.control_id.unwrap_or_else(|| self.base.next_control_id());
 
         // Create MSH segment
         let msh = self.base.create_msh(
@@ -131,39 +236,133 @@ impl OruR01Builder {
 
         self.base.message.add_segment(pid);
 
-        // Create OBR segment
-        let mut obr = Segment::new("OBR");
-        obr.add_field(Field::from_value("1")); // OBR-1: Set ID
-        obr.add_field(Field::from_value("")); // OBR-2: Placer Order Number
-        obr.add_field(Field::from_value(self.filler_order_number.as_deref().unwrap_or(""))); // OBR-3: Filler Order Number
-        obr.add_field(Field::from_value("")); // OBR-4: Universal Service ID
-
-        self.base.message.add_segment(obr);
-
-        // Create OBX segments for observations
-        for obs in &self.observations {
-            let mut obx = Segment::new("OBX");
-            obx.add_field(Field::from_value(obs.set_id.to_string())); // OBX-1: Set ID
-            obx.add_field(Field::from_value(&obs.value_type)); // OBX-2: Value Type
-            obx.add_field(Field::from_value(&obs.identifier)); // OBX-3: Observation Identifier
-            obx.add_field(Field::from_value("")); // OBX-4: Observation Sub-ID
-            obx.add_field(Field::from_value(&obs.value)); // OBX-5: Observation Value
-
-            if let Some(units) = &obs.units {
-                obx.add_field(Field::from_value(units)); // OBX-6: Units
-            } else {
-                obx.add_field(Field::from_value(""));
+        for (index, order) in self.orders.iter().enumerate() {
+            // Create OBR segment
+            let mut obr = Segment::new("OBR");
+            obr.add_field(Field::from_value((index + 1).to_string())); // OBR-1: Set ID
+            obr.add_field(Field::from_value("")); // OBR-2: Placer Order Number
+            let filler = if index == 0 { self.filler_order_number.as_deref() } else { None };
+            obr.add_field(Field::from_value(filler.unwrap_or(""))); // OBR-3: Filler Order Number
+            obr.add_field(Field::from_value("")); // OBR-4: Universal Service ID
+            if let Some(service_id) = &order.universal_service_id {
+                set_components(&mut obr, 4, service_id)?;
             }
 
-            obx.add_field(Field::from_value("")); // OBX-7: References Range
-            obx.add_field(Field::from_value("")); // OBX-8: Abnormal Flags
-            obx.add_field(Field::from_value("")); // OBX-9: Probability
-            obx.add_field(Field::from_value("")); // OBX-10: Nature of Abnormal Test
-            obx.add_field(Field::from_value(&obs.status)); // OBX-11: Observation Result Status
+            self.base.message.add_segment(obr);
+
+            // Untyped observations belong to the first OBR
+            let first_set_id = if index == 0 {
+                for obs in &self.observations {
+                    self.base.message.add_segment(untyped_obx(obs));
+                }
+                self.observations.len() + 1
+            } else {
+                1
+            };
 
-            self.base.message.add_segment(obx);
+            for (offset, (id, value)) in order.observations.iter().enumerate() {
+                let obx = typed_obx(first_set_id + offset, id, value)?;
+                self.base.message.add_segment(obx);
+            }
         }
 
         Ok(self.base.build())
     }
 }
+
+/// Set the non-empty values as consecutive components of a field
+fn set_components<S: AsRef<str>>(segment: &mut Segment, field_index: usize, values: &[S]) -> Result<()> {
+    for (comp_index, value) in values.iter().enumerate() {
+        let value = value.as_ref();
+        if !value.is_empty() {
+            segment.set_component(field_index, 0, comp_index, value)?;
+        }
+    }
+    Ok(())
+}
+
+/// Build an OBX segment for a typed observation
+fn typed_obx(set_id: usize, id: &str, value: &ObxValue) -> Result<Segment> {
+    let mut obx = Segment::new("OBX");
+    obx.set_field_value(1, set_id.to_string())?; // OBX-1: Set ID
+    obx.set_field_value(2, value.value_type())?; // OBX-2: Value Type
+    obx.set_field_value(3, id)?; // OBX-3: Observation Identifier
+
+    match value {
+        ObxValue::Numeric(number, units) => {
+            obx.set_field_value(5, number.to_string())?;
+            obx.set_field_value(6, units.as_str())?;
+        }
+        ObxValue::Text(text) => obx.set_field_value(5, text.as_str())?,
+        ObxValue::Coded(code, text, system) => set_components(&mut obx, 5, &[code, text, system])?,
+    }
+
+    obx.set_field_value(11, "F")?; // OBX-11: Observation Result Status
+    Ok(obx)
+}
+
+/// Build an OBX segment for an untyped observation
+fn untyped_obx(obs: &Observation) -> Segment {
+    let mut obx = Segment::new("OBX");
+    obx.add_field(Field::from_value(obs.set_id.to_string())); // OBX-1: Set ID
+    obx.add_field(Field::from_value(&obs.value_type)); // OBX-2: Value Type
+    obx.add_field(Field::from_value(&obs.identifier)); // OBX-3: Observation Identifier
+    obx.add_field(Field::from_value("")); // OBX-4: Observation Sub-ID
+    obx.add_field(Field::from_value(&obs.value)); // OBX-5: Observation Value
+
+    if let Some(units) = &obs.units {
+        obx.add_field(Field::from_value(units)); // OBX-6: Units
+    } else {
+        obx.add_field(Field::from_value(""));
+    }
+
+    obx.add_field(Field::from_value("")); // OBX-7: References Range
+    obx.add_field(Field::from_value("")); // OBX-8: Abnormal Flags
+    obx.add_field(Field::from_value("")); // OBX-9: Probability
+    obx.add_field(Field::from_value("")); // OBX-10: Nature of Abnormal Test
+    obx.add_field(Field::from_value(&obs.status)); // OBX-11: Observation Result Status
+
+    obx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typed_observations_numbering() {
+        let message = OruR01Builder::new(Version::V2_5)
+            .control_id("MSG001")
+            .filler_order_number("F100")
+            .add_observation(Observation {
+                set_id: 1,
+                value_type: "ST".to_string(),
+                identifier: "NOTE".to_string(),
+                value: "legacy".to_string(),
+                units: None,
+                status: "F".to_string(),
+            })
+            .add_typed_observation("GLU", ObxValue::Numeric(98.0, "mg/dL".to_string()))
+            .add_order("LIP", "Lipid Panel", "")
+            .add_typed_observation("CHOL", ObxValue::Numeric(180.5, String::new()))
+            .build()
+            .unwrap();
+
+        let obrs = message.get_segments_by_id("OBR");
+        assert_eq!(obrs.len(), 2);
+        assert_eq!(obrs[0].get_field_value(3), Some("F100"));
+        assert_eq!(obrs[0].get_field_value(4), Some(""));
+        assert_eq!(obrs[1].get_field_value(1), Some("2"));
+        assert_eq!(obrs[1].get_field_value(3), Some(""));
+        assert_eq!(obrs[1].encode(&message.delimiters), "OBR|2|||LIP^Lipid Panel");
+
+        let obx: Vec<String> = message
+            .get_segments_by_id("OBX")
+            .iter()
+            .map(|s| s.encode(&message.delimiters))
+            .collect();
+        assert_eq!(obx[1], "OBX|2|NM|GLU||98|mg/dL|||||F");
+        assert_eq!(obx[2], "OBX|1|NM|CHOL||180.5||||||F");
+        assert_eq!(ObxValue::Text("x".into()).value_type(), "ST");
+    }
+}
@@ -4,7 +4,8 @@
 //! segments instead of the older QRD-based queries. QBP is the standard query mechanism in
 //! HL7 v2.5+.
 
-use super::{generate_control_id, MessageBuilder};
+use super::{control_id::ControlIdGenerator, MessageBuilder, TimestampPrecision};
+use std::sync::Arc;
 use crate::{
     builders::fields::{QpdBuilder, RcpBuilder},
     error::Result,
@@ -40,7 +41,7 @@ pub struct QbpQ11Builder {
     receiving_facility: String,
     control_id: Option<String>,
     query_name: String,
-    query_tag: String,
+    query_tag: Option<String>,
     qpd_parameters: Vec<String>,
     query_priority: String,
     quantity_limit: Option<String>,
@@ -56,7 +57,7 @@ impl QbpQ11Builder {
             receiving_facility: String::new(),
             control_id: None,
             query_name: "Z44^Request Evaluated History and Forecast^CDCPHINVS".to_string(),
-            query_tag: generate_control_id(),
+            query_tag: None,
             qpd_parameters: Vec::new(),
             query_priority: "I".to_string(),
             quantity_limit: Some("100^RD".to_string()),
@@ -88,6 +89,18 @@ impl QbpQ11Builder {
         self
     }
 
+    /// Set the generator for the control ID used when none is set explicitly
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base.set_control_id_generator(generator);
+        self
+    }
+
+    /// Set the precision of generated timestamps (MSH-7, EVN-2)
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base.set_timestamp_precision(precision);
+        self
+    }
+
     /// Set the query name (QPD-1): identifies the query profile
     pub fn query_name(mut self, name: &str) -> Self {
         self.query_name = name.to_string();
@@ -95,8 +108,10 @@ impl QbpQ11Builder {
     }
 
     /// Set the query tag (QPD-2): unique identifier for this query
+    ///
+    /// Defaults to an ID from the builder's control ID generator.
     pub fn query_tag(mut self, tag: &str) -> Self {
-        self.query_tag = tag.to_string();
+        self.query_tag = Some(tag.to_string());
         self
     }
 
@@ -155,7 +170,8 @@ impl QbpQ11Builder {
     }
 
     pub fn build(mut self) -> Result<Message> {
-        let control_id = self.control_id.unwrap_or_else(generate_control_id);
+        let control_id = self.control_id.unwrap_or_else(|| self.base.next_control_id());
+        let query_tag = self.query_tag.take().unwrap_or_else(|| self.base.next_control_id());
 
         // MSH segment
         let msh = self.base.create_msh(
@@ -171,7 +187,7 @@ impl QbpQ11Builder {
         // QPD segment
         let mut qpd_builder = QpdBuilder::new()
             .message_query_name(&self.query_name)
-            .query_tag(&self.query_tag);
+            .query_tag(&query_tag);
 
         for param in &self.qpd_parameters {
             qpd_builder = qpd_builder.parameter(param);
@@ -204,7 +220,7 @@ pub struct QbpQ15Builder {
     receiving_facility: String,
     control_id: Option<String>,
     query_name: String,
-    query_tag: String,
+    query_tag: Option<String>,
     qpd_parameters: Vec<String>,
     query_priority: String,
     quantity_limit: Option<String>,
@@ -220,7 +236,7 @@ impl QbpQ15Builder {
             receiving_facility: String::new(),
             control_id: None,
             query_name: "Q15^Display Response^HL7".to_string(),
-            query_tag: generate_control_id(),
+            query_tag: None,
             qpd_parameters: Vec::new(),
             query_priority: "I".to_string(),
             quantity_limit: Some("100^RD".to_string()),
@@ -252,13 +268,25 @@ impl QbpQ15Builder {
         self
     }
 
+    /// Set the generator for the control ID used when none is set explicitly
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base.set_control_id_generator(generator);
+        self
+    }
+
+    /// Set the precision of generated timestamps (MSH-7, EVN-2)
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base.set_timestamp_precision(precision);
+        self
+    }
+
     pub fn query_name(mut self, name: &str) -> Self {
         self.query_name = name.to_string();
         self
     }
 
     pub fn query_tag(mut self, tag: &str) -> Self {
-        self.query_tag = tag.to_string();
+        self.query_tag = Some(tag.to_string());
         self
     }
 
@@ -278,7 +306,8 @@ impl QbpQ15Builder {
     }
 
     pub fn build(mut self) -> Result<Message> {
-        let control_id = self.control_id.unwrap_or_else(generate_control_id);
+        let control_id = self.control_id.unwrap_or_else(|| self.base.next_control_id());
+        let query_tag = self.query_tag.take().unwrap_or_else(|| self.base.next_control_id());
 
         let msh = self.base.create_msh(
             &self.sending_app,
@@ -292,7 +321,7 @@ impl QbpQ15Builder {
 
         let mut qpd_builder = QpdBuilder::new()
             .message_query_name(&self.query_name)
-            .query_tag(&self.query_tag);
+            .query_tag(&query_tag);
 
         for param in &self.qpd_parameters {
             qpd_builder = qpd_builder.parameter(param);
@@ -324,7 +353,7 @@ pub struct QbpQ21Builder {
     receiving_facility: String,
     control_id: Option<String>,
     query_name: String,
-    query_tag: String,
+    query_tag: Option<String>,
     qpd_parameters: Vec<String>,
     query_priority: String,
     quantity_limit: Option<String>,
@@ -340,7 +369,7 @@ impl QbpQ21Builder {
             receiving_facility: String::new(),
             control_id: None,
             query_name: "Q21^Get Person Demographics^HL7".to_string(),
-            query_tag: generate_control_id(),
+            query_tag: None,
             qpd_parameters: Vec::new(),
             query_priority: "I".to_string(),
             quantity_limit: Some("1^RD".to_string()),
@@ -372,13 +401,25 @@ impl QbpQ21Builder {
         self
     }
 
+    /// Set the generator for the control ID used when none is set explicitly
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base.set_control_id_generator(generator);
+        self
+    }
+
+    /// Set the precision of generated timestamps (MSH-7, EVN-2)
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base.set_timestamp_precision(precision);
+        self
+    }
+
     pub fn query_name(mut self, name: &str) -> Self {
         self.query_name = name.to_string();
         self
     }
 
     pub fn query_tag(mut self, tag: &str) -> Self {
-        self.query_tag = tag.to_string();
+        self.query_tag = Some(tag.to_string());
         self
     }
 
@@ -404,7 +445,8 @@ impl QbpQ21Builder {
     }
 
     pub fn build(mut self) -> Result<Message> {
-        let control_id = self.control_id.unwrap_or_else(generate_control_id);
+        let control_id = self.control_id.unwrap_or_else(|| self.base.next_control_id());
+        let query_tag = self.query_tag.take().unwrap_or_else(|| self.base.next_control_id());
 
         let msh = self.base.create_msh(
             &self.sending_app,
@@ -418,7 +460,7 @@ impl QbpQ21Builder {
 
         let mut qpd_builder = QpdBuilder::new()
             .message_query_name(&self.query_name)
-            .query_tag(&self.query_tag);
+            .query_tag(&query_tag);
 
         for param in &self.qpd_parameters {
             qpd_builder = qpd_builder.parameter(param);
@@ -469,7 +511,7 @@ pub struct QbpQ22Builder {
     receiving_facility: String,
     control_id: Option<String>,
     query_name: String,
-    query_tag: String,
+    query_tag: Option<String>,
     qpd_parameters: Vec<String>,
     query_priority: String,
     quantity_limit: Option<String>,
@@ -485,7 +527,7 @@ impl QbpQ22Builder {
             receiving_facility: String::new(),
             control_id: None,
             query_name: "Q22^Find Candidates^HL7".to_string(),
-            query_tag: generate_control_id(),
+            query_tag: None,
             qpd_parameters: Vec::new(),
             query_priority: "I".to_string(),
             quantity_limit: Some("50^RD".to_string()),
@@ -517,13 +559,25 @@ impl QbpQ22Builder {
         self
     }
 
+    /// Set the generator for the control ID used when none is set explicitly
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base.set_control_id_generator(generator);
+        self
+    }
+
+    /// Set the precision of generated timestamps (MSH-7, EVN-2)
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base.set_timestamp_precision(precision);
+        self
+    }
+
     pub fn query_name(mut self, name: &str) -> Self {
         self.query_name = name.to_string();
         self
     }
 
     pub fn query_tag(mut self, tag: &str) -> Self {
-        self.query_tag = tag.to_string();
+        self.query_tag = Some(tag.to_string());
         self
     }
 
@@ -544,7 +598,8 @@ impl QbpQ22Builder {
     }
 
     pub fn build(mut self) -> Result<Message> {
-        let control_id = self.control_id.unwrap_or_else(generate_control_id);
+        let control_id = self.control_id.unwrap_or_else(|| self.base.next_control_id());
+        let query_tag = self.query_tag.take().unwrap_or_else(|| self.base.next_control_id());
 
         let msh = self.base.create_msh(
             &self.sending_app,
@@ -558,7 +613,7 @@ impl QbpQ22Builder {
 
         let mut qpd_builder = QpdBuilder::new()
             .message_query_name(&self.query_name)
-            .query_tag(&self.query_tag);
+            .query_tag(&query_tag);
 
         for param in &self.qpd_parameters {
             qpd_builder = qpd_builder.parameter(param);
@@ -1,8 +1,8 @@
 //! QRY (Query) message builders
 
-use super::{generate_control_id, MessageBuilder};
-use crate::{error::Result, field::Field, message::Message, segment::Segment, types::format_timestamp, Version};
-use chrono::Local;
+use super::{control_id::ControlIdGenerator, MessageBuilder, TimestampPrecision};
+use std::sync::Arc;
+use crate::{error::Result, field::Field, message::Message, segment::Segment, Version};
 
 /// Builder for QRY^A19 - Patient Query
 pub struct QryA19Builder {
@@ -12,7 +12,7 @@ pub struct QryA19Builder {
     receiving_app: String,
     receiving_facility: String,
     control_id: Option<String>,
-    query_id: String,
+    query_id: Option<String>,
     patient_id: Option<String>,
     patient_name: Option<(String, String)>,
 }
@@ -26,7 +26,7 @@ impl QryA19Builder {
             receiving_app: String::new(),
             receiving_facility: String::new(),
             control_id: None,
-            query_id: generate_control_id(),
+            query_id: None,
             patient_id: None,
             patient_name: None,
         }
@@ -57,8 +57,20 @@ impl QryA19Builder {
         self
     }
 
+    /// Set the generator for the control ID used when none is set explicitly
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base.set_control_id_generator(generator);
+        self
+    }
+
+    /// Set the precision of generated timestamps (MSH-7, QRD-1)
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base.set_timestamp_precision(precision);
+        self
+    }
+
     pub fn query_id(mut self, id: &str) -> Self {
-        self.query_id = id.to_string();
+        self.query_id = Some(id.to_string());
         self
     }
 
@@ -73,7 +85,8 @@ impl QryA19Builder {
     }
 
     pub fn build(mut self) -> Result<Message> {
-        let control_id = self.control_id.unwrap_or_else(generate_control_id);
+        let control_id = self.control_id.unwrap_or_else(|| self.base.next_control_id());
+        let query_id = self.query_id.take().unwrap_or_else(|| self.base.next_control_id());
 
         let msh = self.base.create_msh(
             &self.sending_app,
@@ -89,7 +102,7 @@ impl QryA19Builder {
         let mut qrd = Segment::new("QRD");
 
         // QRD-1: Query Date/Time
-        let timestamp = format_timestamp(&Local::now().naive_local());
+        let timestamp = self.base.timestamp_now();
         qrd.add_field(Field::from_value(&timestamp));
 
         // QRD-2: Query Format Code (R = Record-oriented)
@@ -99,7 +112,7 @@ impl QryA19Builder {
         qrd.add_field(Field::from_value("I"));
 
         // QRD-4: Query ID
-        qrd.add_field(Field::from_value(&self.query_id));
+        qrd.add_field(Field::from_value(&query_id));
 
         // QRD-5: Deferred Response Type (empty)
         qrd.add_field(Field::from_value(""));
@@ -140,7 +153,7 @@ pub struct QryQ01Builder {
     receiving_app: String,
     receiving_facility: String,
     control_id: Option<String>,
-    query_id: String,
+    query_id: Option<String>,
     what_subject_filter: String,
 }
 
@@ -153,7 +166,7 @@ impl QryQ01Builder {
             receiving_app: String::new(),
             receiving_facility: String::new(),
             control_id: None,
-            query_id: generate_control_id(),
+            query_id: None,
             what_subject_filter: String::new(),
         }
     }
@@ -178,8 +191,18 @@ impl QryQ01Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base.set_control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base.set_timestamp_precision(precision);
+        self
+    }
+
     pub fn query_id(mut self, id: &str) -> Self {
-        self.query_id = id.to_string();
+        self.query_id = Some(id.to_string());
         self
     }
 
@@ -189,7 +212,8 @@ impl QryQ01Builder {
     }
 
     pub fn build(mut self) -> Result<Message> {
-        let control_id = self.control_id.unwrap_or_else(generate_control_id);
+        let control_id = self.control_id.unwrap_or_else(|| self.base.next_control_id());
+        let query_id = self.query_id.take().unwrap_or_else(|| self.base.next_control_id());
 
         let msh = self.base.create_msh(
             &self.sending_app,
@@ -204,11 +228,11 @@ impl QryQ01Builder {
         // QRD segment
         let mut qrd = Segment::new("QRD");
 
-        let timestamp = format_timestamp(&Local::now().naive_local());
+        let timestamp = self.base.timestamp_now();
         qrd.add_field(Field::from_value(&timestamp));
         qrd.add_field(Field::from_value("R")); // Format
         qrd.add_field(Field::from_value("I")); // Priority
-        qrd.add_field(Field::from_value(&self.query_id));
+        qrd.add_field(Field::from_value(&query_id));
         qrd.add_field(Field::from_value("")); // Deferred Response Type
         qrd.add_field(Field::from_value("")); // Deferred Response Date/Time
         qrd.add_field(Field::from_value("")); // Quantity Limited Request
@@ -229,7 +253,7 @@ pub struct QryQ02Builder {
     receiving_app: String,
     receiving_facility: String,
     control_id: Option<String>,
-    query_id: String,
+    query_id: Option<String>,
     what_subject_filter: String,
     deferred_response_type: String,
 }
@@ -243,7 +267,7 @@ impl QryQ02Builder {
             receiving_app: String::new(),
             receiving_facility: String::new(),
             control_id: None,
-            query_id: generate_control_id(),
+            query_id: None,
             what_subject_filter: String::new(),
             deferred_response_type: "B".to_string(), // Before the date/time specified
         }
@@ -269,8 +293,18 @@ impl QryQ02Builder {
         self
     }
 
+    pub fn control_id_generator(mut self, generator: Arc<dyn ControlIdGenerator>) -> Self {
+        self.base.set_control_id_generator(generator);
+        self
+    }
+
+    pub fn timestamp_precision(mut self, precision: TimestampPrecision) -> Self {
+        self.base.set_timestamp_precision(precision);
+        self
+    }
+
     pub fn query_id(mut self, id: &str) -> Self {
-        self.query_id = id.to_string();
+        self.query_id = Some(id.to_string());
         self
     }
 
@@ -285,7 +319,8 @@ impl QryQ02Builder {
     }
 
     pub fn build(mut self) -> Result<Message> {
-        let control_id = self.control_id.unwrap_or_else(generate_control_id);
+        let control_id = self.control_id.unwrap_or_else(|| self.base.next_control_id());
+        let query_id = self.query_id.take().unwrap_or_else(|| self.base.next_control_id());
 
         let msh = self.base.create_msh(
             &self.sending_app,
@@ -300,11 +335,11 @@ impl QryQ02Builder {
         // QRD segment
         let mut qrd = Segment::new("QRD");
 
-        let timestamp = format_timestamp(&Local::now().naive_local());
+        let timestamp = self.base.timestamp_now();
         qrd.add_field(Field::from_value(&timestamp));
         qrd.add_field(Field::from_value("R")); // Format
         qrd.add_field(Field::from_value("D")); // Priority - Deferred
-        qrd.add_field(Field::from_value(&self.query_id));
+        qrd.add_field(Field::from_value(&query_id));
         qrd.add_field(Field::from_value(&self.deferred_response_type)); // Deferred Response Type
         qrd.add_field(Field::from_value("")); // Deferred Response Date/Time
         qrd.add_field(Field::from_value("")); // Quantity Limited Request
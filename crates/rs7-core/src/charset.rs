@@ -0,0 +1,284 @@
+//! Character sets for HL7 byte encoding
+//!
+//! MSH-18 (HL7 Table 0211) declares the character set a message is sent in.
+//! [`Charset`] maps those codes to byte encodings so that messages can be
+//! written as, and read from, non-UTF-8 bytes.
+//!
+//! Conversions are strict: characters that cannot be represented in the
+//! target charset, and byte sequences that are invalid in the source charset,
+//! are reported as errors instead of being replaced.
+
+use crate::error::{Error, Result};
+
+/// Character sets from HL7 Table 0211
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Charset {
+    /// `ASCII` - 7-bit ASCII
+    Ascii,
+    /// `UNICODE UTF-8` - UTF-8 (the default when MSH-18 is empty)
+    #[default]
+    Utf8,
+    /// `8859/1` - Latin-1, Western European
+    Iso8859_1,
+    /// `8859/2` - Latin-2, Central European
+    Iso8859_2,
+    /// `8859/3` - Latin-3, South European
+    Iso8859_3,
+    /// `8859/4` - Latin-4, North European
+    Iso8859_4,
+    /// `8859/5` - Cyrillic
+    Iso8859_5,
+    /// `8859/6` - Arabic
+    Iso8859_6,
+    /// `8859/7` - Greek
+    Iso8859_7,
+    /// `8859/8` - Hebrew
+    Iso8859_8,
+    /// `8859/9` - Latin-5, Turkish
+    Iso8859_9,
+    /// `8859/15` - Latin-9, Western European with euro sign
+    Iso8859_15,
+    /// `ISO IR14` - JIS X 0201, sent as Shift_JIS single-byte codes
+    IsoIr14,
+    /// `ISO IR87` - JIS X 0208 Kanji, sent as ISO-2022-JP
+    IsoIr87,
+    /// `KS X 1001` - Korean, sent as EUC-KR
+    KsX1001,
+    /// `GB 18030-2000` - Chinese
+    Gb18030,
+    /// `BIG-5` - Traditional Chinese
+    Big5,
+}
+
+impl Charset {
+    /// Look up a charset by its HL7 Table 0211 code (e.g., "8859/1")
+    ///
+    /// Matching ignores case and surrounding whitespace. Returns `None` for
+    /// unknown codes and for codes without a supported byte encoding
+    /// (e.g., UTF-16).
+    pub fn from_hl7(code: &str) -> Option<Self> {
+        let code = code.trim().to_ascii_uppercase();
+        let charset = match code.as_str() {
+            "ASCII" => Charset::Ascii,
+            "UNICODE UTF-8" => Charset::Utf8,
+            "8859/1" => Charset::Iso8859_1,
+            "8859/2" => Charset::Iso8859_2,
+            "8859/3" => Charset::Iso8859_3,
+            "8859/4" => Charset::Iso8859_4,
+            "8859/5" => Charset::Iso8859_5,
+            "8859/6" => Charset::Iso8859_6,
+            "8859/7" => Charset::Iso8859_7,
+            "8859/8" => Charset::Iso8859_8,
+            "8859/9" => Charset::Iso8859_9,
+            "8859/15" => Charset::Iso8859_15,
+            "ISO IR14" => Charset::IsoIr14,
+            "ISO IR87" => Charset::IsoIr87,
+            "KS X 1001" => Charset::KsX1001,
+            "GB 18030-2000" => Charset::Gb18030,
+            "BIG-5" => Charset::Big5,
+            _ => return None,
+        };
+        Some(charset)
+    }
+
+    /// Get the HL7 Table 0211 code for MSH-18
+    pub fn as_hl7(&self) -> &'static str {
+        match self {
+            Charset::Ascii => "ASCII",
+            Charset::Utf8 => "UNICODE UTF-8",
+            Charset::Iso8859_1 => "8859/1",
+            Charset::Iso8859_2 => "8859/2",
+            Charset::Iso8859_3 => "8859/3",
+            Charset::Iso8859_4 => "8859/4",
+            Charset::Iso8859_5 => "8859/5",
+            Charset::Iso8859_6 => "8859/6",
+            Charset::Iso8859_7 => "8859/7",
+            Charset::Iso8859_8 => "8859/8",
+            Charset::Iso8859_9 => "8859/9",
+            Charset::Iso8859_15 => "8859/15",
+            Charset::IsoIr14 => "ISO IR14",
+            Charset::IsoIr87 => "ISO IR87",
+            Charset::KsX1001 => "KS X 1001",
+            Charset::Gb18030 => "GB 18030-2000",
+            Charset::Big5 => "BIG-5",
+        }
+    }
+
+    /// The encoding_rs codec, for charsets not converted by hand
+    fn codec(&self) -> Option<&'static encoding_rs::Encoding> {
+        let codec = match self {
+            Charset::Ascii | Charset::Utf8 | Charset::Iso8859_1 => return None,
+            Charset::Iso8859_2 => encoding_rs::ISO_8859_2,
+            Charset::Iso8859_3 => encoding_rs::ISO_8859_3,
+            Charset::Iso8859_4 => encoding_rs::ISO_8859_4,
+            Charset::Iso8859_5 => encoding_rs::ISO_8859_5,
+            Charset::Iso8859_6 => encoding_rs::ISO_8859_6,
+            Charset::Iso8859_7 => encoding_rs::ISO_8859_7,
+            Charset::Iso8859_8 => encoding_rs::ISO_8859_8,
+            // encoding_rs treats ISO-8859-9 as its windows-1254 superset
+            Charset::Iso8859_9 => encoding_rs::WINDOWS_1254,
+            Charset::Iso8859_15 => encoding_rs::ISO_8859_15,
+            Charset::IsoIr14 => encoding_rs::SHIFT_JIS,
+            Charset::IsoIr87 => encoding_rs::ISO_2022_JP,
+            Charset::KsX1001 => encoding_rs::EUC_KR,
+            Charset::Gb18030 => encoding_rs::GB18030,
+            Charset::Big5 => encoding_rs::BIG5,
+        };
+        Some(codec)
+    }
+
+    /// Encode a string into bytes in this charset
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Encoding`] if the string contains a character that
+    /// this charset cannot represent.
+    pub fn encode(&self, input: &str) -> Result<Vec<u8>> {
+        match self {
+            Charset::Utf8 => Ok(input.as_bytes().to_vec()),
+            Charset::Ascii => input
+                .chars()
+                .map(|ch| if ch.is_ascii() { Ok(ch as u8) } else { Err(self.unmappable(ch)) })
+                .collect(),
+            Charset::Iso8859_1 => input
+                .chars()
+                .map(|ch| u8::try_from(u32::from(ch)).map_err(|_| self.unmappable(ch)))
+                .collect(),
+            _ => {
+                let codec = self.codec().expect("charset has a codec");
+                let (bytes, _, had_errors) = codec.encode(input);
+                if had_errors {
+                    // Report the first character the codec could not map
+                    let ch = input
+                        .chars()
+                        .find(|ch| codec.encode(ch.encode_utf8(&mut [0; 4])).2)
+                        .unwrap_or(char::REPLACEMENT_CHARACTER);
+                    return Err(self.unmappable(ch));
+                }
+                Ok(bytes.into_owned())
+            }
+        }
+    }
+
+    /// Decode bytes in this charset into a string
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Decoding`] if the input is not valid in this charset.
+    pub fn decode(&self, input: &[u8]) -> Result<String> {
+        match self {
+            Charset::Utf8 => String::from_utf8(input.to_vec()).map_err(|e| {
+                self.malformed(e.utf8_error().valid_up_to())
+            }),
+            Charset::Ascii => match input.iter().position(|b| !b.is_ascii()) {
+                Some(offset) => Err(self.malformed(offset)),
+                None => Ok(input.iter().map(|&b| b as char).collect()),
+            },
+            Charset::Iso8859_1 => Ok(input.iter().map(|&b| b as char).collect()),
+            _ => {
+                let codec = self.codec().expect("charset has a codec");
+                codec
+                    .decode_without_bom_handling_and_without_replacement(input)
+                    .map(|s| s.into_owned())
+                    .ok_or_else(|| {
+                        Error::Decoding(format!("Invalid {} byte sequence", self.as_hl7()))
+                    })
+            }
+        }
+    }
+
+    fn unmappable(&self, ch: char) -> Error {
+        Error::Encoding(format!(
+            "Character {:?} (U+{:04X}) cannot be represented in {}",
+            ch,
+            u32::from(ch),
+            self.as_hl7()
+        ))
+    }
+
+    fn malformed(&self, offset: usize) -> Error {
+        Error::Decoding(format!(
+            "Invalid {} byte sequence at offset {}",
+            self.as_hl7(),
+            offset
+        ))
+    }
+}
+
+impl std::fmt::Display for Charset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_hl7())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hl7_codes_round_trip() {
+        for charset in [
+            Charset::Ascii,
+            Charset::Utf8,
+            Charset::Iso8859_1,
+            Charset::Iso8859_9,
+            Charset::Iso8859_15,
+            Charset::IsoIr87,
+            Charset::Gb18030,
+            Charset::Big5,
+        ] {
+            assert_eq!(Charset::from_hl7(charset.as_hl7()), Some(charset));
+        }
+        assert_eq!(Charset::from_hl7(" unicode utf-8 "), Some(Charset::Utf8));
+        assert_eq!(Charset::from_hl7("UNICODE UTF-16"), None);
+        assert_eq!(Charset::from_hl7("EBCDIC"), None);
+    }
+
+    #[test]
+    fn test_latin1() {
+        let bytes = Charset::Iso8859_1.encode("Müller").unwrap();
+        assert_eq!(bytes, b"M\xFCller");
+        assert_eq!(Charset::Iso8859_1.decode(&bytes).unwrap(), "Müller");
+
+        let err = Charset::Iso8859_1.encode("€").unwrap_err();
+        assert!(matches!(err, Error::Encoding(_)));
+    }
+
+    #[test]
+    fn test_ascii_is_strict() {
+        assert_eq!(Charset::Ascii.encode("ABC").unwrap(), b"ABC");
+        assert!(Charset::Ascii.encode("é").is_err());
+        assert!(matches!(
+            Charset::Ascii.decode(b"AB\xE9"),
+            Err(Error::Decoding(_))
+        ));
+    }
+
+    #[test]
+    fn test_utf8_rejects_invalid_bytes() {
+        assert_eq!(Charset::Utf8.decode("ü".as_bytes()).unwrap(), "ü");
+        let err = Charset::Utf8.decode(b"ab\xFF").unwrap_err();
+        assert!(err.to_string().contains("offset 2"));
+    }
+
+    #[test]
+    fn test_codec_charsets() {
+        let bytes = Charset::Iso8859_15.encode("€").unwrap();
+        assert_eq!(bytes, vec![0xA4]);
+        assert_eq!(Charset::Iso8859_15.decode(&bytes).unwrap(), "€");
+
+        let kanji = "山田";
+        let bytes = Charset::IsoIr87.encode(kanji).unwrap();
+        assert!(bytes.starts_with(b"\x1b$B"));
+        assert_eq!(Charset::IsoIr87.decode(&bytes).unwrap(), kanji);
+
+        assert!(matches!(
+            Charset::Iso8859_5.encode("山"),
+            Err(Error::Encoding(_))
+        ));
+        assert!(matches!(
+            Charset::IsoIr87.decode(b"\x1b$B\xFF\xFF"),
+            Err(Error::Decoding(_))
+        ));
+    }
+}
@@ -8,6 +8,8 @@ pub const DEFAULT_COMPONENT_SEPARATOR: char = '^';
 pub const DEFAULT_REPETITION_SEPARATOR: char = '~';
 pub const DEFAULT_ESCAPE_CHARACTER: char = '\\';
 pub const DEFAULT_SUBCOMPONENT_SEPARATOR: char = '&';
+/// Truncation character introduced in HL7 v2.7 (fifth MSH-2 character)
+pub const DEFAULT_TRUNCATION_CHARACTER: char = '#';
 
 /// HL7 message delimiters and encoding characters
 ///
@@ -17,6 +19,7 @@ pub const DEFAULT_SUBCOMPONENT_SEPARATOR: char = '&';
 /// - Repetition separator: `~` (separates repeated fields)
 /// - Escape character: `\` (used for escape sequences)
 /// - Subcomponent separator: `&` (separates subcomponents within a component)
+/// - Truncation character: `#` (v2.7+, optional fifth MSH-2 character)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Delimiters {
     pub field_separator: char,
@@ -24,6 +27,7 @@ pub struct Delimiters {
     pub repetition_separator: char,
     pub escape_character: char,
     pub subcomponent_separator: char,
+    pub truncation_character: Option<char>,
 }
 
 impl Default for Delimiters {
@@ -34,6 +38,7 @@ impl Default for Delimiters {
             repetition_separator: DEFAULT_REPETITION_SEPARATOR,
             escape_character: DEFAULT_ESCAPE_CHARACTER,
             subcomponent_separator: DEFAULT_SUBCOMPONENT_SEPARATOR,
+            truncation_character: None,
         }
     }
 }
@@ -44,6 +49,12 @@ impl Delimiters {
         Self::default()
     }
 
+    /// Set the v2.7 truncation character
+    pub fn with_truncation_character(mut self, truncation_character: char) -> Self {
+        self.truncation_character = Some(truncation_character);
+        self
+    }
+
     /// Create delimiters from MSH-2 encoding characters
     ///
     /// MSH-2 contains 4 characters in order:
@@ -52,17 +63,17 @@ impl Delimiters {
     /// 3. Escape character (\)
     /// 4. Subcomponent separator (&)
     ///
-    /// The field separator is always from MSH-1 (typically |)
+    /// From HL7 v2.7 a fifth character, the truncation character (#), may
+    /// follow. The field separator is always from MSH-1 (typically |)
     pub fn from_encoding_characters(field_sep: char, encoding_chars: &str) -> Result<Self> {
-        if encoding_chars.len() != 4 {
+        let chars: Vec<char> = encoding_chars.chars().collect();
+        if chars.len() != 4 && chars.len() != 5 {
             return Err(Error::InvalidDelimiters(format!(
-                "Encoding characters must be exactly 4 characters, got {}",
-                encoding_chars.len()
+                "Encoding characters must be 4 or 5 characters, got {}",
+                chars.len()
             )));
         }
 
-        let chars: Vec<char> = encoding_chars.chars().collect();
-
         // Validate that all delimiters are unique
         let delims = Self {
             field_separator: field_sep,
@@ -70,6 +81,7 @@ impl Delimiters {
             repetition_separator: chars[1],
             escape_character: chars[2],
             subcomponent_separator: chars[3],
+            truncation_character: chars.get(4).copied(),
         };
 
         delims.validate()?;
@@ -77,32 +89,50 @@ impl Delimiters {
     }
 
     /// Get encoding characters as a string (MSH-2 format)
+    ///
+    /// The truncation character is appended only when it is set.
     pub fn encoding_characters(&self) -> String {
-        format!(
+        let mut chars = format!(
             "{}{}{}{}",
             self.component_separator,
             self.repetition_separator,
             self.escape_character,
             self.subcomponent_separator
-        )
+        );
+        chars.extend(self.truncation_character);
+        chars
     }
 
-    /// Validate that all delimiters are unique
+    /// Validate the delimiters
+    ///
+    /// Every delimiter must be distinct, and none may be a control character
+    /// such as CR or LF, which would clash with segment terminators.
     pub fn validate(&self) -> Result<()> {
-        let chars = [self.field_separator,
-            self.component_separator,
-            self.repetition_separator,
-            self.escape_character,
-            self.subcomponent_separator];
-
-        for (i, &c1) in chars.iter().enumerate() {
-            for (j, &c2) in chars.iter().enumerate() {
-                if i != j && c1 == c2 {
-                    return Err(Error::InvalidDelimiters(format!(
-                        "Duplicate delimiter character: '{}'",
-                        c1
-                    )));
-                }
+        let mut roles = vec![
+            ("field separator", self.field_separator),
+            ("component separator", self.component_separator),
+            ("repetition separator", self.repetition_separator),
+            ("escape character", self.escape_character),
+            ("subcomponent separator", self.subcomponent_separator),
+        ];
+        if let Some(truncation_character) = self.truncation_character {
+            roles.push(("truncation character", truncation_character));
+        }
+
+        for (i, &(role, c)) in roles.iter().enumerate() {
+            if c.is_control() {
+                return Err(Error::InvalidDelimiters(format!(
+                    "The {} cannot be a control character: '{}'",
+                    role,
+                    c.escape_debug()
+                )));
+            }
+
+            if let Some(&(other_role, _)) = roles[..i].iter().find(|&&(_, other)| other == c) {
+                return Err(Error::InvalidDelimiters(format!(
+                    "Duplicate delimiter character '{}' used as both {} and {}",
+                    c, other_role, role
+                )));
             }
         }
 
@@ -157,6 +187,43 @@ mod tests {
     fn test_invalid_length() {
         let result = Delimiters::from_encoding_characters('|', "^~\\");
         assert!(result.is_err());
+
+        let result = Delimiters::from_encoding_characters('|', "^~\\&#!");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_duplicate_and_control_delimiters() {
+        let err = Delimiters::from_encoding_characters('|', "^^\\&").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid delimiter configuration: Duplicate delimiter character '^' used as both component separator and repetition separator"
+        );
+
+        assert!(Delimiters::from_encoding_characters('^', "^~\\&").is_err());
+
+        let err = Delimiters::from_encoding_characters('|', "^~\\\r").unwrap_err();
+        assert!(err.to_string().contains("subcomponent separator cannot be a control character: '\\r'"));
+        assert!(Delimiters::from_encoding_characters('\n', "^~\\&").is_err());
+        assert!(Delimiters::from_encoding_characters('|', "^~\\&\t").is_err());
+    }
+
+    #[test]
+    fn test_truncation_character() {
+        let delims = Delimiters::from_encoding_characters('|', "^~\\&").unwrap();
+        assert_eq!(delims.truncation_character, None);
+        assert_eq!(delims.encoding_characters(), "^~\\&");
+
+        let delims = Delimiters::from_encoding_characters('|', "^~\\&#").unwrap();
+        assert_eq!(delims.truncation_character, Some('#'));
+        assert_eq!(delims.encoding_characters(), "^~\\&#");
+        assert_eq!(
+            Delimiters::default().with_truncation_character(DEFAULT_TRUNCATION_CHARACTER),
+            delims
+        );
+
+        // The truncation character must differ from the other delimiters
+        assert!(Delimiters::from_encoding_characters('|', "^~\\&^").is_err());
     }
 
     #[test]
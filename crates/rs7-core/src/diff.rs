@@ -0,0 +1,320 @@
+//! Structural comparison of HL7 messages
+//!
+//! See [`Message::diff`](crate::Message::diff).
+
+use crate::field::{field_leaves, LeafKey};
+use crate::message::Message;
+use crate::segment::Segment;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+
+/// Kind of difference reported by a [`FieldDiff`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    /// The value is only present in the new message
+    Added,
+    /// The value is only present in the old message
+    Removed,
+    /// The value is present in both messages but differs
+    Changed,
+}
+
+/// A single difference between two messages
+///
+/// `path` uses Terser syntax (`PID-5-1`, `OBX(2)-5`, `PID-3(1)-1`); empty
+/// values are treated as absent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    /// Terser-style location of the value
+    pub path: String,
+    /// Value in the old message
+    pub old_value: Option<String>,
+    /// Value in the new message
+    pub new_value: Option<String>,
+}
+
+impl FieldDiff {
+    /// Get the kind of this difference
+    pub fn kind(&self) -> DiffKind {
+        match (&self.old_value, &self.new_value) {
+            (None, _) => DiffKind::Added,
+            (_, None) => DiffKind::Removed,
+            _ => DiffKind::Changed,
+        }
+    }
+}
+
+impl fmt::Display for FieldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.old_value, &self.new_value) {
+            (None, Some(new)) => write!(f, "+ {}: {}", self.path, new),
+            (Some(old), None) => write!(f, "- {}: {}", self.path, old),
+            (Some(old), Some(new)) => write!(f, "~ {}: {} -> {}", self.path, old, new),
+            (None, None) => write!(f, "  {}", self.path),
+        }
+    }
+}
+
+/// Compare two messages segment by segment
+///
+/// Segments are paired by ID and occurrence (the second OBX of `old` with the
+/// second OBX of `new`). Differences are sorted by path: segment ID, then
+/// occurrence, field, repetition, component and subcomponent.
+pub(crate) fn diff_messages(old: &Message, new: &Message) -> Vec<FieldDiff> {
+    let empty = Segment::new("");
+    let old_segments = index_segments(old);
+    let new_segments = index_segments(new);
+    let keys: BTreeSet<&SegmentKey> = old_segments.keys().chain(new_segments.keys()).collect();
+
+    let mut diffs = Vec::new();
+    for key in keys {
+        let old_segment = old_segments.get(key).copied().unwrap_or(&empty);
+        let new_segment = new_segments.get(key).copied().unwrap_or(&empty);
+        diff_segments(key, old_segment, new_segment, &mut diffs);
+    }
+
+    diffs.sort_by(|(a, _), (b, _)| a.cmp(b));
+    diffs.into_iter().map(|(_, diff)| diff).collect()
+}
+
+/// Segment ID and 1-based occurrence
+type SegmentKey = (String, usize);
+
+/// Sort position of a difference: segment, 1-based field and leaf position
+type DiffKey = (SegmentKey, usize, LeafKey);
+
+/// Segments keyed by ID and 1-based occurrence
+fn index_segments(message: &Message) -> BTreeMap<SegmentKey, &Segment> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    message
+        .segments
+        .iter()
+        .map(|segment| {
+            let count = counts.entry(segment.id.as_str()).or_insert(0);
+            *count += 1;
+            ((segment.id.clone(), *count), segment)
+        })
+        .collect()
+}
+
+fn segment_path((id, occurrence): &SegmentKey) -> String {
+    if *occurrence > 1 {
+        format!("{}({})", id, occurrence)
+    } else {
+        id.clone()
+    }
+}
+
+fn diff_segments<'a>(
+    key: &SegmentKey,
+    old: &'a Segment,
+    new: &'a Segment,
+    diffs: &mut Vec<(DiffKey, FieldDiff)>,
+) {
+    let path = segment_path(key);
+    let field_count = old.fields.len().max(new.fields.len());
+    for index in 1..=field_count {
+        let leaves = |segment: &'a Segment| -> BTreeMap<LeafKey, &'a str> {
+            segment.get_field(index).map(|field| field_leaves(field).collect()).unwrap_or_default()
+        };
+        let (old_leaves, new_leaves) = (leaves(old), leaves(new));
+        let field_path = format!("{}-{}", path, index);
+        for (leaf, diff) in diff_fields(&field_path, &old_leaves, &new_leaves) {
+            diffs.push(((key.clone(), index, leaf), diff));
+        }
+    }
+}
+
+fn diff_fields(
+    path: &str,
+    old_leaves: &BTreeMap<LeafKey, &str>,
+    new_leaves: &BTreeMap<LeafKey, &str>,
+) -> Vec<(LeafKey, FieldDiff)> {
+    let mut diffs = Vec::new();
+    if old_leaves == new_leaves {
+        return diffs;
+    }
+
+    let is_simple = |leaves: &BTreeMap<LeafKey, &str>| leaves.keys().all(|key| *key == (0, 0, 0));
+    if is_simple(old_leaves) && is_simple(new_leaves) {
+        push_diff(&mut diffs, path.to_string(), old_leaves, new_leaves, (0, 0, 0));
+        return diffs;
+    }
+
+    // Descend to subcomponents only when either side actually uses them
+    let with_subcomponents = old_leaves
+        .keys()
+        .chain(new_leaves.keys())
+        .any(|(_, _, sub)| *sub > 0);
+
+    let keys: BTreeSet<LeafKey> =
+        old_leaves.keys().chain(new_leaves.keys()).copied().collect();
+    for key in keys {
+        let (rep, comp, sub) = key;
+        let mut leaf_path = path.to_string();
+        if rep > 0 {
+            leaf_path.push_str(&format!("({})", rep));
+        }
+        leaf_path.push_str(&format!("-{}", comp + 1));
+        if with_subcomponents {
+            leaf_path.push_str(&format!("-{}", sub + 1));
+        }
+        push_diff(&mut diffs, leaf_path, old_leaves, new_leaves, key);
+    }
+    diffs
+}
+
+fn push_diff(
+    diffs: &mut Vec<(LeafKey, FieldDiff)>,
+    path: String,
+    old: &BTreeMap<LeafKey, &str>,
+    new: &BTreeMap<LeafKey, &str>,
+    key: LeafKey,
+) {
+    let old_value = old.get(&key).map(|v| v.to_string());
+    let new_value = new.get(&key).map(|v| v.to_string());
+    if old_value != new_value {
+        diffs.push((
+            key,
+            FieldDiff {
+                path,
+                old_value,
+                new_value,
+            },
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::{Component, Field, Repetition, SubComponent};
+
+    fn segment(id: &str, values: &[&str]) -> Segment {
+        let mut segment = Segment::new(id);
+        for value in values {
+            segment.add_field(Field::from_value(*value));
+        }
+        segment
+    }
+
+    fn message(segments: Vec<Segment>) -> Message {
+        let mut message = Message::new();
+        for segment in segments {
+            message.add_segment(segment);
+        }
+        message
+    }
+
+    fn components(values: &[&str]) -> Field {
+        let mut rep = Repetition::new();
+        for value in values {
+            rep.add_component(Component::from_value(*value));
+        }
+        let mut field = Field::new();
+        field.add_repetition(rep);
+        field
+    }
+
+    #[test]
+    fn test_identical_messages() {
+        let msg = message(vec![segment("PID", &["1", "", "12345"])]);
+        assert!(diff_messages(&msg, &msg.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_changed_added_removed_fields() {
+        let old = message(vec![segment("PID", &["1", "", "12345", "", "DOE"])]);
+        let new = message(vec![segment("PID", &["1", "X", "54321"])]);
+
+        let diffs = diff_messages(&old, &new);
+        assert_eq!(diffs.len(), 3);
+
+        assert_eq!(diffs[0].path, "PID-2");
+        assert_eq!(diffs[0].kind(), DiffKind::Added);
+        assert_eq!(diffs[1].path, "PID-3");
+        assert_eq!(diffs[1].kind(), DiffKind::Changed);
+        assert_eq!(diffs[1].old_value.as_deref(), Some("12345"));
+        assert_eq!(diffs[1].new_value.as_deref(), Some("54321"));
+        assert_eq!(diffs[2].path, "PID-5");
+        assert_eq!(diffs[2].kind(), DiffKind::Removed);
+    }
+
+    #[test]
+    fn test_component_and_subcomponent_paths() {
+        let mut old_pid = Segment::new("PID");
+        old_pid.set_field(5, components(&["DOE", "JOHN"])).unwrap();
+        let mut new_pid = Segment::new("PID");
+        new_pid.set_field(5, components(&["DOE", "JANE", "Q"])).unwrap();
+
+        let diffs = diff_messages(&message(vec![old_pid]), &message(vec![new_pid]));
+        let paths: Vec<_> = diffs.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(paths, vec!["PID-5-2", "PID-5-3"]);
+
+        let mut comp = Component::new();
+        comp.add_subcomponent(SubComponent::new("A"));
+        comp.add_subcomponent(SubComponent::new("B"));
+        let mut rep = Repetition::new();
+        rep.add_component(comp);
+        let mut field = Field::new();
+        field.add_repetition(rep);
+        let mut new_seg = Segment::new("ZZZ");
+        new_seg.set_field(1, field).unwrap();
+
+        let diffs = diff_messages(
+            &message(vec![segment("ZZZ", &["A"])]),
+            &message(vec![new_seg]),
+        );
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].path, "ZZZ-1-1-2");
+        assert_eq!(diffs[0].new_value.as_deref(), Some("B"));
+    }
+
+    #[test]
+    fn test_repetition_paths() {
+        let mut old_field = Field::new();
+        old_field.add_repetition(Repetition::from_value("A"));
+        let mut new_field = old_field.clone();
+        new_field.add_repetition(Repetition::from_value("B"));
+
+        let mut old_pid = Segment::new("PID");
+        old_pid.set_field(3, old_field).unwrap();
+        let mut new_pid = Segment::new("PID");
+        new_pid.set_field(3, new_field).unwrap();
+
+        let diffs = diff_messages(&message(vec![old_pid]), &message(vec![new_pid]));
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].path, "PID-3(1)-1");
+    }
+
+    #[test]
+    fn test_segments_aligned_by_occurrence() {
+        let old = message(vec![
+            segment("OBX", &["1", "NM", "A"]),
+            segment("OBX", &["2", "NM", "B"]),
+        ]);
+        let new = message(vec![
+            segment("OBX", &["1", "NM", "A"]),
+            segment("OBX", &["2", "NM", "C"]),
+            segment("OBX", &["3", "NM", "D"]),
+            segment("NTE", &["1"]),
+        ]);
+
+        let diffs = diff_messages(&old, &new);
+        let rendered: Vec<_> = diffs.iter().map(|d| d.to_string()).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "+ NTE-1: 1",
+                "~ OBX(2)-3: B -> C",
+                "+ OBX(3)-1: 3",
+                "+ OBX(3)-2: NM",
+                "+ OBX(3)-3: D",
+            ]
+        );
+
+        let diffs = diff_messages(&new, &old);
+        assert_eq!(diffs.iter().filter(|d| d.kind() == DiffKind::Changed).count(), 1);
+        assert_eq!(diffs.iter().filter(|d| d.kind() == DiffKind::Removed).count(), 4);
+    }
+}
@@ -11,12 +11,25 @@ use crate::error::{Error, Result};
 /// - `\T\` - Subcomponent separator
 /// - `\R\` - Repetition separator
 /// - `\E\` - Escape character
+/// - `\P\` - Truncation character (v2.7+, when the delimiters define one)
 /// - `\Xnn\` - Hexadecimal character (e.g., \X0D\ for carriage return)
 /// - `\Znn...nn\` - Locally defined escape sequence
 /// - `\.br\` - Line break (formatting)
 /// - `\H\` and `\N\` - Highlight on/off (formatting)
 pub struct Encoding;
 
+/// Handling of escape sequences that are not decoded
+///
+/// Used by [`Encoding::decode_with`] when the handler returns `None`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnknownEscape {
+    /// Keep the sequence literally, including its escape characters
+    #[default]
+    Preserve,
+    /// Fail with a decoding error
+    Error,
+}
+
 impl Encoding {
     /// Encode a string by replacing special characters with escape sequences
     pub fn encode(input: &str, delimiters: &Delimiters) -> String {
@@ -43,13 +56,14 @@ impl Encoding {
                 result.push(delimiters.escape_character);
                 result.push('R');
                 result.push(delimiters.escape_character);
-            } else if ch == '\r' {
+            } else if Some(ch) == delimiters.truncation_character {
                 result.push(delimiters.escape_character);
-                result.push_str("X0D");
+                result.push('P');
                 result.push(delimiters.escape_character);
-            } else if ch == '\n' {
+            } else if ch.is_ascii_control() {
+                // Control characters (CR, LF, tab, ...) as hex data
                 result.push(delimiters.escape_character);
-                result.push_str("X0A");
+                result.push_str(&format!("X{:02X}", ch as u32));
                 result.push(delimiters.escape_character);
             } else {
                 result.push(ch);
@@ -60,7 +74,64 @@ impl Encoding {
     }
 
     /// Decode a string by replacing escape sequences with their actual characters
+    ///
+    /// Locally defined `\Z..\` sequences are kept literally; other unknown
+    /// sequences are an error.
     pub fn decode(input: &str, delimiters: &Delimiters) -> Result<String> {
+        Self::decode_sequences(input, delimiters, |seq, result| {
+            if seq.starts_with('Z') {
+                // Locally defined escape - preserve as-is
+                push_literal(result, seq, delimiters);
+                Ok(())
+            } else {
+                Err(unknown_sequence(seq))
+            }
+        })
+    }
+
+    /// Decode a string, passing unrecognized escape sequences to `handler`
+    ///
+    /// `handler` receives the text between the escape characters, e.g.
+    /// `Zbold` for `\Zbold\`, for locally defined `\Z..\` sequences and
+    /// any other sequence the standard decoding does not cover. Its result
+    /// replaces the sequence; when it returns `None`, `unknown` decides
+    /// whether the sequence is kept literally or is an error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rs7_core::{delimiters::Delimiters, encoding::{Encoding, UnknownEscape}};
+    ///
+    /// let delimiters = Delimiters::default();
+    /// let text = Encoding::decode_with("\\Zbold\\Warning\\Zend\\", &delimiters, UnknownEscape::Error, |seq| {
+    ///     match seq {
+    ///         "Zbold" => Some("**".to_string()),
+    ///         "Zend" => Some("**".to_string()),
+    ///         _ => None,
+    ///     }
+    /// })
+    /// .unwrap();
+    /// assert_eq!(text, "**Warning**");
+    /// ```
+    pub fn decode_with<F>(input: &str, delimiters: &Delimiters, unknown: UnknownEscape, handler: F) -> Result<String>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        Self::decode_sequences(input, delimiters, |seq, result| {
+            match (handler(seq), unknown) {
+                (Some(text), _) => result.push_str(&text),
+                (None, UnknownEscape::Preserve) => push_literal(result, seq, delimiters),
+                (None, UnknownEscape::Error) => return Err(unknown_sequence(seq)),
+            }
+            Ok(())
+        })
+    }
+
+    /// Decode the standard escape sequences, passing others to `unknown`
+    fn decode_sequences<F>(input: &str, delimiters: &Delimiters, unknown: F) -> Result<String>
+    where
+        F: Fn(&str, &mut String) -> Result<()>,
+    {
         let mut result = String::with_capacity(input.len());
         let mut chars = input.chars().peekable();
 
@@ -85,40 +156,21 @@ impl Encoding {
                     "T" => result.push(delimiters.subcomponent_separator),
                     "R" => result.push(delimiters.repetition_separator),
                     "E" => result.push(delimiters.escape_character),
+                    "P" if delimiters.truncation_character.is_some() => {
+                        result.extend(delimiters.truncation_character)
+                    }
+                    // Some senders escape the truncation character literally, e.g. \#\
+                    seq if delimiters.truncation_character.is_some_and(|t| seq == t.to_string()) => {
+                        result.extend(delimiters.truncation_character)
+                    }
                     ".br" => result.push('\n'),
                     "H" => {}, // Highlight on - formatting, ignored
                     "N" => {}, // Highlight off - formatting, ignored
                     seq if seq.starts_with('X') => {
-                        // Hexadecimal character
-                        let hex = &seq[1..];
-                        if let Ok(code) = u32::from_str_radix(hex, 16) {
-                            if let Some(decoded_ch) = char::from_u32(code) {
-                                result.push(decoded_ch);
-                            } else {
-                                return Err(Error::Decoding(format!(
-                                    "Invalid hexadecimal escape sequence: \\{}\\",
-                                    seq
-                                )));
-                            }
-                        } else {
-                            return Err(Error::Decoding(format!(
-                                "Invalid hexadecimal escape sequence: \\{}\\",
-                                seq
-                            )));
-                        }
-                    }
-                    seq if seq.starts_with('Z') => {
-                        // Locally defined escape - preserve as-is
-                        result.push(delimiters.escape_character);
-                        result.push_str(seq);
-                        result.push(delimiters.escape_character);
-                    }
-                    _ => {
-                        return Err(Error::Decoding(format!(
-                            "Unknown escape sequence: \\{}\\",
-                            escape_seq
-                        )));
+                        // Hexadecimal data, one or more byte pairs
+                        result.push_str(&Self::decode_hex(&seq[1..])?);
                     }
+                    seq => unknown(seq, &mut result)?,
                 }
             } else {
                 result.push(ch);
@@ -127,6 +179,50 @@ impl Encoding {
 
         Ok(result)
     }
+
+    /// Decode the digits of a `\Xdd..\` escape into text
+    ///
+    /// Each pair of hex digits is one byte. The bytes are read as UTF-8 when
+    /// valid, otherwise each byte is mapped to the character with the same
+    /// code point (ISO 8859-1).
+    fn decode_hex(hex: &str) -> Result<String> {
+        if hex.is_empty() || !hex.len().is_multiple_of(2) {
+            return Err(Error::Decoding(format!(
+                "Hexadecimal escape sequence must have an even number of digits: \\X{}\\",
+                hex
+            )));
+        }
+
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .map(|i| {
+                hex.get(i..i + 2)
+                    .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                    .ok_or_else(|| {
+                        Error::Decoding(format!(
+                            "Invalid hexadecimal escape sequence: \\X{}\\",
+                            hex
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<u8>>>()?;
+
+        Ok(match String::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(e) => e.into_bytes().into_iter().map(char::from).collect(),
+        })
+    }
+}
+
+/// Append an escape sequence as-is, including its escape characters
+fn push_literal(result: &mut String, seq: &str, delimiters: &Delimiters) {
+    result.push(delimiters.escape_character);
+    result.push_str(seq);
+    result.push(delimiters.escape_character);
+}
+
+fn unknown_sequence(seq: &str) -> Error {
+    Error::Decoding(format!("Unknown escape sequence: \\{}\\", seq))
 }
 
 #[cfg(test)]
@@ -170,6 +266,18 @@ mod tests {
         assert_eq!(Encoding::decode("\\E\\", &delims).unwrap(), "\\");
     }
 
+    #[test]
+    fn test_truncation_character_escapes() {
+        let delims = Delimiters::default();
+        assert_eq!(Encoding::encode("A#B", &delims), "A#B");
+        assert!(Encoding::decode("\\P\\", &delims).is_err());
+
+        let delims = Delimiters::default().with_truncation_character('#');
+        assert_eq!(Encoding::encode("A#B", &delims), "A\\P\\B");
+        assert_eq!(Encoding::decode("A\\P\\B", &delims).unwrap(), "A#B");
+        assert_eq!(Encoding::decode("A\\#\\B", &delims).unwrap(), "A#B");
+    }
+
     #[test]
     fn test_decode_hexadecimal() {
         let delims = Delimiters::default();
@@ -178,6 +286,34 @@ mod tests {
         assert_eq!(Encoding::decode("\\X20\\", &delims).unwrap(), " ");
     }
 
+    #[test]
+    fn test_decode_hexadecimal_multiple_bytes() {
+        let delims = Delimiters::default();
+        assert_eq!(Encoding::decode("A\\X0D0A\\B", &delims).unwrap(), "A\r\nB");
+        assert_eq!(Encoding::decode("\\X09\\", &delims).unwrap(), "\t");
+        assert_eq!(Encoding::decode("\\XC3A9\\", &delims).unwrap(), "\u{e9}");
+        // Not valid UTF-8, read as ISO 8859-1
+        assert_eq!(Encoding::decode("\\XE9\\", &delims).unwrap(), "\u{e9}");
+    }
+
+    #[test]
+    fn test_decode_hexadecimal_errors() {
+        let delims = Delimiters::default();
+        assert!(Encoding::decode("\\X0\\", &delims).is_err());
+        assert!(Encoding::decode("\\X0D0\\", &delims).is_err());
+        assert!(Encoding::decode("\\X0G\\", &delims).is_err());
+        assert!(Encoding::decode("\\X\\", &delims).is_err());
+    }
+
+    #[test]
+    fn test_control_character_roundtrip() {
+        let delims = Delimiters::default();
+        let original = "Line1\r\nCol1\tCol2";
+        let encoded = Encoding::encode(original, &delims);
+        assert_eq!(encoded, "Line1\\X0D\\\\X0A\\Col1\\X09\\Col2");
+        assert_eq!(Encoding::decode(&encoded, &delims).unwrap(), original);
+    }
+
     #[test]
     fn test_decode_line_break() {
         let delims = Delimiters::default();
@@ -213,4 +349,24 @@ mod tests {
         let result = Encoding::decode("\\XZZ\\", &delims);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_decode_with_handler() {
+        let delims = Delimiters::default();
+        let handler = |seq: &str| (seq == "Zbold").then(String::new);
+
+        assert_eq!(
+            Encoding::decode_with("\\Zbold\\A\\T\\B", &delims, UnknownEscape::Error, handler).unwrap(),
+            "A&B"
+        );
+        assert_eq!(
+            Encoding::decode_with("\\Zitalic\\x\\Q\\", &delims, UnknownEscape::Preserve, handler).unwrap(),
+            "\\Zitalic\\x\\Q\\"
+        );
+        assert!(Encoding::decode_with("\\Zitalic\\", &delims, UnknownEscape::Error, handler).is_err());
+
+        // Plain decode keeps Z sequences and rejects other unknown sequences
+        assert_eq!(Encoding::decode("\\Zbold\\", &delims).unwrap(), "\\Zbold\\");
+        assert!(Encoding::decode("\\Q\\", &delims).is_err());
+    }
 }
@@ -80,7 +80,7 @@ pub enum Error {
 pub struct ErrorLocation {
     /// Segment ID (e.g., "PID", "OBX")
     pub segment: Option<String>,
-    /// 1-based segment index in the message
+    /// 0-based position of the segment in the message
     pub segment_index: Option<usize>,
     /// 1-based field number
     pub field: Option<usize>,
@@ -94,6 +94,8 @@ pub struct ErrorLocation {
     pub line: Option<usize>,
     /// Column number in the source (if applicable)
     pub column: Option<usize>,
+    /// 0-based byte offset in the source (if applicable)
+    pub byte_offset: Option<usize>,
     /// The actual value that caused the error
     pub value: Option<String>,
 }
@@ -110,7 +112,7 @@ impl ErrorLocation {
         self
     }
 
-    /// Set the segment index
+    /// Set the 0-based segment position
     pub fn segment_index(mut self, idx: usize) -> Self {
         self.segment_index = Some(idx);
         self
@@ -160,14 +162,15 @@ impl ErrorLocation {
 
     /// Generate a path-like string for this location
     ///
-    /// Returns something like "PID-5-1" or "OBX(2)-5-1"
+    /// Returns something like "PID-5-1" or "OBX(2)-5-1"; the segment
+    /// position is shown 1-based.
     pub fn to_path(&self) -> String {
         let mut path = String::new();
 
         if let Some(ref seg) = self.segment {
             path.push_str(seg);
             if let Some(idx) = self.segment_index {
-                path.push_str(&format!("({})", idx));
+                path.push_str(&format!("({})", idx + 1));
             }
         }
 
@@ -201,12 +204,17 @@ impl std::fmt::Display for ErrorLocation {
         let path = self.to_path();
 
         if let (Some(line), Some(col)) = (self.line, self.column) {
-            write!(f, "{} (line {}, column {})", path, line, col)
+            write!(f, "{} (line {}, column {})", path, line, col)?;
         } else if let Some(line) = self.line {
-            write!(f, "{} (line {})", path, line)
+            write!(f, "{} (line {})", path, line)?;
         } else {
-            write!(f, "{}", path)
+            write!(f, "{}", path)?;
         }
+
+        if let Some(offset) = self.byte_offset {
+            write!(f, " at offset {}", offset)?;
+        }
+        Ok(())
     }
 }
 
@@ -326,7 +334,7 @@ impl ErrorBuilder {
         self
     }
 
-    /// Set the segment index
+    /// Set the 0-based segment position
     pub fn segment_index(mut self, idx: usize) -> Self {
         self.location.segment_index = Some(idx);
         self
@@ -399,7 +407,7 @@ mod tests {
     fn test_error_location_with_index() {
         let loc = ErrorLocation::new()
             .segment("OBX")
-            .segment_index(2)
+            .segment_index(1)
             .field(5);
 
         assert_eq!(loc.to_path(), "OBX(2)-5");
@@ -431,6 +439,17 @@ mod tests {
         assert_eq!(err.location().unwrap().segment, Some("PID".to_string()));
     }
 
+    #[test]
+    fn test_error_location_with_byte_offset() {
+        let loc = ErrorLocation {
+            byte_offset: Some(3),
+            ..ErrorLocation::new().segment("MSH").segment_index(0)
+        };
+
+        assert_eq!(loc.byte_offset, Some(3));
+        assert_eq!(format!("{}", loc), "MSH(1) at offset 3");
+    }
+
     #[test]
     fn test_error_builder() {
         let err = ErrorBuilder::new()
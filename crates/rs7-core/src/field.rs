@@ -8,7 +8,7 @@ use crate::error::Result;
 ///
 /// This is the smallest unit in HL7 message hierarchy.
 /// Example: In "Smith&John", "Smith" and "John" are subcomponents
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SubComponent {
     pub value: String,
 }
@@ -60,7 +60,7 @@ impl From<&str> for SubComponent {
 ///
 /// Components can contain subcomponents separated by the subcomponent separator (&).
 /// Example: In "Smith&John^MD", "Smith&John" is a component with two subcomponents
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Component {
     pub subcomponents: Vec<SubComponent>,
 }
@@ -125,7 +125,7 @@ impl Default for Component {
 ///
 /// Fields can repeat, with each repetition containing components.
 /// Example: In "Value1~Value2~Value3", there are 3 repetitions
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Repetition {
     pub components: Vec<Component>,
 }
@@ -190,9 +190,18 @@ impl Default for Repetition {
 ///
 /// Fields can contain multiple repetitions, each with components and subcomponents.
 /// The hierarchy is: Field -> Repetitions -> Components -> Subcomponents
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Eq)]
 pub struct Field {
     pub repetitions: Vec<Repetition>,
+    /// Field text as received, with a fingerprint of the content parsed from it
+    raw_text: Option<(String, u64)>,
+}
+
+impl PartialEq for Field {
+    /// Fields compare by parsed content; the raw text is ignored
+    fn eq(&self, other: &Self) -> bool {
+        self.repetitions == other.repetitions
+    }
 }
 
 impl Field {
@@ -200,6 +209,7 @@ impl Field {
     pub fn new() -> Self {
         Self {
             repetitions: Vec::new(),
+            raw_text: None,
         }
     }
 
@@ -207,9 +217,33 @@ impl Field {
     pub fn from_value<S: Into<String>>(value: S) -> Self {
         Self {
             repetitions: vec![Repetition::from_value(value)],
+            raw_text: None,
         }
     }
 
+    /// Attach the field text as received
+    ///
+    /// Call this once the repetitions parsed from `raw` are in place: the raw
+    /// text is only reported while the field still holds that content.
+    pub fn with_raw(mut self, raw: impl Into<String>) -> Self {
+        self.raw_text = Some((raw.into(), content_fingerprint(&self.repetitions)));
+        self
+    }
+
+    /// Get the field text as received, with all delimiters and escapes intact
+    ///
+    /// This is an escape hatch for senders that do not follow the
+    /// field → repetition → component hierarchy (e.g. `~` inside a
+    /// component): re-split the raw text yourself. Returns `None` unless the
+    /// message was parsed with raw fields preserved, and once the field has
+    /// been edited by any means, so stale text is never returned.
+    pub fn raw(&self) -> Option<&str> {
+        self.raw_text
+            .as_ref()
+            .filter(|(_, fingerprint)| *fingerprint == content_fingerprint(&self.repetitions))
+            .map(|(raw, _)| raw.as_str())
+    }
+
     /// Add a repetition
     pub fn add_repetition(&mut self, repetition: Repetition) {
         self.repetitions.push(repetition);
@@ -268,6 +302,31 @@ impl Default for Field {
     }
 }
 
+/// Position of a value within a field: (repetition, component, subcomponent), 0-based
+pub(crate) type LeafKey = (usize, usize, usize);
+
+/// Non-empty values of a field with their positions, in document order
+pub(crate) fn field_leaves(field: &Field) -> impl Iterator<Item = (LeafKey, &str)> {
+    field.repetitions.iter().enumerate().flat_map(|(rep_index, rep)| {
+        rep.components.iter().enumerate().flat_map(move |(comp_index, comp)| {
+            comp.subcomponents
+                .iter()
+                .enumerate()
+                .filter(|(_, sub)| !sub.as_str().is_empty())
+                .map(move |(sub_index, sub)| ((rep_index, comp_index, sub_index), sub.as_str()))
+        })
+    })
+}
+
+/// Hash of a field's parsed content, used to detect edits after parsing
+fn content_fingerprint(repetitions: &[Repetition]) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    repetitions.hash(&mut hasher);
+    hasher.finish()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -312,6 +371,24 @@ mod tests {
         assert_eq!(field.value(), Some("Rep1"));
     }
 
+    #[test]
+    fn test_raw_text_dropped_after_edit() {
+        let field = Field::from_value("A").with_raw("A");
+        assert_eq!(field.raw(), Some("A"));
+
+        let mut added = field.clone();
+        added.add_repetition(Repetition::from_value("B"));
+        assert_eq!(added.raw(), None);
+
+        let mut edited = field.clone();
+        edited.repetitions[0].components[0].subcomponents[0].value = "Z".to_string();
+        assert_eq!(edited.raw(), None);
+
+        // Restoring the parsed content makes the raw text valid again
+        edited.repetitions[0].components[0].subcomponents[0].value = "A".to_string();
+        assert_eq!(edited.raw(), Some("A"));
+    }
+
     #[test]
     fn test_field_hierarchy() {
         let mut field = Field::new();
@@ -10,21 +10,28 @@
 
 pub mod batch;
 pub mod builders;
+pub mod charset;
 pub mod delimiters;
+pub mod diff;
 pub mod encoding;
 pub mod error;
 pub mod field;
 pub mod message;
 pub mod segment;
+pub mod stats;
 pub mod types;
 
 pub use batch::{Batch, BatchHeader, BatchTrailer, File, FileHeader, FileTrailer};
+pub use charset::Charset;
 pub use delimiters::Delimiters;
-pub use encoding::Encoding;
+pub use diff::{DiffKind, FieldDiff};
+pub use encoding::{Encoding, UnknownEscape};
 pub use error::{Error, Result};
 pub use field::{Component, Field, Repetition, SubComponent};
-pub use message::Message;
+pub use message::{AcknowledgmentType, Message, SegmentIndex, SegmentTerminator};
 pub use segment::Segment;
+pub use stats::{MessageStats, SegmentStats};
+pub use types::CodedValue;
 
 use std::str::FromStr;
 
@@ -66,9 +73,41 @@ impl Version {
     /// Parse version from string (e.g., "2.5" or "2.5.1")
     ///
     /// Note: This method is kept for backward compatibility.
-    /// Consider using the `FromStr` trait implementation instead.
+    /// Use the `FromStr` trait implementation for strict parsing or
+    /// [`Version::parse_lenient`] for raw MSH-12 values.
+    #[deprecated(
+        since = "0.23.0",
+        note = "use `str::parse::<Version>()` or `Version::parse_lenient` instead"
+    )]
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
+        Self::parse_exact(s)
+    }
+
+    /// Parse a version as found in MSH-12, tolerating common variations
+    ///
+    /// Surrounding whitespace is trimmed, anything from the first `^` on
+    /// (the internationalization and international version components of
+    /// the VID data type) is dropped, and `HL7` / `v` prefixes are accepted,
+    /// so `" 2.5.1^USA^HL70399 "`, `"v2.5"` and `"HL7 v2.8.2"` all parse.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rs7_core::Version;
+    ///
+    /// assert_eq!(Version::parse_lenient("2.5.1^USA"), Some(Version::V2_5_1));
+    /// assert_eq!(Version::parse_lenient("HL7 v2.4 "), Some(Version::V2_4));
+    /// assert_eq!(Version::parse_lenient("3.0"), None);
+    /// ```
+    pub fn parse_lenient(s: &str) -> Option<Self> {
+        let version = s.split('^').next().unwrap_or_default().trim();
+        let version = strip_prefix_ignore_case(version, "HL7").trim_start();
+        let version = strip_prefix_ignore_case(version, "v");
+        Self::parse_exact(version)
+    }
+
+    fn parse_exact(s: &str) -> Option<Self> {
         match s {
             "2.1" => Some(Version::V2_1),
             "2.2" => Some(Version::V2_2),
@@ -179,11 +218,19 @@ impl Version {
     }
 }
 
+/// Strip an ASCII prefix, ignoring case
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> &'a str {
+    match s.get(..prefix.len()) {
+        Some(head) if head.eq_ignore_ascii_case(prefix) => &s[prefix.len()..],
+        _ => s,
+    }
+}
+
 impl FromStr for Version {
     type Err = Error;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        Self::from_str(s).ok_or_else(|| Error::UnsupportedVersion(format!("Unknown HL7 version: {}", s)))
+        Self::parse_exact(s).ok_or_else(|| Error::UnsupportedVersion(format!("Unknown HL7 version: {}", s)))
     }
 }
 
@@ -193,11 +240,34 @@ mod tests {
 
     #[test]
     fn test_version_parsing() {
+        assert_eq!("2.5".parse::<Version>().ok(), Some(Version::V2_5));
+        assert_eq!("2.7.1".parse::<Version>().ok(), Some(Version::V2_7_1));
+        assert!("invalid".parse::<Version>().is_err());
+        assert!(" 2.5".parse::<Version>().is_err());
+        assert!("2.5^USA".parse::<Version>().is_err());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_version_from_str_inherent() {
         assert_eq!(Version::from_str("2.5"), Some(Version::V2_5));
-        assert_eq!(Version::from_str("2.7.1"), Some(Version::V2_7_1));
         assert_eq!(Version::from_str("invalid"), None);
     }
 
+    #[test]
+    fn test_version_parse_lenient() {
+        assert_eq!(Version::parse_lenient("2.5"), Some(Version::V2_5));
+        assert_eq!(Version::parse_lenient(" 2.5.1 \r\n"), Some(Version::V2_5_1));
+        assert_eq!(Version::parse_lenient("2.5.1^USA^HL70399"), Some(Version::V2_5_1));
+        assert_eq!(Version::parse_lenient("2.4 ^"), Some(Version::V2_4));
+        assert_eq!(Version::parse_lenient("v2.3.1"), Some(Version::V2_3_1));
+        assert_eq!(Version::parse_lenient("HL7 V2.8.2"), Some(Version::V2_8_2));
+        assert_eq!(Version::parse_lenient("hl7v2.6"), Some(Version::V2_6));
+        assert_eq!(Version::parse_lenient(""), None);
+        assert_eq!(Version::parse_lenient("^2.5"), None);
+        assert_eq!(Version::parse_lenient("2.9"), None);
+    }
+
     #[test]
     fn test_version_as_str() {
         assert_eq!(Version::V2_5.as_str(), "2.5");
@@ -206,8 +276,8 @@ mod tests {
 
     #[test]
     fn test_legacy_versions() {
-        assert_eq!(Version::from_str("2.1"), Some(Version::V2_1));
-        assert_eq!(Version::from_str("2.2"), Some(Version::V2_2));
+        assert_eq!(Version::parse_lenient("2.1"), Some(Version::V2_1));
+        assert_eq!(Version::parse_lenient("2.2"), Some(Version::V2_2));
         assert!(Version::V2_1.is_legacy());
         assert!(Version::V2_2.is_legacy());
         assert!(!Version::V2_3.is_legacy());
@@ -215,9 +285,9 @@ mod tests {
 
     #[test]
     fn test_new_versions() {
-        assert_eq!(Version::from_str("2.8"), Some(Version::V2_8));
-        assert_eq!(Version::from_str("2.8.1"), Some(Version::V2_8_1));
-        assert_eq!(Version::from_str("2.8.2"), Some(Version::V2_8_2));
+        assert_eq!(Version::parse_lenient("2.8"), Some(Version::V2_8));
+        assert_eq!(Version::parse_lenient("2.8.1"), Some(Version::V2_8_1));
+        assert_eq!(Version::parse_lenient("2.8.2"), Some(Version::V2_8_2));
         assert_eq!(Version::V2_8.as_str(), "2.8");
         assert_eq!(Version::V2_8_2.as_str(), "2.8.2");
     }
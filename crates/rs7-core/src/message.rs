@@ -1,20 +1,106 @@
 //! HL7 message structures
 
+use crate::charset::Charset;
 use crate::delimiters::Delimiters;
+use crate::diff::FieldDiff;
 use crate::error::{Error, Result};
+use crate::field::{field_leaves, Field};
 use crate::segment::Segment;
+use crate::stats::MessageStats;
 use crate::Version;
+use std::collections::HashMap;
 
 /// An HL7 message
 ///
 /// A message consists of multiple segments, starting with an MSH segment.
 /// The message structure follows HL7 v2.x specifications.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Eq)]
 pub struct Message {
     /// Message segments
     pub segments: Vec<Segment>,
     /// Delimiters used in this message
     pub delimiters: Delimiters,
+    /// Segment terminator used by [`Message::encode`]
+    segment_terminator: SegmentTerminator,
+    /// Segment ID aliases (alias -> canonical ID), empty by default
+    segment_aliases: HashMap<String, String>,
+    /// Segment ID index, `None` unless enabled with [`Message::with_index`]
+    segment_index: Option<SegmentIndex>,
+    /// Set when a segment was handed out mutably, so its ID may have changed
+    index_dirty: bool,
+    /// Set when field values are stored as received, escape sequences intact
+    values_escaped: bool,
+}
+
+/// Segment ID to positions index for large messages
+///
+/// Lets [`Message::get_segments_by_id`] and [`Message::resolve_segments`]
+/// look segments up by hash instead of scanning every segment.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SegmentIndex {
+    positions: HashMap<String, Vec<usize>>,
+    len: usize,
+}
+
+impl SegmentIndex {
+    /// Build an index over `segments`
+    pub fn build(segments: &[Segment]) -> Self {
+        let mut positions: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, segment) in segments.iter().enumerate() {
+            positions.entry(segment.id.clone()).or_default().push(i);
+        }
+        Self { positions, len: segments.len() }
+    }
+
+    /// Positions of segments with `id`, in document order
+    pub fn positions(&self, id: &str) -> &[usize] {
+        self.positions.get(id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Record a segment inserted at `index`
+    fn insert(&mut self, index: usize, id: &str) {
+        if index == self.len {
+            // Appending never shifts existing positions
+            self.positions.entry(id.to_string()).or_default().push(index);
+            self.len += 1;
+            return;
+        }
+        for positions in self.positions.values_mut() {
+            for pos in positions.iter_mut().filter(|p| **p >= index) {
+                *pos += 1;
+            }
+        }
+        let positions = self.positions.entry(id.to_string()).or_default();
+        let at = positions.partition_point(|&p| p < index);
+        positions.insert(at, index);
+        self.len += 1;
+    }
+
+    /// Record removal of the segment at `index`
+    fn remove(&mut self, index: usize, id: &str) {
+        if let Some(positions) = self.positions.get_mut(id) {
+            positions.retain(|&p| p != index);
+            if positions.is_empty() {
+                self.positions.remove(id);
+            }
+        }
+        for positions in self.positions.values_mut() {
+            for pos in positions.iter_mut().filter(|p| **p > index) {
+                *pos -= 1;
+            }
+        }
+        self.len -= 1;
+    }
+}
+
+impl PartialEq for Message {
+    /// Messages compare by content; the segment terminator, whether they are
+    /// indexed and whether values are escaped are ignored
+    fn eq(&self, other: &Self) -> bool {
+        self.segments == other.segments
+            && self.delimiters == other.delimiters
+            && self.segment_aliases == other.segment_aliases
+    }
 }
 
 impl Message {
@@ -23,6 +109,11 @@ impl Message {
         Self {
             segments: Vec::new(),
             delimiters: Delimiters::default(),
+            segment_terminator: SegmentTerminator::default(),
+            segment_aliases: HashMap::new(),
+            segment_index: None,
+            index_dirty: false,
+            values_escaped: false,
         }
     }
 
@@ -31,41 +122,301 @@ impl Message {
         Self {
             segments: Vec::new(),
             delimiters,
+            segment_terminator: SegmentTerminator::default(),
+            segment_aliases: HashMap::new(),
+            segment_index: None,
+            index_dirty: false,
+            values_escaped: false,
         }
     }
 
+    /// Enable the segment ID index
+    ///
+    /// Worth it for large messages (hundreds of segments) queried repeatedly,
+    /// e.g. through a Terser; small messages are faster to scan.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rs7_core::{Message, Segment};
+    ///
+    /// let mut msg = Message::new().with_index();
+    /// for _ in 0..3 {
+    ///     msg.add_segment(Segment::new("OBX"));
+    /// }
+    /// msg.insert_segment(0, Segment::new("MSH")).unwrap();
+    /// assert_eq!(msg.get_segments_by_id("OBX").len(), 3);
+    /// ```
+    pub fn with_index(mut self) -> Self {
+        self.rebuild_index();
+        self
+    }
+
+    /// Rebuild the segment ID index after editing `segments` directly
+    ///
+    /// Also enables the index if it was off.
+    pub fn rebuild_index(&mut self) {
+        self.segment_index = Some(SegmentIndex::build(&self.segments));
+        self.index_dirty = false;
+    }
+
+    /// Get the segment ID index, if enabled
+    ///
+    /// Kept up to date by [`Message::add_segment`], [`Message::insert_segment`]
+    /// and [`Message::remove_segment`]. [`Message::get_segment_mut`] and
+    /// [`Message::get_segment_by_id_mut`] mark it stale, and lookups scan
+    /// until [`Message::rebuild_index`] is called. Direct edits to `segments`
+    /// are not tracked: call [`Message::rebuild_index`] after them.
+    ///
+    /// Returns `None` while the index is stale.
+    pub fn segment_index(&self) -> Option<&SegmentIndex> {
+        self.segment_index.as_ref().filter(|_| !self.index_dirty)
+    }
+
+    /// Get the segment terminator used by [`Message::encode`]
+    ///
+    /// Fresh messages use `\r`; the parser records the terminator of the input.
+    pub fn segment_terminator(&self) -> SegmentTerminator {
+        self.segment_terminator
+    }
+
+    /// Set the segment terminator used by [`Message::encode`]
+    pub fn with_segment_terminator(mut self, terminator: SegmentTerminator) -> Self {
+        self.segment_terminator = terminator;
+        self
+    }
+
+    /// Check whether field values are stored as received, with escape
+    /// sequences such as `\F\` still in place
+    ///
+    /// `false` for messages from [`Message::new`] and the regular parser,
+    /// which unescape values; the streaming parser keeps them as received.
+    pub fn values_escaped(&self) -> bool {
+        self.values_escaped
+    }
+
+    /// Mark whether field values are stored with escape sequences intact
+    pub fn with_values_escaped(mut self, escaped: bool) -> Self {
+        self.values_escaped = escaped;
+        self
+    }
+
     /// Add a segment to the message
     pub fn add_segment(&mut self, segment: Segment) {
+        if let Some(index) = &mut self.segment_index {
+            index.insert(self.segments.len(), &segment.id);
+        }
         self.segments.push(segment);
     }
 
+    /// Insert a segment at `index`, shifting later segments back
+    ///
+    /// `index` may equal [`Message::segment_count`] to append. Returns an
+    /// error if it is past the end.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rs7_core::{Message, Segment};
+    ///
+    /// let mut msg = Message::new();
+    /// msg.add_segment(Segment::new("MSH"));
+    /// msg.add_segment(Segment::new("PID"));
+    ///
+    /// // Place EVN right after MSH
+    /// msg.insert_segment(1, Segment::new("EVN")).unwrap();
+    /// assert_eq!(msg.segments[1].id, "EVN");
+    /// assert!(msg.insert_segment(9, Segment::new("NTE")).is_err());
+    /// ```
+    pub fn insert_segment(&mut self, index: usize, segment: Segment) -> Result<()> {
+        if index > self.segments.len() {
+            return Err(self.out_of_range(index));
+        }
+        self.segments.insert(index, segment);
+        if let Some(segment_index) = &mut self.segment_index {
+            segment_index.insert(index, &self.segments[index].id);
+        }
+        Ok(())
+    }
+
+    /// Remove and return the segment at `index`, if there is one
+    pub fn remove_segment(&mut self, index: usize) -> Option<Segment> {
+        if index >= self.segments.len() {
+            return None;
+        }
+        let segment = self.segments.remove(index);
+        if let Some(segment_index) = &mut self.segment_index {
+            segment_index.remove(index, &segment.id);
+        }
+        Some(segment)
+    }
+
+    /// Move the segment at `from` so that it ends up at index `to`
+    ///
+    /// Segments in between shift by one to make room. Returns an error if
+    /// either index is out of range.
+    pub fn move_segment(&mut self, from: usize, to: usize) -> Result<()> {
+        if from >= self.segments.len() {
+            return Err(self.out_of_range(from));
+        }
+        if to >= self.segments.len() {
+            return Err(self.out_of_range(to));
+        }
+        if from != to {
+            let segment = self.segments.remove(from);
+            self.segments.insert(to, segment);
+            if self.segment_index.is_some() {
+                self.rebuild_index();
+            }
+        }
+        Ok(())
+    }
+
+    /// Error for a segment position outside the message
+    fn out_of_range(&self, index: usize) -> Error {
+        Error::InvalidSegment(format!(
+            "Segment index {} out of range for message with {} segments",
+            index,
+            self.segments.len()
+        ))
+    }
+
+    /// Index positions for `ids`, if the index is enabled and current
+    ///
+    /// A stale index, or one whose length no longer matches after a direct
+    /// push or removal, is ignored and callers fall back to a scan.
+    fn indexed_positions(&self, ids: &[&str]) -> Option<Vec<usize>> {
+        let index = self.segment_index()?;
+        if index.len != self.segments.len() {
+            return None;
+        }
+        let mut positions = Vec::new();
+        for id in ids {
+            positions.extend_from_slice(index.positions(id));
+        }
+        if ids.len() > 1 {
+            positions.sort_unstable();
+        }
+        Some(positions)
+    }
+
     /// Get a segment by index
     pub fn get_segment(&self, index: usize) -> Option<&Segment> {
         self.segments.get(index)
     }
 
     /// Get a mutable segment by index
+    ///
+    /// Marks the segment index stale, as the segment ID may be changed.
     pub fn get_segment_mut(&mut self, index: usize) -> Option<&mut Segment> {
+        self.mark_index_dirty();
         self.segments.get_mut(index)
     }
 
     /// Get all segments with a specific ID
+    ///
+    /// Uses the segment index when enabled with [`Message::with_index`].
     pub fn get_segments_by_id(&self, id: &str) -> Vec<&Segment> {
+        if let Some(positions) = self.indexed_positions(&[id]) {
+            return positions.into_iter().map(|i| &self.segments[i]).collect();
+        }
         self.segments.iter().filter(|s| s.id == id).collect()
     }
 
+    /// Get all segments whose ID matches `id` ignoring ASCII case
+    ///
+    /// Useful for senders that emit lowercase segment IDs such as `pid`.
+    pub fn get_segments_by_id_ci(&self, id: &str) -> Vec<&Segment> {
+        self.segments
+            .iter()
+            .filter(|s| s.id.eq_ignore_ascii_case(id))
+            .collect()
+    }
+
+    /// Register `alias` as an alternative segment ID for `canonical`
+    ///
+    /// For example, aliasing `ZPD` to `PID` makes `PID-5` Terser paths read
+    /// `ZPD` segments as well. Aliases match the segment ID exactly, so a
+    /// lowercase `pid` can be handled with its own alias.
+    pub fn add_segment_alias(&mut self, alias: impl Into<String>, canonical: impl Into<String>) {
+        self.segment_aliases.insert(alias.into(), canonical.into());
+    }
+
+    /// Builder-style variant of [`Message::add_segment_alias`]
+    pub fn with_segment_alias(mut self, alias: impl Into<String>, canonical: impl Into<String>) -> Self {
+        self.add_segment_alias(alias, canonical);
+        self
+    }
+
+    /// Remove an alias, returning the canonical ID it pointed to
+    pub fn remove_segment_alias(&mut self, alias: &str) -> Option<String> {
+        self.segment_aliases.remove(alias)
+    }
+
+    /// Get the segment ID aliases (alias -> canonical ID)
+    ///
+    /// Aliased segments are found by [`Message::resolve_segments`] and hence by
+    /// Terser paths using the canonical ID. Exact-match lookups ignore them.
+    pub fn segment_aliases(&self) -> &HashMap<String, String> {
+        &self.segment_aliases
+    }
+
+    /// Check whether a segment answers to `id`, directly or through an alias
+    pub fn segment_matches(&self, segment: &Segment, id: &str) -> bool {
+        segment.id == id
+            || self
+                .segment_aliases
+                .get(&segment.id)
+                .is_some_and(|canonical| canonical == id)
+    }
+
+    /// Get all segments that answer to `id`, including aliased segments
+    ///
+    /// Equivalent to [`Message::get_segments_by_id`] when no aliases are set.
+    pub fn resolve_segments(&self, id: &str) -> Vec<&Segment> {
+        if self.segment_index.is_some() {
+            let mut ids = vec![id];
+            ids.extend(
+                self.segment_aliases
+                    .iter()
+                    .filter(|(alias, canonical)| *canonical == id && alias.as_str() != id)
+                    .map(|(alias, _)| alias.as_str()),
+            );
+            if let Some(positions) = self.indexed_positions(&ids) {
+                return positions.into_iter().map(|i| &self.segments[i]).collect();
+            }
+        }
+        self.segments
+            .iter()
+            .filter(|s| self.segment_matches(s, id))
+            .collect()
+    }
+
     /// Get the first segment with a specific ID
     ///
     /// Alias for `get_segment_by_id` for convenience
     pub fn get_segment_by_id(&self, id: &str) -> Option<&Segment> {
+        if let Some(positions) = self.indexed_positions(&[id]) {
+            return positions.first().map(|&i| &self.segments[i]);
+        }
         self.segments.iter().find(|s| s.id == id)
     }
 
     /// Get a mutable reference to the first segment with a specific ID
+    ///
+    /// Marks the segment index stale, as the segment ID may be changed.
     pub fn get_segment_by_id_mut(&mut self, id: &str) -> Option<&mut Segment> {
+        self.mark_index_dirty();
         self.segments.iter_mut().find(|s| s.id == id)
     }
 
+    /// Stop trusting the segment index until it is rebuilt
+    fn mark_index_dirty(&mut self) {
+        if self.segment_index.is_some() {
+            self.index_dirty = true;
+        }
+    }
+
     /// Convenience alias for get_segment_by_id
     #[inline]
     pub fn segment(&self, id: &str) -> Option<&Segment> {
@@ -98,10 +449,13 @@ impl Message {
     }
 
     /// Get the HL7 version from MSH-12
+    ///
+    /// The value is parsed with [`Version::parse_lenient`], so whitespace and
+    /// extra VID components (e.g. `2.5.1^USA`) are tolerated.
     pub fn get_version(&self) -> Option<Version> {
         self.get_msh()
             .and_then(|msh| msh.get_field_value(12))
-            .and_then(Version::from_str)
+            .and_then(Version::parse_lenient)
     }
 
     /// Set the HL7 version in MSH-12
@@ -140,6 +494,39 @@ impl Message {
         self.get_msh().and_then(|msh| msh.get_field_value(6))
     }
 
+    /// Get the accept acknowledgment type from MSH-15
+    ///
+    /// Empty or unknown values give [`AcknowledgmentType::Always`], the
+    /// original mode behaviour.
+    pub fn accept_ack_type(&self) -> AcknowledgmentType {
+        self.ack_type(15)
+    }
+
+    /// Get the application acknowledgment type from MSH-16
+    ///
+    /// Empty or unknown values give [`AcknowledgmentType::Always`], the
+    /// original mode behaviour.
+    pub fn application_ack_type(&self) -> AcknowledgmentType {
+        self.ack_type(16)
+    }
+
+    /// Check if the sender requested enhanced mode acknowledgments
+    ///
+    /// Enhanced mode is in effect when MSH-15 or MSH-16 is valued; otherwise
+    /// the original acknowledgment rules apply.
+    pub fn is_enhanced_mode(&self) -> bool {
+        self.get_msh()
+            .map(|msh| [15, 16].iter().any(|&i| msh.get_field_value(i).is_some_and(|v| !v.is_empty())))
+            .unwrap_or(false)
+    }
+
+    fn ack_type(&self, field: usize) -> AcknowledgmentType {
+        self.get_msh()
+            .and_then(|msh| msh.get_field_value(field))
+            .and_then(AcknowledgmentType::from_hl7)
+            .unwrap_or_default()
+    }
+
     /// Validate the message structure
     pub fn validate(&self) -> Result<()> {
         // Check that message has at least one segment
@@ -170,9 +557,99 @@ impl Message {
 
     /// Encode the message to HL7 format
     ///
-    /// Segments are separated by carriage return (\r) or \r\n
+    /// Segments are separated by [`Message::segment_terminator`]: carriage
+    /// return (\r) unless the message was parsed from, or configured with,
+    /// another terminator. Use [`Message::encode_with_separator`] to override.
     pub fn encode(&self) -> String {
-        self.encode_with_separator("\r")
+        self.encode_with_separator(self.segment_terminator.as_str())
+    }
+
+    /// Encode the message to bytes in the given character set
+    ///
+    /// MSH-18 is not modified; set it to [`Charset::as_hl7`] so receivers can
+    /// decode the bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Encoding`] if the message contains a character that
+    /// `charset` cannot represent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rs7_core::{Charset, Message, Segment};
+    ///
+    /// let mut pid = Segment::new("PID");
+    /// pid.set_field_value(5, "Müller").unwrap();
+    /// let mut message = Message::new();
+    /// message.add_segment(pid);
+    ///
+    /// let bytes = message.encode_bytes(Charset::Iso8859_1).unwrap();
+    /// assert_eq!(bytes, b"PID|||||M\xFCller");
+    /// assert!(message.encode_bytes(Charset::Ascii).is_err());
+    /// ```
+    pub fn encode_bytes(&self, charset: Charset) -> Result<Vec<u8>> {
+        charset.encode(&self.encode())
+    }
+
+    /// Get the character set declared in MSH-18
+    ///
+    /// Returns `None` if MSH-18 is empty or names a charset that is not
+    /// supported. Only the first repetition (the default charset) is used.
+    pub fn get_charset(&self) -> Option<Charset> {
+        self.get_msh()?
+            .get_field(18)?
+            .get_repetition(0)?
+            .value()
+            .and_then(Charset::from_hl7)
+    }
+
+    /// Get a canonical copy of the message for comparison
+    ///
+    /// Only trailing empties are removed, so no value changes position:
+    /// - trailing empty subcomponents of each component (`A&&` becomes `A`)
+    /// - trailing empty components of each repetition (`DOE^JOHN^^` becomes `DOE^JOHN`)
+    /// - trailing empty repetitions of each field (`A~~` becomes `A`)
+    /// - trailing empty fields of each segment (`PID|1||` becomes `PID|1`)
+    ///
+    /// Empties followed by a value (`DOE^^III`, `A~~B`, `PID|1||123`) are kept,
+    /// as are HL7 explicit nulls (`""`). Segments are never removed, and the
+    /// segment terminator is reset to CR. Messages that differ only in
+    /// trailing empties have equal canonical forms.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rs7_core::{Field, Message, Segment};
+    ///
+    /// let mut pid = Segment::new("PID");
+    /// pid.add_field(Field::from_value("1"));
+    /// pid.set_field_value(5, "").unwrap();
+    /// let mut message = Message::new();
+    /// message.add_segment(pid);
+    ///
+    /// assert_eq!(message.encode(), "PID|1||||");
+    /// assert_eq!(message.encode_canonical(), "PID|1");
+    /// ```
+    pub fn canonicalize(&self) -> Message {
+        let mut canonical = self.clone();
+        canonical.segment_terminator = SegmentTerminator::Cr;
+        for segment in &mut canonical.segments {
+            for field in &mut segment.fields {
+                canonicalize_field(field);
+            }
+            while segment.fields.last().is_some_and(|f| f.repetitions.is_empty()) {
+                segment.fields.pop();
+            }
+        }
+        canonical
+    }
+
+    /// Encode the canonical form of the message
+    ///
+    /// See [`Message::canonicalize`] for which empties are removed.
+    pub fn encode_canonical(&self) -> String {
+        self.canonicalize().encode()
     }
 
     /// Encode with a custom segment separator
@@ -184,6 +661,104 @@ impl Message {
             .join(separator)
     }
 
+    /// Compare this message with `other`, field by field
+    ///
+    /// Segments are paired by ID and occurrence and compared down to
+    /// components and subcomponents. Each returned [`FieldDiff`] carries a
+    /// Terser-style path and the old/new values; empty values count as absent.
+    /// Differences are sorted by path: segment ID, then occurrence, field,
+    /// repetition, component and subcomponent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rs7_core::{DiffKind, Field, Message, Segment};
+    ///
+    /// let mut pid = Segment::new("PID");
+    /// pid.add_field(Field::from_value("1"));
+    /// let mut old = Message::new();
+    /// old.add_segment(pid.clone());
+    ///
+    /// pid.set_field_value(3, "12345").unwrap();
+    /// let mut new = Message::new();
+    /// new.add_segment(pid);
+    ///
+    /// let diffs = old.diff(&new);
+    /// assert_eq!(diffs.len(), 1);
+    /// assert_eq!(diffs[0].path, "PID-3");
+    /// assert_eq!(diffs[0].kind(), DiffKind::Added);
+    /// ```
+    pub fn diff(&self, other: &Message) -> Vec<FieldDiff> {
+        crate::diff::diff_messages(self, other)
+    }
+
+    /// Collect size and structure statistics for this message
+    ///
+    /// Reports the encoded size, segment counts and sizes per segment ID, the
+    /// largest field count and the deepest component/subcomponent nesting.
+    /// With the `serde` feature, [`MessageStats`] is serializable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rs7_core::{Field, Message, Segment};
+    ///
+    /// let mut obx = Segment::new("OBX");
+    /// obx.add_field(Field::from_value("1"));
+    /// let mut msg = Message::new();
+    /// msg.add_segment(obx.clone());
+    /// msg.add_segment(obx);
+    ///
+    /// let stats = msg.stats();
+    /// assert_eq!(stats.total_bytes, msg.encode().len());
+    /// assert_eq!(stats.segments["OBX"].count, 2);
+    /// assert_eq!(stats.max_depth, 1);
+    /// ```
+    pub fn stats(&self) -> MessageStats {
+        crate::stats::message_stats(self)
+    }
+
+    /// Iterate over every non-empty value in the message with its Terser path
+    ///
+    /// Values are yielded in document order. Paths use the syntax accepted by
+    /// `Terser`/`TerserMut`: a field holding a single value is reported at
+    /// field level (`PID-3`), otherwise each component (`PID-5-1`), and each
+    /// subcomponent when a component has several (`PID-3-4-2`). Repeated
+    /// segments and repetitions are qualified as `OBX(2)-5` and `PID-3(1)-1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rs7_core::{Field, Message, Segment};
+    ///
+    /// let mut pid = Segment::new("PID");
+    /// pid.add_field(Field::from_value("1"));
+    /// let mut msg = Message::new();
+    /// msg.add_segment(pid);
+    ///
+    /// let leaves: Vec<_> = msg.iter_leaves().collect();
+    /// assert_eq!(leaves, vec![("PID-1".to_string(), "1")]);
+    /// ```
+    pub fn iter_leaves(&self) -> impl Iterator<Item = (String, &str)> + '_ {
+        let mut occurrences: HashMap<&str, usize> = HashMap::new();
+        self.segments.iter().flat_map(move |segment| {
+            let occurrence = occurrences.entry(segment.id.as_str()).or_insert(0);
+            *occurrence += 1;
+            let segment_path = if *occurrence > 1 {
+                format!("{}({})", segment.id, occurrence)
+            } else {
+                segment.id.clone()
+            };
+            segment
+                .fields
+                .iter()
+                .enumerate()
+                .flat_map(move |(index, field)| {
+                    leaf_paths(format!("{}-{}", segment_path, index + 1), field)
+                })
+        })
+    }
+
     /// Get the number of segments
     pub fn segment_count(&self) -> usize {
         self.segments.len()
@@ -197,6 +772,141 @@ impl Message {
     }
 }
 
+/// Separator between encoded segments
+///
+/// HL7 mandates a carriage return; LF and CRLF are kept so that messages from
+/// senders using them can be reproduced byte for byte.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SegmentTerminator {
+    /// Carriage return (`\r`)
+    #[default]
+    Cr,
+    /// Line feed (`\n`)
+    Lf,
+    /// Carriage return and line feed (`\r\n`)
+    CrLf,
+}
+
+impl SegmentTerminator {
+    /// Get the terminator characters
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SegmentTerminator::Cr => "\r",
+            SegmentTerminator::Lf => "\n",
+            SegmentTerminator::CrLf => "\r\n",
+        }
+    }
+
+    /// Detect the terminator used by raw message text
+    ///
+    /// Looks at the first line break; returns `None` if there is none.
+    pub fn detect(input: &str) -> Option<Self> {
+        let pos = input.find(['\r', '\n'])?;
+        let terminator = match &input.as_bytes()[pos..] {
+            [b'\r', b'\n', ..] => SegmentTerminator::CrLf,
+            [b'\r', ..] => SegmentTerminator::Cr,
+            _ => SegmentTerminator::Lf,
+        };
+        Some(terminator)
+    }
+}
+
+/// Conditions for sending an acknowledgment (HL7 Table 0155)
+///
+/// Used by MSH-15 (accept/commit acknowledgments) and MSH-16 (application
+/// acknowledgments) in enhanced acknowledgment mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AcknowledgmentType {
+    /// `AL` - Always acknowledge
+    #[default]
+    Always,
+    /// `NE` - Never acknowledge
+    Never,
+    /// `ER` - Acknowledge only on error or reject
+    ErrorOnly,
+    /// `SU` - Acknowledge only on successful completion
+    SuccessOnly,
+}
+
+impl AcknowledgmentType {
+    /// Look up an acknowledgment type by its HL7 code (e.g., "AL")
+    ///
+    /// Returns `None` for empty or unknown codes.
+    pub fn from_hl7(code: &str) -> Option<Self> {
+        match code.trim().to_uppercase().as_str() {
+            "AL" => Some(AcknowledgmentType::Always),
+            "NE" => Some(AcknowledgmentType::Never),
+            "ER" => Some(AcknowledgmentType::ErrorOnly),
+            "SU" => Some(AcknowledgmentType::SuccessOnly),
+            _ => None,
+        }
+    }
+
+    /// Get the HL7 code
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AcknowledgmentType::Always => "AL",
+            AcknowledgmentType::Never => "NE",
+            AcknowledgmentType::ErrorOnly => "ER",
+            AcknowledgmentType::SuccessOnly => "SU",
+        }
+    }
+
+    /// Check if an acknowledgment should be sent for an outcome
+    ///
+    /// `success` is true for an accept (AA/CA) and false for an error or
+    /// reject (AE/AR, CE/CR).
+    pub fn should_acknowledge(&self, success: bool) -> bool {
+        match self {
+            AcknowledgmentType::Always => true,
+            AcknowledgmentType::Never => false,
+            AcknowledgmentType::ErrorOnly => !success,
+            AcknowledgmentType::SuccessOnly => success,
+        }
+    }
+}
+
+/// Remove trailing empty subcomponents, components and repetitions from a field
+fn canonicalize_field(field: &mut Field) {
+    for repetition in &mut field.repetitions {
+        for component in &mut repetition.components {
+            while component.subcomponents.last().is_some_and(|s| s.is_empty()) {
+                component.subcomponents.pop();
+            }
+        }
+        while repetition.components.last().is_some_and(|c| c.subcomponents.is_empty()) {
+            repetition.components.pop();
+        }
+    }
+    while field.repetitions.last().is_some_and(|r| r.components.is_empty()) {
+        field.repetitions.pop();
+    }
+}
+
+/// Non-empty values of a field with their Terser paths
+fn leaf_paths(path: String, field: &Field) -> Vec<(String, &str)> {
+    let is_simple = field.repetitions.len() == 1
+        && field.repetitions[0].components.len() == 1
+        && field.repetitions[0].components[0].subcomponents.len() == 1;
+
+    field_leaves(field)
+        .map(|((rep_index, comp_index, sub_index), value)| {
+            if is_simple {
+                return (path.clone(), value);
+            }
+            let mut leaf_path = path.clone();
+            if rep_index > 0 {
+                leaf_path.push_str(&format!("({})", rep_index));
+            }
+            leaf_path.push_str(&format!("-{}", comp_index + 1));
+            if field.repetitions[rep_index].components[comp_index].subcomponents.len() > 1 {
+                leaf_path.push_str(&format!("-{}", sub_index + 1));
+            }
+            (leaf_path, value)
+        })
+        .collect()
+}
+
 impl Default for Message {
     fn default() -> Self {
         Self::new()
@@ -363,6 +1073,22 @@ mod tests {
         assert_eq!(msg.get_sending_application(), Some("SendingApp"));
     }
 
+    #[test]
+    fn test_get_version_lenient() {
+        let mut msg = Message::new();
+        msg.add_segment(create_test_msh());
+        assert_eq!(msg.get_version(), None);
+
+        msg.set_version(Version::V2_5).unwrap();
+        assert_eq!(msg.get_version(), Some(Version::V2_5));
+
+        msg.segments[0].set_field_value(12, " 2.5.1^USA ").unwrap();
+        assert_eq!(msg.get_version(), Some(Version::V2_5_1));
+
+        msg.segments[0].set_field_value(12, "9.9").unwrap();
+        assert_eq!(msg.get_version(), None);
+    }
+
     #[test]
     fn test_validate_empty_message() {
         let msg = Message::new();
@@ -403,4 +1129,257 @@ mod tests {
         let pid_segments = msg.get_segments_by_id("PID");
         assert_eq!(pid_segments.len(), 2);
     }
+
+    #[test]
+    fn test_segment_terminator() {
+        assert_eq!(SegmentTerminator::detect("MSH|a\rPID|1"), Some(SegmentTerminator::Cr));
+        assert_eq!(SegmentTerminator::detect("MSH|a\nPID|1"), Some(SegmentTerminator::Lf));
+        assert_eq!(SegmentTerminator::detect("MSH|a\r\nPID|1"), Some(SegmentTerminator::CrLf));
+        assert_eq!(SegmentTerminator::detect("MSH|a"), None);
+
+        let mut msg = Message::new();
+        msg.add_segment(Segment::new("EVN"));
+        msg.add_segment(Segment::new("PID"));
+        assert_eq!(msg.encode(), "EVN\rPID");
+
+        let msg = msg.with_segment_terminator(SegmentTerminator::CrLf);
+        assert_eq!(msg.encode(), "EVN\r\nPID");
+        assert_eq!(msg.encode_with_separator("\n"), "EVN\nPID");
+        assert_eq!(msg.segment_terminator(), SegmentTerminator::CrLf);
+
+        // The terminator is a wire detail, not message content
+        let cr = msg.clone().with_segment_terminator(SegmentTerminator::Cr);
+        assert_eq!(msg, cr);
+    }
+
+    #[test]
+    fn test_segment_index() {
+        let mut msg = Message::new().with_index();
+        msg.add_segment(create_test_msh());
+        for i in 1..=3 {
+            let mut obx = Segment::new("OBX");
+            obx.add_field(Field::from_value(i.to_string()));
+            msg.add_segment(obx);
+        }
+        msg.insert_segment(1, Segment::new("PID")).unwrap();
+        msg.add_segment(Segment::new("ZOB"));
+        msg.add_segment_alias("ZOB", "OBX");
+
+        let ids: Vec<_> = msg.get_segments_by_id("OBX").iter().map(|s| s.get_field_value(1)).collect();
+        assert_eq!(ids, vec![Some("1"), Some("2"), Some("3")]);
+        assert_eq!(msg.get_segment_by_id("PID"), msg.get_segment(1));
+        assert_eq!(msg.resolve_segments("OBX").len(), 4);
+
+        assert_eq!(msg.segment_index(), Some(&SegmentIndex::build(&msg.segments)));
+        let removed = msg.remove_segment(2).unwrap();
+        assert_eq!(removed.get_field_value(1), Some("1"));
+        assert_eq!(msg.segment_index, Some(SegmentIndex::build(&msg.segments)));
+        assert_eq!(msg.get_segments_by_id("OBX")[0].get_field_value(1), Some("2"));
+        assert!(msg.remove_segment(10).is_none());
+
+        // A direct push changes the length, so lookups scan until rebuilt
+        msg.segments.push(Segment::new("OBX"));
+        assert_eq!(msg.get_segments_by_id("OBX").len(), 3);
+        msg.rebuild_index();
+        assert_eq!(msg.segment_index().unwrap().positions("OBX"), &[2, 3, 5]);
+
+        // Renaming through a mutable accessor keeps the counts but marks the
+        // index stale
+        msg.get_segment_mut(1).unwrap().id = "OBX".to_string();
+        assert!(msg.segment_index().is_none());
+        assert_eq!(msg.get_segments_by_id("OBX").len(), 4);
+        assert!(msg.get_segment_by_id("PID").is_none());
+        msg.get_segment_by_id_mut("ZOB").unwrap().id = "PID".to_string();
+        assert_eq!(msg.get_segment_by_id("PID"), msg.get_segment(4));
+        assert_eq!(msg.resolve_segments("OBX").len(), 4);
+        msg.rebuild_index();
+        assert_eq!(msg.segment_index().unwrap().positions("PID"), &[4]);
+        assert_eq!(msg.get_segments_by_id("OBX").len(), 4);
+
+        // Indexing does not affect equality
+        let mut plain = msg.clone();
+        plain.segment_index = None;
+        assert_eq!(plain, msg);
+    }
+
+    #[test]
+    fn test_insert_remove_move_segments() {
+        let mut msg = Message::new();
+        msg.add_segment(create_test_msh());
+        msg.add_segment(Segment::new("PID"));
+        msg.add_segment(Segment::new("OBX"));
+        msg.add_segment(Segment::new("EVN"));
+
+        let ids = |msg: &Message| msg.segments.iter().map(|s| s.id.clone()).collect::<Vec<_>>();
+
+        msg.move_segment(3, 1).unwrap();
+        assert_eq!(ids(&msg), ["MSH", "EVN", "PID", "OBX"]);
+        msg.insert_segment(4, Segment::new("NTE")).unwrap();
+        assert_eq!(ids(&msg), ["MSH", "EVN", "PID", "OBX", "NTE"]);
+        msg.move_segment(1, 4).unwrap();
+        assert_eq!(ids(&msg), ["MSH", "PID", "OBX", "NTE", "EVN"]);
+        assert_eq!(msg.remove_segment(4).unwrap().id, "EVN");
+
+        let err = msg.insert_segment(5, Segment::new("NTE")).unwrap_err();
+        assert!(err.to_string().contains("Segment index 5 out of range"));
+        assert!(msg.move_segment(0, 4).is_err());
+        assert!(msg.move_segment(4, 0).is_err());
+        assert!(msg.remove_segment(4).is_none());
+
+        // The index follows moves
+        let mut indexed = msg.clone().with_index();
+        indexed.move_segment(3, 1).unwrap();
+        assert_eq!(indexed.get_segment_by_id("NTE"), indexed.get_segment(1));
+        assert_eq!(indexed.segment_index, Some(SegmentIndex::build(&indexed.segments)));
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        use crate::field::{Component, Repetition, SubComponent};
+
+        // PID|1||A&&^^~~||DOE^^III|""||
+        let mut name = Component::from_value("A");
+        name.add_subcomponent(SubComponent::new(""));
+        name.add_subcomponent(SubComponent::new(""));
+        let mut first = Repetition::new();
+        first.add_component(name);
+        first.add_component(Component::from_value(""));
+        first.add_component(Component::new());
+        let mut padded = Field::new();
+        padded.add_repetition(first);
+        padded.add_repetition(Repetition::from_value(""));
+        padded.add_repetition(Repetition::new());
+
+        let mut pid = Segment::new("PID");
+        pid.add_field(Field::from_value("1"));
+        pid.add_field(Field::from_value(""));
+        pid.add_field(padded);
+        pid.set_component(5, 0, 0, "DOE").unwrap();
+        pid.set_component(5, 0, 2, "III").unwrap();
+        pid.set_field_value(6, "\"\"").unwrap();
+        pid.set_field_value(8, "").unwrap();
+
+        let mut msg = Message::new().with_segment_terminator(SegmentTerminator::Lf);
+        msg.add_segment(create_test_msh());
+        msg.add_segment(pid);
+        msg.add_segment(Segment::new("NTE"));
+
+        let mut compact_pid = Segment::new("PID");
+        compact_pid.add_field(Field::from_value("1"));
+        compact_pid.set_field_value(3, "A").unwrap();
+        compact_pid.set_component(5, 0, 0, "DOE").unwrap();
+        compact_pid.set_component(5, 0, 2, "III").unwrap();
+        compact_pid.set_field_value(6, "\"\"").unwrap();
+        let mut compact = Message::new();
+        compact.add_segment(create_test_msh());
+        compact.add_segment(compact_pid);
+        compact.add_segment(Segment::new("NTE"));
+
+        assert_ne!(msg.encode(), compact.encode());
+        assert_eq!(msg.canonicalize(), compact.canonicalize());
+
+        let encoded = msg.encode_canonical();
+        assert_eq!(encoded, compact.encode_canonical());
+        assert!(encoded.ends_with("\rPID|1||A||DOE^^III|\"\"\rNTE"));
+    }
+
+    #[test]
+    fn test_case_insensitive_and_aliased_lookup() {
+        let mut msg = Message::new();
+        msg.add_segment(create_test_msh());
+        msg.add_segment(Segment::new("pid"));
+        msg.add_segment(Segment::new("ZPD"));
+
+        assert!(msg.get_segments_by_id("PID").is_empty());
+        assert_eq!(msg.get_segments_by_id_ci("PID").len(), 1);
+        assert!(msg.resolve_segments("PID").is_empty());
+
+        let msg = msg.with_segment_alias("pid", "PID").with_segment_alias("ZPD", "PID");
+        let ids: Vec<_> = msg.resolve_segments("PID").iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["pid", "ZPD"]);
+        assert_eq!(msg.resolve_segments("ZPD").len(), 1);
+        assert!(msg.get_segments_by_id("PID").is_empty());
+
+        let mut msg = msg;
+        assert_eq!(msg.segment_aliases().len(), 2);
+        assert_eq!(msg.remove_segment_alias("pid").as_deref(), Some("PID"));
+        assert_eq!(msg.resolve_segments("PID").len(), 1);
+    }
+
+    #[test]
+    fn test_acknowledgment_types() {
+        let mut msg = Message::new();
+        msg.add_segment(create_test_msh());
+        assert!(!msg.is_enhanced_mode());
+        assert_eq!(msg.accept_ack_type(), AcknowledgmentType::Always);
+        assert_eq!(msg.application_ack_type(), AcknowledgmentType::Always);
+
+        let msh = msg.get_segment_mut(0).unwrap();
+        msh.set_field_value(15, "ne").unwrap();
+        msh.set_field_value(16, "XX").unwrap();
+        assert!(msg.is_enhanced_mode());
+        assert_eq!(msg.accept_ack_type(), AcknowledgmentType::Never);
+        assert_eq!(msg.application_ack_type(), AcknowledgmentType::Always);
+
+        assert!(AcknowledgmentType::ErrorOnly.should_acknowledge(false));
+        assert!(!AcknowledgmentType::ErrorOnly.should_acknowledge(true));
+        assert!(AcknowledgmentType::SuccessOnly.should_acknowledge(true));
+        assert!(!AcknowledgmentType::Never.should_acknowledge(false));
+        assert_eq!(AcknowledgmentType::from_hl7("SU").unwrap().as_str(), "SU");
+        assert_eq!(AcknowledgmentType::from_hl7(""), None);
+    }
+
+    #[test]
+    fn test_iter_leaves() {
+        use crate::field::{Component, Repetition, SubComponent};
+
+        let mut pid = Segment::new("PID");
+        pid.add_field(Field::from_value("1"));
+        pid.add_field(Field::from_value(""));
+
+        // PID-3: two repetitions, the first with subcomponents
+        let mut authority = Component::new();
+        authority.add_subcomponent(SubComponent::new("HOSP"));
+        authority.add_subcomponent(SubComponent::new("1.2.3"));
+        let mut first = Repetition::new();
+        first.add_component(Component::from_value("12345"));
+        first.add_component(Component::from_value(""));
+        first.add_component(authority);
+        let mut pid3 = Field::new();
+        pid3.add_repetition(first);
+        pid3.add_repetition(Repetition::from_value("67890"));
+        pid.add_field(pid3);
+
+        let mut name = Repetition::new();
+        name.add_component(Component::from_value("DOE"));
+        name.add_component(Component::from_value("JOHN"));
+        let mut pid4 = Field::new();
+        pid4.add_repetition(name);
+        pid.add_field(pid4);
+
+        let mut obx = Segment::new("OBX");
+        obx.add_field(Field::from_value("1"));
+
+        let mut msg = Message::new();
+        msg.add_segment(pid);
+        msg.add_segment(obx.clone());
+        msg.add_segment(obx);
+
+        let leaves: Vec<_> = msg.iter_leaves().collect();
+        let leaves: Vec<_> = leaves.iter().map(|(p, v)| (p.as_str(), *v)).collect();
+        assert_eq!(
+            leaves,
+            vec![
+                ("PID-1", "1"),
+                ("PID-3-1", "12345"),
+                ("PID-3-3-1", "HOSP"),
+                ("PID-3-3-2", "1.2.3"),
+                ("PID-3(1)-1", "67890"),
+                ("PID-4-1", "DOE"),
+                ("PID-4-2", "JOHN"),
+                ("OBX-1", "1"),
+                ("OBX(2)-1", "1"),
+            ]
+        );
+    }
 }
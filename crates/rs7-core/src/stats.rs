@@ -0,0 +1,187 @@
+//! Size and shape statistics for HL7 messages
+//!
+//! See [`Message::stats`](crate::Message::stats).
+
+use crate::field::Field;
+use crate::message::Message;
+use std::collections::BTreeMap;
+
+/// Size and structure statistics for a [`Message`]
+///
+/// Byte sizes are measured on the encoded message using its own delimiters
+/// and segment terminator.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MessageStats {
+    /// Length of the encoded message in bytes, including segment terminators
+    pub total_bytes: usize,
+    /// Number of segments
+    pub segment_count: usize,
+    /// Statistics per segment ID, sorted by ID
+    pub segments: BTreeMap<String, SegmentStats>,
+    /// Largest number of fields in a single segment
+    pub max_field_count: usize,
+    /// Deepest nesting of any non-empty value
+    ///
+    /// `0` if the message holds no values, `1` for plain field values, `2`
+    /// when a field uses components and `3` when a component uses
+    /// subcomponents.
+    pub max_depth: usize,
+}
+
+/// Statistics for all segments sharing an ID
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SegmentStats {
+    /// Number of occurrences in the message
+    pub count: usize,
+    /// Encoded size of all occurrences in bytes, excluding terminators
+    pub bytes: usize,
+    /// Encoded size of the largest occurrence in bytes
+    pub max_bytes: usize,
+}
+
+impl MessageStats {
+    /// Average encoded segment size in bytes, excluding terminators
+    pub fn average_segment_bytes(&self) -> usize {
+        if self.segment_count == 0 {
+            return 0;
+        }
+        self.segments.values().map(|s| s.bytes).sum::<usize>() / self.segment_count
+    }
+}
+
+/// Collect statistics for a message
+pub(crate) fn message_stats(message: &Message) -> MessageStats {
+    let mut stats = MessageStats {
+        segment_count: message.segments.len(),
+        ..MessageStats::default()
+    };
+
+    for segment in &message.segments {
+        let bytes = segment.encode(&message.delimiters).len();
+        stats.total_bytes += bytes;
+
+        let entry = stats.segments.entry(segment.id.clone()).or_default();
+        entry.count += 1;
+        entry.bytes += bytes;
+        entry.max_bytes = entry.max_bytes.max(bytes);
+
+        stats.max_field_count = stats.max_field_count.max(segment.fields.len());
+        let depth = segment.fields.iter().map(field_depth).max().unwrap_or(0);
+        stats.max_depth = stats.max_depth.max(depth);
+    }
+
+    let terminators = message.segments.len().saturating_sub(1);
+    stats.total_bytes += terminators * message.segment_terminator().as_str().len();
+
+    stats
+}
+
+/// Nesting depth of the deepest non-empty value in a field
+fn field_depth(field: &Field) -> usize {
+    field
+        .repetitions
+        .iter()
+        .flat_map(|repetition| {
+            let components = repetition.components.len();
+            repetition.components.iter().map(move |component| {
+                let values = component.subcomponents.iter().filter(|s| !s.value.is_empty()).count();
+                match values {
+                    0 => 0,
+                    _ if component.subcomponents.len() > 1 => 3,
+                    _ if components > 1 => 2,
+                    _ => 1,
+                }
+            })
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::{Component, Repetition, SubComponent};
+    use crate::segment::Segment;
+
+    fn test_message() -> Message {
+        let mut msh = Segment::new("MSH");
+        msh.add_field(Field::from_value("|"));
+        msh.add_field(Field::from_value("^~\\&"));
+        msh.add_field(Field::from_value("App"));
+
+        let mut pid = Segment::new("PID");
+        pid.add_field(Field::from_value("1"));
+        pid.set_field_value(5, "").unwrap();
+        pid.set_component(5, 0, 0, "DOE").unwrap();
+        pid.set_component(5, 0, 1, "JOHN").unwrap();
+
+        let mut msg = Message::new();
+        msg.add_segment(msh);
+        msg.add_segment(pid);
+        for value in ["A", "BB"] {
+            let mut obx = Segment::new("OBX");
+            obx.add_field(Field::from_value(value));
+            msg.add_segment(obx);
+        }
+        msg
+    }
+
+    #[test]
+    fn test_message_stats() {
+        let msg = test_message();
+        let stats = msg.stats();
+
+        assert_eq!(stats.total_bytes, msg.encode().len());
+        assert_eq!(stats.segment_count, 4);
+        assert_eq!(stats.segments.len(), 3);
+        assert_eq!(stats.max_field_count, 5);
+        assert_eq!(stats.max_depth, 2);
+
+        let obx = &stats.segments["OBX"];
+        assert_eq!(obx.count, 2);
+        assert_eq!(obx.bytes, "OBX|A".len() + "OBX|BB".len());
+        assert_eq!(obx.max_bytes, "OBX|BB".len());
+        assert_eq!(stats.segments["PID"].bytes, "PID|1||||DOE^JOHN".len());
+
+        let segment_bytes: usize = stats.segments.values().map(|s| s.bytes).sum();
+        assert_eq!(stats.average_segment_bytes(), segment_bytes / 4);
+    }
+
+    #[test]
+    fn test_message_stats_depth() {
+        let mut zzz = Segment::new("ZZZ");
+        zzz.add_field(Field::new());
+        let mut msg = Message::new();
+        msg.add_segment(zzz.clone());
+        assert_eq!(msg.stats().max_depth, 0);
+
+        let mut component = Component::new();
+        component.add_subcomponent(SubComponent::new("A"));
+        component.add_subcomponent(SubComponent::new("B"));
+        let mut repetition = Repetition::new();
+        repetition.add_component(component);
+        let mut field = Field::new();
+        field.add_repetition(repetition);
+        zzz.add_field(field);
+        msg.add_segment(zzz);
+        assert_eq!(msg.stats().max_depth, 3);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_message_stats_serde() {
+        let stats = test_message().stats();
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["segments"]["OBX"]["count"], 2);
+        assert_eq!(serde_json::from_value::<MessageStats>(json).unwrap(), stats);
+    }
+
+    #[test]
+    fn test_empty_message_stats() {
+        let stats = Message::new().stats();
+        assert_eq!(stats, MessageStats::default());
+        assert_eq!(stats.average_segment_bytes(), 0);
+    }
+}
@@ -3,7 +3,7 @@
 //! This module defines common HL7 data types as specified in the standard.
 //! These types provide semantic meaning to field values.
 
-use chrono::{NaiveDate, NaiveDateTime};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
 use std::str::FromStr;
 use crate::error::Error;
 
@@ -129,6 +129,11 @@ impl FromStr for DataType {
 
 /// Parse HL7 date (DT) format: YYYYMMDD or YYYY or YYYYMM
 pub fn parse_date(s: &str) -> Option<NaiveDate> {
+    // Fixed byte offsets below are only char boundaries for ASCII input
+    if !s.is_ascii() {
+        return None;
+    }
+
     match s.len() {
         4 => {
             // YYYY
@@ -154,7 +159,7 @@ pub fn parse_date(s: &str) -> Option<NaiveDate> {
 
 /// Parse HL7 timestamp (TS/DTM) format: YYYYMMDDHHMMSS[.SSSS][+/-ZZZZ]
 pub fn parse_timestamp(s: &str) -> Option<NaiveDateTime> {
-    if s.len() < 8 {
+    if s.len() < 8 || !s.is_ascii() {
         return None;
     }
 
@@ -192,6 +197,317 @@ pub fn format_timestamp(dt: &NaiveDateTime) -> String {
     dt.format("%Y%m%d%H%M%S").to_string()
 }
 
+/// Precision of an HL7 date/time, given by the last part present
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DateTimePrecision {
+    /// YYYY
+    Year,
+    /// YYYYMM
+    Month,
+    /// YYYYMMDD
+    Day,
+    /// YYYYMMDDHH
+    Hour,
+    /// YYYYMMDDHHMM
+    Minute,
+    /// YYYYMMDDHHMMSS
+    Second,
+    /// YYYYMMDDHHMMSS.S with this many fraction digits (1-4)
+    Fraction(u8),
+}
+
+/// An HL7 date/time (DT, TS or DTM) with its precision and optional offset
+///
+/// Shared by every crate that turns HL7 timestamps into other formats, so
+/// they agree on which values are valid and what precision they carry.
+///
+/// # Example
+///
+/// ```rust
+/// use rs7_core::types::{Timestamp, DateTimePrecision};
+///
+/// let ts = Timestamp::parse("202403151430-0500").unwrap();
+/// assert_eq!(ts.precision, DateTimePrecision::Minute);
+/// assert_eq!(ts.to_iso8601(), "2024-03-15T14:30-05:00");
+/// assert_eq!(ts.to_datetime_tz().unwrap().to_rfc3339(), "2024-03-15T14:30:00-05:00");
+///
+/// let ts = Timestamp::parse_iso8601("2024-03-15T14:30:00.5Z").unwrap();
+/// assert_eq!(ts.to_hl7(), "20240315143000.5+0000");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timestamp {
+    /// Local date/time; parts beyond the precision are the start of the period
+    pub datetime: NaiveDateTime,
+    /// Precision the value was given with
+    pub precision: DateTimePrecision,
+    /// UTC offset, `None` when the value is in the sender's unspecified local time
+    pub offset: Option<FixedOffset>,
+}
+
+impl Timestamp {
+    /// Parse `YYYY[MM[DD[HH[MM[SS[.S[S[S[S]]]]]]]]][+/-ZZZZ]`
+    ///
+    /// Offsets may also be given as `+/-ZZ`. Fractional seconds need a full
+    /// time. Surrounding whitespace is ignored.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        // The date/time part only holds digits and a decimal point, so the
+        // first sign starts the offset
+        let (main, offset) = match value.find(['+', '-']) {
+            Some(pos) => (&value[..pos], Some(parse_offset(&value[pos..])?)),
+            None => (value, None),
+        };
+        let (digits, fraction) = match main.split_once('.') {
+            Some((digits, fraction)) => (digits, Some(fraction)),
+            None => (main, None),
+        };
+        if !digits.bytes().all(|b| b.is_ascii_digit()) || (fraction.is_some() && digits.len() != 14) {
+            return None;
+        }
+
+        let precision = match digits.len() {
+            4 => DateTimePrecision::Year,
+            6 => DateTimePrecision::Month,
+            8 => DateTimePrecision::Day,
+            10 => DateTimePrecision::Hour,
+            12 => DateTimePrecision::Minute,
+            14 => DateTimePrecision::Second,
+            _ => return None,
+        };
+        let part = |start: usize| digits.get(start..start + 2).map(|s| s.parse::<u32>().ok());
+        Self::from_parts(
+            digits[..4].parse().ok()?,
+            [part(4), part(6), part(8), part(10), part(12)],
+            precision,
+            fraction,
+            offset,
+        )
+    }
+
+    /// Parse an ISO 8601 date/time such as a FHIR `date`, `dateTime` or `instant`
+    ///
+    /// Accepts `YYYY[-MM[-DD[THH[:MM[:SS[.S+]]]]]][Z|+/-HH[:MM]]`, with a space
+    /// also allowed before the time. Fractional seconds beyond the four digits
+    /// HL7 allows are truncated.
+    pub fn parse_iso8601(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (date, time) = match value.split_once(['T', ' ']) {
+            Some((date, time)) => (date, Some(time)),
+            None => (value, None),
+        };
+
+        let mut date_parts = date.split('-');
+        let year = date_parts.next().filter(|y| y.len() == 4 && is_digits(y))?;
+        let month = date_parts.next();
+        let day = date_parts.next();
+        if date_parts.next().is_some() {
+            return None;
+        }
+
+        let (mut parts, mut fraction, mut offset) = ([month, day, None, None, None], None, None);
+        if let Some(time) = time {
+            let (time, zone) = if let Some(time) = time.strip_suffix('Z') {
+                (time, Some(FixedOffset::east_opt(0)?))
+            } else {
+                match time.find(['+', '-']) {
+                    Some(pos) => (&time[..pos], Some(parse_offset(&time[pos..].replace(':', ""))?)),
+                    None => (time, None),
+                }
+            };
+            let (time, frac) = match time.split_once('.') {
+                Some((time, frac)) => (time, Some(frac.get(..4).unwrap_or(frac))),
+                None => (time, None),
+            };
+            let mut time_parts = time.split(':');
+            parts[2] = Some(time_parts.next()?);
+            parts[3] = time_parts.next();
+            parts[4] = time_parts.next();
+            if time_parts.next().is_some() {
+                return None;
+            }
+            fraction = frac;
+            offset = zone;
+        }
+
+        // Parts must be two digits and cannot skip a level
+        let present = parts.iter().take_while(|p| p.is_some()).count();
+        if parts[present..].iter().any(Option::is_some) || (fraction.is_some() && present < 5) {
+            return None;
+        }
+        let precision = [
+            DateTimePrecision::Year,
+            DateTimePrecision::Month,
+            DateTimePrecision::Day,
+            DateTimePrecision::Hour,
+            DateTimePrecision::Minute,
+            DateTimePrecision::Second,
+        ][present];
+        let parts = parts.map(|p| p.map(|p| Some(p).filter(|p| p.len() == 2 && is_digits(p))?.parse().ok()));
+        Self::from_parts(year.parse().ok()?, parts, precision, fraction, offset)
+    }
+
+    /// Build a timestamp from month, day, hour, minute and second parts
+    ///
+    /// `None` parts are absent; `Some(None)` parts were malformed.
+    fn from_parts(
+        year: i32,
+        parts: [Option<Option<u32>>; 5],
+        precision: DateTimePrecision,
+        fraction: Option<&str>,
+        offset: Option<FixedOffset>,
+    ) -> Option<Self> {
+        let mut values = [1, 1, 0, 0, 0];
+        for (value, part) in values.iter_mut().zip(parts) {
+            if let Some(part) = part {
+                *value = part?;
+            }
+        }
+        let [month, day, hour, minute, second] = values;
+
+        let (precision, nanos) = match fraction {
+            None => (precision, 0),
+            Some(f) if (1..=4).contains(&f.len()) && is_digits(f) => (
+                DateTimePrecision::Fraction(f.len() as u8),
+                f.parse::<u32>().ok()? * 10u32.pow(9 - f.len() as u32),
+            ),
+            Some(_) => return None,
+        };
+
+        let date = NaiveDate::from_ymd_opt(year, month, day)?;
+        let time = NaiveTime::from_hms_nano_opt(hour, minute, second, nanos)?;
+        Some(Self { datetime: date.and_time(time), precision, offset })
+    }
+
+    /// The date/time with its offset, if the value carried one
+    pub fn to_datetime_tz(&self) -> Option<DateTime<FixedOffset>> {
+        self.datetime.and_local_timezone(self.offset?).single()
+    }
+
+    /// Format as HL7 DTM, keeping the precision and offset
+    pub fn to_hl7(&self) -> String {
+        let format = match self.precision {
+            DateTimePrecision::Year => "%Y",
+            DateTimePrecision::Month => "%Y%m",
+            DateTimePrecision::Day => "%Y%m%d",
+            DateTimePrecision::Hour => "%Y%m%d%H",
+            DateTimePrecision::Minute => "%Y%m%d%H%M",
+            DateTimePrecision::Second | DateTimePrecision::Fraction(_) => "%Y%m%d%H%M%S",
+        };
+        let mut out = self.datetime.format(format).to_string();
+        self.push_fraction(&mut out);
+        if let Some(offset) = self.offset {
+            let (sign, minutes) = offset_minutes(offset);
+            out.push_str(&format!("{}{:02}{:02}", sign, minutes / 60, minutes % 60));
+        }
+        out
+    }
+
+    /// Format as ISO 8601, keeping the precision and offset
+    ///
+    /// `20240315` becomes `2024-03-15` and `202403151430-0500` becomes
+    /// `2024-03-15T14:30-05:00`.
+    pub fn to_iso8601(&self) -> String {
+        let format = match self.precision {
+            DateTimePrecision::Year => "%Y",
+            DateTimePrecision::Month => "%Y-%m",
+            DateTimePrecision::Day => "%Y-%m-%d",
+            DateTimePrecision::Hour => "%Y-%m-%dT%H",
+            DateTimePrecision::Minute => "%Y-%m-%dT%H:%M",
+            DateTimePrecision::Second | DateTimePrecision::Fraction(_) => "%Y-%m-%dT%H:%M:%S",
+        };
+        let mut out = self.datetime.format(format).to_string();
+        self.push_fraction(&mut out);
+        if let Some(offset) = self.offset {
+            let (sign, minutes) = offset_minutes(offset);
+            out.push_str(&format!("{}{:02}:{:02}", sign, minutes / 60, minutes % 60));
+        }
+        out
+    }
+
+    /// Append `.S` to `.SSSS` for the fraction digits the value was given with
+    fn push_fraction(&self, out: &mut String) {
+        if let DateTimePrecision::Fraction(digits) = self.precision {
+            let fraction = format!("{:09}", self.datetime.nanosecond());
+            out.push('.');
+            out.push_str(&fraction[..digits as usize]);
+        }
+    }
+}
+
+/// Parse a `+HHMM`, `-HHMM`, `+HH` or `-HH` offset
+fn parse_offset(value: &str) -> Option<FixedOffset> {
+    let (sign, digits) = match value.as_bytes().first()? {
+        b'+' => (1, &value[1..]),
+        b'-' => (-1, &value[1..]),
+        _ => return None,
+    };
+    if !is_digits(digits) {
+        return None;
+    }
+    let (hours, minutes) = match digits.len() {
+        2 => (digits.parse::<i32>().ok()?, 0),
+        4 => (digits[..2].parse::<i32>().ok()?, digits[2..].parse::<i32>().ok()?),
+        _ => return None,
+    };
+    if minutes >= 60 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Split an offset into its sign and absolute minutes
+fn offset_minutes(offset: FixedOffset) -> (char, i32) {
+    let seconds = offset.local_minus_utc();
+    (if seconds < 0 { '-' } else { '+' }, seconds.abs() / 60)
+}
+
+fn is_digits(value: &str) -> bool {
+    !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// A Rust type that maps to codes from an HL7 table
+///
+/// Usually implemented with `#[derive(CodedValue)]` from `rs7-macros` on an
+/// enum whose variants carry `#[code = "..."]` attributes.
+///
+/// # Example
+///
+/// ```rust
+/// use rs7_core::types::CodedValue;
+///
+/// enum PatientClass {
+///     Inpatient,
+///     Outpatient,
+/// }
+///
+/// impl CodedValue for PatientClass {
+///     fn from_code(code: &str) -> Option<Self> {
+///         match code {
+///             "I" => Some(Self::Inpatient),
+///             "O" => Some(Self::Outpatient),
+///             _ => None,
+///         }
+///     }
+///
+///     fn to_code(&self) -> &str {
+///         match self {
+///             Self::Inpatient => "I",
+///             Self::Outpatient => "O",
+///         }
+///     }
+/// }
+///
+/// assert!(matches!(PatientClass::from_code("I"), Some(PatientClass::Inpatient)));
+/// assert_eq!(PatientClass::Outpatient.to_code(), "O");
+/// ```
+pub trait CodedValue: Sized {
+    /// Parse a table code, returning `None` if the code is unknown
+    fn from_code(code: &str) -> Option<Self>;
+
+    /// Get the table code for this value
+    fn to_code(&self) -> &str;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,6 +540,7 @@ mod tests {
             NaiveDate::from_ymd_opt(2024, 1, 1)
         );
         assert_eq!(parse_date("invalid"), None);
+        assert_eq!(parse_date("202é4"), None);
     }
 
     #[test]
@@ -233,6 +550,10 @@ mod tests {
 
         let ts2 = parse_timestamp("20240315").unwrap();
         assert_eq!(ts2.format("%Y%m%d").to_string(), "20240315");
+
+        // Non-ASCII input is rejected rather than sliced mid-character
+        assert_eq!(parse_timestamp("2024é0315"), None);
+        assert_eq!(parse_timestamp("2024031514é"), None);
     }
 
     #[test]
@@ -249,4 +570,41 @@ mod tests {
             .unwrap();
         assert_eq!(format_timestamp(&dt), "20240315143045");
     }
+
+    #[test]
+    fn test_timestamp_parse() {
+        let ts = Timestamp::parse("20240315143000.1234-0330").unwrap();
+        assert_eq!(ts.precision, DateTimePrecision::Fraction(4));
+        assert_eq!(ts.datetime.nanosecond(), 123_400_000);
+        assert_eq!(ts.offset, FixedOffset::west_opt(3 * 3600 + 30 * 60));
+        assert_eq!(ts.to_hl7(), "20240315143000.1234-0330");
+        assert_eq!(ts.to_iso8601(), "2024-03-15T14:30:00.1234-03:30");
+
+        let ts = Timestamp::parse("202403").unwrap();
+        assert_eq!(ts.precision, DateTimePrecision::Month);
+        assert_eq!(ts.offset, None);
+        assert_eq!(ts.to_iso8601(), "2024-03");
+        assert!(ts.to_datetime_tz().is_none());
+
+        assert_eq!(Timestamp::parse("20240315+02").unwrap().to_hl7(), "20240315+0200");
+        for invalid in ["", "2024031", "20241345", "2024031514.5", "20240315143000.12345", "20240315-5", "2024031514X"] {
+            assert!(Timestamp::parse(invalid).is_none(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_timestamp_parse_iso8601() {
+        let ts = Timestamp::parse_iso8601("2024-03-15T14:30").unwrap();
+        assert_eq!(ts.precision, DateTimePrecision::Minute);
+        assert_eq!(ts.to_hl7(), "202403151430");
+
+        let ts = Timestamp::parse_iso8601("2024-03-15T14:30:00.123456+01:00").unwrap();
+        assert_eq!(ts.to_hl7(), "20240315143000.1234+0100");
+        assert_eq!(Timestamp::parse_iso8601("2024-03-15T14:30:00Z").unwrap().to_hl7(), "20240315143000+0000");
+        assert_eq!(Timestamp::parse_iso8601("2024").unwrap().to_hl7(), "2024");
+
+        for invalid in ["2024-02-30", "2024-03-15T25:00", "2024-3-15", "2024-03-15T14:30.5", "03/15/2024"] {
+            assert!(Timestamp::parse_iso8601(invalid).is_none(), "{}", invalid);
+        }
+    }
 }
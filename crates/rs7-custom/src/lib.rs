@@ -44,6 +44,8 @@
 //! The framework uses a trait-based design where each Z-segment implements the
 //! [`CustomSegment`] trait. Segments are registered in a global [`CustomSegmentRegistry`]
 //! which can be queried by the parser when encountering unknown segment IDs.
+//! [`parse_message_with_registry`] does exactly that, returning a [`ParsedMessage`]
+//! with every registered Z-segment already in its typed form.
 //!
 //! # Feature Flags
 //!
@@ -51,6 +53,7 @@
 
 pub mod error;
 pub mod message_ext;
+pub mod parse;
 pub mod registry;
 pub mod segment;
 
@@ -60,6 +63,7 @@ pub mod macros;
 // Re-exports
 pub use error::{CustomSegmentError, Result};
 pub use message_ext::MessageExt;
+pub use parse::{parse_message_with_registry, ParsedMessage};
 pub use registry::CustomSegmentRegistry;
 pub use segment::{
     BuildableField, BuilderField, CustomSegment, FieldDefinition, ParseSegmentField,
@@ -69,7 +73,8 @@ pub use segment::{
 /// Prelude module for convenient imports
 pub mod prelude {
     pub use crate::{
-        CustomSegment, CustomSegmentError, CustomSegmentRegistry, FieldDefinition, MessageExt,
+        parse_message_with_registry, CustomSegment, CustomSegmentError, CustomSegmentRegistry,
+        FieldDefinition, MessageExt, ParsedMessage,
     };
 }
 
@@ -74,6 +74,28 @@ pub trait MessageExt {
     /// ```
     fn get_custom_segments<T: CustomSegment>(&self) -> Result<Vec<T>>;
 
+    /// Shorthand for [`get_custom_segment`](MessageExt::get_custom_segment)
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let zpv: Option<ZPV> = message.get_custom::<ZPV>()?;
+    /// ```
+    fn get_custom<T: CustomSegment>(&self) -> Result<Option<T>> {
+        self.get_custom_segment::<T>()
+    }
+
+    /// Shorthand for [`get_custom_segments`](MessageExt::get_custom_segments)
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let zpvs: Vec<ZPV> = message.get_all_custom::<ZPV>()?;
+    /// ```
+    fn get_all_custom<T: CustomSegment>(&self) -> Result<Vec<T>> {
+        self.get_custom_segments::<T>()
+    }
+
     /// Check if a message contains a custom segment of the specified type
     ///
     /// # Example
@@ -281,6 +303,21 @@ mod tests {
         assert_eq!(zexs[1].test_field, "SecondValue");
     }
 
+    #[test]
+    fn test_get_custom_shorthands() {
+        let mut msg = create_test_message();
+        msg.add_segment(Segment::new("ZEX"));
+
+        let zex = msg.get_custom::<TestZEX>().unwrap().unwrap();
+        assert_eq!(zex.test_field, "TestValue");
+
+        // The second ZEX lacks its required field
+        assert!(msg.get_all_custom::<TestZEX>().is_err());
+
+        msg.segments.pop();
+        assert_eq!(msg.get_all_custom::<TestZEX>().unwrap().len(), 1);
+    }
+
     #[test]
     fn test_has_custom_segment() {
         let msg = create_test_message();
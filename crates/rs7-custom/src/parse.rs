@@ -0,0 +1,195 @@
+//! Registry-driven parsing of custom Z-segments
+//!
+//! This module combines the standard HL7 parser with a [`CustomSegmentRegistry`]
+//! so that every registered Z-segment in a message is parsed into its typed form
+//! up front, instead of calling `from_segment` by hand for each one.
+
+use crate::error::{CustomSegmentError, Result};
+use crate::registry::CustomSegmentRegistry;
+use crate::segment::CustomSegment;
+use rs7_core::message::Message;
+use std::any::Any;
+
+/// A parsed message together with its typed custom segments
+///
+/// The underlying [`Message`] is left untouched; registered Z-segments are
+/// additionally available as typed values, keyed by their position in
+/// `message.segments`.
+pub struct ParsedMessage {
+    message: Message,
+    custom: Vec<(usize, Box<dyn Any + Send>)>,
+}
+
+impl ParsedMessage {
+    /// Get the underlying message
+    pub fn message(&self) -> &Message {
+        &self.message
+    }
+
+    /// Consume this value and return the underlying message
+    pub fn into_message(self) -> Message {
+        self.message
+    }
+
+    /// Get the first parsed custom segment of type `T`
+    ///
+    /// Returns `None` if the message has no such segment or if `T` was not
+    /// registered when the message was parsed.
+    pub fn custom<T: CustomSegment + 'static>(&self) -> Option<&T> {
+        self.custom
+            .iter()
+            .find_map(|(_, segment)| segment.downcast_ref::<T>())
+    }
+
+    /// Get all parsed custom segments of type `T`, in message order
+    pub fn all_custom<T: CustomSegment + 'static>(&self) -> Vec<&T> {
+        self.custom
+            .iter()
+            .filter_map(|(_, segment)| segment.downcast_ref::<T>())
+            .collect()
+    }
+
+    /// Get the parsed custom segment at a position in `message.segments`
+    pub fn custom_at<T: CustomSegment + 'static>(&self, index: usize) -> Option<&T> {
+        self.custom
+            .iter()
+            .find(|(position, _)| *position == index)
+            .and_then(|(_, segment)| segment.downcast_ref::<T>())
+    }
+
+    /// Get the number of custom segments that were parsed
+    pub fn custom_count(&self) -> usize {
+        self.custom.len()
+    }
+}
+
+impl std::fmt::Debug for ParsedMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParsedMessage")
+            .field("message", &self.message)
+            .field(
+                "custom_positions",
+                &self.custom.iter().map(|(pos, _)| *pos).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// Parse an HL7 message and every Z-segment registered in `registry`
+///
+/// Segments whose IDs are not registered stay generic. A registered segment
+/// that fails to parse or validate fails the whole call.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use rs7_custom::{parse_message_with_registry, CustomSegmentRegistry};
+///
+/// CustomSegmentRegistry::global().register::<ZPV>()?;
+///
+/// let parsed = parse_message_with_registry(hl7, CustomSegmentRegistry::global())?;
+/// if let Some(zpv) = parsed.custom::<ZPV>() {
+///     println!("Visit: {}", zpv.visit_number);
+/// }
+/// ```
+pub fn parse_message_with_registry(
+    input: &str,
+    registry: &CustomSegmentRegistry,
+) -> Result<ParsedMessage> {
+    let message = rs7_parser::parse_message(input)
+        .map_err(|e| CustomSegmentError::ParserError(e.to_string()))?;
+
+    let mut custom = Vec::new();
+    for (index, segment) in message.segments.iter().enumerate() {
+        if let Some(parsed) = registry.parse_segment(segment)? {
+            custom.push((index, parsed));
+        }
+    }
+
+    Ok(ParsedMessage { message, custom })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::z_segment;
+
+    z_segment! {
+        ZPV,
+        id = "ZPV",
+        fields = {
+            1 => visit_type: String,
+            2 => visit_number: Option<String>,
+        }
+    }
+
+    z_segment! {
+        ZCU,
+        id = "ZCU",
+        fields = {
+            1 => customer_id: String,
+        }
+    }
+
+    const MESSAGE: &str = "MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5\r\
+                           PID|||12345\r\
+                           ZPV|OUTPATIENT|V1\r\
+                           ZCU|C42\r\
+                           ZPV|INPATIENT";
+
+    #[test]
+    fn test_parse_registered_segments() {
+        let registry = CustomSegmentRegistry::new();
+        registry.register::<ZPV>().unwrap();
+        registry.register::<ZCU>().unwrap();
+
+        let parsed = parse_message_with_registry(MESSAGE, &registry).unwrap();
+        assert_eq!(parsed.custom_count(), 3);
+        assert_eq!(parsed.message().segments.len(), 5);
+
+        let expected = ZPV::builder()
+            .visit_type("OUTPATIENT")
+            .visit_number("V1")
+            .build()
+            .unwrap();
+        assert_eq!(parsed.custom::<ZPV>(), Some(&expected));
+
+        let all: Vec<_> = parsed
+            .all_custom::<ZPV>()
+            .iter()
+            .map(|z| z.visit_type.as_str())
+            .collect();
+        assert_eq!(all, vec!["OUTPATIENT", "INPATIENT"]);
+
+        let expected = ZCU::builder().customer_id("C42").build().unwrap();
+        assert_eq!(parsed.custom_at::<ZCU>(3), Some(&expected));
+        assert!(parsed.custom_at::<ZCU>(2).is_none());
+    }
+
+    #[test]
+    fn test_unregistered_segments_stay_generic() {
+        let registry = CustomSegmentRegistry::new();
+        registry.register::<ZCU>().unwrap();
+
+        let parsed = parse_message_with_registry(MESSAGE, &registry).unwrap();
+        assert_eq!(parsed.custom_count(), 1);
+        assert!(parsed.custom::<ZPV>().is_none());
+        assert_eq!(parsed.into_message().segments[2].id, "ZPV");
+    }
+
+    #[test]
+    fn test_invalid_custom_segment_fails() {
+        let registry = CustomSegmentRegistry::new();
+        registry.register::<ZCU>().unwrap();
+
+        let input = "MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5\rZCU";
+        assert!(parse_message_with_registry(input, &registry).is_err());
+    }
+
+    #[test]
+    fn test_invalid_message_fails() {
+        let registry = CustomSegmentRegistry::new();
+        let result = parse_message_with_registry("not hl7", &registry);
+        assert!(matches!(result, Err(CustomSegmentError::ParserError(_))));
+    }
+}
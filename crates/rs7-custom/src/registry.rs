@@ -3,7 +3,7 @@
 use crate::error::{CustomSegmentError, Result};
 use crate::segment::CustomSegment;
 use once_cell::sync::Lazy;
-use rs7_core::Segment;
+use rs7_core::{Message, Segment};
 use std::any::Any;
 use std::collections::HashMap;
 use std::sync::RwLock;
@@ -278,6 +278,43 @@ impl CustomSegmentRegistry {
         }
     }
 
+    /// Validate every registered custom segment in a message
+    ///
+    /// Each segment whose ID is registered is parsed into its custom type and
+    /// checked with [`CustomSegment::validate`]. Segments that are not
+    /// registered are skipped.
+    ///
+    /// # Returns
+    ///
+    /// The parsing and validation failures, in message order; empty if all
+    /// custom segments are valid
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let errors = CustomSegmentRegistry::global().validate_message(&message);
+    /// for error in &errors {
+    ///     eprintln!("{}", error);
+    /// }
+    /// ```
+    pub fn validate_message(&self, message: &Message) -> Vec<CustomSegmentError> {
+        let factories = match self.factories.read() {
+            Ok(factories) => factories,
+            Err(e) => {
+                return vec![CustomSegmentError::Other(format!(
+                    "Failed to acquire read lock: {}",
+                    e
+                ))]
+            }
+        };
+
+        message
+            .segments
+            .iter()
+            .filter_map(|segment| factories.get(&segment.id)?.create(segment).err())
+            .collect()
+    }
+
     /// Clear all registered segments (mainly for testing)
     ///
     /// # Example
@@ -323,6 +360,16 @@ mod tests {
             let _ = segment.set_field_value(1, &self.visit_type);
             segment
         }
+
+        fn validate(&self) -> Result<()> {
+            if self.visit_type.is_empty() {
+                return Err(CustomSegmentError::validation_failed(
+                    "ZPV",
+                    "Visit type cannot be empty",
+                ));
+            }
+            Ok(())
+        }
     }
 
     #[test]
@@ -384,6 +431,32 @@ mod tests {
         assert!(result.unwrap().is_some());
     }
 
+    #[test]
+    fn test_validate_message() {
+        let registry = CustomSegmentRegistry::new();
+        registry.register::<TestZPV>().unwrap();
+
+        let valid = TestZPV {
+            visit_type: "OUTPATIENT".to_string(),
+        };
+        let mut message = Message::new();
+        message.add_segment(Segment::new("PID"));
+        message.add_segment(valid.to_segment());
+        message.add_segment(Segment::new("ZXY"));
+        assert!(registry.validate_message(&message).is_empty());
+
+        // Empty visit type fails validate(), missing ZPV-1 fails parsing
+        let mut empty = Segment::new("ZPV");
+        empty.set_field_value(1, "").unwrap();
+        message.add_segment(empty);
+        message.add_segment(Segment::new("ZPV"));
+
+        let errors = registry.validate_message(&message);
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0], CustomSegmentError::ValidationFailed { .. }));
+        assert!(matches!(errors[1], CustomSegmentError::MissingField { .. }));
+    }
+
     #[test]
     fn test_parse_unregistered_segment() {
         let registry = CustomSegmentRegistry::new();
@@ -1174,6 +1174,14 @@ fn parse_hl7_datetime_utc(s: &str) -> Option<DateTime<Utc>> {
 // ============================================================================
 // Vec<T> implementations for repeating fields
 // ============================================================================
+//
+// Every repetition of the field becomes one element, and serialization writes
+// the elements back as repetitions (`a~b~c`).
+//
+// Empty repetitions (`a~~c`) carry no value: `Vec<String>` and the numeric and
+// boolean vecs skip them, as they skip repetitions that do not parse. Use
+// `Vec<Option<String>>` when repetition positions matter; it yields `None` for
+// each empty repetition and writes `None` back as an empty repetition.
 
 // Vec<String> - Repeating text fields
 impl BuildableField for Vec<String> {
@@ -1195,7 +1203,7 @@ impl ParseSegmentField for Vec<String> {
             let values: Vec<String> = field
                 .repetitions
                 .iter()
-                .filter_map(|rep| rep.value().map(|s| s.to_string()))
+                .filter_map(|rep| rep.value().filter(|s| !s.is_empty()).map(|s| s.to_string()))
                 .collect();
             Ok(values)
         } else {
@@ -1220,6 +1228,55 @@ impl BuilderFieldType for Vec<String> {
     type Inner = Vec<String>;
 }
 
+// Vec<Option<String>> - Repeating text fields keeping empty repetitions
+impl BuildableField for Vec<Option<String>> {
+    type Storage = Vec<Option<String>>;
+    type Inner = Vec<Option<String>>;
+
+    fn set_value(storage: &mut Self::Storage, value: Self::Inner) {
+        *storage = value;
+    }
+
+    fn build_value(storage: Self::Storage, _field_name: &str, _seg_id: &str) -> Result<Self> {
+        Ok(storage)
+    }
+}
+
+impl ParseSegmentField for Vec<Option<String>> {
+    fn parse_field(segment: &Segment, field_num: usize, _seg_id: &str) -> Result<Self> {
+        if let Some(field) = segment.get_field(field_num) {
+            let values: Vec<Option<String>> = field
+                .repetitions
+                .iter()
+                .map(|rep| rep.value().filter(|s| !s.is_empty()).map(|s| s.to_string()))
+                .collect();
+            // A field that is entirely empty has no repetitions
+            if values.iter().all(Option::is_none) {
+                return Ok(Vec::new());
+            }
+            Ok(values)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+}
+
+impl SerializeSegmentField for Vec<Option<String>> {
+    fn set_field(&self, segment: &mut Segment, field_num: usize) {
+        use rs7_core::{Field, Repetition};
+
+        let mut field = Field::new();
+        for value in self {
+            field.add_repetition(Repetition::from_value(value.as_deref().unwrap_or_default()));
+        }
+        let _ = segment.set_field(field_num, field);
+    }
+}
+
+impl BuilderFieldType for Vec<Option<String>> {
+    type Inner = Vec<Option<String>>;
+}
+
 // Vec<u32> - Repeating unsigned integers
 impl BuildableField for Vec<u32> {
     type Storage = Vec<u32>;
@@ -3147,6 +3204,47 @@ mod tests {
         assert!(encoded.contains("555-1234~555-5678~555-9999"));
     }
 
+    #[test]
+    fn test_vec_string_skips_empty_repetitions() {
+        use rs7_parser::parse_message;
+
+        let message = parse_message("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5\rZRP|a~~c~").unwrap();
+        let segment = &message.segments[1];
+
+        let values = Vec::<String>::parse_field(segment, 1, "ZRP").unwrap();
+        assert_eq!(values, vec!["a", "c"]);
+    }
+
+    #[test]
+    fn test_vec_option_string_keeps_positions() {
+        use rs7_core::Delimiters;
+        use rs7_parser::parse_message;
+
+        let message = parse_message("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5\rZRP|a~~c|").unwrap();
+        let segment = &message.segments[1];
+
+        let values = Vec::<Option<String>>::parse_field(segment, 1, "ZRP").unwrap();
+        assert_eq!(values, vec![Some("a".to_string()), None, Some("c".to_string())]);
+
+        // An empty field is an empty vec, not a single None
+        assert!(Vec::<Option<String>>::parse_field(segment, 2, "ZRP").unwrap().is_empty());
+        assert!(Vec::<Option<String>>::parse_field(segment, 5, "ZRP").unwrap().is_empty());
+
+        let mut out = Segment::new("ZRP");
+        values.set_field(&mut out, 1);
+        assert_eq!(out.encode(&Delimiters::default()), "ZRP|a~~c");
+    }
+
+    #[test]
+    fn test_vec_numeric_skips_empty_repetitions() {
+        use rs7_parser::parse_message;
+
+        let message = parse_message("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5\rZRP|1.5~~2.5~x").unwrap();
+        let segment = &message.segments[1];
+
+        assert_eq!(Vec::<f64>::parse_field(segment, 1, "ZRP").unwrap(), vec![1.5, 2.5]);
+    }
+
     #[test]
     fn test_vec_empty_serializes_correctly() {
         let mut segment = Segment::new("TEST");
@@ -5,11 +5,20 @@ use rs7_terser::Terser;
 use crate::error::{ConversionError, ConversionResult};
 use crate::resources::allergy_intolerance::*;
 use crate::resources::common::*;
+use super::config::ConverterConfig;
 
 pub struct AllergyIntoleranceConverter;
 
 impl AllergyIntoleranceConverter {
     pub fn convert_all(message: &Message) -> ConversionResult<Vec<AllergyIntolerance>> {
+        Self::convert_all_with_config(message, &ConverterConfig::default())
+    }
+
+    /// Same as [`Self::convert_all`], resolving identifier systems through `config`
+    pub fn convert_all_with_config(
+        message: &Message,
+        config: &ConverterConfig,
+    ) -> ConversionResult<Vec<AllergyIntolerance>> {
         let al1_segments: Vec<_> = message
             .segments
             .iter()
@@ -23,7 +32,7 @@ impl AllergyIntoleranceConverter {
 
         let mut allergies = Vec::new();
         for (al1_count, _) in al1_segments.iter().enumerate() {
-            let allergy = Self::convert_single(message, al1_count)?;
+            let allergy = Self::convert_single_with_config(message, al1_count, config)?;
             allergies.push(allergy);
         }
 
@@ -31,6 +40,18 @@ impl AllergyIntoleranceConverter {
     }
 
     pub fn convert_single(message: &Message, al1_index: usize) -> ConversionResult<AllergyIntolerance> {
+        Self::convert_single_with_config(message, al1_index, &ConverterConfig::default())
+    }
+
+    /// Same as [`Self::convert_single`], resolving identifier systems through `config`
+    ///
+    /// The patient reference carries the PID-3 identifier, with the system
+    /// resolved from its assigning authority or the default MRN system.
+    pub fn convert_single_with_config(
+        message: &Message,
+        al1_index: usize,
+        config: &ConverterConfig,
+    ) -> ConversionResult<AllergyIntolerance> {
         let terser = Terser::new(message);
         let mut allergy = AllergyIntolerance::new();
 
@@ -73,14 +94,7 @@ impl AllergyIntoleranceConverter {
         }
 
         // Link to patient
-        if let Ok(Some(patient_id)) = terser.get("PID-3") {
-            allergy.patient = Some(Reference {
-                reference: Some(format!("Patient/{}", patient_id)),
-                type_: Some("Patient".to_string()),
-                identifier: None,
-                display: None,
-            });
-        }
+        allergy.patient = config.patient_reference(&terser);
 
         allergy.clinical_status = Some(CodeableConcept {
             coding: Some(vec![Coding {
@@ -5,11 +5,23 @@ use rs7_terser::Terser;
 use crate::error::{ConversionError, ConversionResult};
 use crate::resources::condition::*;
 use crate::resources::common::*;
+use super::config::ConverterConfig;
 
 pub struct ConditionConverter;
 
 impl ConditionConverter {
     pub fn convert_all(message: &Message) -> ConversionResult<Vec<Condition>> {
+        Self::convert_all_with_config(message, &ConverterConfig::default())
+    }
+
+    /// Same as [`Self::convert_all`], resolving identifier systems through `config`
+    ///
+    /// The patient reference carries the PID-3 identifier, with the system
+    /// resolved from its assigning authority or the default MRN system.
+    pub fn convert_all_with_config(
+        message: &Message,
+        config: &ConverterConfig,
+    ) -> ConversionResult<Vec<Condition>> {
         let prb_segments: Vec<_> = message
             .segments
             .iter()
@@ -24,9 +36,9 @@ impl ConditionConverter {
         let mut conditions = Vec::new();
         for (count, (_, seg)) in prb_segments.iter().enumerate() {
             let condition = if seg.id == "PRB" {
-                Self::convert_prb(message, count)?
+                Self::convert_prb(message, count, config)?
             } else {
-                Self::convert_dg1(message, count)?
+                Self::convert_dg1(message, count, config)?
             };
             conditions.push(condition);
         }
@@ -34,7 +46,7 @@ impl ConditionConverter {
         Ok(conditions)
     }
 
-    fn convert_prb(message: &Message, prb_index: usize) -> ConversionResult<Condition> {
+    fn convert_prb(message: &Message, prb_index: usize, config: &ConverterConfig) -> ConversionResult<Condition> {
         let terser = Terser::new(message);
         let mut condition = Condition::new();
 
@@ -57,14 +69,7 @@ impl ConditionConverter {
         }
 
         // Link to patient
-        if let Ok(Some(patient_id)) = terser.get("PID-3") {
-            condition.subject = Some(Reference {
-                reference: Some(format!("Patient/{}", patient_id)),
-                type_: Some("Patient".to_string()),
-                identifier: None,
-                display: None,
-            });
-        }
+        condition.subject = config.patient_reference(&terser);
 
         condition.clinical_status = Some(CodeableConcept {
             coding: Some(vec![Coding {
@@ -79,7 +84,7 @@ impl ConditionConverter {
         Ok(condition)
     }
 
-    fn convert_dg1(message: &Message, dg1_index: usize) -> ConversionResult<Condition> {
+    fn convert_dg1(message: &Message, dg1_index: usize, config: &ConverterConfig) -> ConversionResult<Condition> {
         let terser = Terser::new(message);
         let mut condition = Condition::new();
 
@@ -108,14 +113,7 @@ impl ConditionConverter {
         }
 
         // Link to patient
-        if let Ok(Some(patient_id)) = terser.get("PID-3") {
-            condition.subject = Some(Reference {
-                reference: Some(format!("Patient/{}", patient_id)),
-                type_: Some("Patient".to_string()),
-                identifier: None,
-                display: None,
-            });
-        }
+        condition.subject = config.patient_reference(&terser);
 
         Ok(condition)
     }
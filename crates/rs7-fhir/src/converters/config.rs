@@ -0,0 +1,213 @@
+//! Converter configuration shared by the HL7 v2 to FHIR converters
+//!
+//! HL7 v2 identifiers (CX, XCN) carry an assigning authority rather than a
+//! FHIR `system` URI. `ConverterConfig` maps assigning authorities to system
+//! URIs so converted identifiers can be matched by a FHIR server.
+
+use std::collections::HashMap;
+
+use rs7_terser::Terser;
+
+use crate::resources::common::{Identifier, Reference};
+
+/// FHIR system for US National Provider Identifiers
+pub const NPI_SYSTEM: &str = "http://hl7.org/fhir/sid/us-npi";
+
+/// Configuration for HL7 v2 to FHIR conversion
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use rs7_fhir::converters::{ConverterConfig, PatientConverter};
+///
+/// let config = ConverterConfig::new()
+///     .with_identifier_system("HOSP", "http://hospital.example.org/mrn")
+///     .with_default_mrn_system("http://hospital.example.org/patients");
+/// let patient = PatientConverter::convert_with_config(&message, &config)?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ConverterConfig {
+    /// FHIR identifier system URIs keyed by HL7 assigning authority (CX-4, XCN-9, EI-2)
+    pub identifier_systems: HashMap<String, String>,
+
+    /// System for patient identifiers (PID-3) whose assigning authority is not mapped
+    pub default_mrn_system: Option<String>,
+
+    /// System for practitioner identifiers (XCN) whose assigning authority is not mapped
+    pub default_practitioner_system: Option<String>,
+
+    /// System for visit numbers (PV1-19) whose assigning authority is not mapped
+    pub visit_number_system: Option<String>,
+
+    /// System for placer order numbers (ORC-2, OBR-2) whose namespace is not mapped
+    pub placer_order_system: Option<String>,
+
+    /// System for filler order numbers (ORC-3, OBR-3) whose namespace is not mapped
+    pub filler_order_system: Option<String>,
+
+    /// System for specimen identifiers (SPM-2) whose namespace is not mapped
+    pub specimen_id_system: Option<String>,
+
+    /// System for specimen accession identifiers (SPM-30) whose assigning authority is not mapped
+    pub accession_system: Option<String>,
+}
+
+impl ConverterConfig {
+    /// Create an empty configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map an assigning authority to a FHIR identifier system URI
+    pub fn with_identifier_system(mut self, authority: impl Into<String>, system: impl Into<String>) -> Self {
+        self.identifier_systems.insert(authority.into(), system.into());
+        self
+    }
+
+    /// Set the fallback system for patient identifiers
+    pub fn with_default_mrn_system(mut self, system: impl Into<String>) -> Self {
+        self.default_mrn_system = Some(system.into());
+        self
+    }
+
+    /// Set the fallback system for practitioner identifiers
+    pub fn with_default_practitioner_system(mut self, system: impl Into<String>) -> Self {
+        self.default_practitioner_system = Some(system.into());
+        self
+    }
+
+    /// Set the fallback system for visit numbers
+    pub fn with_visit_number_system(mut self, system: impl Into<String>) -> Self {
+        self.visit_number_system = Some(system.into());
+        self
+    }
+
+    /// Set the fallback system for placer order numbers
+    pub fn with_placer_order_system(mut self, system: impl Into<String>) -> Self {
+        self.placer_order_system = Some(system.into());
+        self
+    }
+
+    /// Set the fallback system for filler order numbers
+    pub fn with_filler_order_system(mut self, system: impl Into<String>) -> Self {
+        self.filler_order_system = Some(system.into());
+        self
+    }
+
+    /// Set the fallback system for specimen identifiers
+    pub fn with_specimen_id_system(mut self, system: impl Into<String>) -> Self {
+        self.specimen_id_system = Some(system.into());
+        self
+    }
+
+    /// Set the fallback system for specimen accession identifiers
+    pub fn with_accession_system(mut self, system: impl Into<String>) -> Self {
+        self.accession_system = Some(system.into());
+        self
+    }
+
+    /// Resolve the FHIR system for an identifier
+    ///
+    /// A mapped assigning authority wins, then the given default. Without
+    /// either, an unmapped authority becomes a `urn:oid:` URI.
+    pub fn identifier_system(&self, authority: Option<&str>, default: Option<&str>) -> Option<String> {
+        let authority = authority.filter(|a| !a.is_empty());
+
+        if let Some(system) = authority.and_then(|a| self.identifier_systems.get(a)) {
+            return Some(system.clone());
+        }
+        if let Some(default) = default {
+            return Some(default.to_string());
+        }
+        authority.map(|a| format!("urn:oid:{}", a))
+    }
+
+    /// Resolve the FHIR system for a practitioner identifier (XCN)
+    ///
+    /// Falls back to the default practitioner system, or for NPIs (XCN-13
+    /// `NPI`) to the well-known NPI system.
+    pub fn practitioner_system(&self, authority: Option<&str>, id_type: Option<&str>) -> Option<String> {
+        let default_system = self
+            .default_practitioner_system
+            .as_deref()
+            .or(if id_type == Some("NPI") { Some(NPI_SYSTEM) } else { None });
+        self.identifier_system(authority, default_system)
+    }
+
+    /// Reference to the patient in PID-3, identified through its assigning authority (CX-4)
+    pub(crate) fn patient_reference(&self, terser: &Terser) -> Option<Reference> {
+        let id = non_empty(terser, "PID-3")?;
+        let system = self.identifier_system(
+            terser.get("PID-3-4").ok().flatten(),
+            self.default_mrn_system.as_deref(),
+        );
+        Some(identified_reference("Patient", id, system))
+    }
+
+    /// Reference to the practitioner in an XCN field, identified through XCN-9 and XCN-13
+    pub(crate) fn practitioner_reference(&self, terser: &Terser, path: &str) -> Option<Reference> {
+        let id = non_empty(terser, path)?;
+        let system = self.practitioner_system(
+            terser.get(&format!("{}-9", path)).ok().flatten(),
+            terser.get(&format!("{}-13", path)).ok().flatten(),
+        );
+        Some(identified_reference("Practitioner", id, system))
+    }
+
+    /// Reference to the encounter in PV1-19, identified through its assigning authority (CX-4)
+    pub(crate) fn encounter_reference(&self, terser: &Terser) -> Option<Reference> {
+        let id = non_empty(terser, "PV1-19")?;
+        let system = self.identifier_system(
+            terser.get("PV1-19-4").ok().flatten(),
+            self.visit_number_system.as_deref(),
+        );
+        Some(identified_reference("Encounter", id, system))
+    }
+}
+
+/// Get a non-empty value from the message
+fn non_empty<'t>(terser: &'t Terser, path: &str) -> Option<&'t str> {
+    terser.get(path).ok().flatten().filter(|v| !v.is_empty())
+}
+
+/// Literal reference to `resource_type/id` that also carries the identifier
+fn identified_reference(resource_type: &str, id: &str, system: Option<String>) -> Reference {
+    Reference {
+        reference: Some(format!("{}/{}", resource_type, id)),
+        type_: Some(resource_type.to_string()),
+        identifier: Some(Box::new(Identifier {
+            use_: None,
+            type_: None,
+            system,
+            value: Some(id.to_string()),
+            assigner: None,
+        })),
+        display: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identifier_system_resolution() {
+        let config = ConverterConfig::new()
+            .with_identifier_system("HOSP", "http://hospital.example.org/mrn");
+
+        assert_eq!(
+            config.identifier_system(Some("HOSP"), Some("http://default")),
+            Some("http://hospital.example.org/mrn".to_string())
+        );
+        assert_eq!(
+            config.identifier_system(Some("OTHER"), Some("http://default")),
+            Some("http://default".to_string())
+        );
+        assert_eq!(
+            config.identifier_system(Some("1.2.3"), None),
+            Some("urn:oid:1.2.3".to_string())
+        );
+        assert_eq!(config.identifier_system(Some(""), None), None);
+        assert_eq!(config.identifier_system(None, None), None);
+    }
+}
@@ -5,6 +5,7 @@ use rs7_terser::Terser;
 use crate::error::{ConversionError, ConversionResult};
 use crate::resources::diagnostic_report::*;
 use crate::resources::common::*;
+use super::config::ConverterConfig;
 
 /// Converter for transforming OBR segments to FHIR DiagnosticReport resources
 pub struct DiagnosticReportConverter;
@@ -12,6 +13,14 @@ pub struct DiagnosticReportConverter;
 impl DiagnosticReportConverter {
     /// Convert an HL7 v2 message containing OBR segments to FHIR DiagnosticReport resources
     pub fn convert_all(message: &Message) -> ConversionResult<Vec<DiagnosticReport>> {
+        Self::convert_all_with_config(message, &ConverterConfig::default())
+    }
+
+    /// Convert OBR segments to FHIR DiagnosticReport resources using the given configuration
+    pub fn convert_all_with_config(
+        message: &Message,
+        config: &ConverterConfig,
+    ) -> ConversionResult<Vec<DiagnosticReport>> {
         let obr_segments: Vec<_> = message
             .segments
             .iter()
@@ -25,7 +34,7 @@ impl DiagnosticReportConverter {
 
         let mut reports = Vec::new();
         for (obr_count, _) in obr_segments.iter().enumerate() {
-            let report = Self::convert_single(message, obr_count)?;
+            let report = Self::convert_single_with_config(message, obr_count, config)?;
             reports.push(report);
         }
 
@@ -34,6 +43,18 @@ impl DiagnosticReportConverter {
 
     /// Convert a single OBR segment to a FHIR DiagnosticReport resource
     pub fn convert_single(message: &Message, obr_index: usize) -> ConversionResult<DiagnosticReport> {
+        Self::convert_single_with_config(message, obr_index, &ConverterConfig::default())
+    }
+
+    /// Convert a single OBR segment to a FHIR DiagnosticReport resource using the given configuration
+    ///
+    /// The placer order number system is resolved from the OBR-2 namespace ID
+    /// (EI-2) through `config`, falling back to its placer order system.
+    pub fn convert_single_with_config(
+        message: &Message,
+        obr_index: usize,
+        config: &ConverterConfig,
+    ) -> ConversionResult<DiagnosticReport> {
         let terser = Terser::new(message);
 
         // OBR-4: Universal Service Identifier -> DiagnosticReport.code
@@ -99,7 +120,10 @@ impl DiagnosticReportConverter {
                 report.identifier = Some(vec![Identifier {
                     use_: Some("official".to_string()),
                     type_: None,
-                    system: None,
+                    system: config.identifier_system(
+                        terser.get(&format!("{}-2", placer_path)).ok().flatten(),
+                        config.placer_order_system.as_deref(),
+                    ),
                     value: Some(placer.to_string()),
                     assigner: None,
                 }]);
@@ -212,6 +236,27 @@ mod tests {
     use super::*;
     use rs7_parser::parse_message;
 
+    #[test]
+    fn test_placer_order_system() {
+        let hl7 = "MSH|^~\\&|LAB|Hospital|EMR|Hospital|20240315||ORU^R01|MSG123|P|2.5\r\
+                   PID|1||12345|||DOE^JOHN||19800101|M\r\
+                   OBR|1|LAB123^CPOE||24331-1^Lipid Panel^LN|||||||||||||||||||||F";
+        let message = parse_message(hl7).unwrap();
+
+        let config = ConverterConfig::new().with_placer_order_system("http://hospital.example.org/orders");
+        let report = &DiagnosticReportConverter::convert_all_with_config(&message, &config).unwrap()[0];
+        let identifier = &report.identifier.as_ref().unwrap()[0];
+        assert_eq!(identifier.value, Some("LAB123".to_string()));
+        assert_eq!(identifier.system, Some("http://hospital.example.org/orders".to_string()));
+
+        let config = config.with_identifier_system("CPOE", "http://hospital.example.org/cpoe-orders");
+        let report = &DiagnosticReportConverter::convert_all_with_config(&message, &config).unwrap()[0];
+        assert_eq!(
+            report.identifier.as_ref().unwrap()[0].system,
+            Some("http://hospital.example.org/cpoe-orders".to_string())
+        );
+    }
+
     #[test]
     fn test_convert_diagnostic_report() {
         let hl7 = "MSH|^~\\&|LAB|Hospital|EMR|Hospital|20240315||ORU^R01|MSG123|P|2.5\r\
@@ -7,6 +7,7 @@ use rs7_terser::Terser;
 use crate::error::{ConversionError, ConversionResult};
 use crate::resources::encounter::*;
 use crate::resources::common::*;
+use super::config::ConverterConfig;
 
 /// Converter for transforming PV1 segments to FHIR Encounter resources
 pub struct EncounterConverter;
@@ -26,6 +27,14 @@ impl EncounterConverter {
     ///
     /// Returns an error if the PV1 segment is not found or required fields are missing
     pub fn convert(message: &Message) -> ConversionResult<Encounter> {
+        Self::convert_with_config(message, &ConverterConfig::default())
+    }
+
+    /// Convert an HL7 v2 message to a FHIR Encounter resource using the given configuration
+    ///
+    /// The visit number system is resolved from the PV1-19 assigning authority
+    /// (CX-4) through `config`, falling back to its visit number system.
+    pub fn convert_with_config(message: &Message, config: &ConverterConfig) -> ConversionResult<Encounter> {
         let terser = Terser::new(message);
 
         // Check if PV1 segment exists
@@ -55,7 +64,10 @@ impl EncounterConverter {
                         }]),
                         text: Some("Visit Number".to_string()),
                     }),
-                    system: None,
+                    system: config.identifier_system(
+                        terser.get("PV1-19-4").ok().flatten(),
+                        config.visit_number_system.as_deref(),
+                    ),
                     value: Some(visit_number.to_string()),
                     assigner: None,
                 }]);
@@ -350,6 +362,27 @@ mod tests {
         assert_eq!(subject.reference, Some("Patient/MRN123".to_string()));
     }
 
+    #[test]
+    fn test_visit_number_system() {
+        let hl7 = "MSH|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315||ADT^A01|12345|P|2.5\r\
+                   PID|1||MRN123^^^MRN||DOE^JOHN||19800101|M\r\
+                   PV1|1|I|4N^401^01||||||||||||||||V100^^^HOSP";
+        let message = parse_message(hl7).unwrap();
+
+        let config = ConverterConfig::new().with_visit_number_system("http://hospital.example.org/visits");
+        let encounter = EncounterConverter::convert_with_config(&message, &config).unwrap();
+        let identifier = &encounter.identifier.unwrap()[0];
+        assert_eq!(identifier.value, Some("V100".to_string()));
+        assert_eq!(identifier.system, Some("http://hospital.example.org/visits".to_string()));
+
+        let config = config.with_identifier_system("HOSP", "http://hospital.example.org/hosp-visits");
+        let encounter = EncounterConverter::convert_with_config(&message, &config).unwrap();
+        assert_eq!(
+            encounter.identifier.unwrap()[0].system,
+            Some("http://hospital.example.org/hosp-visits".to_string())
+        );
+    }
+
     #[test]
     fn test_convert_outpatient_encounter() {
         let hl7 = "MSH|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315||ADT^A04|12345|P|2.5\r\
@@ -5,12 +5,21 @@ use rs7_terser::Terser;
 use crate::error::{ConversionError, ConversionResult};
 use crate::resources::immunization::*;
 use crate::resources::common::*;
+use super::config::ConverterConfig;
 
 pub struct ImmunizationConverter;
 
 impl ImmunizationConverter {
     /// Convert all RXA segments in message to Immunization resources
     pub fn convert_all(message: &Message) -> ConversionResult<Vec<Immunization>> {
+        Self::convert_all_with_config(message, &ConverterConfig::default())
+    }
+
+    /// Same as [`Self::convert_all`], resolving identifier systems through `config`
+    pub fn convert_all_with_config(
+        message: &Message,
+        config: &ConverterConfig,
+    ) -> ConversionResult<Vec<Immunization>> {
         let rxa_segments: Vec<_> = message
             .segments
             .iter()
@@ -24,7 +33,7 @@ impl ImmunizationConverter {
 
         let mut immunizations = Vec::new();
         for (rxa_count, _) in rxa_segments.iter().enumerate() {
-            let imm = Self::convert_single(message, rxa_count)?;
+            let imm = Self::convert_single_with_config(message, rxa_count, config)?;
             immunizations.push(imm);
         }
 
@@ -37,6 +46,18 @@ impl ImmunizationConverter {
     /// * `message` - HL7 message containing RXA segment
     /// * `rxa_index` - 0-based index (0 = first RXA, 1 = second RXA, etc.)
     pub fn convert_single(message: &Message, rxa_index: usize) -> ConversionResult<Immunization> {
+        Self::convert_single_with_config(message, rxa_index, &ConverterConfig::default())
+    }
+
+    /// Same as [`Self::convert_single`], resolving identifier systems through `config`
+    ///
+    /// The patient, encounter and administering provider references carry the
+    /// PID-3, PV1-19 and RXA-10 identifiers with systems resolved by `config`.
+    pub fn convert_single_with_config(
+        message: &Message,
+        rxa_index: usize,
+        config: &ConverterConfig,
+    ) -> ConversionResult<Immunization> {
         let terser = Terser::new(message);
 
         // Build base path for RXA fields
@@ -60,7 +81,9 @@ impl ImmunizationConverter {
         let vaccine_code = Self::extract_vaccine_code(&terser, &path("5"))?;
 
         // PID-3: Patient ID -> patient reference
-        let patient = Self::extract_patient_reference(&terser)?;
+        let patient = config
+            .patient_reference(&terser)
+            .ok_or_else(|| ConversionError::MissingSegment("PID".to_string()))?;
 
         // RXA-3: Date/Time Start of Administration -> occurrenceDateTime
         let occurrence_date_time = terser.get(&path("3"))
@@ -78,14 +101,7 @@ impl ImmunizationConverter {
         );
 
         // PV1-19: Visit Number -> encounter reference
-        if let Ok(Some(visit_num)) = terser.get("PV1-19") {
-            immunization.encounter = Some(Reference {
-                reference: Some(format!("Encounter/{}", visit_num)),
-                type_: Some("Encounter".to_string()),
-                identifier: None,
-                display: None,
-            });
-        }
+        immunization.encounter = config.encounter_reference(&terser);
 
         // RXA-22: System Entry Date/Time -> recorded
         if let Ok(Some(recorded_dt)) = terser.get(&path("22")) {
@@ -182,7 +198,7 @@ impl ImmunizationConverter {
         }
 
         // RXA-10: Administering Provider -> performer
-        if let Ok(Some(performer_id)) = terser.get(&path("10")) {
+        if let Some(actor) = config.practitioner_reference(&terser, &path("10")) {
             immunization.performer = Some(vec![ImmunizationPerformer {
                 function: Some(CodeableConcept {
                     coding: Some(vec![Coding {
@@ -193,12 +209,7 @@ impl ImmunizationConverter {
                     }]),
                     text: None,
                 }),
-                actor: Reference {
-                    reference: Some(format!("Practitioner/{}", performer_id)),
-                    type_: Some("Practitioner".to_string()),
-                    identifier: None,
-                    display: None,
-                },
+                actor,
             }]);
         }
 
@@ -317,21 +328,6 @@ impl ImmunizationConverter {
             text: None,
         })
     }
-
-    /// Extract patient reference from PID segment
-    fn extract_patient_reference(terser: &Terser) -> ConversionResult<Reference> {
-        let patient_id = terser.get("PID-3")
-            .ok()
-            .flatten()
-            .ok_or_else(|| ConversionError::MissingSegment("PID".to_string()))?;
-
-        Ok(Reference {
-            reference: Some(format!("Patient/{}", patient_id)),
-            type_: Some("Patient".to_string()),
-            identifier: None,
-            display: None,
-        })
-    }
 }
 
 #[cfg(test)]
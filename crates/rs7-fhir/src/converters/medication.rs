@@ -5,11 +5,20 @@ use rs7_terser::Terser;
 use crate::error::{ConversionError, ConversionResult};
 use crate::resources::medication::*;
 use crate::resources::common::*;
+use super::config::ConverterConfig;
 
 pub struct MedicationConverter;
 
 impl MedicationConverter {
     pub fn convert_all(message: &Message) -> ConversionResult<Vec<MedicationAdministration>> {
+        Self::convert_all_with_config(message, &ConverterConfig::default())
+    }
+
+    /// Same as [`Self::convert_all`], resolving identifier systems through `config`
+    pub fn convert_all_with_config(
+        message: &Message,
+        config: &ConverterConfig,
+    ) -> ConversionResult<Vec<MedicationAdministration>> {
         let rxa_segments: Vec<_> = message
             .segments
             .iter()
@@ -23,7 +32,7 @@ impl MedicationConverter {
 
         let mut administrations = Vec::new();
         for (rxa_count, _) in rxa_segments.iter().enumerate() {
-            let admin = Self::convert_single(message, rxa_count)?;
+            let admin = Self::convert_single_with_config(message, rxa_count, config)?;
             administrations.push(admin);
         }
 
@@ -31,6 +40,18 @@ impl MedicationConverter {
     }
 
     pub fn convert_single(message: &Message, rxa_index: usize) -> ConversionResult<MedicationAdministration> {
+        Self::convert_single_with_config(message, rxa_index, &ConverterConfig::default())
+    }
+
+    /// Same as [`Self::convert_single`], resolving identifier systems through `config`
+    ///
+    /// The patient reference carries the PID-3 identifier, with the system
+    /// resolved from its assigning authority or the default MRN system.
+    pub fn convert_single_with_config(
+        message: &Message,
+        rxa_index: usize,
+        config: &ConverterConfig,
+    ) -> ConversionResult<MedicationAdministration> {
         let terser = Terser::new(message);
 
         // RXA-20: Completion Status -> MedicationAdministration.status
@@ -114,14 +135,7 @@ impl MedicationConverter {
             }
 
         // Link to patient
-        if let Ok(Some(patient_id)) = terser.get("PID-3") {
-            admin.subject = Some(Reference {
-                reference: Some(format!("Patient/{}", patient_id)),
-                type_: Some("Patient".to_string()),
-                identifier: None,
-                display: None,
-            });
-        }
+        admin.subject = config.patient_reference(&terser);
 
         Ok(admin)
     }
@@ -0,0 +1,675 @@
+//! Message converter - whole HL7 v2 message to a FHIR Bundle
+//!
+//! Runs the segment converters over a message and assembles the resulting
+//! resources into a single Bundle, linking them together through each entry's
+//! `fullUrl`.
+
+use std::collections::HashSet;
+
+use chrono::{FixedOffset, SecondsFormat};
+use rs7_core::types::{Timestamp, DateTimePrecision};
+use rs7_core::Message;
+use rs7_terser::Terser;
+use serde::Serialize;
+use crate::error::{ConversionError, ConversionResult};
+use crate::resources::bundle::Bundle;
+use crate::resources::message_header::{MessageDestination, MessageHeader, MessageSource};
+use crate::resources::common::*;
+use super::config::ConverterConfig;
+use super::{
+    AllergyIntoleranceConverter, ConditionConverter, DiagnosticReportConverter, EncounterConverter,
+    ImmunizationConverter, MedicationConverter, ObservationConverter, PatientConverter,
+    PractitionerConverter, ProcedureConverter, ServiceRequestConverter, SpecimenConverter,
+};
+
+/// Default base URL used to build entry `fullUrl`s
+pub const DEFAULT_BASE_URL: &str = "http://localhost/fhir";
+
+/// Type of Bundle produced by the [`MessageConverter`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundleType {
+    /// A `message` bundle, starting with a MessageHeader built from MSH
+    Message,
+    /// A plain `collection` of resources
+    Collection,
+}
+
+impl BundleType {
+    /// Get the FHIR code for this bundle type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BundleType::Message => "message",
+            BundleType::Collection => "collection",
+        }
+    }
+}
+
+/// Resource types the [`MessageConverter`] can emit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResourceType {
+    /// Patient from PID
+    Patient,
+    /// Encounter from PV1
+    Encounter,
+    /// Practitioners from PV1 and ORC
+    Practitioner,
+    /// Observations from OBX
+    Observation,
+    /// DiagnosticReports from OBR
+    DiagnosticReport,
+    /// AllergyIntolerances from AL1
+    AllergyIntolerance,
+    /// Conditions from DG1
+    Condition,
+    /// Procedures from PR1
+    Procedure,
+    /// Immunizations from RXA
+    Immunization,
+    /// MedicationAdministrations from RXA
+    MedicationAdministration,
+    /// ServiceRequests from ORC
+    ServiceRequest,
+    /// Specimens from SPM
+    Specimen,
+}
+
+impl ResourceType {
+    /// Every resource type, in the order entries are added to the bundle
+    pub const ALL: [ResourceType; 12] = [
+        ResourceType::Patient,
+        ResourceType::Encounter,
+        ResourceType::Practitioner,
+        ResourceType::Observation,
+        ResourceType::DiagnosticReport,
+        ResourceType::AllergyIntolerance,
+        ResourceType::Condition,
+        ResourceType::Procedure,
+        ResourceType::Immunization,
+        ResourceType::MedicationAdministration,
+        ResourceType::ServiceRequest,
+        ResourceType::Specimen,
+    ];
+
+    /// Get the FHIR resource type name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResourceType::Patient => "Patient",
+            ResourceType::Encounter => "Encounter",
+            ResourceType::Practitioner => "Practitioner",
+            ResourceType::Observation => "Observation",
+            ResourceType::DiagnosticReport => "DiagnosticReport",
+            ResourceType::AllergyIntolerance => "AllergyIntolerance",
+            ResourceType::Condition => "Condition",
+            ResourceType::Procedure => "Procedure",
+            ResourceType::Immunization => "Immunization",
+            ResourceType::MedicationAdministration => "MedicationAdministration",
+            ResourceType::ServiceRequest => "ServiceRequest",
+            ResourceType::Specimen => "Specimen",
+        }
+    }
+}
+
+/// Converter for transforming a whole HL7 v2 message into a FHIR Bundle
+///
+/// Each enabled resource type is converted from its segments, given an id
+/// and a `fullUrl`, and subject/patient/encounter references are pointed at
+/// the Patient and Encounter entries of the same bundle. Observations are
+/// linked as results of the DiagnosticReport for the OBR they follow.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use rs7_fhir::converters::{BundleType, MessageConverter, ResourceType};
+///
+/// let converter = MessageConverter::new()
+///     .with_bundle_type(BundleType::Collection)
+///     .with_resource_types(&[ResourceType::Patient, ResourceType::Observation]);
+/// let bundle = converter.convert(&message)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct MessageConverter {
+    bundle_type: BundleType,
+    resource_types: Vec<ResourceType>,
+    base_url: String,
+    config: ConverterConfig,
+}
+
+impl MessageConverter {
+    /// Create a converter producing a `message` bundle with every resource type
+    pub fn new() -> Self {
+        Self {
+            bundle_type: BundleType::Message,
+            resource_types: ResourceType::ALL.to_vec(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            config: ConverterConfig::default(),
+        }
+    }
+
+    /// Set the type of bundle to produce
+    pub fn with_bundle_type(mut self, bundle_type: BundleType) -> Self {
+        self.bundle_type = bundle_type;
+        self
+    }
+
+    /// Restrict the resource types that are emitted
+    pub fn with_resource_types(mut self, resource_types: &[ResourceType]) -> Self {
+        self.resource_types = resource_types.to_vec();
+        self
+    }
+
+    /// Set the base URL used to build entry `fullUrl`s
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Set the configuration passed to the segment converters
+    pub fn with_config(mut self, config: ConverterConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Convert an HL7 v2 message to a FHIR Bundle
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the MSH segment is missing for a `message` bundle,
+    /// or if a segment converter fails for a reason other than its segment
+    /// being absent from the message
+    pub fn convert(&self, message: &Message) -> ConversionResult<Bundle> {
+        let terser = Terser::new(message);
+        let mut builder = BundleBuilder::new(&self.base_url);
+
+        // Patient first, since most other resources reference it
+        let patient = if self.emits(ResourceType::Patient) {
+            match optional(PatientConverter::convert_with_config(message, &self.config))? {
+                Some(mut patient) => {
+                    let candidate = patient.id.take().or_else(|| get(&terser, "PID-3"));
+                    let (id, reference) = builder.register("Patient", candidate);
+                    patient.id = Some(id);
+                    builder.push(&reference, &patient)?;
+                    Some(reference)
+                }
+                None => None,
+            }
+        } else {
+            None
+        };
+
+        let encounter = if self.emits(ResourceType::Encounter) {
+            match optional(EncounterConverter::convert_with_config(message, &self.config))? {
+                Some(mut encounter) => {
+                    let (id, reference) = builder.register("Encounter", encounter.id.take());
+                    encounter.id = Some(id);
+                    link(&mut encounter.subject, &patient);
+                    builder.push(&reference, &encounter)?;
+                    Some(reference)
+                }
+                None => None,
+            }
+        } else {
+            None
+        };
+
+        if self.emits(ResourceType::Practitioner) {
+            let practitioners = optional(PractitionerConverter::extract_all_practitioners_with_config(message, &self.config))?.unwrap_or_default();
+            for mut practitioner in practitioners {
+                let (id, reference) = builder.register("Practitioner", practitioner.id.take());
+                practitioner.id = Some(id);
+                builder.push(&reference, &practitioner)?;
+            }
+        }
+
+        // Observations, remembering which OBR each one follows
+        let mut observation_refs: Vec<(Option<usize>, Reference)> = Vec::new();
+        if self.emits(ResourceType::Observation) {
+            let groups = obx_report_groups(message);
+            let observations = optional(ObservationConverter::convert_all_with_config(message, &self.config))?.unwrap_or_default();
+            for (mut observation, group) in observations.into_iter().zip(groups) {
+                let (id, reference) = builder.register("Observation", observation.id.take());
+                observation.id = Some(id);
+                link(&mut observation.subject, &patient);
+                link(&mut observation.encounter, &encounter);
+                builder.push(&reference, &observation)?;
+                observation_refs.push((group, reference));
+            }
+        }
+
+        if self.emits(ResourceType::DiagnosticReport) {
+            let reports = optional(DiagnosticReportConverter::convert_all_with_config(message, &self.config))?.unwrap_or_default();
+            for (index, mut report) in reports.into_iter().enumerate() {
+                let (id, reference) = builder.register("DiagnosticReport", report.id.take());
+                report.id = Some(id);
+                link(&mut report.subject, &patient);
+                link(&mut report.encounter, &encounter);
+                if self.emits(ResourceType::Observation) {
+                    let results: Vec<Reference> = observation_refs
+                        .iter()
+                        .filter(|(group, _)| *group == Some(index))
+                        .map(|(_, r)| r.clone())
+                        .collect();
+                    report.result = if results.is_empty() { None } else { Some(results) };
+                }
+                builder.push(&reference, &report)?;
+            }
+        }
+
+        if self.emits(ResourceType::AllergyIntolerance) {
+            let allergies = optional(AllergyIntoleranceConverter::convert_all_with_config(message, &self.config))?.unwrap_or_default();
+            for mut allergy in allergies {
+                let (id, reference) = builder.register("AllergyIntolerance", allergy.id.take());
+                allergy.id = Some(id);
+                link(&mut allergy.patient, &patient);
+                builder.push(&reference, &allergy)?;
+            }
+        }
+
+        if self.emits(ResourceType::Condition) {
+            let conditions = optional(ConditionConverter::convert_all_with_config(message, &self.config))?.unwrap_or_default();
+            for mut condition in conditions {
+                let (id, reference) = builder.register("Condition", condition.id.take());
+                condition.id = Some(id);
+                link(&mut condition.subject, &patient);
+                link(&mut condition.encounter, &encounter);
+                builder.push(&reference, &condition)?;
+            }
+        }
+
+        if self.emits(ResourceType::Procedure) {
+            let procedures = optional(ProcedureConverter::convert_all_with_config(message, &self.config))?.unwrap_or_default();
+            for mut procedure in procedures {
+                let (id, reference) = builder.register("Procedure", procedure.id.take());
+                procedure.id = Some(id);
+                link(&mut procedure.subject, &patient);
+                link(&mut procedure.encounter, &encounter);
+                builder.push(&reference, &procedure)?;
+            }
+        }
+
+        if self.emits(ResourceType::Immunization) {
+            let immunizations = optional(ImmunizationConverter::convert_all_with_config(message, &self.config))?.unwrap_or_default();
+            for mut immunization in immunizations {
+                let (id, reference) = builder.register("Immunization", immunization.id.take());
+                immunization.id = Some(id);
+                if let Some(ref patient) = patient {
+                    immunization.patient = patient.clone();
+                }
+                link(&mut immunization.encounter, &encounter);
+                builder.push(&reference, &immunization)?;
+            }
+        }
+
+        if self.emits(ResourceType::MedicationAdministration) {
+            let administrations = optional(MedicationConverter::convert_all_with_config(message, &self.config))?.unwrap_or_default();
+            for mut administration in administrations {
+                let (id, reference) = builder.register("MedicationAdministration", administration.id.take());
+                administration.id = Some(id);
+                link(&mut administration.subject, &patient);
+                builder.push(&reference, &administration)?;
+            }
+        }
+
+        if self.emits(ResourceType::ServiceRequest) {
+            let requests = optional(ServiceRequestConverter::convert_all_with_config(message, &self.config))?.unwrap_or_default();
+            for mut request in requests {
+                let (id, reference) = builder.register("ServiceRequest", request.id.take());
+                request.id = Some(id);
+                if let Some(ref patient) = patient {
+                    request.subject = patient.clone();
+                }
+                link(&mut request.encounter, &encounter);
+                builder.push(&reference, &request)?;
+            }
+        }
+
+        if self.emits(ResourceType::Specimen) {
+            let specimens = optional(SpecimenConverter::convert_all_with_config(message, &self.config))?.unwrap_or_default();
+            for mut specimen in specimens {
+                let (id, reference) = builder.register("Specimen", specimen.id.take());
+                specimen.id = Some(id);
+                if let Some(ref patient) = patient {
+                    specimen.subject = patient.clone();
+                }
+                builder.push(&reference, &specimen)?;
+            }
+        }
+
+        let mut bundle = Bundle::new(self.bundle_type.as_str().to_string());
+
+        // MSH-10: Message Control ID -> Bundle.id
+        bundle.id = get(&terser, "MSH-10");
+
+        // MSH-7: Date/Time of Message -> Bundle.timestamp
+        if let Some(timestamp) = get(&terser, "MSH-7") {
+            bundle.timestamp = Some(Self::convert_instant(&timestamp)?);
+        }
+
+        if self.bundle_type == BundleType::Message {
+            let mut header = Self::convert_header(message, &terser, &builder)?;
+            let (id, reference) = builder.register("MessageHeader", bundle.id.clone());
+            header.id = Some(id);
+            bundle.add_entry(reference.reference.unwrap_or_default(), serde_json::to_value(&header)?);
+        }
+
+        for (full_url, resource) in builder.entries {
+            bundle.add_entry(full_url, resource);
+        }
+
+        Ok(bundle)
+    }
+
+    /// Build the MessageHeader for a `message` bundle from MSH
+    fn convert_header(
+        message: &Message,
+        terser: &Terser,
+        builder: &BundleBuilder,
+    ) -> ConversionResult<MessageHeader> {
+        if !message.segments.iter().any(|s| s.id == "MSH") {
+            return Err(ConversionError::MissingSegment("MSH".to_string()));
+        }
+
+        // MSH-9-2: Trigger Event -> MessageHeader.eventCoding
+        let event_code = get(terser, "MSH-9-2").or_else(|| get(terser, "MSH-9"));
+        let event_coding = Coding {
+            system: Some("http://terminology.hl7.org/CodeSystem/v2-0003".to_string()),
+            version: None,
+            code: event_code,
+            display: None,
+        };
+
+        // MSH-3: Sending Application -> MessageHeader.source
+        let sending_app = get(terser, "MSH-3");
+        let source = MessageSource {
+            endpoint: sending_app.clone().unwrap_or_else(|| "unknown".to_string()),
+            name: sending_app,
+        };
+
+        let mut header = MessageHeader::new(event_coding, source);
+
+        // MSH-5: Receiving Application -> MessageHeader.destination
+        if let Some(receiving_app) = get(terser, "MSH-5") {
+            header.destination = Some(vec![MessageDestination {
+                name: Some(receiving_app.clone()),
+                endpoint: receiving_app,
+            }]);
+        }
+
+        // Every converted resource is the focus of the message
+        let focus: Vec<Reference> = builder
+            .entries
+            .iter()
+            .map(|(full_url, resource)| Reference {
+                reference: Some(full_url.clone()),
+                type_: resource.get("resourceType").and_then(|t| t.as_str()).map(String::from),
+                identifier: None,
+                display: None,
+            })
+            .collect();
+        if !focus.is_empty() {
+            header.focus = Some(focus);
+        }
+
+        Ok(header)
+    }
+
+    fn emits(&self, resource_type: ResourceType) -> bool {
+        self.resource_types.contains(&resource_type)
+    }
+
+    /// Convert MSH-7 to a FHIR `instant`
+    ///
+    /// An `instant` always carries a timezone, so the MSH-7 offset is kept
+    /// when present and UTC is assumed otherwise. MSH-7 needs at least a full
+    /// date; missing time parts are taken as zero.
+    fn convert_instant(datetime: &str) -> ConversionResult<String> {
+        let instant = Timestamp::parse(datetime)
+            .filter(|ts| ts.precision >= DateTimePrecision::Day)
+            .and_then(|ts| {
+                let offset = ts.offset.or(FixedOffset::east_opt(0))?;
+                ts.datetime.and_local_timezone(offset).single()
+            })
+            .ok_or_else(|| {
+                ConversionError::InvalidFormat(
+                    "MSH-7".to_string(),
+                    "MSH".to_string(),
+                    format!("Invalid datetime format: {}", datetime),
+                )
+            })?;
+
+        Ok(instant.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+    }
+}
+
+impl Default for MessageConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Entries collected while converting, with the ids already handed out
+struct BundleBuilder {
+    base_url: String,
+    used_ids: HashSet<String>,
+    entries: Vec<(String, serde_json::Value)>,
+}
+
+impl BundleBuilder {
+    fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            used_ids: HashSet::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Pick a unique id for a resource and build a reference to its fullUrl
+    ///
+    /// The candidate id is kept when it is a valid FHIR id not already used
+    /// by another resource of the same type; otherwise one is generated.
+    fn register(&mut self, resource_type: &str, candidate: Option<String>) -> (String, Reference) {
+        let id = match candidate {
+            Some(id) if is_valid_id(&id) && !self.used_ids.contains(&format!("{}/{}", resource_type, id)) => id,
+            _ => {
+                let prefix = resource_type.to_lowercase();
+                (1..)
+                    .map(|n| format!("{}-{}", prefix, n))
+                    .find(|id| !self.used_ids.contains(&format!("{}/{}", resource_type, id)))
+                    .unwrap_or(prefix)
+            }
+        };
+        self.used_ids.insert(format!("{}/{}", resource_type, id));
+
+        let reference = Reference {
+            reference: Some(format!("{}/{}/{}", self.base_url, resource_type, id)),
+            type_: Some(resource_type.to_string()),
+            identifier: None,
+            display: None,
+        };
+        (id, reference)
+    }
+
+    fn push<T: Serialize>(&mut self, reference: &Reference, resource: &T) -> ConversionResult<()> {
+        let full_url = reference.reference.clone().unwrap_or_default();
+        self.entries.push((full_url, serde_json::to_value(resource)?));
+        Ok(())
+    }
+}
+
+/// Treat a missing segment as "nothing to convert" rather than a failure
+fn optional<T>(result: ConversionResult<T>) -> ConversionResult<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(ConversionError::MissingSegment(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Point a reference at a bundle entry, if that entry exists
+fn link(target: &mut Option<Reference>, reference: &Option<Reference>) {
+    if let Some(reference) = reference {
+        *target = Some(reference.clone());
+    }
+}
+
+/// Get a non-empty value from the message
+fn get(terser: &Terser, path: &str) -> Option<String> {
+    terser
+        .get(path)
+        .ok()
+        .flatten()
+        .filter(|v| !v.is_empty())
+        .map(String::from)
+}
+
+/// Check that an id matches the FHIR id pattern `[A-Za-z0-9\-\.]{1,64}`
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 64
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+}
+
+/// For each OBX in the message, the index of the OBR it follows (if any)
+fn obx_report_groups(message: &Message) -> Vec<Option<usize>> {
+    let mut obr_count: usize = 0;
+    let mut groups = Vec::new();
+    for segment in &message.segments {
+        match segment.id.as_str() {
+            "OBR" => obr_count += 1,
+            "OBX" => groups.push(obr_count.checked_sub(1)),
+            _ => {}
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rs7_parser::parse_message;
+
+    const ORU: &str = "MSH|^~\\&|LAB|Hospital|EMR|Hospital|20240315143000||ORU^R01|MSG123|P|2.5\r\
+                       PID|1||12345^^^MRN||DOE^JOHN||19800101|M\r\
+                       PV1|1|I|ICU^101^A|||||||||||||||||V789\r\
+                       OBR|1|LAB123|LAB123-01|24331-1^Lipid Panel^LN|||20240315080000|||||||||||||||20240315100000||||F\r\
+                       OBX|1|NM|2093-3^Cholesterol^LN||195|mg/dL||||F\r\
+                       OBX|2|NM|2571-8^Triglycerides^LN||150|mg/dL||||F";
+
+    #[test]
+    fn test_convert_message_bundle() {
+        let message = parse_message(ORU).unwrap();
+        let bundle = MessageConverter::new().convert(&message).unwrap();
+
+        assert_eq!(bundle.resource_type, "Bundle");
+        assert_eq!(bundle.type_, "message");
+        assert_eq!(bundle.id, Some("MSG123".to_string()));
+        assert_eq!(bundle.timestamp, Some("2024-03-15T14:30:00Z".to_string()));
+
+        let entries = bundle.entry.as_ref().unwrap();
+        assert_eq!(entries[0].resource["resourceType"], "MessageHeader");
+        assert_eq!(entries[0].resource["eventCoding"]["code"], "R01");
+        assert_eq!(bundle.resources_of_type("Patient").count(), 1);
+        assert_eq!(bundle.resources_of_type("Encounter").count(), 1);
+        assert_eq!(bundle.resources_of_type("Observation").count(), 2);
+        assert_eq!(bundle.resources_of_type("DiagnosticReport").count(), 1);
+
+        // Every entry has a distinct fullUrl
+        let urls: HashSet<_> = entries.iter().map(|e| e.full_url.clone().unwrap()).collect();
+        assert_eq!(urls.len(), entries.len());
+    }
+
+    #[test]
+    fn test_bundle_timestamp_is_instant() {
+        assert_eq!(
+            MessageConverter::convert_instant("20240315143000.25-0500").unwrap(),
+            "2024-03-15T14:30:00.250-05:00"
+        );
+        assert_eq!(MessageConverter::convert_instant("202403151430+0100").unwrap(), "2024-03-15T14:30:00+01:00");
+        assert_eq!(MessageConverter::convert_instant("20240315").unwrap(), "2024-03-15T00:00:00Z");
+        assert_eq!(MessageConverter::convert_instant("20240315143000-05").unwrap(), "2024-03-15T14:30:00-05:00");
+
+        for invalid in ["2024", "2024031514300", "20240315143000-5", "2024031514é0", "20240315.5"] {
+            assert!(MessageConverter::convert_instant(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_resources_linked_by_full_url() {
+        let message = parse_message(ORU).unwrap();
+        let bundle = MessageConverter::new().convert(&message).unwrap();
+        let entries = bundle.entry.as_ref().unwrap();
+
+        let url_of = |resource_type: &str| {
+            entries
+                .iter()
+                .find(|e| e.resource["resourceType"] == resource_type)
+                .and_then(|e| e.full_url.clone())
+                .unwrap()
+        };
+        let patient_url = url_of("Patient");
+        let encounter_url = url_of("Encounter");
+        assert_eq!(patient_url, "http://localhost/fhir/Patient/12345");
+
+        for observation in bundle.resources_of_type("Observation") {
+            assert_eq!(observation["subject"]["reference"], patient_url.as_str());
+            assert_eq!(observation["encounter"]["reference"], encounter_url.as_str());
+        }
+
+        let report = bundle.resources_of_type("DiagnosticReport").next().unwrap();
+        let results = report["result"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        for result in results {
+            let url = result["reference"].as_str().unwrap();
+            assert!(entries.iter().any(|e| e.full_url.as_deref() == Some(url)));
+        }
+    }
+
+    #[test]
+    fn test_collection_with_selected_resources() {
+        let message = parse_message(ORU).unwrap();
+        let converter = MessageConverter::new()
+            .with_bundle_type(BundleType::Collection)
+            .with_resource_types(&[ResourceType::Patient, ResourceType::Observation])
+            .with_base_url("https://fhir.example.org/r4/");
+        let bundle = converter.convert(&message).unwrap();
+
+        assert_eq!(bundle.type_, "collection");
+        let entries = bundle.entry.as_ref().unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(bundle.resources_of_type("MessageHeader").count(), 0);
+        assert_eq!(bundle.resources_of_type("Encounter").count(), 0);
+        assert_eq!(
+            entries[0].full_url.as_deref(),
+            Some("https://fhir.example.org/r4/Patient/12345")
+        );
+    }
+
+    #[test]
+    fn test_converter_config_applied() {
+        let hl7 = format!("{}\rSPM|1|SPEC123&LABNS||BLD^Blood^HL70487", ORU);
+        let message = parse_message(&hl7).unwrap();
+        let config = ConverterConfig::new()
+            .with_identifier_system("MRN", "http://hospital.example.org/mrn")
+            .with_identifier_system("LABNS", "http://lab.example.org/specimens");
+        let bundle = MessageConverter::new().with_config(config).convert(&message).unwrap();
+
+        let patient = bundle.resources_of_type("Patient").next().unwrap();
+        assert_eq!(patient["identifier"][0]["system"], "http://hospital.example.org/mrn");
+
+        let specimen = bundle.resources_of_type("Specimen").next().unwrap();
+        assert_eq!(specimen["identifier"][0]["system"], "http://lab.example.org/specimens");
+    }
+
+    #[test]
+    fn test_missing_segments_are_skipped() {
+        let hl7 = "MSH|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315||ADT^A01|12345|P|2.5\r\
+                   PID|1||67890^^^MRN||DOE^JOHN^A||19800101|M";
+        let message = parse_message(hl7).unwrap();
+        let bundle = MessageConverter::new().convert(&message).unwrap();
+
+        let entries = bundle.entry.as_ref().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].resource["resourceType"], "MessageHeader");
+        assert_eq!(entries[1].resource["resourceType"], "Patient");
+        assert_eq!(entries[0].resource["focus"][0]["reference"], entries[1].full_url.as_deref().unwrap());
+    }
+}
@@ -5,6 +5,7 @@
 //! - Forward converters: HL7 v2.x -> FHIR R4
 //! - Reverse converters: FHIR R4 -> HL7 v2.x
 
+pub mod config;
 pub mod patient;
 pub mod observation;
 pub mod practitioner;
@@ -17,10 +18,13 @@ pub mod procedure;
 pub mod immunization;
 pub mod service_request;
 pub mod specimen;
+pub mod message;
 
 /// Reverse converters for FHIR R4 -> HL7 v2.x conversion
 pub mod reverse;
 
+pub use config::ConverterConfig;
+
 // Forward converters (HL7 v2.x -> FHIR R4)
 pub use patient::PatientConverter;
 pub use observation::ObservationConverter;
@@ -34,9 +38,11 @@ pub use procedure::ProcedureConverter;
 pub use immunization::ImmunizationConverter;
 pub use service_request::ServiceRequestConverter;
 pub use specimen::SpecimenConverter;
+pub use message::{BundleType, MessageConverter, ResourceType};
 
 // Reverse converters (FHIR R4 -> HL7 v2.x)
 pub use reverse::PatientReverseConverter;
 pub use reverse::ObservationReverseConverter;
 pub use reverse::EncounterReverseConverter;
 pub use reverse::PractitionerReverseConverter;
+pub use reverse::DiagnosticReportReverseConverter;
@@ -7,6 +7,7 @@ use rs7_terser::Terser;
 use crate::error::{ConversionError, ConversionResult};
 use crate::resources::observation::*;
 use crate::resources::common::*;
+use super::config::ConverterConfig;
 
 /// Converter for transforming OBX segments to FHIR Observation resources
 pub struct ObservationConverter;
@@ -26,6 +27,14 @@ impl ObservationConverter {
     ///
     /// Returns an error if no OBX segments are found or required fields are missing
     pub fn convert_all(message: &Message) -> ConversionResult<Vec<Observation>> {
+        Self::convert_all_with_config(message, &ConverterConfig::default())
+    }
+
+    /// Same as [`Self::convert_all`], resolving identifier systems through `config`
+    pub fn convert_all_with_config(
+        message: &Message,
+        config: &ConverterConfig,
+    ) -> ConversionResult<Vec<Observation>> {
         let obx_segments: Vec<_> = message
             .segments
             .iter()
@@ -40,7 +49,7 @@ impl ObservationConverter {
         let mut observations = Vec::new();
 
         for (obx_count, (_index, _)) in obx_segments.iter().enumerate() {
-            let observation = Self::convert_single(message, obx_count)?;
+            let observation = Self::convert_single_with_config(message, obx_count, config)?;
             observations.push(observation);
         }
 
@@ -58,6 +67,18 @@ impl ObservationConverter {
     ///
     /// A FHIR Observation resource
     pub fn convert_single(message: &Message, obx_index: usize) -> ConversionResult<Observation> {
+        Self::convert_single_with_config(message, obx_index, &ConverterConfig::default())
+    }
+
+    /// Same as [`Self::convert_single`], resolving identifier systems through `config`
+    ///
+    /// The responsible observer (OBX-16) reference carries its identifier,
+    /// with the system resolved from XCN-9 like [`ConverterConfig::practitioner_system`].
+    pub fn convert_single_with_config(
+        message: &Message,
+        obx_index: usize,
+        config: &ConverterConfig,
+    ) -> ConversionResult<Observation> {
         let terser = Terser::new(message);
 
         // OBX-3: Observation Identifier -> Observation.code
@@ -206,23 +227,15 @@ impl ObservationConverter {
         } else {
             format!("OBX({})-16", obx_index + 1)
         };
-        if let Ok(Some(observer_id)) = terser.get(&observer_path)
-            && !observer_id.is_empty() {
-                let mut reference = Reference {
-                    reference: Some(format!("Practitioner/{}", observer_id)),
-                    type_: Some("Practitioner".to_string()),
-                    identifier: None,
-                    display: None,
-                };
-
-                // Get observer name if available (component 2 = Family Name, 1-based indexing)
-                if let Ok(Some(observer_name)) = terser.get(&format!("{}-2", observer_path))
-                    && !observer_name.is_empty() {
-                        reference.display = Some(observer_name.to_string());
-                    }
+        if let Some(mut reference) = config.practitioner_reference(&terser, &observer_path) {
+            // Get observer name if available (component 2 = Family Name, 1-based indexing)
+            if let Ok(Some(observer_name)) = terser.get(&format!("{}-2", observer_path))
+                && !observer_name.is_empty() {
+                    reference.display = Some(observer_name.to_string());
+                }
 
-                observation.performer = Some(vec![reference]);
-            }
+            observation.performer = Some(vec![reference]);
+        }
 
         Ok(observation)
     }
@@ -5,7 +5,9 @@
 use rs7_core::Message;
 use rs7_terser::Terser;
 use crate::error::{ConversionError, ConversionResult};
+use crate::issue::{is_valid_timestamp, ConversionIssue};
 use crate::resources::patient::Patient;
+use super::config::ConverterConfig;
 use crate::resources::common::*;
 
 /// Converter for transforming PID segments to FHIR Patient resources
@@ -26,6 +28,68 @@ impl PatientConverter {
     ///
     /// Returns an error if the PID segment is not found or required fields are missing
     pub fn convert(message: &Message) -> ConversionResult<Patient> {
+        Self::convert_with_config(message, &ConverterConfig::default())
+    }
+
+    /// Convert an HL7 v2 message to a FHIR Patient resource using the given configuration
+    ///
+    /// Identifier systems for PID-3 are resolved from the assigning authority
+    /// (CX-4) through `config`, falling back to its default MRN system.
+    pub fn convert_with_config(message: &Message, config: &ConverterConfig) -> ConversionResult<Patient> {
+        Self::convert_collecting(message, config, None)
+    }
+
+    /// Convert an HL7 v2 message to a FHIR Patient resource, collecting issues
+    ///
+    /// Instead of failing on the first problem, values that cannot be
+    /// converted (such as an unparseable birth date) are left out and
+    /// reported, together with questionable input such as an identifier
+    /// without an assigning authority. Without a PID segment the Patient is
+    /// empty and a fatal issue is reported.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let (patient, issues) = PatientConverter::convert_validated(&message);
+    /// if issues.iter().any(|issue| issue.is_error()) {
+    ///     // Review before accepting the best-effort Patient
+    /// }
+    /// ```
+    pub fn convert_validated(message: &Message) -> (Patient, Vec<ConversionIssue>) {
+        Self::convert_validated_with_config(message, &ConverterConfig::default())
+    }
+
+    /// Convert an HL7 v2 message to a FHIR Patient resource using the given
+    /// configuration, collecting issues
+    ///
+    /// See [`convert_validated`](Self::convert_validated).
+    pub fn convert_validated_with_config(
+        message: &Message,
+        config: &ConverterConfig,
+    ) -> (Patient, Vec<ConversionIssue>) {
+        let mut issues = Vec::new();
+        let patient = match Self::convert_collecting(message, config, Some(&mut issues)) {
+            Ok(patient) => patient,
+            Err(e) => {
+                let path = match e {
+                    ConversionError::MissingSegment(ref segment) => segment.clone(),
+                    _ => "PID".to_string(),
+                };
+                issues.push(ConversionIssue::fatal(path, e.to_string()));
+                Patient::new()
+            }
+        };
+        (patient, issues)
+    }
+
+    /// Convert the PID segment, recording issues in `issues` when given
+    ///
+    /// Without `issues`, unconvertible values are errors as before.
+    fn convert_collecting(
+        message: &Message,
+        config: &ConverterConfig,
+        mut issues: Option<&mut Vec<ConversionIssue>>,
+    ) -> ConversionResult<Patient> {
         let terser = Terser::new(message);
 
         // Check if PID segment exists
@@ -36,20 +100,36 @@ impl PatientConverter {
         let mut patient = Patient::new();
 
         // PID-3: Patient Identifier List -> Patient.identifier
-        patient.identifier = Self::convert_identifiers(&terser)?;
+        patient.identifier = Self::convert_identifiers(&terser, config, issues.as_deref_mut())?;
+        if patient.identifier.is_none()
+            && let Some(issues) = issues.as_deref_mut() {
+                issues.push(ConversionIssue::warning("PID-3", "No patient identifier"));
+            }
 
         // PID-5: Patient Name -> Patient.name
         patient.name = Self::convert_names(&terser)?;
+        if patient.name.is_none()
+            && let Some(issues) = issues.as_deref_mut() {
+                issues.push(ConversionIssue::warning("PID-5", "No patient name"));
+            }
 
         // PID-7: Date of Birth -> Patient.birthDate
         if let Ok(Some(dob)) = terser.get("PID-7")
             && !dob.is_empty() {
-                patient.birth_date = Some(Self::convert_date(dob)?);
+                patient.birth_date =
+                    Self::convert_checked(dob, "PID-7", "birth date", Self::convert_date, issues.as_deref_mut())?;
             }
 
         // PID-8: Administrative Sex -> Patient.gender
         if let Ok(Some(sex)) = terser.get("PID-8")
             && !sex.is_empty() {
+                if !matches!(sex, "M" | "F" | "O" | "U" | "A")
+                    && let Some(issues) = issues.as_deref_mut() {
+                        issues.push(ConversionIssue::warning(
+                            "PID-8",
+                            format!("Unknown administrative sex '{}', converted to 'unknown'", sex),
+                        ));
+                    }
                 patient.gender = Some(Self::convert_gender(sex)?);
             }
 
@@ -78,7 +158,13 @@ impl PatientConverter {
         // PID-29: Patient Death Date and Time -> Patient.deceased
         if let Ok(Some(death_date)) = terser.get("PID-29")
             && !death_date.is_empty() {
-                patient.deceased_date_time = Some(Self::convert_datetime(death_date)?);
+                patient.deceased_date_time = Self::convert_checked(
+                    death_date,
+                    "PID-29",
+                    "death date/time",
+                    Self::convert_datetime,
+                    issues,
+                )?;
             }
 
         // PID-30: Patient Death Indicator -> Patient.deceased
@@ -93,7 +179,11 @@ impl PatientConverter {
     }
 
     /// Convert PID-3 (Patient Identifier List) to FHIR Identifier
-    fn convert_identifiers(terser: &Terser) -> ConversionResult<Option<Vec<Identifier>>> {
+    fn convert_identifiers(
+        terser: &Terser,
+        config: &ConverterConfig,
+        mut issues: Option<&mut Vec<ConversionIssue>>,
+    ) -> ConversionResult<Option<Vec<Identifier>>> {
         let mut identifiers = Vec::new();
 
         // Try to get all repetitions of PID-3
@@ -118,22 +208,33 @@ impl PatientConverter {
                     assigner: None,
                 };
 
-                // PID-3-4: Assigning Authority (component index 3 in 0-based)
+                // PID-3-4: Assigning Authority -> Identifier.system
                 let authority_path = if rep == 0 {
-                    "PID-3-3".to_string()
+                    "PID-3-4".to_string()
                 } else {
-                    format!("PID-3({})-3", rep)
+                    format!("PID-3({})-4", rep)
                 };
-                if let Ok(Some(authority)) = terser.get(&authority_path)
-                    && !authority.is_empty() {
-                        identifier.system = Some(format!("urn:oid:{}", authority));
+                let authority = terser.get(&authority_path).ok().flatten();
+                identifier.system = config.identifier_system(authority, config.default_mrn_system.as_deref());
+                if authority.is_none_or(str::is_empty)
+                    && let Some(issues) = issues.as_deref_mut() {
+                        issues.push(match identifier.system {
+                            Some(ref system) => ConversionIssue::information(
+                                &authority_path,
+                                format!("Identifier '{}' has no assigning authority, using system {}", id_value, system),
+                            ),
+                            None => ConversionIssue::warning(
+                                &authority_path,
+                                format!("Identifier '{}' has no assigning authority and no system", id_value),
+                            ),
+                        });
                     }
 
-                // PID-3-5: Identifier Type Code (component index 4 in 0-based)
+                // PID-3-5: Identifier Type Code
                 let type_path = if rep == 0 {
-                    "PID-3-4".to_string()
+                    "PID-3-5".to_string()
                 } else {
-                    format!("PID-3({})-4", rep)
+                    format!("PID-3({})-5", rep)
                 };
                 if let Ok(Some(id_type)) = terser.get(&type_path)
                     && !id_type.is_empty() {
@@ -416,6 +517,29 @@ impl PatientConverter {
         }
     }
 
+    /// Convert a DT/DTM value, recording instead of failing when collecting issues
+    ///
+    /// When collecting, values that are not valid HL7 timestamps are reported
+    /// as errors and left out rather than converted on a best-guess basis.
+    fn convert_checked(
+        value: &str,
+        path: &str,
+        description: &str,
+        convert: fn(&str) -> ConversionResult<String>,
+        issues: Option<&mut Vec<ConversionIssue>>,
+    ) -> ConversionResult<Option<String>> {
+        let Some(issues) = issues else {
+            return convert(value).map(Some);
+        };
+
+        if is_valid_timestamp(value)
+            && let Ok(converted) = convert(value) {
+                return Ok(Some(converted));
+            }
+        issues.push(ConversionIssue::error(path, format!("Unparseable {} '{}'", description, value)));
+        Ok(None)
+    }
+
     /// Convert HL7 v2 date format (YYYYMMDD) to FHIR date format (YYYY-MM-DD)
     fn convert_date(date: &str) -> ConversionResult<String> {
         if date.len() >= 8 {
@@ -458,6 +582,7 @@ impl PatientConverter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::issue::IssueSeverity;
     use rs7_parser::parse_message;
 
     #[test]
@@ -493,4 +618,81 @@ mod tests {
         assert_eq!(addresses[0].country, Some("USA".to_string()));
         assert_eq!(addresses[0].use_, Some("home".to_string()));
     }
+
+    #[test]
+    fn test_convert_identifier_systems() {
+        let hl7 = "MSH|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315||ADT^A01|12345|P|2.5\r\
+                   PID|1||67890^^^HOSP^MR||DOE^JOHN^A||19800101|M";
+        let message = parse_message(hl7).unwrap();
+
+        // Without configuration the assigning authority becomes an OID URN
+        let patient = PatientConverter::convert(&message).unwrap();
+        let identifier = &patient.identifier.unwrap()[0];
+        assert_eq!(identifier.system, Some("urn:oid:HOSP".to_string()));
+        assert_eq!(identifier.type_.as_ref().unwrap().text, Some("MR".to_string()));
+
+        let config = ConverterConfig::new()
+            .with_identifier_system("HOSP", "http://hospital.example.org/mrn");
+        let patient = PatientConverter::convert_with_config(&message, &config).unwrap();
+        let identifier = &patient.identifier.unwrap()[0];
+        assert_eq!(identifier.system, Some("http://hospital.example.org/mrn".to_string()));
+        assert_eq!(identifier.value, Some("67890".to_string()));
+
+        let config = ConverterConfig::new().with_default_mrn_system("http://hospital.example.org/patients");
+        let patient = PatientConverter::convert_with_config(&message, &config).unwrap();
+        assert_eq!(
+            patient.identifier.unwrap()[0].system,
+            Some("http://hospital.example.org/patients".to_string())
+        );
+    }
+
+    #[test]
+    fn test_convert_validated_collects_issues() {
+        let hl7 = "MSH|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315||ADT^A01|12345|P|2.5\r\
+                   PID|1||67890~11111^^^HOSP||DOE^JOHN||1980130|X|||||||||||||||||||||2024XX01";
+        let message = parse_message(hl7).unwrap();
+
+        // The plain conversion guesses silently
+        let guessed = PatientConverter::convert(&message).unwrap();
+        assert_eq!(guessed.birth_date, Some("1980".to_string()));
+        assert_eq!(guessed.deceased_date_time, Some("2024-XX-01".to_string()));
+
+        let (patient, issues) = PatientConverter::convert_validated(&message);
+        let found: Vec<_> = issues.iter().map(|i| (i.severity, i.path.as_str())).collect();
+        assert_eq!(
+            found,
+            vec![
+                (IssueSeverity::Warning, "PID-3-4"),
+                (IssueSeverity::Error, "PID-7"),
+                (IssueSeverity::Warning, "PID-8"),
+                (IssueSeverity::Error, "PID-29"),
+            ]
+        );
+        assert!(issues[1].message.contains("1980130"));
+
+        // Everything else is still converted
+        assert_eq!(patient.identifier.as_ref().unwrap().len(), 2);
+        assert_eq!(patient.birth_date, None);
+        assert_eq!(patient.gender, Some("unknown".to_string()));
+        assert_eq!(patient.deceased_date_time, None);
+        assert_eq!(patient.name.unwrap()[0].family, Some("DOE".to_string()));
+
+        // A default system turns the missing authority into information
+        let config = ConverterConfig::new().with_default_mrn_system("http://hospital.example.org/patients");
+        let (_, issues) = PatientConverter::convert_validated_with_config(&message, &config);
+        assert_eq!(issues[0].severity, IssueSeverity::Information);
+
+        let clean = parse_message(
+            "MSH|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315||ADT^A01|12345|P|2.5\rPID|1||67890^^^MRN||DOE^JOHN||19800101|M",
+        )
+        .unwrap();
+        let (patient, issues) = PatientConverter::convert_validated(&clean);
+        assert!(issues.is_empty(), "{:?}", issues);
+        assert_eq!(patient, PatientConverter::convert(&clean).unwrap());
+
+        let no_pid = parse_message("MSH|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315||ADT^A01|12345|P|2.5").unwrap();
+        let (_, issues) = PatientConverter::convert_validated(&no_pid);
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].severity, issues[0].path.as_str()), (IssueSeverity::Fatal, "PID"));
+    }
 }
@@ -7,6 +7,7 @@ use rs7_terser::Terser;
 use crate::error::{ConversionError, ConversionResult};
 use crate::resources::practitioner::*;
 use crate::resources::common::*;
+use super::config::ConverterConfig;
 
 /// Converter for transforming HL7 v2 practitioner data to FHIR Practitioner resources
 pub struct PractitionerConverter;
@@ -26,6 +27,11 @@ impl PractitionerConverter {
     ///
     /// Returns an error if the PV1 segment or required fields are not found
     pub fn convert_attending_doctor(message: &Message) -> ConversionResult<Practitioner> {
+        Self::convert_attending_doctor_with_config(message, &ConverterConfig::default())
+    }
+
+    /// Same as [`Self::convert_attending_doctor`], resolving identifier systems through `config`
+    pub fn convert_attending_doctor_with_config(message: &Message, config: &ConverterConfig) -> ConversionResult<Practitioner> {
         let terser = Terser::new(message);
 
         // Check if PV1 segment exists
@@ -33,41 +39,60 @@ impl PractitionerConverter {
             return Err(ConversionError::MissingSegment("PV1".to_string()));
         }
 
-        Self::convert_xcn_to_practitioner(&terser, "PV1-7")
+        Self::convert_xcn_to_practitioner(&terser, "PV1-7", config)
     }
 
     /// Convert practitioner information from PV1-8 (Referring Doctor) to a FHIR Practitioner resource
     pub fn convert_referring_doctor(message: &Message) -> ConversionResult<Practitioner> {
+        Self::convert_referring_doctor_with_config(message, &ConverterConfig::default())
+    }
+
+    /// Same as [`Self::convert_referring_doctor`], resolving identifier systems through `config`
+    pub fn convert_referring_doctor_with_config(message: &Message, config: &ConverterConfig) -> ConversionResult<Practitioner> {
         let terser = Terser::new(message);
 
         if !message.segments.iter().any(|s| s.id == "PV1") {
             return Err(ConversionError::MissingSegment("PV1".to_string()));
         }
 
-        Self::convert_xcn_to_practitioner(&terser, "PV1-8")
+        Self::convert_xcn_to_practitioner(&terser, "PV1-8", config)
     }
 
     /// Convert practitioner information from PV1-9 (Consulting Doctor) to a FHIR Practitioner resource
     pub fn convert_consulting_doctor(message: &Message) -> ConversionResult<Practitioner> {
+        Self::convert_consulting_doctor_with_config(message, &ConverterConfig::default())
+    }
+
+    /// Same as [`Self::convert_consulting_doctor`], resolving identifier systems through `config`
+    pub fn convert_consulting_doctor_with_config(message: &Message, config: &ConverterConfig) -> ConversionResult<Practitioner> {
         let terser = Terser::new(message);
 
         if !message.segments.iter().any(|s| s.id == "PV1") {
             return Err(ConversionError::MissingSegment("PV1".to_string()));
         }
 
-        Self::convert_xcn_to_practitioner(&terser, "PV1-9")
+        Self::convert_xcn_to_practitioner(&terser, "PV1-9", config)
     }
 
     /// Convert practitioner information from OBX-16 (Responsible Observer) to a FHIR Practitioner resource
     /// Note: obx_index is 0-based internally, but Terser uses 1-based segment indexing
     pub fn convert_responsible_observer(message: &Message, obx_index: usize) -> ConversionResult<Practitioner> {
+        Self::convert_responsible_observer_with_config(message, obx_index, &ConverterConfig::default())
+    }
+
+    /// Same as [`Self::convert_responsible_observer`], resolving identifier systems through `config`
+    pub fn convert_responsible_observer_with_config(
+        message: &Message,
+        obx_index: usize,
+        config: &ConverterConfig,
+    ) -> ConversionResult<Practitioner> {
         let terser = Terser::new(message);
         let path = if obx_index == 0 {
             "OBX-16".to_string()
         } else {
             format!("OBX({})-16", obx_index + 1)
         };
-        Self::convert_xcn_to_practitioner(&terser, &path)
+        Self::convert_xcn_to_practitioner(&terser, &path, config)
     }
 
     /// Convert an XCN (Extended Composite ID Number and Name) field to a FHIR Practitioner
@@ -88,7 +113,11 @@ impl PractitionerConverter {
     /// 13. Identifier Type Code
     ///
     /// Note: Terser uses 1-based component indexing
-    fn convert_xcn_to_practitioner(terser: &Terser, base_path: &str) -> ConversionResult<Practitioner> {
+    fn convert_xcn_to_practitioner(
+        terser: &Terser,
+        base_path: &str,
+        config: &ConverterConfig,
+    ) -> ConversionResult<Practitioner> {
         let mut practitioner = Practitioner::new();
 
         // XCN-1: ID Number -> Practitioner.identifier (component 1, 1-based indexing)
@@ -103,13 +132,12 @@ impl PractitionerConverter {
                 };
 
                 // XCN-9: Assigning Authority (component 9, 1-based indexing)
-                if let Ok(Some(authority)) = terser.get(&format!("{}-9", base_path))
-                    && !authority.is_empty() {
-                        identifier.system = Some(format!("urn:oid:{}", authority));
-                    }
+                let authority = terser.get(&format!("{}-9", base_path)).ok().flatten();
+                let id_type = terser.get(&format!("{}-13", base_path)).ok().flatten();
+                identifier.system = config.practitioner_system(authority, id_type);
 
                 // XCN-13: Identifier Type Code (component 13, 1-based indexing)
-                if let Ok(Some(id_type)) = terser.get(&format!("{}-13", base_path))
+                if let Some(id_type) = id_type
                     && !id_type.is_empty() {
                         identifier.type_ = Some(CodeableConcept {
                             coding: Some(vec![Coding {
@@ -207,36 +235,49 @@ impl PractitionerConverter {
 
     /// Convert practitioner information from ORC-12 (Ordering Provider) to a FHIR Practitioner resource
     pub fn convert_ordering_provider(message: &Message) -> ConversionResult<Practitioner> {
+        Self::convert_ordering_provider_with_config(message, &ConverterConfig::default())
+    }
+
+    /// Same as [`Self::convert_ordering_provider`], resolving identifier systems through `config`
+    pub fn convert_ordering_provider_with_config(message: &Message, config: &ConverterConfig) -> ConversionResult<Practitioner> {
         let terser = Terser::new(message);
 
         if !message.segments.iter().any(|s| s.id == "ORC") {
             return Err(ConversionError::MissingSegment("ORC".to_string()));
         }
 
-        Self::convert_xcn_to_practitioner(&terser, "ORC-12")
+        Self::convert_xcn_to_practitioner(&terser, "ORC-12", config)
     }
 
     /// Extract all practitioners from a message (attending, referring, consulting doctors)
     pub fn extract_all_practitioners(message: &Message) -> ConversionResult<Vec<Practitioner>> {
+        Self::extract_all_practitioners_with_config(message, &ConverterConfig::default())
+    }
+
+    /// Same as [`Self::extract_all_practitioners`], resolving identifier systems through `config`
+    pub fn extract_all_practitioners_with_config(
+        message: &Message,
+        config: &ConverterConfig,
+    ) -> ConversionResult<Vec<Practitioner>> {
         let mut practitioners = Vec::new();
 
         // Try attending doctor
-        if let Ok(attending) = Self::convert_attending_doctor(message) {
+        if let Ok(attending) = Self::convert_attending_doctor_with_config(message, config) {
             practitioners.push(attending);
         }
 
         // Try referring doctor
-        if let Ok(referring) = Self::convert_referring_doctor(message) {
+        if let Ok(referring) = Self::convert_referring_doctor_with_config(message, config) {
             practitioners.push(referring);
         }
 
         // Try consulting doctor
-        if let Ok(consulting) = Self::convert_consulting_doctor(message) {
+        if let Ok(consulting) = Self::convert_consulting_doctor_with_config(message, config) {
             practitioners.push(consulting);
         }
 
         // Try ordering provider
-        if let Ok(ordering) = Self::convert_ordering_provider(message) {
+        if let Ok(ordering) = Self::convert_ordering_provider_with_config(message, config) {
             practitioners.push(ordering);
         }
 
@@ -252,6 +293,7 @@ impl PractitionerConverter {
 mod tests {
     use super::*;
     use rs7_parser::parse_message;
+    use crate::converters::config::NPI_SYSTEM;
 
     #[test]
     fn test_convert_attending_doctor() {
@@ -278,6 +320,32 @@ mod tests {
         assert_eq!(identifiers[0].value, Some("1234567890".to_string()));
     }
 
+    #[test]
+    fn test_practitioner_identifier_systems() {
+        let hl7 = "MSH|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315||ADT^A01|12345|P|2.5\r\
+                   PV1|1|I|ER^101^1||||1234567890^SMITH^JAMES^^^DR^MD^^^^^^NPI|555^JONES^AMY^^^^^^HOSP";
+        let message = parse_message(hl7).unwrap();
+
+        // NPIs default to the well-known NPI system
+        let attending = PractitionerConverter::convert_attending_doctor(&message).unwrap();
+        assert_eq!(attending.identifier.unwrap()[0].system, Some(NPI_SYSTEM.to_string()));
+
+        let config = ConverterConfig::new()
+            .with_identifier_system("HOSP", "http://hospital.example.org/staff")
+            .with_default_practitioner_system("http://hospital.example.org/providers");
+        let referring = PractitionerConverter::convert_referring_doctor_with_config(&message, &config).unwrap();
+        assert_eq!(
+            referring.identifier.unwrap()[0].system,
+            Some("http://hospital.example.org/staff".to_string())
+        );
+
+        let attending = PractitionerConverter::convert_attending_doctor_with_config(&message, &config).unwrap();
+        assert_eq!(
+            attending.identifier.unwrap()[0].system,
+            Some("http://hospital.example.org/providers".to_string())
+        );
+    }
+
     #[test]
     fn test_convert_referring_doctor() {
         let hl7 = "MSH|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315||ADT^A01|12345|P|2.5\r\
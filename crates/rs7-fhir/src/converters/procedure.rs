@@ -5,11 +5,20 @@ use rs7_terser::Terser;
 use crate::error::{ConversionError, ConversionResult};
 use crate::resources::procedure::*;
 use crate::resources::common::*;
+use super::config::ConverterConfig;
 
 pub struct ProcedureConverter;
 
 impl ProcedureConverter {
     pub fn convert_all(message: &Message) -> ConversionResult<Vec<Procedure>> {
+        Self::convert_all_with_config(message, &ConverterConfig::default())
+    }
+
+    /// Same as [`Self::convert_all`], resolving identifier systems through `config`
+    pub fn convert_all_with_config(
+        message: &Message,
+        config: &ConverterConfig,
+    ) -> ConversionResult<Vec<Procedure>> {
         let pr1_segments: Vec<_> = message
             .segments
             .iter()
@@ -23,7 +32,7 @@ impl ProcedureConverter {
 
         let mut procedures = Vec::new();
         for (pr1_count, _) in pr1_segments.iter().enumerate() {
-            let procedure = Self::convert_single(message, pr1_count)?;
+            let procedure = Self::convert_single_with_config(message, pr1_count, config)?;
             procedures.push(procedure);
         }
 
@@ -31,6 +40,18 @@ impl ProcedureConverter {
     }
 
     pub fn convert_single(message: &Message, pr1_index: usize) -> ConversionResult<Procedure> {
+        Self::convert_single_with_config(message, pr1_index, &ConverterConfig::default())
+    }
+
+    /// Same as [`Self::convert_single`], resolving identifier systems through `config`
+    ///
+    /// The patient reference carries the PID-3 identifier, with the system
+    /// resolved from its assigning authority or the default MRN system.
+    pub fn convert_single_with_config(
+        message: &Message,
+        pr1_index: usize,
+        config: &ConverterConfig,
+    ) -> ConversionResult<Procedure> {
         let terser = Terser::new(message);
         let mut procedure = Procedure::new("completed".to_string());
 
@@ -75,14 +96,7 @@ impl ProcedureConverter {
         }
 
         // Link to patient
-        if let Ok(Some(patient_id)) = terser.get("PID-3") {
-            procedure.subject = Some(Reference {
-                reference: Some(format!("Patient/{}", patient_id)),
-                type_: Some("Patient".to_string()),
-                identifier: None,
-                display: None,
-            });
-        }
+        procedure.subject = config.patient_reference(&terser);
 
         Ok(procedure)
     }
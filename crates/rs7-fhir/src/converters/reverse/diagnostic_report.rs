@@ -0,0 +1,195 @@
+//! DiagnosticReport reverse converter - FHIR DiagnosticReport to OBR and OBX segments
+//!
+//! Converts a FHIR DiagnosticReport and its Observations back to the OBR/OBX
+//! segment group of an HL7 v2.x ORU^R01 message.
+
+use crate::error::ConversionResult;
+use crate::resources::diagnostic_report::DiagnosticReport;
+use crate::resources::observation::Observation;
+use super::fhir_datetime_to_hl7;
+use super::observation::ObservationReverseConverter;
+use rs7_core::segment::Segment;
+
+/// Reverse converter for transforming FHIR DiagnosticReport resources to OBR/OBX segments
+pub struct DiagnosticReportReverseConverter;
+
+impl DiagnosticReportReverseConverter {
+    /// Convert a FHIR DiagnosticReport and its Observations to HL7 v2 segments
+    ///
+    /// The segments are returned in message order (the OBR followed by one OBX
+    /// per observation) so they can be appended to an ORU^R01 message.
+    ///
+    /// # Arguments
+    ///
+    /// * `report` - The FHIR DiagnosticReport resource
+    /// * `observations` - The Observations belonging to the report, in OBX order
+    /// * `set_id` - The set ID for OBR-1
+    ///
+    /// # Returns
+    ///
+    /// The OBR segment followed by its OBX segments
+    pub fn convert(
+        report: &DiagnosticReport,
+        observations: &[Observation],
+        set_id: usize,
+    ) -> ConversionResult<Vec<Segment>> {
+        let mut segments = Vec::with_capacity(observations.len() + 1);
+        segments.push(Self::convert_obr(report, set_id)?);
+
+        for (index, observation) in observations.iter().enumerate() {
+            segments.push(ObservationReverseConverter::convert(observation, index + 1)?);
+        }
+
+        Ok(segments)
+    }
+
+    /// Convert a FHIR DiagnosticReport resource to an HL7 v2 OBR segment
+    ///
+    /// # Arguments
+    ///
+    /// * `report` - The FHIR DiagnosticReport resource
+    /// * `set_id` - The set ID for OBR-1
+    ///
+    /// # Returns
+    ///
+    /// An HL7 v2 OBR segment
+    pub fn convert_obr(report: &DiagnosticReport, set_id: usize) -> ConversionResult<Segment> {
+        let mut obr = Segment::new("OBR");
+
+        // OBR-1: Set ID
+        let _ = obr.set_field_value(1, set_id.to_string());
+
+        // OBR-2: Placer Order Number
+        if let Some(value) = report
+            .identifier
+            .as_ref()
+            .and_then(|identifiers| identifiers.first())
+            .and_then(|i| i.value.as_ref())
+        {
+            let _ = obr.set_field_value(2, value);
+        }
+
+        // OBR-4: Universal Service Identifier
+        ObservationReverseConverter::set_coded_element(&mut obr, 4, &report.code)?;
+
+        // OBR-7: Observation Date/Time
+        if let Some(ref effective_dt) = report.effective_date_time {
+            let _ = obr.set_field_value(7, fhir_datetime_to_hl7(effective_dt));
+        }
+
+        // OBR-22: Results Report/Status Change Date/Time
+        if let Some(ref issued) = report.issued {
+            let _ = obr.set_field_value(22, fhir_datetime_to_hl7(issued));
+        }
+
+        // OBR-25: Result Status
+        let _ = obr.set_field_value(25, Self::convert_status_to_hl7(&report.status));
+
+        Ok(obr)
+    }
+
+    /// Convert FHIR diagnostic report status to HL7 v2 result status
+    fn convert_status_to_hl7(status: &str) -> String {
+        match status {
+            "registered" => "O",  // Order received
+            "partial" => "P",     // Preliminary
+            "preliminary" => "P", // Preliminary
+            "final" => "F",       // Final
+            "amended" => "C",     // Corrected
+            "corrected" => "C",   // Corrected
+            "cancelled" => "X",   // Cancelled
+            _ => "F",             // Default to final
+        }
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converters::DiagnosticReportConverter;
+    use crate::resources::common::*;
+    use rs7_core::Message;
+
+    fn create_code(code: &str, display: &str) -> CodeableConcept {
+        CodeableConcept {
+            coding: Some(vec![Coding {
+                system: Some("http://loinc.org".to_string()),
+                version: None,
+                code: Some(code.to_string()),
+                display: Some(display.to_string()),
+            }]),
+            text: None,
+        }
+    }
+
+    fn create_test_report() -> DiagnosticReport {
+        let mut report = DiagnosticReport::new("final".to_string(), create_code("24331-1", "Lipid Panel"));
+        report.identifier = Some(vec![Identifier {
+            use_: Some("official".to_string()),
+            type_: None,
+            system: None,
+            value: Some("LAB123".to_string()),
+            assigner: None,
+        }]);
+        report.effective_date_time = Some("2024-03-15T08:00:00".to_string());
+        report
+    }
+
+    fn create_observations() -> Vec<Observation> {
+        let mut cholesterol = Observation::new("final".to_string(), create_code("2093-3", "Cholesterol"));
+        cholesterol.value_quantity = Some(Quantity {
+            value: Some(195.0),
+            unit: Some("mg/dL".to_string()),
+            system: None,
+            code: None,
+        });
+
+        let mut comment = Observation::new("preliminary".to_string(), create_code("8251-1", "Comment"));
+        comment.value_string = Some("Fasting sample".to_string());
+
+        vec![cholesterol, comment]
+    }
+
+    #[test]
+    fn test_convert_report_to_segments() {
+        let segments = DiagnosticReportReverseConverter::convert(&create_test_report(), &create_observations(), 1).unwrap();
+
+        assert_eq!(segments.len(), 3);
+
+        let obr = &segments[0];
+        assert_eq!(obr.id, "OBR");
+        assert_eq!(obr.get_field_value(1), Some("1"));
+        assert_eq!(obr.get_field_value(2), Some("LAB123"));
+        assert_eq!(obr.get_field(4).and_then(|f| f.get_component(0, 0)).and_then(|c| c.value()), Some("24331-1"));
+        assert_eq!(obr.get_field(4).and_then(|f| f.get_component(0, 2)).and_then(|c| c.value()), Some("LN"));
+        assert_eq!(obr.get_field_value(7), Some("20240315080000"));
+        assert_eq!(obr.get_field_value(25), Some("F"));
+
+        assert_eq!(segments[1].id, "OBX");
+        assert_eq!(segments[1].get_field_value(1), Some("1"));
+        assert_eq!(segments[1].get_field_value(2), Some("NM"));
+        assert_eq!(segments[1].get_field_value(5), Some("195"));
+        assert_eq!(segments[2].get_field_value(1), Some("2"));
+        assert_eq!(segments[2].get_field_value(2), Some("ST"));
+        assert_eq!(segments[2].get_field_value(11), Some("P"));
+    }
+
+    #[test]
+    fn test_report_round_trip() {
+        let mut message = Message::new();
+        let mut msh = Segment::new("MSH");
+        let _ = msh.set_field_value(9, "ORU^R01");
+        message.add_segment(msh);
+        for segment in DiagnosticReportReverseConverter::convert(&create_test_report(), &create_observations(), 1).unwrap() {
+            message.add_segment(segment);
+        }
+
+        let reports = DiagnosticReportConverter::convert_all(&message).unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].status, "final");
+        assert_eq!(reports[0].id, Some("LAB123".to_string()));
+        assert_eq!(reports[0].effective_date_time, Some("2024-03-15T08:00:00".to_string()));
+        assert_eq!(reports[0].code.coding.as_ref().unwrap()[0].code, Some("24331-1".to_string()));
+    }
+}
@@ -7,8 +7,40 @@ pub mod patient;
 pub mod observation;
 pub mod encounter;
 pub mod practitioner;
+pub mod diagnostic_report;
 
 pub use patient::PatientReverseConverter;
 pub use observation::ObservationReverseConverter;
 pub use encounter::EncounterReverseConverter;
 pub use practitioner::PractitionerReverseConverter;
+pub use diagnostic_report::DiagnosticReportReverseConverter;
+
+use rs7_core::types::Timestamp;
+
+/// Convert a FHIR date, dateTime or instant to an HL7 v2 DTM value
+///
+/// Partial dates keep their precision (`2024-03` becomes `202403`). Times
+/// keep fractional seconds, to the four digits HL7 allows, and the offset,
+/// with `Z` written as `+0000`. Values that are not ISO 8601 only lose their
+/// separators.
+pub(crate) fn fhir_datetime_to_hl7(datetime: &str) -> String {
+    match Timestamp::parse_iso8601(datetime) {
+        Some(timestamp) => timestamp.to_hl7(),
+        None => datetime.replace(['-', ':', 'T'], ""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fhir_datetime_to_hl7() {
+        assert_eq!(fhir_datetime_to_hl7("2024-03-15T10:30:00-05:00"), "20240315103000-0500");
+        assert_eq!(fhir_datetime_to_hl7("2024-03-15T10:30:00Z"), "20240315103000+0000");
+        assert_eq!(fhir_datetime_to_hl7("2024-03-15T10:30:00.25+01:00"), "20240315103000.25+0100");
+        assert_eq!(fhir_datetime_to_hl7("2024-03-15T10:30:00"), "20240315103000");
+        assert_eq!(fhir_datetime_to_hl7("2024-03-15"), "20240315");
+        assert_eq!(fhir_datetime_to_hl7("2024-03"), "202403");
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Converts FHIR Observation resources back to HL7 v2.x OBX segments.
 
+use super::fhir_datetime_to_hl7;
 use crate::error::ConversionResult;
 use crate::resources::common::*;
 use crate::resources::observation::{Observation, ObservationReferenceRange};
@@ -73,16 +74,16 @@ impl ObservationReverseConverter {
 
         // OBX-14: Date/Time of Observation
         if let Some(ref effective_dt) = observation.effective_date_time {
-            let hl7_datetime = effective_dt.replace(['-', ':', 'T'], "");
+            let hl7_datetime = fhir_datetime_to_hl7(effective_dt);
             let _ = obx.set_field_value(14, &hl7_datetime);
         }
 
         Ok(obx)
     }
 
-    /// Determine the HL7 v2 value type from the observation
+    /// Determine the HL7 v2 value type from the observation's value[x]
     fn determine_value_type(observation: &Observation) -> String {
-        if observation.value_quantity.is_some() {
+        if observation.value_quantity.is_some() || observation.value_integer.is_some() {
             "NM".to_string() // Numeric
         } else if observation.value_string.is_some() {
             "ST".to_string() // String
@@ -90,15 +91,27 @@ impl ObservationReverseConverter {
             "CE".to_string() // Coded Element
         } else if observation.value_boolean.is_some() {
             "ST".to_string() // Boolean as string
-        } else if observation.value_date_time.is_some() {
-            "DT".to_string() // Date/Time
+        } else if let Some(ref datetime) = observation.value_date_time {
+            if datetime.contains('T') {
+                "TS".to_string() // Time Stamp
+            } else {
+                "DT".to_string() // Date
+            }
+        } else if observation.value_time.is_some() {
+            "TM".to_string() // Time
+        } else if observation.value_range.is_some() {
+            "NR".to_string() // Numeric Range
+        } else if observation.value_ratio.is_some() {
+            "SN".to_string() // Structured Numeric
+        } else if observation.value_period.is_some() {
+            "DR".to_string() // Date/Time Range
         } else {
             "ST".to_string() // Default to string
         }
     }
 
     /// Set a coded element (CE/CWE) in a field
-    fn set_coded_element(
+    pub(crate) fn set_coded_element(
         obx: &mut Segment,
         field_num: usize,
         concept: &CodeableConcept,
@@ -141,8 +154,36 @@ impl ObservationReverseConverter {
         } else if let Some(boolean_value) = observation.value_boolean {
             let _ = obx.set_field_value(5, if boolean_value { "Y" } else { "N" });
         } else if let Some(ref datetime) = observation.value_date_time {
-            let hl7_datetime = datetime.replace(['-', ':', 'T'], "");
-            let _ = obx.set_field_value(5, &hl7_datetime);
+            let _ = obx.set_field_value(5, fhir_datetime_to_hl7(datetime));
+        } else if let Some(integer_value) = observation.value_integer {
+            let _ = obx.set_field_value(5, integer_value.to_string());
+        } else if let Some(ref time) = observation.value_time {
+            let _ = obx.set_field_value(5, time.replace(':', ""));
+        } else if let Some(ref range) = observation.value_range {
+            // NR: 1=Low Value, 2=High Value
+            if let Some(low) = range.low.as_ref().and_then(|q| q.value) {
+                let _ = obx.set_component(5, 0, 0, low.to_string());
+            }
+            if let Some(high) = range.high.as_ref().and_then(|q| q.value) {
+                let _ = obx.set_component(5, 0, 1, high.to_string());
+            }
+        } else if let Some(ref ratio) = observation.value_ratio {
+            // SN: 1=Comparator, 2=Num1, 3=Separator/Suffix, 4=Num2
+            if let Some(numerator) = ratio.numerator.as_ref().and_then(|q| q.value) {
+                let _ = obx.set_component(5, 0, 1, numerator.to_string());
+            }
+            if let Some(denominator) = ratio.denominator.as_ref().and_then(|q| q.value) {
+                let _ = obx.set_component(5, 0, 2, ":");
+                let _ = obx.set_component(5, 0, 3, denominator.to_string());
+            }
+        } else if let Some(ref period) = observation.value_period {
+            // DR: 1=Range Start, 2=Range End
+            if let Some(ref start) = period.start {
+                let _ = obx.set_component(5, 0, 0, fhir_datetime_to_hl7(start));
+            }
+            if let Some(ref end) = period.end {
+                let _ = obx.set_component(5, 0, 1, fhir_datetime_to_hl7(end));
+            }
         }
         Ok(())
     }
@@ -191,7 +232,7 @@ impl ObservationReverseConverter {
     }
 
     /// Extract a coding system name from a URL
-    fn extract_coding_system_name(system: &str) -> String {
+    pub(crate) fn extract_coding_system_name(system: &str) -> String {
         // Common FHIR coding system URL patterns
         if system.contains("loinc") {
             "LN".to_string()
@@ -207,6 +248,12 @@ impl ObservationReverseConverter {
             "RXNORM".to_string()
         } else if system.contains("ucum") {
             "UCUM".to_string()
+        } else if let Some(table) = system
+            .strip_prefix("http://terminology.hl7.org/CodeSystem/v2-")
+            .filter(|table| !table.is_empty() && table.bytes().all(|b| b.is_ascii_digit()))
+        {
+            // HL7 tables, e.g. v2-0443 -> HL70443
+            format!("HL7{}", table)
         } else {
             // Return the last path segment as the system name
             system
@@ -221,6 +268,7 @@ impl ObservationReverseConverter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::resources::observation::Ratio;
 
     fn create_code() -> CodeableConcept {
         CodeableConcept {
@@ -295,6 +343,32 @@ mod tests {
             ObservationReverseConverter::determine_value_type(&obs2),
             "ST"
         );
+
+        let mut obs3 = Observation::new("final".to_string(), create_code());
+        obs3.value_date_time = Some("2024-03-15".to_string());
+        assert_eq!(ObservationReverseConverter::determine_value_type(&obs3), "DT");
+        obs3.value_date_time = Some("2024-03-15T08:30:00".to_string());
+        assert_eq!(ObservationReverseConverter::determine_value_type(&obs3), "TS");
+
+        let mut obs4 = Observation::new("final".to_string(), create_code());
+        obs4.value_integer = Some(3);
+        assert_eq!(ObservationReverseConverter::determine_value_type(&obs4), "NM");
+    }
+
+    #[test]
+    fn test_convert_ratio_value() {
+        let mut obs = Observation::new("final".to_string(), create_code());
+        obs.value_ratio = Some(Ratio {
+            numerator: Some(Quantity { value: Some(1.0), unit: None, system: None, code: None }),
+            denominator: Some(Quantity { value: Some(128.0), unit: None, system: None, code: None }),
+        });
+
+        let obx = ObservationReverseConverter::convert(&obs, 1).unwrap();
+        assert_eq!(obx.get_field_value(2), Some("SN"));
+        let value = obx.get_field(5).unwrap();
+        assert_eq!(value.get_component(0, 1).and_then(|c| c.value()), Some("1"));
+        assert_eq!(value.get_component(0, 2).and_then(|c| c.value()), Some(":"));
+        assert_eq!(value.get_component(0, 3).and_then(|c| c.value()), Some("128"));
     }
 
     #[test]
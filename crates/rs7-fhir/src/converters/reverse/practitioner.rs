@@ -1,10 +1,22 @@
 //! Practitioner reverse converter - FHIR Practitioner resource to XCN data type
+//! and STF/ROL segments
 //!
 //! Converts FHIR Practitioner resources back to HL7 v2.x XCN (Extended Composite ID
 //! Number and Name for Persons) data type, used in fields like PV1-7, PV1-8, OBR-16, etc.
+//! A Practitioner together with its PractitionerRole also converts to the STF (staff)
+//! and ROL (role) segments of master file and provider directory messages.
 
+use super::fhir_datetime_to_hl7;
+use super::observation::ObservationReverseConverter;
+use crate::converters::config::NPI_SYSTEM;
 use crate::error::ConversionResult;
-use crate::resources::practitioner::Practitioner;
+use crate::resources::common::*;
+use crate::resources::practitioner::{Practitioner, PractitionerRole};
+use rs7_core::field::SubComponent;
+use rs7_core::segment::Segment;
+
+/// OID of the US National Provider Identifier
+const NPI_OID: &str = "2.16.840.1.113883.4.6";
 
 /// Reverse converter for transforming FHIR Practitioner resources to XCN format
 ///
@@ -205,12 +217,225 @@ impl PractitionerReverseConverter {
 
         Ok(components)
     }
+
+    /// Convert a FHIR Practitioner and its PractitionerRole to HL7 v2 segments
+    ///
+    /// The segments are returned in message order (STF, then ROL when a role is
+    /// given) so they can be spliced into an MFN^M02 or similar message.
+    ///
+    /// # Arguments
+    ///
+    /// * `practitioner` - The FHIR Practitioner resource
+    /// * `role` - The practitioner's PractitionerRole, if any
+    ///
+    /// # Returns
+    ///
+    /// The STF segment followed by the ROL segment
+    pub fn convert_segments(
+        practitioner: &Practitioner,
+        role: Option<&PractitionerRole>,
+    ) -> ConversionResult<Vec<Segment>> {
+        let mut segments = vec![Self::convert_stf(practitioner)?];
+        if let Some(role) = role {
+            segments.push(Self::convert_rol(practitioner, role)?);
+        }
+        Ok(segments)
+    }
+
+    /// Convert a FHIR Practitioner resource to an HL7 v2 STF segment
+    ///
+    /// Every identifier becomes a repetition of STF-2 (CX). NPIs, recognized
+    /// by their FHIR system or OID, get the `NPI&2.16.840.1.113883.4.6&ISO`
+    /// assigning authority and identifier type `NPI`; other `urn:oid:` systems
+    /// become an ISO universal ID and remaining systems a URI.
+    ///
+    /// # Arguments
+    ///
+    /// * `practitioner` - The FHIR Practitioner resource
+    ///
+    /// # Returns
+    ///
+    /// An HL7 v2 STF segment
+    pub fn convert_stf(practitioner: &Practitioner) -> ConversionResult<Segment> {
+        let mut stf = Segment::new("STF");
+
+        // STF-1: Primary Key Value
+        if let Some(ref id) = practitioner.id {
+            let _ = stf.set_field_value(1, id);
+        }
+
+        // STF-2: Staff Identifier List
+        if let Some(ref identifiers) = practitioner.identifier {
+            for (rep, identifier) in identifiers.iter().enumerate() {
+                Self::set_cx(&mut stf, 2, rep, identifier);
+            }
+        }
+
+        // STF-3: Staff Name (XPN: 1=Family, 2=Given, 3=Middle, 4=Suffix, 5=Prefix, 7=Type)
+        if let Some(ref names) = practitioner.name {
+            for (rep, name) in names.iter().enumerate() {
+                if let Some(ref family) = name.family {
+                    let _ = stf.set_component(3, rep, 0, family);
+                }
+                if let Some(ref given) = name.given {
+                    if let Some(first_given) = given.first() {
+                        let _ = stf.set_component(3, rep, 1, first_given);
+                    }
+                    if given.len() > 1 {
+                        let _ = stf.set_component(3, rep, 2, &given[1]);
+                    }
+                }
+                if let Some(suffix) = name.suffix.as_ref().and_then(|s| s.first()) {
+                    let _ = stf.set_component(3, rep, 3, suffix);
+                }
+                if let Some(prefix) = name.prefix.as_ref().and_then(|p| p.first()) {
+                    let _ = stf.set_component(3, rep, 4, prefix);
+                }
+                if let Some(ref use_) = name.use_ {
+                    let type_code = match use_.as_str() {
+                        "nickname" => "N",
+                        "maiden" => "M",
+                        _ => "L",
+                    };
+                    let _ = stf.set_component(3, rep, 6, type_code);
+                }
+            }
+        }
+
+        // STF-5: Administrative Sex
+        if let Some(ref gender) = practitioner.gender {
+            let sex = match gender.as_str() {
+                "male" => "M",
+                "female" => "F",
+                "other" => "O",
+                _ => "U",
+            };
+            let _ = stf.set_field_value(5, sex);
+        }
+
+        // STF-6: Date/Time of Birth
+        if let Some(ref birth_date) = practitioner.birth_date {
+            let _ = stf.set_field_value(6, birth_date.replace('-', ""));
+        }
+
+        // STF-7: Active/Inactive Flag
+        if let Some(active) = practitioner.active {
+            let _ = stf.set_field_value(7, if active { "A" } else { "I" });
+        }
+
+        Ok(stf)
+    }
+
+    /// Convert a FHIR PractitionerRole to an HL7 v2 ROL segment
+    ///
+    /// ROL-2 (Action Code) is `UC` (unchanged), as the role describes the
+    /// current state; set it on the returned segment to report a change.
+    ///
+    /// # Arguments
+    ///
+    /// * `practitioner` - The FHIR Practitioner filling the role, for ROL-4
+    /// * `role` - The FHIR PractitionerRole resource
+    ///
+    /// # Returns
+    ///
+    /// An HL7 v2 ROL segment
+    pub fn convert_rol(practitioner: &Practitioner, role: &PractitionerRole) -> ConversionResult<Segment> {
+        let mut rol = Segment::new("ROL");
+
+        // ROL-1: Role Instance ID
+        let instance_id = role
+            .identifier
+            .as_ref()
+            .and_then(|ids| ids.first())
+            .and_then(|id| id.value.as_ref())
+            .or(role.id.as_ref());
+        if let Some(instance_id) = instance_id {
+            let _ = rol.set_field_value(1, instance_id);
+        }
+
+        // ROL-2: Action Code
+        let _ = rol.set_field_value(2, "UC");
+
+        // ROL-3: Role-ROL (CE: 1=Identifier, 2=Text, 3=Coding System)
+        let coding = role
+            .code
+            .as_ref()
+            .and_then(|codes| codes.first())
+            .and_then(|code| code.coding.as_ref())
+            .and_then(|coding| coding.first());
+        if let Some(coding) = coding {
+            if let Some(ref code) = coding.code {
+                let _ = rol.set_component(3, 0, 0, code);
+            }
+            if let Some(ref display) = coding.display {
+                let _ = rol.set_component(3, 0, 1, display);
+            }
+            if let Some(ref system) = coding.system {
+                let system_name = ObservationReverseConverter::extract_coding_system_name(system);
+                let _ = rol.set_component(3, 0, 2, system_name);
+            }
+        }
+
+        // ROL-4: Role Person
+        for (index, value) in Self::convert_to_components(practitioner)? {
+            let _ = rol.set_component(4, 0, index, value);
+        }
+
+        // ROL-5/ROL-6: Role Begin and End Date/Time
+        if let Some(ref period) = role.period {
+            if let Some(ref start) = period.start {
+                let _ = rol.set_field_value(5, fhir_datetime_to_hl7(start));
+            }
+            if let Some(ref end) = period.end {
+                let _ = rol.set_field_value(6, fhir_datetime_to_hl7(end));
+            }
+        }
+
+        Ok(rol)
+    }
+
+    /// Set an identifier as a CX repetition (1=ID, 4=Assigning Authority, 5=Type)
+    fn set_cx(segment: &mut Segment, field: usize, rep: usize, identifier: &Identifier) {
+        let _ = segment.set_component(field, rep, 0, identifier.value.clone().unwrap_or_default());
+
+        let mut type_code = identifier
+            .type_
+            .as_ref()
+            .and_then(|t| t.coding.as_ref())
+            .and_then(|coding| coding.first())
+            .and_then(|coding| coding.code.clone());
+
+        // CX-4: Assigning Authority (HD: 1=Namespace ID, 2=Universal ID, 3=Universal ID Type)
+        if let Some(ref system) = identifier.system {
+            let authority = if system == NPI_SYSTEM || system.strip_prefix("urn:oid:") == Some(NPI_OID) {
+                type_code.get_or_insert_with(|| "NPI".to_string());
+                ["NPI", NPI_OID, "ISO"]
+            } else if let Some(oid) = system.strip_prefix("urn:oid:") {
+                ["", oid, "ISO"]
+            } else {
+                ["", system.as_str(), "URI"]
+            };
+            let _ = segment.set_component(field, rep, 3, "");
+            if let Some(component) = segment
+                .get_field_mut(field)
+                .and_then(|f| f.get_repetition_mut(rep))
+                .and_then(|r| r.get_component_mut(3))
+            {
+                component.subcomponents = authority.iter().map(|&value| SubComponent::new(value)).collect();
+            }
+        }
+
+        // CX-5: Identifier Type Code
+        if let Some(type_code) = type_code {
+            let _ = segment.set_component(field, rep, 4, type_code);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::resources::common::*;
+    use crate::resources::practitioner::Period;
 
     fn create_test_practitioner() -> Practitioner {
         let mut practitioner = Practitioner::new();
@@ -274,6 +499,53 @@ mod tests {
         assert_eq!(given_component.unwrap().1, "John");
     }
 
+    #[test]
+    fn test_convert_segments() {
+        let mut practitioner = create_test_practitioner();
+        practitioner.id = Some("PR001".to_string());
+        practitioner.active = Some(true);
+        practitioner.identifier.as_mut().unwrap().push(Identifier {
+            use_: None,
+            type_: None,
+            system: Some("http://hospital.example.org/staff".to_string()),
+            value: Some("S42".to_string()),
+            assigner: None,
+        });
+
+        let mut role = PractitionerRole::new();
+        role.id = Some("ROLE1".to_string());
+        role.code = Some(vec![CodeableConcept {
+            coding: Some(vec![Coding {
+                system: Some("http://terminology.hl7.org/CodeSystem/v2-0443".to_string()),
+                version: None,
+                code: Some("AT".to_string()),
+                display: Some("Attending".to_string()),
+            }]),
+            text: None,
+        }]);
+        role.period = Some(Period {
+            start: Some("2024-01-01T08:00:00-05:00".to_string()),
+            end: None,
+        });
+
+        let segments = PractitionerReverseConverter::convert_segments(&practitioner, Some(&role)).unwrap();
+        assert_eq!(segments.len(), 2);
+
+        let delimiters = Default::default();
+        assert_eq!(
+            segments[0].encode(&delimiters),
+            "STF|PR001|1234567890^^^NPI&2.16.840.1.113883.4.6&ISO^NPI~S42^^^&http://hospital.example.org/staff&URI|Smith^John^A^MD^Dr^^L||||A"
+        );
+        assert_eq!(
+            segments[1].encode(&delimiters),
+            "ROL|ROLE1|UC|AT^Attending^HL70443|1234567890^Smith^John^A^MD^Dr^^^2.16.840.1.113883.4.6^L^^^NPI|20240101080000-0500"
+        );
+
+        let segments = PractitionerReverseConverter::convert_segments(&Practitioner::new(), None).unwrap();
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].id, "STF");
+    }
+
     #[test]
     fn test_minimal_practitioner() {
         let mut practitioner = Practitioner::new();
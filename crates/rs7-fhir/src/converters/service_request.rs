@@ -5,12 +5,21 @@ use rs7_terser::Terser;
 use crate::error::{ConversionError, ConversionResult};
 use crate::resources::service_request::*;
 use crate::resources::common::*;
+use super::config::ConverterConfig;
 
 pub struct ServiceRequestConverter;
 
 impl ServiceRequestConverter {
     /// Convert all ORC segments in message to ServiceRequest resources
     pub fn convert_all(message: &Message) -> ConversionResult<Vec<ServiceRequest>> {
+        Self::convert_all_with_config(message, &ConverterConfig::default())
+    }
+
+    /// Convert all ORC segments in message to ServiceRequest resources using the given configuration
+    pub fn convert_all_with_config(
+        message: &Message,
+        config: &ConverterConfig,
+    ) -> ConversionResult<Vec<ServiceRequest>> {
         let orc_segments: Vec<_> = message
             .segments
             .iter()
@@ -24,7 +33,7 @@ impl ServiceRequestConverter {
 
         let mut service_requests = Vec::new();
         for (orc_count, _) in orc_segments.iter().enumerate() {
-            let sr = Self::convert_single(message, orc_count)?;
+            let sr = Self::convert_single_with_config(message, orc_count, config)?;
             service_requests.push(sr);
         }
 
@@ -37,6 +46,18 @@ impl ServiceRequestConverter {
     /// * `message` - HL7 message containing ORC segment
     /// * `orc_index` - 0-based index (0 = first ORC, 1 = second ORC, etc.)
     pub fn convert_single(message: &Message, orc_index: usize) -> ConversionResult<ServiceRequest> {
+        Self::convert_single_with_config(message, orc_index, &ConverterConfig::default())
+    }
+
+    /// Convert a specific ORC segment to ServiceRequest resource using the given configuration
+    ///
+    /// Order number systems are resolved from the EI-2 namespace ID through
+    /// `config`, falling back to its placer and filler order systems.
+    pub fn convert_single_with_config(
+        message: &Message,
+        orc_index: usize,
+        config: &ConverterConfig,
+    ) -> ConversionResult<ServiceRequest> {
         let terser = Terser::new(message);
 
         // Build base path for ORC fields
@@ -68,7 +89,10 @@ impl ServiceRequestConverter {
         if let Ok(Some(placer_id)) = terser.get(&orc_path("2")) {
             let mut identifiers = service_request.identifier.unwrap_or_default();
             identifiers.push(Identifier {
-                system: Some("http://hospital.example.org/placer-order-number".to_string()),
+                system: config.identifier_system(
+                    terser.get(&orc_path("2-2")).ok().flatten(),
+                    config.placer_order_system.as_deref(),
+                ),
                 value: Some(placer_id.to_string()),
                 type_: None,
                 use_: None,
@@ -81,7 +105,10 @@ impl ServiceRequestConverter {
         if let Ok(Some(filler_id)) = terser.get(&orc_path("3")) {
             let mut identifiers = service_request.identifier.unwrap_or_default();
             identifiers.push(Identifier {
-                system: Some("http://hospital.example.org/filler-order-number".to_string()),
+                system: config.identifier_system(
+                    terser.get(&orc_path("3-2")).ok().flatten(),
+                    config.filler_order_system.as_deref(),
+                ),
                 value: Some(filler_id.to_string()),
                 type_: None,
                 use_: None,
@@ -93,7 +120,7 @@ impl ServiceRequestConverter {
         // ORC-4: Placer Group Number -> requisition
         if let Ok(Some(group_id)) = terser.get(&orc_path("4")) {
             service_request.requisition = Some(Identifier {
-                system: Some("http://hospital.example.org/requisition".to_string()),
+                system: config.identifier_system(terser.get(&orc_path("4-2")).ok().flatten(), None),
                 value: Some(group_id.to_string()),
                 type_: None,
                 use_: None,
@@ -329,6 +356,28 @@ mod tests {
         assert_eq!(identifiers[1].value, Some("FILLER456".to_string()));
     }
 
+    #[test]
+    fn test_order_number_systems() {
+        let hl7 = "MSH|^~\\&|CPOE|Hospital|LAB|Hospital|20240315||ORM^O01|MSG123|P|2.5\r\
+                   PID|1||12345^^^MRN||DOE^JOHN||19800101|M\r\
+                   ORC|NW|PLACER123^CPOE|FILLER456^LAB|GRP789\r\
+                   OBR|1|PLACER123|FILLER456|CBC^Complete Blood Count^LN";
+        let message = parse_message(hl7).unwrap();
+
+        let config = ConverterConfig::new()
+            .with_identifier_system("CPOE", "http://hospital.example.org/cpoe-orders")
+            .with_filler_order_system("http://hospital.example.org/lab-orders");
+        let sr = &ServiceRequestConverter::convert_all_with_config(&message, &config).unwrap()[0];
+        let identifiers = sr.identifier.as_ref().unwrap();
+        assert_eq!(identifiers[0].system, Some("http://hospital.example.org/cpoe-orders".to_string()));
+        assert_eq!(identifiers[1].system, Some("http://hospital.example.org/lab-orders".to_string()));
+        assert_eq!(sr.requisition.as_ref().unwrap().system, None);
+
+        // Without configuration the namespace becomes an OID URI
+        let sr = &ServiceRequestConverter::convert_all(&message).unwrap()[0];
+        assert_eq!(sr.identifier.as_ref().unwrap()[1].system, Some("urn:oid:LAB".to_string()));
+    }
+
     #[test]
     fn test_convert_service_request_with_code_and_priority() {
         let hl7 = "MSH|^~\\&|CPOE|Hospital|LAB|Hospital|20240315||ORM^O01|MSG123|P|2.5\r\
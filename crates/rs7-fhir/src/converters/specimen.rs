@@ -5,12 +5,21 @@ use rs7_terser::Terser;
 use crate::error::{ConversionError, ConversionResult};
 use crate::resources::specimen::*;
 use crate::resources::common::*;
+use super::config::ConverterConfig;
 
 pub struct SpecimenConverter;
 
 impl SpecimenConverter {
     /// Convert all SPM segments in message to Specimen resources
     pub fn convert_all(message: &Message) -> ConversionResult<Vec<Specimen>> {
+        Self::convert_all_with_config(message, &ConverterConfig::default())
+    }
+
+    /// Same as [`Self::convert_all`], resolving identifier systems through `config`
+    pub fn convert_all_with_config(
+        message: &Message,
+        config: &ConverterConfig,
+    ) -> ConversionResult<Vec<Specimen>> {
         let spm_segments: Vec<_> = message
             .segments
             .iter()
@@ -24,7 +33,7 @@ impl SpecimenConverter {
 
         let mut specimens = Vec::new();
         for (spm_count, _) in spm_segments.iter().enumerate() {
-            let specimen = Self::convert_single(message, spm_count)?;
+            let specimen = Self::convert_single_with_config(message, spm_count, config)?;
             specimens.push(specimen);
         }
 
@@ -37,6 +46,19 @@ impl SpecimenConverter {
     /// * `message` - HL7 message containing SPM segment
     /// * `spm_index` - 0-based index (0 = first SPM, 1 = second SPM, etc.)
     pub fn convert_single(message: &Message, spm_index: usize) -> ConversionResult<Specimen> {
+        Self::convert_single_with_config(message, spm_index, &ConverterConfig::default())
+    }
+
+    /// Same as [`Self::convert_single`], resolving identifier systems through `config`
+    ///
+    /// The SPM-2 system comes from the placer namespace (EI-2), falling back to
+    /// the configured specimen ID system; the SPM-30 system comes from the
+    /// assigning authority (CX-4), falling back to the accession system.
+    pub fn convert_single_with_config(
+        message: &Message,
+        spm_index: usize,
+        config: &ConverterConfig,
+    ) -> ConversionResult<Specimen> {
         let terser = Terser::new(message);
 
         // Build base path for SPM fields
@@ -53,14 +75,19 @@ impl SpecimenConverter {
         let specimen_type = Self::extract_specimen_type(&terser, &spm_path("4"))?;
 
         // PID-3: Patient ID -> subject reference
-        let subject = Self::extract_patient_reference(&terser)?;
+        let subject = config
+            .patient_reference(&terser)
+            .ok_or_else(|| ConversionError::MissingSegment("PID".to_string()))?;
 
         let mut specimen = Specimen::new(specimen_type, subject);
 
         // SPM-2: Specimen ID -> identifier
         if let Ok(Some(specimen_id)) = terser.get(&spm_path("2")) {
             specimen.identifier = Some(vec![Identifier {
-                system: Some("http://hospital.example.org/specimen-id".to_string()),
+                system: config.identifier_system(
+                    terser.get(&spm_path("2-1-2")).ok().flatten(),
+                    config.specimen_id_system.as_deref(),
+                ),
                 value: Some(specimen_id.to_string()),
                 type_: None,
                 use_: None,
@@ -71,7 +98,10 @@ impl SpecimenConverter {
         // SPM-30: Accession ID -> accessionIdentifier
         if let Ok(Some(accession_id)) = terser.get(&spm_path("30")) {
             specimen.accession_identifier = Some(Identifier {
-                system: Some("http://hospital.example.org/accession".to_string()),
+                system: config.identifier_system(
+                    terser.get(&spm_path("30-4")).ok().flatten(),
+                    config.accession_system.as_deref(),
+                ),
                 value: Some(accession_id.to_string()),
                 type_: None,
                 use_: None,
@@ -301,20 +331,6 @@ impl SpecimenConverter {
         })
     }
 
-    /// Extract patient reference from PID segment
-    fn extract_patient_reference(terser: &Terser) -> ConversionResult<Reference> {
-        let patient_id = terser.get("PID-3")
-            .ok()
-            .flatten()
-            .ok_or_else(|| ConversionError::MissingSegment("PID".to_string()))?;
-
-        Ok(Reference {
-            reference: Some(format!("Patient/{}", patient_id)),
-            type_: Some("Patient".to_string()),
-            identifier: None,
-            display: None,
-        })
-    }
 }
 
 #[cfg(test)]
@@ -371,6 +387,30 @@ mod tests {
         assert_eq!(spec.accession_identifier.as_ref().unwrap().value, Some("ACC123".to_string()));
     }
 
+    #[test]
+    fn test_convert_specimen_identifier_systems_from_config() {
+        let hl7 = "MSH|^~\\&|LAB|Hospital|EMR|Hospital|20240315||OML^O21|MSG123|P|2.5\r\
+                   PID|1||12345^^^MRN||DOE^JOHN||19800101|M\r\
+                   SPM|1|SPEC123&LABNS||BLD^Blood^HL70487||||||||||||||||||||||||||ACC123^^^LABACC";
+
+        let message = parse_message(hl7).unwrap();
+
+        let config = ConverterConfig::new()
+            .with_identifier_system("LABNS", "http://lab.example.org/specimens")
+            .with_accession_system("http://lab.example.org/accessions");
+        let spec = &SpecimenConverter::convert_all_with_config(&message, &config).unwrap()[0];
+        let identifier = &spec.identifier.as_ref().unwrap()[0];
+        assert_eq!(identifier.value, Some("SPEC123".to_string()));
+        assert_eq!(identifier.system, Some("http://lab.example.org/specimens".to_string()));
+        let accession = spec.accession_identifier.as_ref().unwrap();
+        assert_eq!(accession.system, Some("http://lab.example.org/accessions".to_string()));
+
+        // Without a config, unmapped authorities become OIDs rather than made-up URIs
+        let spec = &SpecimenConverter::convert_all(&message).unwrap()[0];
+        assert_eq!(spec.identifier.as_ref().unwrap()[0].system, Some("urn:oid:LABNS".to_string()));
+        assert_eq!(spec.accession_identifier.as_ref().unwrap().system, Some("urn:oid:LABACC".to_string()));
+    }
+
     #[test]
     fn test_convert_multiple_specimens() {
         let hl7 = "MSH|^~\\&|LAB|Hospital|EMR|Hospital|20240315||OML^O21|MSG123|P|2.5\r\
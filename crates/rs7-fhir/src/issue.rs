@@ -0,0 +1,151 @@
+//! Issues found while converting HL7 v2 data to FHIR
+//!
+//! Validated conversions such as
+//! [`PatientConverter::convert_validated`](crate::converters::PatientConverter::convert_validated)
+//! return a best-effort resource together with the problems found along the
+//! way, so callers can decide whether to accept it. Issues follow the shape of
+//! a FHIR `OperationOutcome.issue`: a severity, a location (the source HL7
+//! path) and a human-readable diagnostic.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Severity of a conversion issue, as in `OperationOutcome.issue.severity`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueSeverity {
+    /// The conversion could not produce a meaningful resource
+    Fatal,
+    /// A source value could not be converted and was left out
+    Error,
+    /// A value was converted, but may not mean what the receiver expects
+    Warning,
+    /// A default or assumption was applied
+    Information,
+}
+
+impl IssueSeverity {
+    /// Get the FHIR code for this severity
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssueSeverity::Fatal => "fatal",
+            IssueSeverity::Error => "error",
+            IssueSeverity::Warning => "warning",
+            IssueSeverity::Information => "information",
+        }
+    }
+}
+
+impl fmt::Display for IssueSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A problem found while converting a message, located by its HL7 path
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversionIssue {
+    /// How serious the issue is
+    pub severity: IssueSeverity,
+
+    /// Terser path of the source value, e.g. `PID-7` or `PID-3(1)-4`
+    pub path: String,
+
+    /// Description of the issue
+    pub message: String,
+}
+
+impl ConversionIssue {
+    /// Create an issue
+    pub fn new(severity: IssueSeverity, path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Create a fatal issue
+    pub fn fatal(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(IssueSeverity::Fatal, path, message)
+    }
+
+    /// Create an error issue
+    pub fn error(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(IssueSeverity::Error, path, message)
+    }
+
+    /// Create a warning issue
+    pub fn warning(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(IssueSeverity::Warning, path, message)
+    }
+
+    /// Create an informational issue
+    pub fn information(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(IssueSeverity::Information, path, message)
+    }
+
+    /// Check if the issue is fatal or an error
+    pub fn is_error(&self) -> bool {
+        self.severity <= IssueSeverity::Error
+    }
+}
+
+impl fmt::Display for ConversionIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}: {}", self.severity, self.path, self.message)
+    }
+}
+
+/// Check that a value is an HL7 DT or DTM: `YYYY[MM[DD[HH[MM[SS[.S+]]]]]][+/-ZZZZ]`
+pub(crate) fn is_valid_timestamp(value: &str) -> bool {
+    let (value, offset) = match value.char_indices().skip(1).find(|(_, c)| *c == '+' || *c == '-') {
+        Some((i, _)) => (&value[..i], Some(&value[i + 1..])),
+        None => (value, None),
+    };
+    if offset.is_some_and(|offset| offset.len() != 4 || !offset.bytes().all(|b| b.is_ascii_digit())) {
+        return false;
+    }
+    let (digits, fraction) = match value.split_once('.') {
+        Some((digits, fraction)) => (digits, Some(fraction)),
+        None => (value, None),
+    };
+    if fraction.is_some_and(|fraction| {
+        digits.len() != 14 || fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit())
+    }) {
+        return false;
+    }
+    if !matches!(digits.len(), 4 | 6 | 8 | 10 | 12 | 14) || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+
+    // Month, day, hour, minute and second ranges, as far as present
+    let ranges = [(1, 12), (1, 31), (0, 23), (0, 59), (0, 59)];
+    digits.as_bytes()[4..]
+        .chunks(2)
+        .zip(ranges)
+        .all(|(pair, (min, max))| {
+            let number = (pair[0] - b'0') * 10 + (pair[1] - b'0');
+            (min..=max).contains(&number)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_timestamp() {
+        for valid in ["1980", "198001", "19800101", "20240315143000", "20240315143000.123-0500", "198001010930+0100"] {
+            assert!(is_valid_timestamp(valid), "{}", valid);
+        }
+        for invalid in ["", "80", "1980011", "19801301", "19800132", "20240315250000", "1980-01-01", "19800101+01", "1980.5"] {
+            assert!(!is_valid_timestamp(invalid), "{}", invalid);
+        }
+
+        let issue = ConversionIssue::warning("PID-3-4", "No assigning authority");
+        assert_eq!(issue.to_string(), "warning at PID-3-4: No assigning authority");
+        assert!(!issue.is_error());
+        assert!(ConversionIssue::fatal("PID", "Missing").is_error());
+    }
+}
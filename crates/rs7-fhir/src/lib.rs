@@ -8,7 +8,7 @@
 //! - Convert HL7 v2.x segments to FHIR R4 resources
 //! - Support for Patient, Observation, Practitioner, and other common resources
 //! - Configurable conversion with extension support
-//! - Validation of converted resources
+//! - Validation of converted resources, with issues reported per source HL7 path
 //!
 //! # Examples
 //!
@@ -23,18 +23,31 @@
 //! let patient = PatientConverter::convert(&message)?;
 //! let json = serde_json::to_string_pretty(&patient)?;
 //! ```
+//!
+//! A whole message can be converted into a FHIR Bundle:
+//!
+//! ```rust,ignore
+//! use rs7_fhir::converters::MessageConverter;
+//!
+//! let bundle = MessageConverter::new().convert(&message)?;
+//! ```
 
 pub mod converters;
 pub mod resources;
 pub mod error;
+pub mod issue;
 
 pub use error::{ConversionError, ConversionResult};
+pub use issue::{ConversionIssue, IssueSeverity};
 
 /// Re-export commonly used types
 pub mod prelude {
     pub use crate::converters::patient::PatientConverter;
     pub use crate::converters::observation::ObservationConverter;
     pub use crate::converters::practitioner::PractitionerConverter;
+    pub use crate::converters::message::{BundleType, MessageConverter};
+    pub use crate::converters::config::ConverterConfig;
     pub use crate::resources::*;
     pub use crate::error::{ConversionError, ConversionResult};
+    pub use crate::issue::{ConversionIssue, IssueSeverity};
 }
@@ -0,0 +1,69 @@
+//! FHIR Bundle resource
+//!
+//! Based on FHIR R4 Bundle: <https://www.hl7.org/fhir/R4/bundle.html>
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// FHIR Bundle resource - A container for a collection of resources
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Bundle {
+    /// Resource type (always "Bundle")
+    pub resource_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// document | message | transaction | transaction-response | batch | batch-response | history | searchset | collection
+    pub type_: String,
+
+    /// When the bundle was assembled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+
+    /// Entry in the bundle - will have a resource or information
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry: Option<Vec<BundleEntry>>,
+}
+
+/// Bundle entry - A resource together with its full URL
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleEntry {
+    /// URI for the resource, used to resolve references within the bundle
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_url: Option<String>,
+
+    /// The resource in this entry
+    pub resource: Value,
+}
+
+impl Bundle {
+    pub fn new(type_: String) -> Self {
+        Self {
+            resource_type: "Bundle".to_string(),
+            id: None,
+            type_,
+            timestamp: None,
+            entry: None,
+        }
+    }
+
+    /// Add a resource entry to the bundle
+    pub fn add_entry(&mut self, full_url: String, resource: Value) {
+        self.entry.get_or_insert_with(Vec::new).push(BundleEntry {
+            full_url: Some(full_url),
+            resource,
+        });
+    }
+
+    /// Find the resources of a given type in the bundle
+    pub fn resources_of_type<'a>(&'a self, resource_type: &'a str) -> impl Iterator<Item = &'a Value> + 'a {
+        self.entry
+            .iter()
+            .flatten()
+            .map(|e| &e.resource)
+            .filter(move |r| r.get("resourceType").and_then(Value::as_str) == Some(resource_type))
+    }
+}
@@ -0,0 +1,68 @@
+//! FHIR MessageHeader resource
+//!
+//! Based on FHIR R4 MessageHeader: <https://www.hl7.org/fhir/R4/messageheader.html>
+
+use serde::{Deserialize, Serialize};
+use super::common::*;
+
+/// FHIR MessageHeader resource - The header for a message exchange
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageHeader {
+    /// Resource type (always "MessageHeader")
+    pub resource_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Code for the event this message represents
+    pub event_coding: Coding,
+
+    /// Message destination application(s)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination: Option<Vec<MessageDestination>>,
+
+    /// Message source application
+    pub source: MessageSource,
+
+    /// The actual content of the message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focus: Option<Vec<Reference>>,
+}
+
+/// Message source application
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageSource {
+    /// Name of system
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Actual message source address or id
+    pub endpoint: String,
+}
+
+/// Message destination application
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageDestination {
+    /// Name of system
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Actual destination address or id
+    pub endpoint: String,
+}
+
+impl MessageHeader {
+    pub fn new(event_coding: Coding, source: MessageSource) -> Self {
+        Self {
+            resource_type: "MessageHeader".to_string(),
+            id: None,
+            event_coding,
+            destination: None,
+            source,
+            focus: None,
+        }
+    }
+}
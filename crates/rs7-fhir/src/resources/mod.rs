@@ -15,11 +15,13 @@ pub mod procedure;
 pub mod immunization;
 pub mod service_request;
 pub mod specimen;
+pub mod bundle;
+pub mod message_header;
 pub mod common;
 
 pub use patient::Patient;
 pub use observation::Observation;
-pub use practitioner::Practitioner;
+pub use practitioner::{Practitioner, PractitionerRole};
 pub use encounter::Encounter;
 pub use diagnostic_report::DiagnosticReport;
 pub use allergy_intolerance::AllergyIntolerance;
@@ -29,4 +31,6 @@ pub use procedure::Procedure;
 pub use immunization::Immunization;
 pub use service_request::ServiceRequest;
 pub use specimen::Specimen;
+pub use bundle::{Bundle, BundleEntry};
+pub use message_header::{MessageDestination, MessageHeader, MessageSource};
 pub use common::*;
@@ -55,6 +55,54 @@ pub struct Practitioner {
     pub communication: Option<Vec<CodeableConcept>>,
 }
 
+/// FHIR PractitionerRole resource - A practitioner's roles at an organization
+///
+/// Based on FHIR R4 PractitionerRole: <https://www.hl7.org/fhir/R4/practitionerrole.html>
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PractitionerRole {
+    /// Resource type (always "PractitionerRole")
+    pub resource_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+
+    /// Business identifiers specific to this role
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<Vec<Identifier>>,
+
+    /// Whether this role record is in active use
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<bool>,
+
+    /// The period during which the practitioner is authorized to perform the role
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<Period>,
+
+    /// Practitioner that is able to provide the services
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub practitioner: Option<Reference>,
+
+    /// Organization where the roles are available
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization: Option<Reference>,
+
+    /// Roles which this practitioner may perform
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<Vec<CodeableConcept>>,
+
+    /// Specific specialty of the practitioner
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub specialty: Option<Vec<CodeableConcept>>,
+
+    /// Contact details that are specific to the role
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telecom: Option<Vec<ContactPoint>>,
+}
+
 /// Qualifications obtained by training and certification
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -111,3 +159,28 @@ impl Default for Practitioner {
         Self::new()
     }
 }
+
+impl PractitionerRole {
+    /// Create a new PractitionerRole resource with default values
+    pub fn new() -> Self {
+        Self {
+            resource_type: "PractitionerRole".to_string(),
+            id: None,
+            meta: None,
+            identifier: None,
+            active: None,
+            period: None,
+            practitioner: None,
+            organization: None,
+            code: None,
+            specialty: None,
+            telecom: None,
+        }
+    }
+}
+
+impl Default for PractitionerRole {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -9,6 +9,9 @@ use std::time::Duration;
 #[cfg(feature = "tls")]
 use crate::tls::TlsClientConfig;
 
+#[cfg(feature = "retry")]
+use crate::retry::{RetryExecutor, RetryPolicy};
+
 /// HTTP client for sending HL7 v2.x messages
 ///
 /// # Example
@@ -39,6 +42,8 @@ pub struct HttpClient {
     #[cfg(feature = "tls")]
     tls_config: Option<TlsClientConfig>,
     http2_only: bool,
+    #[cfg(feature = "retry")]
+    retry_policy: Option<RetryPolicy>,
 }
 
 impl HttpClient {
@@ -70,6 +75,8 @@ impl HttpClient {
             #[cfg(feature = "tls")]
             tls_config: None,
             http2_only: false,
+            #[cfg(feature = "retry")]
+            retry_policy: None,
         })
     }
 
@@ -149,6 +156,37 @@ impl HttpClient {
         self
     }
 
+    /// Retry failed sends according to a retry policy
+    ///
+    /// Only transport errors and HTTP 5xx responses are retried; other 4xx
+    /// responses fail immediately, and AE/AR acknowledgments sent with 422
+    /// are returned without retrying.
+    /// Every attempt sends the same encoded message, so MSH-10 is unchanged
+    /// and the receiver can detect duplicates. When all attempts fail the last
+    /// error is returned; an attempt still running at the policy's deadline
+    /// is cancelled.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "retry")]
+    /// # {
+    /// use rs7_http::{HttpClient, retry::RetryPolicy};
+    /// use std::time::Duration;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = HttpClient::new("https://example.com/hl7")?
+    ///     .with_retry(RetryPolicy::exponential().with_deadline(Duration::from_secs(10)));
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    #[cfg(feature = "retry")]
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
     /// Set request timeout
     ///
     /// # Arguments
@@ -181,16 +219,20 @@ impl HttpClient {
     /// * `message` - The HL7 message to send
     ///
     /// # Returns
-    /// The response message (typically an ACK)
+    /// The response message (typically an ACK). AE/AR acknowledgments that
+    /// the server sends with 422 Unprocessable Entity are returned too, so
+    /// check MSA-1 and any ERR segments for rejections.
     ///
     /// # Errors
     /// Returns an error if:
     /// - The network request fails
-    /// - The server returns a non-2xx status code
+    /// - The server returns a non-2xx status code other than 422 with an
+    ///   HL7 body
     /// - The response content type is invalid
     /// - The response cannot be parsed as an HL7 message
     pub async fn send_message(&self, message: &Message) -> Result<Message> {
-        let hl7_text = message.encode();
+        // HL7 mandates carriage returns on the wire, whatever the input used
+        let hl7_text = message.encode_with_separator("\r");
         let ack_text = self.send_message_raw(&hl7_text).await?;
         Ok(parse_message(&ack_text)?)
     }
@@ -209,6 +251,18 @@ impl HttpClient {
     /// # Errors
     /// Returns an error if the request fails or the response is invalid
     pub async fn send_message_raw(&self, hl7_text: &str) -> Result<String> {
+        #[cfg(feature = "retry")]
+        if let Some(policy) = &self.retry_policy {
+            return RetryExecutor::new(policy.clone())
+                .execute(|| self.send_once(hl7_text))
+                .await;
+        }
+
+        self.send_once(hl7_text).await
+    }
+
+    /// Make a single POST attempt
+    async fn send_once(&self, hl7_text: &str) -> Result<String> {
         // Build request
         let mut request = self
             .client
@@ -225,9 +279,18 @@ impl HttpClient {
         // Send request
         let response = request.send().await?;
 
-        // Check status code
         let status = response.status();
-        if !status.is_success() {
+        let content_type = response
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+            .to_string();
+
+        // Check status code; AE/AR acknowledgments arrive as 422 with the
+        // ACK as body and are returned like any other response
+        let is_nack = status == reqwest::StatusCode::UNPROCESSABLE_ENTITY && content_type.contains("hl7");
+        if !status.is_success() && !is_nack {
             return Err(Error::Http(format!(
                 "HTTP {} {}",
                 status.as_u16(),
@@ -236,16 +299,10 @@ impl HttpClient {
         }
 
         // Verify content type
-        let content_type = response
-            .headers()
-            .get("content-type")
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("");
-
         if !content_type.contains("hl7") {
             return Err(Error::ContentType {
                 expected: CONTENT_TYPE_HL7_ER7.to_string(),
-                actual: content_type.to_string(),
+                actual: content_type,
             });
         }
 
@@ -272,6 +329,41 @@ mod tests {
         assert!(HttpClient::new("").is_err());
     }
 
+    #[cfg(all(feature = "retry", feature = "testing"))]
+    #[tokio::test]
+    async fn test_retry_on_server_error() {
+        use crate::testing::MockHttpServer;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::{Arc, Mutex};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let control_ids = Arc::new(Mutex::new(Vec::new()));
+        let (calls_clone, ids_clone) = (calls.clone(), control_ids.clone());
+
+        let server = MockHttpServer::new()
+            .with_handler(move |msg| {
+                ids_clone.lock().unwrap().push(msg.get_control_id().map(String::from));
+                if calls_clone.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err(Error::Http("temporarily unavailable".into()))
+                } else {
+                    Ok(msg)
+                }
+            })
+            .start()
+            .await
+            .unwrap();
+
+        let message = parse_message("MSH|^~\\&|SEND|FAC|RECV|FAC|20240101||ADT^A01|MSG001|P|2.5").unwrap();
+        let policy = RetryPolicy::new(3, Duration::from_millis(1)).with_jitter(false);
+        let client = HttpClient::new(server.url()).unwrap().with_retry(policy);
+
+        assert!(client.send_message(&message).await.is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert!(control_ids.lock().unwrap().iter().all(|id| id.as_deref() == Some("MSG001")));
+
+        server.shutdown().await.unwrap();
+    }
+
     #[test]
     fn test_with_auth() {
         let client = HttpClient::new("http://example.com")
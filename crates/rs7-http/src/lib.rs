@@ -115,7 +115,7 @@ pub mod websocket;
 
 pub use client::HttpClient;
 pub use error::{Error, Result};
-pub use server::{HttpServer, MessageHandler};
+pub use server::{HttpHl7Response, HttpServer, MessageHandler, StatusCode, TypedMessageHandler};
 
 /// Content type for HL7 v2.x messages in pipe-delimited (ER7) format
 ///
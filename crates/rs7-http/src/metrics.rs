@@ -182,7 +182,7 @@ pub fn record_hl7_message_metrics(message: &rs7_core::Message, direction: &str,
     let status = if success { "success" } else { "error" };
 
     // Encode the message to get size
-    let message_size = message.encode().len();
+    let message_size = message.encode_with_separator("\r").len();
 
     // Record metrics with labels
     counter!(
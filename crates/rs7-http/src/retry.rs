@@ -22,6 +22,8 @@ pub struct RetryPolicy {
     pub backoff_multiplier: f64,
     /// Whether to add jitter to backoff durations
     pub jitter: bool,
+    /// Upper bound on the total time spent across all attempts
+    pub deadline: Option<Duration>,
 }
 
 #[cfg(feature = "retry")]
@@ -51,6 +53,7 @@ impl RetryPolicy {
             max_backoff: Duration::from_secs(60),
             backoff_multiplier: 2.0,
             jitter: true,
+            deadline: None,
         }
     }
 
@@ -89,6 +92,7 @@ impl RetryPolicy {
             max_backoff: Duration::from_secs(60),
             backoff_multiplier: 1.0,
             jitter: false,
+            deadline: None,
         }
     }
 
@@ -111,6 +115,7 @@ impl RetryPolicy {
             max_backoff: Duration::from_secs(1),
             backoff_multiplier: 1.0,
             jitter: false,
+            deadline: None,
         }
     }
 
@@ -132,6 +137,22 @@ impl RetryPolicy {
         self
     }
 
+    /// Cap the total time spent retrying
+    ///
+    /// An attempt still running at the deadline is cancelled and fails with a
+    /// timed-out I/O error. No retry is started if its backoff would end past
+    /// the deadline; the last error is returned instead.
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Check if waiting `backoff` more would exceed the deadline
+    fn past_deadline(&self, started: std::time::Instant, backoff: Duration) -> bool {
+        self.deadline
+            .is_some_and(|deadline| started.elapsed() + backoff >= deadline)
+    }
+
     /// Calculate the backoff duration for a given attempt
     ///
     /// # Arguments
@@ -171,14 +192,19 @@ impl RetryPolicy {
 
     /// Check if an error is retryable
     ///
-    /// By default, only network/timeout errors are retryable.
-    /// HTTP 5xx errors are also retryable.
+    /// Transport errors (connection failures, resets and timeouts) and HTTP
+    /// 5xx responses are retryable. 4xx responses, including 422 for AE/AR
+    /// acknowledgments, are not.
     pub fn is_retryable(&self, error: &Error) -> bool {
         match error {
             Error::Io(_) => true,
+            Error::Network(e) => e.is_connect() || e.is_timeout() || e.is_request(),
             Error::Http(msg) => {
-                // Retry on 5xx server errors
-                msg.contains("500") || msg.contains("502") || msg.contains("503") || msg.contains("504")
+                // Retry on 5xx server errors ("HTTP 503 Service Unavailable")
+                msg.strip_prefix("HTTP ")
+                    .and_then(|rest| rest.split_whitespace().next())
+                    .and_then(|code| code.parse::<u16>().ok())
+                    .is_some_and(|code| (500..600).contains(&code))
             }
             _ => false,
         }
@@ -220,6 +246,24 @@ impl RetryExecutor {
         Self { policy }
     }
 
+    /// Run one attempt, cancelling it if it outlives the policy's deadline
+    async fn attempt<T>(
+        &self,
+        started: std::time::Instant,
+        attempt: impl std::future::Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let Some(deadline) = self.policy.deadline else {
+            return attempt.await;
+        };
+        let remaining = deadline.saturating_sub(started.elapsed());
+        tokio::time::timeout(remaining, attempt).await.map_err(|_| {
+            Error::Io(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "retry deadline exceeded",
+            ))
+        })?
+    }
+
     /// Execute an async operation with retries
     ///
     /// # Arguments
@@ -250,9 +294,10 @@ impl RetryExecutor {
         Fut: std::future::Future<Output = Result<T>>,
     {
         let mut attempt = 0;
+        let started = std::time::Instant::now();
 
         loop {
-            match operation().await {
+            match self.attempt(started, operation()).await {
                 Ok(result) => return Ok(result),
                 Err(error) => {
                     if attempt >= self.policy.max_attempts || !self.policy.is_retryable(&error) {
@@ -260,6 +305,9 @@ impl RetryExecutor {
                     }
 
                     let backoff = self.policy.backoff_duration(attempt);
+                    if self.policy.past_deadline(started, backoff) {
+                        return Err(error);
+                    }
 
                     #[cfg(feature = "logging")]
                     {
@@ -314,9 +362,10 @@ impl RetryExecutor {
         P: Fn(&Error) -> bool,
     {
         let mut attempt = 0;
+        let started = std::time::Instant::now();
 
         loop {
-            match operation().await {
+            match self.attempt(started, operation()).await {
                 Ok(result) => return Ok(result),
                 Err(error) => {
                     if attempt >= self.policy.max_attempts || !should_retry(&error) {
@@ -324,6 +373,9 @@ impl RetryExecutor {
                     }
 
                     let backoff = self.policy.backoff_duration(attempt);
+                    if self.policy.past_deadline(started, backoff) {
+                        return Err(error);
+                    }
 
                     #[cfg(feature = "logging")]
                     {
@@ -381,6 +433,50 @@ mod tests {
         assert_eq!(backoff1, Duration::from_secs(1));
     }
 
+    #[test]
+    fn test_is_retryable() {
+        let policy = RetryPolicy::exponential();
+
+        assert!(policy.is_retryable(&Error::Http("HTTP 503 Service Unavailable".into())));
+        assert!(policy.is_retryable(&Error::Http("HTTP 500 Internal Server Error".into())));
+        assert!(!policy.is_retryable(&Error::Http("HTTP 422 Unprocessable Entity".into())));
+        assert!(!policy.is_retryable(&Error::Http("HTTP 404 Not Found (500 retries)".into())));
+        assert!(!policy.is_retryable(&Error::InvalidUrl("bad".into())));
+    }
+
+    #[tokio::test]
+    async fn test_deadline_stops_retries() {
+        let policy = RetryPolicy::fixed().with_deadline(Duration::from_millis(500));
+        let executor = RetryExecutor::new(policy);
+        let mut calls = 0;
+
+        let result: Result<()> = executor
+            .execute(|| {
+                calls += 1;
+                async { Err(Error::Http("HTTP 503 Service Unavailable".into())) }
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[tokio::test]
+    async fn test_deadline_cancels_attempt() {
+        let policy = RetryPolicy::fixed().with_deadline(Duration::from_millis(50));
+        assert_eq!(policy.deadline, Some(Duration::from_millis(50)));
+        let executor = RetryExecutor::new(policy);
+
+        let started = std::time::Instant::now();
+        let result: Result<()> = executor.execute(std::future::pending).await;
+
+        match result {
+            Err(Error::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::TimedOut),
+            other => panic!("expected a timeout, got {:?}", other),
+        }
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
     #[test]
     fn test_max_backoff() {
         let policy = RetryPolicy::exponential()
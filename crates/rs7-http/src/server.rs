@@ -3,7 +3,7 @@
 use crate::{Error, Result, CONTENT_TYPE_HL7_ER7};
 use axum::{
     extract::State,
-    http::{HeaderMap, StatusCode},
+    http::HeaderMap,
     routing::post,
     Router,
 };
@@ -12,6 +12,8 @@ use rs7_parser::parse_message;
 use std::sync::Arc;
 use tower_http::trace::TraceLayer;
 
+pub use axum::http::StatusCode;
+
 #[cfg(feature = "compression")]
 use tower_http::compression::CompressionLayer;
 
@@ -28,6 +30,59 @@ use tower::Service;
 /// The handler is wrapped in an Arc for thread-safe sharing.
 pub type MessageHandler = Arc<dyn Fn(Message) -> Result<Message> + Send + Sync>;
 
+/// Typed message handler function type
+///
+/// Returns the ACK together with the HTTP status to send. Errors are
+/// reported to the sender as 500 Internal Server Error.
+pub type TypedMessageHandler = Arc<dyn Fn(Message) -> Result<HttpHl7Response> + Send + Sync>;
+
+/// Response produced by a [`TypedMessageHandler`]
+///
+/// Unless `http_status` is set, the status follows the HL7-over-HTTP
+/// specification based on the acknowledgment code in MSA-1:
+/// AA/CA map to 200 OK and AE/AR/CE/CR to 422 Unprocessable Entity.
+#[derive(Debug, Clone)]
+pub struct HttpHl7Response {
+    /// Acknowledgment message returned to the sender
+    pub ack: Message,
+    /// Explicit HTTP status, overriding the one derived from MSA-1
+    pub http_status: Option<StatusCode>,
+}
+
+impl HttpHl7Response {
+    /// Create a response whose status is derived from the ACK
+    pub fn new(ack: Message) -> Self {
+        Self {
+            ack,
+            http_status: None,
+        }
+    }
+
+    /// Set an explicit HTTP status
+    pub fn with_status(mut self, status: StatusCode) -> Self {
+        self.http_status = Some(status);
+        self
+    }
+
+    /// HTTP status to send for this response
+    pub fn status(&self) -> StatusCode {
+        if let Some(status) = self.http_status {
+            return status;
+        }
+
+        let ack_code = self
+            .ack
+            .get_segments_by_id("MSA")
+            .first()
+            .and_then(|msa| msa.get_field_value(1));
+
+        match ack_code {
+            Some("AE" | "AR" | "CE" | "CR") => StatusCode::UNPROCESSABLE_ENTITY,
+            _ => StatusCode::OK,
+        }
+    }
+}
+
 /// HTTP server for receiving HL7 v2.x messages
 ///
 /// # Example
@@ -54,7 +109,7 @@ pub type MessageHandler = Arc<dyn Fn(Message) -> Result<Message> + Send + Sync>;
 /// ```
 #[derive(Clone)]
 pub struct HttpServer {
-    handler: MessageHandler,
+    handler: TypedMessageHandler,
     auth: Option<(String, String)>,
     #[cfg(feature = "tls")]
     tls_config: Option<TlsServerConfig>,
@@ -68,7 +123,7 @@ impl HttpServer {
     /// The default handler simply echoes the received message back.
     pub fn new() -> Self {
         Self {
-            handler: Arc::new(Ok),
+            handler: Arc::new(|message| Ok(HttpHl7Response::new(message))),
             auth: None,
             #[cfg(feature = "tls")]
             tls_config: None,
@@ -79,9 +134,37 @@ impl HttpServer {
 
     /// Set the message handler function
     ///
+    /// Responses are always sent with 200 OK; use
+    /// [`HttpServer::with_typed_handler`] to signal rejections with 422.
+    ///
     /// # Arguments
     /// * `handler` - Function that processes incoming messages and returns responses
     pub fn with_handler(mut self, handler: MessageHandler) -> Self {
+        self.handler = Arc::new(move |message| {
+            handler(message).map(|ack| HttpHl7Response::new(ack).with_status(StatusCode::OK))
+        });
+        self
+    }
+
+    /// Set a typed message handler that controls the HTTP status
+    ///
+    /// # Arguments
+    /// * `handler` - Function that processes incoming messages and returns an ACK with its status
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rs7_http::{HttpHl7Response, HttpServer};
+    /// use rs7_core::builders::ack::{build_ack, AckCode};
+    /// use rs7_core::Message;
+    /// use std::sync::Arc;
+    ///
+    /// let server = HttpServer::new().with_typed_handler(Arc::new(|message: Message| {
+    ///     // AE and AR acknowledgments are sent with 422 Unprocessable Entity
+    ///     Ok(HttpHl7Response::new(build_ack(&message, AckCode::Error)?))
+    /// }));
+    /// ```
+    pub fn with_typed_handler(mut self, handler: TypedMessageHandler) -> Self {
         self.handler = handler;
         self
     }
@@ -378,7 +461,7 @@ pub(crate) async fn handle_message(
     );
 
     // Return response
-    Ok((StatusCode::OK, response_headers, response.encode()))
+    Ok((response.status(), response_headers, response.ack.encode_with_separator("\r")))
 }
 
 #[cfg(test)]
@@ -402,6 +485,67 @@ mod tests {
         assert_eq!(password, "pass");
     }
 
+    fn ack_with_code(code: &str) -> Message {
+        let hl7 = format!(
+            "MSH|^~\\&|RECV|FAC|SEND|FAC|20240101||ACK|1|P|2.5\rMSA|{}|MSG001",
+            code
+        );
+        parse_message(&hl7).unwrap()
+    }
+
+    fn hl7_headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", CONTENT_TYPE_HL7_ER7.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_response_status_from_ack_code() {
+        assert_eq!(HttpHl7Response::new(ack_with_code("AA")).status(), StatusCode::OK);
+        assert_eq!(HttpHl7Response::new(ack_with_code("CA")).status(), StatusCode::OK);
+        assert_eq!(
+            HttpHl7Response::new(ack_with_code("AE")).status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        assert_eq!(
+            HttpHl7Response::new(ack_with_code("AR")).status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        assert_eq!(
+            HttpHl7Response::new(ack_with_code("AR"))
+                .with_status(StatusCode::OK)
+                .status(),
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn test_typed_handler_status_codes() {
+        let body = "MSH|^~\\&|SEND|FAC|RECV|FAC|20240101||ADT^A01|MSG001|P|2.5".to_string();
+
+        let server = HttpServer::new()
+            .with_typed_handler(Arc::new(|_| Ok(HttpHl7Response::new(ack_with_code("AR")))));
+        let (status, headers, response) = handle_message(State(server), hl7_headers(), body.clone())
+            .await
+            .unwrap();
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(headers.get("content-type").unwrap(), CONTENT_TYPE_HL7_ER7);
+        assert!(response.contains("MSA|AR|MSG001"));
+
+        let server = HttpServer::new()
+            .with_typed_handler(Arc::new(|_| Err(Error::Http("backend down".into()))));
+        let (status, _) = handle_message(State(server), hl7_headers(), body.clone())
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+
+        let server = HttpServer::new().with_handler(Arc::new(|_| Ok(ack_with_code("AE"))));
+        let (status, _, _) = handle_message(State(server), hl7_headers(), body)
+            .await
+            .unwrap();
+        assert_eq!(status, StatusCode::OK);
+    }
+
     #[test]
     fn test_with_handler() {
         let custom_handler: MessageHandler = Arc::new(|msg| {
@@ -90,6 +90,17 @@ impl MockHttpServer {
         self
     }
 
+    /// Set a handler that controls the HTTP status of each response
+    ///
+    /// See [`HttpServer::with_typed_handler`].
+    pub fn with_typed_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(Message) -> Result<crate::HttpHl7Response> + Send + Sync + 'static,
+    {
+        self.http_server = self.http_server.clone().with_typed_handler(std::sync::Arc::new(handler));
+        self
+    }
+
     /// Enable HTTP Basic Authentication
     ///
     /// # Example
@@ -322,6 +333,36 @@ mod tests {
         server.shutdown().await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_client_receives_nack() {
+        let server = MockHttpServer::new()
+            .with_typed_handler(|_| {
+                let ack = rs7_parser::parse_message(
+                    "MSH|^~\\&|RECV|FAC|SEND|FAC|20240101||ACK|2|P|2.5\r\
+                     MSA|AE|TEST001\r\
+                     ERR|||207^Application internal error^HL70357|E",
+                )?;
+                Ok(crate::HttpHl7Response::new(ack))
+            })
+            .start()
+            .await
+            .unwrap();
+
+        let client = HttpClient::new(server.url()).unwrap();
+        let mut msg = Message::default();
+        let mut msh = Segment::new("MSH");
+        msh.fields.push(Field::from_value("|"));
+        msh.fields.push(Field::from_value("^~\\&"));
+        msg.segments.push(msh);
+
+        // The 422 response still yields the ACK and its ERR segment
+        let ack = client.send_message(&msg).await.unwrap();
+        assert_eq!(ack.segment("MSA").unwrap().get_field_value(1), Some("AE"));
+        assert_eq!(ack.segment("ERR").unwrap().get_field_value(4), Some("E"));
+
+        server.shutdown().await.unwrap();
+    }
+
     #[tokio::test]
     #[cfg(feature = "auth")]
     async fn test_mock_server_with_auth() {
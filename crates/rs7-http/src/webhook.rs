@@ -89,7 +89,7 @@ impl WebhookPayload {
             trigger_event,
             message_control_id,
             message_content: if include_content {
-                Some(message.encode())
+                Some(message.encode_with_separator("\r"))
             } else {
                 None
             },
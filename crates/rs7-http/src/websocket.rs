@@ -163,7 +163,7 @@ async fn handle_websocket(socket: WebSocket, config: WebSocketConfig) {
                         // Handle message
                         match (config.handler)(hl7_msg) {
                             Ok(response) => {
-                                let response_text = response.encode();
+                                let response_text = response.encode_with_separator("\r");
                                 if let Err(_e) = sender.send(WsMessage::Text(response_text.into())).await {
                                     #[cfg(feature = "logging")]
                                     tracing::error!("Failed to send response: {}", _e);
@@ -198,7 +198,7 @@ async fn handle_websocket(socket: WebSocket, config: WebSocketConfig) {
                         if let Ok(hl7_msg) = parse_message(&text) {
                             match (config.handler)(hl7_msg) {
                                 Ok(response) => {
-                                    let response_bytes = response.encode().into_bytes();
+                                    let response_bytes = response.encode_with_separator("\r").into_bytes();
                                     let _ = sender
                                         .send(WsMessage::Binary(response_bytes.into()))
                                         .await;
@@ -294,7 +294,7 @@ impl BroadcastChannel {
     /// # }
     /// ```
     pub fn broadcast(&self, message: &Message) {
-        let encoded = message.encode();
+        let encoded = message.encode_with_separator("\r");
         let _ = self.sender.send(encoded);
     }
 
@@ -435,7 +435,7 @@ impl WebSocketClient {
         let (mut write, mut read) = ws_stream.split();
 
         // Send message
-        let encoded = message.encode();
+        let encoded = message.encode_with_separator("\r");
         write
             .send(TungsteniteMessage::Text(encoded.into()))
             .await
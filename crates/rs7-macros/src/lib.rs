@@ -63,10 +63,9 @@ use syn::{
 struct FieldAttr {
     field_num: Option<usize>,
     component: Option<usize>,
-    #[allow(dead_code)]
     subcomponent: Option<usize>,
-    #[allow(dead_code)]
     repetition: Option<usize>,
+    table: Option<String>,
     optional: bool,
 }
 
@@ -77,6 +76,7 @@ impl Default for FieldAttr {
             component: None,
             subcomponent: None,
             repetition: None,
+            table: None,
             optional: false,
         }
     }
@@ -143,6 +143,12 @@ fn parse_field_attrs(attrs: &[Attribute]) -> FieldAttr {
                 if let Lit::Int(lit_int) = lit {
                     result.repetition = lit_int.base10_parse().ok();
                 }
+            } else if meta.path.is_ident("table") {
+                let _: syn::Token![=] = meta.input.parse()?;
+                let lit: Lit = meta.input.parse()?;
+                if let Lit::Str(lit_str) = lit {
+                    result.table = Some(lit_str.value());
+                }
             } else if meta.path.is_ident("optional") {
                 result.optional = true;
             }
@@ -217,6 +223,63 @@ fn is_option_type(ty: &Type) -> bool {
     false
 }
 
+/// Get `T` from a `Wrapper<T>` type such as `Option<T>` or `Vec<T>`
+fn wrapped_type<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != wrapper {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    })
+}
+
+/// Check if a type is `String`
+fn is_string_type(ty: &Type) -> bool {
+    matches!(ty, Type::Path(type_path) if type_path.path.segments.last().is_some_and(|s| s.ident == "String"))
+}
+
+/// Generate code writing `value` to a component (or one of its subcomponents)
+/// of `segment`
+///
+/// `rep_index` and `comp_index` are 0-based, `subcomponent` is 1-based.
+fn write_component(
+    field_num: usize,
+    rep_index: proc_macro2::TokenStream,
+    comp_index: usize,
+    subcomponent: Option<usize>,
+    value: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let Some(sub) = subcomponent else {
+        return quote! {
+            let _ = segment.set_component(#field_num, #rep_index, #comp_index, #value);
+        };
+    };
+    let sub_index = sub - 1;
+
+    quote! {
+        if segment.get_field(#field_num).and_then(|f| f.get_component(#rep_index, #comp_index)).is_none() {
+            let _ = segment.set_component(#field_num, #rep_index, #comp_index, "");
+        }
+        if let Some(component) = segment.get_field_mut(#field_num)
+            .and_then(|f| f.get_repetition_mut(#rep_index))
+            .and_then(|r| r.get_component_mut(#comp_index))
+        {
+            while component.subcomponents.len() <= #sub_index {
+                component.add_subcomponent(rs7_core::field::SubComponent::new(""));
+            }
+            component.subcomponents[#sub_index] = rs7_core::field::SubComponent::new(#value);
+        }
+    }
+}
+
 /// Derive macro for HL7 segments
 ///
 /// This macro generates implementations for converting between a Rust struct
@@ -227,8 +290,29 @@ fn is_option_type(ty: &Type) -> bool {
 /// - `#[hl7(id = "XXX")]` - Required. Specifies the 3-character segment ID.
 /// - `#[hl7(field = N)]` - Specifies which field number (1-based) this struct field maps to.
 /// - `#[hl7(component = N)]` - Specifies which component within the field (1-based).
+/// - `#[hl7(component = N, subcomponent = M)]` - Specifies subcomponent M (1-based)
+///   of component N.
+/// - `#[hl7(repetition = N)]` - Specifies which repetition of the field (1-based).
+/// - `#[hl7(table = "NNNN")]` - Maps a coded field onto a type implementing
+///   `rs7_core::types::CodedValue` (see [`macro@CodedValue`]) for HL7 table
+///   NNNN. `from_segment` maps unknown or missing codes to `None` for
+///   `Option<T>` fields and `T::default()` otherwise; `try_from_segment` (and
+///   `TryFrom<&Segment>`) rejects codes that are present but not in the table.
 /// - `#[hl7(optional)]` - Marks the field as optional (also inferred from Option<T>).
 ///
+/// A `Vec<String>` field maps to every repetition of a repeating field, e.g.
+/// `#[hl7(field = 3)] ids: Vec<String>`; with `component = N` each element is
+/// that component of a repetition.
+///
+/// A `Vec<T>` of any other type maps each repetition to a composite `T`
+/// defined with [`macro@hl7_type`], through `T::from_repetition` and
+/// `T::to_repetition`, e.g. `#[hl7(field = 11)] addresses: Vec<Address>`.
+///
+/// Each mapped field also gets a Terser path constant named after it, e.g.
+/// `PATH_FAMILY_NAME = "PID-5-1"` for the example below, or `PID-3-4-1`
+/// for a subcomponent. Repetitions are
+/// written 0-based as in Terser paths, so `repetition = 2` gives `PID-13(1)`.
+///
 /// # Example
 ///
 /// ```ignore
@@ -274,68 +358,228 @@ pub fn derive_segment(input: TokenStream) -> TokenStream {
     // Generate field extraction code for from_segment
     let mut from_segment_fields = Vec::new();
     let mut to_segment_fields = Vec::new();
+    let mut path_consts = Vec::new();
+    let mut code_checks = Vec::new();
 
     for field in fields {
         let field_name = field.ident.as_ref().unwrap();
         let field_type = &field.ty;
         let attrs = parse_field_attrs(&field.attrs);
 
-        if let Some(field_num) = attrs.field_num {
-            let is_optional = is_option_type(field_type) || attrs.optional;
+        let Some(field_num) = attrs.field_num else {
+            continue;
+        };
+        if attrs.subcomponent.is_some() && attrs.component.is_none() {
+            panic!("Segment field `{}`: `subcomponent` requires `component`", field_name);
+        }
+        if attrs.component == Some(0) || attrs.subcomponent == Some(0) {
+            panic!("Segment field `{}`: component and subcomponent numbers are 1-based", field_name);
+        }
 
-            // Generate extraction code based on component
-            let extraction = if let Some(comp) = attrs.component {
-                // Extract specific component
-                quote! {
-                    segment.get_field(#field_num)
-                        .and_then(|f| f.get_repetition(0))
-                        .and_then(|r| r.get_component(#comp - 1))
-                        .and_then(|c| c.value())
-                        .map(|s| s.to_string())
+        // Terser path constant, e.g. PATH_FAMILY_NAME = "PID-5-1"
+        let mut path = format!("{}-{}", segment_id, field_num);
+        if let Some(repetition) = attrs.repetition {
+            // Terser repetition indices are 0-based
+            path.push_str(&format!("({})", repetition.saturating_sub(1)));
+        }
+        if let Some(comp) = attrs.component {
+            path.push_str(&format!("-{}", comp));
+        }
+        if let Some(sub) = attrs.subcomponent {
+            path.push_str(&format!("-{}", sub));
+        }
+        let field_label = field_name.to_string().trim_start_matches("r#").to_string();
+        let const_name = syn::Ident::new(&format!("PATH_{}", field_label.to_uppercase()), field_name.span());
+        let const_doc = format!("Terser path of `{}`", field_label);
+        path_consts.push(quote! {
+            #[doc = #const_doc]
+            pub const #const_name: &'static str = #path;
+        });
+
+        // Value of one repetition, as Option<&str>
+        let repetition_value = match (attrs.component, attrs.subcomponent) {
+            (Some(comp), Some(sub)) => quote! {
+                r.get_component(#comp - 1)
+                    .and_then(|c| c.get_subcomponent(#sub - 1))
+                    .map(|s| s.as_str())
+            },
+            (Some(comp), None) => quote! { r.get_component(#comp - 1).and_then(|c| c.value()) },
+            _ => quote! { r.value() },
+        };
+        let comp_index = attrs.component.map(|c| c - 1).unwrap_or(0);
+        let subcomponent = attrs.subcomponent;
+
+        if let Some(inner) = wrapped_type(field_type, "Vec").filter(|inner| !is_string_type(inner)) {
+            // Repeating composite field: one `hl7_type` value per repetition
+            from_segment_fields.push(quote! {
+                #field_name: segment.get_field(#field_num)
+                    .filter(|f| !f.is_empty())
+                    .map(|f| f.repetitions.iter().map(#inner::from_repetition).collect())
+                    .unwrap_or_default()
+            });
+
+            to_segment_fields.push(quote! {
+                if !self.#field_name.is_empty() {
+                    let mut field = rs7_core::field::Field::new();
+                    for val in &self.#field_name {
+                        field.add_repetition(val.to_repetition());
+                    }
+                    let _ = segment.set_field(#field_num, field);
                 }
-            } else {
-                // Extract entire field value
-                quote! {
-                    segment.get_field(#field_num)
-                        .and_then(|f| f.value())
-                        .map(|s| s.to_string())
+            });
+            continue;
+        }
+
+        if wrapped_type(field_type, "Vec").is_some() {
+            // Repeating field: one element per repetition
+            from_segment_fields.push(quote! {
+                #field_name: segment.get_field(#field_num)
+                    .filter(|f| !f.is_empty())
+                    .map(|f| {
+                        f.repetitions
+                            .iter()
+                            .map(|r| #repetition_value.unwrap_or("").to_string())
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            });
+
+            let write = write_component(field_num, quote! { rep_index }, comp_index, subcomponent, quote! { val });
+            to_segment_fields.push(quote! {
+                for (rep_index, val) in self.#field_name.iter().enumerate() {
+                    #write
+                }
+            });
+            continue;
+        }
+
+        if let Some(table) = &attrs.table {
+            // Coded field mapped onto a CodedValue type
+            let rep_index = attrs.repetition.unwrap_or(1).saturating_sub(1);
+            let is_optional = is_option_type(field_type) || attrs.optional;
+            let coded_type = wrapped_type(field_type, "Option").unwrap_or(field_type);
+
+            code_checks.push(quote! {
+                if let Some(code) = segment.get_field(#field_num)
+                    .and_then(|f| f.get_repetition(#rep_index))
+                    .and_then(|r| #repetition_value)
+                    .filter(|code| {
+                        !code.is_empty()
+                            && <#coded_type as rs7_core::types::CodedValue>::from_code(code).is_none()
+                    })
+                {
+                    return Err(rs7_core::error::Error::Validation(format!(
+                        "Unknown code '{}' in {} (table {})",
+                        code, #path, #table
+                    )));
                 }
+            });
+
+            let extraction = quote! {
+                segment.get_field(#field_num)
+                    .and_then(|f| f.get_repetition(#rep_index))
+                    .and_then(|r| #repetition_value)
+                    .and_then(rs7_core::types::CodedValue::from_code)
             };
 
+            let write = write_component(field_num, quote! { #rep_index }, comp_index, subcomponent, quote! { code });
             if is_optional {
-                from_segment_fields.push(quote! {
-                    #field_name: #extraction
+                from_segment_fields.push(quote! { #field_name: #extraction });
+                to_segment_fields.push(quote! {
+                    if let Some(ref val) = self.#field_name {
+                        let code = rs7_core::types::CodedValue::to_code(val);
+                        #write
+                    }
                 });
             } else {
-                from_segment_fields.push(quote! {
-                    #field_name: #extraction.unwrap_or_default()
+                from_segment_fields.push(quote! { #field_name: #extraction.unwrap_or_default() });
+                to_segment_fields.push(quote! {
+                    let code = rs7_core::types::CodedValue::to_code(&self.#field_name);
+                    #write
                 });
             }
+            continue;
+        }
+
+        if let Some(repetition) = attrs.repetition {
+            // Specific repetition (1-based)
+            let rep_index = repetition.saturating_sub(1);
+            let is_optional = is_option_type(field_type) || attrs.optional;
+            let extraction = quote! {
+                segment.get_field(#field_num)
+                    .and_then(|f| f.get_repetition(#rep_index))
+                    .and_then(|r| #repetition_value)
+                    .map(|s| s.to_string())
+            };
 
-            // Generate serialization code
             if is_optional {
-                if let Some(comp) = attrs.component {
-                    to_segment_fields.push(quote! {
-                        if let Some(ref val) = self.#field_name {
-                            let _ = segment.set_component(#field_num, 0, #comp - 1, val);
-                        }
-                    });
-                } else {
-                    to_segment_fields.push(quote! {
-                        if let Some(ref val) = self.#field_name {
-                            let _ = segment.set_field_value(#field_num, val);
-                        }
-                    });
-                }
-            } else if let Some(comp) = attrs.component {
+                let write = write_component(field_num, quote! { #rep_index }, comp_index, subcomponent, quote! { val });
+                from_segment_fields.push(quote! { #field_name: #extraction });
+                to_segment_fields.push(quote! {
+                    if let Some(ref val) = self.#field_name {
+                        #write
+                    }
+                });
+            } else {
+                let write = write_component(field_num, quote! { #rep_index }, comp_index, subcomponent, quote! { &self.#field_name });
+                from_segment_fields.push(quote! { #field_name: #extraction.unwrap_or_default() });
+                to_segment_fields.push(quote! { #write });
+            }
+            continue;
+        }
+
+        let is_optional = is_option_type(field_type) || attrs.optional;
+
+        // Generate extraction code based on component
+        let extraction = if attrs.component.is_some() {
+            // Extract specific component or subcomponent
+            quote! {
+                segment.get_field(#field_num)
+                    .and_then(|f| f.get_repetition(0))
+                    .and_then(|r| #repetition_value)
+                    .map(|s| s.to_string())
+            }
+        } else {
+            // Extract entire field value
+            quote! {
+                segment.get_field(#field_num)
+                    .and_then(|f| f.value())
+                    .map(|s| s.to_string())
+            }
+        };
+
+        if is_optional {
+            from_segment_fields.push(quote! {
+                #field_name: #extraction
+            });
+        } else {
+            from_segment_fields.push(quote! {
+                #field_name: #extraction.unwrap_or_default()
+            });
+        }
+
+        // Generate serialization code
+        if is_optional {
+            if attrs.component.is_some() {
+                let write = write_component(field_num, quote! { 0 }, comp_index, subcomponent, quote! { val });
                 to_segment_fields.push(quote! {
-                    let _ = segment.set_component(#field_num, 0, #comp - 1, &self.#field_name);
+                    if let Some(ref val) = self.#field_name {
+                        #write
+                    }
                 });
             } else {
                 to_segment_fields.push(quote! {
-                    let _ = segment.set_field_value(#field_num, &self.#field_name);
+                    if let Some(ref val) = self.#field_name {
+                        let _ = segment.set_field_value(#field_num, val);
+                    }
                 });
             }
+        } else if attrs.component.is_some() {
+            to_segment_fields.push(write_component(field_num, quote! { 0 }, comp_index, subcomponent, quote! { &self.#field_name }));
+        } else {
+            to_segment_fields.push(quote! {
+                let _ = segment.set_field_value(#field_num, &self.#field_name);
+            });
         }
     }
 
@@ -344,6 +588,8 @@ pub fn derive_segment(input: TokenStream) -> TokenStream {
             /// The HL7 segment ID for this type
             pub const SEGMENT_ID: &'static str = #segment_id;
 
+            #(#path_consts)*
+
             /// Create from an rs7_core Segment
             pub fn from_segment(segment: &rs7_core::segment::Segment) -> Option<Self> {
                 if segment.id != #segment_id {
@@ -355,6 +601,21 @@ pub fn derive_segment(input: TokenStream) -> TokenStream {
                 })
             }
 
+            /// Create from an rs7_core Segment, rejecting unknown table codes
+            ///
+            /// Fails if the segment ID does not match, or if a field mapped
+            /// with `table = "..."` holds a code its `CodedValue` type does not
+            /// know. Empty coded fields are accepted.
+            pub fn try_from_segment(segment: &rs7_core::segment::Segment) -> rs7_core::error::Result<Self> {
+                let value = Self::from_segment(segment).ok_or_else(|| rs7_core::error::Error::InvalidSegment(
+                    format!("Expected {} segment, got {}", #segment_id, segment.id)
+                ))?;
+
+                #(#code_checks)*
+
+                Ok(value)
+            }
+
             /// Convert to an rs7_core Segment
             pub fn to_segment(&self) -> rs7_core::segment::Segment {
                 let mut segment = rs7_core::segment::Segment::new(#segment_id);
@@ -378,10 +639,7 @@ pub fn derive_segment(input: TokenStream) -> TokenStream {
             type Error = rs7_core::error::Error;
 
             fn try_from(segment: &rs7_core::segment::Segment) -> Result<Self, Self::Error> {
-                Self::from_segment(segment)
-                    .ok_or_else(|| rs7_core::error::Error::InvalidSegment(
-                        format!("Expected {} segment, got {}", #segment_id, segment.id)
-                    ))
+                Self::try_from_segment(segment)
             }
         }
     };
@@ -402,6 +660,12 @@ pub fn derive_segment(input: TokenStream) -> TokenStream {
 /// - `#[hl7(segment, optional)]` - Marks a field as an optional segment.
 /// - `#[hl7(segment, repeating)]` - Marks a field as a repeating segment (Vec<T>).
 ///
+/// Segment field types must be `#[derive(Segment)]` types (optionally wrapped
+/// in `Option` or `Vec`). The generated `from_message` looks up segments by
+/// each type's `SEGMENT_ID`: a required field takes the first matching
+/// segment and fails if there is none, an `Option` field is `None` when the
+/// segment is absent, and a `Vec` field collects every matching segment.
+///
 /// # Example
 ///
 /// ```ignore
@@ -444,29 +708,16 @@ pub fn derive_message(input: TokenStream) -> TokenStream {
     for field in fields {
         let field_name = field.ident.as_ref().unwrap();
         let field_type = &field.ty;
-        let is_optional = is_option_type(field_type);
-
-        // Check if this is a Vec (repeating segment)
-        let is_vec = if let Type::Path(type_path) = field_type {
-            type_path
-                .path
-                .segments
-                .first()
-                .map(|s| s.ident == "Vec")
-                .unwrap_or(false)
-        } else {
-            false
-        };
 
-        if is_vec {
+        if let Some(inner) = wrapped_type(field_type, "Vec") {
             // Repeating segment
             from_message_fields.push(quote! {
-                #field_name: {
-                    let items = Vec::new();
-                    // This would need to know the inner type's SEGMENT_ID
-                    // For now, just create empty vec
-                    items
-                }
+                #field_name: message
+                    .segments
+                    .iter()
+                    .filter(|s| s.id == <#inner>::SEGMENT_ID)
+                    .filter_map(<#inner>::from_segment)
+                    .collect()
             });
 
             to_message_fields.push(quote! {
@@ -474,10 +725,14 @@ pub fn derive_message(input: TokenStream) -> TokenStream {
                     message.add_segment(item.to_segment());
                 }
             });
-        } else if is_optional {
+        } else if let Some(inner) = wrapped_type(field_type, "Option") {
             // Optional segment
             from_message_fields.push(quote! {
-                #field_name: None // Would need type info to extract
+                #field_name: message
+                    .segments
+                    .iter()
+                    .find(|s| s.id == <#inner>::SEGMENT_ID)
+                    .and_then(<#inner>::from_segment)
             });
 
             to_message_fields.push(quote! {
@@ -486,9 +741,16 @@ pub fn derive_message(input: TokenStream) -> TokenStream {
                 }
             });
         } else {
-            // Required segment - would need Default trait
+            // Required segment
             from_message_fields.push(quote! {
-                #field_name: Default::default()
+                #field_name: message
+                    .segments
+                    .iter()
+                    .find(|s| s.id == <#field_type>::SEGMENT_ID)
+                    .and_then(<#field_type>::from_segment)
+                    .ok_or_else(|| rs7_core::error::Error::MissingRequiredField(
+                        format!("Required segment {} is missing", <#field_type>::SEGMENT_ID)
+                    ))?
             });
 
             to_message_fields.push(quote! {
@@ -515,6 +777,15 @@ pub fn derive_message(input: TokenStream) -> TokenStream {
                 #trigger_event
             }
 
+            /// Create from an rs7_core Message
+            ///
+            /// Fails if a required segment is missing.
+            pub fn from_message(message: &rs7_core::message::Message) -> rs7_core::error::Result<Self> {
+                Ok(Self {
+                    #(#from_message_fields),*
+                })
+            }
+
             /// Convert to an rs7_core Message
             pub fn to_message(&self) -> rs7_core::message::Message {
                 let mut message = rs7_core::message::Message::new();
@@ -528,6 +799,93 @@ pub fn derive_message(input: TokenStream) -> TokenStream {
                 value.to_message()
             }
         }
+
+        impl std::convert::TryFrom<&rs7_core::message::Message> for #name {
+            type Error = rs7_core::error::Error;
+
+            fn try_from(message: &rs7_core::message::Message) -> Result<Self, Self::Error> {
+                Self::from_message(message)
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Derive macro for HL7 coded values
+///
+/// Implements `rs7_core::types::CodedValue` for an enum of unit variants.
+/// Each variant's code is given with `#[code = "X"]` and defaults to the
+/// variant name.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(CodedValue, Default)]
+/// enum AdministrativeSex {
+///     #[code = "F"]
+///     Female,
+///     #[code = "M"]
+///     Male,
+///     #[default]
+///     #[code = "U"]
+///     Unknown,
+/// }
+/// ```
+#[proc_macro_derive(CodedValue, attributes(code))]
+pub fn derive_coded_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => panic!("CodedValue derive only supports enums"),
+    };
+
+    let mut from_arms = Vec::new();
+    let mut to_arms = Vec::new();
+
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            panic!("CodedValue derive only supports unit variants");
+        }
+        let variant_name = &variant.ident;
+
+        let code = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("code"))
+            .find_map(|attr| match &attr.meta {
+                Meta::NameValue(nv) => match &nv.value {
+                    Expr::Lit(expr_lit) => match &expr_lit.lit {
+                        Lit::Str(lit_str) => Some(lit_str.value()),
+                        _ => None,
+                    },
+                    _ => None,
+                },
+                _ => None,
+            })
+            .unwrap_or_else(|| variant_name.to_string());
+
+        from_arms.push(quote! { #code => Some(Self::#variant_name) });
+        to_arms.push(quote! { Self::#variant_name => #code });
+    }
+
+    let expanded = quote! {
+        impl rs7_core::types::CodedValue for #name {
+            fn from_code(code: &str) -> Option<Self> {
+                match code {
+                    #(#from_arms,)*
+                    _ => None,
+                }
+            }
+
+            fn to_code(&self) -> &str {
+                match self {
+                    #(#to_arms,)*
+                }
+            }
+        }
     };
 
     TokenStream::from(expanded)
@@ -541,6 +899,9 @@ pub fn derive_message(input: TokenStream) -> TokenStream {
 /// # Attributes
 ///
 /// - `#[hl7_type(data_type = "XXX")]` - Specifies the HL7 data type code.
+/// - `#[hl7(component = N)]` - Maps a struct field to component N (1-based).
+/// - `#[hl7(component = N, subcomponent = M)]` - Maps a struct field to
+///   subcomponent M (1-based) of component N.
 ///
 /// # Example
 ///
@@ -602,54 +963,88 @@ pub fn hl7_type(attr: TokenStream, item: TokenStream) -> TokenStream {
         let attrs = parse_field_attrs(&field.attrs);
         let is_optional = is_option_type(field_type);
 
+        if attrs.subcomponent.is_some() && attrs.component.is_none() {
+            panic!("hl7_type field `{}`: `subcomponent` requires `component`", field_name);
+        }
+        if attrs.component == Some(0) || attrs.subcomponent == Some(0) {
+            panic!("hl7_type field `{}`: component and subcomponent numbers are 1-based", field_name);
+        }
+
         if let Some(comp) = attrs.component {
             let comp_idx = comp - 1; // Convert to 0-based
 
+            // Read as Option<String>, and write `val` (a &String)
+            let (read, write) = if let Some(sub) = attrs.subcomponent {
+                let sub_idx = sub - 1;
+                (
+                    quote! {
+                        repetition.get_component(#comp_idx)
+                            .and_then(|c| c.get_subcomponent(#sub_idx))
+                            .map(|s| s.as_str().to_string())
+                    },
+                    quote! {
+                        while repetition.components.len() <= #comp_idx {
+                            repetition.add_component(rs7_core::field::Component::new());
+                        }
+                        let component = &mut repetition.components[#comp_idx];
+                        while component.subcomponents.len() <= #sub_idx {
+                            component.add_subcomponent(rs7_core::field::SubComponent::new(""));
+                        }
+                        component.subcomponents[#sub_idx] = rs7_core::field::SubComponent::new(val);
+                    },
+                )
+            } else {
+                (
+                    quote! {
+                        repetition.get_component(#comp_idx)
+                            .and_then(|c| c.value())
+                            .map(|s| s.to_string())
+                    },
+                    quote! {
+                        while repetition.components.len() <= #comp_idx {
+                            repetition.add_component(rs7_core::field::Component::new());
+                        }
+                        repetition.components[#comp_idx] = rs7_core::field::Component::from_value(val);
+                    },
+                )
+            };
+
             if is_optional {
                 from_rep_fields.push(quote! {
-                    #field_name: repetition.get_component(#comp_idx)
-                        .and_then(|c| c.value())
-                        .map(|s| s.to_string())
+                    #field_name: #read
                 });
 
                 to_rep_fields.push(quote! {
                     if let Some(ref val) = self.#field_name {
-                        while repetition.components.len() <= #comp_idx {
-                            repetition.add_component(rs7_core::field::Component::new());
-                        }
-                        repetition.components[#comp_idx] = rs7_core::field::Component::from_value(val);
+                        #write
                     }
                 });
             } else {
                 from_rep_fields.push(quote! {
-                    #field_name: repetition.get_component(#comp_idx)
-                        .and_then(|c| c.value())
-                        .map(|s| s.to_string())
-                        .unwrap_or_default()
+                    #field_name: #read.unwrap_or_default()
                 });
 
                 to_rep_fields.push(quote! {
-                    while repetition.components.len() <= #comp_idx {
-                        repetition.add_component(rs7_core::field::Component::new());
+                    {
+                        let val = &self.#field_name;
+                        #write
                     }
-                    repetition.components[#comp_idx] = rs7_core::field::Component::from_value(&self.#field_name);
                 });
             }
         }
     }
 
-    // Get original struct definition parts
-    let vis = &input.vis;
-    let struct_fields = match &input.data {
-        Data::Struct(data) => match &data.fields {
-            Fields::Named(fields) => fields,
-            _ => panic!("hl7_type only supports named fields"),
-        },
-        _ => panic!("hl7_type only supports structs"),
-    };
+    // Re-emit the struct without the `#[hl7(...)]` field attributes, which
+    // are only meaningful to this macro
+    let mut item = input.clone();
+    if let Data::Struct(data) = &mut item.data {
+        for field in data.fields.iter_mut() {
+            field.attrs.retain(|attr| !attr.path().is_ident("hl7"));
+        }
+    }
 
     let expanded = quote! {
-        #vis struct #name #struct_fields
+        #item
 
         impl #name {
             /// The HL7 data type code
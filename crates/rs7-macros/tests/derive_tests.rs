@@ -1,7 +1,8 @@
 //! Integration tests for rs7-macros derive macros
 
 use rs7_core::segment::Segment;
-use rs7_macros::{Message, Segment as DeriveSegment};
+use rs7_core::types::CodedValue as _;
+use rs7_macros::{CodedValue, Message, Segment as DeriveSegment};
 
 /// Test segment with simple fields
 #[derive(DeriveSegment, Default, Debug, PartialEq)]
@@ -46,6 +47,64 @@ struct Observation {
     result_status: Option<String>,
 }
 
+/// Test segment with repeating fields
+#[derive(DeriveSegment, Default, Debug, PartialEq)]
+#[hl7(id = "PID")]
+struct PatientIdentifiers {
+    #[hl7(field = 3)]
+    ids: Vec<String>,
+
+    #[hl7(field = 3, component = 4)]
+    authorities: Vec<String>,
+
+    #[hl7(field = 13, repetition = 2)]
+    second_phone: Option<String>,
+}
+
+/// Test segment with subcomponent fields (PID-3 assigning authority, HD)
+#[derive(DeriveSegment, Default, Debug, PartialEq)]
+#[hl7(id = "PID")]
+struct PatientAuthority {
+    #[hl7(field = 3, component = 1)]
+    id: String,
+
+    #[hl7(field = 3, component = 4, subcomponent = 1)]
+    authority_namespace: String,
+
+    #[hl7(field = 3, component = 4, subcomponent = 2)]
+    authority_oid: Option<String>,
+}
+
+/// Test coded value for HL7 table 0001
+#[derive(CodedValue, Default, Debug, PartialEq)]
+enum AdministrativeSex {
+    #[code = "F"]
+    Female,
+    #[code = "M"]
+    Male,
+    #[default]
+    #[code = "U"]
+    Unknown,
+}
+
+/// Test coded value using variant names as codes
+#[derive(CodedValue, Debug, PartialEq)]
+enum PatientClass {
+    E,
+    I,
+}
+
+/// Test segment with coded fields
+#[derive(DeriveSegment, Default, Debug, PartialEq)]
+#[hl7(id = "PID")]
+struct CodedPatient {
+    #[hl7(field = 8, table = "0001")]
+    sex: AdministrativeSex,
+
+    #[hl7(field = 18, component = 2, table = "0004")]
+    class: Option<PatientClass>,
+}
+
 /// Test message with segments
 #[derive(Message, Default, Debug)]
 #[hl7(message_type = "ADT", trigger_event = "A01")]
@@ -57,12 +116,34 @@ struct AdtA01 {
     obx: Option<Observation>,
 }
 
+/// Test message with a repeating segment
+#[derive(Message, Debug)]
+#[hl7(message_type = "ORU", trigger_event = "R01")]
+struct OruR01 {
+    #[hl7(segment)]
+    pid: PatientIdentification,
+
+    #[hl7(segment, repeating)]
+    obx: Vec<Observation>,
+}
+
 #[test]
 fn test_segment_id_const() {
     assert_eq!(PatientIdentification::SEGMENT_ID, "PID");
     assert_eq!(Observation::SEGMENT_ID, "OBX");
 }
 
+#[test]
+fn test_path_consts() {
+    assert_eq!(PatientIdentification::PATH_PATIENT_ID, "PID-3");
+    assert_eq!(PatientIdentification::PATH_FAMILY_NAME, "PID-5-1");
+    assert_eq!(PatientIdentification::PATH_GIVEN_NAME, "PID-5-2");
+    assert_eq!(PatientIdentifiers::PATH_AUTHORITIES, "PID-3-4");
+    assert_eq!(PatientIdentifiers::PATH_SECOND_PHONE, "PID-13(1)");
+    assert_eq!(Observation::PATH_OBSERVATION_VALUE, "OBX-5");
+    assert_eq!(PatientAuthority::PATH_AUTHORITY_OID, "PID-3-4-2");
+}
+
 #[test]
 fn test_segment_id_method() {
     let pid = PatientIdentification::default();
@@ -257,3 +338,293 @@ fn test_optional_segment_serialization() {
     // Should have 2 segments when OBX is present
     assert_eq!(message.segments.len(), 2);
 }
+
+#[test]
+fn test_message_from_message() {
+    let mut message = rs7_core::message::Message::new();
+    let mut pid = Segment::new("PID");
+    let _ = pid.set_field_value(3, "P001");
+    message.add_segment(pid);
+    for (set_id, value) in [("1", "100"), ("2", "140")] {
+        let mut obx = Segment::new("OBX");
+        let _ = obx.set_field_value(1, set_id);
+        let _ = obx.set_field_value(5, value);
+        message.add_segment(obx);
+    }
+
+    let oru = OruR01::from_message(&message).unwrap();
+    assert_eq!(oru.pid.patient_id, "P001");
+    assert_eq!(oru.obx.len(), 2);
+    assert_eq!(oru.obx[1].observation_value, Some("140".to_string()));
+
+    let adt = AdtA01::from_message(&message).unwrap();
+    assert_eq!(adt.obx.unwrap().set_id, "1");
+}
+
+#[test]
+fn test_message_from_message_optional_and_missing() {
+    let mut message = rs7_core::message::Message::new();
+    message.add_segment(Segment::new("PID"));
+
+    let adt = AdtA01::from_message(&message).unwrap();
+    assert!(adt.obx.is_none());
+
+    let oru = OruR01::from_message(&message).unwrap();
+    assert!(oru.obx.is_empty());
+
+    let empty = rs7_core::message::Message::new();
+    assert!(AdtA01::from_message(&empty).is_err());
+}
+
+#[test]
+fn test_message_round_trip() {
+    use std::convert::TryFrom;
+
+    let oru = OruR01 {
+        pid: PatientIdentification {
+            patient_id: "P002".to_string(),
+            ..Default::default()
+        },
+        obx: vec![Observation {
+            set_id: "1".to_string(),
+            ..Default::default()
+        }],
+    };
+
+    let parsed = OruR01::try_from(&oru.to_message()).unwrap();
+    assert_eq!(parsed.pid.patient_id, "P002");
+    assert_eq!(parsed.obx.len(), 1);
+}
+
+#[test]
+fn test_repeating_field_extraction() {
+    let mut segment = Segment::new("PID");
+    let _ = segment.set_component(3, 0, 0, "123");
+    let _ = segment.set_component(3, 0, 3, "HOSP");
+    let _ = segment.set_component(3, 1, 0, "456");
+    let _ = segment.set_component(13, 0, 0, "555-0100");
+    let _ = segment.set_component(13, 1, 0, "555-0199");
+
+    let pid = PatientIdentifiers::from_segment(&segment).unwrap();
+    assert_eq!(pid.ids, vec!["123", "456"]);
+    assert_eq!(pid.authorities, vec!["HOSP", ""]);
+    assert_eq!(pid.second_phone, Some("555-0199".to_string()));
+
+    let empty = PatientIdentifiers::from_segment(&Segment::new("PID")).unwrap();
+    assert!(empty.ids.is_empty());
+    assert_eq!(empty.second_phone, None);
+}
+
+#[test]
+fn test_repeating_field_serialization() {
+    let pid = PatientIdentifiers {
+        ids: vec!["123".to_string(), "456".to_string()],
+        authorities: vec!["HOSP".to_string()],
+        second_phone: Some("555-0199".to_string()),
+    };
+
+    let segment = pid.to_segment();
+    let field3 = segment.get_field(3).unwrap();
+    assert_eq!(field3.repetitions.len(), 2);
+    assert_eq!(field3.get_component(0, 3).and_then(|c| c.value()), Some("HOSP"));
+    assert_eq!(field3.get_repetition(1).and_then(|r| r.value()), Some("456"));
+
+    let field13 = segment.get_field(13).unwrap();
+    assert_eq!(field13.get_repetition(1).and_then(|r| r.value()), Some("555-0199"));
+
+    // Authorities are read back once per PID-3 repetition
+    let parsed = PatientIdentifiers::from_segment(&segment).unwrap();
+    assert_eq!(parsed.ids, pid.ids);
+    assert_eq!(parsed.authorities, vec!["HOSP", ""]);
+}
+
+#[test]
+fn test_coded_value_derive() {
+    assert_eq!(AdministrativeSex::from_code("M"), Some(AdministrativeSex::Male));
+    assert_eq!(AdministrativeSex::from_code("X"), None);
+    assert_eq!(AdministrativeSex::Female.to_code(), "F");
+    assert_eq!(PatientClass::from_code("I"), Some(PatientClass::I));
+    assert_eq!(PatientClass::E.to_code(), "E");
+}
+
+#[test]
+fn test_coded_field_mapping() {
+    let mut segment = Segment::new("PID");
+    let _ = segment.set_field_value(8, "F");
+    let _ = segment.set_component(18, 0, 1, "I");
+
+    let pid = CodedPatient::from_segment(&segment).unwrap();
+    assert_eq!(pid.sex, AdministrativeSex::Female);
+    assert_eq!(pid.class, Some(PatientClass::I));
+
+    let out = pid.to_segment();
+    assert_eq!(out.get_field_value(8), Some("F"));
+    assert_eq!(out.get_field(18).and_then(|f| f.get_component(0, 1)).and_then(|c| c.value()), Some("I"));
+
+    // Unknown codes fall back to the default / None
+    let mut segment = Segment::new("PID");
+    let _ = segment.set_field_value(8, "X");
+    let _ = segment.set_component(18, 0, 1, "Z");
+    let pid = CodedPatient::from_segment(&segment).unwrap();
+    assert_eq!(pid.sex, AdministrativeSex::Unknown);
+    assert_eq!(pid.class, None);
+}
+
+#[test]
+fn test_coded_field_unknown_code_rejected() {
+    use std::convert::TryFrom;
+
+    let mut segment = Segment::new("PID");
+    let _ = segment.set_field_value(8, "M");
+    let _ = segment.set_component(18, 0, 1, "Z");
+
+    let err = CodedPatient::try_from(&segment).unwrap_err();
+    assert!(err.to_string().contains("Unknown code 'Z' in PID-18-2 (table 0004)"), "{}", err);
+
+    let mut segment = Segment::new("PID");
+    let _ = segment.set_field_value(8, "X");
+    let err = CodedPatient::try_from_segment(&segment).unwrap_err();
+    assert!(err.to_string().contains("table 0001"), "{}", err);
+
+    // Known and empty codes are accepted
+    let mut segment = Segment::new("PID");
+    let _ = segment.set_field_value(8, "M");
+    let pid = CodedPatient::try_from_segment(&segment).unwrap();
+    assert_eq!(pid.sex, AdministrativeSex::Male);
+    assert_eq!(pid.class, None);
+
+    assert!(CodedPatient::try_from_segment(&Segment::new("PV1")).is_err());
+}
+
+/// Composite XAD type used in repeating fields
+#[rs7_macros::hl7_type(data_type = "XAD")]
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ExtendedAddress {
+    #[hl7(component = 1)]
+    street: String,
+
+    #[hl7(component = 3)]
+    city: Option<String>,
+
+    #[hl7(component = 4)]
+    state: Option<String>,
+}
+
+/// Test segment with a repeating composite field
+#[derive(DeriveSegment, Default, Debug, PartialEq)]
+#[hl7(id = "PID")]
+struct PatientAddresses {
+    #[hl7(field = 3)]
+    patient_id: String,
+
+    #[hl7(field = 11)]
+    addresses: Vec<ExtendedAddress>,
+}
+
+#[test]
+fn test_repeating_composite_field() {
+    let mut segment = Segment::new("PID");
+    let _ = segment.set_field_value(3, "123");
+    let _ = segment.set_component(11, 0, 0, "1 Main St");
+    let _ = segment.set_component(11, 0, 2, "Boston");
+    let _ = segment.set_component(11, 0, 3, "MA");
+    let _ = segment.set_component(11, 1, 0, "PO Box 7");
+
+    let pid = PatientAddresses::from_segment(&segment).unwrap();
+    assert_eq!(ExtendedAddress::DATA_TYPE, "XAD");
+    assert_eq!(
+        pid.addresses,
+        vec![
+            ExtendedAddress {
+                street: "1 Main St".to_string(),
+                city: Some("Boston".to_string()),
+                state: Some("MA".to_string()),
+            },
+            ExtendedAddress {
+                street: "PO Box 7".to_string(),
+                city: None,
+                state: None,
+            },
+        ]
+    );
+
+    let round_trip = PatientAddresses::from_segment(&pid.to_segment()).unwrap();
+    assert_eq!(round_trip, pid);
+    assert_eq!(pid.to_segment().get_field(11).unwrap().repetitions.len(), 2);
+
+    let empty = PatientAddresses::from_segment(&Segment::new("PID")).unwrap();
+    assert!(empty.addresses.is_empty());
+    assert!(empty.to_segment().get_field(11).is_none());
+}
+
+/// Composite XPN type with a subcomponent-structured family name
+#[rs7_macros::hl7_type(data_type = "XPN")]
+#[derive(Debug, Clone, Default, PartialEq)]
+struct PersonName {
+    #[hl7(component = 1, subcomponent = 1)]
+    surname: String,
+
+    #[hl7(component = 1, subcomponent = 2)]
+    surname_prefix: Option<String>,
+
+    #[hl7(component = 2)]
+    given_name: Option<String>,
+}
+
+#[test]
+fn test_composite_subcomponents() {
+    let mut segment = Segment::new("PID");
+    let _ = segment.set_component(5, 0, 0, "");
+    let _ = segment.set_component(5, 0, 1, "JAN");
+    let field = segment.get_field_mut(5).unwrap();
+    field.repetitions[0].components[0] = rs7_core::field::Component {
+        subcomponents: vec!["BERG".into(), "VAN DEN".into()],
+    };
+
+    let name = PersonName::from_repetition(field.get_repetition(0).unwrap());
+    assert_eq!(name.surname, "BERG");
+    assert_eq!(name.surname_prefix.as_deref(), Some("VAN DEN"));
+    assert_eq!(name.given_name.as_deref(), Some("JAN"));
+
+    let repetition = name.to_repetition();
+    let family = repetition.get_component(0).unwrap();
+    assert_eq!(family.subcomponents.len(), 2);
+    assert_eq!(family.get_subcomponent(1).unwrap().as_str(), "VAN DEN");
+    assert_eq!(PersonName::from_repetition(&repetition), name);
+
+    // Subcomponents are created on write even when earlier ones are unset
+    let prefix_only = PersonName {
+        surname_prefix: Some("DE".to_string()),
+        ..Default::default()
+    };
+    let repetition = prefix_only.to_repetition();
+    assert_eq!(repetition.get_component(0).unwrap().get_subcomponent(1).unwrap().as_str(), "DE");
+    assert_eq!(PersonName::from_repetition(&rs7_core::field::Repetition::new()).surname_prefix, None);
+}
+
+#[test]
+fn test_subcomponent_fields() {
+    let delimiters = rs7_core::delimiters::Delimiters::default();
+
+    let pid = PatientAuthority {
+        id: "123".to_string(),
+        authority_namespace: "HOSP".to_string(),
+        authority_oid: Some("1.2.3".to_string()),
+    };
+    let segment = pid.to_segment();
+    assert_eq!(segment.encode(&delimiters), "PID|||123^^^HOSP&1.2.3");
+    assert_eq!(PatientAuthority::from_segment(&segment).unwrap(), pid);
+
+    // Only the second subcomponent present
+    let mut segment = Segment::new("PID");
+    let _ = segment.set_component(3, 0, 3, "");
+    let component = segment
+        .get_field_mut(3)
+        .and_then(|f| f.get_repetition_mut(0))
+        .and_then(|r| r.get_component_mut(3))
+        .unwrap();
+    component.add_subcomponent(rs7_core::field::SubComponent::new("2.16.840"));
+    let parsed = PatientAuthority::from_segment(&segment).unwrap();
+    assert_eq!(parsed.authority_namespace, "");
+    assert_eq!(parsed.authority_oid, Some("2.16.840".to_string()));
+}
@@ -0,0 +1,360 @@
+//! Transport-independent MLLP framing
+//!
+//! [`MllpFrameReader`] and [`MllpFrameWriter`] implement MLLP framing over any
+//! `AsyncRead`/`AsyncWrite`, so the same logic used by [`MllpClient`] and
+//! [`MllpConnection`] can be reused over in-memory pipes or non-TCP transports.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rs7_mllp::frame::{MllpFrameReader, MllpFrameWriter};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> rs7_core::error::Result<()> {
+//! let (client, server) = tokio::io::duplex(1024);
+//!
+//! let mut writer = MllpFrameWriter::new(client);
+//! writer.write_frame("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5").await?;
+//!
+//! let mut reader = MllpFrameReader::new(server);
+//! let frame = reader.read_frame().await?;
+//! assert!(frame.unwrap().starts_with("MSH|"));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`MllpClient`]: crate::MllpClient
+//! [`MllpConnection`]: crate::MllpConnection
+
+use crate::{find_end_of_block, MllpFrame, DEFAULT_MAX_MESSAGE_SIZE, START_OF_BLOCK};
+use rs7_core::{
+    error::{Error, Result},
+    message::Message,
+};
+use rs7_parser::parse_message;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Size of each read from the underlying transport
+const READ_CHUNK_SIZE: usize = 8192;
+
+/// Reads MLLP frames from an `AsyncRead`
+///
+/// Bytes read past the end of a frame are kept for the next call, so
+/// pipelined messages arriving in a single read are not lost. Bytes preceding
+/// a start-of-block marker are discarded.
+pub struct MllpFrameReader<R> {
+    inner: R,
+    buffer: Vec<u8>,
+    /// Number of buffered bytes already searched for the end-of-block sequence
+    scanned: usize,
+    max_message_size: usize,
+}
+
+impl<R: AsyncRead + Unpin> MllpFrameReader<R> {
+    /// Create a reader with the default maximum message size
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buffer: Vec::new(),
+            scanned: 0,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+        }
+    }
+
+    /// Set the maximum frame size in bytes, including the framing markers
+    pub fn with_max_message_size(mut self, size: usize) -> Self {
+        self.max_message_size = size;
+        self
+    }
+
+    /// Set the maximum frame size in bytes, including the framing markers
+    pub fn set_max_message_size(&mut self, size: usize) {
+        self.max_message_size = size;
+    }
+
+    /// Read the next frame and return its HL7 payload
+    ///
+    /// Returns `Ok(None)` if the transport reached EOF between frames, and an
+    /// error if it closed in the middle of a frame.
+    pub async fn read_frame(&mut self) -> Result<Option<String>> {
+        match self.read_raw_frame().await? {
+            Some(frame) => MllpFrame::unwrap(&frame).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Read the next frame, including its framing markers
+    ///
+    /// If the frame exceeds the maximum size, its start stays in the buffer
+    /// (see [`buffered`](Self::buffered)).
+    pub(crate) async fn read_raw_frame(&mut self) -> Result<Option<Vec<u8>>> {
+        let mut chunk = [0u8; READ_CHUNK_SIZE];
+
+        loop {
+            if let Some(frame) = self.next_frame()? {
+                return Ok(Some(frame));
+            }
+
+            let n = self
+                .inner
+                .read(&mut chunk)
+                .await
+                .map_err(|e| Error::Network(format!("Failed to read: {}", e)))?;
+
+            if n == 0 {
+                if self.buffer.is_empty() {
+                    return Ok(None);
+                }
+                return Err(Error::Network("Connection closed".to_string()));
+            }
+
+            self.buffer.extend_from_slice(&chunk[..n]);
+        }
+    }
+
+    /// Read the next frame and parse it as an HL7 message
+    pub async fn read_message(&mut self) -> Result<Option<Message>> {
+        match self.read_frame().await? {
+            Some(hl7_text) => parse_message(&hl7_text).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Get a reference to the underlying reader
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying reader
+    ///
+    /// Reading from it directly will corrupt the framing state.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consume the frame reader, returning the underlying reader
+    ///
+    /// Any buffered bytes that have not been returned as a frame are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Bytes read but not yet returned as a frame
+    pub(crate) fn buffered(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Remove and return the next complete frame, if the buffer holds one
+    fn next_frame(&mut self) -> Result<Option<Vec<u8>>> {
+        match self.buffer.iter().position(|&b| b == START_OF_BLOCK) {
+            Some(0) => {}
+            Some(start) => {
+                self.buffer.drain(..start);
+                self.scanned = 0;
+            }
+            None => {
+                self.buffer.clear();
+                self.scanned = 0;
+                return Ok(None);
+            }
+        }
+
+        // The end is the first FS immediately followed by CR after the start
+        // marker; a lone FS or CR is payload
+        let from = self.scanned.max(1);
+        let end = find_end_of_block(&self.buffer[from..]).map(|pos| from + pos + 2);
+
+        let frame_len = end.unwrap_or(self.buffer.len());
+        if frame_len > self.max_message_size {
+            return Err(Error::Mllp(format!(
+                "Message exceeds maximum size of {} bytes",
+                self.max_message_size
+            )));
+        }
+
+        match end {
+            Some(end) => {
+                self.scanned = 0;
+                Ok(Some(self.buffer.drain(..end).collect()))
+            }
+            None => {
+                // Re-check the last byte next time in case it is a split FS
+                self.scanned = self.buffer.len().saturating_sub(1);
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Writes MLLP frames to an `AsyncWrite`
+pub struct MllpFrameWriter<W> {
+    inner: W,
+}
+
+impl<W: AsyncWrite + Unpin> MllpFrameWriter<W> {
+    /// Create a frame writer
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Wrap an HL7 payload in an MLLP frame, write it and flush
+    pub async fn write_frame(&mut self, hl7_text: &str) -> Result<()> {
+        let framed = MllpFrame::wrap(hl7_text);
+        self.inner
+            .write_all(&framed)
+            .await
+            .map_err(|e| Error::Network(format!("Failed to send: {}", e)))?;
+        self.inner
+            .flush()
+            .await
+            .map_err(|e| Error::Network(format!("Failed to send: {}", e)))
+    }
+
+    /// Encode an HL7 message and write it as a single frame
+    ///
+    /// Segments are always separated by carriage returns on the wire,
+    /// whatever [`Message::segment_terminator`] the message was parsed with.
+    pub async fn write_message(&mut self, message: &Message) -> Result<()> {
+        self.write_frame(&message.encode_with_separator("\r")).await
+    }
+
+    /// Get a reference to the underlying writer
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying writer
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consume the frame writer, returning the underlying writer
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CARRIAGE_RETURN, END_OF_BLOCK};
+    use proptest::prelude::*;
+
+    #[tokio::test]
+    async fn test_read_pipelined_frames() {
+        let (mut tx, rx) = tokio::io::duplex(4096);
+        let mut data = MllpFrame::wrap("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5");
+        data.extend(MllpFrame::wrap("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|2|P|2.5"));
+        tx.write_all(&data).await.unwrap();
+        drop(tx);
+
+        let mut reader = MllpFrameReader::new(rx);
+        let first = reader.read_message().await.unwrap().unwrap();
+        let second = reader.read_message().await.unwrap().unwrap();
+        assert_eq!(first.get_control_id(), Some("1"));
+        assert_eq!(second.get_control_id(), Some("2"));
+        assert!(reader.read_message().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_frame_split_across_writes() {
+        let (mut tx, rx) = tokio::io::duplex(4096);
+        let mut reader = MllpFrameReader::new(rx);
+
+        tx.write_all(&[0x00, START_OF_BLOCK, b'M', b'S', b'H', END_OF_BLOCK]).await.unwrap();
+        let read = tokio::spawn(async move { reader.read_frame().await });
+        tokio::task::yield_now().await;
+        tx.write_all(&[CARRIAGE_RETURN]).await.unwrap();
+
+        assert_eq!(read.await.unwrap().unwrap(), Some("MSH".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_read_frame_errors() {
+        let (mut tx, rx) = tokio::io::duplex(4096);
+        tx.write_all(&MllpFrame::wrap("MSH|^~\\&|TOO|LONG")).await.unwrap();
+        let mut reader = MllpFrameReader::new(rx).with_max_message_size(8);
+        assert!(reader.read_frame().await.is_err());
+
+        let (mut tx, rx) = tokio::io::duplex(4096);
+        tx.write_all(&[START_OF_BLOCK, b'M']).await.unwrap();
+        drop(tx);
+        let mut reader = MllpFrameReader::new(rx);
+        assert!(reader.read_frame().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_writer_round_trip() {
+        let (tx, rx) = tokio::io::duplex(4096);
+        let mut writer = MllpFrameWriter::new(tx);
+        let mut reader = MllpFrameReader::new(rx);
+
+        let msg = parse_message("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|42|P|2.5\rPID|1||123").unwrap();
+        writer.write_message(&msg).await.unwrap();
+
+        let received = reader.read_message().await.unwrap().unwrap();
+        assert_eq!(received.encode(), msg.encode());
+    }
+
+    #[tokio::test]
+    async fn test_writer_sends_carriage_returns() {
+        let (tx, rx) = tokio::io::duplex(4096);
+        let mut writer = MllpFrameWriter::new(tx);
+        let mut reader = MllpFrameReader::new(rx);
+
+        let msg = parse_message("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|42|P|2.5\nPID|1||123").unwrap();
+        writer.write_message(&msg).await.unwrap();
+
+        let frame = reader.read_frame().await.unwrap().unwrap();
+        assert_eq!(frame, msg.encode().replace('\n', "\r"));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_frames_split_at_first_end_sequence(
+            payloads in prop::collection::vec("[A-Z|\x1c\r]{0,32}", 1..4),
+            chunk in 1usize..16,
+        ) {
+            let mut data = Vec::new();
+            for payload in &payloads {
+                data.extend(MllpFrame::wrap(payload));
+            }
+
+            // Each frame ends at the first FS CR after its start marker
+            let mut expected = Vec::new();
+            let mut rest = &data[..];
+            while let Some(start) = rest.iter().position(|&b| b == START_OF_BLOCK) {
+                rest = &rest[start + 1..];
+                match find_end_of_block(rest) {
+                    Some(end) => {
+                        expected.push(rest[..end].to_vec());
+                        rest = &rest[end + 2..];
+                    }
+                    None => break,
+                }
+            }
+
+            let mut reader = MllpFrameReader::new(tokio::io::empty());
+            let mut frames = Vec::new();
+            for bytes in data.chunks(chunk) {
+                reader.buffer.extend_from_slice(bytes);
+                while let Some(frame) = reader.next_frame().unwrap() {
+                    prop_assert_eq!(frame[0], START_OF_BLOCK);
+                    prop_assert_eq!(&frame[frame.len() - 2..], &[END_OF_BLOCK, CARRIAGE_RETURN]);
+                    frames.push(frame[1..frame.len() - 2].to_vec());
+                }
+            }
+            prop_assert_eq!(frames, expected);
+        }
+
+        #[test]
+        fn prop_read_frame_round_trips_clean_payloads(payload in "[A-Z|^\x1c\r]{0,64}") {
+            prop_assume!(!payload.contains("\x1c\r"));
+            let mut reader = MllpFrameReader::new(tokio::io::empty());
+            reader.buffer = MllpFrame::wrap(&payload);
+            let frame = reader.next_frame().unwrap().unwrap();
+            prop_assert_eq!(MllpFrame::unwrap(&frame).unwrap(), payload);
+            prop_assert!(reader.buffer.is_empty());
+        }
+    }
+}
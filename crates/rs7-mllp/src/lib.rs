@@ -6,6 +6,9 @@
 //! - FS (File Separator): 0x1C - End of block
 //! - CR (Carriage Return): 0x0D - End of message
 
+// Transport-independent framing
+pub mod frame;
+
 // Message routing
 pub mod router;
 
@@ -21,13 +24,17 @@ pub mod tls;
 pub mod testing;
 
 use rs7_core::{
+    builders::ack::AckBuilder,
     error::{Error, Result},
     message::Message,
 };
-use rs7_parser::parse_message;
-use std::time::Duration;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
 use tokio::{
-    io::{AsyncReadExt, AsyncWriteExt},
+    io::{AsyncRead, AsyncWrite, AsyncWriteExt, ReadBuf},
     net::{TcpListener, TcpStream},
 };
 
@@ -45,44 +52,54 @@ enum MllpStream {
     TlsServer(tokio_rustls::server::TlsStream<TcpStream>),
 }
 
-impl MllpStream {
-    async fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        match self {
-            MllpStream::Plain(stream) => stream.read(buf).await,
+impl AsyncRead for MllpStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            MllpStream::Plain(stream) => Pin::new(stream).poll_read(cx, buf),
             #[cfg(feature = "tls")]
-            MllpStream::TlsClient(stream) => stream.read(buf).await,
+            MllpStream::TlsClient(stream) => Pin::new(stream).poll_read(cx, buf),
             #[cfg(feature = "tls")]
-            MllpStream::TlsServer(stream) => stream.read(buf).await,
+            MllpStream::TlsServer(stream) => Pin::new(stream).poll_read(cx, buf),
         }
     }
+}
 
-    async fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
-        match self {
-            MllpStream::Plain(stream) => stream.write_all(buf).await,
+impl AsyncWrite for MllpStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            MllpStream::Plain(stream) => Pin::new(stream).poll_write(cx, buf),
             #[cfg(feature = "tls")]
-            MllpStream::TlsClient(stream) => stream.write_all(buf).await,
+            MllpStream::TlsClient(stream) => Pin::new(stream).poll_write(cx, buf),
             #[cfg(feature = "tls")]
-            MllpStream::TlsServer(stream) => stream.write_all(buf).await,
+            MllpStream::TlsServer(stream) => Pin::new(stream).poll_write(cx, buf),
         }
     }
 
-    async fn flush(&mut self) -> std::io::Result<()> {
-        match self {
-            MllpStream::Plain(stream) => stream.flush().await,
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            MllpStream::Plain(stream) => Pin::new(stream).poll_flush(cx),
             #[cfg(feature = "tls")]
-            MllpStream::TlsClient(stream) => stream.flush().await,
+            MllpStream::TlsClient(stream) => Pin::new(stream).poll_flush(cx),
             #[cfg(feature = "tls")]
-            MllpStream::TlsServer(stream) => stream.flush().await,
+            MllpStream::TlsServer(stream) => Pin::new(stream).poll_flush(cx),
         }
     }
 
-    async fn shutdown(&mut self) -> std::io::Result<()> {
-        match self {
-            MllpStream::Plain(stream) => stream.shutdown().await,
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            MllpStream::Plain(stream) => Pin::new(stream).poll_shutdown(cx),
             #[cfg(feature = "tls")]
-            MllpStream::TlsClient(stream) => stream.shutdown().await,
+            MllpStream::TlsClient(stream) => Pin::new(stream).poll_shutdown(cx),
             #[cfg(feature = "tls")]
-            MllpStream::TlsServer(stream) => stream.shutdown().await,
+            MllpStream::TlsServer(stream) => Pin::new(stream).poll_shutdown(cx),
         }
     }
 }
@@ -90,6 +107,8 @@ impl MllpStream {
 // Re-export pool types
 pub use pool::{MllpPool, PoolConfig, PoolStats};
 
+pub use frame::{MllpFrameReader, MllpFrameWriter};
+
 /// MLLP frame markers
 pub const START_OF_BLOCK: u8 = 0x0B; // Vertical Tab (VT)
 pub const END_OF_BLOCK: u8 = 0x1C; // File Separator (FS)
@@ -119,6 +138,8 @@ pub struct MllpConfig {
     pub write_timeout: Duration,
     /// Connection timeout (default: 10 seconds)
     pub connect_timeout: Duration,
+    /// Answer rejected messages with an AR acknowledgment (default: false)
+    pub nack_on_error: bool,
 }
 
 impl Default for MllpConfig {
@@ -128,6 +149,7 @@ impl Default for MllpConfig {
             read_timeout: DEFAULT_READ_TIMEOUT,
             write_timeout: DEFAULT_WRITE_TIMEOUT,
             connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            nack_on_error: false,
         }
     }
 }
@@ -161,6 +183,20 @@ impl MllpConfig {
         self.connect_timeout = timeout;
         self
     }
+
+    /// Send an AR acknowledgment when a received message is rejected
+    ///
+    /// When a server connection receives a frame that is too large, is not
+    /// valid MLLP or does not parse as HL7, it writes back an AR ACK before
+    /// returning the error, so the sender stops retrying. The ACK references
+    /// the original MSH when it can be read (MSA-2 carries its control ID);
+    /// otherwise a generic v2.5 AR ACK with an empty MSA-2 is sent. The
+    /// connection should be closed after such an error, as [`MllpServer::serve`]
+    /// does.
+    pub fn with_nack_on_error(mut self, nack_on_error: bool) -> Self {
+        self.nack_on_error = nack_on_error;
+        self
+    }
 }
 
 /// MLLP message framing
@@ -178,6 +214,10 @@ impl MllpFrame {
     }
 
     /// Unwrap an MLLP frame to get the HL7 message
+    ///
+    /// The frame must start with `VT` and end with `FS CR`, and the payload
+    /// may not itself contain `FS CR`, since a reader would end the frame
+    /// there. A lone `FS` or `CR` inside the payload is allowed.
     pub fn unwrap(framed: &[u8]) -> Result<String> {
         if framed.len() < 3 {
             return Err(Error::Mllp("Frame too short".to_string()));
@@ -197,15 +237,57 @@ impl MllpFrame {
         }
 
         let message_bytes = &framed[1..end_pos];
+        if let Some(pos) = find_end_of_block(message_bytes) {
+            return Err(Error::Mllp(format!(
+                "End-of-block sequence inside payload at byte {}",
+                pos + 1
+            )));
+        }
+
         String::from_utf8(message_bytes.to_vec())
             .map_err(|e| Error::Mllp(format!("Invalid UTF-8: {}", e)))
     }
 }
 
+/// Build an AR acknowledgment for a frame that could not be accepted
+///
+/// The first segment of the frame is used as the original MSH if it parses,
+/// so the ACK is addressed to the sender and references its control ID.
+fn reject_ack(frame: &[u8], reason: &str) -> Option<Message> {
+    let payload = frame.strip_prefix(&[START_OF_BLOCK]).unwrap_or(frame);
+    let text = String::from_utf8_lossy(payload);
+    let header = text
+        .split(['\r', '\n'])
+        .next()
+        .filter(|line| line.starts_with("MSH"))
+        .and_then(|line| rs7_parser::parse_message(line).ok());
+
+    let builder = match header {
+        Some(original) => AckBuilder::for_message(&original),
+        None => AckBuilder::with_version(rs7_core::Version::V2_5),
+    };
+    builder.reject(reason).build().ok()
+}
+
+/// Truncate `bytes` after its last segment terminator, dropping a partial
+/// trailing segment
+fn complete_lines(bytes: &[u8]) -> &[u8] {
+    match bytes.iter().rposition(|&b| b == b'\r' || b == b'\n') {
+        Some(pos) => &bytes[..pos],
+        None => &[],
+    }
+}
+
+/// Find the first `FS CR` end-of-block sequence in `bytes`
+pub(crate) fn find_end_of_block(bytes: &[u8]) -> Option<usize> {
+    bytes
+        .windows(2)
+        .position(|w| w[0] == END_OF_BLOCK && w[1] == CARRIAGE_RETURN)
+}
+
 /// MLLP client for sending messages
 pub struct MllpClient {
-    stream: MllpStream,
-    max_message_size: usize,
+    reader: MllpFrameReader<MllpStream>,
     read_timeout: Duration,
     write_timeout: Duration,
 }
@@ -231,8 +313,8 @@ impl MllpClient {
             .map_err(|e| Error::Network(format!("Failed to set TCP_NODELAY: {}", e)))?;
 
         Ok(Self {
-            stream: MllpStream::Plain(tcp_stream),
-            max_message_size: config.max_message_size,
+            reader: MllpFrameReader::new(MllpStream::Plain(tcp_stream))
+                .with_max_message_size(config.max_message_size),
             read_timeout: config.read_timeout,
             write_timeout: config.write_timeout,
         })
@@ -342,8 +424,8 @@ impl MllpClient {
             .map_err(|e| Error::Network(format!("TLS handshake failed: {}", e)))?;
 
         Ok(Self {
-            stream: MllpStream::TlsClient(tls_stream),
-            max_message_size: config.max_message_size,
+            reader: MllpFrameReader::new(MllpStream::TlsClient(tls_stream))
+                .with_max_message_size(config.max_message_size),
             read_timeout: config.read_timeout,
             write_timeout: config.write_timeout,
         })
@@ -351,7 +433,7 @@ impl MllpClient {
 
     /// Set the maximum message size
     pub fn set_max_message_size(&mut self, size: usize) {
-        self.max_message_size = size;
+        self.reader.set_max_message_size(size);
     }
 
     /// Set the read timeout
@@ -366,23 +448,13 @@ impl MllpClient {
 
     /// Send a message and wait for acknowledgment
     pub async fn send_message(&mut self, message: &Message) -> Result<Message> {
-        // Encode message
-        let hl7_text = message.encode();
-
-        // Wrap in MLLP frame
-        let framed = MllpFrame::wrap(&hl7_text);
-
         // Send with timeout
         tokio::time::timeout(
             self.write_timeout,
-            async {
-                self.stream.write_all(&framed).await?;
-                self.stream.flush().await
-            }
+            MllpFrameWriter::new(self.reader.get_mut()).write_message(message)
         )
         .await
-        .map_err(|_| Error::Network(format!("Write timeout after {:?}", self.write_timeout)))?
-        .map_err(|e| Error::Network(format!("Failed to send: {}", e)))?;
+        .map_err(|_| Error::Network(format!("Write timeout after {:?}", self.write_timeout)))??;
 
         // Receive acknowledgment
         self.receive_message().await
@@ -392,66 +464,17 @@ impl MllpClient {
     pub async fn receive_message(&mut self) -> Result<Message> {
         tokio::time::timeout(
             self.read_timeout,
-            self.receive_message_internal()
+            self.reader.read_message()
         )
         .await
-        .map_err(|_| Error::Network(format!("Read timeout after {:?}", self.read_timeout)))?
-    }
-
-    /// Internal method to receive a message with buffer size protection
-    async fn receive_message_internal(&mut self) -> Result<Message> {
-        let mut buffer = Vec::with_capacity(8192); // Pre-allocate reasonable size
-        let mut chunk = [0u8; 4096]; // Read in larger chunks for efficiency
-        let mut found_start = false;
-
-        loop {
-            let n = self.stream
-                .read(&mut chunk)
-                .await
-                .map_err(|e| Error::Network(format!("Failed to read: {}", e)))?;
-
-            if n == 0 {
-                return Err(Error::Network("Connection closed".to_string()));
-            }
-
-            for i in 0..n {
-                let byte = chunk[i];
-
-                if !found_start {
-                    if byte == START_OF_BLOCK {
-                        found_start = true;
-                        buffer.push(byte);
-                    }
-                    continue;
-                }
-
-                // Check buffer size limit
-                if buffer.len() >= self.max_message_size {
-                    return Err(Error::Mllp(format!(
-                        "Message exceeds maximum size of {} bytes",
-                        self.max_message_size
-                    )));
-                }
-
-                buffer.push(byte);
-
-                // Check for end sequence (FS CR)
-                if buffer.len() >= 3 {
-                    let len = buffer.len();
-                    if buffer[len - 2] == END_OF_BLOCK && buffer[len - 1] == CARRIAGE_RETURN {
-                        // Unwrap frame
-                        let hl7_text = MllpFrame::unwrap(&buffer)?;
-                        // Parse message
-                        return parse_message(&hl7_text);
-                    }
-                }
-            }
-        }
+        .map_err(|_| Error::Network(format!("Read timeout after {:?}", self.read_timeout)))??
+        .ok_or_else(|| Error::Network("Connection closed".to_string()))
     }
 
     /// Close the connection
     pub async fn close(mut self) -> Result<()> {
-        self.stream
+        self.reader
+            .get_mut()
             .shutdown()
             .await
             .map_err(|e| Error::Network(format!("Failed to close: {}", e)))
@@ -573,14 +596,130 @@ impl MllpServer {
     /// Accept a connection and return an MllpConnection with the server's configuration
     ///
     /// If the server was created with `bind_tls()` or `bind_tls_with_config()`, this will
-    /// perform TLS handshake on the accepted connection.
+    /// perform TLS handshake on the accepted connection. The handshake is
+    /// bounded by the configured connect timeout.
     pub async fn accept(&self) -> Result<MllpConnection> {
+        let tcp_stream = self.accept_tcp().await?;
+        self.connection_setup().establish(tcp_stream).await
+    }
+
+    /// Accept a raw TCP connection from the listener
+    async fn accept_tcp(&self) -> Result<TcpStream> {
         let (tcp_stream, _addr) = self
             .listener
             .accept()
             .await
             .map_err(|e| Error::Network(format!("Failed to accept: {}", e)))?;
+        Ok(tcp_stream)
+    }
 
+    /// Settings needed to turn an accepted TCP stream into a connection
+    fn connection_setup(&self) -> ConnectionSetup {
+        ConnectionSetup {
+            config: self.config.clone(),
+            #[cfg(feature = "tls")]
+            tls_acceptor: self.tls_acceptor.clone(),
+        }
+    }
+
+    /// Get the local address
+    pub fn local_addr(&self) -> Result<std::net::SocketAddr> {
+        self.listener
+            .local_addr()
+            .map_err(|e| Error::Network(format!("Failed to get local addr: {}", e)))
+    }
+
+    /// Accept connections forever, answering each received message with the
+    /// handler's response
+    ///
+    /// Every connection is served on its own Tokio task. A connection is
+    /// closed when the peer sends EOF, a read or write times out, or the
+    /// handler returns an error; failures on one connection never stop the
+    /// server itself.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> rs7_core::error::Result<()> {
+    /// use rs7_mllp::MllpServer;
+    ///
+    /// let server = MllpServer::bind("0.0.0.0:2575").await?;
+    /// server.serve(|message| {
+    ///     // Build and return an ACK for `message`
+    ///     Ok(message)
+    /// }).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn serve<F>(self, handler: F) -> Result<()>
+    where
+        F: Fn(Message) -> Result<Message> + Send + Sync + 'static,
+    {
+        let handler = std::sync::Arc::new(handler);
+        let mut backoff = ACCEPT_BACKOFF_MIN;
+
+        loop {
+            // Accept errors such as EMFILE tend to persist, so back off
+            // instead of retrying immediately
+            let tcp_stream = match self.accept_tcp().await {
+                Ok(tcp_stream) => {
+                    backoff = ACCEPT_BACKOFF_MIN;
+                    tcp_stream
+                }
+                Err(e) => {
+                    tracing::warn!("{}; retrying in {:?}", e, backoff);
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(ACCEPT_BACKOFF_MAX);
+                    continue;
+                }
+            };
+
+            // The TLS handshake runs on the connection's task so a stalled
+            // peer cannot hold up later accepts
+            let setup = self.connection_setup();
+            let handler = handler.clone();
+            tokio::spawn(async move {
+                let mut conn = match setup.establish(tcp_stream).await {
+                    Ok(conn) => conn,
+                    Err(e) => {
+                        tracing::debug!("Dropping connection: {}", e);
+                        return;
+                    }
+                };
+
+                while let Ok(Some(message)) = conn.try_receive_message().await {
+                    let response = match handler(message) {
+                        Ok(response) => response,
+                        Err(_) => break,
+                    };
+                    if conn.send_message(&response).await.is_err() {
+                        break;
+                    }
+                }
+                let _ = conn.close().await;
+            });
+        }
+    }
+}
+
+/// Initial delay before retrying after a failed accept
+const ACCEPT_BACKOFF_MIN: Duration = Duration::from_millis(10);
+
+/// Maximum delay before retrying after a failed accept
+const ACCEPT_BACKOFF_MAX: Duration = Duration::from_secs(1);
+
+/// Per-connection setup shared between [`MllpServer::accept`] and the tasks
+/// spawned by [`MllpServer::serve`]
+struct ConnectionSetup {
+    config: MllpConfig,
+    #[cfg(feature = "tls")]
+    tls_acceptor: Option<TlsAcceptor>,
+}
+
+impl ConnectionSetup {
+    /// Configure the TCP stream and perform the TLS handshake if enabled,
+    /// within the connect timeout
+    async fn establish(&self, tcp_stream: TcpStream) -> Result<MllpConnection> {
         // Disable Nagle's algorithm for low-latency messaging
         tcp_stream.set_nodelay(true)
             .map_err(|e| Error::Network(format!("Failed to set TCP_NODELAY: {}", e)))?;
@@ -588,9 +727,14 @@ impl MllpServer {
         #[cfg(feature = "tls")]
         let stream = if let Some(ref acceptor) = self.tls_acceptor {
             // Perform TLS handshake
-            let tls_stream = acceptor
-                .accept(tcp_stream)
+            let tls_stream = tokio::time::timeout(self.config.connect_timeout, acceptor.accept(tcp_stream))
                 .await
+                .map_err(|_| {
+                    Error::Network(format!(
+                        "TLS handshake timeout after {:?}",
+                        self.config.connect_timeout
+                    ))
+                })?
                 .map_err(|e| Error::Network(format!("TLS handshake failed: {}", e)))?;
             MllpStream::TlsServer(tls_stream)
         } else {
@@ -601,111 +745,135 @@ impl MllpServer {
         let stream = MllpStream::Plain(tcp_stream);
 
         Ok(MllpConnection {
-            stream,
-            max_message_size: self.config.max_message_size,
+            reader: MllpFrameReader::new(stream)
+                .with_max_message_size(self.config.max_message_size),
             read_timeout: self.config.read_timeout,
             write_timeout: self.config.write_timeout,
+            nack_on_error: self.config.nack_on_error,
         })
     }
-
-    /// Get the local address
-    pub fn local_addr(&self) -> Result<std::net::SocketAddr> {
-        self.listener
-            .local_addr()
-            .map_err(|e| Error::Network(format!("Failed to get local addr: {}", e)))
-    }
 }
 
 /// An MLLP connection
 pub struct MllpConnection {
-    stream: MllpStream,
-    max_message_size: usize,
+    reader: MllpFrameReader<MllpStream>,
     read_timeout: Duration,
     write_timeout: Duration,
+    nack_on_error: bool,
 }
 
 impl MllpConnection {
     /// Receive a message with timeout and size limit
     pub async fn receive_message(&mut self) -> Result<Message> {
-        tokio::time::timeout(
+        self.try_receive_message()
+            .await?
+            .ok_or_else(|| Error::Network("Connection closed".to_string()))
+    }
+
+    /// Receive a message, returning `None` if the peer closed the connection
+    /// before starting a new frame
+    async fn try_receive_message(&mut self) -> Result<Option<Message>> {
+        let frame = tokio::time::timeout(
             self.read_timeout,
-            self.receive_message_internal()
+            self.reader.read_raw_frame()
         )
         .await
-        .map_err(|_| Error::Network(format!("Read timeout after {:?}", self.read_timeout)))?
-    }
-
-    /// Internal method to receive a message with buffer size protection
-    async fn receive_message_internal(&mut self) -> Result<Message> {
-        let mut buffer = Vec::with_capacity(8192); // Pre-allocate reasonable size
-        let mut chunk = [0u8; 4096]; // Read in larger chunks for efficiency
-        let mut found_start = false;
-
-        loop {
-            let n = self.stream
-                .read(&mut chunk)
-                .await
-                .map_err(|e| Error::Network(format!("Failed to read: {}", e)))?;
-
-            if n == 0 {
-                return Err(Error::Network("Connection closed".to_string()));
+        .map_err(|_| Error::Network(format!("Read timeout after {:?}", self.read_timeout)))?;
+
+        let frame = match frame {
+            Ok(Some(frame)) => frame,
+            Ok(None) => return Ok(None),
+            Err(e @ Error::Mllp(_)) => {
+                // Oversized: the start of the frame is still buffered
+                let header = complete_lines(self.reader.buffered()).to_vec();
+                return Err(self.reject(&header, e).await);
             }
+            Err(e) => return Err(e),
+        };
 
-            for i in 0..n {
-                let byte = chunk[i];
-
-                if !found_start {
-                    if byte == START_OF_BLOCK {
-                        found_start = true;
-                        buffer.push(byte);
-                    }
-                    continue;
-                }
-
-                // Check buffer size limit
-                if buffer.len() >= self.max_message_size {
-                    return Err(Error::Mllp(format!(
-                        "Message exceeds maximum size of {} bytes",
-                        self.max_message_size
-                    )));
-                }
-
-                buffer.push(byte);
+        let result = MllpFrame::unwrap(&frame).and_then(|text| rs7_parser::parse_message(&text));
+        match result {
+            Ok(message) => Ok(Some(message)),
+            Err(e) => Err(self.reject(&frame, e).await),
+        }
+    }
 
-                // Check for end sequence (FS CR)
-                if buffer.len() >= 3 {
-                    let len = buffer.len();
-                    if buffer[len - 2] == END_OF_BLOCK && buffer[len - 1] == CARRIAGE_RETURN {
-                        // Unwrap frame
-                        let hl7_text = MllpFrame::unwrap(&buffer)?;
-                        // Parse message
-                        return parse_message(&hl7_text);
-                    }
-                }
-            }
+    /// Send an AR acknowledgment for a rejected frame if configured to,
+    /// returning the error that caused the rejection
+    async fn reject(&mut self, frame: &[u8], error: Error) -> Error {
+        if self.nack_on_error
+            && let Some(nack) = reject_ack(frame, &error.to_string())
+        {
+            // The original error is what the caller needs to see
+            let _ = self.send_message(&nack).await;
         }
+        error
     }
 
     /// Send a message with timeout
     pub async fn send_message(&mut self, message: &Message) -> Result<()> {
-        let hl7_text = message.encode();
-        let framed = MllpFrame::wrap(&hl7_text);
-
         tokio::time::timeout(
             self.write_timeout,
-            async {
-                self.stream.write_all(&framed).await?;
-                self.stream.flush().await
-            }
+            MllpFrameWriter::new(self.reader.get_mut()).write_message(message)
         )
         .await
         .map_err(|_| Error::Network(format!("Write timeout after {:?}", self.write_timeout)))?
-        .map_err(|e| Error::Network(format!("Failed to send: {}", e)))
+    }
+
+    /// Get the certificate chain the peer presented during the TLS handshake
+    ///
+    /// On a connection accepted by an mTLS server this is the client's
+    /// verified certificate chain, end-entity certificate first. Returns
+    /// `None` for plain TCP connections and when the peer sent no
+    /// certificate.
+    #[cfg(feature = "tls")]
+    pub fn peer_certificates(&self) -> Option<&[rustls::pki_types::CertificateDer<'static>]> {
+        match self.reader.get_ref() {
+            MllpStream::Plain(_) => None,
+            MllpStream::TlsClient(stream) => stream.get_ref().1.peer_certificates(),
+            MllpStream::TlsServer(stream) => stream.get_ref().1.peer_certificates(),
+        }
+    }
+
+    /// Get the subject common name (CN) of the peer's certificate
+    ///
+    /// Subject alternative names are not considered; use
+    /// [`Self::peer_dns_names`] for those. See [`tls::certificate_common_name`].
+    ///
+    /// Lets a handler authorize a connection by the client certificate it
+    /// presented, e.g. on a listener shared by several partners:
+    ///
+    /// ```no_run
+    /// # async fn example(server: rs7_mllp::MllpServer) -> rs7_core::error::Result<()> {
+    /// let mut conn = server.accept().await?;
+    /// if conn.peer_common_name().as_deref() != Some("partner-a.example") {
+    ///     return conn.close().await;
+    /// }
+    /// let message = conn.receive_message().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tls")]
+    pub fn peer_common_name(&self) -> Option<String> {
+        crate::tls::certificate_common_name(self.peer_certificates()?.first()?)
+    }
+
+    /// Get the DNS subject alternative names of the peer's certificate
+    ///
+    /// Empty for plain TCP connections, peers that presented no certificate
+    /// and certificates without DNS names; see [`tls::certificate_dns_names`].
+    #[cfg(feature = "tls")]
+    pub fn peer_dns_names(&self) -> Vec<String> {
+        self.peer_certificates()
+            .and_then(|chain| chain.first())
+            .map(crate::tls::certificate_dns_names)
+            .unwrap_or_default()
     }
 
     /// Close the connection
     pub async fn close(mut self) -> Result<()> {
-        self.stream
+        self.reader
+            .get_mut()
             .shutdown()
             .await
             .map_err(|e| Error::Network(format!("Failed to close: {}", e)))
@@ -715,6 +883,7 @@ impl MllpConnection {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rs7_parser::parse_message;
 
     #[test]
     fn test_wrap_frame() {
@@ -755,4 +924,150 @@ mod tests {
         framed[len - 2] = 0x00; // Corrupt end marker
         assert!(MllpFrame::unwrap(&framed).is_err());
     }
+
+    #[test]
+    fn test_unwrap_embedded_end_markers() {
+        // A lone FS or CR in the payload is not a terminator
+        let framed = MllpFrame::wrap("A\x1cB\rC\x1c");
+        assert_eq!(MllpFrame::unwrap(&framed).unwrap(), "A\x1cB\rC\x1c");
+
+        // FS CR inside the payload would end the frame early
+        let framed = MllpFrame::wrap("A\x1c\rB");
+        assert!(MllpFrame::unwrap(&framed).is_err());
+
+        // FS without the trailing CR
+        assert!(MllpFrame::unwrap(&[START_OF_BLOCK, b'A', b'B', END_OF_BLOCK]).is_err());
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_unwrap_round_trips_without_end_sequence(payload in "[A-Z|^\x1c\r]{0,64}") {
+            let framed = MllpFrame::wrap(&payload);
+            let result = MllpFrame::unwrap(&framed);
+            if payload.contains("\x1c\r") {
+                proptest::prop_assert!(result.is_err());
+            } else {
+                proptest::prop_assert_eq!(result.unwrap(), payload);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_receive_pipelined_frames() {
+        let server = MllpServer::bind("127.0.0.1:0").await.unwrap();
+        let addr = server.local_addr().unwrap();
+
+        let mut data = MllpFrame::wrap("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5");
+        data.extend(MllpFrame::wrap("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|2|P|2.5"));
+        let sender = tokio::spawn(async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream.write_all(&data).await.unwrap();
+            stream.shutdown().await.unwrap();
+        });
+
+        let mut conn = server.accept().await.unwrap();
+        sender.await.unwrap();
+
+        let first = conn.receive_message().await.unwrap();
+        let second = conn.receive_message().await.unwrap();
+        assert_eq!(first.get_control_id(), Some("1"));
+        assert_eq!(second.get_control_id(), Some("2"));
+        assert!(conn.try_receive_message().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_serve_answers_each_message() {
+        let server = MllpServer::bind("127.0.0.1:0").await.unwrap();
+        let addr = server.local_addr().unwrap().to_string();
+
+        tokio::spawn(server.serve(|mut message| {
+            message.segments.retain(|s| s.id == "MSH");
+            Ok(message)
+        }));
+
+        let msg = parse_message("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5\rPID|1||123").unwrap();
+        let mut client = MllpClient::connect(&addr).await.unwrap();
+        for _ in 0..2 {
+            let response = client.send_message(&msg).await.unwrap();
+            assert_eq!(response.segments.len(), 1);
+        }
+        client.close().await.unwrap();
+
+        // The server keeps accepting new connections after one closes
+        let mut client = MllpClient::connect(&addr).await.unwrap();
+        assert!(client.send_message(&msg).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_nack_on_error() {
+        let config = MllpConfig::new()
+            .with_max_message_size(200)
+            .with_nack_on_error(true);
+        assert!(config.nack_on_error);
+        let server = MllpServer::bind_with_config("127.0.0.1:0", config).await.unwrap();
+        let addr = server.local_addr().unwrap();
+
+        let accepted = tokio::spawn(async move {
+            let mut errors = Vec::new();
+            for _ in 0..3 {
+                let mut conn = server.accept().await.unwrap();
+                errors.push(conn.receive_message().await.unwrap_err().to_string());
+            }
+            errors
+        });
+
+        let exchange = |frame: Vec<u8>| async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream.write_all(&frame).await.unwrap();
+            let mut reader = MllpFrameReader::new(stream);
+            reader.read_message().await.unwrap().unwrap()
+        };
+
+        // Unparseable body: the ACK still references the readable MSH
+        let ack = exchange(MllpFrame::wrap(
+            "MSH|^~\\&|APP|FAC|RCV|RFAC|20240101||ADT^A01|CTRL1|P|2.5\rPID|1\rX",
+        ))
+        .await;
+        let msa = ack.segment("MSA").unwrap();
+        assert_eq!(msa.get_field_value(1), Some("AR"));
+        assert_eq!(msa.get_field_value(2), Some("CTRL1"));
+        assert_eq!(ack.get_receiving_application(), Some("APP"));
+
+        // Oversized: the control ID is salvaged from the buffered header
+        let mut big = "MSH|^~\\&|APP|FAC|||20240101||ADT^A01|CTRL2|P|2.5\rNTE|1||".to_string();
+        big.push_str(&"x".repeat(300));
+        let ack = exchange(MllpFrame::wrap(&big)).await;
+        assert_eq!(ack.segment("MSA").unwrap().get_field_value(2), Some("CTRL2"));
+
+        // No readable MSH: generic NACK
+        let ack = exchange(MllpFrame::wrap("garbage")).await;
+        let msa = ack.segment("MSA").unwrap();
+        assert_eq!(msa.get_field_value(1), Some("AR"));
+        assert_eq!(msa.get_field_value(2), Some(""));
+
+        let errors = accepted.await.unwrap();
+        assert!(errors[1].contains("maximum size"));
+    }
+
+    #[tokio::test]
+    async fn test_serve_survives_handler_error() {
+        let server = MllpServer::bind("127.0.0.1:0").await.unwrap();
+        let addr = server.local_addr().unwrap().to_string();
+
+        tokio::spawn(server.serve(|message| {
+            if message.segments.len() > 1 {
+                Err(Error::Mllp("rejected".to_string()))
+            } else {
+                Ok(message)
+            }
+        }));
+
+        let bad = parse_message("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5\rPID|1").unwrap();
+        let mut client = MllpClient::connect(&addr).await.unwrap();
+        assert!(client.send_message(&bad).await.is_err());
+
+        let good = parse_message("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|2|P|2.5").unwrap();
+        let mut client = MllpClient::connect(&addr).await.unwrap();
+        assert!(client.send_message(&good).await.is_ok());
+    }
 }
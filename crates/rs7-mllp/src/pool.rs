@@ -31,16 +31,20 @@
 //! let message = Message::new();
 //! let ack = pool.send(&message).await?;
 //!
-//! // The connection is automatically returned to the pool
+//! // Or hold on to a connection for several exchanges; it is returned to
+//! // the pool when the guard is dropped
+//! let mut conn = pool.acquire().await?;
+//! let ack = conn.send_message(&message).await?;
+//! drop(conn);
 //! # Ok(())
 //! # }
 //! ```
 
 use std::collections::VecDeque;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
 use std::time::{Duration, Instant};
-use tokio::sync::{Mutex, Semaphore};
+use tokio::sync::Semaphore;
 
 use rs7_core::error::{Error, Result};
 use rs7_core::message::Message;
@@ -122,6 +126,12 @@ impl PoolConfig {
         self
     }
 
+    /// Set how often idle and expired connections are closed in the background
+    pub fn with_cleanup_interval(mut self, interval: Duration) -> Self {
+        self.cleanup_interval = interval;
+        self
+    }
+
     /// Set the MLLP configuration
     pub fn with_mllp_config(mut self, config: MllpConfig) -> Self {
         self.mllp_config = config;
@@ -188,7 +198,10 @@ pub struct PoolStats {
 
 /// Connection pool for MLLP clients
 ///
-/// Manages a pool of connections to a single MLLP server endpoint.
+/// Manages a pool of connections to a single MLLP server endpoint. Idle
+/// connections are closed by a background task every
+/// [`PoolConfig::cleanup_interval`] once they have been unused for longer
+/// than [`PoolConfig::idle_timeout`]; the task stops when the pool is dropped.
 pub struct MllpPool {
     address: String,
     config: PoolConfig,
@@ -225,6 +238,7 @@ impl MllpPool {
 
         // Pre-create minimum connections
         pool.initialize_connections().await?;
+        pool.spawn_cleanup_task();
 
         Ok(pool)
     }
@@ -259,6 +273,7 @@ impl MllpPool {
         };
 
         pool.initialize_connections().await?;
+        pool.spawn_cleanup_task();
 
         Ok(pool)
     }
@@ -269,20 +284,47 @@ impl MllpPool {
             let client = self.create_connection().await?;
             let conn = PooledConnection::new(client);
 
-            let mut connections = self.connections.lock().await;
-            connections.push_back(conn);
+            self.lock_connections().push_back(conn);
 
-            let mut stats = self.stats.lock().await;
+            let mut stats = self.lock_stats();
             stats.connections_created += 1;
             stats.idle_connections += 1;
         }
         Ok(())
     }
 
+    /// Periodically close idle and expired connections
+    fn spawn_cleanup_task(&self) {
+        let connections = Arc::downgrade(&self.connections);
+        let stats = Arc::downgrade(&self.stats);
+        let config = self.config.clone();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(config.cleanup_interval);
+            // The first tick completes immediately
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+                if !remove_stale_connections(&connections, &stats, &config) {
+                    break;
+                }
+            }
+        });
+    }
+
+    fn lock_connections(&self) -> MutexGuard<'_, VecDeque<PooledConnection>> {
+        self.connections.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn lock_stats(&self) -> MutexGuard<'_, PoolStats> {
+        self.stats.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     /// Create a new connection
     async fn create_connection(&self) -> Result<MllpClient> {
         #[cfg(feature = "tls")]
-        if let (Some(ref tls_config), Some(ref server_name)) =
+        if let (Some(tls_config), Some(server_name)) =
             (&self.tls_config, &self.server_name)
         {
             return MllpClient::connect_tls_with_config(
@@ -298,7 +340,35 @@ impl MllpPool {
     }
 
     /// Acquire a connection from the pool
-    async fn acquire(&self) -> Result<PooledConnection> {
+    ///
+    /// Waits up to [`PoolConfig::acquire_timeout`] when all
+    /// `max_connections` connections are in use. The connection is returned
+    /// to the pool when the guard is dropped, unless an exchange through the
+    /// guard failed, in which case it is closed instead.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rs7_mllp::MllpPool;
+    /// use rs7_core::message::Message;
+    ///
+    /// # async fn example(pool: MllpPool, message: Message) -> rs7_core::error::Result<()> {
+    /// let mut conn = pool.acquire().await?;
+    /// let ack = conn.send_message(&message).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn acquire(&self) -> Result<PooledConnectionGuard<'_>> {
+        let conn = self.acquire_connection().await?;
+        Ok(PooledConnectionGuard {
+            pool: self,
+            conn: Some(conn),
+            broken: false,
+        })
+    }
+
+    /// Take an idle connection or open a new one
+    async fn acquire_connection(&self) -> Result<PooledConnection> {
         // Try to acquire a permit (limits concurrent connections)
         let permit = tokio::time::timeout(
             self.config.acquire_timeout,
@@ -307,10 +377,7 @@ impl MllpPool {
         .await
         .map_err(|_| {
             // Update timeout stats
-            let mut stats = self.stats.try_lock().ok();
-            if let Some(ref mut s) = stats {
-                s.timeouts += 1;
-            }
+            self.lock_stats().timeouts += 1;
             Error::Network(format!(
                 "Connection pool acquire timeout after {:?}",
                 self.config.acquire_timeout
@@ -319,23 +386,19 @@ impl MllpPool {
         .map_err(|e| Error::Network(format!("Semaphore closed: {}", e)))?;
 
         // Track that we're waiting
-        {
-            let mut stats = self.stats.lock().await;
-            stats.waits += 1;
-        }
+        self.lock_stats().waits += 1;
 
         // Try to get an existing connection
         loop {
-            let mut connections = self.connections.lock().await;
+            let conn = self.lock_connections().pop_front();
 
-            if let Some(mut conn) = connections.pop_front() {
+            if let Some(mut conn) = conn {
                 // Check if connection is still valid
                 if conn.is_expired(&self.config) || conn.is_idle(&self.config) {
                     // Connection expired, close it and try again
-                    drop(connections);
                     drop(conn.client.close().await);
 
-                    let mut stats = self.stats.lock().await;
+                    let mut stats = self.lock_stats();
                     stats.connections_closed += 1;
                     stats.idle_connections = stats.idle_connections.saturating_sub(1);
 
@@ -345,7 +408,7 @@ impl MllpPool {
                 // Good connection found
                 conn.touch();
 
-                let mut stats = self.stats.lock().await;
+                let mut stats = self.lock_stats();
                 stats.acquires += 1;
                 stats.idle_connections = stats.idle_connections.saturating_sub(1);
                 stats.active_connections += 1;
@@ -353,18 +416,16 @@ impl MllpPool {
                 self.active_count.fetch_add(1, Ordering::SeqCst);
 
                 // Forget the permit - we'll release it when the connection is returned
-                std::mem::forget(permit);
+                permit.forget();
 
                 return Ok(conn);
             } else {
                 // No idle connections, create a new one
-                drop(connections);
-
                 let client = self.create_connection().await?;
                 let mut conn = PooledConnection::new(client);
                 conn.touch();
 
-                let mut stats = self.stats.lock().await;
+                let mut stats = self.lock_stats();
                 stats.connections_created += 1;
                 stats.acquires += 1;
                 stats.active_connections += 1;
@@ -372,7 +433,7 @@ impl MllpPool {
                 self.active_count.fetch_add(1, Ordering::SeqCst);
 
                 // Forget the permit - we'll release it when the connection is returned
-                std::mem::forget(permit);
+                permit.forget();
 
                 return Ok(conn);
             }
@@ -380,25 +441,24 @@ impl MllpPool {
     }
 
     /// Return a connection to the pool
-    async fn release(&self, mut conn: PooledConnection) {
+    fn release(&self, mut conn: PooledConnection) {
         self.active_count.fetch_sub(1, Ordering::SeqCst);
 
         // Check if we should keep this connection
         if conn.is_expired(&self.config) {
-            // Close expired connection
-            drop(conn.client.close().await);
+            // Dropping the client closes the socket
+            drop(conn);
 
-            let mut stats = self.stats.lock().await;
+            let mut stats = self.lock_stats();
             stats.connections_closed += 1;
             stats.active_connections = stats.active_connections.saturating_sub(1);
         } else {
             // Return to pool
             conn.touch();
 
-            let mut connections = self.connections.lock().await;
-            let mut stats = self.stats.lock().await;
+            self.lock_connections().push_back(conn);
 
-            connections.push_back(conn);
+            let mut stats = self.lock_stats();
             stats.recycles += 1;
             stats.active_connections = stats.active_connections.saturating_sub(1);
             stats.idle_connections += 1;
@@ -408,40 +468,31 @@ impl MllpPool {
         self.semaphore.add_permits(1);
     }
 
+    /// Drop a connection that failed instead of returning it to the pool
+    fn discard(&self, conn: PooledConnection) {
+        drop(conn);
+
+        self.active_count.fetch_sub(1, Ordering::SeqCst);
+        self.semaphore.add_permits(1);
+
+        let mut stats = self.lock_stats();
+        stats.connections_closed += 1;
+        stats.active_connections = stats.active_connections.saturating_sub(1);
+    }
+
     /// Send a message using a pooled connection
     ///
     /// This method acquires a connection from the pool, sends the message,
-    /// and returns the connection to the pool.
+    /// and returns the connection to the pool. A connection whose exchange
+    /// fails is closed rather than returned.
     pub async fn send(&self, message: &Message) -> Result<Message> {
         let mut conn = self.acquire().await?;
-
-        let result = conn.client.send_message(message).await;
-
-        match result {
-            Ok(ack) => {
-                // Success - return connection to pool
-                self.release(conn).await;
-                Ok(ack)
-            }
-            Err(e) => {
-                // Error - close the connection and don't return to pool
-                drop(conn.client.close().await);
-
-                self.active_count.fetch_sub(1, Ordering::SeqCst);
-                self.semaphore.add_permits(1);
-
-                let mut stats = self.stats.lock().await;
-                stats.connections_closed += 1;
-                stats.active_connections = stats.active_connections.saturating_sub(1);
-
-                Err(e)
-            }
-        }
+        conn.send_message(message).await
     }
 
     /// Get current pool statistics
     pub async fn stats(&self) -> PoolStats {
-        self.stats.lock().await.clone()
+        self.lock_stats().clone()
     }
 
     /// Get the number of currently active (in-use) connections
@@ -451,58 +502,45 @@ impl MllpPool {
 
     /// Get the number of idle connections
     pub async fn idle_connections(&self) -> usize {
-        self.connections.lock().await.len()
+        self.lock_connections().len()
     }
 
     /// Close all connections and clear the pool
     pub async fn close(&self) {
-        let mut connections = self.connections.lock().await;
+        let idle: Vec<_> = self.lock_connections().drain(..).collect();
 
-        while let Some(conn) = connections.pop_front() {
+        for conn in idle {
             drop(conn.client.close().await);
         }
 
-        let mut stats = self.stats.lock().await;
+        let mut stats = self.lock_stats();
         stats.connections_closed += stats.idle_connections;
         stats.idle_connections = 0;
     }
 
     /// Remove idle and expired connections
     pub async fn cleanup(&self) {
-        let mut connections = self.connections.lock().await;
-        let mut stats = self.stats.lock().await;
-
-        let initial_count = connections.len();
-        connections.retain(|conn| {
-            if conn.is_expired(&self.config) || conn.is_idle(&self.config) {
-                false
-            } else {
-                true
-            }
-        });
-
-        let removed = initial_count - connections.len();
-        stats.connections_closed += removed;
-        stats.idle_connections = connections.len();
+        remove_stale_connections(
+            &Arc::downgrade(&self.connections),
+            &Arc::downgrade(&self.stats),
+            &self.config,
+        );
     }
 
     /// Ensure minimum connections are maintained
     pub async fn maintain_min_connections(&self) -> Result<()> {
-        let connections = self.connections.lock().await;
-        let current_count = connections.len();
+        let current_count = self.lock_connections().len();
 
         if current_count < self.config.min_connections {
             let needed = self.config.min_connections - current_count;
-            drop(connections);
 
             for _ in 0..needed {
                 match self.create_connection().await {
                     Ok(client) => {
                         let conn = PooledConnection::new(client);
-                        let mut connections = self.connections.lock().await;
-                        connections.push_back(conn);
+                        self.lock_connections().push_back(conn);
 
-                        let mut stats = self.stats.lock().await;
+                        let mut stats = self.lock_stats();
                         stats.connections_created += 1;
                         stats.idle_connections += 1;
                     }
@@ -518,29 +556,87 @@ impl MllpPool {
     }
 }
 
+/// Close idle and expired connections, returning `false` once the pool is gone
+fn remove_stale_connections(
+    connections: &Weak<Mutex<VecDeque<PooledConnection>>>,
+    stats: &Weak<Mutex<PoolStats>>,
+    config: &PoolConfig,
+) -> bool {
+    let (Some(connections), Some(stats)) = (connections.upgrade(), stats.upgrade()) else {
+        return false;
+    };
+    let mut connections = connections.lock().unwrap_or_else(|e| e.into_inner());
+    let mut stats = stats.lock().unwrap_or_else(|e| e.into_inner());
+
+    let initial_count = connections.len();
+    connections.retain(|conn| !(conn.is_expired(config) || conn.is_idle(config)));
+
+    let removed = initial_count - connections.len();
+    stats.connections_closed += removed;
+    stats.idle_connections = connections.len();
+    true
+}
+
 /// A guard that automatically returns the connection to the pool when dropped
+///
+/// If an exchange made through [`send_message`](Self::send_message) or
+/// [`receive_message`](Self::receive_message) fails, the connection is
+/// considered dead and is closed on drop instead of being reused.
 pub struct PooledConnectionGuard<'a> {
-    #[allow(dead_code)]
     pool: &'a MllpPool,
     conn: Option<PooledConnection>,
+    broken: bool,
 }
 
 impl<'a> PooledConnectionGuard<'a> {
     /// Get mutable access to the underlying client
+    ///
+    /// Errors from calls made directly on the client are not tracked; call
+    /// [`discard`](Self::discard) if the connection should not be reused.
     pub fn client(&mut self) -> &mut MllpClient {
         &mut self.conn.as_mut().unwrap().client
     }
+
+    /// Send a message and wait for the acknowledgment
+    ///
+    /// If the call fails or its future is dropped before the acknowledgment
+    /// is read, the connection is discarded rather than returned to the pool.
+    pub async fn send_message(&mut self, message: &Message) -> Result<Message> {
+        let was_broken = std::mem::replace(&mut self.broken, true);
+        let result = self.client().send_message(message).await;
+        if result.is_ok() {
+            self.broken = was_broken;
+        }
+        result
+    }
+
+    /// Receive a message from the server
+    ///
+    /// Like [`send_message`](Self::send_message), a failed or cancelled
+    /// receive discards the connection.
+    pub async fn receive_message(&mut self) -> Result<Message> {
+        let was_broken = std::mem::replace(&mut self.broken, true);
+        let result = self.client().receive_message().await;
+        if result.is_ok() {
+            self.broken = was_broken;
+        }
+        result
+    }
+
+    /// Close the connection instead of returning it to the pool
+    pub fn discard(mut self) {
+        self.broken = true;
+    }
 }
 
 impl<'a> Drop for PooledConnectionGuard<'a> {
     fn drop(&mut self) {
-        if let Some(_conn) = self.conn.take() {
-            // Note: In a full implementation, we'd use channels or other async-safe mechanisms
-            // to return the connection to the pool. Since drop can't be async, we'd need
-            // to use something like:
-            // - A channel to send the connection back
-            // - Arc<Mutex> for the pool reference
-            // For now, the connection is simply dropped/closed when the guard is dropped.
+        if let Some(conn) = self.conn.take() {
+            if self.broken {
+                self.pool.discard(conn);
+            } else {
+                self.pool.release(conn);
+            }
         }
     }
 }
@@ -583,6 +679,108 @@ mod tests {
         assert_eq!(stats.active_connections, 0);
     }
 
+    async fn spawn_echo_server() -> String {
+        let server = crate::MllpServer::bind("127.0.0.1:0").await.unwrap();
+        let addr = server.local_addr().unwrap().to_string();
+        tokio::spawn(server.serve(Ok));
+        addr
+    }
+
+    fn test_message() -> Message {
+        rs7_parser::parse_message("MSH|^~\\&|APP|FAC|||20240101||ADT^A01|1|P|2.5").unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_acquire_returns_connection_on_drop() {
+        let addr = spawn_echo_server().await;
+        let pool = MllpPool::with_config(&addr, PoolConfig::new().with_min_connections(0))
+            .await
+            .unwrap();
+
+        {
+            let mut conn = pool.acquire().await.unwrap();
+            conn.send_message(&test_message()).await.unwrap();
+            assert_eq!(pool.active_connections(), 1);
+        }
+        assert_eq!(pool.active_connections(), 0);
+        assert_eq!(pool.idle_connections().await, 1);
+
+        // The idle connection is reused rather than a new one being opened
+        pool.send(&test_message()).await.unwrap();
+        let stats = pool.stats().await;
+        assert_eq!(stats.connections_created, 1);
+        assert_eq!(stats.recycles, 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_connection_is_discarded() {
+        let server = crate::MllpServer::bind("127.0.0.1:0").await.unwrap();
+        let addr = server.local_addr().unwrap().to_string();
+        // Accept connections and close them without answering
+        tokio::spawn(async move {
+            while let Ok(conn) = server.accept().await {
+                drop(conn);
+            }
+        });
+
+        let pool = MllpPool::with_config(&addr, PoolConfig::new().with_min_connections(0))
+            .await
+            .unwrap();
+        assert!(pool.send(&test_message()).await.is_err());
+
+        assert_eq!(pool.active_connections(), 0);
+        assert_eq!(pool.idle_connections().await, 0);
+        assert_eq!(pool.stats().await.connections_closed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_send_is_discarded() {
+        let server = crate::MllpServer::bind("127.0.0.1:0").await.unwrap();
+        let addr = server.local_addr().unwrap().to_string();
+        // Answer each message only after a delay
+        tokio::spawn(async move {
+            while let Ok(mut conn) = server.accept().await {
+                tokio::spawn(async move {
+                    while let Ok(message) = conn.receive_message().await {
+                        tokio::time::sleep(Duration::from_millis(200)).await;
+                        if conn.send_message(&message).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+
+        let pool = MllpPool::with_config(&addr, PoolConfig::new().with_min_connections(0))
+            .await
+            .unwrap();
+        {
+            let message = test_message();
+            let mut conn = pool.acquire().await.unwrap();
+            let send = conn.send_message(&message);
+            assert!(tokio::time::timeout(Duration::from_millis(20), send).await.is_err());
+        }
+
+        // The ACK is still in flight, so the connection must not be reused
+        assert_eq!(pool.idle_connections().await, 0);
+        assert_eq!(pool.stats().await.connections_closed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_idle_connections_are_closed() {
+        let addr = spawn_echo_server().await;
+        let config = PoolConfig::new()
+            .with_min_connections(1)
+            .with_idle_timeout(Duration::from_millis(20))
+            .with_cleanup_interval(Duration::from_millis(10));
+        let pool = MllpPool::with_config(&addr, config).await.unwrap();
+        assert_eq!(pool.idle_connections().await, 1);
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(pool.idle_connections().await, 0);
+        assert_eq!(pool.stats().await.connections_closed, 1);
+    }
+
     #[tokio::test]
     async fn test_pooled_connection_expiry() {
         let config = PoolConfig::new()
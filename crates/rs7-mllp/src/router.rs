@@ -11,6 +11,7 @@
 //! - Wildcard matching for trigger events
 //! - Default handler for unmatched messages
 //! - Sync handlers with automatic ACK generation
+//! - Enhanced acknowledgment mode (MSH-15/MSH-16)
 //!
 //! # Examples
 //!
@@ -41,6 +42,27 @@
 //! });
 //! ```
 //!
+//! ## Enhanced Acknowledgment Mode
+//!
+//! When a sender values MSH-15 or MSH-16, [`MessageRouter::commit_ack`]
+//! gives the commit ACK (CA) if MSH-15 requests one, and
+//! [`MessageRouter::handle`] only returns the application ACK if MSH-16
+//! requests it for the outcome. Without either field, the original mode
+//! applies: no commit ACK and an application ACK for every message.
+//!
+//! ```rust
+//! use rs7_mllp::router::{MessageRouter, RouteResult};
+//! use rs7_parser::parse_message;
+//!
+//! let mut router = MessageRouter::new();
+//! router.route("ADT", "*", |_msg| RouteResult::Ack);
+//!
+//! // Commit ACK always, application ACK only on error
+//! let msg = parse_message("MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5|||AL|ER").unwrap();
+//! assert!(router.commit_ack(&msg).is_some());
+//! assert!(router.handle(&msg).is_none());
+//! ```
+//!
 //! ## With MLLP Server
 //!
 //! ```rust,no_run
@@ -58,6 +80,9 @@
 //!
 //!     tokio::spawn(async move {
 //!         if let Ok(msg) = conn.receive_message().await {
+//!             if let Some(commit) = router.commit_ack(&msg) {
+//!                 let _ = conn.send_message(&commit).await;
+//!             }
 //!             if let Some(response) = router.handle(&msg) {
 //!                 let _ = conn.send_message(&response).await;
 //!             }
@@ -69,7 +94,7 @@
 //! ```
 
 use rs7_core::{
-    builders::ack::AckBuilder,
+    builders::ack::{AckBuilder, CommitAckBuilder},
     Message,
 };
 use std::sync::Arc;
@@ -233,7 +258,9 @@ impl MessageRouter {
     /// Handle an incoming message
     ///
     /// Routes the message to the appropriate handler and generates
-    /// an acknowledgment response.
+    /// an acknowledgment response. In enhanced mode, the acknowledgment is
+    /// only returned if MSH-16 requests one for the handler's outcome;
+    /// custom responses are always returned.
     ///
     /// # Returns
     ///
@@ -262,10 +289,35 @@ impl MessageRouter {
             return None;
         };
 
+        // In enhanced mode, MSH-16 decides whether an application ACK is sent
+        if message.is_enhanced_mode() {
+            let success = match result {
+                RouteResult::Ack | RouteResult::AckWithMessage(_) => Some(true),
+                RouteResult::Error(_) | RouteResult::ErrorWithCode(..) | RouteResult::Reject(_) => Some(false),
+                RouteResult::Custom(_) | RouteResult::NoResponse => None,
+            };
+            if success.is_some_and(|success| !message.application_ack_type().should_acknowledge(success)) {
+                return None;
+            }
+        }
+
         // Generate response
         self.generate_response(message, result)
     }
 
+    /// Generate the commit acknowledgment for an accepted message
+    ///
+    /// Returns a CA acknowledgment if the message is in enhanced mode and
+    /// MSH-15 requests a commit ACK on success (`AL` or `SU`), and `None`
+    /// otherwise. Send it once the message is safely received, before the
+    /// application ACK from [`handle`](Self::handle).
+    pub fn commit_ack(&self, message: &Message) -> Option<Message> {
+        if !message.is_enhanced_mode() || !message.accept_ack_type().should_acknowledge(true) {
+            return None;
+        }
+        CommitAckBuilder::for_message(message).commit_accept().build().ok()
+    }
+
     /// Find a matching route for the given message type and trigger
     fn find_route(&self, message_type: &str, trigger_event: &str) -> Option<&SyncHandler> {
         for route in &self.routes {
@@ -610,6 +662,35 @@ mod tests {
         assert_eq!(ack.segment("MSA").unwrap().get_field_value(1), Some("AE"));
     }
 
+    #[test]
+    fn test_enhanced_mode() {
+        let mut router = MessageRouter::new();
+        router.route("ADT", "A01", |_msg| RouteResult::Ack);
+        router.route("ADT", "A08", |_msg| RouteResult::Error("Failed".to_string()));
+
+        // Original mode: no commit ACK, application ACK always
+        let msg = create_test_message("ADT", "A01");
+        assert!(router.commit_ack(&msg).is_none());
+        assert!(router.handle(&msg).is_some());
+
+        // Enhanced mode: commit ACK always, application ACK only on error
+        let mut msg = create_test_message("ADT", "A01");
+        let msh = msg.get_segment_mut(0).unwrap();
+        msh.set_field_value(15, "AL").unwrap();
+        msh.set_field_value(16, "ER").unwrap();
+        let commit = router.commit_ack(&msg).unwrap();
+        assert_eq!(commit.segment("MSA").unwrap().get_field_value(1), Some("CA"));
+        assert!(router.handle(&msg).is_none());
+
+        let mut error = create_test_message("ADT", "A08");
+        let msh = error.get_segment_mut(0).unwrap();
+        msh.set_field_value(15, "NE").unwrap();
+        msh.set_field_value(16, "ER").unwrap();
+        assert!(router.commit_ack(&error).is_none());
+        let ack = router.handle(&error).unwrap();
+        assert_eq!(ack.segment("MSA").unwrap().get_field_value(1), Some("AE"));
+    }
+
     #[test]
     fn test_ack_with_message() {
         let mut router = MessageRouter::new();
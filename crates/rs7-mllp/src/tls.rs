@@ -19,6 +19,10 @@ use rustls::pki_types::CertificateDer;
 use rustls::{ClientConfig, RootCertStore, ServerConfig};
 #[cfg(feature = "tls")]
 use rustls_pemfile::{certs, private_key};
+#[cfg(feature = "tls")]
+use x509_parser::asn1_rs::Tag;
+#[cfg(feature = "tls")]
+use x509_parser::prelude::{AttributeTypeAndValue, FromDer, GeneralName, X509Certificate};
 
 /// TLS configuration for MLLP client connections
 #[cfg(feature = "tls")]
@@ -299,6 +303,64 @@ impl TlsServerConfig {
     }
 }
 
+/// Get the subject common name (CN) of a DER-encoded X.509 certificate
+///
+/// If the subject has several CNs, the last one (the most specific in the
+/// usual RDN order) is returned. Subject alternative names are not
+/// considered; see [`certificate_dns_names`]. Returns `None` if the
+/// certificate cannot be parsed or its subject has no CN.
+#[cfg(feature = "tls")]
+pub fn certificate_common_name(cert: &CertificateDer<'_>) -> Option<String> {
+    let (_, certificate) = X509Certificate::from_der(cert.as_ref()).ok()?;
+    certificate.subject().iter_common_name().filter_map(directory_string).last()
+}
+
+/// Get the DNS subject alternative names of a DER-encoded X.509 certificate
+///
+/// These are the names TLS hostname checks match against, in certificate
+/// order. Returns an empty list if the certificate cannot be parsed or has
+/// no DNS subject alternative names.
+#[cfg(feature = "tls")]
+pub fn certificate_dns_names(cert: &CertificateDer<'_>) -> Vec<String> {
+    let Ok((_, certificate)) = X509Certificate::from_der(cert.as_ref()) else {
+        return Vec::new();
+    };
+    let Ok(Some(san)) = certificate.subject_alternative_name() else {
+        return Vec::new();
+    };
+
+    san.value
+        .general_names
+        .iter()
+        .filter_map(|name| match name {
+            GeneralName::DNSName(dns_name) => Some(dns_name.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Decode a directory string attribute value
+#[cfg(feature = "tls")]
+fn directory_string(attribute: &AttributeTypeAndValue<'_>) -> Option<String> {
+    if let Ok(value) = attribute.as_str() {
+        return Some(value.to_string());
+    }
+    let value = attribute.as_slice();
+    match attribute.attr_value().tag() {
+        // Treated as Latin-1
+        Tag::TeletexString => Some(value.iter().map(|&b| b as char).collect()),
+        // UTF-16BE
+        Tag::BmpString => {
+            let units: Vec<u16> = value
+                .chunks_exact(2)
+                .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                .collect();
+            String::from_utf16(&units).ok()
+        }
+        _ => None,
+    }
+}
+
 #[cfg(feature = "tls")]
 #[cfg(test)]
 mod tests {
@@ -310,6 +372,74 @@ mod tests {
         assert!(config.is_ok());
     }
 
+    /// Wrap `contents` in a DER element with the given tag
+    fn der(tag: u8, contents: &[u8]) -> Vec<u8> {
+        let mut out = vec![tag];
+        if contents.len() < 0x80 {
+            out.push(contents.len() as u8);
+        } else {
+            out.extend([0x82, (contents.len() >> 8) as u8, contents.len() as u8]);
+        }
+        out.extend_from_slice(contents);
+        out
+    }
+
+    fn name(attributes: &[(&[u8], u8, &[u8])]) -> Vec<u8> {
+        let rdns: Vec<u8> = attributes
+            .iter()
+            .flat_map(|(oid, tag, value)| {
+                let attribute = [der(0x06, oid), der(*tag, value)].concat();
+                der(0x31, &der(0x30, &attribute))
+            })
+            .collect();
+        der(0x30, &rdns)
+    }
+
+    #[test]
+    fn test_certificate_common_name() {
+        const CN: &[u8] = &[0x55, 0x04, 0x03];
+        const O: &[u8] = &[0x55, 0x04, 0x0A];
+
+        let algorithm = der(0x30, &der(0x06, &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x02]));
+        let cert_with = |subject: Vec<u8>, extensions: Vec<u8>| {
+            let tbs = [
+                der(0xA0, &der(0x02, &[2])),
+                der(0x02, &[0x01; 16]),
+                algorithm.clone(),
+                name(&[(CN, 0x13, b"Test CA")]),
+                der(0x30, &[der(0x17, b"250101000000Z"), der(0x17, b"260101000000Z")].concat()),
+                subject,
+                der(0x30, &[algorithm.clone(), der(0x03, &[0, 0x04])].concat()),
+                extensions,
+            ]
+            .concat();
+            let certificate = [der(0x30, &tbs), algorithm.clone(), der(0x03, &[0])].concat();
+            CertificateDer::from(der(0x30, &certificate))
+        };
+        let cert = |subject: Vec<u8>| cert_with(subject, Vec::new());
+
+        let partner = cert(name(&[(O, 0x0C, b"Partner A"), (CN, 0x0C, b"partner-a.example")]));
+        assert_eq!(certificate_common_name(&partner).as_deref(), Some("partner-a.example"));
+
+        let bmp = cert(name(&[(CN, 0x1E, &[0x00, b'c', 0x00, b'n'])]));
+        assert_eq!(certificate_common_name(&bmp).as_deref(), Some("cn"));
+
+        let no_cn = cert(name(&[(O, 0x0C, b"Partner A")]));
+        assert_eq!(certificate_common_name(&no_cn), None);
+
+        // subjectAltName (2.5.29.17) with an email and a DNS name
+        let general_names = [der(0x81, b"ops@partner-a.example"), der(0x82, b"mllp.partner-a.example")].concat();
+        let san = der(0x30, &[der(0x06, &[0x55, 0x1D, 0x11]), der(0x04, &der(0x30, &general_names))].concat());
+        let with_san = cert_with(name(&[(CN, 0x0C, b"Partner A")]), der(0xA3, &der(0x30, &san)));
+        assert_eq!(certificate_common_name(&with_san).as_deref(), Some("Partner A"));
+        assert_eq!(certificate_dns_names(&with_san), vec!["mllp.partner-a.example".to_string()]);
+        assert!(certificate_dns_names(&partner).is_empty());
+
+        let garbage = CertificateDer::from(vec![0x30, 0x05, 0x30]);
+        assert_eq!(certificate_common_name(&garbage), None);
+        assert!(certificate_dns_names(&garbage).is_empty());
+    }
+
     // Note: Additional tests would require test certificates
     // These should be added in integration tests
 }
@@ -8,7 +8,7 @@ use rs7_core::{Field, Message, Segment};
 use rs7_mllp::{
     testing::MockMllpServer,
     tls::{TlsClientConfig, TlsServerConfig},
-    MllpClient,
+    MllpClient, MllpServer,
 };
 
 mod test_certs;
@@ -332,3 +332,87 @@ async fn test_tls_concurrent_connections() {
     server.shutdown().await.expect("Failed to shutdown server");
     certs.cleanup();
 }
+
+#[tokio::test]
+async fn test_mtls_peer_common_name() {
+    let certs = test_certs::generate_test_certs_with_client().await;
+
+    let server_config = TlsServerConfig::with_mtls(
+        &certs.server_cert_path,
+        &certs.server_key_path,
+        &certs.ca_cert_path,
+    )
+    .expect("Failed to create mTLS server config");
+    let server = MllpServer::bind_tls("127.0.0.1:0", server_config)
+        .await
+        .expect("Failed to bind mTLS server");
+    let addr = server.local_addr().expect("Failed to get local addr").to_string();
+
+    let accepted = tokio::spawn(async move {
+        let mut conn = server.accept().await.expect("Failed to accept");
+        let common_name = conn.peer_common_name();
+        let chain_len = conn.peer_certificates().map(|chain| chain.len());
+        let message = conn.receive_message().await.expect("Failed to receive");
+        conn.send_message(&message).await.expect("Failed to respond");
+        (common_name, chain_len)
+    });
+
+    let client_config = TlsClientConfig::with_mtls(
+        &certs.ca_cert_path,
+        &certs.client_cert_path,
+        &certs.client_key_path,
+    )
+    .expect("Failed to create mTLS client config");
+    let mut client = MllpClient::connect_tls(&addr, "localhost", client_config)
+        .await
+        .expect("Failed to connect with mTLS");
+
+    let mut msg = Message::default();
+    let mut msh = Segment::new("MSH");
+    msh.fields.push(Field::from_value("|"));
+    msh.fields.push(Field::from_value("^~\\&"));
+    msg.segments.push(msh);
+    client.send_message(&msg).await.expect("Failed to send message");
+
+    let (common_name, chain_len) = accepted.await.expect("Server task panicked");
+    assert_eq!(common_name.as_deref(), Some("test-client"));
+    assert_eq!(chain_len, Some(1));
+
+    client.close().await.expect("Failed to close client");
+    certs.cleanup();
+}
+
+#[tokio::test]
+async fn test_tls_stalled_handshake_does_not_block_accept() {
+    let certs = test_certs::generate_test_certs().await;
+
+    let server_config = TlsServerConfig::new(&certs.server_cert_path, &certs.server_key_path)
+        .expect("Failed to create TLS server config");
+    let config = rs7_mllp::MllpConfig::new().with_connect_timeout(std::time::Duration::from_millis(500));
+    let server = MllpServer::bind_tls_with_config("127.0.0.1:0", server_config, config)
+        .await
+        .expect("Failed to bind TLS server");
+    let addr = server.local_addr().unwrap().to_string();
+    tokio::spawn(server.serve(Ok));
+
+    // A peer that connects but never starts the handshake
+    let _stalled = tokio::net::TcpStream::connect(&addr).await.unwrap();
+
+    let client_config = TlsClientConfig::with_ca_cert(&certs.ca_cert_path)
+        .expect("Failed to create TLS client config");
+    let exchange = async {
+        let mut client = MllpClient::connect_tls(&addr, "localhost", client_config).await?;
+        let mut msg = Message::default();
+        let mut msh = Segment::new("MSH");
+        msh.fields.push(Field::from_value("|"));
+        msh.fields.push(Field::from_value("^~\\&"));
+        msg.segments.push(msh);
+        client.send_message(&msg).await
+    };
+    let response = tokio::time::timeout(std::time::Duration::from_secs(5), exchange)
+        .await
+        .expect("Accept loop blocked by stalled handshake");
+    assert!(response.is_ok());
+
+    certs.cleanup();
+}
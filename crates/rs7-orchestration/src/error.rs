@@ -26,6 +26,10 @@ pub enum OrchestrationError {
     #[error("Retry limit exceeded after {0} attempts")]
     RetryLimitExceeded(usize),
 
+    /// Message was sent to the dead letter queue after a step failed
+    #[error("Message dead-lettered after step '{0}' failed: {1}")]
+    DeadLettered(String, String),
+
     /// Dead letter queue error
     #[error("Dead letter queue error: {0}")]
     DeadLetterQueueError(String),
@@ -51,6 +55,11 @@ impl OrchestrationError {
         OrchestrationError::StepExecutionFailed(step_name.into(), msg.into())
     }
 
+    /// Create a dead-lettered error
+    pub fn dead_lettered(step_name: impl Into<String>, msg: impl Into<String>) -> Self {
+        OrchestrationError::DeadLettered(step_name.into(), msg.into())
+    }
+
     /// Create a filter error
     pub fn filter_failed(filter_name: impl Into<String>, msg: impl Into<String>) -> Self {
         OrchestrationError::FilterFailed(filter_name.into(), msg.into())
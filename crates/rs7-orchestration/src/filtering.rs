@@ -60,6 +60,33 @@ impl Filter {
     }
 }
 
+/// Result of evaluating a message against a [`MessageFilter`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterOutcome {
+    /// The message passed the filter
+    Accepted,
+    /// The message was rejected
+    Rejected {
+        /// Name of the first rule, in registration order, that the message failed
+        rule_name: String,
+    },
+}
+
+impl FilterOutcome {
+    /// Check if the message was accepted
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, FilterOutcome::Accepted)
+    }
+
+    /// Name of the rule that rejected the message, if any
+    pub fn rejected_by(&self) -> Option<&str> {
+        match self {
+            FilterOutcome::Accepted => None,
+            FilterOutcome::Rejected { rule_name } => Some(rule_name),
+        }
+    }
+}
+
 /// Message filter that applies multiple filter rules
 ///
 /// All rules must pass for the message to be accepted.
@@ -131,18 +158,65 @@ impl MessageFilter {
         }
     }
 
+    /// Evaluate a message, reporting which rule rejected it
+    ///
+    /// Rules are checked in registration order. In [`FilterMode::All`] the
+    /// first failing rule is reported and the rest are skipped. In
+    /// [`FilterMode::Any`] a message is only rejected when every rule fails,
+    /// and the first rule is reported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs7_orchestration::filtering::{FilterOutcome, MessageFilter};
+    /// use rs7_core::Message;
+    ///
+    /// let mut filter = MessageFilter::new();
+    /// filter.add_rule("has_segments", |msg| !msg.segments.is_empty());
+    ///
+    /// let outcome = filter.evaluate(&Message::default());
+    /// assert_eq!(outcome.rejected_by(), Some("has_segments"));
+    /// ```
+    pub fn evaluate(&self, message: &Message) -> FilterOutcome {
+        let first_failure = match self.mode {
+            FilterMode::All => self.filters.iter().find(|filter| !filter.matches(message)),
+            FilterMode::Any => {
+                if self.filters.iter().any(|filter| filter.matches(message)) {
+                    None
+                } else {
+                    self.filters.first()
+                }
+            }
+        };
+
+        match first_failure {
+            Some(filter) => FilterOutcome::Rejected {
+                rule_name: filter.name.clone(),
+            },
+            None => FilterOutcome::Accepted,
+        }
+    }
+
+    /// Get the names of every rule the message fails, in registration order
+    ///
+    /// Unlike [`MessageFilter::evaluate`] this checks all rules, which is
+    /// useful for audit logs. It ignores the filter mode, so in
+    /// [`FilterMode::Any`] an accepted message can still fail some rules.
+    pub fn failing_rules(&self, message: &Message) -> Vec<&str> {
+        self.filters
+            .iter()
+            .filter(|filter| !filter.matches(message))
+            .map(|filter| filter.name.as_str())
+            .collect()
+    }
+
     /// Filter a message, returning Ok if it passes all filters
     pub fn filter(&self, message: &Message) -> Result<()> {
         if self.matches(message) {
             Ok(())
         } else {
             // Find which filter(s) failed for better error message
-            let failed_filters: Vec<&str> = self
-                .filters
-                .iter()
-                .filter(|f| !f.matches(message))
-                .map(|f| f.name.as_str())
-                .collect();
+            let failed_filters = self.failing_rules(message);
 
             if !failed_filters.is_empty() {
                 Err(OrchestrationError::filter_failed(
@@ -282,6 +356,44 @@ mod tests {
         assert!(filter.filter(&test_oru).is_err());
     }
 
+    #[test]
+    fn test_filter_evaluate_outcome() {
+        let mut filter = MessageFilter::new();
+        filter.add_rule("production_only", |msg| {
+            use rs7_terser::Terser;
+            Terser::new(msg).get("MSH-11").ok().flatten() == Some("P")
+        });
+        filter.add_rule("adt_only", |msg| {
+            use rs7_terser::Terser;
+            Terser::new(msg)
+                .get("MSH-9")
+                .ok()
+                .flatten()
+                .map(|v| v.starts_with("ADT"))
+                .unwrap_or(false)
+        });
+
+        let prod_adt = create_production_adt_message();
+        let test_oru = create_test_oru_message();
+
+        assert_eq!(filter.evaluate(&prod_adt), FilterOutcome::Accepted);
+        assert!(filter.evaluate(&prod_adt).is_accepted());
+        assert_eq!(
+            filter.evaluate(&test_oru),
+            FilterOutcome::Rejected {
+                rule_name: "production_only".to_string()
+            }
+        );
+        assert_eq!(filter.failing_rules(&test_oru), vec!["production_only", "adt_only"]);
+        assert!(filter.failing_rules(&prod_adt).is_empty());
+
+        let mut any = MessageFilter::new_any();
+        assert!(any.evaluate(&test_oru).is_accepted());
+        any.add_rule("never", |_| false);
+        any.add_rule("also_never", |_| false);
+        assert_eq!(any.evaluate(&test_oru).rejected_by(), Some("never"));
+    }
+
     #[test]
     fn test_filter_count() {
         let mut filter = MessageFilter::new();
@@ -43,6 +43,7 @@ use rs7_core::Message;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Type alias for orchestration step functions
 pub type StepHandler =
@@ -80,7 +81,16 @@ impl OrchestrationStep {
 
     /// Execute the step
     pub async fn execute(&self, message: Message) -> Result<Message> {
-        if let Some(retry_config) = &self.retry_config {
+        self.execute_with_fallback(message, None).await
+    }
+
+    /// Execute the step, retrying with `fallback` if it has no retry configuration of its own
+    async fn execute_with_fallback(
+        &self,
+        message: Message,
+        fallback: Option<&RetryConfig>,
+    ) -> Result<Message> {
+        if let Some(retry_config) = self.retry_config.as_ref().or(fallback) {
             self.execute_with_retry(message, retry_config).await
         } else {
             (self.handler)(message).await
@@ -106,8 +116,7 @@ impl OrchestrationStep {
                 Err(e) => {
                     last_error = Some(e);
                     if attempts < config.max_attempts {
-                        let delay = config.delay_ms * attempts as u64;
-                        tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
+                        tokio::time::sleep(config.delay_for(attempts)).await;
                     }
                 }
             }
@@ -124,6 +133,16 @@ impl OrchestrationStep {
     }
 }
 
+/// How the delay between retries grows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backoff {
+    /// `delay_ms * attempt` (100, 200, 300, ...)
+    #[default]
+    Linear,
+    /// `delay_ms * 2^(attempt - 1)` (100, 200, 400, ...)
+    Exponential,
+}
+
 /// Retry configuration for orchestration steps
 #[derive(Debug, Clone)]
 pub struct RetryConfig {
@@ -131,39 +150,57 @@ pub struct RetryConfig {
     pub max_attempts: usize,
     /// Base delay between retries in milliseconds
     pub delay_ms: u64,
+    /// How the delay grows with each failed attempt
+    pub backoff: Backoff,
 }
 
 impl RetryConfig {
-    /// Create a new retry configuration
+    /// Create a new retry configuration with linear backoff
     pub fn new(max_attempts: usize, delay_ms: u64) -> Self {
         Self {
             max_attempts,
             delay_ms,
+            backoff: Backoff::Linear,
         }
     }
 
+    /// Create a retry configuration with exponential backoff
+    pub fn exponential(max_attempts: usize, delay_ms: u64) -> Self {
+        Self::new(max_attempts, delay_ms).with_backoff(Backoff::Exponential)
+    }
+
+    /// Set the backoff strategy
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Delay to wait after the given failed attempt (1-based)
+    pub fn delay_for(&self, attempt: usize) -> Duration {
+        let attempt = attempt.max(1);
+        let delay = match self.backoff {
+            Backoff::Linear => self.delay_ms.saturating_mul(attempt as u64),
+            Backoff::Exponential => {
+                let factor = 1u64.checked_shl((attempt - 1) as u32).unwrap_or(u64::MAX);
+                self.delay_ms.saturating_mul(factor)
+            }
+        };
+        Duration::from_millis(delay)
+    }
+
     /// No retries (default)
     pub fn none() -> Self {
-        Self {
-            max_attempts: 1,
-            delay_ms: 0,
-        }
+        Self::new(1, 0)
     }
 
     /// Standard retry (3 attempts, 100ms delay)
     pub fn standard() -> Self {
-        Self {
-            max_attempts: 3,
-            delay_ms: 100,
-        }
+        Self::new(3, 100)
     }
 
     /// Aggressive retry (5 attempts, 50ms delay)
     pub fn aggressive() -> Self {
-        Self {
-            max_attempts: 5,
-            delay_ms: 50,
-        }
+        Self::new(5, 50)
     }
 }
 
@@ -177,6 +214,8 @@ impl Default for RetryConfig {
 pub struct MessageOrchestrator {
     steps: Vec<OrchestrationStep>,
     error_handler: Option<ErrorHandler>,
+    retry_config: Option<RetryConfig>,
+    dead_letter: Option<DeadLetterSink>,
 }
 
 /// Type alias for error handler functions
@@ -186,15 +225,44 @@ pub type ErrorHandler = Arc<
         + Sync,
 >;
 
+/// Type alias for dead letter sinks
+///
+/// Receives the message as it was passed to [`MessageOrchestrator::execute`],
+/// before any step ran, along with the error.
+pub type DeadLetterSink = Arc<dyn Fn(Message, OrchestrationError) + Send + Sync>;
+
 impl MessageOrchestrator {
     /// Create a new message orchestrator
     pub fn new() -> Self {
         Self {
             steps: Vec::new(),
             error_handler: None,
+            retry_config: None,
+            dead_letter: None,
         }
     }
 
+    /// Retry failing steps with this configuration
+    ///
+    /// Applies to every step without its own retry configuration
+    /// (see [`MessageOrchestrator::add_step_with_retry`]).
+    pub fn with_retry(mut self, config: RetryConfig) -> Self {
+        self.retry_config = Some(config);
+        self
+    }
+
+    /// Send messages whose step failed after all retries to a dead letter sink
+    ///
+    /// [`MessageOrchestrator::execute`] then returns
+    /// [`OrchestrationError::DeadLettered`] for those messages.
+    pub fn with_dead_letter<S>(mut self, sink: S) -> Self
+    where
+        S: Fn(Message, OrchestrationError) + Send + Sync + 'static,
+    {
+        self.dead_letter = Some(Arc::new(sink));
+        self
+    }
+
     /// Add a step to the orchestration workflow
     pub fn add_step<H, F>(&mut self, name: impl Into<String>, handler: H) -> &mut Self
     where
@@ -236,9 +304,19 @@ impl MessageOrchestrator {
     }
 
     /// Execute the orchestration workflow
+    ///
+    /// If a step still fails after its retries and a dead letter sink is
+    /// configured, the original input message is handed to the sink and
+    /// [`OrchestrationError::DeadLettered`] is returned.
     pub async fn execute(&self, mut message: Message) -> Result<Message> {
+        // Earlier steps may have transformed the message; dead letters get the input
+        let original = self.dead_letter.as_ref().map(|_| message.clone());
+
         for step in &self.steps {
-            match step.execute(message.clone()).await {
+            match step
+                .execute_with_fallback(message.clone(), self.retry_config.as_ref())
+                .await
+            {
                 Ok(result) => {
                     message = result;
                 }
@@ -248,7 +326,14 @@ impl MessageOrchestrator {
                         handler(step.name.clone(), e.clone(), message.clone()).await;
                     }
 
-                    return Err(OrchestrationError::step_failed(&step.name, e.to_string()));
+                    let error = OrchestrationError::step_failed(&step.name, e.to_string());
+
+                    if let (Some(sink), Some(original)) = (&self.dead_letter, original) {
+                        sink(original, error);
+                        return Err(OrchestrationError::dead_lettered(&step.name, e.to_string()));
+                    }
+
+                    return Err(error);
                 }
             }
         }
@@ -261,7 +346,10 @@ impl MessageOrchestrator {
         let mut errors = Vec::new();
 
         for step in &self.steps {
-            match step.execute(message.clone()).await {
+            match step
+                .execute_with_fallback(message.clone(), self.retry_config.as_ref())
+                .await
+            {
                 Ok(result) => {
                     message = result;
                 }
@@ -410,6 +498,58 @@ mod tests {
         assert_eq!(counter.load(Ordering::SeqCst), 3); // Should have tried 3 times
     }
 
+    #[test]
+    fn test_retry_backoff() {
+        let linear = RetryConfig::new(4, 100);
+        assert_eq!(linear.delay_for(1), Duration::from_millis(100));
+        assert_eq!(linear.delay_for(3), Duration::from_millis(300));
+
+        let exponential = RetryConfig::exponential(4, 100);
+        assert_eq!(exponential.backoff, Backoff::Exponential);
+        assert_eq!(exponential.delay_for(1), Duration::from_millis(100));
+        assert_eq!(exponential.delay_for(3), Duration::from_millis(400));
+        assert_eq!(exponential.delay_for(100), Duration::from_millis(u64::MAX));
+    }
+
+    #[tokio::test]
+    async fn test_orchestrator_retry_and_dead_letter() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let attempts_clone = attempts.clone();
+        let dead_letters = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let dead_letters_clone = dead_letters.clone();
+
+        let mut orchestrator = MessageOrchestrator::new()
+            .with_retry(RetryConfig::exponential(3, 1))
+            .with_dead_letter(move |msg: Message, err| {
+                dead_letters_clone.lock().unwrap().push((msg, err.to_string()));
+            });
+
+        orchestrator.add_step("enrich", |mut msg: Message| async move {
+            msg.add_segment(Segment::new("ZZZ"));
+            Ok(msg)
+        });
+        orchestrator.add_step("failing_step", move |_msg| {
+            let attempts = attempts_clone.clone();
+            async move {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err(OrchestrationError::custom("Downstream unavailable"))
+            }
+        });
+
+        let result = orchestrator.execute(create_test_message()).await;
+
+        assert!(matches!(
+            result,
+            Err(OrchestrationError::DeadLettered(ref step, _)) if step == "failing_step"
+        ));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+        let dead_letters = dead_letters.lock().unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].0.segments.len(), 1);
+        assert!(dead_letters[0].1.contains("Failed after 3 attempts"));
+    }
+
     #[tokio::test]
     async fn test_error_handler() {
         let error_count = Arc::new(AtomicUsize::new(0));
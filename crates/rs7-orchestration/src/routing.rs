@@ -44,6 +44,7 @@
 
 use crate::error::{OrchestrationError, Result};
 use rs7_core::Message;
+use rs7_terser::Terser;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -91,6 +92,89 @@ impl ContentRoute {
     }
 }
 
+/// A field comparison used by declarative routes
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldCondition {
+    /// Terser path of the field to compare (e.g. "MSH-9-1")
+    pub path: String,
+    /// Value the field must equal
+    pub value: String,
+}
+
+/// Declarative route definition built from Terser field conditions
+///
+/// All conditions must match, and evaluation stops at the first one that
+/// does not. The definition is plain data, so routing tables can be stored
+/// and loaded (with the `serde` feature) before handlers are attached.
+///
+/// ## Example
+///
+/// ```rust
+/// use rs7_orchestration::routing::{ContentRouter, RouteBuilder};
+///
+/// let mut router = ContentRouter::new();
+/// router.add_content_route(
+///     RouteBuilder::new("adt_production")
+///         .when_equals("MSH-9-1", "ADT")
+///         .and_equals("MSH-11", "P")
+///         .handler(|msg| async move { Ok(msg) }),
+/// );
+/// assert_eq!(router.route_count(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RouteBuilder {
+    /// Route name
+    pub name: String,
+    /// Conditions that must all match
+    pub conditions: Vec<FieldCondition>,
+}
+
+impl RouteBuilder {
+    /// Create a route definition with no conditions (matches every message)
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            conditions: Vec::new(),
+        }
+    }
+
+    /// Require the value at `path` to equal `value`
+    pub fn when_equals(mut self, path: impl Into<String>, value: impl Into<String>) -> Self {
+        self.conditions.push(FieldCondition {
+            path: path.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    /// Add another required condition; reads naturally after [`RouteBuilder::when_equals`]
+    pub fn and_equals(self, path: impl Into<String>, value: impl Into<String>) -> Self {
+        self.when_equals(path, value)
+    }
+
+    /// Check if every condition matches the given message
+    ///
+    /// Missing fields and invalid paths count as non-matching.
+    pub fn matches(&self, message: &Message) -> bool {
+        let terser = Terser::new(message);
+        self.conditions.iter().all(|condition| {
+            terser.get(&condition.path).ok().flatten() == Some(condition.value.as_str())
+        })
+    }
+
+    /// Attach a handler, producing a route for [`ContentRouter::add_content_route`]
+    pub fn handler<H, F>(self, handler: H) -> ContentRoute
+    where
+        H: Fn(Message) -> F + Send + Sync + 'static,
+        F: Future<Output = Result<Message>> + Send + 'static,
+    {
+        let name = self.name.clone();
+        ContentRoute::new(name, move |msg: &Message| self.matches(msg), handler)
+    }
+}
+
 /// Content-based message router
 ///
 /// Routes messages to different handlers based on content predicates.
@@ -118,6 +202,11 @@ impl ContentRouter {
         self.routes.push(ContentRoute::new(name, condition, handler));
     }
 
+    /// Add a prebuilt route, such as one produced by [`RouteBuilder::handler`]
+    pub fn add_content_route(&mut self, route: ContentRoute) {
+        self.routes.push(route);
+    }
+
     /// Set a default handler for messages that don't match any route
     pub fn set_default_handler<H, F>(&mut self, handler: H)
     where
@@ -326,6 +415,32 @@ mod tests {
         assert_eq!(router.route_count(), 0);
     }
 
+    #[tokio::test]
+    async fn test_route_builder_conditions() {
+        let hl7 = "MSH|^~\\&|APP|FAC|||20231101120000||ADT^A01|MSG001|P|2.5\rPID|1";
+        let prod_adt = rs7_parser::parse_message(hl7).unwrap();
+        let test_adt = rs7_parser::parse_message(&hl7.replace("|P|", "|T|")).unwrap();
+
+        let definition = RouteBuilder::new("adt_production")
+            .when_equals("MSH-9-1", "ADT")
+            .and_equals("MSH-11", "P");
+        assert_eq!(definition.conditions.len(), 2);
+        assert!(definition.matches(&prod_adt));
+        assert!(!definition.matches(&test_adt));
+        assert!(!RouteBuilder::new("bad").when_equals("BAD PATH", "").matches(&prod_adt));
+        assert!(RouteBuilder::new("any").matches(&test_adt));
+
+        let mut router = ContentRouter::new();
+        router.add_content_route(definition.handler(|msg| async move { Ok(msg) }));
+        router.set_default_handler(|_| async move { Err(OrchestrationError::custom("unmatched")) });
+
+        assert!(router.route(prod_adt).await.is_ok());
+        assert!(matches!(
+            router.route(test_adt).await,
+            Err(OrchestrationError::Custom(msg)) if msg == "unmatched"
+        ));
+    }
+
     #[tokio::test]
     async fn test_route_all() {
         let mut router = ContentRouter::new();
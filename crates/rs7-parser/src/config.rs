@@ -121,8 +121,124 @@ pub struct ParserConfig {
     /// When false, any 3-character ID is accepted
     /// Default: false
     pub validate_segment_ids: bool,
+
+    /// Skip segments that fail to parse instead of aborting
+    ///
+    /// When true, a malformed segment is dropped from the message and
+    /// reported as an [`WarningCode::InvalidSegment`] warning. The MSH
+    /// segment is always strictly required.
+    /// Default: false (strict)
+    pub skip_invalid_segments: bool,
+
+    /// Allow a segment ID that is not followed by the field separator
+    ///
+    /// When true, "PID" or "PID 1" is kept as a segment with no fields and a
+    /// [`WarningCode::MissingFieldSeparator`] warning is recorded.
+    /// Default: false (strict)
+    pub allow_missing_field_separator: bool,
+
+    /// Keep each field's original text, available through `Field::raw`
+    ///
+    /// Useful for re-parsing fields from senders that nest delimiters in
+    /// non-standard ways. Costs roughly a second copy of the message.
+    /// Default: false
+    pub preserve_raw_fields: bool,
+
+    /// Structural limits that abort parsing when exceeded
+    ///
+    /// Unlike `max_segments`/`max_repetitions`, which truncate, exceeding a
+    /// limit is always an error, and it is detected while splitting, before
+    /// the oversized structure is allocated.
+    /// Default: None (unlimited)
+    pub limits: Option<ParseLimits>,
+}
+
+/// Structural limits for parsing untrusted input
+///
+/// A byte cap alone does not bound memory: a few kilobytes of `~~~~` expand
+/// into thousands of repetitions, each with its own allocations. Parsing
+/// fails as soon as any count goes over its limit.
+///
+/// # Example
+///
+/// ```rust
+/// use rs7_parser::{parse_message_with_limits, ParseLimits};
+///
+/// let limits = ParseLimits::default().max_repetitions_per_field(3);
+/// let input = "MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5\rPID|||1~2~3~4";
+/// let err = parse_message_with_limits(input, &limits).unwrap_err();
+/// assert!(err.to_string().contains("more than 3 repetitions"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// Maximum segments per message (default: 100,000)
+    pub max_segments: usize,
+    /// Maximum fields per segment (default: 1,000)
+    pub max_fields_per_segment: usize,
+    /// Maximum repetitions per field (default: 10,000)
+    pub max_repetitions_per_field: usize,
+    /// Maximum components per repetition, and subcomponents per component
+    /// (default: 1,000)
+    pub max_components: usize,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        Self {
+            max_segments: 100_000,
+            max_fields_per_segment: 1_000,
+            max_repetitions_per_field: 10_000,
+            max_components: 1_000,
+        }
+    }
+}
+
+impl ParseLimits {
+    /// Create limits with the default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits that never trigger
+    pub fn unlimited() -> Self {
+        Self {
+            max_segments: usize::MAX,
+            max_fields_per_segment: usize::MAX,
+            max_repetitions_per_field: usize::MAX,
+            max_components: usize::MAX,
+        }
+    }
+
+    /// Set the maximum segments per message
+    pub fn max_segments(mut self, max: usize) -> Self {
+        self.max_segments = max;
+        self
+    }
+
+    /// Set the maximum fields per segment
+    pub fn max_fields_per_segment(mut self, max: usize) -> Self {
+        self.max_fields_per_segment = max;
+        self
+    }
+
+    /// Set the maximum repetitions per field
+    pub fn max_repetitions_per_field(mut self, max: usize) -> Self {
+        self.max_repetitions_per_field = max;
+        self
+    }
+
+    /// Set the maximum components per repetition and subcomponents per component
+    pub fn max_components(mut self, max: usize) -> Self {
+        self.max_components = max;
+        self
+    }
 }
 
+/// Options for [`parse_message_with_options`](crate::parse_message_with_options)
+///
+/// This is the same type as [`ParserConfig`].
+pub type ParseOptions = ParserConfig;
+
 impl Default for ParserConfig {
     fn default() -> Self {
         Self::strict()
@@ -153,6 +269,10 @@ impl ParserConfig {
             max_segments: 0,
             continue_on_error: false,
             validate_segment_ids: false,
+            skip_invalid_segments: false,
+            allow_missing_field_separator: false,
+            preserve_raw_fields: false,
+            limits: None,
         }
     }
 
@@ -161,6 +281,13 @@ impl ParserConfig {
     /// This configuration tolerates common real-world deviations from
     /// the HL7 specification. Use this for parsing messages from systems
     /// known to produce non-compliant output.
+    ///
+    /// The segment recovery options, [`skip_invalid_segments`] and
+    /// [`allow_missing_field_separator`], stay off and must be enabled
+    /// explicitly.
+    ///
+    /// [`skip_invalid_segments`]: Self::skip_invalid_segments()
+    /// [`allow_missing_field_separator`]: Self::allow_missing_field_separator()
     pub fn lenient() -> Self {
         Self {
             allow_trailing_delimiters: true,
@@ -176,6 +303,10 @@ impl ParserConfig {
             max_segments: 0,
             continue_on_error: true,
             validate_segment_ids: false,
+            skip_invalid_segments: false,
+            allow_missing_field_separator: false,
+            preserve_raw_fields: false,
+            limits: None,
         }
     }
 
@@ -256,12 +387,39 @@ impl ParserConfig {
         self.validate_segment_ids = validate;
         self
     }
+
+    /// Set whether to skip segments that fail to parse
+    pub fn skip_invalid_segments(mut self, skip: bool) -> Self {
+        self.skip_invalid_segments = skip;
+        self
+    }
+
+    /// Set whether to allow a missing field separator after the segment ID
+    pub fn allow_missing_field_separator(mut self, allow: bool) -> Self {
+        self.allow_missing_field_separator = allow;
+        self
+    }
+
+    /// Set whether to keep each field's original text
+    pub fn preserve_raw_fields(mut self, preserve: bool) -> Self {
+        self.preserve_raw_fields = preserve;
+        self
+    }
+
+    /// Set structural limits that abort parsing when exceeded
+    pub fn limits(mut self, limits: ParseLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
 }
 
 /// Warning generated during lenient parsing
 #[derive(Debug, Clone)]
 pub struct ParseWarning {
     /// Line or segment index where warning occurred
+    ///
+    /// [`WarningCode::MissingFieldSeparator`] reports the byte offset in the
+    /// input instead.
     pub location: usize,
     /// Warning message
     pub message: String,
@@ -290,6 +448,12 @@ pub enum WarningCode {
     RepetitionsTruncated,
     /// Unknown segment ID
     UnknownSegmentId,
+    /// Segment failed to parse and was skipped
+    InvalidSegment,
+    /// Segment ID was not followed by a field separator
+    ///
+    /// The warning's location is the byte offset of the missing separator.
+    MissingFieldSeparator,
 }
 
 impl std::fmt::Display for WarningCode {
@@ -304,6 +468,8 @@ impl std::fmt::Display for WarningCode {
             WarningCode::FieldTruncated => write!(f, "FIELD_TRUNCATED"),
             WarningCode::RepetitionsTruncated => write!(f, "REPETITIONS_TRUNCATED"),
             WarningCode::UnknownSegmentId => write!(f, "UNKNOWN_SEGMENT_ID"),
+            WarningCode::InvalidSegment => write!(f, "INVALID_SEGMENT"),
+            WarningCode::MissingFieldSeparator => write!(f, "MISSING_FIELD_SEPARATOR"),
         }
     }
 }
@@ -377,6 +543,8 @@ mod tests {
         assert!(config.allow_non_standard_encoding_chars);
         assert!(config.preserve_invalid_escapes);
         assert!(config.continue_on_error);
+        assert!(!config.skip_invalid_segments);
+        assert!(!config.allow_missing_field_separator);
     }
 
     #[test]
@@ -14,27 +14,107 @@
 mod config;
 mod optimized;
 pub mod streaming;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
-pub use config::{ParserConfig, ParseResult, ParseWarning, WarningCode};
-pub use streaming::{StreamingParser, StreamingMessageBuilder, SegmentEvent, SegmentHandler, parse_streaming, process_with_handler};
+pub use config::{ParseLimits, ParseOptions, ParserConfig, ParseResult, ParseWarning, WarningCode};
+pub use streaming::{FileStreamReader, StreamingParser, StreamingMessageBuilder, SegmentEvent, SegmentHandler, parse_streaming, process_with_handler};
 
 // nom parser combinators (for future enhancements)
 use rs7_core::{
     batch::{Batch, BatchHeader, BatchTrailer, File, FileHeader, FileTrailer},
+    charset::Charset,
     delimiters::Delimiters,
     encoding::Encoding,
-    error::{Error, Result},
+    error::{Error, ErrorLocation, Result},
     field::{Component, Field, Repetition, SubComponent},
-    message::Message,
+    message::{Message, SegmentTerminator},
     segment::Segment,
+    types::Timestamp,
 };
-use chrono::NaiveDateTime;
+use chrono::{DateTime, FixedOffset, NaiveDateTime};
 
 /// Parse a complete HL7 message with default strict configuration
 pub fn parse_message(input: &str) -> Result<Message> {
     parse_message_with_config(input, &ParserConfig::strict()).map(|r| r.value)
 }
 
+/// Parse a complete HL7 message, failing if it exceeds structural limits
+///
+/// Use this for untrusted input: a message that passes a byte cap can still
+/// expand into huge numbers of segments, fields or repetitions. Otherwise
+/// parsing is the same as [`parse_message`].
+///
+/// # Example
+///
+/// ```rust
+/// use rs7_parser::{parse_message_with_limits, ParseLimits};
+///
+/// let input = "MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5\rPID|1||123";
+/// let message = parse_message_with_limits(input, &ParseLimits::default()).unwrap();
+/// assert_eq!(message.segments.len(), 2);
+///
+/// let tight = ParseLimits::default().max_segments(1);
+/// assert!(parse_message_with_limits(input, &tight).is_err());
+/// ```
+pub fn parse_message_with_limits(input: &str, limits: &ParseLimits) -> Result<Message> {
+    parse_message_with_config(input, &ParserConfig::strict().limits(*limits)).map(|r| r.value)
+}
+
+/// Parse a complete HL7 message from bytes, decoding them per MSH-18
+///
+/// The character set is read from the first repetition of MSH-18; an empty
+/// or missing MSH-18 means UTF-8. Byte sequences that are invalid in that
+/// character set are an error rather than being replaced.
+///
+/// # Example
+///
+/// ```rust
+/// use rs7_parser::parse_message_bytes;
+///
+/// let bytes = b"MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5||||||8859/1\rPID|||||M\xFCller";
+/// let message = parse_message_bytes(bytes).unwrap();
+/// assert_eq!(message.segment("PID").unwrap().get_field_value(5), Some("Müller"));
+/// ```
+pub fn parse_message_bytes(bytes: &[u8]) -> Result<Message> {
+    let charset = detect_charset(bytes)?;
+    parse_message(&charset.decode(bytes)?)
+}
+
+/// Read the character set declared in MSH-18 of raw message bytes
+///
+/// MSH is ASCII in every supported character set, so the header can be
+/// split before the message is decoded.
+fn detect_charset(bytes: &[u8]) -> Result<Charset> {
+    let header_end = bytes
+        .iter()
+        .position(|&b| b == b'\r' || b == b'\n')
+        .unwrap_or(bytes.len());
+    let header = &bytes[..header_end];
+
+    if header.len() < 8 || !header.starts_with(b"MSH") {
+        return Err(Error::parse("Message must start with an MSH segment"));
+    }
+    let field_separator = header[3];
+    let repetition_separator = header[5];
+
+    // MSH-1 is the separator itself, so split part n is MSH-(n + 1)
+    let Some(msh18) = header.split(|&b| b == field_separator).nth(17) else {
+        return Ok(Charset::Utf8);
+    };
+    let code = msh18
+        .split(|&b| b == repetition_separator)
+        .next()
+        .unwrap_or_default();
+    if code.iter().all(|b| b.is_ascii_whitespace()) {
+        return Ok(Charset::Utf8);
+    }
+
+    let code = String::from_utf8_lossy(code);
+    Charset::from_hl7(&code)
+        .ok_or_else(|| Error::Decoding(format!("Unsupported character set in MSH-18: {}", code)))
+}
+
 /// Parse a complete HL7 message with custom configuration
 ///
 /// This function allows specifying parsing options for handling non-compliant
@@ -60,6 +140,10 @@ pub fn parse_message_with_config(input: &str, config: &ParserConfig) -> Result<P
     // Note: ParseWarning and WarningCode are available in config module for future warning support
 
     let mut warnings = Vec::new();
+    let original = input;
+
+    // Remember the sender's segment terminator so encoding reproduces it
+    let terminator = SegmentTerminator::detect(input).unwrap_or_default();
 
     // Handle whitespace based on config
     let input = if config.strip_trailing_whitespace {
@@ -77,19 +161,17 @@ pub fn parse_message_with_config(input: &str, config: &ParserConfig) -> Result<P
     let input = input.trim_matches(|c| c == '\r' || c == '\n');
 
     // Extract delimiters from MSH segment
-    let delimiters = extract_delimiters_with_config(input, config)?;
-
-    // Split message into segments (by \r or \n)
-    let mut segment_strings: Vec<&str> = input
+    let delimiters = extract_delimiters_with_config(input, config)
+        .map_err(|e| {
+            let segment_id = input.starts_with("MSH").then_some("MSH");
+            locate_error(e, 0, segment_id, byte_offset(original, input))
+        })?;
+
+    // Split message into segments (by \r or \n), applying config-based filtering
+    let segments = input
         .split('\r')
         .flat_map(|s| s.split('\n'))
-        .collect();
-
-    // Apply config-based filtering
-    segment_strings = segment_strings
-        .into_iter()
-        .enumerate()
-        .filter_map(|(_idx, s)| {
+        .filter_map(|s| {
             let s = if config.strip_trailing_whitespace {
                 s.trim_end()
             } else {
@@ -110,8 +192,8 @@ pub fn parse_message_with_config(input: &str, config: &ParserConfig) -> Result<P
             } else {
                 Some(s)
             }
-        })
-        .collect();
+        });
+    let mut segment_strings = collect_within(segments, max_limit(config).max_segments, "Message", "segments")?;
 
     if segment_strings.is_empty() {
         return Err(Error::parse("Empty message"));
@@ -129,8 +211,7 @@ pub fn parse_message_with_config(input: &str, config: &ParserConfig) -> Result<P
         segment_strings.truncate(config.max_segments);
     }
 
-    let mut message = Message::with_delimiters(delimiters);
-    let mut errors = Vec::new();
+    let mut message = Message::with_delimiters(delimiters).with_segment_terminator(terminator);
 
     for (idx, seg_str) in segment_strings.iter().enumerate() {
         // Handle trailing delimiters
@@ -144,20 +225,33 @@ pub fn parse_message_with_config(input: &str, config: &ParserConfig) -> Result<P
             parse_msh_segment_with_config(seg_str, &delimiters, config)
         } else {
             parse_segment_with_config(seg_str, &delimiters, config)
-        };
+        }
+        .map_err(|e| {
+            let segment_id = seg_str.split(delimiters.field_separator).next().filter(|id| !id.is_empty());
+            locate_error(e, idx, segment_id, byte_offset(original, seg_str))
+        });
 
         match segment_result {
             Ok((segment, seg_warnings)) => {
                 message.add_segment(segment);
-                warnings.extend(seg_warnings);
+                let offset = byte_offset(original, seg_str);
+                warnings.extend(seg_warnings.into_iter().map(|mut warning| {
+                    // Reported relative to the segment; rebase onto the input
+                    if warning.code == WarningCode::MissingFieldSeparator {
+                        warning.location += offset;
+                    }
+                    warning
+                }));
             }
-            Err(e) => {
-                if config.continue_on_error {
-                    errors.push((idx, e));
-                } else {
-                    return Err(e);
-                }
+            // The MSH segment is always strictly required
+            Err(e) if idx > 0 && (config.skip_invalid_segments || config.continue_on_error) => {
+                warnings.push(ParseWarning {
+                    location: idx,
+                    message: format!("Skipped invalid segment '{}': {}", seg_str, e),
+                    code: WarningCode::InvalidSegment,
+                });
             }
+            Err(e) => return Err(e),
         }
     }
 
@@ -167,31 +261,119 @@ pub fn parse_message_with_config(input: &str, config: &ParserConfig) -> Result<P
     })
 }
 
+/// Parse a complete HL7 message with lenient segment handling options
+///
+/// Segments that fail to parse are reported as [`ParseWarning`]s on the
+/// returned [`ParseResult`] when [`ParseOptions::skip_invalid_segments`] is
+/// set, rather than aborting the whole message. The MSH segment is always
+/// strictly required.
+///
+/// # Example
+///
+/// ```rust
+/// use rs7_parser::{parse_message_with_options, ParseOptions, WarningCode};
+///
+/// let options = ParseOptions::new().skip_invalid_segments(true);
+/// let input = "MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5\rZ|bad\rPID|1||123";
+///
+/// let parsed = parse_message_with_options(input, &options).unwrap();
+/// assert_eq!(parsed.value.segments.len(), 2);
+/// assert_eq!(parsed.warnings[0].code, WarningCode::InvalidSegment);
+/// ```
+pub fn parse_message_with_options(input: &str, options: &ParseOptions) -> Result<ParseResult<Message>> {
+    parse_message_with_config(input, options)
+}
+
+/// Read the encoding characters of an MSH/BHS/FHS header
+///
+/// These are the characters between the first and second field separators:
+/// four before HL7 v2.7, optionally five from v2.7 (truncation character).
+fn header_encoding_characters(input: &str, field_sep: char) -> String {
+    input
+        .chars()
+        .skip(4)
+        .take_while(|&c| c != field_sep && c != '\r' && c != '\n')
+        .take(5)
+        .collect()
+}
+
+/// Byte offset of the third field of an MSH/BHS/FHS header ("MSH|^~\&|".len())
+fn header_field_start(delimiters: &Delimiters) -> usize {
+    4 + delimiters.encoding_characters().len() + 1
+}
+
+/// Byte offset of `inner`, a subslice of `outer`, within `outer`
+fn byte_offset(outer: &str, inner: &str) -> usize {
+    inner.as_ptr() as usize - outer.as_ptr() as usize
+}
+
+/// Attach the segment position and byte offset to a parse error
+///
+/// `segment_index` is the 0-based position of the segment in the message and
+/// `offset` its byte offset in the input. Offsets already present on the
+/// error are taken as relative to the segment start.
+fn locate_error(error: Error, segment_index: usize, segment_id: Option<&str>, offset: usize) -> Error {
+    let (message, mut location) = match error {
+        Error::Parse(message) => (message, ErrorLocation::new()),
+        Error::ParseWithContext { message, location } => (message, location),
+        other => return other,
+    };
+    if location.segment.is_none() {
+        location.segment = segment_id.map(String::from);
+    }
+    location.segment_index.get_or_insert(segment_index);
+    location.byte_offset = Some(offset + location.byte_offset.unwrap_or(0));
+    Error::parse_at(message, location)
+}
+
+/// Location of an error `offset` bytes into the input
+fn at_offset(offset: usize) -> ErrorLocation {
+    ErrorLocation {
+        byte_offset: Some(offset),
+        ..ErrorLocation::new()
+    }
+}
+
 /// Extract delimiters with configuration options
 fn extract_delimiters_with_config(input: &str, config: &ParserConfig) -> Result<Delimiters> {
     if !input.starts_with("MSH") {
-        return Err(Error::parse("Message must start with MSH segment"));
+        return Err(Error::parse_at(
+            "Message must start with MSH segment",
+            at_offset(0),
+        ));
     }
 
     if input.len() < 8 {
-        return Err(Error::parse("MSH segment too short"));
+        return Err(Error::parse_at(
+            "MSH segment too short",
+            at_offset(input.len()),
+        ));
     }
 
     let field_sep = input.chars().nth(3).ok_or_else(|| {
-        Error::parse("Cannot extract field separator")
+        Error::parse_at("Cannot extract field separator", at_offset(3))
     })?;
 
-    let encoding_chars: String = input.chars().skip(4).take(4).collect();
+    let encoding_chars = header_encoding_characters(input, field_sep);
 
     // In lenient mode, allow non-standard encoding characters
-    if encoding_chars.len() < 4 && !config.allow_non_standard_encoding_chars {
-        return Err(Error::parse(format!(
-            "Invalid encoding characters (expected 4, got {})",
-            encoding_chars.len()
-        )));
-    }
-
-    Delimiters::from_encoding_characters(field_sep, &encoding_chars)
+    if encoding_chars.chars().count() < 4 && !config.allow_non_standard_encoding_chars {
+        return Err(Error::parse_at(
+            format!(
+                "Invalid encoding characters (expected 4, got {})",
+                encoding_chars.chars().count()
+            ),
+            at_offset(4),
+        ));
+    }
+
+    Delimiters::from_encoding_characters(field_sep, &encoding_chars).map_err(|e| match e {
+        Error::InvalidDelimiters(message) => Error::parse_at(
+            format!("Invalid delimiters: {}", message),
+            at_offset(3),
+        ),
+        other => other,
+    })
 }
 
 /// Parse MSH segment with configuration
@@ -208,20 +390,27 @@ fn parse_msh_segment_with_config(
 
     let mut segment = Segment::new("MSH");
 
-    // Add MSH-1 (field separator)
-    segment.add_field(Field::from_value(delimiters.field_separator.to_string()));
+    // MSH-1 (field separator) and MSH-2 (encoding characters)
+    let mut msh1 = Field::from_value(delimiters.field_separator.to_string());
+    let mut msh2 = Field::from_value(delimiters.encoding_characters());
 
-    // Add MSH-2 (encoding characters)
-    segment.add_field(Field::from_value(delimiters.encoding_characters()));
+    if config.preserve_raw_fields {
+        msh1 = msh1.with_raw(delimiters.field_separator.to_string());
+        msh2 = msh2.with_raw(delimiters.encoding_characters());
+    }
+    segment.add_field(msh1);
+    segment.add_field(msh2);
 
     // Parse the rest of the fields
-    let field_start = 9;
+    let field_start = header_field_start(delimiters);
     if input.len() <= field_start {
         return Ok((segment, warnings));
     }
 
+    // MSH-1 and MSH-2 count towards the field limit
+    let max_fields = max_limit(config).max_fields_per_segment.saturating_sub(2);
     let rest = &input[field_start..];
-    let field_strings: Vec<&str> = rest.split(delimiters.field_separator).collect();
+    let field_strings = collect_within(rest.split(delimiters.field_separator), max_fields, "Segment", "fields")?;
 
     for field_str in field_strings {
         let field = parse_field_with_config(field_str, delimiters, config)?;
@@ -282,6 +471,14 @@ fn parse_segment_with_config(
 
     let next_char = input.chars().nth(id_len);
     if next_char != Some(delimiters.field_separator) {
+        if config.allow_missing_field_separator {
+            warnings.push(ParseWarning {
+                location: id_len,
+                message: format!("Missing field separator after segment ID '{}'", segment_id),
+                code: WarningCode::MissingFieldSeparator,
+            });
+            return Ok((segment, warnings));
+        }
         if config.continue_on_error {
             return Ok((segment, warnings));
         }
@@ -292,7 +489,8 @@ fn parse_segment_with_config(
     }
 
     let rest = &input[id_len + 1..];
-    let field_strings: Vec<&str> = rest.split(delimiters.field_separator).collect();
+    let max_fields = max_limit(config).max_fields_per_segment;
+    let field_strings = collect_within(rest.split(delimiters.field_separator), max_fields, "Segment", "fields")?;
 
     for field_str in field_strings {
         let field = parse_field_with_config(field_str, delimiters, config)?;
@@ -305,11 +503,17 @@ fn parse_segment_with_config(
 /// Parse a field with configuration
 fn parse_field_with_config(input: &str, delimiters: &Delimiters, config: &ParserConfig) -> Result<Field> {
     let mut field = Field::new();
+    let limits = max_limit(config);
 
     let repetition_strings: Vec<&str> = if input.is_empty() {
         vec![""]
     } else {
-        input.split(delimiters.repetition_separator).collect()
+        collect_within(
+            input.split(delimiters.repetition_separator),
+            limits.max_repetitions_per_field,
+            "Field",
+            "repetitions",
+        )?
     };
 
     // Apply max repetitions limit
@@ -327,13 +531,45 @@ fn parse_field_with_config(input: &str, delimiters: &Delimiters, config: &Parser
             rep_str
         };
 
-        let repetition = parse_repetition(rep_str, delimiters)?;
+        let repetition = parse_repetition_within(rep_str, delimiters, limits.max_components)?;
         field.add_repetition(repetition);
     }
 
+    if config.preserve_raw_fields {
+        field = field.with_raw(input);
+    }
+
     Ok(field)
 }
 
+/// Limits from `config`, unlimited if none are set
+fn max_limit(config: &ParserConfig) -> ParseLimits {
+    config.limits.unwrap_or_else(ParseLimits::unlimited)
+}
+
+/// Collect `parts`, failing as soon as there are more than `max`
+///
+/// `container` and `what` name the structure for the error message, as in
+/// "Field has more than 3 repetitions".
+fn collect_within<'a>(
+    parts: impl Iterator<Item = &'a str>,
+    max: usize,
+    container: &str,
+    what: &str,
+) -> Result<Vec<&'a str>> {
+    let mut collected = Vec::new();
+    for part in parts {
+        if collected.len() == max {
+            return Err(Error::parse(format!(
+                "{} has more than {} {} (parse limit exceeded)",
+                container, max, what
+            )));
+        }
+        collected.push(part);
+    }
+    Ok(collected)
+}
+
 /// Extract delimiters from MSH segment
 ///
 /// MSH format: MSH|^~\&|...
@@ -353,7 +589,7 @@ fn extract_delimiters(input: &str) -> Result<Delimiters> {
         Error::parse("Cannot extract field separator")
     })?;
 
-    let encoding_chars: String = input.chars().skip(4).take(4).collect();
+    let encoding_chars = header_encoding_characters(input, field_sep);
 
     Delimiters::from_encoding_characters(field_sep, &encoding_chars)
 }
@@ -373,9 +609,9 @@ fn parse_msh_segment(input: &str, delimiters: &Delimiters) -> Result<Segment> {
     // Add MSH-2 (encoding characters - appears after MSH|)
     segment.add_field(Field::from_value(delimiters.encoding_characters()));
 
-    // Parse the rest of the fields starting after "MSH|^~\&"
-    // Note: the character at position 8 is the field separator before MSH-3
-    let field_start = 9; // "MSH|^~\&|".len() - we want to start after the | following encoding chars
+    // Parse the rest of the fields starting after "MSH|^~\&" (or "MSH|^~\&#")
+    // Note: the character after the encoding characters is the field separator before MSH-3
+    let field_start = header_field_start(delimiters);
     if input.len() <= field_start {
         return Ok(segment);
     }
@@ -445,17 +681,23 @@ fn parse_field(input: &str, delimiters: &Delimiters) -> Result<Field> {
 
 /// Parse a repetition (can contain components)
 fn parse_repetition(input: &str, delimiters: &Delimiters) -> Result<Repetition> {
+    parse_repetition_within(input, delimiters, usize::MAX)
+}
+
+/// Parse a repetition with at most `max_components` components and
+/// subcomponents per component
+fn parse_repetition_within(input: &str, delimiters: &Delimiters, max_components: usize) -> Result<Repetition> {
     let mut repetition = Repetition::new();
 
     // Even empty repetitions should have one (empty) component
     let component_strings: Vec<&str> = if input.is_empty() {
         vec![""]
     } else {
-        input.split(delimiters.component_separator).collect()
+        collect_within(input.split(delimiters.component_separator), max_components, "Repetition", "components")?
     };
 
     for comp_str in component_strings {
-        let component = parse_component(comp_str, delimiters)?;
+        let component = parse_component(comp_str, delimiters, max_components)?;
         repetition.add_component(component);
     }
 
@@ -463,14 +705,19 @@ fn parse_repetition(input: &str, delimiters: &Delimiters) -> Result<Repetition>
 }
 
 /// Parse a component (can contain subcomponents)
-fn parse_component(input: &str, delimiters: &Delimiters) -> Result<Component> {
+fn parse_component(input: &str, delimiters: &Delimiters, max_subcomponents: usize) -> Result<Component> {
     let mut component = Component::new();
 
     // Even empty components should have one (empty) subcomponent
     let subcomponent_strings: Vec<&str> = if input.is_empty() {
         vec![""]
     } else {
-        input.split(delimiters.subcomponent_separator).collect()
+        collect_within(
+            input.split(delimiters.subcomponent_separator),
+            max_subcomponents,
+            "Component",
+            "subcomponents",
+        )?
     };
 
     for sub_str in subcomponent_strings {
@@ -633,6 +880,31 @@ pub fn parse_file(input: &str) -> Result<File> {
     Ok(file)
 }
 
+/// Parse an HL7 file or batch export incrementally from a reader
+///
+/// Unlike [`parse_file`], this never loads the whole input into memory: the
+/// returned [`FileStreamReader`] yields one [`Message`] at a time and only keeps
+/// the current message and the FHS/BHS headers. BTS/FTS counts are validated
+/// when each trailer is reached, and an error is returned if the stream ends
+/// before the closing FTS (or BTS, for batch-only input).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::fs::File;
+/// use rs7_parser::parse_file_streaming;
+///
+/// let file = File::open("export.hl7")?;
+/// for message in parse_file_streaming(file) {
+///     let message = message?;
+///     println!("{} segments", message.segments.len());
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn parse_file_streaming<R: std::io::Read>(reader: R) -> FileStreamReader<R> {
+    FileStreamReader::new(reader)
+}
+
 /// Extract delimiters from BHS segment (same format as MSH)
 fn extract_delimiters_from_bhs(input: &str) -> Result<Delimiters> {
     if !input.starts_with("BHS") {
@@ -647,7 +919,7 @@ fn extract_delimiters_from_bhs(input: &str) -> Result<Delimiters> {
         Error::parse("Cannot extract field separator from BHS")
     })?;
 
-    let encoding_chars: String = input.chars().skip(4).take(4).collect();
+    let encoding_chars = header_encoding_characters(input, field_sep);
 
     Delimiters::from_encoding_characters(field_sep, &encoding_chars)
 }
@@ -666,7 +938,7 @@ fn extract_delimiters_from_fhs(input: &str) -> Result<Delimiters> {
         Error::parse("Cannot extract field separator from FHS")
     })?;
 
-    let encoding_chars: String = input.chars().skip(4).take(4).collect();
+    let encoding_chars = header_encoding_characters(input, field_sep);
 
     Delimiters::from_encoding_characters(field_sep, &encoding_chars)
 }
@@ -675,44 +947,46 @@ fn extract_delimiters_from_fhs(input: &str) -> Result<Delimiters> {
 fn parse_fhs_segment(input: &str, delimiters: &Delimiters) -> Result<FileHeader> {
     let segment = parse_segment_like_msh("FHS", input, delimiters)?;
 
-    Ok(FileHeader {
-        field_separator: delimiters.field_separator,
-        encoding_characters: delimiters.encoding_characters(),
-        sending_application: segment.get_field_value(3).map(String::from),
-        sending_facility: segment.get_field_value(4).map(String::from),
-        receiving_application: segment.get_field_value(5).map(String::from),
-        receiving_facility: segment.get_field_value(6).map(String::from),
-        creation_datetime: parse_datetime_field(segment.get_field_value(7)),
-        security: segment.get_field_value(8).map(String::from),
-        file_name_id: segment.get_field_value(9).map(String::from),
-        comment: segment.get_field_value(10).map(String::from),
-        control_id: segment.get_field_value(11).map(String::from),
-        reference_control_id: segment.get_field_value(12).map(String::from),
-        sending_network_address: segment.get_field_value(13).map(String::from),
-        receiving_network_address: segment.get_field_value(14).map(String::from),
-    })
+    let mut header = FileHeader::new();
+    header.field_separator = delimiters.field_separator;
+    header.encoding_characters = delimiters.encoding_characters();
+    header.sending_application = segment.get_field_value(3).map(String::from);
+    header.sending_facility = segment.get_field_value(4).map(String::from);
+    header.receiving_application = segment.get_field_value(5).map(String::from);
+    header.receiving_facility = segment.get_field_value(6).map(String::from);
+    header.creation_datetime = parse_datetime_field(segment.get_field_value(7));
+    header.security = segment.get_field_value(8).map(String::from);
+    header.file_name_id = segment.get_field_value(9).map(String::from);
+    header.comment = segment.get_field_value(10).map(String::from);
+    header.control_id = segment.get_field_value(11).map(String::from);
+    header.reference_control_id = segment.get_field_value(12).map(String::from);
+    header.sending_network_address = segment.get_field_value(13).map(String::from);
+    header.receiving_network_address = segment.get_field_value(14).map(String::from);
+    header.creation_offset = parse_datetime_field_tz(segment.get_field_value(7)).map(|dt| *dt.offset());
+    Ok(header)
 }
 
 /// Parse BHS (Batch Header Segment)
 fn parse_bhs_segment(input: &str, delimiters: &Delimiters) -> Result<BatchHeader> {
     let segment = parse_segment_like_msh("BHS", input, delimiters)?;
 
-    Ok(BatchHeader {
-        field_separator: delimiters.field_separator,
-        encoding_characters: delimiters.encoding_characters(),
-        sending_application: segment.get_field_value(3).map(String::from),
-        sending_facility: segment.get_field_value(4).map(String::from),
-        receiving_application: segment.get_field_value(5).map(String::from),
-        receiving_facility: segment.get_field_value(6).map(String::from),
-        creation_datetime: parse_datetime_field(segment.get_field_value(7)),
-        security: segment.get_field_value(8).map(String::from),
-        batch_name_id_type: segment.get_field_value(9).map(String::from),
-        comment: segment.get_field_value(10).map(String::from),
-        control_id: segment.get_field_value(11).map(String::from),
-        reference_control_id: segment.get_field_value(12).map(String::from),
-        sending_network_address: segment.get_field_value(13).map(String::from),
-        receiving_network_address: segment.get_field_value(14).map(String::from),
-    })
+    let mut header = BatchHeader::new();
+    header.field_separator = delimiters.field_separator;
+    header.encoding_characters = delimiters.encoding_characters();
+    header.sending_application = segment.get_field_value(3).map(String::from);
+    header.sending_facility = segment.get_field_value(4).map(String::from);
+    header.receiving_application = segment.get_field_value(5).map(String::from);
+    header.receiving_facility = segment.get_field_value(6).map(String::from);
+    header.creation_datetime = parse_datetime_field(segment.get_field_value(7));
+    header.security = segment.get_field_value(8).map(String::from);
+    header.batch_name_id_type = segment.get_field_value(9).map(String::from);
+    header.comment = segment.get_field_value(10).map(String::from);
+    header.control_id = segment.get_field_value(11).map(String::from);
+    header.reference_control_id = segment.get_field_value(12).map(String::from);
+    header.sending_network_address = segment.get_field_value(13).map(String::from);
+    header.receiving_network_address = segment.get_field_value(14).map(String::from);
+    header.creation_offset = parse_datetime_field_tz(segment.get_field_value(7)).map(|dt| *dt.offset());
+    Ok(header)
 }
 
 /// Parse FTS (File Trailer Segment)
@@ -781,7 +1055,7 @@ fn parse_segment_like_msh(segment_type: &str, input: &str, delimiters: &Delimite
     segment.add_field(Field::from_value(delimiters.encoding_characters()));
 
     // Parse the rest of the fields starting after "XXX|^~\&"
-    let field_start = 9; // "XXX|^~\&|".len()
+    let field_start = header_field_start(delimiters);
     if input.len() <= field_start {
         return Ok(segment);
     }
@@ -803,34 +1077,32 @@ fn parse_segment_like_msh(segment_type: &str, input: &str, delimiters: &Delimite
 /// HL7 datetime formats:
 /// - TS (v2.3-v2.5): YYYYMMDDHHMMSS[.SSSS][+/-ZZZZ]
 /// - DTM (v2.6+): YYYY[MM[DD[HH[MM[SS[.S[S[S[S]]]]]]]]][+/-ZZZZ]
+///
+/// Any time zone offset is ignored; see [`parse_datetime_field_tz`] to keep it.
 fn parse_datetime_field(value: Option<&str>) -> Option<NaiveDateTime> {
-    value.and_then(|s| {
-        if s.is_empty() {
-            return None;
-        }
-
-        // Remove timezone offset if present (we use NaiveDateTime)
-        let datetime_str = s.split('+').next()
-            .and_then(|s| s.split('-').next())
-            .unwrap_or(s);
-
-        // Try various datetime formats
-        // Full: YYYYMMDDHHMMSS
-        if datetime_str.len() >= 14 {
-            if let Ok(dt) = NaiveDateTime::parse_from_str(&datetime_str[..14], "%Y%m%d%H%M%S") {
-                return Some(dt);
-            }
-        }
-
-        // Date only: YYYYMMDD
-        if datetime_str.len() >= 8 {
-            if let Ok(date) = chrono::NaiveDate::parse_from_str(&datetime_str[..8], "%Y%m%d") {
-                return Some(date.and_hms_opt(0, 0, 0)?);
-            }
-        }
+    Timestamp::parse(value?).map(|ts| ts.datetime)
+}
 
-        None
-    })
+/// Parse an HL7 datetime field, keeping its time zone offset
+///
+/// Accepts the same TS/DTM formats as [`Timestamp::parse`], including
+/// fractional seconds (`.S` to `.SSSS`) and offsets given as `+/-HHMM` or
+/// `+/-HH`.
+///
+/// Returns `None` if the value is empty, malformed, or carries no offset,
+/// since a timestamp without an offset is in the sender's unspecified local time.
+///
+/// # Example
+///
+/// ```rust
+/// use rs7_parser::parse_datetime_field_tz;
+///
+/// let dt = parse_datetime_field_tz(Some("20240315143000-0500")).unwrap();
+/// assert_eq!(dt.offset().local_minus_utc(), -5 * 3600);
+/// assert_eq!(dt.to_rfc3339(), "2024-03-15T14:30:00-05:00");
+/// ```
+pub fn parse_datetime_field_tz(value: Option<&str>) -> Option<DateTime<FixedOffset>> {
+    Timestamp::parse(value?)?.to_datetime_tz()
 }
 
 #[cfg(test)]
@@ -850,6 +1122,147 @@ mod tests {
         assert_eq!(delims.subcomponent_separator, '&');
     }
 
+    #[test]
+    fn test_invalid_delimiters_rejected() {
+        // Component and repetition separators are both '^'
+        let input = "MSH|^^\\&|App|Fac|||20240315||ADT^A01|123|P|2.5\rPID|1||123";
+        assert!(extract_delimiters(input).is_err());
+
+        let err = parse_message(input).unwrap_err();
+        let location = err.location().unwrap();
+        assert_eq!(location.segment.as_deref(), Some("MSH"));
+        assert_eq!(location.byte_offset, Some(3));
+        assert!(err
+            .to_string()
+            .contains("Duplicate delimiter character '^' used as both component separator and repetition separator"));
+
+        // The field separator doubles as the escape character
+        assert!(parse_message("MSH|^~|&|App|Fac|||20240315||ADT^A01|123|P|2.5").is_err());
+    }
+
+    #[test]
+    fn test_truncation_character() {
+        // Before v2.7: four encoding characters, no truncation character
+        let v25 = "MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5\rPID|||||A#B";
+        let delims = extract_delimiters(v25).unwrap();
+        assert_eq!(delims.truncation_character, None);
+        let message = parse_message(v25).unwrap();
+        assert_eq!(message.get_msh().unwrap().get_field_value(3), Some("App"));
+        assert_eq!(message.segment("PID").unwrap().get_field_value(5), Some("A#B"));
+        assert_eq!(message.encode(), v25);
+
+        // v2.7: fifth encoding character
+        let v27 = "MSH|^~\\&#|App|Fac|||20240315||ADT^A01|123|P|2.7\rPID|||||A\\#\\B";
+        let delims = extract_delimiters(v27).unwrap();
+        assert_eq!(delims.truncation_character, Some('#'));
+        let message = parse_message(v27).unwrap();
+        let msh = message.get_msh().unwrap();
+        assert_eq!(msh.get_field_value(2), Some("^~\\&#"));
+        assert_eq!(msh.get_field_value(3), Some("App"));
+        assert_eq!(msh.get_field_value(12), Some("2.7"));
+        assert_eq!(message.segment("PID").unwrap().get_field_value(5), Some("A#B"));
+
+        let encoded = message.encode();
+        assert!(encoded.starts_with("MSH|^~\\&#|App|"));
+        assert!(encoded.ends_with("PID|||||A\\P\\B"));
+        assert_eq!(parse_message(&encoded).unwrap(), message);
+
+        let bhs = "BHS|^~\\&#|App|Fac";
+        let delims = extract_delimiters_from_bhs(bhs).unwrap();
+        assert_eq!(delims.truncation_character, Some('#'));
+        assert_eq!(parse_bhs_segment(bhs, &delims).unwrap().sending_application.as_deref(), Some("App"));
+    }
+
+    #[test]
+    fn test_parse_error_location() {
+        let err = parse_message("MSH|^~").unwrap_err();
+        let location = err.location().unwrap();
+        assert_eq!(location.segment.as_deref(), Some("MSH"));
+        assert_eq!(location.segment_index, Some(0));
+        assert_eq!(location.byte_offset, Some(6));
+        assert!(err.to_string().contains("MSH segment too short"));
+
+        let err = parse_message("PID|1").unwrap_err();
+        let location = err.location().unwrap();
+        assert_eq!(location.segment, None);
+        assert_eq!(location.byte_offset, Some(0));
+
+        // The third segment has an invalid ID
+        let input = "MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5\rPID|1\rPIDX|2";
+        let offset = input.find("PIDX").unwrap();
+        let err = parse_message(input).unwrap_err();
+        let location = err.location().unwrap();
+        assert_eq!(location.segment.as_deref(), Some("PIDX"));
+        assert_eq!(location.segment_index, Some(2));
+        assert_eq!(location.byte_offset, Some(offset));
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Parse error at PIDX(3) at offset {}: Segment ID must be 3 characters, got 4 ('PIDX')",
+                offset
+            )
+        );
+    }
+
+    #[test]
+    fn test_segment_terminator_round_trip() {
+        let segments = ["MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5", "PID|1||123", "PV1|1|I"];
+        for (terminator, separator) in [
+            (SegmentTerminator::Cr, "\r"),
+            (SegmentTerminator::Lf, "\n"),
+            (SegmentTerminator::CrLf, "\r\n"),
+        ] {
+            let input = segments.join(separator);
+            let message = parse_message(&input).unwrap();
+            assert_eq!(message.segment_terminator(), terminator);
+            assert_eq!(message.segments.len(), 3);
+            assert_eq!(message.encode(), input);
+        }
+
+        let message = parse_message(&segments.join("\n")).unwrap();
+        let message = message.with_segment_terminator(SegmentTerminator::Cr);
+        assert_eq!(message.encode(), segments.join("\r"));
+    }
+
+    #[test]
+    fn test_parse_message_bytes_charsets() {
+        let header = "MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5||||||";
+
+        // No MSH-18: UTF-8
+        let utf8 = "MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5\rPID|||||Müller";
+        let message = parse_message_bytes(utf8.as_bytes()).unwrap();
+        assert_eq!(message.segment("PID").unwrap().get_field_value(5), Some("Müller"));
+        assert!(parse_message_bytes(b"MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5\rPID|||||M\xFCller").is_err());
+
+        // Latin-1, with a second MSH-18 repetition ignored
+        let mut latin1 = format!("{}8859/1~ISO IR87\rPID|||||", header).into_bytes();
+        latin1.extend_from_slice(b"M\xFCller");
+        let message = parse_message_bytes(&latin1).unwrap();
+        assert_eq!(message.segment("PID").unwrap().get_field_value(5), Some("Müller"));
+        assert_eq!(message.get_charset(), Some(Charset::Iso8859_1));
+
+        // Round trip through ISO-2022-JP
+        let text = format!("{}ISO IR87\rPID|||||山田^太郎", header);
+        let message = parse_message(&text).unwrap();
+        let bytes = message.encode_bytes(Charset::IsoIr87).unwrap();
+        assert_eq!(parse_message_bytes(&bytes).unwrap(), message);
+    }
+
+    #[test]
+    fn test_parse_message_bytes_errors() {
+        let header = "MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5||||||";
+
+        let unknown = format!("{}EBCDIC\rPID|1", header);
+        let err = parse_message_bytes(unknown.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("EBCDIC"));
+
+        let mut ascii = format!("{}ASCII\rPID|||||", header).into_bytes();
+        ascii.push(0xE9);
+        assert!(matches!(parse_message_bytes(&ascii), Err(Error::Decoding(_))));
+
+        assert!(parse_message_bytes(b"PID|1").is_err());
+    }
+
     #[test]
     fn test_parse_msh_segment() {
         let msh = "MSH|^~\\&|SendApp|SendFac|RecApp|RecFac";
@@ -1064,6 +1477,95 @@ FTS|2";
         assert_eq!(file.batches[1].messages.len(), 1);
     }
 
+    #[test]
+    fn test_preserve_raw_fields() {
+        // A vendor nesting repetitions inside the second component of PID-5
+        let input = "MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5\rPID|1||123||DOE^JOHN~JACK\\T\\CO^III";
+
+        let message = parse_message(input).unwrap();
+        assert_eq!(message.segment("PID").unwrap().get_field(5).unwrap().raw(), None);
+
+        let config = ParserConfig::strict().preserve_raw_fields(true);
+        let mut message = parse_message_with_config(input, &config).unwrap().value;
+        let pid5 = message.segment("PID").unwrap().get_field(5).unwrap();
+        assert_eq!(pid5.raw(), Some("DOE^JOHN~JACK\\T\\CO^III"));
+        assert_eq!(pid5.repetitions.len(), 2);
+        assert_eq!(message.get_msh().unwrap().get_field(2).unwrap().raw(), Some("^~\\&"));
+        assert_eq!(message.get_msh().unwrap().get_field(3).unwrap().raw(), Some("App"));
+
+        // Raw text does not affect equality or encoding
+        assert_eq!(message, parse_message(input).unwrap());
+        assert_eq!(message.encode(), input);
+
+        // Edited fields no longer report the text they were parsed from
+        let pid = message.get_segment_by_id_mut("PID").unwrap();
+        pid.set_component(5, 0, 0, "SMITH").unwrap();
+        assert_eq!(pid.get_field(5).unwrap().raw(), None);
+        assert_eq!(pid.get_field(3).unwrap().raw(), Some("123"));
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        let msh = "MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5";
+        let input = format!("{}\rPID|1||A~B~C||DOE^JOHN&X\rNTE|1", msh);
+
+        let exact = ParseLimits::new()
+            .max_segments(3)
+            .max_fields_per_segment(12)
+            .max_repetitions_per_field(3)
+            .max_components(2);
+        assert!(parse_message_with_limits(&input, &exact).is_ok());
+
+        let err = parse_message_with_limits(&input, &exact.max_segments(2)).unwrap_err();
+        assert!(err.to_string().contains("Message has more than 2 segments"));
+
+        let err = parse_message_with_limits(&input, &exact.max_repetitions_per_field(2)).unwrap_err();
+        assert!(err.to_string().contains("Field has more than 2 repetitions"));
+        assert_eq!(err.location().unwrap().segment.as_deref(), Some("PID"));
+
+        let err = parse_message_with_limits(&input, &exact.max_components(1)).unwrap_err();
+        assert!(err.to_string().contains("more than 1 components"));
+
+        // MSH has 12 fields including MSH-1 and MSH-2
+        let err = parse_message_with_limits(&input, &exact.max_fields_per_segment(11)).unwrap_err();
+        assert!(err.to_string().contains("Segment has more than"));
+        assert_eq!(err.location().unwrap().segment.as_deref(), Some("MSH"));
+
+        // A small message that would expand into a huge structure
+        let hostile = format!("{}\rPID|{}", msh, "~".repeat(50_000));
+        assert!(parse_message_with_limits(&hostile, &ParseLimits::default()).is_err());
+        assert!(parse_message(&hostile).is_ok());
+    }
+
+    #[test]
+    fn test_batch_and_file_encode_round_trip() {
+        let batch_text = "\
+BHS|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315143000||BATCH001||B12345
+MSH|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315143000||ADT^A01|MSG001|P|2.5
+PID|1|12345|67890^^^MRN|DOE^JOHN^A||19800101|M
+MSH|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315143100||ADT^A01|MSG002|P|2.5
+BTS|2";
+
+        let mut batch = parse_batch(batch_text).unwrap();
+        let reparsed = parse_batch(&batch.encode()).unwrap();
+        assert_eq!(reparsed.messages.len(), 2);
+        assert_eq!(reparsed.messages[0].encode(), batch.messages[0].encode());
+        assert_eq!(reparsed.header.control_id, Some("B12345".to_string()));
+
+        // Counts are recomputed when messages are added or removed
+        batch.messages.pop();
+        assert!(parse_batch(&batch.encode()).unwrap().validate().is_ok());
+
+        let mut file = File::new();
+        file.header.control_id = Some("F001".to_string());
+        file.add_batch(batch);
+        file.add_batch(reparsed);
+        let reparsed = parse_file(&file.encode()).unwrap();
+        assert_eq!(reparsed.trailer.batch_count, Some(2));
+        assert_eq!(reparsed.total_message_count(), 3);
+        assert_eq!(reparsed.header.control_id, Some("F001".to_string()));
+    }
+
     #[test]
     fn test_parse_file_with_validation_error() {
         // FTS says 5 batches but only 2 are present
@@ -1114,6 +1616,57 @@ FTS|5";
         assert!(dt4.is_none());
     }
 
+    #[test]
+    fn test_parse_datetime_field_ignores_offset() {
+        let dt = parse_datetime_field(Some("20240315143000.1234-0500")).unwrap();
+        assert_eq!(dt.hour(), 14);
+        assert_eq!(dt.nanosecond(), 123_400_000);
+
+        let dt = parse_datetime_field(Some("202403151430")).unwrap();
+        assert_eq!(dt.minute(), 30);
+    }
+
+    #[test]
+    fn test_parse_datetime_field_tz() {
+        let dt = parse_datetime_field_tz(Some("20240315143000-0500")).unwrap();
+        assert_eq!(dt.offset().local_minus_utc(), -5 * 3600);
+        assert_eq!(dt.hour(), 14);
+
+        // Fractional seconds
+        let dt = parse_datetime_field_tz(Some("20240315143000.1234+0130")).unwrap();
+        assert_eq!(dt.nanosecond(), 123_400_000);
+        assert_eq!(dt.offset().local_minus_utc(), 90 * 60);
+
+        // Offset without minutes
+        let dt = parse_datetime_field_tz(Some("20240315143000+02")).unwrap();
+        assert_eq!(dt.offset().local_minus_utc(), 2 * 3600);
+
+        // Reduced precision
+        let dt = parse_datetime_field_tz(Some("20240315-0800")).unwrap();
+        assert_eq!(dt.day(), 15);
+        assert_eq!(dt.hour(), 0);
+
+        assert!(parse_datetime_field_tz(Some("20240315143000")).is_none());
+        assert!(parse_datetime_field_tz(Some("20240315143000-05")).is_some());
+        assert!(parse_datetime_field_tz(Some("20240315143000-5")).is_none());
+        assert!(parse_datetime_field_tz(Some("")).is_none());
+        assert!(parse_datetime_field_tz(None).is_none());
+    }
+
+    #[test]
+    fn test_parse_bhs_segment_with_offset() {
+        let bhs = "BHS|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315143000-0500||BATCH001||B12345";
+        let delims = Delimiters::default();
+        let header = parse_bhs_segment(bhs, &delims).unwrap();
+
+        assert_eq!(header.creation_datetime.unwrap().hour(), 14);
+        assert_eq!(header.creation_offset.unwrap().local_minus_utc(), -5 * 3600);
+        assert_eq!(
+            header.to_segment().get_field_value(7),
+            Some("20240315143000-0500")
+        );
+    }
+
     #[test]
     fn test_parse_batch_single_message() {
         let batch_text = "\
@@ -1139,6 +1692,42 @@ BTS|1";
         assert_eq!(delims.subcomponent_separator, '&');
     }
 
+    #[test]
+    fn test_parse_with_options_skips_invalid_segments() {
+        let msg = "MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5\nPI\nPID|1||123\nOBX1|bad";
+        let options = ParseOptions::new().skip_invalid_segments(true);
+
+        let parsed = parse_message_with_options(msg, &options).unwrap();
+        assert_eq!(parsed.value.segments.len(), 2);
+        assert_eq!(parsed.value.segments[1].id, "PID");
+        assert_eq!(parsed.warning_count(), 2);
+        assert_eq!(parsed.warnings[0].location, 1);
+        assert!(parsed.warnings.iter().all(|w| w.code == WarningCode::InvalidSegment));
+
+        // Strict options still abort on the first bad segment
+        assert!(parse_message_with_options(msg, &ParseOptions::new()).is_err());
+    }
+
+    #[test]
+    fn test_parse_with_options_requires_msh() {
+        let options = ParseOptions::new().skip_invalid_segments(true);
+        assert!(parse_message_with_options("PID|1||123\rPV1|1", &options).is_err());
+        assert!(parse_message_with_options("MS|^~\\&|App", &options).is_err());
+    }
+
+    #[test]
+    fn test_parse_with_options_missing_field_separator() {
+        let msg = "MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5\rPID 1 123\rPV1|1";
+        let options = ParseOptions::new().allow_missing_field_separator(true);
+
+        let parsed = parse_message_with_options(msg, &options).unwrap();
+        assert_eq!(parsed.value.segments.len(), 3);
+        assert_eq!(parsed.value.segments[1].id, "PID");
+        assert!(parsed.value.segments[1].fields.is_empty());
+        assert_eq!(parsed.warnings[0].code, WarningCode::MissingFieldSeparator);
+        assert_eq!(parsed.warnings[0].location, msg.find("PID 1").unwrap() + 3);
+    }
+
     #[test]
     fn test_extract_delimiters_from_fhs() {
         let fhs = "FHS|^~\\&|SendApp|SendFac";
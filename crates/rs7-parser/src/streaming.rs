@@ -40,6 +40,9 @@
 //! }
 //! ```
 
+use std::io::{BufRead, BufReader, Read};
+
+use rs7_core::batch::{BatchHeader, FileHeader};
 use rs7_core::delimiters::Delimiters;
 use rs7_core::error::{Error, ErrorLocation, Result};
 use rs7_core::field::{Component, Field, Repetition, SubComponent};
@@ -174,7 +177,12 @@ impl<'a> StreamingParser<'a> {
         }
 
         let field_sep = line.chars().nth(3).unwrap();
-        let encoding_chars = &line[4..8];
+        // v2.7+ may add a fifth encoding character (truncation character)
+        let encoding_end = match line.chars().nth(8) {
+            Some(c) if c != field_sep => 9,
+            _ => 8,
+        };
+        let encoding_chars = &line[4..encoding_end];
 
         let delimiters = Delimiters {
             field_separator: field_sep,
@@ -182,12 +190,13 @@ impl<'a> StreamingParser<'a> {
             repetition_separator: encoding_chars.chars().nth(1).unwrap_or('~'),
             escape_character: encoding_chars.chars().nth(2).unwrap_or('\\'),
             subcomponent_separator: encoding_chars.chars().nth(3).unwrap_or('&'),
+            truncation_character: encoding_chars.chars().nth(4),
         };
 
         self.delimiters = Some(delimiters.clone());
         self.current_segment_id = Some("MSH");
         self.current_field_index = 0;
-        self.field_position = 8; // After MSH + field sep + encoding chars
+        self.field_position = encoding_end; // After MSH + field sep + encoding chars
         self.state = ParserState::InMsh;
 
         // First emit the delimiters event
@@ -222,7 +231,7 @@ impl<'a> StreamingParser<'a> {
             self.current_field_index = 3;
             return Some(Ok(SegmentEvent::Field {
                 index: 2,
-                value: &line[4..8], // The encoding characters
+                value: &line[4..self.field_position], // The encoding characters
             }));
         }
 
@@ -257,7 +266,8 @@ impl<'a> StreamingParser<'a> {
             let field_value = &remaining[..sep_pos];
             let field_index = self.current_field_index;
             self.current_field_index += 1;
-            self.field_position += sep_pos + 1;
+            // Leave the separator in place: it opens the next (possibly empty) field
+            self.field_position += sep_pos;
 
             Some(Ok(SegmentEvent::Field {
                 index: field_index,
@@ -309,10 +319,9 @@ impl<'a> StreamingParser<'a> {
             return self.parse_next_segment();
         }
 
-        // Extract segment ID (first 3 characters typically)
+        // Extract segment ID (up to the first field separator)
         let delims = self.delimiters.as_ref()?;
-        let seg_end = line.find(delims.field_separator).unwrap_or(line.len());
-        let segment_id = &line[..seg_end.min(3)];
+        let segment_id = line_segment_id(line, delims.field_separator);
 
         // Validate segment ID (should be 3 uppercase letters typically)
         if segment_id.len() < 2 {
@@ -324,7 +333,7 @@ impl<'a> StreamingParser<'a> {
 
         self.current_segment_id = Some(segment_id);
         self.current_field_index = 0;
-        self.field_position = seg_end.min(3);
+        self.field_position = segment_id.len();
         self.state = ParserState::InSegment;
 
         Some(Ok(SegmentEvent::Start {
@@ -369,7 +378,8 @@ impl<'a> StreamingParser<'a> {
         if let Some(sep_pos) = remaining.find(delims.field_separator) {
             let field_value = &remaining[..sep_pos];
             let field_index = self.current_field_index;
-            self.field_position += sep_pos + 1;
+            // Leave the separator in place: it opens the next (possibly empty) field
+            self.field_position += sep_pos;
 
             Some(Ok(SegmentEvent::Field {
                 index: field_index,
@@ -419,7 +429,8 @@ impl StreamingMessageBuilder {
     /// Create a new builder
     pub fn new() -> Self {
         Self {
-            message: Message::new(),
+            // Field values are split but not unescaped
+            message: Message::new().with_values_escaped(true),
             current_segment: None,
             delimiters: None,
         }
@@ -574,6 +585,11 @@ pub fn process_with_handler<H: SegmentHandler>(input: &str, handler: &mut H) ->
     Ok(())
 }
 
+/// Segment ID of a line: everything before the first field separator
+fn line_segment_id(line: &str, field_separator: char) -> &str {
+    line.split(field_separator).next().unwrap_or(line)
+}
+
 /// Parse a field value with given delimiters
 fn parse_field_with_delimiters(value: &str, delims: &Delimiters) -> Field {
     let mut field = Field::new();
@@ -601,6 +617,321 @@ fn parse_field_with_delimiters(value: &str, delims: &Delimiters) -> Field {
     field
 }
 
+/// Streaming reader for HL7 file and batch exports
+///
+/// Reads FHS/BHS/MSH/BTS/FTS segments from any [`Read`] source and yields one
+/// [`Message`] at a time, so only the current message and the file/batch
+/// headers are held in memory. BTS and FTS counts are validated as each
+/// trailer is reached.
+///
+/// The delimiters extracted from the FHS (or BHS, for batch-only input) are
+/// available from [`FileStreamReader::delimiters`] once the first message has
+/// been requested.
+///
+/// # Examples
+///
+/// ```rust
+/// use rs7_parser::parse_file_streaming;
+///
+/// let data = "FHS|^~\\&|APP|FAC\rBHS|^~\\&|APP|FAC\r\
+/// MSH|^~\\&|APP|FAC|||20240315||ADT^A01|1|P|2.5\rPID|1||123\r\
+/// BTS|1\rFTS|1\r";
+///
+/// let mut reader = parse_file_streaming(data.as_bytes());
+/// let message = reader.next().unwrap().unwrap();
+/// assert_eq!(message.segments.len(), 2);
+/// assert_eq!(reader.delimiters().unwrap().field_separator, '|');
+/// assert!(reader.next().is_none());
+/// ```
+pub struct FileStreamReader<R: Read> {
+    reader: BufReader<R>,
+    delimiters: Option<Delimiters>,
+    file_header: Option<FileHeader>,
+    batch_header: Option<BatchHeader>,
+    pending: Vec<String>,
+    lookahead: Option<String>,
+    line: usize,
+    started: bool,
+    finished: bool,
+    in_batch: bool,
+    batch_count: usize,
+    batch_message_count: usize,
+}
+
+impl<R: Read> FileStreamReader<R> {
+    /// Create a new streaming reader over the given source
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            delimiters: None,
+            file_header: None,
+            batch_header: None,
+            pending: Vec::new(),
+            lookahead: None,
+            line: 0,
+            started: false,
+            finished: false,
+            in_batch: false,
+            batch_count: 0,
+            batch_message_count: 0,
+        }
+    }
+
+    /// Get the delimiters extracted from the FHS/BHS header
+    pub fn delimiters(&self) -> Option<&Delimiters> {
+        self.delimiters.as_ref()
+    }
+
+    /// Get the parsed file header (FHS), if the input is a file
+    pub fn file_header(&self) -> Option<&FileHeader> {
+        self.file_header.as_ref()
+    }
+
+    /// Get the header (BHS) of the batch currently being read
+    pub fn batch_header(&self) -> Option<&BatchHeader> {
+        self.batch_header.as_ref()
+    }
+
+    /// Get the number of the last segment line read (1-based)
+    pub fn current_line(&self) -> usize {
+        self.line
+    }
+
+    /// Read the next non-empty segment line from the source
+    fn read_segment(&mut self) -> Result<Option<String>> {
+        if let Some(line) = self.lookahead.take() {
+            return Ok(Some(line));
+        }
+
+        let mut buf = Vec::new();
+        loop {
+            let available = self.reader.fill_buf()?;
+            if available.is_empty() {
+                break;
+            }
+
+            if let Some(pos) = available.iter().position(|&b| b == b'\r' || b == b'\n') {
+                buf.extend_from_slice(&available[..pos]);
+                self.reader.consume(pos + 1);
+                if buf.iter().all(u8::is_ascii_whitespace) {
+                    buf.clear();
+                    continue;
+                }
+                break;
+            }
+
+            let len = available.len();
+            buf.extend_from_slice(available);
+            self.reader.consume(len);
+        }
+
+        if buf.iter().all(u8::is_ascii_whitespace) {
+            return Ok(None);
+        }
+
+        self.line += 1;
+        let line = String::from_utf8(buf).map_err(|e| {
+            Error::parse_at(
+                format!("Invalid UTF-8 in segment: {}", e),
+                ErrorLocation::new().line(self.line),
+            )
+        })?;
+
+        Ok(Some(line.trim().to_string()))
+    }
+
+    /// Read and parse the leading FHS or BHS header
+    fn read_header(&mut self) -> Result<()> {
+        self.started = true;
+
+        let line = self
+            .read_segment()?
+            .ok_or_else(|| Error::parse("File must start with FHS segment"))?;
+
+        if line.starts_with("FHS") {
+            let delimiters = crate::extract_delimiters_from_fhs(&line)?;
+            self.file_header = Some(crate::parse_fhs_segment(&line, &delimiters)?);
+            self.delimiters = Some(delimiters);
+        } else if line.starts_with("BHS") {
+            self.open_batch(&line)?;
+        } else {
+            return Err(Error::parse_at(
+                "File must start with FHS or BHS segment",
+                ErrorLocation::new().line(self.line),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Start a new batch from a BHS line
+    fn open_batch(&mut self, line: &str) -> Result<()> {
+        if self.in_batch {
+            return Err(Error::parse_at(
+                "BHS segment found before BTS of previous batch",
+                ErrorLocation::new().line(self.line),
+            ));
+        }
+
+        let delimiters = match self.delimiters {
+            Some(d) => d,
+            None => {
+                let d = crate::extract_delimiters_from_bhs(line)?;
+                self.delimiters = Some(d);
+                d
+            }
+        };
+
+        self.batch_header = Some(crate::parse_bhs_segment(line, &delimiters)?);
+        self.in_batch = true;
+        self.batch_count += 1;
+        self.batch_message_count = 0;
+        Ok(())
+    }
+
+    /// Parse the buffered segments of the current message
+    fn take_message(&mut self) -> Result<Message> {
+        let text = self.pending.join("\r");
+        self.pending.clear();
+        self.batch_message_count += 1;
+        crate::parse_message(&text)
+    }
+
+    /// Advance until the next complete message or the end of the stream
+    fn next_message(&mut self) -> Result<Option<Message>> {
+        if !self.started {
+            self.read_header()?;
+        }
+
+        loop {
+            let Some(line) = self.read_segment()? else {
+                if !self.pending.is_empty() {
+                    return self.take_message().map(Some);
+                }
+                if self.file_header.is_some() {
+                    return Err(Error::parse_at(
+                        "Unexpected end of stream before FTS segment",
+                        ErrorLocation::new().line(self.line),
+                    ));
+                }
+                return Err(Error::parse_at(
+                    "Unexpected end of stream before BTS segment",
+                    ErrorLocation::new().line(self.line),
+                ));
+            };
+
+            let is_boundary = ["MSH", "BHS", "BTS", "FTS"]
+                .iter()
+                .any(|id| line.starts_with(id));
+
+            // Any boundary segment completes the message being buffered
+            if is_boundary && !self.pending.is_empty() {
+                self.lookahead = Some(line);
+                return self.take_message().map(Some);
+            }
+
+            if line.starts_with("MSH") {
+                if !self.in_batch && self.file_header.is_some() {
+                    return Err(Error::parse_at(
+                        "MSH segment found outside of a batch",
+                        ErrorLocation::new().line(self.line),
+                    ));
+                }
+                self.pending.push(line);
+            } else if line.starts_with("BHS") {
+                self.open_batch(&line)?;
+            } else if line.starts_with("BTS") {
+                self.close_batch(&line)?;
+                if self.file_header.is_none() {
+                    self.finished = true;
+                    return Ok(None);
+                }
+            } else if line.starts_with("FTS") {
+                self.close_file(&line)?;
+                self.finished = true;
+                return Ok(None);
+            } else if self.pending.is_empty() {
+                return Err(Error::parse_at(
+                    format!(
+                        "Segment '{}' found outside of a message",
+                        line_segment_id(&line, self.delimiters.unwrap_or_default().field_separator)
+                    ),
+                    ErrorLocation::new().line(self.line),
+                ));
+            } else {
+                self.pending.push(line);
+            }
+        }
+    }
+
+    /// Validate a BTS trailer against the messages read in the batch
+    fn close_batch(&mut self, line: &str) -> Result<()> {
+        if !self.in_batch {
+            return Err(Error::parse_at(
+                "BTS segment found without matching BHS",
+                ErrorLocation::new().line(self.line),
+            ));
+        }
+
+        let delimiters = self.delimiters.unwrap_or_default();
+        let trailer = crate::parse_bts_segment(line, &delimiters)?;
+        if let Some(expected) = trailer.message_count
+            && expected != self.batch_message_count
+        {
+            return Err(Error::validation(format!(
+                "Batch message count mismatch: BTS-1 indicates {} messages but batch contains {} messages",
+                expected, self.batch_message_count
+            )));
+        }
+
+        self.in_batch = false;
+        self.batch_header = None;
+        Ok(())
+    }
+
+    /// Validate the FTS trailer against the batches read in the file
+    fn close_file(&mut self, line: &str) -> Result<()> {
+        if self.in_batch {
+            return Err(Error::parse_at(
+                "FTS segment found before BTS of the last batch",
+                ErrorLocation::new().line(self.line),
+            ));
+        }
+
+        let delimiters = self.delimiters.unwrap_or_default();
+        let trailer = crate::parse_fts_segment(line, &delimiters)?;
+        if let Some(expected) = trailer.batch_count
+            && expected != self.batch_count
+        {
+            return Err(Error::validation(format!(
+                "File batch count mismatch: FTS-1 indicates {} batches but file contains {} batches",
+                expected, self.batch_count
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for FileStreamReader<R> {
+    type Item = Result<Message>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        match self.next_message() {
+            Ok(Some(message)) => Some(Ok(message)),
+            Ok(None) => None,
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -640,6 +971,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_streaming_parser_truncation_character() {
+        let hl7 = "MSH|^~\\&#|APP|FAC|||20250115||ADT^A01|123|P|2.7\rPID|1\r";
+
+        let message = parse_streaming(hl7).unwrap();
+        assert_eq!(message.delimiters.truncation_character, Some('#'));
+        assert_eq!(message.segments[0].get_field_value(3), Some("APP"));
+        assert_eq!(message.segments[1].id, "PID");
+    }
+
     #[test]
     fn test_streaming_message_builder() {
         let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ADT^A01|123|P|2.5\rPID|1||PAT001||DOE^JOHN\r";
@@ -704,6 +1045,8 @@ mod tests {
         assert_eq!(streaming.segments.len(), regular.segments.len());
         assert_eq!(streaming.segments[0].id, regular.segments[0].id);
         assert_eq!(streaming.segments[1].id, regular.segments[1].id);
+        assert_eq!(streaming.segments[0].fields[2..], regular.segments[0].fields[2..]);
+        assert_eq!(streaming.segments[1], regular.segments[1]);
     }
 
     #[test]
@@ -715,6 +1058,13 @@ mod tests {
         assert_eq!(message.segments.len(), 1);
         // Should have multiple fields including empty ones
         assert!(message.segments[0].fields.len() > 3);
+        assert_eq!(message.segments[0].get_field_value(9), Some(""));
+        assert_eq!(message.segments[0].get_field_value(10), Some("P"));
+
+        let message = parse_streaming("MSH|^~\\&|APP\rOBX|1||A|").unwrap();
+        let obx = &message.segments[1];
+        assert_eq!(obx.get_field_value(2), Some(""));
+        assert_eq!(obx.get_field_value(3), Some("A"));
     }
 
     #[test]
@@ -735,4 +1085,107 @@ mod tests {
 
         assert!(has_components, "Message should have a field with 3+ components");
     }
+
+    const STREAM_FILE: &str = "FHS|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315143000||FILE001||F12345\r\
+BHS|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315143000||BATCH001||B12345\r\
+MSH|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315143000||ADT^A01|MSG001|P|2.5\r\
+PID|1|12345|67890^^^MRN|DOE^JOHN^A||19800101|M\r\
+MSH|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315143100||ADT^A01|MSG002|P|2.5\r\
+PID|1|54321|09876^^^MRN|SMITH^JANE^B||19900202|F\r\
+BTS|2\r\
+BHS|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315143200||BATCH002||B12346\r\
+MSH|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315143200||ADT^A01|MSG003|P|2.5\r\
+PID|1|11111\r\
+BTS|1\r\
+FTS|2\r";
+
+    #[test]
+    fn test_file_stream_reader() {
+        let mut reader = FileStreamReader::new(STREAM_FILE.as_bytes());
+
+        let first = reader.next().unwrap().unwrap();
+        assert_eq!(first.segments.len(), 2);
+        assert_eq!(first.get_control_id(), Some("MSG001"));
+        assert_eq!(reader.delimiters().unwrap().component_separator, '^');
+        assert_eq!(
+            reader.file_header().unwrap().control_id,
+            Some("F12345".to_string())
+        );
+        assert_eq!(
+            reader.batch_header().unwrap().control_id,
+            Some("B12345".to_string())
+        );
+
+        let rest: Vec<Message> = reader.map(|m| m.unwrap()).collect();
+        assert_eq!(rest.len(), 2);
+        assert_eq!(rest[0].get_control_id(), Some("MSG002"));
+        assert_eq!(rest[1].get_control_id(), Some("MSG003"));
+    }
+
+    #[test]
+    fn test_file_stream_reader_newlines() {
+        let data = STREAM_FILE.replace('\r', "\r\n");
+        let messages: Result<Vec<Message>> = FileStreamReader::new(data.as_bytes()).collect();
+        assert_eq!(messages.unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_file_stream_reader_batch_only() {
+        let data = "BHS|^~\\&|APP|FAC|||20240315||BATCH||B001\n\
+MSH|^~\\&|APP|FAC|SYS|HOSP|20240315||ADT^A01|MSG001|P|2.5\n\
+PID|1|12345\n\
+BTS|1\n";
+
+        let messages: Result<Vec<Message>> = FileStreamReader::new(data.as_bytes()).collect();
+        assert_eq!(messages.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_file_stream_reader_count_mismatch() {
+        let data = STREAM_FILE.replace("BTS|2", "BTS|5");
+        let results: Vec<Result<Message>> = FileStreamReader::new(data.as_bytes()).collect();
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_ok());
+        let err = results[2].as_ref().unwrap_err();
+        assert!(err.to_string().contains("message count mismatch"));
+    }
+
+    #[test]
+    fn test_non_ascii_segment_id() {
+        let data = "BHS|^~\\&|APP|FAC|||20240315||BATCH||B001\nÄÖ|x\n";
+        let results: Vec<Result<Message>> = FileStreamReader::new(data.as_bytes()).collect();
+        let err = results[0].as_ref().unwrap_err();
+        assert!(err.to_string().contains("Segment 'ÄÖ' found outside of a message"), "{}", err);
+
+        let message = parse_streaming("MSH|^~\\&|APP|FAC|||20240315||ADT^A01|1|P|2.5\rÄÖÜß|x").unwrap();
+        assert_eq!(message.segments[1].id, "ÄÖÜß");
+        assert_eq!(message.segments[1].get_field_value(1), Some("x"));
+    }
+
+    #[test]
+    fn test_segment_id_split_on_field_separator() {
+        let message = parse_streaming("MSH|^~\\&|APP|FAC|||20240315||ADT^A01|1|P|2.5\rZ1|A|B\rZABC|C").unwrap();
+        assert_eq!(message.segments[1].id, "Z1");
+        assert_eq!(message.segments[1].get_field_value(1), Some("A"));
+        assert_eq!(message.segments[1].get_field_value(2), Some("B"));
+        assert_eq!(message.segments[2].id, "ZABC");
+        assert_eq!(message.segments[2].get_field_value(1), Some("C"));
+
+        // Custom field separator
+        let message = parse_streaming("MSH#^~\\&#APP#FAC###20240315##ADT^A01#1#P#2.5\rZ1#A|B").unwrap();
+        assert_eq!(message.segments[1].id, "Z1");
+        assert_eq!(message.segments[1].get_field_value(1), Some("A|B"));
+    }
+
+    #[test]
+    fn test_file_stream_reader_missing_fts() {
+        let data = STREAM_FILE.replace("FTS|2\r", "");
+        let results: Vec<Result<Message>> = FileStreamReader::new(data.as_bytes()).collect();
+
+        assert_eq!(results.len(), 4);
+        let err = results[3].as_ref().unwrap_err();
+        assert!(err.to_string().contains("before FTS"));
+    }
 }
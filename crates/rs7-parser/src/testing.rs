@@ -0,0 +1,141 @@
+//! Round-trip test helpers
+//!
+//! [`assert_roundtrip`] checks that a message survives parse → encode without
+//! losing or altering anything, which guards against drift between the parser
+//! and the encoder. Enable the `testing` feature to use it from other crates:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! rs7-parser = { version = "*", features = ["testing"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! use rs7_parser::testing::{assert_roundtrip, check_roundtrip};
+//!
+//! assert_roundtrip("MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5\nPID|1||123||DOE^JOHN");
+//!
+//! // Escape sequences are re-encoded in their canonical form
+//! let report = check_roundtrip("MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5\rNTE|1||\\X41\\").unwrap_err();
+//! assert!(report.contains("segment 2"));
+//! ```
+
+use crate::parse_message;
+use rs7_core::message::Message;
+use std::fmt::Write;
+
+/// Assert that `er7` round-trips losslessly, panicking with a diff if not
+///
+/// See [`check_roundtrip`] for what is compared.
+#[track_caller]
+pub fn assert_roundtrip(er7: &str) {
+    if let Err(report) = check_roundtrip(er7) {
+        panic!("{}", report);
+    }
+}
+
+/// Check that `er7` round-trips losslessly
+///
+/// The message is parsed and re-encoded, and the result must equal the input
+/// byte for byte, using the input's own segment terminator; only trailing
+/// line breaks after the last segment are ignored. The re-encoded text is then
+/// parsed again and both messages must have the same canonical form (see
+/// `Message::canonicalize`), so the encoding is stable.
+///
+/// Returns a readable report of the first mismatch, with the differing
+/// segments or field paths.
+pub fn check_roundtrip(er7: &str) -> Result<(), String> {
+    let message = parse_message(er7).map_err(|e| format!("round-trip failed: input does not parse: {}", e))?;
+
+    let expected = er7.trim_end_matches(['\r', '\n']);
+    let encoded = message.encode();
+    if encoded != expected {
+        return Err(segment_diff(expected, &encoded, message.segment_terminator().as_str()));
+    }
+
+    let reparsed = parse_message(&encoded)
+        .map_err(|e| format!("round-trip failed: encoded message does not parse: {}", e))?;
+    if reparsed.encode_canonical() != message.encode_canonical() {
+        return Err(field_diff(&message, &reparsed));
+    }
+
+    Ok(())
+}
+
+/// Describe the segments that differ between the input and its encoding
+fn segment_diff(expected: &str, actual: &str, terminator: &str) -> String {
+    let expected: Vec<&str> = expected.split(terminator).collect();
+    let actual: Vec<&str> = actual.split(terminator).collect();
+
+    let mut report = String::from("round-trip mismatch: encoded message differs from input\n");
+    for i in 0..expected.len().max(actual.len()) {
+        let (left, right) = (expected.get(i), actual.get(i));
+        if left == right {
+            continue;
+        }
+        let _ = writeln!(report, "  segment {}:", i + 1);
+        match left {
+            Some(line) => {
+                let _ = writeln!(report, "    input:   {:?}", line);
+            }
+            None => report.push_str("    input:   (missing)\n"),
+        }
+        match right {
+            Some(line) => {
+                let _ = writeln!(report, "    encoded: {:?}", line);
+            }
+            None => report.push_str("    encoded: (missing)\n"),
+        }
+        if let (Some(left), Some(right)) = (left, right) {
+            let column = left
+                .chars()
+                .zip(right.chars())
+                .take_while(|(a, b)| a == b)
+                .count();
+            let _ = writeln!(report, "    first difference at character {}", column + 1);
+        }
+    }
+    report
+}
+
+/// Describe the fields that differ between the parsed and re-parsed message
+fn field_diff(original: &Message, reparsed: &Message) -> String {
+    let mut report = String::from("round-trip mismatch: re-parsed message differs after canonicalization\n");
+    for diff in original.canonicalize().diff(&reparsed.canonicalize()) {
+        let _ = writeln!(report, "  {}: {:?} -> {:?}", diff.path, diff.old_value, diff.new_value);
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_preserves_terminators() {
+        let segments = ["MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5", "PID|1||123~456||DOE^JOHN&X||"];
+        for separator in ["\r", "\n", "\r\n"] {
+            assert_roundtrip(&segments.join(separator));
+        }
+        assert_roundtrip(&format!("{}\r", segments.join("\r")));
+    }
+
+    #[test]
+    fn test_roundtrip_report() {
+        let input = "MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5\rPID|1\rNTE|1||\\X41\\";
+        let report = check_roundtrip(input).unwrap_err();
+        assert!(report.contains("segment 3"));
+        assert!(!report.contains("segment 2"));
+        assert!(report.contains("first difference at character 8"));
+
+        let report = check_roundtrip("PID|1").unwrap_err();
+        assert!(report.contains("input does not parse"));
+    }
+
+    #[test]
+    #[should_panic(expected = "round-trip mismatch")]
+    fn test_assert_roundtrip_panics() {
+        assert_roundtrip("MSH|^~\\&|App|Fac|||20240315||ADT^A01|123|P|2.5\rNTE|1||\\X41\\");
+    }
+}
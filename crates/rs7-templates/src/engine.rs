@@ -8,6 +8,9 @@ use std::collections::HashMap;
 pub struct TemplateEngine {
     /// Variables for substitution
     variables: HashMap<String, String>,
+
+    /// Array variables for segments that repeat over a list
+    arrays: HashMap<String, Vec<HashMap<String, String>>>,
 }
 
 impl TemplateEngine {
@@ -15,6 +18,7 @@ impl TemplateEngine {
     pub fn new() -> Self {
         Self {
             variables: HashMap::new(),
+            arrays: HashMap::new(),
         }
     }
 
@@ -33,6 +37,47 @@ impl TemplateEngine {
         self.variables.get(key)
     }
 
+    /// Set an array variable
+    ///
+    /// Segment templates marked with [`crate::SegmentTemplate::repeat_over`]
+    /// emit one segment per element. Each element's entries are substituted as
+    /// ordinary variables (overriding engine variables of the same name) and
+    /// `{{index}}` holds the element's 1-based position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs7_templates::{FieldTemplate, MessageTemplate, SegmentTemplate, TemplateEngine};
+    /// use std::collections::HashMap;
+    ///
+    /// let template = MessageTemplate::new("Results", "2.5", "ORU", "R01").with_segment(
+    ///     SegmentTemplate::new("OBX")
+    ///         .repeat_over("observations")
+    ///         .with_field(1, FieldTemplate::new().with_placeholder("{{index}}"))
+    ///         .with_field(5, FieldTemplate::new().with_placeholder("{{value}}")),
+    /// );
+    ///
+    /// let mut engine = TemplateEngine::new();
+    /// engine.set_array(
+    ///     "observations",
+    ///     vec![
+    ///         HashMap::from([("value".to_string(), "120".to_string())]),
+    ///         HashMap::from([("value".to_string(), "80".to_string())]),
+    ///     ],
+    /// );
+    ///
+    /// let message = engine.create_message(&template).unwrap();
+    /// assert_eq!(message.segments.len(), 2);
+    /// ```
+    pub fn set_array(&mut self, key: impl Into<String>, elements: Vec<HashMap<String, String>>) {
+        self.arrays.insert(key.into(), elements);
+    }
+
+    /// Get an array variable
+    pub fn get_array(&self, key: &str) -> Option<&Vec<HashMap<String, String>>> {
+        self.arrays.get(key)
+    }
+
     /// Create a message from a template
     pub fn create_message(&self, template: &MessageTemplate) -> Result<Message> {
         // Merge template default variables with instance variables
@@ -43,19 +88,81 @@ impl TemplateEngine {
         let mut segments = Vec::new();
 
         for seg_template in &template.segments {
+            if let Some(array_name) = &seg_template.repeat_over {
+                let elements = self.arrays.get(array_name).map(Vec::as_slice).unwrap_or_default();
+                if elements.is_empty() && seg_template.required {
+                    return Err(Error::substitution(format!(
+                        "Required segment {} has no elements in array '{}'",
+                        seg_template.id, array_name
+                    )));
+                }
+
+                for (index, element) in elements.iter().enumerate() {
+                    let mut element_vars = all_vars.clone();
+                    element_vars.insert("index".to_string(), (index + 1).to_string());
+                    element_vars.extend(element.iter().map(|(k, v)| (k.clone(), v.clone())));
+
+                    if self.condition_holds(seg_template, &element_vars)? {
+                        segments.push(self.create_segment(seg_template, &element_vars)?);
+                    }
+                }
+                continue;
+            }
+
+            if !self.condition_holds(seg_template, &all_vars)? {
+                continue;
+            }
+
             let segment = self.create_segment(seg_template, &all_vars)?;
             segments.push(segment);
         }
 
         // Create the message
-        let message = Message {
-            segments,
-            delimiters: Delimiters::default(),
-        };
+        let mut message = Message::with_delimiters(Delimiters::default());
+        message.segments = segments;
 
         Ok(message)
     }
 
+    /// Evaluate a segment's condition; segments without one are always emitted
+    ///
+    /// Supported forms are `variable` (set and non-empty), `variable == value`
+    /// and `variable != value`. Values may be quoted.
+    fn condition_holds(
+        &self,
+        seg_template: &crate::SegmentTemplate,
+        variables: &HashMap<String, String>,
+    ) -> Result<bool> {
+        let Some(condition) = &seg_template.condition else {
+            return Ok(true);
+        };
+
+        let (name, expected) = if let Some((name, value)) = condition.split_once("==") {
+            (name, Some((value, true)))
+        } else if let Some((name, value)) = condition.split_once("!=") {
+            (name, Some((value, false)))
+        } else {
+            (condition.as_str(), None)
+        };
+
+        let name = name.trim();
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err(Error::parse(format!(
+                "Invalid condition '{}' on segment {}",
+                condition, seg_template.id
+            )));
+        }
+
+        let actual = variables.get(name).map(String::as_str);
+        Ok(match expected {
+            None => actual.is_some_and(|v| !v.is_empty()),
+            Some((value, equal)) => {
+                let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+                (actual.unwrap_or_default() == value) == equal
+            }
+        })
+    }
+
     /// Create a segment from a segment template
     fn create_segment(
         &self,
@@ -126,7 +233,7 @@ impl TemplateEngine {
                 self.substitute_variables(placeholder, variables)?
             } else if let Some(default) = &field_template.default {
                 default.clone()
-            } else if field_template.required {
+            } else if field_template.is_required() {
                 return Err(Error::substitution(
                     "Required field has no placeholder or default value",
                 ));
@@ -324,6 +431,150 @@ mod tests {
         assert_eq!(msg.segments[0].fields[4].value(), Some("DefaultHospital"));
     }
 
+    fn observation(id: &str, value: &str) -> HashMap<String, String> {
+        HashMap::from([
+            ("observation_id".to_string(), id.to_string()),
+            ("observation_value".to_string(), value.to_string()),
+        ])
+    }
+
+    fn repeating_obx_template() -> MessageTemplate {
+        let mut template = MessageTemplate::new("Test", "2.5", "ORU", "R01");
+        template.add_segment(
+            SegmentTemplate::new("MSH").with_field(3, FieldTemplate::new().with_placeholder("{{sending_app}}")),
+        );
+        template.add_segment(
+            SegmentTemplate::new("OBX")
+                .required()
+                .repeat_over("observations")
+                .with_field(1, FieldTemplate::new().with_placeholder("{{index}}"))
+                .with_field(3, FieldTemplate::new().with_placeholder("{{observation_id}}"))
+                .with_field(5, FieldTemplate::new().with_placeholder("{{observation_value}}"))
+                .with_field(11, FieldTemplate::new().with_placeholder("{{status}}")),
+        );
+        template
+    }
+
+    #[test]
+    fn test_repeat_over_array() {
+        let mut engine = TemplateEngine::new();
+        engine.set_variable("sending_app", "LAB");
+        engine.set_variable("status", "F");
+        engine.set_array(
+            "observations",
+            vec![
+                observation("8480-6", "120"),
+                observation("8462-4", "80"),
+                observation("8867-4", "72"),
+            ],
+        );
+
+        let msg = engine.create_message(&repeating_obx_template()).unwrap();
+
+        assert_eq!(msg.segments.len(), 4);
+        assert_eq!(msg.segments[0].id, "MSH");
+        for (i, (id, value)) in [("8480-6", "120"), ("8462-4", "80"), ("8867-4", "72")].iter().enumerate() {
+            let obx = &msg.segments[i + 1];
+            assert_eq!(obx.id, "OBX");
+            assert_eq!(obx.fields[1].value(), Some((i + 1).to_string().as_str()));
+            assert_eq!(obx.fields[3].value(), Some(*id));
+            assert_eq!(obx.fields[5].value(), Some(*value));
+            assert_eq!(obx.fields[11].value(), Some("F"));
+        }
+    }
+
+    #[test]
+    fn test_repeat_over_element_overrides_variable() {
+        let mut engine = TemplateEngine::new();
+        engine.set_variable("sending_app", "LAB");
+        engine.set_variable("status", "F");
+
+        let mut corrected = observation("8480-6", "125");
+        corrected.insert("status".to_string(), "C".to_string());
+        engine.set_array("observations", vec![corrected, observation("8462-4", "80")]);
+
+        let msg = engine.create_message(&repeating_obx_template()).unwrap();
+        assert_eq!(msg.segments[1].fields[11].value(), Some("C"));
+        assert_eq!(msg.segments[2].fields[11].value(), Some("F"));
+    }
+
+    #[test]
+    fn test_repeat_over_empty_array() {
+        let mut template = repeating_obx_template();
+        let mut engine = TemplateEngine::new();
+        engine.set_variable("sending_app", "LAB");
+
+        // Required repeating segment with nothing to repeat over
+        assert!(engine.create_message(&template).is_err());
+
+        template.segments[1].required = false;
+        let msg = engine.create_message(&template).unwrap();
+        assert_eq!(msg.segments.len(), 1);
+    }
+
+    fn conditional_nk1_template(condition: &str) -> MessageTemplate {
+        let mut template = MessageTemplate::new("Test", "2.5", "ADT", "A01");
+        template.add_segment(SegmentTemplate::new("PID"));
+        template.add_segment(
+            SegmentTemplate::new("NK1")
+                .with_condition(condition)
+                .with_field(2, FieldTemplate::new().required().with_placeholder("{{next_of_kin}}")),
+        );
+        template
+    }
+
+    #[test]
+    fn test_condition_on_variable_presence() {
+        let template = conditional_nk1_template("next_of_kin");
+
+        let engine = TemplateEngine::new();
+        let msg = engine.create_message(&template).unwrap();
+        assert_eq!(msg.segments.len(), 1);
+
+        let mut engine = TemplateEngine::new();
+        engine.set_variable("next_of_kin", "Doe^Jane");
+        let msg = engine.create_message(&template).unwrap();
+        assert_eq!(msg.segments.len(), 2);
+        assert_eq!(msg.segments[1].fields[2].value(), Some("Doe^Jane"));
+
+        let mut engine = TemplateEngine::new();
+        engine.set_variable("next_of_kin", "");
+        assert_eq!(engine.create_message(&template).unwrap().segments.len(), 1);
+    }
+
+    #[test]
+    fn test_condition_comparison() {
+        let template = conditional_nk1_template("include_nk1 == \"Y\"");
+        let mut engine = TemplateEngine::new();
+        engine.set_variable("next_of_kin", "Doe^Jane");
+
+        engine.set_variable("include_nk1", "N");
+        assert_eq!(engine.create_message(&template).unwrap().segments.len(), 1);
+
+        engine.set_variable("include_nk1", "Y");
+        assert_eq!(engine.create_message(&template).unwrap().segments.len(), 2);
+
+        let template = conditional_nk1_template("include_nk1 != N");
+        assert_eq!(engine.create_message(&template).unwrap().segments.len(), 2);
+    }
+
+    #[test]
+    fn test_condition_included_segment_still_requires_variables() {
+        let template = conditional_nk1_template("include_nk1 == Y");
+        let mut engine = TemplateEngine::new();
+        engine.set_variable("include_nk1", "Y");
+
+        let result = engine.create_message(&template);
+        assert!(result.unwrap_err().to_string().contains("Variable 'next_of_kin' not found"));
+    }
+
+    #[test]
+    fn test_invalid_condition() {
+        let template = conditional_nk1_template("== Y");
+        let engine = TemplateEngine::new();
+        assert!(engine.create_message(&template).is_err());
+    }
+
     #[test]
     fn test_engine_variables_override_template_defaults() {
         let mut template = MessageTemplate::new("Test", "2.5", "ADT", "A01");
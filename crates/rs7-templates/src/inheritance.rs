@@ -1,9 +1,55 @@
 //! Template inheritance support.
 
-use crate::{Error, MessageTemplate, Result, SegmentTemplate};
+use crate::{Error, FieldTemplate, MessageTemplate, Result, SegmentTemplate};
 use std::collections::HashMap;
 
 /// Template resolver for handling inheritance
+///
+/// A derived template is merged into its base, the derived template winning
+/// every conflict:
+///
+/// - segments with a new ID are appended;
+/// - segments with a base ID override the segment's `required` and
+///   `repeating` flags, keep the base `condition`/`repeat_over` unless the
+///   derived segment sets them, and have their fields merged one by one:
+///   field properties set in the derived template (including `required`)
+///   replace the base's and unset ones are inherited, so a derived field can
+///   change just a placeholder;
+/// - segments marked [`removed`](SegmentTemplate::removed) drop the base
+///   segment with that ID, even if the base requires it.
+///
+/// # Example
+///
+/// ```
+/// use rs7_templates::{FieldTemplate, MessageTemplate, SegmentTemplate, TemplateResolver};
+///
+/// let base = MessageTemplate::new("BaseADT", "2.5", "ADT", "A01")
+///     .with_segment(SegmentTemplate::new("PID").required().with_field(
+///         3,
+///         FieldTemplate::new().required().with_datatype("CX").with_placeholder("{{mrn}}"),
+///     ))
+///     .with_segment(SegmentTemplate::new("PV1").required());
+///
+/// // Same PID-3 with another placeholder, and no PV1
+/// let derived = MessageTemplate::new("LabADT", "2.5", "ADT", "A01")
+///     .with_extends("BaseADT")
+///     .with_segment(SegmentTemplate::new("PID").required().with_field(
+///         3,
+///         FieldTemplate::new().with_placeholder("{{lab_id}}"),
+///     ))
+///     .with_segment(SegmentTemplate::new("PV1").removed());
+///
+/// let mut resolver = TemplateResolver::new();
+/// resolver.register(base);
+/// resolver.register(derived);
+///
+/// let resolved = resolver.resolve("LabADT").unwrap();
+/// assert!(resolved.segments.iter().all(|s| s.id != "PV1"));
+/// let pid_3 = &resolved.segments[0].fields.as_ref().unwrap()[&3];
+/// assert_eq!(pid_3.placeholder.as_deref(), Some("{{lab_id}}"));
+/// assert_eq!(pid_3.datatype.as_deref(), Some("CX"));
+/// assert!(pid_3.is_required());
+/// ```
 pub struct TemplateResolver {
     /// Template registry
     templates: HashMap<String, MessageTemplate>,
@@ -58,9 +104,11 @@ impl TemplateResolver {
             .get(name)
             .ok_or_else(|| Error::not_found(format!("Template '{}' not found", name)))?;
 
-        // If no base template, return as-is
+        // If no base template, return as-is (without removal markers)
         let Some(base_name) = &template.extends else {
-            return Ok(template.clone());
+            let mut template = template.clone();
+            template.segments.retain(|s| !s.remove);
+            return Ok(template);
         };
 
         // Track this template in the visit chain
@@ -122,6 +170,12 @@ impl TemplateResolver {
         let mut merged = base_segments.to_vec();
 
         for child_seg in child_segments {
+            // Drop removed segments from the base
+            if child_seg.remove {
+                merged.retain(|s| s.id != child_seg.id);
+                continue;
+            }
+
             // Find if this segment already exists in base
             if let Some(pos) = merged.iter().position(|s| s.id == child_seg.id) {
                 // Override the base segment
@@ -145,6 +199,14 @@ impl TemplateResolver {
         // Child properties override
         merged.required = child.required;
         merged.repeating = child.repeating;
+        if child.repeat_over.is_some() {
+            merged.repeat_over = child.repeat_over.clone();
+        }
+        if child.condition.is_some() {
+            merged.condition = child.condition.clone();
+        }
+        // An inherited repeat_over still implies repetition
+        merged.repeating |= merged.repeat_over.is_some();
 
         if child.description.is_some() {
             merged.description = child.description.clone();
@@ -154,13 +216,31 @@ impl TemplateResolver {
         if let Some(child_fields) = &child.fields {
             let mut fields = base.fields.clone().unwrap_or_default();
             for (pos, field) in child_fields {
-                fields.insert(*pos, field.clone());
+                let field = match fields.get(pos) {
+                    Some(base_field) => Self::merge_field_templates(base_field, field),
+                    None => field.clone(),
+                };
+                fields.insert(*pos, field);
             }
             merged.fields = Some(fields);
         }
 
         merged
     }
+
+    /// Merge two field templates (properties set in child override base)
+    fn merge_field_templates(base: &FieldTemplate, child: &FieldTemplate) -> FieldTemplate {
+        FieldTemplate {
+            required: child.required.or(base.required),
+            datatype: child.datatype.clone().or_else(|| base.datatype.clone()),
+            length: child.length.or(base.length),
+            conformance_length: child.conformance_length.or(base.conformance_length),
+            placeholder: child.placeholder.clone().or_else(|| base.placeholder.clone()),
+            default: child.default.clone().or_else(|| base.default.clone()),
+            description: child.description.clone().or_else(|| base.description.clone()),
+            components: child.components.clone().or_else(|| base.components.clone()),
+        }
+    }
 }
 
 impl Default for TemplateResolver {
@@ -230,6 +310,150 @@ mod tests {
         assert_eq!(field3.placeholder, Some("{{child_id}}".to_string()));
     }
 
+    #[test]
+    fn test_override_field_keeps_base_properties() {
+        let mut resolver = TemplateResolver::new();
+
+        let mut base = MessageTemplate::new("BaseADT", "2.5", "ADT", "A01");
+        let mut pid_base = SegmentTemplate::new("PID").required();
+        pid_base.add_field(
+            5,
+            FieldTemplate::new()
+                .required()
+                .with_datatype("XPN")
+                .with_length(250)
+                .with_placeholder("{{patient_name}}"),
+        );
+        pid_base.add_field(8, FieldTemplate::new().required().with_placeholder("{{sex}}"));
+        base.add_segment(pid_base);
+
+        // Child only changes the placeholder of PID-5 and relaxes PID-8
+        let mut child = MessageTemplate::new("ExtendedADT", "2.5", "ADT", "A01")
+            .with_extends("BaseADT");
+        let mut pid_child = SegmentTemplate::new("PID").required();
+        pid_child.add_field(5, FieldTemplate::new().with_placeholder("{{legal_name}}"));
+        pid_child.add_field(8, FieldTemplate::new().optional());
+        child.add_segment(pid_child);
+
+        resolver.register(base);
+        resolver.register(child);
+
+        let resolved = resolver.resolve("ExtendedADT").unwrap();
+        let fields = resolved.segments[0].fields.as_ref().unwrap();
+
+        let field5 = fields.get(&5).unwrap();
+        assert_eq!(field5.placeholder, Some("{{legal_name}}".to_string()));
+        assert_eq!(field5.datatype, Some("XPN".to_string()));
+        assert_eq!(field5.length, Some(250));
+        assert!(field5.is_required());
+
+        // An explicit override still wins over the base
+        let field8 = fields.get(&8).unwrap();
+        assert_eq!(field8.placeholder, Some("{{sex}}".to_string()));
+        assert!(!field8.is_required());
+    }
+
+    #[test]
+    fn test_override_segment_keeps_condition_and_repeat_over() {
+        let mut resolver = TemplateResolver::new();
+
+        let mut base = MessageTemplate::new("BaseORU", "2.5", "ORU", "R01");
+        base.add_segment(
+            SegmentTemplate::new("OBX")
+                .repeat_over("results")
+                .with_condition("results"),
+        );
+
+        let mut child = MessageTemplate::new("ExtendedORU", "2.5", "ORU", "R01")
+            .with_extends("BaseORU");
+        let mut obx_child = SegmentTemplate::new("OBX");
+        obx_child.add_field(5, FieldTemplate::new().with_placeholder("{{value}}"));
+        child.add_segment(obx_child);
+
+        resolver.register(base);
+        resolver.register(child);
+
+        let resolved = resolver.resolve("ExtendedORU").unwrap();
+        let obx = &resolved.segments[0];
+        assert_eq!(obx.repeat_over, Some("results".to_string()));
+        assert_eq!(obx.condition, Some("results".to_string()));
+        assert!(obx.repeating);
+        assert!(obx.fields.as_ref().unwrap().contains_key(&5));
+    }
+
+    #[test]
+    fn test_remove_segment() {
+        let mut resolver = TemplateResolver::new();
+
+        let base = MessageTemplate::new("BaseADT", "2.5", "ADT", "A01")
+            .with_segment(SegmentTemplate::new("MSH").required())
+            .with_segment(SegmentTemplate::new("PID").required())
+            .with_segment(SegmentTemplate::new("PV1").required());
+
+        // Removes a required base segment and a segment the base doesn't have
+        let child = MessageTemplate::new("NoVisitADT", "2.5", "ADT", "A01")
+            .with_extends("BaseADT")
+            .with_segment(SegmentTemplate::new("PV1").removed())
+            .with_segment(SegmentTemplate::new("NK1").removed());
+
+        // A grandchild can add the segment back
+        let grandchild = MessageTemplate::new("VisitADT", "2.5", "ADT", "A01")
+            .with_extends("NoVisitADT")
+            .with_segment(SegmentTemplate::new("PV1"));
+
+        resolver.register(base);
+        resolver.register(child);
+        resolver.register(grandchild);
+
+        let resolved = resolver.resolve("NoVisitADT").unwrap();
+        let ids: Vec<&str> = resolved.segments.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["MSH", "PID"]);
+
+        let resolved = resolver.resolve("VisitADT").unwrap();
+        let pv1 = resolved.segments.iter().find(|s| s.id == "PV1").unwrap();
+        assert!(!pv1.required);
+    }
+
+    #[test]
+    fn test_remove_segment_from_yaml() {
+        let yaml = r#"
+name: "NoVisitADT"
+version: "2.5"
+message_type: "ADT"
+trigger_event: "A01"
+extends: "BaseADT"
+segments:
+  - id: "PV1"
+    remove: true
+  - id: "PID"
+    required: true
+    fields:
+      3:
+        placeholder: "{{lab_id}}"
+"#;
+        let child = MessageTemplate::from_yaml(yaml).unwrap();
+        assert!(child.segments[0].remove);
+        assert!(!child.segments[1].remove);
+
+        let mut base = MessageTemplate::new("BaseADT", "2.5", "ADT", "A01")
+            .with_segment(SegmentTemplate::new("PID").required())
+            .with_segment(SegmentTemplate::new("PV1").required());
+        base.segments[0].add_field(3, FieldTemplate::new().with_datatype("CX"));
+
+        let mut resolver = TemplateResolver::new();
+        resolver.register(base);
+        resolver.register(child);
+
+        let resolved = resolver.resolve("NoVisitADT").unwrap();
+        assert_eq!(resolved.segments.len(), 1);
+        let field3 = resolved.segments[0].fields.as_ref().unwrap().get(&3).unwrap();
+        assert_eq!(field3.placeholder, Some("{{lab_id}}".to_string()));
+        assert_eq!(field3.datatype, Some("CX".to_string()));
+
+        // Removal markers are not serialized for ordinary segments
+        assert!(!resolved.to_yaml().unwrap().contains("remove"));
+    }
+
     #[test]
     fn test_merge_variables() {
         let mut resolver = TemplateResolver::new();
@@ -8,7 +8,7 @@
 //!
 //! - **Message Templates**: Reusable message patterns with segment and field definitions
 //! - **Template Engine**: Create messages from templates with variable substitution
-//! - **Template Validation**: Validate messages against template definitions
+//! - **Template Validation**: Validate messages against template definitions, including field data types
 //! - **YAML/JSON Support**: Load and save templates from/to YAML and JSON files
 //! - **Standard Library**: Pre-built templates for common message types (ADT, ORU, ORM, etc.)
 //! - **Template Inheritance**: Extend base templates to create specialized variants
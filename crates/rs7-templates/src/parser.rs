@@ -273,15 +273,37 @@ segments:
         assert!(fields.contains_key(&7));
 
         let field5 = &fields[&5];
-        assert!(field5.required);
+        assert!(field5.is_required());
         assert_eq!(field5.placeholder, Some("{{patient_name}}".to_string()));
         assert_eq!(field5.datatype, Some("XPN".to_string()));
 
         let field7 = &fields[&7];
-        assert!(!field7.required);
+        assert!(!field7.is_required());
         assert_eq!(field7.default, Some("19900101".to_string()));
     }
 
+    #[test]
+    fn test_yaml_with_repeat_over() {
+        let yaml = r#"
+name: "ORU with Observations"
+version: "2.5"
+message_type: "ORU"
+trigger_event: "R01"
+segments:
+  - id: "OBX"
+    repeating: true
+    repeat_over: "observations"
+    fields:
+      1:
+        placeholder: "{{index}}"
+"#;
+
+        let template = MessageTemplate::from_yaml(yaml).unwrap();
+        let obx = &template.segments[0];
+        assert!(obx.repeating);
+        assert_eq!(obx.repeat_over, Some("observations".to_string()));
+    }
+
     #[test]
     fn test_yaml_with_variables() {
         let yaml = r#"
@@ -105,6 +105,20 @@ pub struct SegmentTemplate {
     #[serde(default)]
     pub repeating: bool,
 
+    /// Name of the array variable to emit one segment per element from
+    ///
+    /// Each element's fields are available as `{{field}}` placeholders while
+    /// its segment is built, together with its 1-based `{{index}}`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub repeat_over: Option<String>,
+
+    /// Condition under which the segment is emitted
+    ///
+    /// Either a variable name (`next_of_kin`), true when the variable is set
+    /// and non-empty, or a comparison (`patient_class == I`, `status != F`).
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub condition: Option<String>,
+
     /// Field templates (keyed by field position)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fields: Option<HashMap<usize, FieldTemplate>>,
@@ -112,6 +126,13 @@ pub struct SegmentTemplate {
     /// Segment description
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+
+    /// Whether a derived template removes this segment from its base
+    ///
+    /// Resolving the template drops the base segment with the same ID, even a
+    /// required one; the marker itself never appears in a resolved template.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub remove: bool,
 }
 
 impl SegmentTemplate {
@@ -121,8 +142,11 @@ impl SegmentTemplate {
             id: id.into(),
             required: false,
             repeating: false,
+            repeat_over: None,
+            condition: None,
             fields: None,
             description: None,
+            remove: false,
         }
     }
 
@@ -138,12 +162,35 @@ impl SegmentTemplate {
         self
     }
 
+    /// Repeat the segment once per element of an array variable
+    ///
+    /// Also marks the segment as repeating.
+    pub fn repeat_over(mut self, variable: impl Into<String>) -> Self {
+        self.repeating = true;
+        self.repeat_over = Some(variable.into());
+        self
+    }
+
+    /// Only emit the segment when `condition` holds
+    pub fn with_condition(mut self, condition: impl Into<String>) -> Self {
+        self.condition = Some(condition.into());
+        self
+    }
+
     /// Set segment description
     pub fn with_description(mut self, description: impl Into<String>) -> Self {
         self.description = Some(description.into());
         self
     }
 
+    /// Mark the segment as removed from the base template
+    ///
+    /// See [`TemplateResolver`](crate::TemplateResolver).
+    pub fn removed(mut self) -> Self {
+        self.remove = true;
+        self
+    }
+
     /// Add a field template
     pub fn add_field(&mut self, position: usize, field: FieldTemplate) {
         self.fields
@@ -163,11 +210,17 @@ impl SegmentTemplate {
 /// A field template
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FieldTemplate {
-    /// Whether the field is required
-    #[serde(default)]
-    pub required: bool,
-
-    /// Field data type (optional)
+    /// Whether the field is required, `None` if not specified
+    ///
+    /// Unspecified fields are optional, but in a child template they inherit
+    /// the base template's setting; see [`TemplateResolver`](crate::TemplateResolver).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+
+    /// Field data type (optional), e.g. "DT" or "XPN"
+    ///
+    /// [`TemplateValidator`](crate::TemplateValidator) checks values against
+    /// the format of this type.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub datatype: Option<String>,
 
@@ -175,6 +228,13 @@ pub struct FieldTemplate {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub length: Option<usize>,
 
+    /// Whether `length` is a conformance length, `None` if not specified
+    ///
+    /// Values longer than a conformance length are validation errors; other
+    /// maximum lengths only produce warnings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conformance_length: Option<bool>,
+
     /// Placeholder value or variable reference (e.g., "{{patient_name}}")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub placeholder: Option<String>,
@@ -196,9 +256,10 @@ impl FieldTemplate {
     /// Create a new field template
     pub fn new() -> Self {
         Self {
-            required: false,
+            required: None,
             datatype: None,
             length: None,
+            conformance_length: None,
             placeholder: None,
             default: None,
             description: None,
@@ -208,10 +269,24 @@ impl FieldTemplate {
 
     /// Mark field as required
     pub fn required(mut self) -> Self {
-        self.required = true;
+        self.required = Some(true);
         self
     }
 
+    /// Mark field as explicitly optional
+    ///
+    /// Only differs from leaving `required` unset in a child template, where
+    /// it overrides a base template that requires the field.
+    pub fn optional(mut self) -> Self {
+        self.required = Some(false);
+        self
+    }
+
+    /// Check whether the field is required
+    pub fn is_required(&self) -> bool {
+        self.required.unwrap_or(false)
+    }
+
     /// Set field data type
     pub fn with_datatype(mut self, datatype: impl Into<String>) -> Self {
         self.datatype = Some(datatype.into());
@@ -224,6 +299,18 @@ impl FieldTemplate {
         self
     }
 
+    /// Set a conformance length, which values must not exceed
+    pub fn with_conformance_length(mut self, length: usize) -> Self {
+        self.length = Some(length);
+        self.conformance_length = Some(true);
+        self
+    }
+
+    /// Check whether the maximum length is a conformance length
+    pub fn is_conformance_length(&self) -> bool {
+        self.conformance_length.unwrap_or(false)
+    }
+
     /// Set placeholder value
     pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
         self.placeholder = Some(placeholder.into());
@@ -340,9 +427,18 @@ mod tests {
         assert_eq!(segment.id, "PID");
         assert!(segment.required);
         assert!(!segment.repeating);
+        assert!(segment.repeat_over.is_none());
         assert_eq!(segment.description, Some("Patient identification".to_string()));
     }
 
+    #[test]
+    fn test_segment_template_repeat_over() {
+        let segment = SegmentTemplate::new("OBX").repeat_over("observations");
+
+        assert!(segment.repeating);
+        assert_eq!(segment.repeat_over, Some("observations".to_string()));
+    }
+
     #[test]
     fn test_field_template_creation() {
         let field = FieldTemplate::new()
@@ -351,7 +447,7 @@ mod tests {
             .with_placeholder("{{patient_name}}")
             .with_length(250);
 
-        assert!(field.required);
+        assert!(field.is_required());
         assert_eq!(field.datatype, Some("XPN".to_string()));
         assert_eq!(field.placeholder, Some("{{patient_name}}".to_string()));
         assert_eq!(field.length, Some(250));
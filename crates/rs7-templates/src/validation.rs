@@ -1,7 +1,9 @@
 //! Template validation for messages.
 
 use crate::{MessageTemplate};
+use rs7_core::types::DataType;
 use rs7_core::Message;
+use rs7_validator::validate_data_type;
 
 /// Validation result for a message against a template
 #[derive(Debug, Clone)]
@@ -101,6 +103,11 @@ impl ValidationWarning {
 }
 
 /// Validator for messages against templates
+///
+/// Besides segment and field presence, field templates with a `datatype` have
+/// their values checked with the same format rules as
+/// [`rs7_validator::Validator`], so a template can constrain a message without
+/// a separate schema. Data types without format rules are not checked.
 pub struct TemplateValidator;
 
 impl TemplateValidator {
@@ -191,7 +198,7 @@ impl TemplateValidator {
 
             // Check if field exists
             if *pos >= segment.fields.len() {
-                if field_template.required {
+                if field_template.is_required() {
                     result.add_error(
                         ValidationError::new("Required field not found").with_location(&location),
                     );
@@ -204,24 +211,33 @@ impl TemplateValidator {
             // Check if field is empty
             let is_empty = field.is_empty();
 
-            if is_empty && field_template.required {
+            if is_empty && field_template.is_required() {
                 result.add_error(
                     ValidationError::new("Required field is empty").with_location(&location),
                 );
             }
 
+            // Check field data type if specified
+            if let (Some(datatype), Some(value)) = (&field_template.datatype, field.value())
+                && let Some(data_type) = DataType::from_str(datatype)
+                && let Some(reason) = validate_data_type(value, data_type).error_message()
+            {
+                result.add_error(
+                    ValidationError::new(format!("Invalid {} format: {}", datatype, reason))
+                        .with_location(&location),
+                );
+            }
+
             // Check field length if specified
             if let Some(max_length) = field_template.length {
                 if let Some(value) = field.value() {
                     if value.len() > max_length {
-                        result.add_warning(
-                            ValidationWarning::new(format!(
-                                "Field length {} exceeds maximum {}",
-                                value.len(),
-                                max_length
-                            ))
-                            .with_location(&location),
-                        );
+                        let message = format!("Field length {} exceeds maximum {}", value.len(), max_length);
+                        if field_template.is_conformance_length() {
+                            result.add_error(ValidationError::new(message).with_location(&location));
+                        } else {
+                            result.add_warning(ValidationWarning::new(message).with_location(&location));
+                        }
                     }
                 }
             }
@@ -233,38 +249,37 @@ impl TemplateValidator {
 mod tests {
     use super::*;
     use crate::{FieldTemplate, SegmentTemplate};
-    use rs7_core::{Delimiters, Field, Segment};
+    use rs7_core::{Field, Segment};
 
     fn create_test_message() -> Message {
-        Message {
-            delimiters: Delimiters::default(),
-            segments: vec![
-                Segment {
-                    id: "MSH".to_string(),
-                    fields: vec![
-                        Field::from_value("MSH"),
-                        Field::from_value("|"),
-                        Field::from_value("^~\\&"),
-                        Field::from_value("APP"),
-                        Field::from_value("FACILITY"),
-                        Field::new(),
-                        Field::new(),
-                        Field::new(),
-                        Field::new(),
-                        Field::from_value("ADT^A01"),
-                    ],
-                },
-                Segment {
-                    id: "PID".to_string(),
-                    fields: vec![
-                        Field::from_value("PID"),
-                        Field::new(),
-                        Field::new(),
-                        Field::from_value("12345"),
-                    ],
-                },
-            ],
-        }
+        let mut message = Message::new();
+        message.segments = vec![
+            Segment {
+                id: "MSH".to_string(),
+                fields: vec![
+                    Field::from_value("MSH"),
+                    Field::from_value("|"),
+                    Field::from_value("^~\\&"),
+                    Field::from_value("APP"),
+                    Field::from_value("FACILITY"),
+                    Field::new(),
+                    Field::new(),
+                    Field::new(),
+                    Field::new(),
+                    Field::from_value("ADT^A01"),
+                ],
+            },
+            Segment {
+                id: "PID".to_string(),
+                fields: vec![
+                    Field::from_value("PID"),
+                    Field::new(),
+                    Field::new(),
+                    Field::from_value("12345"),
+                ],
+            },
+        ];
+        message
     }
 
     #[test]
@@ -361,6 +376,52 @@ mod tests {
         assert!(result.valid); // Still valid, but has warning
         assert!(result.has_warnings());
         assert!(result.warnings.iter().any(|w| w.message.contains("exceeds maximum")));
+
+        // A conformance length is enforced
+        let mut template = MessageTemplate::new("Test", "2.5", "ADT", "A01");
+        let mut pid_segment = SegmentTemplate::new("PID");
+        pid_segment.add_field(3, FieldTemplate::new().with_conformance_length(20));
+        template.add_segment(pid_segment);
+
+        let result = TemplateValidator::validate(&message, &template);
+        assert!(!result.valid);
+        assert!(result.errors.iter().any(|e| e.location == Some("PID-3".to_string())));
+        assert!(!result.has_warnings());
+    }
+
+    #[test]
+    fn test_validate_field_datatype() {
+        let mut message = create_test_message();
+        message.segments[1].fields.extend([
+            Field::new(),
+            Field::new(),
+            Field::new(),
+            Field::from_value("19800230"),
+        ]);
+
+        let mut template = MessageTemplate::new("Test", "2.5", "ADT", "A01");
+        let mut pid_segment = SegmentTemplate::new("PID");
+        pid_segment.add_field(3, FieldTemplate::new().with_datatype("ST"));
+        pid_segment.add_field(7, FieldTemplate::new().with_datatype("DT"));
+        template.add_segment(pid_segment);
+
+        let result = TemplateValidator::validate(&message, &template);
+        assert!(!result.valid);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].location, Some("PID-7".to_string()));
+        assert!(result.errors[0].message.starts_with("Invalid DT format"));
+
+        // Valid values, empty values and unchecked types pass
+        message.segments[1].fields[7] = Field::from_value("19800229");
+        message.segments[1].fields[6] = Field::new();
+        let mut pid_segment = SegmentTemplate::new("PID");
+        pid_segment.add_field(6, FieldTemplate::new().with_datatype("NM"));
+        pid_segment.add_field(7, FieldTemplate::new().with_datatype("DT"));
+        pid_segment.add_field(3, FieldTemplate::new().with_datatype("XON"));
+        let template = MessageTemplate::new("Test", "2.5", "ADT", "A01").with_segment(pid_segment);
+
+        let result = TemplateValidator::validate(&message, &template);
+        assert!(result.valid, "{:?}", result.errors);
     }
 
     #[test]
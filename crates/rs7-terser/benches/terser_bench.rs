@@ -1,6 +1,6 @@
 use criterion::{criterion_group, criterion_main, Criterion, BenchmarkId};
 use rs7_parser::parse_message;
-use rs7_terser::Terser;
+use rs7_terser::{BulkTerser, Terser};
 use std::hint::black_box;
 
 const TEST_MESSAGE: &str = r"MSH|^~\&|SendApp|SendFac|RecApp|RecFac|20240315143000||ADT^A01|12345|P|2.5
@@ -92,12 +92,46 @@ fn bench_terser_path_parsing(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_extract_batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("extract_batch_1000x20");
+
+    let messages: Vec<_> = (0..1000)
+        .map(|i| parse_message(&TEST_MESSAGE.replace("|12345|P|", &format!("|{}|P|", i))).unwrap())
+        .collect();
+    let paths = [
+        "MSH-3", "MSH-4", "MSH-7", "MSH-9-1", "MSH-9-2", "MSH-10", "PID-2", "PID-3-1", "PID-5-1", "PID-5-2",
+        "PID-7", "PID-8", "PID-11-1", "PID-11-3", "PID-11-4", "PV1-2", "PV1-3-1", "OBX-3-1", "OBX(2)-5", "OBX(2)-6",
+    ];
+
+    group.bench_function("terser_get_per_path", |b| {
+        b.iter(|| {
+            messages
+                .iter()
+                .map(|message| {
+                    let terser = Terser::new(message);
+                    paths
+                        .iter()
+                        .map(|path| terser.get(path).ok().flatten().map(str::to_string))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+
+    group.bench_function("bulk_extract_batch", |b| {
+        b.iter(|| BulkTerser::extract_batch(black_box(&messages), black_box(&paths)).unwrap())
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_terser_get_simple,
     bench_terser_get_component,
     bench_terser_get_indexed_segment,
     bench_terser_multiple_gets,
-    bench_terser_path_parsing
+    bench_terser_path_parsing,
+    bench_extract_batch
 );
 criterion_main!(benches);
@@ -4,11 +4,16 @@
 //!
 //! This module provides the `BulkTerser` which allows extracting multiple
 //! field values in a single operation, as well as pattern-based extraction
-//! for repeating segments.
+//! for repeating segments. [`BulkTerser::extract_batch`] applies the same
+//! paths to many messages, in parallel with the `rayon` feature.
 
-use crate::Terser;
+use crate::{path::TerserPath, Terser};
+use rs7_core::Message;
 use std::collections::HashMap;
 
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
 /// Bulk terser for extracting multiple fields efficiently
 ///
 /// # Examples
@@ -85,6 +90,57 @@ impl<'a> BulkTerser<'a> {
         Ok(result)
     }
 
+    /// Extract the same paths from many messages
+    ///
+    /// Each path is parsed once and then applied to every message, which is
+    /// much faster than calling [`Terser::get`] per message and path. With
+    /// the `rayon` feature, messages are processed in parallel.
+    ///
+    /// Returns one row per message, with one value per path in the order
+    /// given. Each value equals `Terser::new(message).get(path).ok().flatten()`,
+    /// so values missing from a message (including a segment index past the
+    /// last matching segment) are `None`. Fails only if a path is malformed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs7_terser::BulkTerser;
+    /// use rs7_parser::parse_message;
+    ///
+    /// # fn main() -> rs7_core::Result<()> {
+    /// let messages = vec![
+    ///     parse_message("MSH|^~\\&|APP|FAC|||20250115||ADT^A01|1|P|2.5\rPID|1||PAT001||DOE^JOHN")?,
+    ///     parse_message("MSH|^~\\&|APP|FAC|||20250115||ADT^A01|2|P|2.5\rPID|1||PAT002")?,
+    /// ];
+    ///
+    /// let rows = BulkTerser::extract_batch(&messages, &["PID-3", "PID-5-1"])?;
+    /// assert_eq!(rows[0], vec![Some("PAT001".to_string()), Some("DOE".to_string())]);
+    /// assert_eq!(rows[1], vec![Some("PAT002".to_string()), None]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn extract_batch(messages: &[Message], paths: &[&str]) -> rs7_core::Result<Vec<Vec<Option<String>>>> {
+        let parsed = paths
+            .iter()
+            .map(|path| TerserPath::parse(path))
+            .collect::<rs7_core::Result<Vec<_>>>()?;
+
+        let extract = |message: &Message| -> Vec<Option<String>> {
+            let terser = Terser::new(message);
+            parsed
+                .iter()
+                .map(|path| terser.get_path(path).ok().flatten().map(str::to_string))
+                .collect()
+        };
+
+        #[cfg(feature = "rayon")]
+        let rows = messages.par_iter().map(extract).collect();
+        #[cfg(not(feature = "rayon"))]
+        let rows = messages.iter().map(extract).collect();
+
+        Ok(rows)
+    }
+
     /// Extract all values matching a glob pattern
     ///
     /// Supports patterns with wildcards for segment indices:
@@ -152,7 +208,7 @@ impl<'a> BulkTerser<'a> {
         let mut results = Vec::new();
 
         // Get all segments with this ID
-        let segments = self.terser.message.get_segments_by_id(segment_id);
+        let segments = self.terser.message.resolve_segments(segment_id);
 
         // Extract value from each segment
         for (idx, _segment) in segments.iter().enumerate() {
@@ -202,7 +258,7 @@ impl<'a> BulkTerser<'a> {
         let mut results = Vec::new();
 
         // Get the segment
-        let segments = self.terser.message.get_segments_by_id(segment_id);
+        let segments = self.terser.message.resolve_segments(segment_id);
         if segments.is_empty() || segment_idx >= segments.len() {
             return Ok(results);
         }
@@ -453,4 +509,33 @@ OBX|5|NM|CO2||24";
         assert_eq!(values.get("PID-5-1"), Some(&Some("DOE")));
         assert_eq!(values.get("PID-11-3"), Some(&Some("Boston")));
     }
+
+    #[test]
+    fn test_extract_batch_matches_terser() {
+        let messages: Vec<_> = [
+            "MSH|^~\\&|APP|FAC|||20250115||ORU^R01|1|P|2.5\rPID|1||PAT001||DOE^JOHN\rOBX|1|NM|GLU||98\rOBX|2|NM|NA||140",
+            "MSH|^~\\&|APP|FAC|||20250115||ORU^R01|2|P|2.5\rPID|1||PAT002~ALT||ROE\rOBX|1|NM|K||4.2",
+            "MSH|^~\\&|APP|FAC|||20250115||ORU^R01|3|P|2.5",
+        ]
+        .iter()
+        .map(|hl7| parse_message(hl7).unwrap())
+        .collect();
+        let paths = ["MSH-10", "PID-3(1)", "PID-5-2", "OBX(2)-5", "OBX-3-1", "ZZZ-1"];
+
+        let rows = BulkTerser::extract_batch(&messages, &paths).unwrap();
+        assert_eq!(rows.len(), 3);
+        for (message, row) in messages.iter().zip(&rows) {
+            let terser = Terser::new(message);
+            let expected: Vec<_> = paths
+                .iter()
+                .map(|path| terser.get(path).ok().flatten().map(str::to_string))
+                .collect();
+            assert_eq!(row, &expected);
+        }
+        assert_eq!(rows[1][1].as_deref(), Some("ALT"));
+        assert_eq!(rows[1][3], None);
+
+        assert!(BulkTerser::extract_batch(&messages, &["PID"]).is_err());
+        assert!(BulkTerser::extract_batch(&[], &paths).unwrap().is_empty());
+    }
 }
@@ -9,6 +9,7 @@ use rs7_core::{
     message::Message,
 };
 use crate::path::TerserPath;
+use crate::SegmentTerser;
 
 /// A cache entry for a terser path lookup
 #[derive(Debug, Clone)]
@@ -20,6 +21,14 @@ struct CacheEntry {
 }
 
 /// A caching terser that stores parsed paths and segment locations
+///
+/// Cached segment locations assume the message does not change between
+/// invalidations. The cache borrows the message immutably, so the borrow
+/// checker already rules out mutation through [`TerserMut`](crate::TerserMut)
+/// or [`Message::add_segment`] while it is alive: to modify the message, drop
+/// the `CachedTerser`, make the changes, and create a new one. Use
+/// [`invalidate`](Self::invalidate) or [`invalidate_path`](Self::invalidate_path)
+/// to drop entries early, e.g. to bound memory in long-lived caches.
 pub struct CachedTerser<'a> {
     message: &'a Message,
     /// Cache of path string -> cache entry
@@ -56,7 +65,7 @@ impl<'a> CachedTerser<'a> {
             let parsed_path = TerserPath::parse(path)?;
 
             // Find the segment
-            let segments = self.message.get_segments_by_id(&parsed_path.segment_id);
+            let segments = self.message.resolve_segments(&parsed_path.segment_id);
             if segments.is_empty() {
                 return Ok(None);
             }
@@ -64,7 +73,7 @@ impl<'a> CachedTerser<'a> {
             let actual_index = self.message.segments
                 .iter()
                 .enumerate()
-                .filter(|(_, s)| s.id == parsed_path.segment_id)
+                .filter(|(_, s)| self.message.segment_matches(s, &parsed_path.segment_id))
                 .nth(parsed_path.segment_index)
                 .map(|(idx, _)| idx);
 
@@ -83,60 +92,26 @@ impl<'a> CachedTerser<'a> {
 
         // Get the value using the cached entry
         let segment = &self.message.segments[entry.segment_index];
-        let field = match segment.get_field(entry.path.field_index) {
-            Some(f) => f,
-            None => return Ok(None),
-        };
+        Ok(SegmentTerser::new(segment).get_path(&entry.path))
+    }
 
-        Ok(self.get_field_value(
-            field,
-            entry.path.repetition_index,
-            entry.path.component_index,
-            entry.path.subcomponent_index,
-        ))
+    /// Clear the cache
+    pub fn clear_cache(&mut self) {
+        self.cache.clear();
     }
 
-    /// Get a field value at the specified indices
+    /// Drop all cached entries
     ///
-    /// Note: Component and subcomponent indices are 1-based (HL7 standard notation)
-    /// but internally converted to 0-based for array access.
-    #[inline]
-    fn get_field_value<'b>(
-        &self,
-        field: &'b rs7_core::field::Field,
-        rep_idx: usize,
-        comp_idx: Option<usize>,
-        sub_idx: Option<usize>,
-    ) -> Option<&'b str> {
-        let repetition = field.get_repetition(rep_idx)?;
-
-        match (comp_idx, sub_idx) {
-            (None, None) => repetition.value(),
-            (Some(c_idx), None) => {
-                // Convert 1-based HL7 to 0-based internal
-                if c_idx == 0 {
-                    return None; // Invalid: HL7 uses 1-based indexing
-                }
-                repetition.get_component(c_idx - 1)?.value()
-            }
-            (Some(c_idx), Some(s_idx)) => {
-                // Convert 1-based HL7 to 0-based internal
-                if c_idx == 0 || s_idx == 0 {
-                    return None; // Invalid: HL7 uses 1-based indexing
-                }
-                repetition
-                    .get_component(c_idx - 1)?
-                    .get_subcomponent(s_idx - 1)?
-                    .as_str()
-                    .into()
-            }
-            (None, Some(_)) => None,
-        }
+    /// The next access to each path parses it and locates its segment again.
+    pub fn invalidate(&mut self) {
+        self.clear_cache();
     }
 
-    /// Clear the cache
-    pub fn clear_cache(&mut self) {
-        self.cache.clear();
+    /// Drop the cached entry for one path
+    ///
+    /// Returns `true` if the path was cached.
+    pub fn invalidate_path(&mut self, path: &str) -> bool {
+        self.cache.remove(path).is_some()
     }
 
     /// Get the number of cached entries
@@ -221,4 +196,28 @@ mod tests {
         let _ = terser.get("PID-5").unwrap();
         assert_eq!(terser.cache_size(), 5);
     }
+
+    #[test]
+    fn test_invalidate() {
+        let hl7 = "MSH|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315||ADT^A01|12345|P|2.5\r\
+                   PID|1||MRN123||DOE^JOHN||19800101|M";
+
+        let mut message = parse_message(hl7).unwrap();
+        let mut terser = CachedTerser::new(&message);
+        terser.warm_cache(&["PID-5-1", "PID-7", "PID-8"]).unwrap();
+
+        assert!(terser.invalidate_path("PID-7"));
+        assert!(!terser.invalidate_path("PID-7"));
+        assert_eq!(terser.cache_size(), 2);
+        assert_eq!(terser.get("PID-7").unwrap(), Some("19800101"));
+
+        terser.invalidate();
+        assert_eq!(terser.cache_size(), 0);
+        assert_eq!(terser.get("PID-5-1").unwrap(), Some("DOE"));
+
+        // Mutation requires dropping the cache first; a new one sees the change
+        crate::TerserMut::new(&mut message).set("PID-5-1", "SMITH").unwrap();
+        let mut terser = CachedTerser::new(&message);
+        assert_eq!(terser.get("PID-5-1").unwrap(), Some("SMITH"));
+    }
 }
@@ -36,7 +36,12 @@
 //! # }
 //! ```
 
-use rs7_core::{message::Message, segment::Segment};
+use crate::path::TerserPath;
+use rs7_core::{
+    error::{Error, Result},
+    message::Message,
+    segment::Segment,
+};
 
 /// A segment group containing a header segment and child segments
 #[derive(Debug, Clone)]
@@ -368,6 +373,349 @@ impl<'a> Iterator for GroupIterator<'a> {
     }
 }
 
+/// Hierarchical group structure of a message type
+///
+/// Unlike [`GroupConfig`], which describes a single flat group, a schema
+/// nests groups the way the HL7 abstract message syntax does, e.g.
+/// ORDER_OBSERVATION → OBSERVATION in ORU^R01. Each group starts at its
+/// header segment; the root describes the message itself and has no header.
+#[derive(Debug, Clone)]
+pub struct GroupSchema {
+    /// Group name (e.g., "ORDER_OBSERVATION")
+    pub name: String,
+    /// Segment ID that starts a new instance of the group (`None` for the root)
+    pub header_id: Option<String>,
+    /// Other segment IDs that belong directly to the group
+    pub segment_ids: Vec<String>,
+    /// Nested groups
+    pub groups: Vec<GroupSchema>,
+}
+
+impl GroupSchema {
+    /// Create a root schema for a message structure (e.g., "ORU_R01")
+    pub fn message(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            header_id: None,
+            segment_ids: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    /// Create a group started by `header_id`
+    pub fn group(name: &str, header_id: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            header_id: Some(header_id.to_string()),
+            segment_ids: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    /// Set the segment IDs that belong directly to the group
+    pub fn with_segments(mut self, segment_ids: &[&str]) -> Self {
+        self.segment_ids = segment_ids.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    /// Add a nested group
+    pub fn with_group(mut self, group: GroupSchema) -> Self {
+        self.groups.push(group);
+        self
+    }
+
+    /// Group structure of ORU^R01
+    ///
+    /// The PATIENT_RESULT wrapper is flattened: PATIENT and ORDER_OBSERVATION
+    /// are direct children of the root.
+    pub fn oru_r01() -> Self {
+        Self::message("ORU_R01")
+            .with_segments(&["MSH", "SFT", "DSC"])
+            .with_group(Self::patient())
+            .with_group(
+                Self::group("ORDER_OBSERVATION", "OBR")
+                    .with_segments(&["NTE", "CTD", "FT1", "CTI"])
+                    .with_group(Self::group("TIMING_QTY", "TQ1").with_segments(&["TQ2"]))
+                    .with_group(Self::group("OBSERVATION", "OBX").with_segments(&["NTE"]))
+                    .with_group(Self::group("SPECIMEN", "SPM").with_segments(&["OBX"])),
+            )
+    }
+
+    /// Group structure of ORM^O01
+    pub fn orm_o01() -> Self {
+        Self::message("ORM_O01")
+            .with_segments(&["MSH", "NTE"])
+            .with_group(
+                Self::patient()
+                    .with_group(Self::group("INSURANCE", "IN1").with_segments(&["IN2", "IN3"])),
+            )
+            .with_group(
+                Self::group("ORDER", "ORC")
+                    .with_segments(&["FT1", "CTI", "BLG"])
+                    .with_group(
+                        Self::group("ORDER_DETAIL", "OBR")
+                            .with_segments(&["NTE", "CTD", "DG1"])
+                            .with_group(Self::group("OBSERVATION", "OBX").with_segments(&["NTE"])),
+                    ),
+            )
+    }
+
+    /// PATIENT group shared by the built-in schemas
+    fn patient() -> Self {
+        Self::group("PATIENT", "PID")
+            .with_segments(&["PD1", "NTE", "NK1", "GT1", "AL1"])
+            .with_group(Self::group("VISIT", "PV1").with_segments(&["PV2"]))
+    }
+
+    /// Check whether a segment continues an open instance of this group
+    ///
+    /// The group's own header is not included: it starts a new instance.
+    fn continues_with(&self, segment_id: &str) -> bool {
+        self.segment_ids.iter().any(|id| id == segment_id)
+            || self.groups.iter().any(|group| {
+                group.header_id.as_deref() == Some(segment_id) || group.continues_with(segment_id)
+            })
+    }
+}
+
+/// One occurrence of a schema group within a message
+#[derive(Debug, Clone)]
+pub struct GroupInstance<'a> {
+    /// Group name from the schema
+    pub name: String,
+    /// Segments belonging directly to this group, header first
+    pub segments: Vec<&'a Segment>,
+    /// Indices of those segments in the message
+    pub segment_indices: Vec<usize>,
+    /// Nested group instances, in message order
+    pub groups: Vec<GroupInstance<'a>>,
+}
+
+impl<'a> GroupInstance<'a> {
+    fn build(schema: &GroupSchema, message: &'a Message, indices: Vec<usize>) -> Self {
+        let mut instance = Self {
+            name: schema.name.clone(),
+            segments: Vec::new(),
+            segment_indices: Vec::new(),
+            groups: Vec::new(),
+        };
+
+        let mut indices = indices.into_iter();
+        if let Some(header) = schema.header_id.as_ref().and_then(|_| indices.next()) {
+            instance.push_segment(message, header);
+        }
+
+        // Open child instance: schema position and claimed segment indices
+        let mut open: Option<(usize, Vec<usize>)> = None;
+
+        for idx in indices {
+            let id = message.segments[idx].id.as_str();
+
+            if let Some((_, claimed)) = open
+                .as_mut()
+                .filter(|(child, _)| schema.groups[*child].continues_with(id))
+            {
+                claimed.push(idx);
+                continue;
+            }
+
+            if let Some((child, claimed)) = open.take() {
+                instance.groups.push(Self::build(&schema.groups[child], message, claimed));
+            }
+
+            match schema
+                .groups
+                .iter()
+                .position(|group| group.header_id.as_deref() == Some(id))
+            {
+                Some(child) => open = Some((child, vec![idx])),
+                None => instance.push_segment(message, idx),
+            }
+        }
+
+        if let Some((child, claimed)) = open {
+            instance.groups.push(Self::build(&schema.groups[child], message, claimed));
+        }
+
+        instance
+    }
+
+    fn push_segment(&mut self, message: &'a Message, idx: usize) {
+        self.segments.push(&message.segments[idx]);
+        self.segment_indices.push(idx);
+    }
+
+    /// Get the header segment (the first segment of the group)
+    pub fn header(&self) -> Option<&'a Segment> {
+        self.segments.first().copied()
+    }
+
+    /// Get the message index of the header segment
+    pub fn header_index(&self) -> Option<usize> {
+        self.segment_indices.first().copied()
+    }
+
+    /// Get the first segment with the given ID belonging directly to this group
+    pub fn segment(&self, segment_id: &str) -> Option<&'a Segment> {
+        self.segments.iter().find(|s| s.id == segment_id).copied()
+    }
+
+    /// Get nested group instances with the given name (direct children only)
+    pub fn groups(&self, name: &str) -> Vec<&GroupInstance<'a>> {
+        self.groups.iter().filter(|g| g.name == name).collect()
+    }
+
+    /// Find all group instances with the given name at any depth, in message order
+    pub fn find_all(&self, name: &str) -> Vec<&GroupInstance<'a>> {
+        let mut found = Vec::new();
+        for group in &self.groups {
+            if group.name == name {
+                found.push(group);
+            }
+            found.extend(group.find_all(name));
+        }
+        found
+    }
+
+    /// Get all segments of this group and its nested groups, in message order
+    pub fn all_segments(&self) -> Vec<&'a Segment> {
+        let mut segments: Vec<(usize, &'a Segment)> = Vec::new();
+        self.collect_segments(&mut segments);
+        segments.sort_by_key(|(idx, _)| *idx);
+        segments.into_iter().map(|(_, segment)| segment).collect()
+    }
+
+    fn collect_segments(&self, out: &mut Vec<(usize, &'a Segment)>) {
+        out.extend(self.segment_indices.iter().copied().zip(self.segments.iter().copied()));
+        for group in &self.groups {
+            group.collect_segments(out);
+        }
+    }
+}
+
+/// Hierarchical view of a message's segment groups
+///
+/// The grouping is computed from segment order and a [`GroupSchema`]; the
+/// message itself is not modified.
+///
+/// # Examples
+///
+/// ```rust
+/// use rs7_terser::{GroupSchema, MessageGroups};
+/// use rs7_parser::parse_message;
+///
+/// # fn main() -> rs7_core::Result<()> {
+/// let hl7 = r"MSH|^~\&|APP|FAC|||20250115||ORU^R01|123|P|2.5
+/// PID|1||PAT001||DOE^JOHN
+/// OBR|1||ORDER001|CBC
+/// OBX|1|NM|WBC||7.5
+/// NTE|1||Within range
+/// OBX|2|NM|RBC||4.5
+/// OBR|2||ORDER002|BMP
+/// OBX|1|NM|GLU||98";
+///
+/// let message = parse_message(hl7)?;
+/// let schema = GroupSchema::oru_r01();
+/// let groups = MessageGroups::new(&message, &schema);
+///
+/// let orders = groups.order_observations();
+/// assert_eq!(orders.len(), 2);
+/// assert_eq!(orders[0].groups("OBSERVATION").len(), 2);
+///
+/// // Group-qualified paths
+/// assert_eq!(groups.get("ORDER_OBSERVATION(2)/OBX-5")?, None);
+/// assert_eq!(groups.get("ORDER_OBSERVATION(2)/OBSERVATION/OBX-5")?, Some("98"));
+/// assert_eq!(groups.get("ORDER_OBSERVATION/OBSERVATION(1)/NTE-3")?, Some("Within range"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MessageGroups<'a> {
+    message: &'a Message,
+    root: GroupInstance<'a>,
+}
+
+impl<'a> MessageGroups<'a> {
+    /// Group the segments of a message according to a schema
+    pub fn new(message: &'a Message, schema: &GroupSchema) -> Self {
+        let root = GroupInstance::build(schema, message, (0..message.segments.len()).collect());
+        Self { message, root }
+    }
+
+    /// Get the root instance (segments outside any group and top-level groups)
+    pub fn root(&self) -> &GroupInstance<'a> {
+        &self.root
+    }
+
+    /// Get top-level group instances with the given name
+    pub fn groups(&self, name: &str) -> Vec<&GroupInstance<'a>> {
+        self.root.groups(name)
+    }
+
+    /// Find all group instances with the given name at any depth
+    pub fn find_all(&self, name: &str) -> Vec<&GroupInstance<'a>> {
+        self.root.find_all(name)
+    }
+
+    /// Get all ORDER_OBSERVATION group instances
+    pub fn order_observations(&self) -> Vec<&GroupInstance<'a>> {
+        self.find_all("ORDER_OBSERVATION")
+    }
+
+    /// Get a value using a group-qualified path
+    ///
+    /// The path is a `/`-separated list of group names, each with an optional
+    /// 1-based index (`OBSERVATION(2)`), followed by a Terser path that is
+    /// resolved against the segments belonging directly to the last group,
+    /// e.g. `ORDER_OBSERVATION(2)/OBSERVATION(1)/OBX-5`.
+    ///
+    /// Returns `Ok(None)` if a group or segment is not present.
+    pub fn get(&self, path: &str) -> Result<Option<&'a str>> {
+        let mut parts: Vec<&str> = path.split('/').collect();
+        let segment_path = parts
+            .pop()
+            .filter(|p| !p.is_empty())
+            .ok_or_else(|| Error::terser_path("Empty group path"))?;
+
+        let mut group = &self.root;
+        for part in parts {
+            let (name, index) = Self::parse_group_part(part)?;
+            match group.groups(name).get(index) {
+                Some(next) => group = next,
+                None => return Ok(None),
+            }
+        }
+
+        let parsed = TerserPath::parse(segment_path)?;
+        let segments: Vec<&'a Segment> = group
+            .segments
+            .iter()
+            .filter(|s| s.id == parsed.segment_id)
+            .copied()
+            .collect();
+        if parsed.segment_index >= segments.len() {
+            return Ok(None);
+        }
+
+        crate::Terser::new(self.message).get_from_segments(&parsed, &segments)
+    }
+
+    /// Parse a group part such as "OBSERVATION" or "OBSERVATION(2)" into name and 0-based index
+    fn parse_group_part(part: &str) -> Result<(&str, usize)> {
+        let Some(paren) = part.find('(') else {
+            return Ok((part, 0));
+        };
+
+        let index = part[paren + 1..]
+            .strip_suffix(')')
+            .and_then(|i| i.parse::<usize>().ok())
+            .filter(|i| *i > 0)
+            .ok_or_else(|| Error::terser_path(format!("Invalid group index in '{}'", part)))?;
+
+        Ok((&part[..paren], index - 1))
+    }
+}
+
 /// Extension trait to add group navigation to Terser
 impl<'a> crate::Terser<'a> {
     /// Create a group navigator for this message
@@ -394,6 +742,11 @@ impl<'a> crate::Terser<'a> {
     pub fn iter_groups(&self, config: GroupConfig) -> GroupIterator<'a> {
         GroupIterator::new(self.message, config)
     }
+
+    /// Get the hierarchical group view of this message for a schema
+    pub fn groups(&self, schema: &GroupSchema) -> MessageGroups<'a> {
+        MessageGroups::new(self.message, schema)
+    }
 }
 
 #[cfg(test)]
@@ -589,4 +942,111 @@ OBR|1||ORDER001|CBC";
         assert_eq!(groups[0].child_count(), 0);
         assert!(!groups[0].has_children());
     }
+
+    fn sample_nested_oru_message() -> Message {
+        let hl7 = r"MSH|^~\&|APP|FAC|||20250115||ORU^R01|123|P|2.5
+PID|1||PAT001||DOE^JOHN
+PV1|1|O
+OBR|1||ORDER001|CBC
+NTE|1||Order comment
+OBX|1|NM|WBC||7.5
+NTE|1||WBC comment
+OBX|2|NM|RBC||4.5
+SPM|1|SPEC001
+OBX|3|NM|TEMP||4
+OBR|2||ORDER002|BMP
+OBX|1|NM|GLU||98";
+        parse_message(hl7).unwrap()
+    }
+
+    #[test]
+    fn test_message_groups_hierarchy() {
+        let message = sample_nested_oru_message();
+        let schema = GroupSchema::oru_r01();
+        let groups = MessageGroups::new(&message, &schema);
+
+        let root = groups.root();
+        assert_eq!(root.segments.len(), 1); // MSH
+        assert_eq!(root.groups.len(), 3); // PATIENT + 2 ORDER_OBSERVATION
+
+        let patient = &groups.groups("PATIENT")[0];
+        assert_eq!(patient.header().unwrap().id, "PID");
+        assert_eq!(patient.groups("VISIT").len(), 1);
+
+        let orders = groups.order_observations();
+        assert_eq!(orders.len(), 2);
+
+        let first = orders[0];
+        assert_eq!(first.header_index(), Some(3));
+        assert_eq!(first.segment("NTE").unwrap().get_field_value(3), Some("Order comment"));
+
+        let observations = first.groups("OBSERVATION");
+        assert_eq!(observations.len(), 2);
+        assert_eq!(observations[0].segments.len(), 2); // OBX + NTE
+        assert_eq!(observations[1].segments.len(), 1);
+
+        // The OBX after SPM belongs to the specimen
+        let specimens = first.groups("SPECIMEN");
+        assert_eq!(specimens.len(), 1);
+        assert_eq!(specimens[0].segment_indices, vec![8, 9]);
+
+        let ids: Vec<_> = first.all_segments().iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["OBR", "NTE", "OBX", "NTE", "OBX", "SPM", "OBX"]);
+
+        assert_eq!(orders[1].groups("OBSERVATION").len(), 1);
+    }
+
+    #[test]
+    fn test_message_groups_paths() {
+        let message = sample_nested_oru_message();
+        let terser = crate::Terser::new(&message);
+        let groups = terser.groups(&GroupSchema::oru_r01());
+
+        assert_eq!(groups.get("PATIENT/PID-5-1").unwrap(), Some("DOE"));
+        assert_eq!(groups.get("PATIENT/VISIT/PV1-2").unwrap(), Some("O"));
+        assert_eq!(
+            groups.get("ORDER_OBSERVATION(1)/OBSERVATION(2)/OBX-3").unwrap(),
+            Some("RBC")
+        );
+        assert_eq!(
+            groups.get("ORDER_OBSERVATION/SPECIMEN/OBX-3").unwrap(),
+            Some("TEMP")
+        );
+        assert_eq!(
+            groups.get("ORDER_OBSERVATION(2)/OBSERVATION/OBX-5").unwrap(),
+            Some("98")
+        );
+
+        // Missing groups and segments resolve to None
+        assert_eq!(groups.get("ORDER_OBSERVATION(3)/OBR-4").unwrap(), None);
+        assert_eq!(groups.get("ORDER_OBSERVATION(2)/NTE-3").unwrap(), None);
+
+        assert!(groups.get("ORDER_OBSERVATION(0)/OBR-4").is_err());
+        assert!(groups.get("ORDER_OBSERVATION/").is_err());
+    }
+
+    #[test]
+    fn test_orm_group_schema() {
+        let hl7 = r"MSH|^~\&|APP|FAC|||20250115||ORM^O01|123|P|2.5
+PID|1||PAT001
+IN1|1|INS001
+IN2|1
+ORC|NW|ORD1
+OBR|1|ORD1||CBC
+OBX|1|NM|WBC||7.5
+ORC|NW|ORD2
+OBR|1|ORD2||BMP";
+        let message = parse_message(hl7).unwrap();
+        let schema = GroupSchema::orm_o01();
+        let groups = MessageGroups::new(&message, &schema);
+
+        let patient = &groups.groups("PATIENT")[0];
+        assert_eq!(patient.groups("INSURANCE")[0].segments.len(), 2);
+
+        let orders = groups.groups("ORDER");
+        assert_eq!(orders.len(), 2);
+        let detail = &orders[0].groups("ORDER_DETAIL")[0];
+        assert_eq!(detail.groups("OBSERVATION").len(), 1);
+        assert_eq!(groups.get("ORDER(2)/ORDER_DETAIL/OBR-2").unwrap(), Some("ORD2"));
+    }
 }
@@ -53,7 +53,7 @@ impl<'a> Iterator for FieldIterator<'a> {
     type Item = &'a str;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let segments = self.message.get_segments_by_id(&self.segment_id);
+        let segments = self.message.resolve_segments(&self.segment_id);
 
         while self.current_segment < segments.len() {
             let segment = &segments[self.current_segment];
@@ -127,7 +127,7 @@ impl<'a> Iterator for ComponentIterator<'a> {
     type Item = &'a str;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let segments = self.message.get_segments_by_id(&self.segment_id);
+        let segments = self.message.resolve_segments(&self.segment_id);
 
         while self.current_segment < segments.len() {
             let segment = &segments[self.current_segment];
@@ -208,7 +208,7 @@ impl<'a> Iterator for RepetitionIterator<'a> {
     type Item = &'a str;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let segments = self.message.get_segments_by_id(&self.segment_id);
+        let segments = self.message.resolve_segments(&self.segment_id);
 
         if self.segment_index >= segments.len() {
             return None;
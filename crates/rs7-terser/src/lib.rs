@@ -14,10 +14,14 @@ pub mod cache;
 pub mod bulk;
 pub mod groups;
 pub mod iterator;
+pub mod overlay;
 pub mod query;
 pub mod query_result;
+pub mod segment;
 
 use rs7_core::{
+    delimiters::Delimiters,
+    encoding::Encoding,
     error::{Error, Result},
     field::Field,
     message::Message,
@@ -26,10 +30,18 @@ use rs7_core::{
 
 pub use bulk::BulkTerser;
 pub use cache::CachedTerser;
-pub use groups::{GroupConfig, GroupIterator, GroupNavigator, GroupPattern, SegmentGroup};
+pub use groups::{
+    GroupConfig, GroupInstance, GroupIterator, GroupNavigator, GroupPattern, GroupSchema,
+    MessageGroups, SegmentGroup,
+};
+pub use overlay::MessageOverlay;
 pub use query::TerserQuery;
 pub use query_result::{QueryAcknowledgment, QueryResponseStatus, QueryResultParser};
+pub use segment::SegmentTerser;
+use chrono::NaiveDateTime;
 use path::TerserPath;
+use rs7_core::types::{parse_timestamp, Timestamp};
+use std::collections::HashMap;
 
 /// Terser for accessing HL7 message fields using path notation
 pub struct Terser<'a> {
@@ -49,79 +61,299 @@ impl<'a> Terser<'a> {
     /// - `get("OBX(2)-5")` - Get second OBX segment, field 5
     /// - `get("PID-11(2)-1")` - Get PID field 11, repetition 2, component 1
     pub fn get(&self, path: &str) -> Result<Option<&str>> {
-        let parsed_path = TerserPath::parse(path)?;
+        self.get_path(&TerserPath::parse(path)?)
+    }
 
-        // Find the segment
-        let segments = self.message.get_segments_by_id(&parsed_path.segment_id);
+    /// Get the escape-decoded value using path notation
+    ///
+    /// Values are normally stored decoded: the parser unescapes them and
+    /// [`TerserMut::set`] stores plain text that is escaped on encode. The
+    /// stored value is then returned as is and never decoded a second time,
+    /// so a backslash in the text (wire `\E\`) stays a backslash. Use
+    /// [`TerserMut::set_encoded`] to store wire-format text.
+    ///
+    /// Messages built by the streaming parser keep values as received
+    /// ([`Message::values_escaped`]); their values are decoded here with the
+    /// message's delimiters. On such messages, values stored with
+    /// [`TerserMut::set`] are read back as wire-format text too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs7_terser::{Terser, TerserMut};
+    /// use rs7_parser::parse_message;
+    ///
+    /// # fn main() -> rs7_core::Result<()> {
+    /// let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ORU^R01|123|P|2.5\rOBX|1|ST|CODE||A\\T\\B";
+    /// let mut message = parse_message(hl7)?;
+    /// TerserMut::new(&mut message).set("OBX-3", "C:\\temp")?;
+    ///
+    /// let terser = Terser::new(&message);
+    /// assert_eq!(terser.get_decoded("OBX-5")?, Some("A&B".to_string()));
+    /// assert_eq!(terser.get_decoded("OBX-3")?, Some("C:\\temp".to_string()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_decoded(&self, path: &str) -> Result<Option<String>> {
+        match self.get(path)? {
+            Some(value) if self.message.values_escaped() => {
+                Encoding::decode(value, &self.message.delimiters).map(Some)
+            }
+            value => Ok(value.map(str::to_string)),
+        }
+    }
 
-        if segments.is_empty() {
+    /// Get a value as an integer
+    ///
+    /// Returns `Ok(None)` if the value is absent or empty, and an error if a
+    /// value is present but is not a valid integer. Surrounding whitespace is
+    /// ignored.
+    pub fn get_int(&self, path: &str) -> Result<Option<i64>> {
+        self.get_parsed(path, "integer", |v| v.parse::<i64>().ok())
+    }
+
+    /// Get a value as a floating point number
+    ///
+    /// Returns `Ok(None)` if the value is absent or empty, and an error if a
+    /// value is present but is not a valid number. Surrounding whitespace is
+    /// ignored.
+    pub fn get_f64(&self, path: &str) -> Result<Option<f64>> {
+        self.get_parsed(path, "number", |v| v.parse::<f64>().ok())
+    }
+
+    /// Get a value as an HL7 date/time (TS/DTM)
+    ///
+    /// Returns `Ok(None)` if the value is absent or empty, and an error if a
+    /// value is present but is not a valid HL7 date/time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs7_terser::Terser;
+    /// use rs7_parser::parse_message;
+    ///
+    /// # fn main() -> rs7_core::Result<()> {
+    /// let hl7 = "MSH|^~\\&|APP|FAC|||20250115143000||ORU^R01|123|P|2.5\rOBX|1|NM|GLU|| 98 |mg/dL";
+    /// let message = parse_message(hl7)?;
+    /// let terser = Terser::new(&message);
+    ///
+    /// assert_eq!(terser.get_int("OBX-5")?, Some(98));
+    /// assert_eq!(terser.get_f64("OBX-8")?, None);
+    /// assert!(terser.get_f64("OBX-6").is_err());
+    /// assert_eq!(terser.get_datetime("MSH-7")?.unwrap().to_string(), "2025-01-15 14:30:00");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_datetime(&self, path: &str) -> Result<Option<NaiveDateTime>> {
+        self.get_parsed(path, "date/time", parse_timestamp)
+    }
+
+    /// Get a value as an HL7 date/time, keeping its precision and offset
+    ///
+    /// Unlike [`Terser::get_datetime`], the result remembers whether the
+    /// value was a date, a time to the minute, etc. and any `+/-ZZZZ` offset,
+    /// so it can be formatted back without inventing precision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs7_terser::Terser;
+    /// use rs7_parser::parse_message;
+    ///
+    /// # fn main() -> rs7_core::Result<()> {
+    /// let hl7 = "MSH|^~\\&|APP|FAC|||202501151430-0500||ADT^A01|123|P|2.5";
+    /// let message = parse_message(hl7)?;
+    /// let terser = Terser::new(&message);
+    ///
+    /// let ts = terser.get_timestamp("MSH-7")?.unwrap();
+    /// assert_eq!(ts.to_iso8601(), "2025-01-15T14:30-05:00");
+    /// assert!(terser.get_timestamp("MSH-10").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_timestamp(&self, path: &str) -> Result<Option<Timestamp>> {
+        self.get_parsed(path, "date/time", Timestamp::parse)
+    }
+
+    /// Get a trimmed value and convert it, distinguishing absent from invalid values
+    fn get_parsed<T>(
+        &self,
+        path: &str,
+        kind: &str,
+        parse: impl Fn(&str) -> Option<T>,
+    ) -> Result<Option<T>> {
+        let value = match self.get(path)? {
+            Some(v) => v.trim(),
+            None => return Ok(None),
+        };
+
+        if value.is_empty() {
             return Ok(None);
         }
 
+        parse(value).map(Some).ok_or_else(|| {
+            Error::parse(format!("Value '{}' at {} is not a valid {}", value, path, kind))
+        })
+    }
+
+    /// Get several values at once using path notation
+    ///
+    /// The message's segments are indexed by ID once, so each path costs only
+    /// its parse and a direct lookup instead of a scan of the segment list.
+    /// Results are in the same order as `paths` and match calling
+    /// [`Terser::get`] for each path individually.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs7_terser::Terser;
+    /// use rs7_parser::parse_message;
+    ///
+    /// # fn main() -> rs7_core::Result<()> {
+    /// let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ADT^A01|123|P|2.5\rPID|1||PAT001||DOE^JOHN";
+    /// let message = parse_message(hl7)?;
+    /// let terser = Terser::new(&message);
+    ///
+    /// let values = terser.get_many(&["PID-5-1", "PID-5-2", "PID-99", "MSH-10"])?;
+    /// assert_eq!(values, vec![Some("DOE"), Some("JOHN"), None, Some("123")]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_many(&self, paths: &[&str]) -> Result<Vec<Option<&str>>> {
+        let mut index: HashMap<&str, Vec<&Segment>> = HashMap::new();
+        for segment in &self.message.segments {
+            index.entry(segment.id.as_str()).or_default().push(segment);
+        }
+
+        paths
+            .iter()
+            .map(|path| {
+                let parsed_path = TerserPath::parse(path)?;
+                let segments = index
+                    .get(parsed_path.segment_id.as_str())
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                self.get_from_segments(&parsed_path, segments)
+            })
+            .collect()
+    }
+
+    /// Get a value from every repetition of a field
+    ///
+    /// A path without an explicit repetition index (e.g. `PID-3` or `PID-3-1`)
+    /// returns the value from each repetition in order. A path with an explicit
+    /// repetition index (e.g. `PID-3(1)`) returns at most that single value.
+    /// Empty repetitions produce an empty string so positions stay aligned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs7_terser::Terser;
+    /// use rs7_parser::parse_message;
+    ///
+    /// # fn main() -> rs7_core::Result<()> {
+    /// let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ADT^A01|123|P|2.5\rPID|1||111^^^MRN~~333^^^SSN";
+    /// let message = parse_message(hl7)?;
+    /// let terser = Terser::new(&message);
+    ///
+    /// assert_eq!(terser.get_all("PID-3-1")?, vec!["111", "", "333"]);
+    /// assert_eq!(terser.get_all("PID-3-4")?, vec!["MRN", "", "SSN"]);
+    /// assert_eq!(terser.get_all("PID-3(2)-1")?, vec!["333"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_all(&self, path: &str) -> Result<Vec<&str>> {
+        let parsed_path = TerserPath::parse(path)?;
+
+        let segments = self.message.resolve_segments(&parsed_path.segment_id);
+        if segments.is_empty() {
+            return Ok(Vec::new());
+        }
+
         let segment = segments.get(parsed_path.segment_index)
             .ok_or_else(|| Error::terser_path(format!(
                 "Segment index {} out of bounds for {}",
                 parsed_path.segment_index, parsed_path.segment_id
             )))?;
 
-        // Get the field
-        let field = match segment.get_field(parsed_path.field_index) {
-            Some(f) => f,
-            None => return Ok(None),
+        Ok(SegmentTerser::new(segment).get_all_path(&parsed_path))
+    }
+
+    /// Get a value from every segment matched by a segment wildcard
+    ///
+    /// The path uses `(*)` in the segment index position, e.g. `OBX(*)-5-1`,
+    /// and yields one entry per matching segment in document order. Segments
+    /// where the value is missing produce an empty string so positions stay
+    /// aligned with the segments. A message with no matching segments yields
+    /// an empty vector.
+    ///
+    /// The wildcard is only supported in the segment index position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs7_terser::Terser;
+    /// use rs7_parser::parse_message;
+    ///
+    /// # fn main() -> rs7_core::Result<()> {
+    /// let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ORU^R01|123|P|2.5\rOBX|1|NM|GLU||98^mg/dL\rOBX|2|NM|NA||140^mmol/L";
+    /// let message = parse_message(hl7)?;
+    /// let terser = Terser::new(&message);
+    ///
+    /// assert_eq!(terser.get_wildcard("OBX(*)-5-1")?, vec!["98", "140"]);
+    /// assert!(terser.get_wildcard("NTE(*)-3")?.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_wildcard(&self, path: &str) -> Result<Vec<&str>> {
+        let (segment_part, rest) = path.split_once('-').unwrap_or((path, ""));
+
+        let Some(segment_id) = segment_part.strip_suffix("(*)") else {
+            return Err(Error::terser_path(format!(
+                "Path '{}' must use (*) in the segment index position",
+                path
+            )));
         };
 
-        // Navigate to the value
-        let value = self.get_field_value(
-            field,
-            parsed_path.repetition_index,
-            parsed_path.component_index,
-            parsed_path.subcomponent_index,
-        );
+        if rest.contains('*') {
+            return Err(Error::terser_path(
+                "Wildcard (*) is only supported in the segment index position",
+            ));
+        }
 
-        Ok(value)
+        let parsed_path = TerserPath::parse(&format!("{}-{}", segment_id, rest))?;
+
+        Ok(self
+            .message
+            .resolve_segments(segment_id)
+            .into_iter()
+            .map(|segment| SegmentTerser::new(segment).get_path(&parsed_path).unwrap_or(""))
+            .collect())
     }
 
-    /// Get a field value at the specified indices
-    ///
-    /// Note: Component and subcomponent indices are 1-based (HL7 standard notation)
-    /// but internally converted to 0-based for array access.
-    fn get_field_value<'b>(
-        &self,
-        field: &'b Field,
-        rep_idx: usize,
-        comp_idx: Option<usize>,
-        sub_idx: Option<usize>,
-    ) -> Option<&'b str> {
-        let repetition = field.get_repetition(rep_idx)?;
+    /// Get a value using an already parsed path
+    pub(crate) fn get_path(&self, parsed_path: &TerserPath) -> Result<Option<&'a str>> {
+        let segments = self.message.resolve_segments(&parsed_path.segment_id);
+        self.get_from_segments(parsed_path, &segments)
+    }
 
-        match (comp_idx, sub_idx) {
-            (None, None) => {
-                // Just the field value
-                repetition.value()
-            }
-            (Some(c_idx), None) => {
-                // Component value (convert 1-based HL7 to 0-based internal)
-                if c_idx == 0 {
-                    return None; // Invalid: HL7 uses 1-based indexing
-                }
-                repetition.get_component(c_idx - 1)?.value()
-            }
-            (Some(c_idx), Some(s_idx)) => {
-                // Subcomponent value (convert 1-based HL7 to 0-based internal)
-                if c_idx == 0 || s_idx == 0 {
-                    return None; // Invalid: HL7 uses 1-based indexing
-                }
-                repetition
-                    .get_component(c_idx - 1)?
-                    .get_subcomponent(s_idx - 1)?
-                    .as_str()
-                    .into()
-            }
-            (None, Some(_)) => {
-                // Invalid: can't have subcomponent without component
-                None
-            }
+    /// Resolve a parsed path against the segments matching its segment ID
+    fn get_from_segments<'b>(
+        &self,
+        parsed_path: &TerserPath,
+        segments: &[&'b Segment],
+    ) -> Result<Option<&'b str>> {
+        if segments.is_empty() {
+            return Ok(None);
         }
+
+        let segment = segments.get(parsed_path.segment_index)
+            .ok_or_else(|| Error::terser_path(format!(
+                "Segment index {} out of bounds for {}",
+                parsed_path.segment_index, parsed_path.segment_id
+            )))?;
+
+        Ok(SegmentTerser::new(segment).get_path(parsed_path))
     }
 }
 
@@ -137,9 +369,19 @@ impl<'a> TerserMut<'a> {
     }
 
     /// Set a value using path notation
+    ///
+    /// Setting `MSH-1` (field separator) or `MSH-2` (encoding characters)
+    /// also updates the message delimiters used when encoding.
     pub fn set(&mut self, path: &str, value: &str) -> Result<()> {
         let parsed_path = TerserPath::parse(path)?;
 
+        if parsed_path.segment_id == "MSH"
+            && parsed_path.segment_index == 0
+            && (parsed_path.field_index == 1 || parsed_path.field_index == 2)
+        {
+            return self.set_delimiter_field(&parsed_path, value);
+        }
+
         // Find or create the segment
         let segment_index = self.ensure_segment(&parsed_path.segment_id, parsed_path.segment_index)?;
 
@@ -155,12 +397,16 @@ impl<'a> TerserMut<'a> {
             let field = segment.get_field_mut(parsed_path.field_index)
                 .ok_or_else(|| Error::terser_path("Failed to get field"))?;
 
+            let (rep_idx, component_index) = parsed_path
+                .resolve(field)
+                .ok_or_else(|| Error::terser_path(format!("Index out of range in path: {}", path)))?;
+
             // Set the value
             Self::set_field_value_static(
                 field,
                 value,
-                parsed_path.repetition_index,
-                parsed_path.component_index,
+                rep_idx,
+                component_index,
                 parsed_path.subcomponent_index,
             )?;
         }
@@ -168,13 +414,191 @@ impl<'a> TerserMut<'a> {
         Ok(())
     }
 
+    /// Set a value given in encoded (wire) form using path notation
+    ///
+    /// [`TerserMut::set`] stores plain text, which is escaped when the message
+    /// is encoded. Wire-format input such as `A\T\B` would be escaped a second
+    /// time that way, so this decodes the escape sequences with the message's
+    /// delimiters before storing. Use it when copying text taken from an
+    /// encoded message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs7_terser::{Terser, TerserMut};
+    /// use rs7_parser::parse_message;
+    ///
+    /// # fn main() -> rs7_core::Result<()> {
+    /// let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ORU^R01|123|P|2.5\rOBX|1|ST|CODE";
+    /// let mut message = parse_message(hl7)?;
+    /// TerserMut::new(&mut message).set_encoded("OBX-5", "A\\T\\B")?;
+    ///
+    /// assert_eq!(Terser::new(&message).get("OBX-5")?, Some("A&B"));
+    /// assert!(message.encode().contains("|A\\T\\B"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_encoded(&mut self, path: &str, value: &str) -> Result<()> {
+        let decoded = Encoding::decode(value, &self.message.delimiters)?;
+        self.set(path, &decoded)
+    }
+
+    /// Remove the value at a path
+    ///
+    /// Positions are preserved wherever possible:
+    /// - `PID-5` clears the whole field but keeps its slot
+    /// - `PID-5-2` clears the component, leaving an empty component in place
+    /// - `PID-5-2-1` clears the subcomponent, leaving an empty subcomponent in place
+    /// - `PID-11(2)` removes the repetition, so later repetitions move down by one
+    ///
+    /// Returns `true` if a non-empty value was removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs7_terser::{Terser, TerserMut};
+    /// use rs7_parser::parse_message;
+    ///
+    /// # fn main() -> rs7_core::Result<()> {
+    /// let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ADT^A01|123|P|2.5\rPID|1||PAT001||DOE^JOHN^A";
+    /// let mut message = parse_message(hl7)?;
+    ///
+    /// assert!(TerserMut::new(&mut message).remove("PID-5-2")?);
+    /// assert_eq!(Terser::new(&message).get("PID-5-2")?, Some(""));
+    /// assert_eq!(Terser::new(&message).get("PID-5-3")?, Some("A"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn remove(&mut self, path: &str) -> Result<bool> {
+        use rs7_core::field::{Component, SubComponent};
+
+        let parsed_path = TerserPath::parse(path)?;
+
+        let Some(segment_index) = self.find_segment(&parsed_path.segment_id, parsed_path.segment_index) else {
+            return Ok(false);
+        };
+
+        let segment = &mut self.message.segments[segment_index];
+        let Some(field) = segment.get_field_mut(parsed_path.field_index) else {
+            return Ok(false);
+        };
+
+        let Some((rep_idx, component_index)) = parsed_path.resolve(field) else {
+            return Ok(false);
+        };
+
+        match (component_index, parsed_path.subcomponent_index) {
+            (None, None) if parsed_path.explicit_repetition => {
+                if rep_idx >= field.repetitions.len() {
+                    return Ok(false);
+                }
+                let removed = field.repetitions.remove(rep_idx);
+                Ok(!removed.is_empty())
+            }
+            (None, None) => {
+                let removed = !field.is_empty();
+                *field = Field::from_value("");
+                Ok(removed)
+            }
+            (Some(c_idx), None) => {
+                if c_idx == 0 {
+                    return Err(Error::terser_path(
+                        "Invalid component index 0: HL7 uses 1-based indexing",
+                    ));
+                }
+                let Some(component) = field
+                    .get_repetition_mut(rep_idx)
+                    .and_then(|rep| rep.get_component_mut(c_idx - 1))
+                else {
+                    return Ok(false);
+                };
+                let removed = !component.is_empty();
+                *component = Component::from_value("");
+                Ok(removed)
+            }
+            (Some(c_idx), Some(s_idx)) => {
+                if c_idx == 0 || s_idx == 0 {
+                    return Err(Error::terser_path(
+                        "Invalid index 0: HL7 uses 1-based indexing",
+                    ));
+                }
+                let Some(subcomponent) = field
+                    .get_repetition_mut(rep_idx)
+                    .and_then(|rep| rep.get_component_mut(c_idx - 1))
+                    .and_then(|comp| comp.get_subcomponent_mut(s_idx - 1))
+                else {
+                    return Ok(false);
+                };
+                let removed = !subcomponent.is_empty();
+                *subcomponent = SubComponent::new("");
+                Ok(removed)
+            }
+            (None, Some(_)) => Err(Error::terser_path(
+                "Cannot remove subcomponent without component index",
+            )),
+        }
+    }
+
+    /// Set MSH-1 or MSH-2, keeping `message.delimiters` in sync
+    fn set_delimiter_field(&mut self, path: &TerserPath, value: &str) -> Result<()> {
+        if path.explicit_repetition || path.component_index.is_some() {
+            return Err(Error::terser_path(format!(
+                "MSH-{} has no repetitions or components",
+                path.field_index
+            )));
+        }
+
+        let current = self.message.delimiters;
+        let delimiters = if path.field_index == 1 {
+            let mut chars = value.chars();
+            let field_separator = match (chars.next(), chars.next()) {
+                (Some(c), None) => c,
+                _ => {
+                    return Err(Error::InvalidDelimiters(format!(
+                        "Field separator must be a single character, got '{}'",
+                        value
+                    )))
+                }
+            };
+            let delimiters = Delimiters {
+                field_separator,
+                ..current
+            };
+            delimiters.validate()?;
+            delimiters
+        } else {
+            Delimiters::from_encoding_characters(current.field_separator, value)?
+        };
+
+        let segment_index = self.ensure_segment("MSH", 0)?;
+        let msh = &mut self.message.segments[segment_index];
+        while msh.fields.len() < 2 {
+            msh.add_field(Field::new());
+        }
+        msh.fields[0] = Field::from_value(delimiters.field_separator.to_string());
+        msh.fields[1] = Field::from_value(delimiters.encoding_characters());
+        self.message.delimiters = delimiters;
+        Ok(())
+    }
+
+    /// Find the message index of the nth segment with the given ID
+    fn find_segment(&self, segment_id: &str, index: usize) -> Option<usize> {
+        self.message
+            .segments
+            .iter()
+            .enumerate()
+            .filter(|(_, seg)| self.message.segment_matches(seg, segment_id))
+            .nth(index)
+            .map(|(i, _)| i)
+    }
+
     /// Ensure segment exists at the given index
-    fn ensure_segment(&mut self, segment_id: &str, index: usize) -> Result<usize> {
+    pub(crate) fn ensure_segment(&mut self, segment_id: &str, index: usize) -> Result<usize> {
         let mut current_index = 0;
         let mut actual_index = None;
 
         for (i, seg) in self.message.segments.iter().enumerate() {
-            if seg.id == segment_id {
+            if self.message.segment_matches(seg, segment_id) {
                 if current_index == index {
                     actual_index = Some(i);
                     break;
@@ -299,6 +723,97 @@ mod tests {
         assert!(TerserPath::parse("PID-").is_err());
     }
 
+    #[test]
+    fn test_last_and_negative_indices() {
+        let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ADT^A01|123|P|2.5\r\
+                   PID|1||111^^^MRN~222^^^SSN~333^^^DL||DOE^JOHN^Q";
+        let mut message = parse_message(hl7).unwrap();
+
+        let terser = Terser::new(&message);
+        assert_eq!(terser.get("PID-3(last)-1").unwrap(), Some("333"));
+        assert_eq!(terser.get("PID-3(-1)-4").unwrap(), Some("DL"));
+        assert_eq!(terser.get("PID-3(-3)-1").unwrap(), Some("111"));
+        assert_eq!(terser.get("PID-3(-4)-1").unwrap(), None);
+        assert_eq!(terser.get("PID-5-last").unwrap(), Some("Q"));
+        assert_eq!(terser.get("PID-3(-2)-last").unwrap(), Some("SSN"));
+        assert_eq!(terser.get_all("PID-3-last").unwrap(), vec!["MRN", "SSN", "DL"]);
+        assert_eq!(terser.get_all("PID-3(last)-1").unwrap(), vec!["333"]);
+
+        let mut terser = TerserMut::new(&mut message);
+        terser.set("PID-3(last)-1", "444").unwrap();
+        terser.set("PID-5-last", "R").unwrap();
+        assert!(terser.set("PID-3(-9)-1", "X").is_err());
+        assert!(!terser.remove("PID-3(-9)").unwrap());
+
+        let terser = Terser::new(&message);
+        assert_eq!(terser.get("PID-3(2)-1").unwrap(), Some("444"));
+        assert_eq!(terser.get("PID-5-3").unwrap(), Some("R"));
+    }
+
+    #[test]
+    fn test_msh_delimiter_fields() {
+        let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ADT^A01|123|P|2.5\rPID|1||111||DOE^JOHN";
+        let mut message = parse_message(hl7).unwrap();
+
+        let terser = Terser::new(&message);
+        assert_eq!(terser.get("MSH-1").unwrap(), Some("|"));
+        assert_eq!(terser.get("MSH-2").unwrap(), Some("^~\\&"));
+
+        let mut terser = TerserMut::new(&mut message);
+        terser.set("MSH-1", "#").unwrap();
+        terser.set("MSH-2", "$*\\@").unwrap();
+        assert!(terser.set("MSH-1", "##").is_err());
+        assert!(terser.set("MSH-1", "$").is_err());
+        assert!(terser.set("MSH-2", "$*").is_err());
+        assert!(terser.set("MSH-2-1", "$").is_err());
+
+        assert_eq!(message.delimiters.field_separator, '#');
+        assert_eq!(message.delimiters.component_separator, '$');
+        let terser = Terser::new(&message);
+        assert_eq!(terser.get("MSH-1").unwrap(), Some("#"));
+        assert_eq!(terser.get("MSH-2").unwrap(), Some("$*\\@"));
+
+        let encoded = message.encode();
+        assert_eq!(
+            encoded,
+            "MSH#$*\\@#APP#FAC###20250115##ADT$A01#123#P#2.5\rPID#1##111##DOE$JOHN"
+        );
+        assert_eq!(parse_message(&encoded).unwrap().segments, message.segments);
+    }
+
+    #[test]
+    fn test_segment_aliases() {
+        let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ADT^A01|123|P|2.5\rpid|1||111||DOE^JOHN";
+        let message = parse_message(hl7).unwrap();
+        assert_eq!(Terser::new(&message).get("PID-5-1").unwrap(), None);
+
+        let mut message = message.with_segment_alias("pid", "PID");
+        assert_eq!(Terser::new(&message).get("PID-5-1").unwrap(), Some("DOE"));
+
+        let mut terser = TerserMut::new(&mut message);
+        terser.set("PID-5-2", "JANE").unwrap();
+        assert_eq!(message.segments.len(), 2);
+        assert_eq!(message.segments[1].id, "pid");
+        assert_eq!(Terser::new(&message).get("PID-5-2").unwrap(), Some("JANE"));
+    }
+
+    #[test]
+    fn test_iter_leaves_paths_resolve() {
+        let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ORU^R01|123|P|2.5\r\
+                   PID|1||111^^^MRN&1.2.3~222^^^SSN||DOE^JOHN\r\
+                   OBX|1|NM|GLU||98\r\
+                   OBX|2|NM|NA||140";
+        let message = parse_message(hl7).unwrap();
+        let terser = Terser::new(&message);
+
+        let mut count = 0;
+        for (path, value) in message.iter_leaves() {
+            assert_eq!(terser.get(&path).unwrap(), Some(value), "{}", path);
+            count += 1;
+        }
+        assert!(count > 15);
+    }
+
     #[test]
     fn test_1_based_component_indexing() {
         // Standard HL7 message with components: ADT^A01^ADT_A01
@@ -350,6 +865,76 @@ mod tests {
         assert_eq!(result, None);
     }
 
+    #[test]
+    fn test_typed_getters() {
+        let hl7 = "MSH|^~\\&|APP|FAC|||20250115143025||ORU^R01|123|P|2.5\rOBX|1|NM|GLU|| 98 |mg/dL||4.5|2025011514|abc|2025é0115\r";
+        let message = parse_message(hl7).unwrap();
+        let terser = Terser::new(&message);
+
+        assert_eq!(terser.get_int("OBX-5").unwrap(), Some(98));
+        assert_eq!(terser.get_f64("OBX-8").unwrap(), Some(4.5));
+        assert!(terser.get_int("OBX-8").is_err());
+        assert!(terser.get_f64("OBX-10").is_err());
+
+        // Absent and empty values are not errors
+        assert_eq!(terser.get_int("OBX-7").unwrap(), None);
+        assert_eq!(terser.get_f64("OBX-20").unwrap(), None);
+        assert_eq!(terser.get_int("NTE-1").unwrap(), None);
+
+        let dt = terser.get_datetime("MSH-7").unwrap().unwrap();
+        assert_eq!(dt.to_string(), "2025-01-15 14:30:25");
+        let dt = terser.get_datetime("OBX-9").unwrap().unwrap();
+        assert_eq!(dt.to_string(), "2025-01-15 14:00:00");
+        assert!(terser.get_datetime("OBX-6").is_err());
+        assert!(terser.get_datetime("OBX-11").is_err());
+    }
+
+    #[test]
+    fn test_get_many_matches_get() {
+        let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ORU^R01|123|P|2.5\rPID|1||PAT001||DOE^JOHN^A\rOBX|1|NM|GLU||98\rOBX|2|NM|NA||140\r";
+        let message = parse_message(hl7).unwrap();
+        let terser = Terser::new(&message);
+
+        let paths = ["PID-5-1", "OBX(2)-5", "OBX-3", "PID-5-9", "PID-50", "NK1-2", "MSH-9-2"];
+        let many = terser.get_many(&paths).unwrap();
+        let single: Vec<Option<&str>> = paths.iter().map(|p| terser.get(p).unwrap()).collect();
+
+        assert_eq!(many, single);
+        assert_eq!(many[1], Some("140"));
+        assert_eq!(many[3], None);
+    }
+
+    #[test]
+    fn test_get_many_errors_like_get() {
+        let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ORU^R01|123|P|2.5\rOBX|1|NM|GLU||98\r";
+        let message = parse_message(hl7).unwrap();
+        let terser = Terser::new(&message);
+
+        assert!(terser.get_many(&["OBX(3)-5"]).is_err());
+        assert!(terser.get_many(&["PID-5", "INVALID"]).is_err());
+        assert!(terser.get_many(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_get_all_repetitions() {
+        let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ADT^A01|123|P|2.5\rPID|1||111^^^MRN~~333^^^SSN||DOE^JOHN\r";
+        let message = parse_message(hl7).unwrap();
+        let terser = Terser::new(&message);
+
+        assert_eq!(terser.get_all("PID-3").unwrap(), vec!["111", "", "333"]);
+        assert_eq!(terser.get_all("PID-3-4").unwrap(), vec!["MRN", "", "SSN"]);
+        assert_eq!(terser.get_all("PID-3(0)").unwrap(), vec!["111"]);
+        assert_eq!(terser.get_all("PID-3(1)-1").unwrap(), vec![""]);
+        assert!(terser.get_all("PID-3(5)").unwrap().is_empty());
+
+        // Non-repeating field yields a single entry
+        assert_eq!(terser.get_all("PID-5-2").unwrap(), vec!["JOHN"]);
+
+        // Missing field or segment yields no entries
+        assert!(terser.get_all("PID-30").unwrap().is_empty());
+        assert!(terser.get_all("NK1-2").unwrap().is_empty());
+    }
+
     #[test]
     fn test_set_with_1_based_indexing() {
         let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ADT^A01|123|P|2.5\rPID|1\r";
@@ -368,6 +953,55 @@ mod tests {
         assert_eq!(read_terser.get("PID-5-3").unwrap(), Some("M"));
     }
 
+    #[test]
+    fn test_get_wildcard_segments() {
+        let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ORU^R01|123|P|2.5\rOBX|1|NM|GLU||98^mg/dL\rOBX|2|NM|NA\rOBX|3|NM|K||4.2^mmol/L~4.3^mmol/L\r";
+        let message = parse_message(hl7).unwrap();
+        let terser = Terser::new(&message);
+
+        assert_eq!(terser.get_wildcard("OBX(*)-5-1").unwrap(), vec!["98", "", "4.2"]);
+        assert_eq!(terser.get_wildcard("OBX(*)-5(1)-1").unwrap(), vec!["", "", "4.3"]);
+        assert_eq!(terser.get_wildcard("OBX(*)-3").unwrap(), vec!["GLU", "NA", "K"]);
+
+        // No matching segments is not an error
+        assert!(terser.get_wildcard("NTE(*)-3").unwrap().is_empty());
+
+        // Wildcards are only allowed in the segment index position
+        assert!(terser.get_wildcard("OBX-5").is_err());
+        assert!(terser.get_wildcard("OBX(*)-5(*)").is_err());
+    }
+
+    #[test]
+    fn test_remove_preserves_positions() {
+        let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ADT^A01|123|P|2.5\rPID|1||PAT001||DOE^JOHN^A|||M|||1 Main^^Boston~2 Elm^^Salem~3 Oak^^Lynn\r";
+        let mut message = parse_message(hl7).unwrap();
+        let mut terser = TerserMut::new(&mut message);
+
+        // Component removal leaves an empty component in place
+        assert!(terser.remove("PID-5-2").unwrap());
+        assert!(!terser.remove("PID-5-2").unwrap());
+
+        // Repetition removal renumbers the following repetitions
+        assert!(terser.remove("PID-11(1)").unwrap());
+        assert!(!terser.remove("PID-11(5)").unwrap());
+
+        // Field removal clears the field but keeps its slot
+        assert!(terser.remove("PID-8").unwrap());
+
+        // Missing targets report nothing removed
+        assert!(!terser.remove("PID-40").unwrap());
+        assert!(!terser.remove("NK1-2").unwrap());
+        assert!(terser.remove("PID-5-0").is_err());
+
+        let read = Terser::new(&message);
+        assert_eq!(read.get("PID-5-1").unwrap(), Some("DOE"));
+        assert_eq!(read.get("PID-5-2").unwrap(), Some(""));
+        assert_eq!(read.get("PID-5-3").unwrap(), Some("A"));
+        assert_eq!(read.get_all("PID-11-3").unwrap(), vec!["Boston", "Lynn"]);
+        assert_eq!(read.get("PID-8").unwrap(), Some(""));
+        assert_eq!(message.segments[1].fields.len(), 11);
+    }
+
     #[test]
     fn test_set_invalid_0_index_returns_error() {
         let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ADT^A01|123|P|2.5\rPID|1\r";
@@ -378,4 +1012,56 @@ mod tests {
         let result = terser.set("PID-5-0", "INVALID");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_get_decoded_and_set_encoded() {
+        let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ORU^R01|123|P|2.5\rOBX|1|ST|CODE||A\\F\\B\r";
+        let mut message = parse_message(hl7).unwrap();
+
+        let mut terser = TerserMut::new(&mut message);
+        terser.set_encoded("OBX-3-1", "X\\T\\Y").unwrap();
+        terser.set("OBX-4", "1\\R\\2").unwrap();
+        assert!(terser.set_encoded("OBX-6", "bad\\Q").is_err());
+
+        let read = Terser::new(&message);
+        assert_eq!(read.get("OBX-5").unwrap(), Some("A|B"));
+        assert_eq!(read.get_decoded("OBX-5").unwrap(), Some("A|B".to_string()));
+        assert_eq!(read.get("OBX-3-1").unwrap(), Some("X&Y"));
+        assert_eq!(read.get_decoded("OBX-4").unwrap(), Some("1\\R\\2".to_string()));
+        assert_eq!(read.get_decoded("OBX-99").unwrap(), None);
+
+        let encoded = message.encode();
+        assert!(encoded.contains("|X\\T\\Y|"));
+        assert!(encoded.contains("|A\\F\\B"));
+    }
+
+    #[test]
+    fn test_get_decoded_escaped_escape() {
+        // \E\ is a literal backslash and must not be decoded again
+        let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ORU^R01|123|P|2.5\rOBX|1|ST|CODE||A\\E\\T\\E\\B\r";
+        let mut message = parse_message(hl7).unwrap();
+        TerserMut::new(&mut message).set("OBX-3", "C:\\x").unwrap();
+
+        let terser = Terser::new(&message);
+        assert_eq!(terser.get_decoded("OBX-5").unwrap(), Some("A\\T\\B".to_string()));
+        assert_eq!(terser.get_decoded("OBX-3").unwrap(), Some("C:\\x".to_string()));
+        assert!(message.encode().contains("|A\\E\\T\\E\\B"));
+    }
+
+    #[test]
+    fn test_get_decoded_streaming_message() {
+        let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ORU^R01|123|P|2.5\rOBX|1|ST|CODE||A\\T\\B\\E\\C\r";
+        let message = rs7_parser::parse_streaming(hl7).unwrap();
+        assert!(message.values_escaped());
+
+        let terser = Terser::new(&message);
+        assert_eq!(terser.get("OBX-5").unwrap(), Some("A\\T\\B\\E\\C"));
+        assert_eq!(terser.get_decoded("OBX-5").unwrap(), Some("A&B\\C".to_string()));
+        assert_eq!(terser.get_decoded("OBX-99").unwrap(), None);
+
+        // Same text as the regular parser
+        let parsed = parse_message(hl7).unwrap();
+        assert!(!parsed.values_escaped());
+        assert_eq!(Terser::new(&parsed).get_decoded("OBX-5").unwrap(), terser.get_decoded("OBX-5").unwrap());
+    }
 }
@@ -0,0 +1,233 @@
+//! Copying values between messages
+//!
+//! [`MessageOverlay`] copies selected paths or whole segment types from one
+//! message into another, the usual pattern when enriching a message with
+//! data from another source, such as updating PID from a master patient
+//! index or adding an MRG segment for a merge.
+
+use crate::{path::TerserPath, Terser, TerserMut};
+use rs7_core::{
+    error::{Error, Result},
+    field::{Component, Field, Repetition, SubComponent},
+    message::Message,
+};
+
+/// Extension trait for copying values from another message
+///
+/// # Example
+///
+/// ```rust
+/// use rs7_terser::{MessageOverlay, Terser};
+/// use rs7_parser::parse_message;
+///
+/// # fn main() -> rs7_core::Result<()> {
+/// let mut message = parse_message("MSH|^~\\&|APP|FAC|||20250115||ADT^A08|1|P|2.5\rPID|1||123||DOE^JON")?;
+/// let master = parse_message("MSH|^~\\&|MPI|FAC|||20250115||ADT^A08|2|P|2.5\rPID|1||123||DOE^JOHN^Q||19800101|M\rIN1|1|PLAN1")?;
+///
+/// message.overlay(&master, &["PID-5", "PID-7", "PID-8"])?;
+/// message.overlay_segment(&master, "IN1");
+///
+/// let terser = Terser::new(&message);
+/// assert_eq!(terser.get("PID-5-2")?, Some("JOHN"));
+/// assert_eq!(terser.get("PID-7")?, Some("19800101"));
+/// assert_eq!(terser.get("IN1-2")?, Some("PLAN1"));
+/// # Ok(())
+/// # }
+/// ```
+pub trait MessageOverlay {
+    /// Copy the values at `paths` from `other` into this message
+    ///
+    /// Paths use [`Terser`] syntax, and the level of the path decides what is
+    /// copied: `PID-5` copies the whole field with all repetitions and
+    /// components, `PID-3(1)` one repetition, `PID-5-2` one component and
+    /// `PID-5-2-1` one subcomponent. Missing segments, fields, repetitions and
+    /// components are created in this message as needed.
+    ///
+    /// Values present in `other` overwrite this message's values, even when
+    /// empty. Paths that `other` does not contain are left unchanged here.
+    /// Returns an error if a path is malformed.
+    fn overlay(&mut self, other: &Message, paths: &[&str]) -> Result<()>;
+
+    /// Replace every segment with ID `segment_id` by the ones in `other`
+    ///
+    /// The copies take the position of the first existing segment of that
+    /// type, or are appended when there is none. Nothing changes if `other`
+    /// has no such segment. Returns the number of segments copied.
+    fn overlay_segment(&mut self, other: &Message, segment_id: &str) -> usize;
+}
+
+impl MessageOverlay for Message {
+    fn overlay(&mut self, other: &Message, paths: &[&str]) -> Result<()> {
+        for path in paths {
+            overlay_path(self, other, path)?;
+        }
+        Ok(())
+    }
+
+    fn overlay_segment(&mut self, other: &Message, segment_id: &str) -> usize {
+        let copies: Vec<_> = other.resolve_segments(segment_id).into_iter().cloned().collect();
+        if copies.is_empty() {
+            return 0;
+        }
+
+        let positions: Vec<usize> = self
+            .segments
+            .iter()
+            .enumerate()
+            .filter(|(_, segment)| self.segment_matches(segment, segment_id))
+            .map(|(i, _)| i)
+            .collect();
+        let position = positions.first().copied().unwrap_or(self.segments.len());
+
+        for &i in positions.iter().rev() {
+            self.remove_segment(i);
+        }
+        let count = copies.len();
+        for (offset, segment) in copies.into_iter().enumerate() {
+            self.insert_segment(position + offset, segment)
+                .expect("overlay position is within the message");
+        }
+        count
+    }
+}
+
+/// Copy a single path from `other` into `message`
+fn overlay_path(message: &mut Message, other: &Message, path: &str) -> Result<()> {
+    let parsed = TerserPath::parse(path)?;
+
+    // MSH-1 and MSH-2 hold the delimiters, which TerserMut keeps in sync
+    if parsed.segment_id == "MSH" && parsed.segment_index == 0 && parsed.field_index <= 2 {
+        if let Some(value) = Terser::new(other).get(path)? {
+            TerserMut::new(message).set(path, value)?;
+        }
+        return Ok(());
+    }
+
+    let Some(source) = other
+        .resolve_segments(&parsed.segment_id)
+        .get(parsed.segment_index)
+        .and_then(|segment| segment.get_field(parsed.field_index))
+    else {
+        return Ok(());
+    };
+    let Some((rep_idx, component_index)) = parsed.resolve(source) else {
+        return Ok(());
+    };
+
+    let segment_index = TerserMut::new(message).ensure_segment(&parsed.segment_id, parsed.segment_index)?;
+    let segment = &mut message.segments[segment_index];
+    while segment.fields.len() < parsed.field_index {
+        segment.add_field(Field::new());
+    }
+    let target = segment
+        .get_field_mut(parsed.field_index)
+        .ok_or_else(|| Error::terser_path("Failed to get field"))?;
+
+    if component_index.is_none() && parsed.subcomponent_index.is_none() && !parsed.explicit_repetition {
+        *target = source.clone();
+        return Ok(());
+    }
+
+    let Some(repetition) = source.get_repetition(rep_idx) else {
+        return Ok(());
+    };
+    let target = ensure_with(&mut target.repetitions, rep_idx, Repetition::new);
+
+    match (component_index, parsed.subcomponent_index) {
+        (None, None) => *target = repetition.clone(),
+        (Some(c_idx), sub_idx) => {
+            if c_idx == 0 || sub_idx == Some(0) {
+                return Err(Error::terser_path("Invalid index 0: HL7 uses 1-based indexing"));
+            }
+            let Some(component) = repetition.get_component(c_idx - 1) else {
+                return Ok(());
+            };
+            let target = ensure_with(&mut target.components, c_idx - 1, Component::new);
+            match sub_idx {
+                None => *target = component.clone(),
+                Some(s_idx) => {
+                    let Some(subcomponent) = component.get_subcomponent(s_idx - 1) else {
+                        return Ok(());
+                    };
+                    *ensure_with(&mut target.subcomponents, s_idx - 1, || SubComponent::new("")) =
+                        subcomponent.clone();
+                }
+            }
+        }
+        (None, Some(_)) => {
+            return Err(Error::terser_path("Cannot copy subcomponent without component index"));
+        }
+    }
+    Ok(())
+}
+
+/// Get the item at `index`, padding `items` with new items as needed
+fn ensure_with<T>(items: &mut Vec<T>, index: usize, new: impl Fn() -> T) -> &mut T {
+    while items.len() <= index {
+        items.push(new());
+    }
+    &mut items[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rs7_parser::parse_message;
+
+    #[test]
+    fn test_overlay_levels() {
+        let mut message = parse_message(
+            "MSH|^~\\&|APP|FAC|||20250115||ADT^A08|1|P|2.5\rPID|1||111^^^MRN||DOE^JON^A||19700101\rOBX|1|ST|X",
+        )
+        .unwrap();
+        let other = parse_message(
+            "MSH|^~\\&|MPI|FAC|||20250115||ADT^A08|2|P|2.5\rPID|1||111^^^MRN~222^^^SSN||SMITH^JOHN&J||\rOBX|1|ST|A\rOBX|2|ST|B^Bee",
+        )
+        .unwrap();
+
+        message
+            .overlay(&other, &["PID-5-2-2", "PID-3(1)", "PID-7", "PID-20", "OBX(2)-3-2", "MSH-3"])
+            .unwrap();
+        let terser = Terser::new(&message);
+        assert_eq!(terser.get("PID-5-1").unwrap(), Some("DOE"));
+        assert_eq!(terser.get("PID-5-2-1").unwrap(), Some("JON"));
+        assert_eq!(terser.get("PID-5-2-2").unwrap(), Some("J"));
+        assert_eq!(terser.get("PID-5-3").unwrap(), Some("A"));
+        assert_eq!(terser.get("PID-3(1)-4").unwrap(), Some("SSN"));
+        // Present but empty in `other` overwrites, absent leaves unchanged
+        assert_eq!(terser.get("PID-7").unwrap(), Some(""));
+        assert_eq!(terser.get("PID-20").unwrap(), None);
+        assert_eq!(terser.get("OBX(2)-3-2").unwrap(), Some("Bee"));
+        assert_eq!(terser.get("MSH-3").unwrap(), Some("MPI"));
+
+        // Whole fields replace all repetitions and components
+        message.overlay(&other, &["PID-5"]).unwrap();
+        assert_eq!(Terser::new(&message).get("PID-5-3").unwrap(), None);
+
+        assert!(message.overlay(&other, &["PID"]).is_err());
+    }
+
+    #[test]
+    fn test_overlay_segment() {
+        let mut message = parse_message(
+            "MSH|^~\\&|APP|FAC|||20250115||ADT^A08|1|P|2.5\rPID|1\rIN1|1|OLD\rNK1|1\rIN1|2|OLD2\rPV1|1",
+        )
+        .unwrap();
+        let other = parse_message(
+            "MSH|^~\\&|MPI|FAC|||20250115||ADT^A08|2|P|2.5\rIN1|1|NEW\rIN1|2|NEW2\rIN1|3|NEW3",
+        )
+        .unwrap();
+
+        assert_eq!(message.overlay_segment(&other, "IN1"), 3);
+        let ids: Vec<_> = message.segments.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["MSH", "PID", "IN1", "IN1", "IN1", "NK1", "PV1"]);
+        assert_eq!(message.segments[4].get_field_value(2), Some("NEW3"));
+
+        assert_eq!(message.overlay_segment(&other, "MRG"), 0);
+        assert_eq!(message.segment_count(), 7);
+
+        let mut empty = parse_message("MSH|^~\\&|APP|FAC|||20250115||ADT^A08|1|P|2.5").unwrap();
+        empty.overlay_segment(&other, "IN1");
+        assert_eq!(empty.segment_count(), 4);
+    }
+}
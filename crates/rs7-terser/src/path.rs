@@ -3,6 +3,7 @@
 //! This module handles parsing of terser path strings like "PID-5-1" or "OBX(2)-3-1"
 
 use rs7_core::error::{Error, Result};
+use rs7_core::field::Field;
 
 /// Parsed terser path
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -11,7 +12,13 @@ pub(crate) struct TerserPath {
     pub segment_index: usize,
     pub field_index: usize,
     pub repetition_index: usize,
+    /// Whether the repetition index was given explicitly, e.g. `PID-3(1)`
+    pub explicit_repetition: bool,
+    /// Repetition counted from the end (1 = last), e.g. `PID-3(last)` or `PID-3(-2)`
+    pub repetition_from_end: Option<usize>,
     pub component_index: Option<usize>,
+    /// Whether the component is the last one, e.g. `PID-5-last`
+    pub last_component: bool,
     pub subcomponent_index: Option<usize>,
 }
 
@@ -26,8 +33,13 @@ impl TerserPath {
     /// - PID-5-1-2 (field 5, component 1, subcomponent 2)
     /// - OBX(2)-5 (field 5 of second OBX, 1-indexed)
     /// - PID-11(1)-1 (field 11, second repetition, component 1)
+    /// - PID-3(last)-1 or PID-3(-1)-1 (field 3, last repetition, component 1)
+    /// - PID-5-last (field 5, last component)
+    ///
+    /// `last` and negative indices are resolved against the actual field at
+    /// access time, see [`TerserPath::resolve`].
     pub fn parse(path: &str) -> Result<Self> {
-        let parts: Vec<&str> = path.split('-').collect();
+        let parts = Self::split_parts(path);
 
         if parts.is_empty() {
             return Err(Error::terser_path("Empty path"));
@@ -41,18 +53,43 @@ impl TerserPath {
             return Err(Error::terser_path("Missing field index"));
         }
 
-        let (field_index, repetition_index) = Self::parse_field_part(parts[1])?;
+        Self::parse_field_parts(segment_id, segment_index, &parts[1..])
+    }
+
+    /// Parse a path relative to a segment, without the segment ID
+    ///
+    /// Format: FIELD[(rep)]-COMPONENT-SUBCOMPONENT, e.g. `5-1` or `3(1)-1`.
+    /// The segment ID of the result is empty.
+    pub fn parse_relative(path: &str) -> Result<Self> {
+        if path.is_empty() {
+            return Err(Error::terser_path("Empty path"));
+        }
+        Self::parse_field_parts(String::new(), 0, &Self::split_parts(path))
+    }
+
+    /// Parse the field, component and subcomponent parts of a path
+    fn parse_field_parts(segment_id: String, segment_index: usize, parts: &[&str]) -> Result<Self> {
+        let (field_index, repetition) = Self::parse_field_part(parts[0])?;
+        let explicit_repetition = parts[0].contains('(');
+        let (repetition_index, repetition_from_end) = match repetition {
+            RepetitionIndex::Absolute(index) => (index, None),
+            RepetitionIndex::FromEnd(count) => (0, Some(count)),
+        };
 
         // Parse component index (optional)
-        let component_index = if parts.len() >= 3 {
-            Some(Self::parse_index(parts[2])?)
+        let last_component = parts.get(1) == Some(&"last");
+        let component_index = if last_component {
+            // Placeholder, resolved at access time
+            Some(1)
+        } else if parts.len() >= 2 {
+            Some(Self::parse_index(parts[1])?)
         } else {
             None
         };
 
         // Parse subcomponent index (optional)
-        let subcomponent_index = if parts.len() >= 4 {
-            Some(Self::parse_index(parts[3])?)
+        let subcomponent_index = if parts.len() >= 3 {
+            Some(Self::parse_index(parts[2])?)
         } else {
             None
         };
@@ -62,11 +99,57 @@ impl TerserPath {
             segment_index,
             field_index,
             repetition_index,
+            explicit_repetition,
+            repetition_from_end,
             component_index,
+            last_component,
             subcomponent_index,
         })
     }
 
+    /// Resolve `last` and negative indices against `field`
+    ///
+    /// Returns the concrete 0-based repetition index and 1-based component
+    /// index, or `None` if an index counted from the end is out of range.
+    pub fn resolve(&self, field: &Field) -> Option<(usize, Option<usize>)> {
+        let repetition_index = match self.repetition_from_end {
+            Some(count) => field.repetitions.len().checked_sub(count)?,
+            None => self.repetition_index,
+        };
+        Some((repetition_index, self.resolve_component(field, repetition_index)?))
+    }
+
+    /// Resolve a `last` component against one repetition of `field`
+    ///
+    /// Returns `None` if the repetition does not exist or has no components.
+    pub fn resolve_component(&self, field: &Field, repetition_index: usize) -> Option<Option<usize>> {
+        if !self.last_component {
+            return Some(self.component_index);
+        }
+        let count = field.repetitions.get(repetition_index)?.components.len();
+        (count > 0).then_some(Some(count))
+    }
+
+    /// Split a path on `-`, except inside parentheses (e.g. `PID-3(-1)-1`)
+    fn split_parts(path: &str) -> Vec<&str> {
+        let mut parts = Vec::new();
+        let mut depth = 0usize;
+        let mut start = 0;
+        for (i, c) in path.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                '-' if depth == 0 => {
+                    parts.push(&path[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        parts.push(&path[start..]);
+        parts
+    }
+
     /// Parse segment part (e.g., "PID" or "OBX(2)")
     /// Note: Uses 1-based indexing in API (OBX(1) = first, OBX(2) = second)
     /// but converts to 0-based internally for array access
@@ -98,8 +181,8 @@ impl TerserPath {
         }
     }
 
-    /// Parse field part (e.g., "5" or "11(1)")
-    fn parse_field_part(part: &str) -> Result<(usize, usize)> {
+    /// Parse field part (e.g., "5", "11(1)", "3(last)" or "3(-2)")
+    fn parse_field_part(part: &str) -> Result<(usize, RepetitionIndex)> {
         if let Some(paren_pos) = part.find('(') {
             let field_str = &part[..paren_pos];
             let rep_str = &part[paren_pos + 1..];
@@ -109,9 +192,21 @@ impl TerserPath {
                 .map_err(|_| Error::terser_path("Invalid field index"))?;
 
             if let Some(close_paren) = rep_str.find(')') {
-                let rep_index = rep_str[..close_paren]
-                    .parse::<usize>()
-                    .map_err(|_| Error::terser_path("Invalid repetition index"))?;
+                let rep_str = &rep_str[..close_paren];
+                let rep_index = if rep_str == "last" {
+                    RepetitionIndex::FromEnd(1)
+                } else if let Some(count) = rep_str.strip_prefix('-') {
+                    match count.parse::<usize>() {
+                        Ok(count) if count > 0 => RepetitionIndex::FromEnd(count),
+                        _ => return Err(Error::terser_path("Invalid repetition index")),
+                    }
+                } else {
+                    RepetitionIndex::Absolute(
+                        rep_str
+                            .parse::<usize>()
+                            .map_err(|_| Error::terser_path("Invalid repetition index"))?,
+                    )
+                };
                 Ok((field_index, rep_index))
             } else {
                 Err(Error::terser_path("Missing closing parenthesis"))
@@ -120,7 +215,7 @@ impl TerserPath {
             let field_index = part
                 .parse::<usize>()
                 .map_err(|_| Error::terser_path("Invalid field index"))?;
-            Ok((field_index, 0))
+            Ok((field_index, RepetitionIndex::Absolute(0)))
         }
     }
 
@@ -131,6 +226,14 @@ impl TerserPath {
     }
 }
 
+/// Repetition index as written in a path
+enum RepetitionIndex {
+    /// 0-based index from the start
+    Absolute(usize),
+    /// 1-based count from the end (1 = last)
+    FromEnd(usize),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -190,6 +293,30 @@ mod tests {
         let path = TerserPath::parse("PID-11(1)-1").unwrap();
         assert_eq!(path.field_index, 11);
         assert_eq!(path.repetition_index, 1);
+        assert!(path.explicit_repetition);
         assert_eq!(path.component_index, Some(1));
+
+        let path = TerserPath::parse("PID-11-1").unwrap();
+        assert!(!path.explicit_repetition);
+    }
+
+    #[test]
+    fn test_parse_from_end_indices() {
+        let path = TerserPath::parse("PID-3(last)-1").unwrap();
+        assert_eq!(path.field_index, 3);
+        assert!(path.explicit_repetition);
+        assert_eq!(path.repetition_from_end, Some(1));
+        assert_eq!(path.component_index, Some(1));
+
+        let path = TerserPath::parse("PID-3(-2)").unwrap();
+        assert_eq!(path.repetition_from_end, Some(2));
+
+        let path = TerserPath::parse("PID-5-last").unwrap();
+        assert!(path.last_component);
+        assert_eq!(path.repetition_from_end, None);
+
+        assert!(TerserPath::parse("PID-3(-0)").is_err());
+        assert!(TerserPath::parse("PID-3(-x)").is_err());
+        assert!(TerserPath::parse("PID-5-1-last").is_err());
     }
 }
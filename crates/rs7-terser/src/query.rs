@@ -3,7 +3,7 @@
 //! This module provides the `TerserQuery` which allows finding segments
 //! based on conditions and extracting field values conditionally.
 
-use crate::Terser;
+use crate::{SegmentTerser, Terser};
 use rs7_core::{Message, Segment};
 
 /// Query interface for conditional field access
@@ -134,6 +134,57 @@ impl<'a> TerserQuery<'a> {
             .collect()
     }
 
+    /// Find segments for which a Terser predicate holds
+    ///
+    /// The predicate receives a [`SegmentTerser`] over the segment being
+    /// tested, so paths leave out the segment ID (`3-1`). Aliased segments
+    /// are included as in Terser paths. Each match is returned with its
+    /// 1-based occurrence number, which can be used in paths such as
+    /// `OBX(n)-5`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs7_terser::{Terser, TerserQuery};
+    /// use rs7_parser::parse_message;
+    ///
+    /// # fn main() -> rs7_core::Result<()> {
+    /// let hl7 = r"MSH|^~\&|APP|FAC|||20250115||ORU^R01|123|P|2.5
+    /// OBX|1|NM|GLU^Glucose||98
+    /// OBX|2|NM|NA^Sodium||140
+    /// OBX|3|NM|GLU^Glucose||102";
+    ///
+    /// let message = parse_message(hl7)?;
+    /// let query = TerserQuery::new(&message);
+    ///
+    /// let glucose = query.segments_where("OBX", |t| t.get("3-1").ok().flatten() == Some("GLU"));
+    /// let occurrences: Vec<usize> = glucose.iter().map(|(n, _)| *n).collect();
+    /// assert_eq!(occurrences, vec![1, 3]);
+    ///
+    /// // Compose with Terser paths to extract just the matching values
+    /// let terser = Terser::new(&message);
+    /// let mut values = Vec::new();
+    /// for (n, _) in &glucose {
+    ///     values.extend(terser.get_all(&format!("OBX({})-5", n))?);
+    /// }
+    /// assert_eq!(values, vec!["98", "102"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn segments_where(
+        &self,
+        segment_id: &str,
+        predicate: impl Fn(&SegmentTerser<'a>) -> bool,
+    ) -> Vec<(usize, &'a Segment)> {
+        self.message()
+            .resolve_segments(segment_id)
+            .into_iter()
+            .enumerate()
+            .filter(|(_, segment)| predicate(&SegmentTerser::new(segment)))
+            .map(|(index, segment)| (index + 1, segment))
+            .collect()
+    }
+
     /// Find repeating segments where a specific field equals a value
     ///
     /// # Examples
@@ -452,6 +503,27 @@ OBX|3|NM|K||4.2";
         assert_eq!(high_values.len(), 1);
     }
 
+    #[test]
+    fn test_segments_where() {
+        let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ORU^R01|123|P|2.5\r\
+OBX|1|NM|GLU^Glucose||98||||||F\r\
+OBX|2|NM|NA^Sodium||140|||HH||F\r\
+ZOB|3|NM|K^Potassium||6.8|||HH||F\r\
+OBX|4|NM|GLU^Glucose||350|||HH||F";
+
+        let mut message = parse_message(hl7).unwrap();
+        message.add_segment_alias("ZOB", "OBX");
+        let query = TerserQuery::new(&message);
+
+        let critical = query.segments_where("OBX", |t| t.get("8").ok().flatten() == Some("HH"));
+        let ids: Vec<_> = critical.iter().map(|(n, seg)| (*n, seg.get_field_value(1))).collect();
+        assert_eq!(ids, vec![(2, Some("2")), (3, Some("3")), (4, Some("4"))]);
+
+        let glucose = query.segments_where("OBX", |t| t.get("3-1").ok().flatten() == Some("GLU"));
+        assert_eq!(glucose.len(), 2);
+        assert!(query.segments_where("PID", |_| true).is_empty());
+    }
+
     #[test]
     fn test_filter_repeating() {
         let hl7 = r"MSH|^~\&|APP|FAC|||20250115||ORU^R01|123|P|2.5
@@ -0,0 +1,171 @@
+//! Terser access relative to a single segment
+//!
+//! [`SegmentTerser`] reads values from one segment with paths that leave out
+//! the segment ID, such as `5-1` for component 1 of field 5. It suits code
+//! that already holds a segment, like per-segment predicates or converters
+//! iterating over OBX segments.
+
+use crate::path::TerserPath;
+use rs7_core::{error::Result, field::Field, segment::Segment};
+
+/// Terser scoped to a single segment
+///
+/// Paths use the same syntax as [`Terser`](crate::Terser) after the segment
+/// part: `5`, `5-1`, `5-1-2`, `3(1)-1`, `3(last)-1` and `5-last`. As there,
+/// repetition indices are 0-based, so `3(1)` is the second repetition.
+///
+/// # Examples
+///
+/// ```
+/// use rs7_terser::SegmentTerser;
+/// use rs7_parser::parse_message;
+///
+/// # fn main() -> rs7_core::Result<()> {
+/// let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ORU^R01|123|P|2.5\rOBX|1|NM|GLU^Glucose||98|mg/dL\rOBX|2|NM|NA^Sodium||140|mmol/L";
+/// let message = parse_message(hl7)?;
+///
+/// let codes: Vec<_> = message
+///     .get_segments_by_id("OBX")
+///     .into_iter()
+///     .map(|obx| SegmentTerser::new(obx).get("3-1"))
+///     .collect::<rs7_core::Result<_>>()?;
+/// assert_eq!(codes, vec![Some("GLU"), Some("NA")]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct SegmentTerser<'a> {
+    segment: &'a Segment,
+}
+
+impl<'a> SegmentTerser<'a> {
+    /// Create a Terser over `segment`
+    pub fn new(segment: &'a Segment) -> Self {
+        Self { segment }
+    }
+
+    /// Get the segment this Terser reads from
+    pub fn segment(&self) -> &'a Segment {
+        self.segment
+    }
+
+    /// Get a value using a path without the segment ID
+    ///
+    /// Returns `Ok(None)` if the value is absent, and an error if the path
+    /// is malformed or includes a segment ID.
+    pub fn get(&self, path: &str) -> Result<Option<&'a str>> {
+        Ok(self.get_path(&TerserPath::parse_relative(path)?))
+    }
+
+    /// Get a value from every repetition of a field
+    ///
+    /// Repetitions where the value is missing produce an empty string. With
+    /// an explicit repetition, e.g. `3(1)-1`, only that repetition is read.
+    /// See [`Terser::get_all`](crate::Terser::get_all).
+    pub fn get_all(&self, path: &str) -> Result<Vec<&'a str>> {
+        Ok(self.get_all_path(&TerserPath::parse_relative(path)?))
+    }
+
+    /// Resolve the field part of a parsed path against this segment
+    pub(crate) fn get_path(&self, path: &TerserPath) -> Option<&'a str> {
+        let field = self.segment.get_field(path.field_index)?;
+
+        // Resolve `last`/negative indices, then navigate to the value
+        let (rep_idx, component_index) = path.resolve(field)?;
+        field_value(field, rep_idx, component_index, path.subcomponent_index)
+    }
+
+    /// Resolve the field part of a parsed path for every repetition
+    pub(crate) fn get_all_path(&self, path: &TerserPath) -> Vec<&'a str> {
+        let Some(field) = self.segment.get_field(path.field_index) else {
+            return Vec::new();
+        };
+
+        let repetitions = if path.explicit_repetition {
+            match path.resolve(field) {
+                Some((idx, _)) => idx..(idx + 1).min(field.repetitions.len()),
+                None => return Vec::new(),
+            }
+        } else {
+            0..field.repetitions.len()
+        };
+
+        repetitions
+            .map(|rep_idx| {
+                path.resolve_component(field, rep_idx)
+                    .and_then(|component_index| {
+                        field_value(field, rep_idx, component_index, path.subcomponent_index)
+                    })
+                    .unwrap_or("")
+            })
+            .collect()
+    }
+}
+
+/// Get a field value at the specified indices
+///
+/// Note: Component and subcomponent indices are 1-based (HL7 standard notation)
+/// but internally converted to 0-based for array access.
+fn field_value(
+    field: &Field,
+    rep_idx: usize,
+    comp_idx: Option<usize>,
+    sub_idx: Option<usize>,
+) -> Option<&str> {
+    let repetition = field.get_repetition(rep_idx)?;
+
+    match (comp_idx, sub_idx) {
+        (None, None) => {
+            // Just the field value
+            repetition.value()
+        }
+        (Some(c_idx), None) => {
+            // Component value (convert 1-based HL7 to 0-based internal)
+            if c_idx == 0 {
+                return None; // Invalid: HL7 uses 1-based indexing
+            }
+            repetition.get_component(c_idx - 1)?.value()
+        }
+        (Some(c_idx), Some(s_idx)) => {
+            // Subcomponent value (convert 1-based HL7 to 0-based internal)
+            if c_idx == 0 || s_idx == 0 {
+                return None; // Invalid: HL7 uses 1-based indexing
+            }
+            repetition
+                .get_component(c_idx - 1)?
+                .get_subcomponent(s_idx - 1)?
+                .as_str()
+                .into()
+        }
+        (None, Some(_)) => {
+            // Invalid: can't have subcomponent without component
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rs7_parser::parse_message;
+
+    #[test]
+    fn test_segment_terser_paths() {
+        let hl7 = "MSH|^~\\&|APP|FAC|||20250115||ADT^A01|123|P|2.5\rPID|1||111^^^MRN~222^^^SSN||DOE^JOHN&J^III";
+        let message = parse_message(hl7).unwrap();
+        let pid = SegmentTerser::new(message.segment("PID").unwrap());
+
+        assert_eq!(pid.get("1").unwrap(), Some("1"));
+        assert_eq!(pid.get("5-1").unwrap(), Some("DOE"));
+        assert_eq!(pid.get("5-2-2").unwrap(), Some("J"));
+        assert_eq!(pid.get("5-last").unwrap(), Some("III"));
+        assert_eq!(pid.get("3(1)-1").unwrap(), Some("222"));
+        assert_eq!(pid.get("3(last)-4").unwrap(), Some("SSN"));
+        assert_eq!(pid.get("30").unwrap(), None);
+        assert_eq!(pid.get_all("3-1").unwrap(), vec!["111", "222"]);
+        assert_eq!(pid.get_all("3(0)-4").unwrap(), vec!["MRN"]);
+        assert_eq!(pid.segment().id, "PID");
+
+        assert!(pid.get("PID-5").is_err());
+        assert!(pid.get("").is_err());
+    }
+}
@@ -13,6 +13,15 @@
 //!   - source: PID-3-1
 //!     target: PID-3-1
 //!     default: UNKNOWN
+//!   - kind: concat
+//!     sources: [PID-5-1, PID-5-2]
+//!     separator: ", "
+//!     target: PID-9-1
+//!   - kind: split
+//!     source: PID-11-1
+//!     separator: " "
+//!     targets: [PID-11-2, PID-11-3]
+//!     truncate: true
 //! ```
 //!
 //! Loading configuration:
@@ -33,12 +42,34 @@ use std::collections::HashMap;
 /// Configuration for a single transformation rule
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RuleConfig {
-    /// Source field path (terser notation)
+    /// Rule kind: `map` (default), `concat` or `split`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+
+    /// Source field path (terser notation); unused by `concat` rules
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub source: String,
 
-    /// Target field path (terser notation)
+    /// Target field path (terser notation); unused by `split` rules
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub target: String,
 
+    /// Source field paths joined by a `concat` rule
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sources: Option<Vec<String>>,
+
+    /// Target field paths filled by a `split` rule
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub targets: Option<Vec<String>>,
+
+    /// Separator for `concat` and `split` rules (default: a single space)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub separator: Option<String>,
+
+    /// Whether a `split` rule drops extra parts instead of failing (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncate: Option<bool>,
+
     /// Optional transformation function name
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transform: Option<String>,
@@ -54,18 +85,53 @@ pub struct RuleConfig {
     /// Optional transformation parameters
     #[serde(skip_serializing_if = "Option::is_none")]
     pub params: Option<HashMap<String, String>>,
+
+    /// Optional rule name, referenced by other rules' `after`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Names of rules that must run before this one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Vec<String>>,
 }
 
 impl RuleConfig {
     /// Create a new rule configuration
     pub fn new<S: Into<String>, T: Into<String>>(source: S, target: T) -> Self {
         Self {
+            kind: None,
             source: source.into(),
             target: target.into(),
+            sources: None,
+            targets: None,
+            separator: None,
+            truncate: None,
             transform: None,
             default: None,
             skip_if_empty: None,
             params: None,
+            name: None,
+            after: None,
+        }
+    }
+
+    /// Create a rule configuration joining several sources into a target
+    pub fn concat<S: Into<String>, T: Into<String>>(sources: Vec<String>, separator: S, target: T) -> Self {
+        Self {
+            kind: Some("concat".to_string()),
+            sources: Some(sources),
+            separator: Some(separator.into()),
+            ..Self::new("", target)
+        }
+    }
+
+    /// Create a rule configuration splitting a source into several targets
+    pub fn split<S: Into<String>, T: Into<String>>(source: S, separator: T, targets: Vec<String>) -> Self {
+        Self {
+            kind: Some("split".to_string()),
+            targets: Some(targets),
+            separator: Some(separator.into()),
+            ..Self::new(source, "")
         }
     }
 
@@ -89,7 +155,26 @@ impl RuleConfig {
 
     /// Convert to a TransformationRule
     pub fn to_rule(&self, context: &mut TransformContext) -> Result<TransformationRule> {
-        let mut rule = TransformationRule::new(&self.source, &self.target);
+        let separator = self.separator.as_deref().unwrap_or(" ");
+        let mut rule = match self.kind.as_deref().unwrap_or("map") {
+            "map" => TransformationRule::new(&self.source, &self.target),
+            "concat" => {
+                let sources = self
+                    .sources
+                    .as_ref()
+                    .ok_or_else(|| Error::config("concat rule requires sources"))?;
+                TransformationRule::concat(sources, separator, &self.target)
+            }
+            "split" => {
+                let targets = self
+                    .targets
+                    .as_ref()
+                    .ok_or_else(|| Error::config("split rule requires targets"))?;
+                TransformationRule::split(&self.source, separator, targets)
+                    .truncate(self.truncate.unwrap_or(false))
+            }
+            kind => return Err(Error::config(format!("Unknown rule kind: {}", kind))),
+        };
 
         // Set default value if provided
         if let Some(default) = &self.default {
@@ -101,6 +186,14 @@ impl RuleConfig {
             rule = rule.skip_if_empty(skip);
         }
 
+        // Set name and dependencies if provided
+        if let Some(name) = &self.name {
+            rule = rule.with_name(name);
+        }
+        for dependency in self.after.iter().flatten() {
+            rule = rule.after(dependency);
+        }
+
         // Add params to context if provided
         if let Some(params) = &self.params {
             for (key, value) in params {
@@ -120,8 +213,10 @@ impl RuleConfig {
                 "substring" => transforms::substring,
                 "format_date" => transforms::format_date,
                 "format_datetime" => transforms::format_datetime,
+                "hl7_to_iso8601" => transforms::hl7_to_iso8601,
+                "iso8601_to_hl7" => transforms::iso8601_to_hl7,
                 "replace" => transforms::replace,
-                "regex_replace" => transforms::regex_replace,
+                "regex_replace" => transforms::regex_replace_from_context,
                 "prefix" => transforms::prefix,
                 "suffix" => transforms::suffix,
                 "pad" => transforms::pad,
@@ -256,6 +351,7 @@ impl Default for TransformConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rule::RuleKind;
 
     #[test]
     fn test_rule_config_creation() {
@@ -401,6 +497,77 @@ rules:
         assert_eq!(transformer.rule_count(), 1);
     }
 
+    #[test]
+    fn test_yaml_rule_dependencies() {
+        let yaml = r#"
+rules:
+  - source: PID-9-1
+    target: PID-9-1
+    transform: uppercase
+    after: [copy_name]
+  - name: copy_name
+    source: PID-5-1
+    target: PID-9-1
+"#;
+
+        let config = TransformConfig::from_yaml(yaml).unwrap();
+        assert_eq!(config.rules[0].after, Some(vec!["copy_name".to_string()]));
+        assert_eq!(config.rules[1].name, Some("copy_name".to_string()));
+
+        let transformer = config.build().unwrap();
+        assert!(transformer.validate_rules().is_ok());
+    }
+
+    #[test]
+    fn test_yaml_concat_and_split() {
+        let yaml = r#"
+rules:
+  - kind: concat
+    sources: [PID-5-1, PID-5-2]
+    separator: ", "
+    target: PID-9-1
+  - kind: split
+    source: PID-11-1
+    targets: [PID-11-2, PID-11-3]
+    truncate: true
+"#;
+
+        let config = TransformConfig::from_yaml(yaml).unwrap();
+        let mut context = TransformContext::new();
+
+        let concat = config.rules[0].to_rule(&mut context).unwrap();
+        assert_eq!(
+            concat.kind,
+            RuleKind::Concat {
+                sources: vec!["PID-5-1".to_string(), "PID-5-2".to_string()],
+                separator: ", ".to_string(),
+            }
+        );
+        assert!(concat.validate().is_ok());
+
+        let split = config.rules[1].to_rule(&mut context).unwrap();
+        assert_eq!(
+            split.kind,
+            RuleKind::Split {
+                targets: vec!["PID-11-2".to_string(), "PID-11-3".to_string()],
+                separator: " ".to_string(),
+                truncate: true,
+            }
+        );
+        assert!(split.validate().is_ok());
+
+        // Round-trips without the unused single source/target
+        let yaml = config.to_yaml().unwrap();
+        assert!(!yaml.contains("source: ''"));
+        assert_eq!(TransformConfig::from_yaml(&yaml).unwrap().rules.len(), 2);
+
+        let rule = RuleConfig::new("PID-5-1", "PID-5-1");
+        let rule = RuleConfig { kind: Some("merge".to_string()), ..rule };
+        assert!(rule.to_rule(&mut context).is_err());
+        let rule = RuleConfig { kind: Some("split".to_string()), ..RuleConfig::new("PID-5-1", "") };
+        assert!(rule.to_rule(&mut context).is_err());
+    }
+
     #[test]
     fn test_unknown_transform_function() {
         let rule = RuleConfig::new("PID-5-1", "PID-5-1")
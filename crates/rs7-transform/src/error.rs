@@ -20,6 +20,10 @@ pub enum Error {
     #[error("Invalid transformation rule: {0}")]
     InvalidRule(String),
 
+    /// Rules whose dependencies form a cycle
+    #[error("Rule dependency cycle among: {}", .0.join(", "))]
+    DependencyCycle(Vec<String>),
+
     /// Configuration error (serde feature)
     #[cfg(feature = "serde")]
     #[error("Configuration error: {0}")]
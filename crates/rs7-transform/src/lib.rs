@@ -1,7 +1,7 @@
 //! Message transformation framework for HL7 v2.x messages
 //!
 //! This crate provides a flexible framework for transforming HL7 messages:
-//! - Field-to-field mappings
+//! - Field-to-field mappings, concatenation and splitting
 //! - Built-in transformation functions
 //! - Custom user-defined transforms
 //! - Declarative YAML/JSON configuration (with `serde` feature)
@@ -36,13 +36,13 @@ pub mod transforms;
 pub mod config;
 
 pub use error::{Error, Result};
-pub use rule::{TransformContext, TransformFn, TransformationRule};
+pub use rule::{RuleCondition, RuleKind, TransformContext, TransformFn, TransformationRule};
 pub use transformer::MessageTransformer;
 
 /// Prelude module for convenient imports
 pub mod prelude {
     pub use crate::error::{Error, Result};
-    pub use crate::rule::{TransformContext, TransformFn, TransformationRule};
+    pub use crate::rule::{RuleCondition, RuleKind, TransformContext, TransformFn, TransformationRule};
     pub use crate::transformer::MessageTransformer;
     pub use crate::transforms;
 
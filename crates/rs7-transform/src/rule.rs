@@ -1,6 +1,8 @@
 //! Transformation rule types and function signatures
 
 use crate::error::{Error, Result};
+use rs7_core::Message;
+use std::sync::Arc;
 
 /// A transformation function that converts a string value
 ///
@@ -12,7 +14,16 @@ use crate::error::{Error, Result};
 /// # Returns
 ///
 /// The transformed value or an error
-pub type TransformFn = fn(&str, &TransformContext) -> Result<String>;
+///
+/// Plain functions such as those in [`crate::transforms`] and closures are both
+/// accepted, so a transform can carry pre-computed state (e.g. a compiled regex).
+pub type TransformFn = Arc<dyn Fn(&str, &TransformContext) -> Result<String> + Send + Sync>;
+
+/// A predicate deciding whether a rule applies to a source message
+///
+/// Conditions are evaluated against the source message, before any rule has
+/// modified it, so they see the same values regardless of rule order.
+pub type RuleCondition = Arc<dyn Fn(&Message) -> bool + Send + Sync>;
 
 /// Context information available during transformation
 #[derive(Debug, Clone)]
@@ -59,6 +70,36 @@ impl Default for TransformContext {
     }
 }
 
+/// How a rule reads its source fields and writes its target fields
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum RuleKind {
+    /// Copy the source field to the target field
+    #[default]
+    Map,
+
+    /// Join several source fields into the target field
+    ///
+    /// Missing source values are treated as empty.
+    Concat {
+        /// Source field paths, in join order
+        sources: Vec<String>,
+        /// Separator placed between the values
+        separator: String,
+    },
+
+    /// Split the source field into several target fields
+    ///
+    /// Targets without a matching part are left unchanged.
+    Split {
+        /// Target field paths, in split order
+        targets: Vec<String>,
+        /// Delimiter the value is split on
+        separator: String,
+        /// Drop parts beyond the last target instead of failing
+        truncate: bool,
+    },
+}
+
 /// A transformation rule that maps a source field to a target field
 ///
 /// # Examples
@@ -72,6 +113,12 @@ impl Default for TransformContext {
 /// // Field mapping with transformation
 /// let rule = TransformationRule::new("PID-5-1", "PID-5-1")
 ///     .with_transform(|value, _ctx| Ok(value.to_uppercase()));
+///
+/// // "SMITH, JOHN" from family and given name
+/// let rule = TransformationRule::concat(["PID-5-1", "PID-5-2"], ", ", "PID-9-1");
+///
+/// // "JOHN SMITH" split into given and family name
+/// let rule = TransformationRule::split("PID-9-1", " ", ["PID-5-2", "PID-5-1"]);
 /// ```
 #[derive(Clone)]
 pub struct TransformationRule {
@@ -89,6 +136,18 @@ pub struct TransformationRule {
 
     /// Whether to skip if source is empty
     pub skip_if_empty: bool,
+
+    /// Optional condition the source message must satisfy for the rule to apply
+    pub condition: Option<RuleCondition>,
+
+    /// Optional name other rules can refer to in [`after`](Self::after)
+    pub name: Option<String>,
+
+    /// Names of the rules that must be applied before this one
+    pub dependencies: Vec<String>,
+
+    /// Whether the rule maps, concatenates or splits fields
+    pub kind: RuleKind,
 }
 
 impl TransformationRule {
@@ -105,12 +164,87 @@ impl TransformationRule {
             transform_fn: None,
             default_value: None,
             skip_if_empty: true,
+            condition: None,
+            name: None,
+            dependencies: Vec::new(),
+            kind: RuleKind::Map,
+        }
+    }
+
+    /// Create a rule joining several source fields into one target field
+    ///
+    /// The joined value goes through the transform and default like a single
+    /// source value; it counts as empty only when every source is empty.
+    pub fn concat<I, S, P, T>(sources: I, separator: P, target_path: T) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+        P: Into<String>,
+        T: Into<String>,
+    {
+        let sources: Vec<String> = sources.into_iter().map(Into::into).collect();
+        let mut rule = Self::new(sources.first().cloned().unwrap_or_default(), target_path);
+        rule.kind = RuleKind::Concat {
+            sources,
+            separator: separator.into(),
+        };
+        rule
+    }
+
+    /// Create a rule splitting one source field into several target fields
+    ///
+    /// The source value goes through the transform and default before it is
+    /// split. Extra parts are an error unless [`truncate`](Self::truncate) is set.
+    pub fn split<S, P, I, T>(source_path: S, separator: P, targets: I) -> Self
+    where
+        S: Into<String>,
+        P: Into<String>,
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        let targets: Vec<String> = targets.into_iter().map(Into::into).collect();
+        let mut rule = Self::new(source_path, targets.first().cloned().unwrap_or_default());
+        rule.kind = RuleKind::Split {
+            targets,
+            separator: separator.into(),
+            truncate: false,
+        };
+        rule
+    }
+
+    /// Set whether a split rule drops parts beyond its last target
+    ///
+    /// Default is false, which makes extra parts an error. Has no effect on
+    /// other rule kinds.
+    pub fn truncate(mut self, truncate: bool) -> Self {
+        if let RuleKind::Split { truncate: current, .. } = &mut self.kind {
+            *current = truncate;
+        }
+        self
+    }
+
+    /// Get the source field paths the rule reads
+    pub fn source_paths(&self) -> Vec<&str> {
+        match &self.kind {
+            RuleKind::Concat { sources, .. } => sources.iter().map(String::as_str).collect(),
+            _ => vec![self.source_path.as_str()],
+        }
+    }
+
+    /// Get the target field paths the rule writes
+    pub fn target_paths(&self) -> Vec<&str> {
+        match &self.kind {
+            RuleKind::Split { targets, .. } => targets.iter().map(String::as_str).collect(),
+            _ => vec![self.target_path.as_str()],
         }
     }
 
     /// Set the transformation function
-    pub fn with_transform(mut self, transform_fn: TransformFn) -> Self {
-        self.transform_fn = Some(transform_fn);
+    pub fn with_transform<F>(mut self, transform_fn: F) -> Self
+    where
+        F: Fn(&str, &TransformContext) -> Result<String> + Send + Sync + 'static,
+    {
+        self.transform_fn = Some(Arc::new(transform_fn));
         self
     }
 
@@ -128,6 +262,64 @@ impl TransformationRule {
         self
     }
 
+    /// Only apply the rule when the source message satisfies `condition`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rs7_transform::{rule::TransformationRule, transforms};
+    /// use rs7_terser::Terser;
+    ///
+    /// // Uppercase the family name of ADT messages only
+    /// let rule = TransformationRule::new("PID-5-1", "PID-5-1")
+    ///     .with_transform(transforms::uppercase)
+    ///     .with_condition(|msg| {
+    ///         Terser::new(msg).get("MSH-9-1").ok().flatten() == Some("ADT")
+    ///     });
+    /// ```
+    pub fn with_condition<F>(mut self, condition: F) -> Self
+    where
+        F: Fn(&Message) -> bool + Send + Sync + 'static,
+    {
+        self.condition = Some(Arc::new(condition));
+        self
+    }
+
+    /// Name the rule so other rules can depend on it
+    pub fn with_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Apply this rule after the rule named `rule_name`
+    ///
+    /// A rule with dependencies reads its source value from the message as
+    /// transformed so far, so it sees what the earlier rules wrote. See
+    /// [`MessageTransformer`](crate::MessageTransformer) for how rules are
+    /// scheduled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rs7_transform::{rule::TransformationRule, transforms};
+    ///
+    /// let copy = TransformationRule::new("PID-5-1", "PID-9-1").with_name("copy_name");
+    /// let upper = TransformationRule::new("PID-9-1", "PID-9-1")
+    ///     .with_transform(transforms::uppercase)
+    ///     .after("copy_name");
+    /// ```
+    pub fn after<S: Into<String>>(mut self, rule_name: S) -> Self {
+        self.dependencies.push(rule_name.into());
+        self
+    }
+
+    /// Check whether the rule applies to a source message
+    ///
+    /// Rules without a condition always apply.
+    pub fn applies_to(&self, message: &Message) -> bool {
+        self.condition.as_ref().is_none_or(|condition| condition(message))
+    }
+
     /// Apply the transformation to a value
     ///
     /// # Arguments
@@ -150,7 +342,7 @@ impl TransformationRule {
         }
 
         // Apply transformation if present
-        let result = if let Some(transform) = self.transform_fn {
+        let result = if let Some(transform) = &self.transform_fn {
             transform(value, context)?
         } else {
             value.to_string()
@@ -159,6 +351,41 @@ impl TransformationRule {
         Ok(Some(result))
     }
 
+    /// Pair a transformed value with the target paths it is written to
+    ///
+    /// Split rules yield one pair per part; other kinds yield the value for
+    /// the target path.
+    pub fn assignments(&self, value: String) -> Result<Vec<(&str, String)>> {
+        let RuleKind::Split {
+            targets,
+            separator,
+            truncate,
+        } = &self.kind
+        else {
+            return Ok(vec![(self.target_path.as_str(), value)]);
+        };
+
+        let parts: Vec<&str> = if separator.is_empty() {
+            vec![value.as_str()]
+        } else {
+            value.split(separator.as_str()).collect()
+        };
+        if parts.len() > targets.len() && !truncate {
+            return Err(Error::transform_fn(format!(
+                "Splitting {} produced {} values for {} targets",
+                self.source_path,
+                parts.len(),
+                targets.len()
+            )));
+        }
+
+        Ok(targets
+            .iter()
+            .map(String::as_str)
+            .zip(parts.into_iter().map(str::to_string))
+            .collect())
+    }
+
     /// Validate the rule
     pub fn validate(&self) -> Result<()> {
         if self.source_path.is_empty() {
@@ -169,17 +396,21 @@ impl TransformationRule {
         }
 
         // Basic validation of terser path format
-        if !self.is_valid_terser_path(&self.source_path) {
-            return Err(Error::invalid_rule(format!(
-                "Invalid source path format: {}",
-                self.source_path
-            )));
+        for source_path in self.source_paths() {
+            if !self.is_valid_terser_path(source_path) {
+                return Err(Error::invalid_rule(format!(
+                    "Invalid source path format: {}",
+                    source_path
+                )));
+            }
         }
-        if !self.is_valid_terser_path(&self.target_path) {
-            return Err(Error::invalid_rule(format!(
-                "Invalid target path format: {}",
-                self.target_path
-            )));
+        for target_path in self.target_paths() {
+            if !self.is_valid_terser_path(target_path) {
+                return Err(Error::invalid_rule(format!(
+                    "Invalid target path format: {}",
+                    target_path
+                )));
+            }
         }
 
         Ok(())
@@ -201,6 +432,10 @@ impl std::fmt::Debug for TransformationRule {
             .field("has_transform_fn", &self.transform_fn.is_some())
             .field("default_value", &self.default_value)
             .field("skip_if_empty", &self.skip_if_empty)
+            .field("has_condition", &self.condition.is_some())
+            .field("name", &self.name)
+            .field("dependencies", &self.dependencies)
+            .field("kind", &self.kind)
             .finish()
     }
 }
@@ -216,6 +451,18 @@ mod tests {
         assert_eq!(rule.target_path, "PID-5-1");
         assert!(rule.transform_fn.is_none());
         assert!(rule.skip_if_empty);
+        assert!(rule.condition.is_none());
+    }
+
+    #[test]
+    fn test_rule_condition() {
+        let message = Message::new();
+
+        let rule = TransformationRule::new("PID-5-1", "PID-5-1");
+        assert!(rule.applies_to(&message));
+
+        let rule = rule.with_condition(|msg| !msg.segments.is_empty());
+        assert!(!rule.applies_to(&message));
     }
 
     #[test]
@@ -304,6 +551,49 @@ mod tests {
         assert!(rule.validate().is_err());
     }
 
+    #[test]
+    fn test_concat_rule() {
+        let rule = TransformationRule::concat(["PID-5-1", "PID-5-2"], ", ", "PID-9-1");
+        assert_eq!(rule.source_paths(), vec!["PID-5-1", "PID-5-2"]);
+        assert_eq!(rule.target_paths(), vec!["PID-9-1"]);
+        assert!(rule.validate().is_ok());
+
+        let rule = TransformationRule::concat(["PID-5-1", "INVALID"], " ", "PID-9-1");
+        assert!(rule.validate().is_err());
+
+        let rule = TransformationRule::concat(Vec::<String>::new(), " ", "PID-9-1");
+        assert!(rule.validate().is_err());
+    }
+
+    #[test]
+    fn test_split_assignments() {
+        let rule = TransformationRule::split("PID-9-1", " ", ["PID-5-2", "PID-5-1"]);
+        assert!(rule.validate().is_ok());
+
+        let assignments = rule.assignments("JOHN SMITH".to_string()).unwrap();
+        assert_eq!(
+            assignments,
+            vec![("PID-5-2", "JOHN".to_string()), ("PID-5-1", "SMITH".to_string())]
+        );
+
+        // Fewer parts than targets leaves the remaining targets out
+        let assignments = rule.assignments("JOHN".to_string()).unwrap();
+        assert_eq!(assignments, vec![("PID-5-2", "JOHN".to_string())]);
+
+        // Extra parts fail unless truncating
+        assert!(rule.assignments("JOHN Q SMITH".to_string()).is_err());
+        let rule = rule.truncate(true);
+        let assignments = rule.assignments("JOHN Q SMITH".to_string()).unwrap();
+        assert_eq!(
+            assignments,
+            vec![("PID-5-2", "JOHN".to_string()), ("PID-5-1", "Q".to_string())]
+        );
+
+        let rule = TransformationRule::new("PID-5-1", "PID-5-1");
+        let assignments = rule.assignments("SMITH".to_string()).unwrap();
+        assert_eq!(assignments, vec![("PID-5-1", "SMITH".to_string())]);
+    }
+
     #[test]
     fn test_context_creation() {
         let ctx = TransformContext::new();
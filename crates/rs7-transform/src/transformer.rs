@@ -1,9 +1,10 @@
 //! Message transformer with fluent API
 
 use crate::error::{Error, Result};
-use crate::rule::{TransformContext, TransformFn, TransformationRule};
+use crate::rule::{RuleKind, TransformContext, TransformationRule};
 use rs7_core::Message;
 use rs7_terser::{Terser, TerserMut};
+use std::collections::{BTreeSet, HashMap};
 
 /// Message transformer that applies transformation rules to HL7 messages
 ///
@@ -25,6 +26,36 @@ use rs7_terser::{Terser, TerserMut};
 /// # let source = Message::new();
 /// let result = transformer.transform(&source);
 /// ```
+///
+/// # Rule Scheduling
+///
+/// Rules run in insertion order unless they declare dependencies with
+/// [`TransformationRule::after`]. Before transforming, the rules are sorted
+/// topologically: a rule runs after every rule it names, and among rules whose
+/// dependencies are satisfied the earliest added runs first, so the order is
+/// deterministic and unchanged for rules without dependencies.
+///
+/// Rules without dependencies read their source value from the original
+/// message. Rules with dependencies read from the message as transformed so
+/// far, which lets one rule's output be another's input:
+///
+/// ```rust
+/// use rs7_transform::{MessageTransformer, rule::TransformationRule, transforms};
+///
+/// let mut transformer = MessageTransformer::new();
+/// // Added first, but applied after the copy it depends on
+/// transformer.add_rule(
+///     TransformationRule::new("PID-9-1", "PID-9-1")
+///         .with_transform(transforms::uppercase)
+///         .after("copy_name"),
+/// );
+/// transformer.add_rule(TransformationRule::new("PID-5-1", "PID-9-1").with_name("copy_name"));
+/// assert!(transformer.validate_rules().is_ok());
+/// ```
+///
+/// Transforming fails with [`Error::InvalidRule`] if a dependency names an
+/// unknown rule or two rules share a name, and with [`Error::DependencyCycle`]
+/// if dependencies form a cycle.
 #[derive(Debug)]
 pub struct MessageTransformer {
     /// List of transformation rules to apply
@@ -79,14 +110,55 @@ impl MessageTransformer {
     /// let mut transformer = MessageTransformer::new();
     /// transformer.add_transform("PID-5-1", "PID-5-1", transforms::uppercase);
     /// ```
-    pub fn add_transform<S: Into<String>, T: Into<String>>(
+    pub fn add_transform<S, T, F>(&mut self, source_path: S, target_path: T, transform_fn: F)
+    where
+        S: Into<String>,
+        T: Into<String>,
+        F: Fn(&str, &TransformContext) -> Result<String> + Send + Sync + 'static,
+    {
+        let rule = TransformationRule::new(source_path, target_path)
+            .with_transform(transform_fn);
+        self.rules.push(rule);
+    }
+
+    /// Add a field mapping with a transformation that only applies when `condition` holds
+    ///
+    /// The condition is evaluated against the source message, so mapping tables
+    /// for several message types can live in one transformer.
+    ///
+    /// # Arguments
+    ///
+    /// * `source_path` - Source field path (terser notation)
+    /// * `target_path` - Target field path (terser notation)
+    /// * `transform_fn` - Transformation function to apply
+    /// * `condition` - Predicate on the source message gating the rule
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rs7_transform::{MessageTransformer, transforms};
+    /// use rs7_terser::Terser;
+    ///
+    /// let mut transformer = MessageTransformer::new();
+    /// transformer.add_conditional_transform("PID-5-1", "PID-5-1", transforms::uppercase, |msg| {
+    ///     Terser::new(msg).get("MSH-9-1").ok().flatten() == Some("ADT")
+    /// });
+    /// ```
+    pub fn add_conditional_transform<S, T, F, C>(
         &mut self,
         source_path: S,
         target_path: T,
-        transform_fn: TransformFn,
-    ) {
+        transform_fn: F,
+        condition: C,
+    ) where
+        S: Into<String>,
+        T: Into<String>,
+        F: Fn(&str, &TransformContext) -> Result<String> + Send + Sync + 'static,
+        C: Fn(&Message) -> bool + Send + Sync + 'static,
+    {
         let rule = TransformationRule::new(source_path, target_path)
-            .with_transform(transform_fn);
+            .with_transform(transform_fn)
+            .with_condition(condition);
         self.rules.push(rule);
     }
 
@@ -138,33 +210,7 @@ impl MessageTransformer {
     pub fn transform(&self, source: &Message) -> Result<Message> {
         // Create target message as a clone of source
         let mut target = source.clone();
-
-        // Create terser for source (read-only)
-        let source_terser = Terser::new(source);
-
-        // Create mutable terser for target
-        let mut target_terser = TerserMut::new(&mut target);
-
-        // Apply each rule
-        for rule in &self.rules {
-            // Validate rule
-            rule.validate()?;
-
-            // Get source value
-            let source_value = source_terser
-                .get(&rule.source_path)
-                .map_err(|e| Error::field_access(format!("Failed to get {}: {}", rule.source_path, e)))?
-                .unwrap_or_default();
-
-            // Apply transformation
-            if let Some(transformed_value) = rule.apply(&source_value, &self.context)? {
-                // Set target value
-                target_terser
-                    .set(&rule.target_path, &transformed_value)
-                    .map_err(|e| Error::field_access(format!("Failed to set {}: {}", rule.target_path, e)))?;
-            }
-        }
-
+        self.apply_rules(source, &mut target)?;
         Ok(target)
     }
 
@@ -176,34 +222,121 @@ impl MessageTransformer {
     pub fn transform_in_place(&self, message: &mut Message) -> Result<()> {
         // Create a source clone for reading
         let source = message.clone();
-        let source_terser = Terser::new(&source);
-
-        // Create mutable terser for modifying the target
-        let mut target_terser = TerserMut::new(message);
+        self.apply_rules(&source, message)
+    }
 
+    /// Apply the scheduled rules, reading from `source` and writing to `target`
+    fn apply_rules(&self, source: &Message, target: &mut Message) -> Result<()> {
         // Apply each rule
-        for rule in &self.rules {
+        for rule in self.schedule()? {
             // Validate rule
             rule.validate()?;
 
-            // Get source value
-            let source_value = source_terser
-                .get(&rule.source_path)
-                .map_err(|e| Error::field_access(format!("Failed to get {}: {}", rule.source_path, e)))?
-                .unwrap_or_default();
+            // Skip rules whose condition the source does not meet
+            if !rule.applies_to(source) {
+                continue;
+            }
+
+            // Get source value; dependent rules see the output of earlier rules
+            let source_value = if rule.dependencies.is_empty() {
+                Self::read_source(rule, source)?
+            } else {
+                Self::read_source(rule, target)?
+            };
 
             // Apply transformation
             if let Some(transformed_value) = rule.apply(&source_value, &self.context)? {
-                // Set target value
-                target_terser
-                    .set(&rule.target_path, &transformed_value)
-                    .map_err(|e| Error::field_access(format!("Failed to set {}: {}", rule.target_path, e)))?;
+                // Set target values
+                let mut target_terser = TerserMut::new(target);
+                for (target_path, value) in rule.assignments(transformed_value)? {
+                    target_terser
+                        .set(target_path, &value)
+                        .map_err(|e| Error::field_access(format!("Failed to set {}: {}", target_path, e)))?;
+                }
             }
         }
 
         Ok(())
     }
 
+    /// Read the source value of a rule, joining the sources of a concat rule
+    ///
+    /// Missing values read as empty; a concat rule is empty only when all of
+    /// its sources are.
+    fn read_source(rule: &TransformationRule, message: &Message) -> Result<String> {
+        let terser = Terser::new(message);
+        let values = rule
+            .source_paths()
+            .into_iter()
+            .map(|path| {
+                terser
+                    .get(path)
+                    .map(Option::unwrap_or_default)
+                    .map_err(|e| Error::field_access(format!("Failed to get {}: {}", path, e)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        match &rule.kind {
+            RuleKind::Concat { separator, .. } if values.iter().any(|v| !v.is_empty()) => {
+                Ok(values.join(separator))
+            }
+            _ => Ok(values.concat()),
+        }
+    }
+
+    /// Order the rules so that every rule comes after its dependencies
+    ///
+    /// Kahn's algorithm, always taking the earliest added rule that is ready.
+    fn schedule(&self) -> Result<Vec<&TransformationRule>> {
+        let mut indices = HashMap::new();
+        for (i, rule) in self.rules.iter().enumerate() {
+            if let Some(name) = &rule.name
+                && indices.insert(name.as_str(), i).is_some()
+            {
+                return Err(Error::invalid_rule(format!("Duplicate rule name: {}", name)));
+            }
+        }
+
+        let mut dependents = vec![Vec::new(); self.rules.len()];
+        let mut pending = vec![0; self.rules.len()];
+        for (i, rule) in self.rules.iter().enumerate() {
+            for dependency in &rule.dependencies {
+                let &j = indices.get(dependency.as_str()).ok_or_else(|| {
+                    Error::invalid_rule(format!("Rule depends on unknown rule: {}", dependency))
+                })?;
+                dependents[j].push(i);
+                pending[i] += 1;
+            }
+        }
+
+        let mut ready: BTreeSet<usize> = (0..self.rules.len()).filter(|&i| pending[i] == 0).collect();
+        let mut order = Vec::with_capacity(self.rules.len());
+        while let Some(i) = ready.pop_first() {
+            order.push(&self.rules[i]);
+            for &dependent in &dependents[i] {
+                pending[dependent] -= 1;
+                if pending[dependent] == 0 {
+                    ready.insert(dependent);
+                }
+            }
+        }
+
+        if order.len() < self.rules.len() {
+            // Drop rules that are only blocked by the cycle, not part of it
+            let mut blocked: BTreeSet<usize> = (0..self.rules.len()).filter(|&i| pending[i] > 0).collect();
+            while let Some(&i) = blocked
+                .iter()
+                .find(|&&i| !dependents[i].iter().any(|d| blocked.contains(d)))
+            {
+                blocked.remove(&i);
+            }
+            let names = blocked.into_iter().filter_map(|i| self.rules[i].name.clone()).collect();
+            return Err(Error::DependencyCycle(names));
+        }
+
+        Ok(order)
+    }
+
     /// Get the number of transformation rules
     pub fn rule_count(&self) -> usize {
         self.rules.len()
@@ -214,11 +347,12 @@ impl MessageTransformer {
         self.rules.clear();
     }
 
-    /// Validate all rules
+    /// Validate all rules and their dependencies
     pub fn validate_rules(&self) -> Result<()> {
         for rule in &self.rules {
             rule.validate()?;
         }
+        self.schedule()?;
         Ok(())
     }
 }
@@ -318,6 +452,57 @@ mod tests {
         assert_eq!(terser.get("PID-3").unwrap(), Some("ABC123"));
     }
 
+    #[test]
+    fn test_transform_with_regex_replace() {
+        use rs7_parser::parse_message;
+
+        let msg = AdtBuilder::a01(Version::V2_5)
+            .patient_id("MRN-001-234")
+            .build()
+            .unwrap();
+        let source = parse_message(&msg.encode()).unwrap();
+
+        let mut transformer = MessageTransformer::new();
+        transformer.add_transform("PID-3", "PID-3", transforms::regex_replace(r"\D", "").unwrap());
+
+        let target = transformer.transform(&source).unwrap();
+
+        let terser = Terser::new(&target);
+        assert_eq!(terser.get("PID-3").unwrap(), Some("001234"));
+    }
+
+    #[test]
+    fn test_conditional_transform() {
+        use rs7_parser::parse_message;
+
+        let msg = AdtBuilder::a01(Version::V2_5)
+            .patient_id("abc123")
+            .sex("m")
+            .build()
+            .unwrap();
+        let source = parse_message(&msg.encode()).unwrap();
+
+        let is_message_type = |expected: &'static str| {
+            move |msg: &Message| Terser::new(msg).get("MSH-9-1").ok().flatten() == Some(expected)
+        };
+
+        let mut transformer = MessageTransformer::new();
+        transformer.add_conditional_transform("PID-3", "PID-3", transforms::uppercase, is_message_type("ADT"));
+        transformer.add_conditional_transform("PID-8", "PID-8", transforms::uppercase, is_message_type("ORU"));
+
+        let target = transformer.transform(&source).unwrap();
+
+        let terser = Terser::new(&target);
+        assert_eq!(terser.get("PID-3").unwrap(), Some("ABC123"));
+        assert_eq!(terser.get("PID-8").unwrap(), Some("m"));
+
+        let mut in_place = source.clone();
+        transformer.transform_in_place(&mut in_place).unwrap();
+        let terser = Terser::new(&in_place);
+        assert_eq!(terser.get("PID-3").unwrap(), Some("ABC123"));
+        assert_eq!(terser.get("PID-8").unwrap(), Some("m"));
+    }
+
     #[test]
     fn test_transform_multiple_rules() {
         use rs7_parser::parse_message;
@@ -394,4 +579,134 @@ mod tests {
 
         assert!(transformer.validate_rules().is_err());
     }
+
+    #[test]
+    fn test_rule_dependencies_order() {
+        use rs7_parser::parse_message;
+
+        let msg = AdtBuilder::a01(Version::V2_5)
+            .patient_id("abc123")
+            .build()
+            .unwrap();
+        let source = parse_message(&msg.encode()).unwrap();
+
+        // Added in reverse order; each rule reads the previous rule's output
+        let mut transformer = MessageTransformer::new();
+        transformer.add_rule(
+            TransformationRule::new("PID-19", "PID-19")
+                .with_transform(transforms::prefix)
+                .after("upper"),
+        );
+        transformer.add_rule(
+            TransformationRule::new("PID-19", "PID-19")
+                .with_name("upper")
+                .with_transform(transforms::uppercase)
+                .after("copy"),
+        );
+        transformer.add_rule(TransformationRule::new("PID-3", "PID-19").with_name("copy"));
+        transformer.set_context_data("prefix".to_string(), "SSN-".to_string());
+
+        let target = transformer.transform(&source).unwrap();
+
+        let terser = Terser::new(&target);
+        assert_eq!(terser.get("PID-19").unwrap(), Some("SSN-ABC123"));
+    }
+
+    #[test]
+    fn test_rules_without_dependencies_keep_order() {
+        let source = AdtBuilder::a01(Version::V2_5).build().unwrap();
+
+        // Both rules write the same field; the later one wins
+        let mut transformer = MessageTransformer::new();
+        transformer.add_rule(TransformationRule::new("PID-5-1", "PID-5-1").with_default("FIRST"));
+        transformer.add_rule(TransformationRule::new("PID-5-1", "PID-5-1").with_default("SECOND"));
+
+        let target = transformer.transform(&source).unwrap();
+
+        let terser = Terser::new(&target);
+        assert_eq!(terser.get("PID-5-1").unwrap(), Some("SECOND"));
+    }
+
+    #[test]
+    fn test_rule_dependency_cycle() {
+        let mut transformer = MessageTransformer::new();
+        transformer.add_rule(TransformationRule::new("PID-3", "PID-3").with_name("root"));
+        transformer.add_rule(TransformationRule::new("PID-5", "PID-5").with_name("a").after("b"));
+        transformer.add_rule(TransformationRule::new("PID-8", "PID-8").with_name("b").after("a"));
+        transformer.add_rule(TransformationRule::new("PID-7", "PID-7").with_name("c").after("a"));
+
+        let source = AdtBuilder::a01(Version::V2_5).build().unwrap();
+        match transformer.transform(&source) {
+            Err(Error::DependencyCycle(names)) => assert_eq!(names, vec!["a", "b"]),
+            other => panic!("Expected dependency cycle, got {:?}", other),
+        }
+        assert!(transformer.validate_rules().is_err());
+    }
+
+    #[test]
+    fn test_rule_unknown_dependency() {
+        let mut transformer = MessageTransformer::new();
+        transformer.add_rule(TransformationRule::new("PID-3", "PID-3").after("missing"));
+
+        assert!(matches!(transformer.validate_rules(), Err(Error::InvalidRule(_))));
+
+        let mut transformer = MessageTransformer::new();
+        transformer.add_rule(TransformationRule::new("PID-3", "PID-3").with_name("dup"));
+        transformer.add_rule(TransformationRule::new("PID-5", "PID-5").with_name("dup"));
+
+        assert!(matches!(transformer.validate_rules(), Err(Error::InvalidRule(_))));
+    }
+
+    #[test]
+    fn test_transform_concat() {
+        use rs7_parser::parse_message;
+
+        let source = parse_message(
+            "MSH|^~\\&|APP|FAC|APP|FAC|20240101||ADT^A01|1|P|2.5\rPID|1||123||SMITH^JOHN",
+        )
+        .unwrap();
+
+        let mut transformer = MessageTransformer::new();
+        transformer.add_rule(TransformationRule::concat(["PID-5-1", "PID-5-2"], ", ", "PID-9-1"));
+        // Missing sources read as empty
+        transformer.add_rule(TransformationRule::concat(["PID-5-2", "PID-5-3"], "/", "PID-9-2"));
+        transformer.add_rule(TransformationRule::concat(["PID-5-3", "PID-5-4"], "/", "PID-9-3"));
+
+        let target = transformer.transform(&source).unwrap();
+
+        let terser = Terser::new(&target);
+        assert_eq!(terser.get("PID-9-1").unwrap(), Some("SMITH, JOHN"));
+        assert_eq!(terser.get("PID-9-2").unwrap(), Some("JOHN/"));
+        // All sources empty, so the rule is skipped
+        assert_eq!(terser.get("PID-9-3").unwrap(), None);
+    }
+
+    #[test]
+    fn test_transform_split() {
+        use rs7_parser::parse_message;
+
+        let source = parse_message(
+            "MSH|^~\\&|APP|FAC|APP|FAC|20240101||ADT^A01|1|P|2.5\rPID|1||123||X||||||123 MAIN ST",
+        )
+        .unwrap();
+
+        let mut transformer = MessageTransformer::new();
+        transformer.add_rule(TransformationRule::split("PID-11-1", " ", ["PID-11-2", "PID-11-3"]));
+        assert!(transformer.transform(&source).is_err());
+
+        transformer.clear_rules();
+        transformer.add_rule(TransformationRule::split("PID-11-1", " ", ["PID-11-2", "PID-11-3"]).truncate(true));
+        transformer.add_rule(
+            TransformationRule::split("PID-5-1", " ", ["PID-5-1", "PID-5-2", "PID-5-3"])
+                .with_transform(transforms::lowercase),
+        );
+
+        let target = transformer.transform(&source).unwrap();
+
+        let terser = Terser::new(&target);
+        assert_eq!(terser.get("PID-11-2").unwrap(), Some("123"));
+        assert_eq!(terser.get("PID-11-3").unwrap(), Some("MAIN"));
+        assert_eq!(terser.get("PID-5-1").unwrap(), Some("x"));
+        assert_eq!(terser.get("PID-5-2").unwrap(), None);
+    }
 }
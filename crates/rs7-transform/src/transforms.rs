@@ -6,6 +6,7 @@
 use crate::error::{Error, Result};
 use crate::rule::TransformContext;
 use chrono::NaiveDateTime;
+use rs7_core::types::Timestamp;
 
 /// Convert value to uppercase
 ///
@@ -205,6 +206,70 @@ pub fn format_datetime(value: &str, ctx: &TransformContext) -> Result<String> {
     Ok(result)
 }
 
+/// Convert an HL7 date/time (DT, TS, DTM) to ISO 8601
+///
+/// The output keeps the precision of the input: `2024` stays a year,
+/// `20240315` becomes `2024-03-15` and `20240315143000` becomes
+/// `2024-03-15T14:30:00`. Fractional seconds and a `+/-ZZZZ` offset are
+/// carried over as `.SSSS` and `+/-ZZ:ZZ`.
+///
+/// Invalid input is returned unchanged. Set the context key "on_invalid" to
+/// "empty" to get an empty string instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use rs7_transform::{transforms, rule::TransformContext};
+///
+/// let ctx = TransformContext::new();
+/// assert_eq!(transforms::hl7_to_iso8601("20240315143000", &ctx).unwrap(), "2024-03-15T14:30:00");
+/// assert_eq!(transforms::hl7_to_iso8601("20240315", &ctx).unwrap(), "2024-03-15");
+/// assert_eq!(transforms::hl7_to_iso8601("202403151430-0500", &ctx).unwrap(), "2024-03-15T14:30-05:00");
+/// ```
+pub fn hl7_to_iso8601(value: &str, ctx: &TransformContext) -> Result<String> {
+    match Timestamp::parse(value) {
+        Some(ts) => Ok(ts.to_iso8601()),
+        None => invalid_datetime(value, ctx),
+    }
+}
+
+/// Convert an ISO 8601 date/time to HL7 (DT, TS, DTM)
+///
+/// The inverse of [`hl7_to_iso8601`]: `2024-03-15T14:30:00` becomes
+/// `20240315143000` and `2024-03-15` becomes `20240315`. A `Z` suffix is
+/// written as `+0000`.
+///
+/// Invalid input is returned unchanged. Set the context key "on_invalid" to
+/// "empty" to get an empty string instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use rs7_transform::{transforms, rule::TransformContext};
+///
+/// let ctx = TransformContext::new();
+/// assert_eq!(transforms::iso8601_to_hl7("2024-03-15T14:30:00", &ctx).unwrap(), "20240315143000");
+/// assert_eq!(transforms::iso8601_to_hl7("2024-03", &ctx).unwrap(), "202403");
+///
+/// let ctx = TransformContext::new().add_data("on_invalid".to_string(), "empty".to_string());
+/// assert_eq!(transforms::iso8601_to_hl7("not a date", &ctx).unwrap(), "");
+/// ```
+pub fn iso8601_to_hl7(value: &str, ctx: &TransformContext) -> Result<String> {
+    match Timestamp::parse_iso8601(value) {
+        Some(ts) => Ok(ts.to_hl7()),
+        None => invalid_datetime(value, ctx),
+    }
+}
+
+/// Handle a value that is not a valid date/time according to "on_invalid"
+fn invalid_datetime(value: &str, ctx: &TransformContext) -> Result<String> {
+    match ctx.get_data("on_invalid").map(String::as_str) {
+        None | Some("passthrough") => Ok(value.to_string()),
+        Some("empty") => Ok(String::new()),
+        Some(other) => Err(Error::transform_fn(format!("Invalid on_invalid value: {}", other))),
+    }
+}
+
 /// Replace all occurrences of a pattern with a replacement string
 ///
 /// Note: This function expects the context to contain "pattern" and "replacement" keys.
@@ -232,9 +297,11 @@ pub fn replace(value: &str, ctx: &TransformContext) -> Result<String> {
     Ok(value.replace(pattern, replacement))
 }
 
-/// Replace using a regular expression
+/// Replace using a regular expression read from the context
 ///
 /// Note: This function expects the context to contain "regex" and "replacement" keys.
+/// The pattern is compiled on every call; prefer [`regex_replace`] when the
+/// pattern is known up front.
 ///
 /// # Examples
 ///
@@ -245,10 +312,10 @@ pub fn replace(value: &str, ctx: &TransformContext) -> Result<String> {
 ///     .add_data("regex".to_string(), r"\d+".to_string())
 ///     .add_data("replacement".to_string(), "XXX".to_string());
 ///
-/// let result = transforms::regex_replace("ID-12345", &ctx).unwrap();
+/// let result = transforms::regex_replace_from_context("ID-12345", &ctx).unwrap();
 /// assert_eq!(result, "ID-XXX");
 /// ```
-pub fn regex_replace(value: &str, ctx: &TransformContext) -> Result<String> {
+pub fn regex_replace_from_context(value: &str, ctx: &TransformContext) -> Result<String> {
     let pattern = ctx
         .get_data("regex")
         .ok_or_else(|| Error::transform_fn("regex_replace requires 'regex' in context"))?;
@@ -262,6 +329,75 @@ pub fn regex_replace(value: &str, ctx: &TransformContext) -> Result<String> {
     Ok(re.replace_all(value, replacement.as_str()).to_string())
 }
 
+/// Build a transform that replaces every match of `pattern` with a literal string
+///
+/// The pattern is compiled once, here, so an invalid pattern is reported when
+/// the transform is built rather than when a message is transformed. Compiling
+/// a regex is far more expensive than running it: build the transform once and
+/// reuse it (it is `Clone`, `Send` and `Sync`) instead of rebuilding it per message.
+///
+/// `$` in `replacement` is taken literally; use [`regex_replace_captures`] to
+/// refer to capture groups.
+///
+/// # Examples
+///
+/// ```rust
+/// use rs7_transform::{transforms, rule::TransformContext};
+///
+/// // Strip everything but digits from a phone number
+/// let digits_only = transforms::regex_replace(r"\D", "").unwrap();
+/// let ctx = TransformContext::new();
+/// assert_eq!(digits_only("(555) 123-4567", &ctx).unwrap(), "5551234567");
+///
+/// assert!(transforms::regex_replace("(", "").is_err());
+/// ```
+pub fn regex_replace(
+    pattern: &str,
+    replacement: &str,
+) -> Result<impl Fn(&str, &TransformContext) -> Result<String> + Clone + Send + Sync + 'static> {
+    let re = compile_regex(pattern)?;
+    let replacement = replacement.to_string();
+
+    Ok(move |value: &str, _ctx: &TransformContext| {
+        Ok(re.replace_all(value, regex::NoExpand(&replacement)).into_owned())
+    })
+}
+
+/// Build a transform that replaces every match of `pattern`, expanding capture groups
+///
+/// `replacement` may refer to capture groups by index (`$1`, `${1}`) or by
+/// name (`$name`, `${name}`); `$$` produces a literal `$`. As with
+/// [`regex_replace`], the pattern is compiled once when the transform is built.
+///
+/// # Examples
+///
+/// ```rust
+/// use rs7_transform::{transforms, rule::TransformContext};
+///
+/// let format_phone = transforms::regex_replace_captures(
+///     r"^(\d{3})(\d{3})(\d{4})$",
+///     "($1) $2-$3",
+/// ).unwrap();
+/// let ctx = TransformContext::new();
+/// assert_eq!(format_phone("5551234567", &ctx).unwrap(), "(555) 123-4567");
+/// ```
+pub fn regex_replace_captures(
+    pattern: &str,
+    replacement: &str,
+) -> Result<impl Fn(&str, &TransformContext) -> Result<String> + Clone + Send + Sync + 'static> {
+    let re = compile_regex(pattern)?;
+    let replacement = replacement.to_string();
+
+    Ok(move |value: &str, _ctx: &TransformContext| {
+        Ok(re.replace_all(value, replacement.as_str()).into_owned())
+    })
+}
+
+fn compile_regex(pattern: &str) -> Result<regex::Regex> {
+    regex::Regex::new(pattern)
+        .map_err(|e| Error::transform_fn(format!("Invalid regex '{}': {}", pattern, e)))
+}
+
 /// Concatenate with a prefix
 ///
 /// Note: This function expects the context to contain a "prefix" key.
@@ -472,6 +608,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hl7_to_iso8601_precision() {
+        let ctx = TransformContext::new();
+
+        assert_eq!(hl7_to_iso8601("2024", &ctx).unwrap(), "2024");
+        assert_eq!(hl7_to_iso8601("202403", &ctx).unwrap(), "2024-03");
+        assert_eq!(hl7_to_iso8601("20240315", &ctx).unwrap(), "2024-03-15");
+        assert_eq!(hl7_to_iso8601("2024031514", &ctx).unwrap(), "2024-03-15T14");
+        assert_eq!(hl7_to_iso8601("202403151430", &ctx).unwrap(), "2024-03-15T14:30");
+        assert_eq!(hl7_to_iso8601("20240315143000", &ctx).unwrap(), "2024-03-15T14:30:00");
+        assert_eq!(hl7_to_iso8601("20240315143000.123", &ctx).unwrap(), "2024-03-15T14:30:00.123");
+        assert_eq!(hl7_to_iso8601("20240315143000+0100", &ctx).unwrap(), "2024-03-15T14:30:00+01:00");
+    }
+
+    #[test]
+    fn test_iso8601_to_hl7_precision() {
+        let ctx = TransformContext::new();
+
+        assert_eq!(iso8601_to_hl7("2024", &ctx).unwrap(), "2024");
+        assert_eq!(iso8601_to_hl7("2024-03-15", &ctx).unwrap(), "20240315");
+        assert_eq!(iso8601_to_hl7("2024-03-15T14:30", &ctx).unwrap(), "202403151430");
+        assert_eq!(iso8601_to_hl7("2024-03-15T14:30:00", &ctx).unwrap(), "20240315143000");
+        assert_eq!(iso8601_to_hl7("2024-03-15T14:30:00.5Z", &ctx).unwrap(), "20240315143000.5+0000");
+        assert_eq!(iso8601_to_hl7("2024-03-15T14:30:00-05:00", &ctx).unwrap(), "20240315143000-0500");
+    }
+
+    #[test]
+    fn test_datetime_round_trip() {
+        let ctx = TransformContext::new();
+
+        for hl7 in ["2024", "202403", "20240315", "202403151430", "20240315143000.1234-0330"] {
+            let iso = hl7_to_iso8601(hl7, &ctx).unwrap();
+            assert_eq!(iso8601_to_hl7(&iso, &ctx).unwrap(), hl7);
+        }
+    }
+
+    #[test]
+    fn test_datetime_invalid_handling() {
+        let ctx = TransformContext::new();
+        assert_eq!(hl7_to_iso8601("20241345", &ctx).unwrap(), "20241345");
+        assert_eq!(hl7_to_iso8601("2024031", &ctx).unwrap(), "2024031");
+        assert_eq!(iso8601_to_hl7("2024-02-30", &ctx).unwrap(), "2024-02-30");
+        assert_eq!(iso8601_to_hl7("2024-03-15T25:00", &ctx).unwrap(), "2024-03-15T25:00");
+
+        let ctx = TransformContext::new()
+            .add_data("on_invalid".to_string(), "empty".to_string());
+        assert_eq!(hl7_to_iso8601("garbage", &ctx).unwrap(), "");
+        assert_eq!(iso8601_to_hl7("03/15/2024", &ctx).unwrap(), "");
+
+        let ctx = TransformContext::new()
+            .add_data("on_invalid".to_string(), "fail".to_string());
+        assert!(hl7_to_iso8601("garbage", &ctx).is_err());
+    }
+
     #[test]
     fn test_replace() {
         let ctx = TransformContext::new()
@@ -487,7 +677,35 @@ mod tests {
             .add_data("regex".to_string(), r"\d+".to_string())
             .add_data("replacement".to_string(), "XXX".to_string());
 
-        assert_eq!(regex_replace("ID-12345", &ctx).unwrap(), "ID-XXX");
+        assert_eq!(regex_replace_from_context("ID-12345", &ctx).unwrap(), "ID-XXX");
+    }
+
+    #[test]
+    fn test_regex_replace_compiled() {
+        let ctx = TransformContext::new();
+        let strip = regex_replace(r"[^A-Z0-9]", "").unwrap();
+
+        assert_eq!(strip("MRN-00 123", &ctx).unwrap(), "MRN00123");
+        assert_eq!(strip("A.B.C", &ctx).unwrap(), "ABC");
+
+        // Replacement is literal, capture references are not expanded
+        let literal = regex_replace(r"(\d+)", "$1").unwrap();
+        assert_eq!(literal("ID-42", &ctx).unwrap(), "ID-$1");
+    }
+
+    #[test]
+    fn test_regex_replace_captures() {
+        let ctx = TransformContext::new();
+        let swap = regex_replace_captures(r"^(?P<family>\w+),\s*(?P<given>\w+)$", "${given} ${family}").unwrap();
+
+        assert_eq!(swap("DOE, JOHN", &ctx).unwrap(), "JOHN DOE");
+        assert_eq!(swap("unchanged", &ctx).unwrap(), "unchanged");
+    }
+
+    #[test]
+    fn test_regex_replace_invalid_pattern() {
+        assert!(matches!(regex_replace("[", ""), Err(Error::TransformFn(_))));
+        assert!(matches!(regex_replace_captures("(", ""), Err(Error::TransformFn(_))));
     }
 
     #[test]
@@ -0,0 +1,262 @@
+//! JSON Schema export of message schemas
+//!
+//! [`MessageSchema::to_json_schema`] describes a message schema as a JSON
+//! Schema (draft 2020-12) so web tooling can check user input against the
+//! same definitions as the [`Validator`](crate::Validator) before a message
+//! is built. It is not a schema for ER7 text.
+//!
+//! # Mapping
+//!
+//! - The message is an object with one property per segment ID. Required
+//!   segments are listed in `required`; repeating segments are arrays of
+//!   segment objects (with `minItems: 1` when required).
+//! - A segment is an object whose properties are field numbers as strings
+//!   (`"1"`, `"2"`, ...), with required fields listed in `required`.
+//! - A field is a string, or an object keyed by component number when the
+//!   schema defines components. Repeating fields are arrays of these.
+//! - `maxLength` comes from the field's `max_length`. `NM`, `SI`, `DT`, `TM`,
+//!   `DTM` and `TS` values get a `pattern` for their format.
+//! - When a field or component references a table known to the
+//!   [`TableRegistry`], its non-deprecated codes become an `enum`, except
+//!   for suggest-only tables, which the validator does not enforce.
+//! - Segment, field and component schemas carry the definition's name as
+//!   `title`. Fields and components also carry `x-hl7-data-type`, and
+//!   `x-hl7-table` when they reference a table.
+//!
+//! Absent values should be omitted rather than sent as empty strings.
+//! Segments, fields, `required` lists and `enum` values are emitted in sorted
+//! order, so the output is deterministic.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rs7_core::Version;
+//! use rs7_validator::load_schema;
+//!
+//! let schema = load_schema(Version::V2_5, "ADT", "A01").unwrap();
+//! let json_schema = schema.to_json_schema();
+//! assert_eq!(json_schema["properties"]["PID"]["type"], "object");
+//! ```
+
+use crate::{ComponentDefinition, FieldDefinition, MessageSchema, SegmentDefinition, TableRegistry};
+use serde_json::{json, Map, Value};
+
+const NUMERIC_PATTERN: &str = r"^[+-]?(\d+(\.\d*)?|\.\d+)$";
+const SEQUENCE_ID_PATTERN: &str = r"^\d+$";
+const DATE_PATTERN: &str = r"^\d{4}(\d{2}(\d{2})?)?$";
+const TIME_PATTERN: &str = r"^\d{2}(\d{2}(\d{2}(\.\d{1,4})?)?)?([+-]\d{4})?$";
+const TIMESTAMP_PATTERN: &str = r"^\d{4}(\d{2}(\d{2}(\d{2}(\d{2}(\d{2}(\.\d{1,4})?)?)?)?)?)?([+-]\d{4})?$";
+
+impl MessageSchema {
+    /// Export this schema as a JSON Schema, using the standard HL7 tables
+    ///
+    /// See the [module documentation](crate::json_schema) for the mapping.
+    pub fn to_json_schema(&self) -> Value {
+        self.to_json_schema_with_tables(&TableRegistry::new())
+    }
+
+    /// Export this schema as a JSON Schema, taking table values from `tables`
+    pub fn to_json_schema_with_tables(&self, tables: &TableRegistry) -> Value {
+        let mut ids: Vec<&String> = self.segments.keys().collect();
+        ids.sort();
+
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for id in ids {
+            let definition = &self.segments[id];
+            properties.insert(id.clone(), segment_schema(definition, tables));
+            if definition.required {
+                required.push(Value::from(id.as_str()));
+            }
+        }
+
+        let title = if self.trigger_event.is_empty() {
+            self.message_type.clone()
+        } else {
+            format!("{}^{}", self.message_type, self.trigger_event)
+        };
+
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": format!("{} (HL7 v{})", title, self.version),
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        })
+    }
+}
+
+fn segment_schema(definition: &SegmentDefinition, tables: &TableRegistry) -> Value {
+    let mut numbers: Vec<&usize> = definition.fields.keys().collect();
+    numbers.sort();
+
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for number in numbers {
+        let field = &definition.fields[number];
+        properties.insert(number.to_string(), field_schema(field, tables));
+        if field.required {
+            required.push(Value::from(number.to_string()));
+        }
+    }
+
+    let segment = json!({
+        "title": definition.name,
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    });
+    if definition.repeating {
+        repeated(segment, definition.required)
+    } else {
+        segment
+    }
+}
+
+fn field_schema(field: &FieldDefinition, tables: &TableRegistry) -> Value {
+    let value = match &field.components {
+        Some(components) => {
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+            for (i, component) in components.iter().enumerate() {
+                properties.insert((i + 1).to_string(), component_schema(component, tables));
+                if component.required {
+                    required.push(Value::from((i + 1).to_string()));
+                }
+            }
+            json!({
+                "title": field.name,
+                "type": "object",
+                "x-hl7-data-type": field.data_type,
+                "properties": properties,
+                "required": required,
+            })
+        }
+        None => {
+            let mut schema = value_schema(&field.name, &field.data_type, field.table_id.as_deref(), tables);
+            if let Some(max_length) = field.max_length {
+                schema["maxLength"] = Value::from(max_length);
+            }
+            schema
+        }
+    };
+
+    if field.repeating {
+        repeated(value, field.required)
+    } else {
+        value
+    }
+}
+
+fn component_schema(component: &ComponentDefinition, tables: &TableRegistry) -> Value {
+    value_schema(&component.name, &component.data_type, component.table_id.as_deref(), tables)
+}
+
+/// Schema for a single string value of the given data type and table
+fn value_schema(name: &str, data_type: &str, table_id: Option<&str>, tables: &TableRegistry) -> Value {
+    let mut schema = json!({
+        "title": name,
+        "type": "string",
+        "x-hl7-data-type": data_type,
+    });
+
+    let pattern = match data_type {
+        "NM" => Some(NUMERIC_PATTERN),
+        "SI" => Some(SEQUENCE_ID_PATTERN),
+        "DT" => Some(DATE_PATTERN),
+        "TM" => Some(TIME_PATTERN),
+        "DTM" | "TS" => Some(TIMESTAMP_PATTERN),
+        _ => None,
+    };
+    if let Some(pattern) = pattern {
+        schema["pattern"] = Value::from(pattern);
+    }
+
+    if let Some(table_id) = table_id {
+        schema["x-hl7-table"] = Value::from(table_id);
+        if let Some(table) = tables.get_table(table_id).filter(|t| !t.suggest_only) {
+            let mut codes: Vec<&str> = table
+                .values
+                .values()
+                .filter(|v| !v.deprecated)
+                .map(|v| v.code.as_str())
+                .collect();
+            codes.sort();
+            schema["enum"] = Value::from(codes);
+        }
+    }
+
+    schema
+}
+
+/// Wrap `items` in an array schema
+fn repeated(items: Value, required: bool) -> Value {
+    let mut schema = json!({
+        "type": "array",
+        "items": items,
+    });
+    if required {
+        schema["minItems"] = Value::from(1);
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_schema;
+    use rs7_core::Version;
+
+    #[test]
+    fn test_to_json_schema() {
+        let schema = load_schema(Version::V2_5, "BAR", "P01").unwrap();
+        let json_schema = schema.to_json_schema();
+
+        assert_eq!(json_schema["title"], "BAR^P01 (HL7 v2.5)");
+        let required = json_schema["required"].as_array().unwrap();
+        assert!(required.contains(&Value::from("MSH")));
+        assert!(required.contains(&Value::from("PID")));
+
+        let sex = &json_schema["properties"]["PID"]["properties"]["8"];
+        assert_eq!(sex["type"], "string");
+        assert_eq!(sex["x-hl7-table"], "0001");
+        assert!(sex["enum"].as_array().unwrap().contains(&Value::from("F")));
+
+        // Deterministic output
+        assert_eq!(json_schema, load_schema(Version::V2_5, "BAR", "P01").unwrap().to_json_schema());
+        assert_eq!(json_schema.to_string(), schema.to_json_schema().to_string());
+    }
+
+    #[test]
+    fn test_json_schema_repetition_and_components() {
+        let json = r#"{
+            "message_type": "ZMI", "trigger_event": "Z01", "version": "2.5",
+            "segments": {
+                "ZIN": {"name": "Item", "required": true, "repeating": true, "fields": {
+                    "1": {"name": "Count", "data_type": "NM", "required": true, "repeating": false, "max_length": 5},
+                    "2": {"name": "Code", "data_type": "CWE", "required": false, "repeating": true, "max_length": null,
+                          "components": [
+                              {"name": "Identifier", "data_type": "ST", "required": true},
+                              {"name": "Text", "data_type": "ST", "required": false}
+                          ]}
+                }}
+            }
+        }"#;
+        let schema: MessageSchema = serde_json::from_str(json).unwrap();
+        let json_schema = schema.to_json_schema();
+
+        let zin = &json_schema["properties"]["ZIN"];
+        assert_eq!(zin["type"], "array");
+        assert_eq!(zin["minItems"], 1);
+        let fields = &zin["items"]["properties"];
+        assert_eq!(fields["1"]["maxLength"], 5);
+        assert_eq!(fields["1"]["pattern"], NUMERIC_PATTERN);
+        assert_eq!(zin["items"]["required"], json!(["1"]));
+
+        let code = &fields["2"];
+        assert_eq!(code["type"], "array");
+        assert!(code.get("minItems").is_none());
+        assert_eq!(code["items"]["properties"]["1"]["title"], "Identifier");
+        assert_eq!(code["items"]["required"], json!(["1"]));
+    }
+}
@@ -34,12 +34,16 @@
 //! - And many more standard HL7 tables
 
 pub mod datatype;
+pub mod json_schema;
+pub mod policy;
 pub mod rules;
 pub mod schema_loader;
+pub mod schema_registry;
 pub mod vocabulary;
 
 use rs7_core::{
     error::Result,
+    field::Repetition,
     message::Message,
     segment::Segment,
     types::DataType,
@@ -49,8 +53,10 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 pub use datatype::{validate_data_type, DataTypeValidation};
+pub use policy::{ValidationPolicy, ValidationSeverity};
 pub use rules::{BuiltinRules, CrossFieldValidator, RulesEngine, RulesValidationResult, RuleSeverity, RuleViolation, ValidationRule, RuleConfig, RuleDefinition, ConditionConfig, DeclarativeError};
 pub use schema_loader::{load_schema, list_available_schemas};
+pub use schema_registry::SchemaRegistry;
 pub use vocabulary::{TableRegistry, Hl7Table, VocabularyValidation};
 
 /// Validation result
@@ -113,7 +119,7 @@ impl ValidationError {
 }
 
 /// Type of validation error
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ValidationErrorType {
     MissingRequiredField,
     InvalidDataType,
@@ -121,6 +127,14 @@ pub enum ValidationErrorType {
     InvalidCardinality,
     InvalidValue,
     StructuralError,
+    /// MSH-12 is empty or not a supported HL7 version
+    UnsupportedVersion,
+    /// MSH-12 names a different version than the validator's
+    VersionMismatch,
+    /// A business rule from the rules engine was violated
+    BusinessRule,
+    /// A value is not in a "suggest only" vocabulary table
+    TableSuggestion,
 }
 
 /// Validation warning
@@ -128,11 +142,26 @@ pub enum ValidationErrorType {
 pub struct ValidationWarning {
     pub location: String,
     pub message: String,
+    /// Kind of finding, if known
+    pub error_type: Option<ValidationErrorType>,
 }
 
 impl ValidationWarning {
     pub fn new(location: String, message: String) -> Self {
-        Self { location, message }
+        Self {
+            location,
+            message,
+            error_type: None,
+        }
+    }
+
+    /// Create a warning for a finding of a known type
+    pub fn with_type(location: String, message: String, error_type: ValidationErrorType) -> Self {
+        Self {
+            location,
+            message,
+            error_type: Some(error_type),
+        }
     }
 }
 
@@ -140,8 +169,10 @@ impl ValidationWarning {
 pub struct Validator {
     version: Version,
     schema: Option<MessageSchema>,
+    schema_registry: Option<SchemaRegistry>,
     table_registry: TableRegistry,
     rules_engine: Option<RulesEngine>,
+    policy: ValidationPolicy,
 }
 
 impl Validator {
@@ -150,8 +181,10 @@ impl Validator {
         Self {
             version,
             schema: None,
+            schema_registry: None,
             table_registry: TableRegistry::new(),
             rules_engine: None,
+            policy: ValidationPolicy::default(),
         }
     }
 
@@ -160,8 +193,10 @@ impl Validator {
         Self {
             version,
             schema: Some(schema),
+            schema_registry: None,
             table_registry: TableRegistry::new(),
             rules_engine: None,
+            policy: ValidationPolicy::default(),
         }
     }
 
@@ -171,11 +206,47 @@ impl Validator {
         Ok(Self {
             version,
             schema: Some(schema),
+            schema_registry: None,
             table_registry: TableRegistry::new(),
             rules_engine: None,
+            policy: ValidationPolicy::default(),
         })
     }
 
+    /// Create a validator that classifies findings using `policy`
+    pub fn with_policy(version: Version, policy: ValidationPolicy) -> Self {
+        Self {
+            policy,
+            ..Self::new(version)
+        }
+    }
+
+    /// Resolve each message's schema from `registry`
+    ///
+    /// Used when the validator has no fixed schema: the schema is looked up
+    /// by the validator version and the message's MSH-9 type and trigger
+    /// event, falling back to the embedded schemas. Messages with no matching
+    /// schema only get structural validation.
+    pub fn with_schema_registry(mut self, registry: SchemaRegistry) -> Self {
+        self.schema_registry = Some(registry);
+        self
+    }
+
+    /// Get the schema registry, if any
+    pub fn schema_registry(&self) -> Option<&SchemaRegistry> {
+        self.schema_registry.as_ref()
+    }
+
+    /// Get the severity policy
+    pub fn policy(&self) -> &ValidationPolicy {
+        &self.policy
+    }
+
+    /// Replace the severity policy
+    pub fn set_policy(&mut self, policy: ValidationPolicy) {
+        self.policy = policy;
+    }
+
     /// Get a reference to the table registry
     pub fn table_registry(&self) -> &TableRegistry {
         &self.table_registry
@@ -234,6 +305,8 @@ impl Validator {
         // Schema-based validation (if schema is available)
         if let Some(schema) = &self.schema {
             self.validate_against_schema(message, schema, &mut result);
+        } else if let Some(schema) = self.registry_schema(message) {
+            self.validate_against_schema(message, schema, &mut result);
         }
 
         // Business rules validation (if rules engine is available)
@@ -244,11 +317,47 @@ impl Validator {
         result
     }
 
+    /// Look up the message's schema in the schema registry
+    fn registry_schema(&self, message: &Message) -> Option<&MessageSchema> {
+        let registry = self.schema_registry.as_ref()?;
+        let message_type = message.get_msh()?.get_field(9)?.get_repetition(0)?;
+        let type_code = message_type.get_component(0)?.value()?;
+        let trigger_event = message_type
+            .get_component(1)
+            .and_then(|c| c.value())
+            .unwrap_or("");
+        registry.resolve(self.version, type_code, trigger_event)
+    }
+
+    /// Record an error, or a warning if the policy downgrades its type
+    fn add_error(&self, result: &mut ValidationResult, error: ValidationError) {
+        match self.policy.severity(Some(error.error_type), ValidationSeverity::Error) {
+            ValidationSeverity::Error => result.add_error(error),
+            ValidationSeverity::Warning => result.add_warning(ValidationWarning::with_type(
+                error.location,
+                error.message,
+                error.error_type,
+            )),
+        }
+    }
+
+    /// Record a warning, or an error if the policy upgrades it
+    fn add_warning(&self, result: &mut ValidationResult, warning: ValidationWarning) {
+        match self.policy.severity(warning.error_type, ValidationSeverity::Warning) {
+            ValidationSeverity::Warning => result.add_warning(warning),
+            ValidationSeverity::Error => result.add_error(ValidationError::new(
+                warning.location,
+                warning.message,
+                warning.error_type.unwrap_or(ValidationErrorType::InvalidValue),
+            )),
+        }
+    }
+
     /// Validate basic message structure
     fn validate_structure(&self, message: &Message, result: &mut ValidationResult) {
         // Must have at least one segment (MSH)
         if message.segments.is_empty() {
-            result.add_error(ValidationError::new(
+            self.add_error(result, ValidationError::new(
                 "Message".to_string(),
                 "Message must contain at least one segment".to_string(),
                 ValidationErrorType::StructuralError,
@@ -258,7 +367,7 @@ impl Validator {
 
         // First segment must be MSH
         if message.segments[0].id != "MSH" {
-            result.add_error(ValidationError::new(
+            self.add_error(result, ValidationError::new(
                 "Segment[0]".to_string(),
                 "First segment must be MSH".to_string(),
                 ValidationErrorType::StructuralError,
@@ -268,7 +377,7 @@ impl Validator {
         // Validate segment IDs
         for (i, segment) in message.segments.iter().enumerate() {
             if let Err(e) = segment.validate_id() {
-                result.add_error(ValidationError::new(
+                self.add_error(result, ValidationError::new(
                     format!("Segment[{}]", i),
                     e.to_string(),
                     ValidationErrorType::StructuralError,
@@ -276,18 +385,35 @@ impl Validator {
             }
         }
 
-        // Check version matches
-        if let Some(msg_version) = message.get_version()
-            && msg_version != self.version {
-                result.add_warning(ValidationWarning::new(
+        // Check version is present, supported and matches
+        match message.get_version() {
+            Some(msg_version) if msg_version != self.version => {
+                self.add_warning(result, ValidationWarning::with_type(
                     "MSH-12".to_string(),
                     format!(
                         "Message version ({}) differs from validator version ({})",
                         msg_version.as_str(),
                         self.version.as_str()
                     ),
+                    ValidationErrorType::VersionMismatch,
+                ));
+            }
+            Some(_) => {}
+            None if message.segments[0].id == "MSH" => {
+                let text = match message.segments[0].get_field_value(12) {
+                    Some(value) if !value.is_empty() => {
+                        format!("Message version ({}) is not a supported HL7 version", value)
+                    }
+                    _ => "Message version (MSH-12) is missing".to_string(),
+                };
+                self.add_warning(result, ValidationWarning::with_type(
+                    "MSH-12".to_string(),
+                    text,
+                    ValidationErrorType::UnsupportedVersion,
                 ));
             }
+            None => {}
+        }
     }
 
     /// Validate against schema
@@ -309,7 +435,7 @@ impl Validator {
             if seg_def.required {
                 let found = message.segments.iter().any(|s| &s.id == seg_id);
                 if !found {
-                    result.add_error(ValidationError::new(
+                    self.add_error(result, ValidationError::new(
                         "Message".to_string(),
                         format!("Required segment {} is missing", seg_id),
                         ValidationErrorType::MissingRequiredField,
@@ -335,7 +461,7 @@ impl Validator {
             let field = segment.get_field(*field_idx);
 
             if field_def.required && (field.is_none() || field.unwrap().is_empty()) {
-                result.add_error(ValidationError::new(
+                self.add_error(result, ValidationError::new(
                     format!("{}-{}", location_prefix, field_idx),
                     format!("Required field {} is missing or empty", field_idx),
                     ValidationErrorType::MissingRequiredField,
@@ -359,7 +485,7 @@ impl Validator {
                     };
 
                     if field_length > max_len {
-                        result.add_error(ValidationError::new(
+                        self.add_error(result, ValidationError::new(
                             field_location.clone(),
                             format!(
                                 "Field exceeds maximum length ({} > {})",
@@ -376,7 +502,7 @@ impl Validator {
                     if let Some(data_type) = DataType::from_str(&field_def.data_type) {
                         let validation = datatype::validate_data_type(value, data_type);
                         if !validation.is_valid() {
-                            result.add_error(ValidationError::new(
+                            self.add_error(result, ValidationError::new(
                                 field_location.clone(),
                                 format!(
                                     "Invalid {} format: {}",
@@ -390,21 +516,103 @@ impl Validator {
 
                     // Validate vocabulary/code set
                     if let Some(table_id) = &field_def.table_id {
-                        let vocab_validation = self.table_registry.validate(table_id, value);
-                        if !vocab_validation.is_valid()
-                            && let Some(err_msg) = vocab_validation.error_message() {
-                                result.add_error(ValidationError::new(
-                                    field_location.clone(),
-                                    err_msg.to_string(),
-                                    ValidationErrorType::InvalidValue,
-                                ));
-                            }
+                        self.validate_table_value(table_id, value, &field_location, result);
+                    }
+                }
+
+                // Validate individual components of each non-empty repetition
+                if let Some(components) = &field_def.components {
+                    for (rep_idx, repetition) in f.repetitions.iter().enumerate() {
+                        if repetition.is_empty() {
+                            continue;
+                        }
+                        let rep_location = if rep_idx == 0 {
+                            field_location.clone()
+                        } else {
+                            format!("{}({})", field_location, rep_idx)
+                        };
+                        self.validate_components(repetition, components, &rep_location, result);
                     }
                 }
             }
         }
     }
 
+    /// Validate the components of a field repetition
+    fn validate_components(
+        &self,
+        repetition: &Repetition,
+        definitions: &[ComponentDefinition],
+        location_prefix: &str,
+        result: &mut ValidationResult,
+    ) {
+        for (comp_idx, comp_def) in definitions.iter().enumerate() {
+            let location = format!("{}-{}", location_prefix, comp_idx + 1);
+            let value = repetition
+                .get_component(comp_idx)
+                .and_then(|c| c.value())
+                .unwrap_or("");
+
+            if value.is_empty() {
+                if comp_def.required {
+                    self.add_error(result, ValidationError::new(
+                        location,
+                        format!("Required component {} is missing or empty", comp_idx + 1),
+                        ValidationErrorType::MissingRequiredField,
+                    ));
+                }
+                continue;
+            }
+
+            if let Some(data_type) = DataType::from_str(&comp_def.data_type) {
+                let validation = datatype::validate_data_type(value, data_type);
+                if !validation.is_valid() {
+                    self.add_error(result, ValidationError::new(
+                        location.clone(),
+                        format!(
+                            "Invalid {} format: {}",
+                            comp_def.data_type,
+                            validation.error_message().unwrap_or("unknown error")
+                        ),
+                        ValidationErrorType::InvalidDataType,
+                    ));
+                }
+            }
+
+            if let Some(table_id) = &comp_def.table_id {
+                self.validate_table_value(table_id, value, &location, result);
+            }
+        }
+    }
+
+    /// Validate a coded value against a vocabulary table
+    ///
+    /// Misses against "suggest only" tables are reported as warnings.
+    fn validate_table_value(
+        &self,
+        table_id: &str,
+        value: &str,
+        location: &str,
+        result: &mut ValidationResult,
+    ) {
+        let vocab_validation = self.table_registry.validate(table_id, value);
+        if let Some(err_msg) = vocab_validation.error_message() {
+            if self.table_registry.is_suggest_only(table_id) {
+                self.add_warning(result, ValidationWarning::with_type(
+                    location.to_string(),
+                    err_msg.to_string(),
+                    ValidationErrorType::TableSuggestion,
+                ));
+            } else {
+                self.add_error(result, ValidationError::new(
+                    location.to_string(),
+                    err_msg.to_string(),
+                    ValidationErrorType::InvalidValue,
+                ));
+            }
+        }
+    }
+
     /// Validate business rules using the rules engine
     fn validate_business_rules(
         &self,
@@ -418,16 +626,17 @@ impl Validator {
         for violation in rules_result.violations {
             match violation.severity {
                 RuleSeverity::Error => {
-                    result.add_error(ValidationError::new(
+                    self.add_error(result, ValidationError::new(
                         violation.location.unwrap_or_else(|| "Message".to_string()),
                         violation.message,
-                        ValidationErrorType::InvalidValue,
+                        ValidationErrorType::BusinessRule,
                     ));
                 }
                 RuleSeverity::Warning | RuleSeverity::Info => {
-                    result.add_warning(ValidationWarning::new(
+                    self.add_warning(result, ValidationWarning::with_type(
                         violation.location.unwrap_or_else(|| "Message".to_string()),
                         violation.message,
+                        ValidationErrorType::BusinessRule,
                     ));
                 }
             }
@@ -463,6 +672,19 @@ pub struct FieldDefinition {
     pub max_length: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub table_id: Option<String>,
+    /// Per-component definitions, in component order (component 1 first)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<ComponentDefinition>>,
+}
+
+/// Component definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentDefinition {
+    pub name: String,
+    pub data_type: String,
+    pub required: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table_id: Option<String>,
 }
 
 #[cfg(test)]
@@ -529,4 +751,261 @@ mod tests {
 
         assert!(!result.is_valid());
     }
+
+    #[test]
+    fn test_policy_reclassifies_findings() {
+        let mut msg = Message::new();
+        msg.add_segment(Segment::new("PID"));
+
+        // "First segment must be MSH" is a structural error by default
+        let result = Validator::new(Version::V2_5).validate(&msg);
+        assert!(!result.is_valid());
+
+        let policy = ValidationPolicy::new()
+            .with_severity(ValidationErrorType::StructuralError, ValidationSeverity::Warning);
+        let result = Validator::with_policy(Version::V2_5, policy).validate(&msg);
+        assert!(result.is_valid());
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.error_type == Some(ValidationErrorType::StructuralError)));
+
+        // Version mismatch is a warning by default
+        let msg = rs7_parser::parse_message("MSH|^~\\&|App|Fac|||20240101||ADT^A01|1|P|2.3").unwrap();
+        assert!(Validator::new(Version::V2_5).validate(&msg).is_valid());
+        let policy = ValidationPolicy::new().with_warnings_as_errors(true);
+        let result = Validator::with_policy(Version::V2_5, policy).validate(&msg);
+        assert!(!result.is_valid());
+        assert_eq!(result.errors[0].location, "MSH-12");
+        assert_eq!(result.errors[0].error_type, ValidationErrorType::VersionMismatch);
+
+        // A policy for structural errors leaves the version mismatch alone
+        let policy = ValidationPolicy::new()
+            .with_severity(ValidationErrorType::StructuralError, ValidationSeverity::Error);
+        let result = Validator::with_policy(Version::V2_5, policy).validate(&msg);
+        assert!(result.is_valid());
+        assert_eq!(result.warnings[0].error_type, Some(ValidationErrorType::VersionMismatch));
+    }
+
+    #[test]
+    fn test_unsupported_version_warning() {
+        let validator = Validator::new(Version::V2_5);
+        for (version, expected) in [("", "missing"), ("9.9", "9.9")] {
+            let msg = rs7_parser::parse_message(&format!(
+                "MSH|^~\\&|App|Fac|||20240101||ADT^A01|1|P|{}",
+                version
+            ))
+            .unwrap();
+            let result = validator.validate(&msg);
+            assert!(result.is_valid());
+            let warning = result
+                .warnings
+                .iter()
+                .find(|w| w.error_type == Some(ValidationErrorType::UnsupportedVersion))
+                .unwrap();
+            assert_eq!(warning.location, "MSH-12");
+            assert!(warning.message.contains(expected));
+        }
+
+        let msg = rs7_parser::parse_message("MSH|^~\\&|App|Fac|||20240101||ADT^A01|1|P|2.5").unwrap();
+        assert!(validator.validate(&msg).warnings.is_empty());
+
+        let policy = ValidationPolicy::new()
+            .with_severity(ValidationErrorType::UnsupportedVersion, ValidationSeverity::Error);
+        let msg = rs7_parser::parse_message("MSH|^~\\&|App|Fac|||20240101||ADT^A01|1|P|X").unwrap();
+        let result = Validator::with_policy(Version::V2_5, policy).validate(&msg);
+        assert_eq!(result.errors[0].error_type, ValidationErrorType::UnsupportedVersion);
+    }
+
+    #[test]
+    fn test_schema_registry_lookup() {
+        let mut segments = HashMap::new();
+        segments.insert(
+            "ZIN".to_string(),
+            SegmentDefinition {
+                name: "Inventory Item".to_string(),
+                required: true,
+                repeating: true,
+                fields: HashMap::new(),
+            },
+        );
+        let mut registry = SchemaRegistry::new();
+        registry.register(MessageSchema {
+            message_type: "ZMI".to_string(),
+            trigger_event: "Z01".to_string(),
+            version: "2.5".to_string(),
+            segments,
+        });
+        let validator = Validator::new(Version::V2_5).with_schema_registry(registry);
+
+        let msg = rs7_parser::parse_message("MSH|^~\\&|App|Fac|||20240101||ZMI^Z01|1|P|2.5").unwrap();
+        let result = validator.validate(&msg);
+        assert!(result.errors.iter().any(|e| e.message.contains("ZIN")));
+
+        // Not registered: falls back to the embedded ADT^A01 schema
+        let msg = rs7_parser::parse_message("MSH|^~\\&|App|Fac|||20240101||ADT^A01|1|P|2.5").unwrap();
+        let result = validator.validate(&msg);
+        assert!(result.errors.iter().any(|e| e.message.contains("PID")));
+    }
+
+    #[test]
+    fn test_suggest_only_table_reports_warning() {
+        let mut fields = HashMap::new();
+        fields.insert(
+            3,
+            FieldDefinition {
+                name: "Assigned Patient Location".to_string(),
+                data_type: "PL".to_string(),
+                required: false,
+                repeating: false,
+                max_length: None,
+                table_id: Some("L001".to_string()),
+                components: None,
+            },
+        );
+        let mut segments = HashMap::new();
+        segments.insert(
+            "PV1".to_string(),
+            SegmentDefinition {
+                name: "Patient Visit".to_string(),
+                required: false,
+                repeating: false,
+                fields,
+            },
+        );
+        let schema = MessageSchema {
+            message_type: "ADT".to_string(),
+            trigger_event: "A01".to_string(),
+            version: "2.5".to_string(),
+            segments,
+        };
+
+        let mut validator = Validator::with_schema(Version::V2_5, schema);
+        validator
+            .table_registry_mut()
+            .load_from_csv("L001", "ICU,Intensive care".as_bytes())
+            .unwrap();
+
+        let msg = rs7_parser::parse_message(
+            "MSH|^~\\&|App|Fac|||20240101||ADT^A01|1|P|2.5\rPV1|1|I|WARD9",
+        )
+        .unwrap();
+        assert!(!validator.validate(&msg).is_valid());
+
+        validator.table_registry_mut().set_suggest_only("L001", true);
+        let result = validator.validate(&msg);
+        assert!(result.is_valid());
+        let warning = result.warnings.iter().find(|w| w.location == "PV1[1]-3").unwrap();
+        assert_eq!(warning.error_type, Some(ValidationErrorType::TableSuggestion));
+    }
+
+    #[test]
+    fn test_policy_leaves_unrelated_findings() {
+        let validator = |error_type| {
+            let mut engine = RulesEngine::new();
+            engine.add_rule(
+                ValidationRule::new("pid_present", "PID segment expected", RuleSeverity::Warning)
+                    .with_condition(|msg| msg.get_segment_by_id("PID").is_some()),
+            );
+            let policy = ValidationPolicy::new().with_severity(error_type, ValidationSeverity::Error);
+            Validator::with_policy(Version::V2_5, policy).with_rules_engine(engine)
+        };
+
+        let msg = rs7_parser::parse_message("MSH|^~\\&|App|Fac|||20240101||ADT^A01|1|P|2.5").unwrap();
+        // Promoting invalid values does not touch business rule findings
+        let result = validator(ValidationErrorType::InvalidValue).validate(&msg);
+        assert!(result.is_valid());
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.error_type == Some(ValidationErrorType::BusinessRule)));
+
+        let result = validator(ValidationErrorType::BusinessRule).validate(&msg);
+        assert_eq!(result.errors[0].error_type, ValidationErrorType::BusinessRule);
+    }
+
+    #[test]
+    fn test_validate_components() {
+        let component = |name: &str, data_type: &str, required: bool| ComponentDefinition {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            required,
+            table_id: None,
+        };
+
+        let mut fields = HashMap::new();
+        fields.insert(
+            3,
+            FieldDefinition {
+                name: "Patient Identifier List".to_string(),
+                data_type: "CX".to_string(),
+                required: true,
+                repeating: true,
+                max_length: None,
+                table_id: None,
+                components: Some(vec![
+                    component("ID Number", "ST", true),
+                    component("Check Digit", "ST", false),
+                    component("Check Digit Scheme", "ID", false),
+                    component("Assigning Authority", "HD", true),
+                ]),
+            },
+        );
+        fields.insert(
+            5,
+            FieldDefinition {
+                name: "Patient Name".to_string(),
+                data_type: "XPN".to_string(),
+                required: true,
+                repeating: true,
+                max_length: None,
+                table_id: None,
+                components: Some(vec![
+                    component("Family Name", "ST", true),
+                    component("Given Name", "ST", false),
+                ]),
+            },
+        );
+        fields.insert(
+            7,
+            FieldDefinition {
+                name: "Date/Time of Birth".to_string(),
+                data_type: "ST".to_string(),
+                required: false,
+                repeating: false,
+                max_length: None,
+                table_id: None,
+                components: Some(vec![component("Time", "DTM", true)]),
+            },
+        );
+
+        let mut segments = HashMap::new();
+        segments.insert(
+            "PID".to_string(),
+            SegmentDefinition {
+                name: "Patient Identification".to_string(),
+                required: true,
+                repeating: false,
+                fields,
+            },
+        );
+        let schema = MessageSchema {
+            message_type: "ADT".to_string(),
+            trigger_event: "A01".to_string(),
+            version: "2.5".to_string(),
+            segments,
+        };
+
+        let msg = rs7_parser::parse_message(
+            "MSH|^~\\&|App|Fac|||20240101||ADT^A01|1|P|2.5\rPID|1||123^^^HOSP~456||^John||notadate",
+        )
+        .unwrap();
+        let result = Validator::with_schema(Version::V2_5, schema).validate(&msg);
+
+        let locations: Vec<_> = result.errors.iter().map(|e| e.location.as_str()).collect();
+        assert_eq!(locations.len(), 3, "{:?}", result.errors);
+        assert!(locations.contains(&"PID[1]-3(1)-4"));
+        assert!(locations.contains(&"PID[1]-5-1"));
+        assert!(locations.contains(&"PID[1]-7-1"));
+    }
 }
@@ -0,0 +1,125 @@
+//! Severity policy for validation findings
+//!
+//! A [`ValidationPolicy`] decides whether each finding produced by the
+//! [`Validator`](crate::Validator) is reported as an error or as a warning,
+//! so strictness can be tuned without code changes.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rs7_core::Version;
+//! use rs7_validator::{ValidationErrorType, ValidationPolicy, ValidationSeverity, Validator};
+//!
+//! // Tolerate over-long fields during a migration, keep everything else strict
+//! let policy = ValidationPolicy::new()
+//!     .with_severity(ValidationErrorType::InvalidLength, ValidationSeverity::Warning);
+//! let validator = Validator::with_policy(Version::V2_5, policy);
+//!
+//! // Dry run: report every finding as a warning
+//! let dry_run = Validator::with_policy(Version::V2_5, ValidationPolicy::all_warnings());
+//! ```
+
+use crate::ValidationErrorType;
+use std::collections::HashMap;
+
+/// Bucket a validation finding is reported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationSeverity {
+    Error,
+    Warning,
+}
+
+/// Rules for classifying validation findings as errors or warnings
+#[derive(Debug, Clone, Default)]
+pub struct ValidationPolicy {
+    overrides: HashMap<ValidationErrorType, ValidationSeverity>,
+    warnings_as_errors: bool,
+    all_warnings: bool,
+}
+
+impl ValidationPolicy {
+    /// Create a policy that keeps the validator's default classification
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a policy that reports every finding as a warning
+    pub fn all_warnings() -> Self {
+        Self {
+            all_warnings: true,
+            ..Self::default()
+        }
+    }
+
+    /// Report findings of the given type with the given severity
+    pub fn with_severity(mut self, error_type: ValidationErrorType, severity: ValidationSeverity) -> Self {
+        self.overrides.insert(error_type, severity);
+        self
+    }
+
+    /// Report all warnings without a specific override as errors
+    pub fn with_warnings_as_errors(mut self, enabled: bool) -> Self {
+        self.warnings_as_errors = enabled;
+        self
+    }
+
+    /// Decide the severity of a finding
+    ///
+    /// `default` is the severity the validator would use without a policy.
+    /// Precedence is: [`all_warnings`](Self::all_warnings), then per-type
+    /// overrides, then [`with_warnings_as_errors`](Self::with_warnings_as_errors).
+    pub fn severity(
+        &self,
+        error_type: Option<ValidationErrorType>,
+        default: ValidationSeverity,
+    ) -> ValidationSeverity {
+        if self.all_warnings {
+            return ValidationSeverity::Warning;
+        }
+        if let Some(severity) = error_type.and_then(|t| self.overrides.get(&t)) {
+            return *severity;
+        }
+        if self.warnings_as_errors {
+            return ValidationSeverity::Error;
+        }
+        default
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy_keeps_severity() {
+        let policy = ValidationPolicy::new();
+        assert_eq!(
+            policy.severity(Some(ValidationErrorType::InvalidLength), ValidationSeverity::Error),
+            ValidationSeverity::Error
+        );
+        assert_eq!(policy.severity(None, ValidationSeverity::Warning), ValidationSeverity::Warning);
+    }
+
+    #[test]
+    fn test_policy_precedence() {
+        let policy = ValidationPolicy::new()
+            .with_severity(ValidationErrorType::InvalidLength, ValidationSeverity::Warning)
+            .with_warnings_as_errors(true);
+
+        assert_eq!(
+            policy.severity(Some(ValidationErrorType::InvalidLength), ValidationSeverity::Error),
+            ValidationSeverity::Warning
+        );
+        assert_eq!(
+            policy.severity(Some(ValidationErrorType::InvalidValue), ValidationSeverity::Warning),
+            ValidationSeverity::Error
+        );
+
+        let dry_run = ValidationPolicy::all_warnings()
+            .with_severity(ValidationErrorType::InvalidLength, ValidationSeverity::Error);
+        assert_eq!(
+            dry_run.severity(Some(ValidationErrorType::InvalidLength), ValidationSeverity::Error),
+            ValidationSeverity::Warning
+        );
+    }
+}
@@ -5,9 +5,33 @@
 
 use crate::MessageSchema;
 use rs7_core::{error::Result, Version};
+use std::sync::OnceLock;
+
+/// Parse an embedded schema on first use and keep it for later lookups
+macro_rules! embedded {
+    ($path:literal) => {{
+        static SCHEMA: OnceLock<Option<MessageSchema>> = OnceLock::new();
+        SCHEMA.get_or_init(|| parse_schema_json(include_str!($path))).as_ref()
+    }};
+}
 
 /// Load a schema for a specific message type and version
 pub fn load_schema(version: Version, message_type: &str, trigger_event: &str) -> Result<MessageSchema> {
+    match embedded_schema(version, message_type, trigger_event) {
+        Some(schema) => Ok(schema.clone()),
+        None => Err(rs7_core::error::Error::Validation(format!(
+            "Schema not found for {} {} version {}",
+            message_type, trigger_event, version.as_str()
+        ))),
+    }
+}
+
+/// Get an embedded schema, parsed once and shared by later lookups
+pub(crate) fn embedded_schema(
+    version: Version,
+    message_type: &str,
+    trigger_event: &str,
+) -> Option<&'static MessageSchema> {
     let version_str = match version {
         // Legacy versions fall back to v2.3 schemas
         Version::V2_1 | Version::V2_2 => "v2_3",
@@ -27,249 +51,242 @@ pub fn load_schema(version: Version, message_type: &str, trigger_event: &str) ->
         format!("{}_{}", message_type, trigger_event)
     };
 
-    // Try to load embedded schema
-    match load_embedded_schema(version_str, &schema_key) {
-        Some(schema) => Ok(schema),
-        None => Err(rs7_core::error::Error::Validation(format!(
-            "Schema not found for {} {} version {}",
-            message_type, trigger_event, version.as_str()
-        ))),
-    }
+    load_embedded_schema(version_str, &schema_key)
 }
 
 /// Load an embedded schema from compile-time included JSON
-fn load_embedded_schema(version: &str, schema_key: &str) -> Option<MessageSchema> {
+fn load_embedded_schema(version: &str, schema_key: &str) -> Option<&'static MessageSchema> {
     // Include schemas at compile time
     match (version, schema_key) {
         // V2.3 schemas - ADT messages
-        ("v2_3", "ADT_A01") => parse_schema_json(include_str!("../schemas/v2_3/ADT_A01.json")),
-        ("v2_3", "ADT_A02") => parse_schema_json(include_str!("../schemas/v2_3/ADT_A02.json")),
-        ("v2_3", "ADT_A03") => parse_schema_json(include_str!("../schemas/v2_3/ADT_A03.json")),
-        ("v2_3", "ADT_A04") => parse_schema_json(include_str!("../schemas/v2_3/ADT_A04.json")),
-        ("v2_3", "ADT_A05") => parse_schema_json(include_str!("../schemas/v2_3/ADT_A05.json")),
-        ("v2_3", "ADT_A06") => parse_schema_json(include_str!("../schemas/v2_3/ADT_A06.json")),
-        ("v2_3", "ADT_A07") => parse_schema_json(include_str!("../schemas/v2_3/ADT_A07.json")),
-        ("v2_3", "ADT_A08") => parse_schema_json(include_str!("../schemas/v2_3/ADT_A08.json")),
-        ("v2_3", "ADT_A09") => parse_schema_json(include_str!("../schemas/v2_3/ADT_A09.json")),
-        ("v2_3", "ADT_A10") => parse_schema_json(include_str!("../schemas/v2_3/ADT_A10.json")),
-        ("v2_3", "ADT_A11") => parse_schema_json(include_str!("../schemas/v2_3/ADT_A11.json")),
-        ("v2_3", "ADT_A12") => parse_schema_json(include_str!("../schemas/v2_3/ADT_A12.json")),
-        ("v2_3", "ADT_A13") => parse_schema_json(include_str!("../schemas/v2_3/ADT_A13.json")),
-        ("v2_3", "ADT_A17") => parse_schema_json(include_str!("../schemas/v2_3/ADT_A17.json")),
-        ("v2_3", "ADT_A28") => parse_schema_json(include_str!("../schemas/v2_3/ADT_A28.json")),
-        ("v2_3", "ADT_A31") => parse_schema_json(include_str!("../schemas/v2_3/ADT_A31.json")),
-        ("v2_3", "ADT_A40") => parse_schema_json(include_str!("../schemas/v2_3/ADT_A40.json")),
+        ("v2_3", "ADT_A01") => embedded!("../schemas/v2_3/ADT_A01.json"),
+        ("v2_3", "ADT_A02") => embedded!("../schemas/v2_3/ADT_A02.json"),
+        ("v2_3", "ADT_A03") => embedded!("../schemas/v2_3/ADT_A03.json"),
+        ("v2_3", "ADT_A04") => embedded!("../schemas/v2_3/ADT_A04.json"),
+        ("v2_3", "ADT_A05") => embedded!("../schemas/v2_3/ADT_A05.json"),
+        ("v2_3", "ADT_A06") => embedded!("../schemas/v2_3/ADT_A06.json"),
+        ("v2_3", "ADT_A07") => embedded!("../schemas/v2_3/ADT_A07.json"),
+        ("v2_3", "ADT_A08") => embedded!("../schemas/v2_3/ADT_A08.json"),
+        ("v2_3", "ADT_A09") => embedded!("../schemas/v2_3/ADT_A09.json"),
+        ("v2_3", "ADT_A10") => embedded!("../schemas/v2_3/ADT_A10.json"),
+        ("v2_3", "ADT_A11") => embedded!("../schemas/v2_3/ADT_A11.json"),
+        ("v2_3", "ADT_A12") => embedded!("../schemas/v2_3/ADT_A12.json"),
+        ("v2_3", "ADT_A13") => embedded!("../schemas/v2_3/ADT_A13.json"),
+        ("v2_3", "ADT_A17") => embedded!("../schemas/v2_3/ADT_A17.json"),
+        ("v2_3", "ADT_A28") => embedded!("../schemas/v2_3/ADT_A28.json"),
+        ("v2_3", "ADT_A31") => embedded!("../schemas/v2_3/ADT_A31.json"),
+        ("v2_3", "ADT_A40") => embedded!("../schemas/v2_3/ADT_A40.json"),
         // V2.3 - Other message types
-        ("v2_3", "ORU_R01") => parse_schema_json(include_str!("../schemas/v2_3/ORU_R01.json")),
-        ("v2_3", "ORM_O01") => parse_schema_json(include_str!("../schemas/v2_3/ORM_O01.json")),
-        ("v2_3", "ACK") => parse_schema_json(include_str!("../schemas/v2_3/ACK.json")),
-        ("v2_3", "SIU_S12") => parse_schema_json(include_str!("../schemas/v2_3/SIU_S12.json")),
-        ("v2_3", "SIU_S13") => parse_schema_json(include_str!("../schemas/v2_3/SIU_S13.json")),
-        ("v2_3", "SIU_S14") => parse_schema_json(include_str!("../schemas/v2_3/SIU_S14.json")),
-        ("v2_3", "SIU_S15") => parse_schema_json(include_str!("../schemas/v2_3/SIU_S15.json")),
-        ("v2_3", "MDM_T01") => parse_schema_json(include_str!("../schemas/v2_3/MDM_T01.json")),
-        ("v2_3", "MDM_T02") => parse_schema_json(include_str!("../schemas/v2_3/MDM_T02.json")),
-        ("v2_3", "MDM_T04") => parse_schema_json(include_str!("../schemas/v2_3/MDM_T04.json")),
-        ("v2_3", "DFT_P03") => parse_schema_json(include_str!("../schemas/v2_3/DFT_P03.json")),
-        ("v2_3", "DFT_P11") => parse_schema_json(include_str!("../schemas/v2_3/DFT_P11.json")),
-        ("v2_3", "QRY_A19") => parse_schema_json(include_str!("../schemas/v2_3/QRY_A19.json")),
-        ("v2_3", "QRY_Q01") => parse_schema_json(include_str!("../schemas/v2_3/QRY_Q01.json")),
-        ("v2_3", "QRY_Q02") => parse_schema_json(include_str!("../schemas/v2_3/QRY_Q02.json")),
-        ("v2_3", "BAR_P01") => parse_schema_json(include_str!("../schemas/v2_3/BAR_P01.json")),
-        ("v2_3", "BAR_P02") => parse_schema_json(include_str!("../schemas/v2_3/BAR_P02.json")),
-        ("v2_3", "RDE_O11") => parse_schema_json(include_str!("../schemas/v2_3/RDE_O11.json")),
-        ("v2_3", "RAS_O17") => parse_schema_json(include_str!("../schemas/v2_3/RAS_O17.json")),
-        ("v2_3", "MFN_M01") => parse_schema_json(include_str!("../schemas/v2_3/MFN_M01.json")),
-        ("v2_3", "RDS_O13") => parse_schema_json(include_str!("../schemas/v2_3/RDS_O13.json")),
-        ("v2_3", "RGV_O15") => parse_schema_json(include_str!("../schemas/v2_3/RGV_O15.json")),
-        ("v2_3", "RRD_O14") => parse_schema_json(include_str!("../schemas/v2_3/RRD_O14.json")),
-        ("v2_3", "RRA_O18") => parse_schema_json(include_str!("../schemas/v2_3/RRA_O18.json")),
-        ("v2_3", "OUL_R21") => parse_schema_json(include_str!("../schemas/v2_3/OUL_R21.json")),
-        ("v2_3", "OML_O21") => parse_schema_json(include_str!("../schemas/v2_3/OML_O21.json")),
+        ("v2_3", "ORU_R01") => embedded!("../schemas/v2_3/ORU_R01.json"),
+        ("v2_3", "ORM_O01") => embedded!("../schemas/v2_3/ORM_O01.json"),
+        ("v2_3", "ACK") => embedded!("../schemas/v2_3/ACK.json"),
+        ("v2_3", "SIU_S12") => embedded!("../schemas/v2_3/SIU_S12.json"),
+        ("v2_3", "SIU_S13") => embedded!("../schemas/v2_3/SIU_S13.json"),
+        ("v2_3", "SIU_S14") => embedded!("../schemas/v2_3/SIU_S14.json"),
+        ("v2_3", "SIU_S15") => embedded!("../schemas/v2_3/SIU_S15.json"),
+        ("v2_3", "MDM_T01") => embedded!("../schemas/v2_3/MDM_T01.json"),
+        ("v2_3", "MDM_T02") => embedded!("../schemas/v2_3/MDM_T02.json"),
+        ("v2_3", "MDM_T04") => embedded!("../schemas/v2_3/MDM_T04.json"),
+        ("v2_3", "DFT_P03") => embedded!("../schemas/v2_3/DFT_P03.json"),
+        ("v2_3", "DFT_P11") => embedded!("../schemas/v2_3/DFT_P11.json"),
+        ("v2_3", "QRY_A19") => embedded!("../schemas/v2_3/QRY_A19.json"),
+        ("v2_3", "QRY_Q01") => embedded!("../schemas/v2_3/QRY_Q01.json"),
+        ("v2_3", "QRY_Q02") => embedded!("../schemas/v2_3/QRY_Q02.json"),
+        ("v2_3", "BAR_P01") => embedded!("../schemas/v2_3/BAR_P01.json"),
+        ("v2_3", "BAR_P02") => embedded!("../schemas/v2_3/BAR_P02.json"),
+        ("v2_3", "RDE_O11") => embedded!("../schemas/v2_3/RDE_O11.json"),
+        ("v2_3", "RAS_O17") => embedded!("../schemas/v2_3/RAS_O17.json"),
+        ("v2_3", "MFN_M01") => embedded!("../schemas/v2_3/MFN_M01.json"),
+        ("v2_3", "RDS_O13") => embedded!("../schemas/v2_3/RDS_O13.json"),
+        ("v2_3", "RGV_O15") => embedded!("../schemas/v2_3/RGV_O15.json"),
+        ("v2_3", "RRD_O14") => embedded!("../schemas/v2_3/RRD_O14.json"),
+        ("v2_3", "RRA_O18") => embedded!("../schemas/v2_3/RRA_O18.json"),
+        ("v2_3", "OUL_R21") => embedded!("../schemas/v2_3/OUL_R21.json"),
+        ("v2_3", "OML_O21") => embedded!("../schemas/v2_3/OML_O21.json"),
 
         // V2.4 schemas - ADT messages
-        ("v2_4", "ADT_A01") => parse_schema_json(include_str!("../schemas/v2_4/ADT_A01.json")),
-        ("v2_4", "ADT_A02") => parse_schema_json(include_str!("../schemas/v2_4/ADT_A02.json")),
-        ("v2_4", "ADT_A03") => parse_schema_json(include_str!("../schemas/v2_4/ADT_A03.json")),
-        ("v2_4", "ADT_A04") => parse_schema_json(include_str!("../schemas/v2_4/ADT_A04.json")),
-        ("v2_4", "ADT_A05") => parse_schema_json(include_str!("../schemas/v2_4/ADT_A05.json")),
-        ("v2_4", "ADT_A06") => parse_schema_json(include_str!("../schemas/v2_4/ADT_A06.json")),
-        ("v2_4", "ADT_A07") => parse_schema_json(include_str!("../schemas/v2_4/ADT_A07.json")),
-        ("v2_4", "ADT_A08") => parse_schema_json(include_str!("../schemas/v2_4/ADT_A08.json")),
-        ("v2_4", "ADT_A09") => parse_schema_json(include_str!("../schemas/v2_4/ADT_A09.json")),
-        ("v2_4", "ADT_A10") => parse_schema_json(include_str!("../schemas/v2_4/ADT_A10.json")),
-        ("v2_4", "ADT_A11") => parse_schema_json(include_str!("../schemas/v2_4/ADT_A11.json")),
-        ("v2_4", "ADT_A12") => parse_schema_json(include_str!("../schemas/v2_4/ADT_A12.json")),
-        ("v2_4", "ADT_A13") => parse_schema_json(include_str!("../schemas/v2_4/ADT_A13.json")),
-        ("v2_4", "ADT_A17") => parse_schema_json(include_str!("../schemas/v2_4/ADT_A17.json")),
-        ("v2_4", "ADT_A28") => parse_schema_json(include_str!("../schemas/v2_4/ADT_A28.json")),
-        ("v2_4", "ADT_A31") => parse_schema_json(include_str!("../schemas/v2_4/ADT_A31.json")),
-        ("v2_4", "ADT_A40") => parse_schema_json(include_str!("../schemas/v2_4/ADT_A40.json")),
+        ("v2_4", "ADT_A01") => embedded!("../schemas/v2_4/ADT_A01.json"),
+        ("v2_4", "ADT_A02") => embedded!("../schemas/v2_4/ADT_A02.json"),
+        ("v2_4", "ADT_A03") => embedded!("../schemas/v2_4/ADT_A03.json"),
+        ("v2_4", "ADT_A04") => embedded!("../schemas/v2_4/ADT_A04.json"),
+        ("v2_4", "ADT_A05") => embedded!("../schemas/v2_4/ADT_A05.json"),
+        ("v2_4", "ADT_A06") => embedded!("../schemas/v2_4/ADT_A06.json"),
+        ("v2_4", "ADT_A07") => embedded!("../schemas/v2_4/ADT_A07.json"),
+        ("v2_4", "ADT_A08") => embedded!("../schemas/v2_4/ADT_A08.json"),
+        ("v2_4", "ADT_A09") => embedded!("../schemas/v2_4/ADT_A09.json"),
+        ("v2_4", "ADT_A10") => embedded!("../schemas/v2_4/ADT_A10.json"),
+        ("v2_4", "ADT_A11") => embedded!("../schemas/v2_4/ADT_A11.json"),
+        ("v2_4", "ADT_A12") => embedded!("../schemas/v2_4/ADT_A12.json"),
+        ("v2_4", "ADT_A13") => embedded!("../schemas/v2_4/ADT_A13.json"),
+        ("v2_4", "ADT_A17") => embedded!("../schemas/v2_4/ADT_A17.json"),
+        ("v2_4", "ADT_A28") => embedded!("../schemas/v2_4/ADT_A28.json"),
+        ("v2_4", "ADT_A31") => embedded!("../schemas/v2_4/ADT_A31.json"),
+        ("v2_4", "ADT_A40") => embedded!("../schemas/v2_4/ADT_A40.json"),
         // V2.4 - Other message types
-        ("v2_4", "ORU_R01") => parse_schema_json(include_str!("../schemas/v2_4/ORU_R01.json")),
-        ("v2_4", "ORM_O01") => parse_schema_json(include_str!("../schemas/v2_4/ORM_O01.json")),
-        ("v2_4", "ACK") => parse_schema_json(include_str!("../schemas/v2_4/ACK.json")),
-        ("v2_4", "SIU_S12") => parse_schema_json(include_str!("../schemas/v2_4/SIU_S12.json")),
-        ("v2_4", "SIU_S13") => parse_schema_json(include_str!("../schemas/v2_4/SIU_S13.json")),
-        ("v2_4", "SIU_S14") => parse_schema_json(include_str!("../schemas/v2_4/SIU_S14.json")),
-        ("v2_4", "SIU_S15") => parse_schema_json(include_str!("../schemas/v2_4/SIU_S15.json")),
-        ("v2_4", "MDM_T01") => parse_schema_json(include_str!("../schemas/v2_4/MDM_T01.json")),
-        ("v2_4", "MDM_T02") => parse_schema_json(include_str!("../schemas/v2_4/MDM_T02.json")),
-        ("v2_4", "MDM_T04") => parse_schema_json(include_str!("../schemas/v2_4/MDM_T04.json")),
-        ("v2_4", "DFT_P03") => parse_schema_json(include_str!("../schemas/v2_4/DFT_P03.json")),
-        ("v2_4", "DFT_P11") => parse_schema_json(include_str!("../schemas/v2_4/DFT_P11.json")),
-        ("v2_4", "QRY_A19") => parse_schema_json(include_str!("../schemas/v2_4/QRY_A19.json")),
-        ("v2_4", "QRY_Q01") => parse_schema_json(include_str!("../schemas/v2_4/QRY_Q01.json")),
-        ("v2_4", "QRY_Q02") => parse_schema_json(include_str!("../schemas/v2_4/QRY_Q02.json")),
-        ("v2_4", "BAR_P01") => parse_schema_json(include_str!("../schemas/v2_4/BAR_P01.json")),
-        ("v2_4", "BAR_P02") => parse_schema_json(include_str!("../schemas/v2_4/BAR_P02.json")),
-        ("v2_4", "RDE_O11") => parse_schema_json(include_str!("../schemas/v2_4/RDE_O11.json")),
-        ("v2_4", "RAS_O17") => parse_schema_json(include_str!("../schemas/v2_4/RAS_O17.json")),
-        ("v2_4", "MFN_M01") => parse_schema_json(include_str!("../schemas/v2_4/MFN_M01.json")),
-        ("v2_4", "RDS_O13") => parse_schema_json(include_str!("../schemas/v2_4/RDS_O13.json")),
-        ("v2_4", "RGV_O15") => parse_schema_json(include_str!("../schemas/v2_4/RGV_O15.json")),
-        ("v2_4", "RRD_O14") => parse_schema_json(include_str!("../schemas/v2_4/RRD_O14.json")),
-        ("v2_4", "RRA_O18") => parse_schema_json(include_str!("../schemas/v2_4/RRA_O18.json")),
-        ("v2_4", "OUL_R21") => parse_schema_json(include_str!("../schemas/v2_4/OUL_R21.json")),
-        ("v2_4", "OML_O21") => parse_schema_json(include_str!("../schemas/v2_4/OML_O21.json")),
+        ("v2_4", "ORU_R01") => embedded!("../schemas/v2_4/ORU_R01.json"),
+        ("v2_4", "ORM_O01") => embedded!("../schemas/v2_4/ORM_O01.json"),
+        ("v2_4", "ACK") => embedded!("../schemas/v2_4/ACK.json"),
+        ("v2_4", "SIU_S12") => embedded!("../schemas/v2_4/SIU_S12.json"),
+        ("v2_4", "SIU_S13") => embedded!("../schemas/v2_4/SIU_S13.json"),
+        ("v2_4", "SIU_S14") => embedded!("../schemas/v2_4/SIU_S14.json"),
+        ("v2_4", "SIU_S15") => embedded!("../schemas/v2_4/SIU_S15.json"),
+        ("v2_4", "MDM_T01") => embedded!("../schemas/v2_4/MDM_T01.json"),
+        ("v2_4", "MDM_T02") => embedded!("../schemas/v2_4/MDM_T02.json"),
+        ("v2_4", "MDM_T04") => embedded!("../schemas/v2_4/MDM_T04.json"),
+        ("v2_4", "DFT_P03") => embedded!("../schemas/v2_4/DFT_P03.json"),
+        ("v2_4", "DFT_P11") => embedded!("../schemas/v2_4/DFT_P11.json"),
+        ("v2_4", "QRY_A19") => embedded!("../schemas/v2_4/QRY_A19.json"),
+        ("v2_4", "QRY_Q01") => embedded!("../schemas/v2_4/QRY_Q01.json"),
+        ("v2_4", "QRY_Q02") => embedded!("../schemas/v2_4/QRY_Q02.json"),
+        ("v2_4", "BAR_P01") => embedded!("../schemas/v2_4/BAR_P01.json"),
+        ("v2_4", "BAR_P02") => embedded!("../schemas/v2_4/BAR_P02.json"),
+        ("v2_4", "RDE_O11") => embedded!("../schemas/v2_4/RDE_O11.json"),
+        ("v2_4", "RAS_O17") => embedded!("../schemas/v2_4/RAS_O17.json"),
+        ("v2_4", "MFN_M01") => embedded!("../schemas/v2_4/MFN_M01.json"),
+        ("v2_4", "RDS_O13") => embedded!("../schemas/v2_4/RDS_O13.json"),
+        ("v2_4", "RGV_O15") => embedded!("../schemas/v2_4/RGV_O15.json"),
+        ("v2_4", "RRD_O14") => embedded!("../schemas/v2_4/RRD_O14.json"),
+        ("v2_4", "RRA_O18") => embedded!("../schemas/v2_4/RRA_O18.json"),
+        ("v2_4", "OUL_R21") => embedded!("../schemas/v2_4/OUL_R21.json"),
+        ("v2_4", "OML_O21") => embedded!("../schemas/v2_4/OML_O21.json"),
 
         // V2.5 schemas - ADT messages
-        ("v2_5", "ADT_A01") => parse_schema_json(include_str!("../schemas/v2_5/ADT_A01.json")),
-        ("v2_5", "ADT_A02") => parse_schema_json(include_str!("../schemas/v2_5/ADT_A02.json")),
-        ("v2_5", "ADT_A03") => parse_schema_json(include_str!("../schemas/v2_5/ADT_A03.json")),
-        ("v2_5", "ADT_A04") => parse_schema_json(include_str!("../schemas/v2_5/ADT_A04.json")),
-        ("v2_5", "ADT_A05") => parse_schema_json(include_str!("../schemas/v2_5/ADT_A05.json")),
-        ("v2_5", "ADT_A06") => parse_schema_json(include_str!("../schemas/v2_5/ADT_A06.json")),
-        ("v2_5", "ADT_A07") => parse_schema_json(include_str!("../schemas/v2_5/ADT_A07.json")),
-        ("v2_5", "ADT_A08") => parse_schema_json(include_str!("../schemas/v2_5/ADT_A08.json")),
-        ("v2_5", "ADT_A09") => parse_schema_json(include_str!("../schemas/v2_5/ADT_A09.json")),
-        ("v2_5", "ADT_A10") => parse_schema_json(include_str!("../schemas/v2_5/ADT_A10.json")),
-        ("v2_5", "ADT_A11") => parse_schema_json(include_str!("../schemas/v2_5/ADT_A11.json")),
-        ("v2_5", "ADT_A12") => parse_schema_json(include_str!("../schemas/v2_5/ADT_A12.json")),
-        ("v2_5", "ADT_A13") => parse_schema_json(include_str!("../schemas/v2_5/ADT_A13.json")),
-        ("v2_5", "ADT_A17") => parse_schema_json(include_str!("../schemas/v2_5/ADT_A17.json")),
-        ("v2_5", "ADT_A28") => parse_schema_json(include_str!("../schemas/v2_5/ADT_A28.json")),
-        ("v2_5", "ADT_A31") => parse_schema_json(include_str!("../schemas/v2_5/ADT_A31.json")),
-        ("v2_5", "ADT_A40") => parse_schema_json(include_str!("../schemas/v2_5/ADT_A40.json")),
+        ("v2_5", "ADT_A01") => embedded!("../schemas/v2_5/ADT_A01.json"),
+        ("v2_5", "ADT_A02") => embedded!("../schemas/v2_5/ADT_A02.json"),
+        ("v2_5", "ADT_A03") => embedded!("../schemas/v2_5/ADT_A03.json"),
+        ("v2_5", "ADT_A04") => embedded!("../schemas/v2_5/ADT_A04.json"),
+        ("v2_5", "ADT_A05") => embedded!("../schemas/v2_5/ADT_A05.json"),
+        ("v2_5", "ADT_A06") => embedded!("../schemas/v2_5/ADT_A06.json"),
+        ("v2_5", "ADT_A07") => embedded!("../schemas/v2_5/ADT_A07.json"),
+        ("v2_5", "ADT_A08") => embedded!("../schemas/v2_5/ADT_A08.json"),
+        ("v2_5", "ADT_A09") => embedded!("../schemas/v2_5/ADT_A09.json"),
+        ("v2_5", "ADT_A10") => embedded!("../schemas/v2_5/ADT_A10.json"),
+        ("v2_5", "ADT_A11") => embedded!("../schemas/v2_5/ADT_A11.json"),
+        ("v2_5", "ADT_A12") => embedded!("../schemas/v2_5/ADT_A12.json"),
+        ("v2_5", "ADT_A13") => embedded!("../schemas/v2_5/ADT_A13.json"),
+        ("v2_5", "ADT_A17") => embedded!("../schemas/v2_5/ADT_A17.json"),
+        ("v2_5", "ADT_A28") => embedded!("../schemas/v2_5/ADT_A28.json"),
+        ("v2_5", "ADT_A31") => embedded!("../schemas/v2_5/ADT_A31.json"),
+        ("v2_5", "ADT_A40") => embedded!("../schemas/v2_5/ADT_A40.json"),
         // V2.5 - Other message types
-        ("v2_5", "ORU_R01") => parse_schema_json(include_str!("../schemas/v2_5/ORU_R01.json")),
-        ("v2_5", "ORM_O01") => parse_schema_json(include_str!("../schemas/v2_5/ORM_O01.json")),
-        ("v2_5", "ACK") => parse_schema_json(include_str!("../schemas/v2_5/ACK.json")),
-        ("v2_5", "SIU_S12") => parse_schema_json(include_str!("../schemas/v2_5/SIU_S12.json")),
-        ("v2_5", "SIU_S13") => parse_schema_json(include_str!("../schemas/v2_5/SIU_S13.json")),
-        ("v2_5", "SIU_S14") => parse_schema_json(include_str!("../schemas/v2_5/SIU_S14.json")),
-        ("v2_5", "SIU_S15") => parse_schema_json(include_str!("../schemas/v2_5/SIU_S15.json")),
-        ("v2_5", "MDM_T01") => parse_schema_json(include_str!("../schemas/v2_5/MDM_T01.json")),
-        ("v2_5", "MDM_T02") => parse_schema_json(include_str!("../schemas/v2_5/MDM_T02.json")),
-        ("v2_5", "MDM_T04") => parse_schema_json(include_str!("../schemas/v2_5/MDM_T04.json")),
-        ("v2_5", "DFT_P03") => parse_schema_json(include_str!("../schemas/v2_5/DFT_P03.json")),
-        ("v2_5", "DFT_P11") => parse_schema_json(include_str!("../schemas/v2_5/DFT_P11.json")),
-        ("v2_5", "QRY_A19") => parse_schema_json(include_str!("../schemas/v2_5/QRY_A19.json")),
-        ("v2_5", "QRY_Q01") => parse_schema_json(include_str!("../schemas/v2_5/QRY_Q01.json")),
-        ("v2_5", "QRY_Q02") => parse_schema_json(include_str!("../schemas/v2_5/QRY_Q02.json")),
-        ("v2_5", "BAR_P01") => parse_schema_json(include_str!("../schemas/v2_5/BAR_P01.json")),
-        ("v2_5", "BAR_P02") => parse_schema_json(include_str!("../schemas/v2_5/BAR_P02.json")),
-        ("v2_5", "RDE_O11") => parse_schema_json(include_str!("../schemas/v2_5/RDE_O11.json")),
-        ("v2_5", "RAS_O17") => parse_schema_json(include_str!("../schemas/v2_5/RAS_O17.json")),
-        ("v2_5", "MFN_M01") => parse_schema_json(include_str!("../schemas/v2_5/MFN_M01.json")),
-        ("v2_5", "RDS_O13") => parse_schema_json(include_str!("../schemas/v2_5/RDS_O13.json")),
-        ("v2_5", "RGV_O15") => parse_schema_json(include_str!("../schemas/v2_5/RGV_O15.json")),
-        ("v2_5", "RRD_O14") => parse_schema_json(include_str!("../schemas/v2_5/RRD_O14.json")),
-        ("v2_5", "RRA_O18") => parse_schema_json(include_str!("../schemas/v2_5/RRA_O18.json")),
-        ("v2_5", "OUL_R21") => parse_schema_json(include_str!("../schemas/v2_5/OUL_R21.json")),
-        ("v2_5", "OML_O21") => parse_schema_json(include_str!("../schemas/v2_5/OML_O21.json")),
+        ("v2_5", "ORU_R01") => embedded!("../schemas/v2_5/ORU_R01.json"),
+        ("v2_5", "ORM_O01") => embedded!("../schemas/v2_5/ORM_O01.json"),
+        ("v2_5", "ACK") => embedded!("../schemas/v2_5/ACK.json"),
+        ("v2_5", "SIU_S12") => embedded!("../schemas/v2_5/SIU_S12.json"),
+        ("v2_5", "SIU_S13") => embedded!("../schemas/v2_5/SIU_S13.json"),
+        ("v2_5", "SIU_S14") => embedded!("../schemas/v2_5/SIU_S14.json"),
+        ("v2_5", "SIU_S15") => embedded!("../schemas/v2_5/SIU_S15.json"),
+        ("v2_5", "MDM_T01") => embedded!("../schemas/v2_5/MDM_T01.json"),
+        ("v2_5", "MDM_T02") => embedded!("../schemas/v2_5/MDM_T02.json"),
+        ("v2_5", "MDM_T04") => embedded!("../schemas/v2_5/MDM_T04.json"),
+        ("v2_5", "DFT_P03") => embedded!("../schemas/v2_5/DFT_P03.json"),
+        ("v2_5", "DFT_P11") => embedded!("../schemas/v2_5/DFT_P11.json"),
+        ("v2_5", "QRY_A19") => embedded!("../schemas/v2_5/QRY_A19.json"),
+        ("v2_5", "QRY_Q01") => embedded!("../schemas/v2_5/QRY_Q01.json"),
+        ("v2_5", "QRY_Q02") => embedded!("../schemas/v2_5/QRY_Q02.json"),
+        ("v2_5", "BAR_P01") => embedded!("../schemas/v2_5/BAR_P01.json"),
+        ("v2_5", "BAR_P02") => embedded!("../schemas/v2_5/BAR_P02.json"),
+        ("v2_5", "RDE_O11") => embedded!("../schemas/v2_5/RDE_O11.json"),
+        ("v2_5", "RAS_O17") => embedded!("../schemas/v2_5/RAS_O17.json"),
+        ("v2_5", "MFN_M01") => embedded!("../schemas/v2_5/MFN_M01.json"),
+        ("v2_5", "RDS_O13") => embedded!("../schemas/v2_5/RDS_O13.json"),
+        ("v2_5", "RGV_O15") => embedded!("../schemas/v2_5/RGV_O15.json"),
+        ("v2_5", "RRD_O14") => embedded!("../schemas/v2_5/RRD_O14.json"),
+        ("v2_5", "RRA_O18") => embedded!("../schemas/v2_5/RRA_O18.json"),
+        ("v2_5", "OUL_R21") => embedded!("../schemas/v2_5/OUL_R21.json"),
+        ("v2_5", "OML_O21") => embedded!("../schemas/v2_5/OML_O21.json"),
 
         // V2.6 schemas - ADT messages
-        ("v2_6", "ADT_A01") => parse_schema_json(include_str!("../schemas/v2_6/ADT_A01.json")),
-        ("v2_6", "ADT_A02") => parse_schema_json(include_str!("../schemas/v2_6/ADT_A02.json")),
-        ("v2_6", "ADT_A03") => parse_schema_json(include_str!("../schemas/v2_6/ADT_A03.json")),
-        ("v2_6", "ADT_A04") => parse_schema_json(include_str!("../schemas/v2_6/ADT_A04.json")),
-        ("v2_6", "ADT_A05") => parse_schema_json(include_str!("../schemas/v2_6/ADT_A05.json")),
-        ("v2_6", "ADT_A06") => parse_schema_json(include_str!("../schemas/v2_6/ADT_A06.json")),
-        ("v2_6", "ADT_A07") => parse_schema_json(include_str!("../schemas/v2_6/ADT_A07.json")),
-        ("v2_6", "ADT_A08") => parse_schema_json(include_str!("../schemas/v2_6/ADT_A08.json")),
-        ("v2_6", "ADT_A09") => parse_schema_json(include_str!("../schemas/v2_6/ADT_A09.json")),
-        ("v2_6", "ADT_A10") => parse_schema_json(include_str!("../schemas/v2_6/ADT_A10.json")),
-        ("v2_6", "ADT_A11") => parse_schema_json(include_str!("../schemas/v2_6/ADT_A11.json")),
-        ("v2_6", "ADT_A12") => parse_schema_json(include_str!("../schemas/v2_6/ADT_A12.json")),
-        ("v2_6", "ADT_A13") => parse_schema_json(include_str!("../schemas/v2_6/ADT_A13.json")),
-        ("v2_6", "ADT_A17") => parse_schema_json(include_str!("../schemas/v2_6/ADT_A17.json")),
-        ("v2_6", "ADT_A28") => parse_schema_json(include_str!("../schemas/v2_6/ADT_A28.json")),
-        ("v2_6", "ADT_A31") => parse_schema_json(include_str!("../schemas/v2_6/ADT_A31.json")),
-        ("v2_6", "ADT_A40") => parse_schema_json(include_str!("../schemas/v2_6/ADT_A40.json")),
+        ("v2_6", "ADT_A01") => embedded!("../schemas/v2_6/ADT_A01.json"),
+        ("v2_6", "ADT_A02") => embedded!("../schemas/v2_6/ADT_A02.json"),
+        ("v2_6", "ADT_A03") => embedded!("../schemas/v2_6/ADT_A03.json"),
+        ("v2_6", "ADT_A04") => embedded!("../schemas/v2_6/ADT_A04.json"),
+        ("v2_6", "ADT_A05") => embedded!("../schemas/v2_6/ADT_A05.json"),
+        ("v2_6", "ADT_A06") => embedded!("../schemas/v2_6/ADT_A06.json"),
+        ("v2_6", "ADT_A07") => embedded!("../schemas/v2_6/ADT_A07.json"),
+        ("v2_6", "ADT_A08") => embedded!("../schemas/v2_6/ADT_A08.json"),
+        ("v2_6", "ADT_A09") => embedded!("../schemas/v2_6/ADT_A09.json"),
+        ("v2_6", "ADT_A10") => embedded!("../schemas/v2_6/ADT_A10.json"),
+        ("v2_6", "ADT_A11") => embedded!("../schemas/v2_6/ADT_A11.json"),
+        ("v2_6", "ADT_A12") => embedded!("../schemas/v2_6/ADT_A12.json"),
+        ("v2_6", "ADT_A13") => embedded!("../schemas/v2_6/ADT_A13.json"),
+        ("v2_6", "ADT_A17") => embedded!("../schemas/v2_6/ADT_A17.json"),
+        ("v2_6", "ADT_A28") => embedded!("../schemas/v2_6/ADT_A28.json"),
+        ("v2_6", "ADT_A31") => embedded!("../schemas/v2_6/ADT_A31.json"),
+        ("v2_6", "ADT_A40") => embedded!("../schemas/v2_6/ADT_A40.json"),
         // V2.6 - Other message types
-        ("v2_6", "ORU_R01") => parse_schema_json(include_str!("../schemas/v2_6/ORU_R01.json")),
-        ("v2_6", "ORM_O01") => parse_schema_json(include_str!("../schemas/v2_6/ORM_O01.json")),
-        ("v2_6", "ACK") => parse_schema_json(include_str!("../schemas/v2_6/ACK.json")),
-        ("v2_6", "SIU_S12") => parse_schema_json(include_str!("../schemas/v2_6/SIU_S12.json")),
-        ("v2_6", "SIU_S13") => parse_schema_json(include_str!("../schemas/v2_6/SIU_S13.json")),
-        ("v2_6", "SIU_S14") => parse_schema_json(include_str!("../schemas/v2_6/SIU_S14.json")),
-        ("v2_6", "SIU_S15") => parse_schema_json(include_str!("../schemas/v2_6/SIU_S15.json")),
-        ("v2_6", "MDM_T01") => parse_schema_json(include_str!("../schemas/v2_6/MDM_T01.json")),
-        ("v2_6", "MDM_T02") => parse_schema_json(include_str!("../schemas/v2_6/MDM_T02.json")),
-        ("v2_6", "MDM_T04") => parse_schema_json(include_str!("../schemas/v2_6/MDM_T04.json")),
-        ("v2_6", "DFT_P03") => parse_schema_json(include_str!("../schemas/v2_6/DFT_P03.json")),
-        ("v2_6", "DFT_P11") => parse_schema_json(include_str!("../schemas/v2_6/DFT_P11.json")),
-        ("v2_6", "QRY_A19") => parse_schema_json(include_str!("../schemas/v2_6/QRY_A19.json")),
-        ("v2_6", "QRY_Q01") => parse_schema_json(include_str!("../schemas/v2_6/QRY_Q01.json")),
-        ("v2_6", "QRY_Q02") => parse_schema_json(include_str!("../schemas/v2_6/QRY_Q02.json")),
-        ("v2_6", "BAR_P01") => parse_schema_json(include_str!("../schemas/v2_6/BAR_P01.json")),
-        ("v2_6", "BAR_P02") => parse_schema_json(include_str!("../schemas/v2_6/BAR_P02.json")),
-        ("v2_6", "RDE_O11") => parse_schema_json(include_str!("../schemas/v2_6/RDE_O11.json")),
-        ("v2_6", "RAS_O17") => parse_schema_json(include_str!("../schemas/v2_6/RAS_O17.json")),
-        ("v2_6", "MFN_M01") => parse_schema_json(include_str!("../schemas/v2_6/MFN_M01.json")),
-        ("v2_6", "RDS_O13") => parse_schema_json(include_str!("../schemas/v2_6/RDS_O13.json")),
-        ("v2_6", "RGV_O15") => parse_schema_json(include_str!("../schemas/v2_6/RGV_O15.json")),
-        ("v2_6", "RRD_O14") => parse_schema_json(include_str!("../schemas/v2_6/RRD_O14.json")),
-        ("v2_6", "RRA_O18") => parse_schema_json(include_str!("../schemas/v2_6/RRA_O18.json")),
-        ("v2_6", "OUL_R21") => parse_schema_json(include_str!("../schemas/v2_6/OUL_R21.json")),
-        ("v2_6", "OML_O21") => parse_schema_json(include_str!("../schemas/v2_6/OML_O21.json")),
+        ("v2_6", "ORU_R01") => embedded!("../schemas/v2_6/ORU_R01.json"),
+        ("v2_6", "ORM_O01") => embedded!("../schemas/v2_6/ORM_O01.json"),
+        ("v2_6", "ACK") => embedded!("../schemas/v2_6/ACK.json"),
+        ("v2_6", "SIU_S12") => embedded!("../schemas/v2_6/SIU_S12.json"),
+        ("v2_6", "SIU_S13") => embedded!("../schemas/v2_6/SIU_S13.json"),
+        ("v2_6", "SIU_S14") => embedded!("../schemas/v2_6/SIU_S14.json"),
+        ("v2_6", "SIU_S15") => embedded!("../schemas/v2_6/SIU_S15.json"),
+        ("v2_6", "MDM_T01") => embedded!("../schemas/v2_6/MDM_T01.json"),
+        ("v2_6", "MDM_T02") => embedded!("../schemas/v2_6/MDM_T02.json"),
+        ("v2_6", "MDM_T04") => embedded!("../schemas/v2_6/MDM_T04.json"),
+        ("v2_6", "DFT_P03") => embedded!("../schemas/v2_6/DFT_P03.json"),
+        ("v2_6", "DFT_P11") => embedded!("../schemas/v2_6/DFT_P11.json"),
+        ("v2_6", "QRY_A19") => embedded!("../schemas/v2_6/QRY_A19.json"),
+        ("v2_6", "QRY_Q01") => embedded!("../schemas/v2_6/QRY_Q01.json"),
+        ("v2_6", "QRY_Q02") => embedded!("../schemas/v2_6/QRY_Q02.json"),
+        ("v2_6", "BAR_P01") => embedded!("../schemas/v2_6/BAR_P01.json"),
+        ("v2_6", "BAR_P02") => embedded!("../schemas/v2_6/BAR_P02.json"),
+        ("v2_6", "RDE_O11") => embedded!("../schemas/v2_6/RDE_O11.json"),
+        ("v2_6", "RAS_O17") => embedded!("../schemas/v2_6/RAS_O17.json"),
+        ("v2_6", "MFN_M01") => embedded!("../schemas/v2_6/MFN_M01.json"),
+        ("v2_6", "RDS_O13") => embedded!("../schemas/v2_6/RDS_O13.json"),
+        ("v2_6", "RGV_O15") => embedded!("../schemas/v2_6/RGV_O15.json"),
+        ("v2_6", "RRD_O14") => embedded!("../schemas/v2_6/RRD_O14.json"),
+        ("v2_6", "RRA_O18") => embedded!("../schemas/v2_6/RRA_O18.json"),
+        ("v2_6", "OUL_R21") => embedded!("../schemas/v2_6/OUL_R21.json"),
+        ("v2_6", "OML_O21") => embedded!("../schemas/v2_6/OML_O21.json"),
 
         // V2.7 schemas - ADT messages
-        ("v2_7", "ADT_A01") => parse_schema_json(include_str!("../schemas/v2_7/ADT_A01.json")),
-        ("v2_7", "ADT_A02") => parse_schema_json(include_str!("../schemas/v2_7/ADT_A02.json")),
-        ("v2_7", "ADT_A03") => parse_schema_json(include_str!("../schemas/v2_7/ADT_A03.json")),
-        ("v2_7", "ADT_A04") => parse_schema_json(include_str!("../schemas/v2_7/ADT_A04.json")),
-        ("v2_7", "ADT_A05") => parse_schema_json(include_str!("../schemas/v2_7/ADT_A05.json")),
-        ("v2_7", "ADT_A06") => parse_schema_json(include_str!("../schemas/v2_7/ADT_A06.json")),
-        ("v2_7", "ADT_A07") => parse_schema_json(include_str!("../schemas/v2_7/ADT_A07.json")),
-        ("v2_7", "ADT_A08") => parse_schema_json(include_str!("../schemas/v2_7/ADT_A08.json")),
-        ("v2_7", "ADT_A09") => parse_schema_json(include_str!("../schemas/v2_7/ADT_A09.json")),
-        ("v2_7", "ADT_A10") => parse_schema_json(include_str!("../schemas/v2_7/ADT_A10.json")),
-        ("v2_7", "ADT_A11") => parse_schema_json(include_str!("../schemas/v2_7/ADT_A11.json")),
-        ("v2_7", "ADT_A12") => parse_schema_json(include_str!("../schemas/v2_7/ADT_A12.json")),
-        ("v2_7", "ADT_A13") => parse_schema_json(include_str!("../schemas/v2_7/ADT_A13.json")),
-        ("v2_7", "ADT_A17") => parse_schema_json(include_str!("../schemas/v2_7/ADT_A17.json")),
-        ("v2_7", "ADT_A28") => parse_schema_json(include_str!("../schemas/v2_7/ADT_A28.json")),
-        ("v2_7", "ADT_A31") => parse_schema_json(include_str!("../schemas/v2_7/ADT_A31.json")),
-        ("v2_7", "ADT_A40") => parse_schema_json(include_str!("../schemas/v2_7/ADT_A40.json")),
+        ("v2_7", "ADT_A01") => embedded!("../schemas/v2_7/ADT_A01.json"),
+        ("v2_7", "ADT_A02") => embedded!("../schemas/v2_7/ADT_A02.json"),
+        ("v2_7", "ADT_A03") => embedded!("../schemas/v2_7/ADT_A03.json"),
+        ("v2_7", "ADT_A04") => embedded!("../schemas/v2_7/ADT_A04.json"),
+        ("v2_7", "ADT_A05") => embedded!("../schemas/v2_7/ADT_A05.json"),
+        ("v2_7", "ADT_A06") => embedded!("../schemas/v2_7/ADT_A06.json"),
+        ("v2_7", "ADT_A07") => embedded!("../schemas/v2_7/ADT_A07.json"),
+        ("v2_7", "ADT_A08") => embedded!("../schemas/v2_7/ADT_A08.json"),
+        ("v2_7", "ADT_A09") => embedded!("../schemas/v2_7/ADT_A09.json"),
+        ("v2_7", "ADT_A10") => embedded!("../schemas/v2_7/ADT_A10.json"),
+        ("v2_7", "ADT_A11") => embedded!("../schemas/v2_7/ADT_A11.json"),
+        ("v2_7", "ADT_A12") => embedded!("../schemas/v2_7/ADT_A12.json"),
+        ("v2_7", "ADT_A13") => embedded!("../schemas/v2_7/ADT_A13.json"),
+        ("v2_7", "ADT_A17") => embedded!("../schemas/v2_7/ADT_A17.json"),
+        ("v2_7", "ADT_A28") => embedded!("../schemas/v2_7/ADT_A28.json"),
+        ("v2_7", "ADT_A31") => embedded!("../schemas/v2_7/ADT_A31.json"),
+        ("v2_7", "ADT_A40") => embedded!("../schemas/v2_7/ADT_A40.json"),
         // V2.7 - Other message types
-        ("v2_7", "ORU_R01") => parse_schema_json(include_str!("../schemas/v2_7/ORU_R01.json")),
-        ("v2_7", "ORM_O01") => parse_schema_json(include_str!("../schemas/v2_7/ORM_O01.json")),
-        ("v2_7", "ACK") => parse_schema_json(include_str!("../schemas/v2_7/ACK.json")),
-        ("v2_7", "SIU_S12") => parse_schema_json(include_str!("../schemas/v2_7/SIU_S12.json")),
-        ("v2_7", "SIU_S13") => parse_schema_json(include_str!("../schemas/v2_7/SIU_S13.json")),
-        ("v2_7", "SIU_S14") => parse_schema_json(include_str!("../schemas/v2_7/SIU_S14.json")),
-        ("v2_7", "SIU_S15") => parse_schema_json(include_str!("../schemas/v2_7/SIU_S15.json")),
-        ("v2_7", "MDM_T01") => parse_schema_json(include_str!("../schemas/v2_7/MDM_T01.json")),
-        ("v2_7", "MDM_T02") => parse_schema_json(include_str!("../schemas/v2_7/MDM_T02.json")),
-        ("v2_7", "MDM_T04") => parse_schema_json(include_str!("../schemas/v2_7/MDM_T04.json")),
-        ("v2_7", "DFT_P03") => parse_schema_json(include_str!("../schemas/v2_7/DFT_P03.json")),
-        ("v2_7", "DFT_P11") => parse_schema_json(include_str!("../schemas/v2_7/DFT_P11.json")),
-        ("v2_7", "QRY_A19") => parse_schema_json(include_str!("../schemas/v2_7/QRY_A19.json")),
-        ("v2_7", "QRY_Q01") => parse_schema_json(include_str!("../schemas/v2_7/QRY_Q01.json")),
-        ("v2_7", "QRY_Q02") => parse_schema_json(include_str!("../schemas/v2_7/QRY_Q02.json")),
-        ("v2_7", "BAR_P01") => parse_schema_json(include_str!("../schemas/v2_7/BAR_P01.json")),
-        ("v2_7", "BAR_P02") => parse_schema_json(include_str!("../schemas/v2_7/BAR_P02.json")),
-        ("v2_7", "RDE_O11") => parse_schema_json(include_str!("../schemas/v2_7/RDE_O11.json")),
-        ("v2_7", "RAS_O17") => parse_schema_json(include_str!("../schemas/v2_7/RAS_O17.json")),
-        ("v2_7", "MFN_M01") => parse_schema_json(include_str!("../schemas/v2_7/MFN_M01.json")),
-        ("v2_7", "RDS_O13") => parse_schema_json(include_str!("../schemas/v2_7/RDS_O13.json")),
-        ("v2_7", "RGV_O15") => parse_schema_json(include_str!("../schemas/v2_7/RGV_O15.json")),
-        ("v2_7", "RRD_O14") => parse_schema_json(include_str!("../schemas/v2_7/RRD_O14.json")),
-        ("v2_7", "RRA_O18") => parse_schema_json(include_str!("../schemas/v2_7/RRA_O18.json")),
-        ("v2_7", "OUL_R21") => parse_schema_json(include_str!("../schemas/v2_7/OUL_R21.json")),
-        ("v2_7", "OML_O21") => parse_schema_json(include_str!("../schemas/v2_7/OML_O21.json")),
+        ("v2_7", "ORU_R01") => embedded!("../schemas/v2_7/ORU_R01.json"),
+        ("v2_7", "ORM_O01") => embedded!("../schemas/v2_7/ORM_O01.json"),
+        ("v2_7", "ACK") => embedded!("../schemas/v2_7/ACK.json"),
+        ("v2_7", "SIU_S12") => embedded!("../schemas/v2_7/SIU_S12.json"),
+        ("v2_7", "SIU_S13") => embedded!("../schemas/v2_7/SIU_S13.json"),
+        ("v2_7", "SIU_S14") => embedded!("../schemas/v2_7/SIU_S14.json"),
+        ("v2_7", "SIU_S15") => embedded!("../schemas/v2_7/SIU_S15.json"),
+        ("v2_7", "MDM_T01") => embedded!("../schemas/v2_7/MDM_T01.json"),
+        ("v2_7", "MDM_T02") => embedded!("../schemas/v2_7/MDM_T02.json"),
+        ("v2_7", "MDM_T04") => embedded!("../schemas/v2_7/MDM_T04.json"),
+        ("v2_7", "DFT_P03") => embedded!("../schemas/v2_7/DFT_P03.json"),
+        ("v2_7", "DFT_P11") => embedded!("../schemas/v2_7/DFT_P11.json"),
+        ("v2_7", "QRY_A19") => embedded!("../schemas/v2_7/QRY_A19.json"),
+        ("v2_7", "QRY_Q01") => embedded!("../schemas/v2_7/QRY_Q01.json"),
+        ("v2_7", "QRY_Q02") => embedded!("../schemas/v2_7/QRY_Q02.json"),
+        ("v2_7", "BAR_P01") => embedded!("../schemas/v2_7/BAR_P01.json"),
+        ("v2_7", "BAR_P02") => embedded!("../schemas/v2_7/BAR_P02.json"),
+        ("v2_7", "RDE_O11") => embedded!("../schemas/v2_7/RDE_O11.json"),
+        ("v2_7", "RAS_O17") => embedded!("../schemas/v2_7/RAS_O17.json"),
+        ("v2_7", "MFN_M01") => embedded!("../schemas/v2_7/MFN_M01.json"),
+        ("v2_7", "RDS_O13") => embedded!("../schemas/v2_7/RDS_O13.json"),
+        ("v2_7", "RGV_O15") => embedded!("../schemas/v2_7/RGV_O15.json"),
+        ("v2_7", "RRD_O14") => embedded!("../schemas/v2_7/RRD_O14.json"),
+        ("v2_7", "RRA_O18") => embedded!("../schemas/v2_7/RRA_O18.json"),
+        ("v2_7", "OUL_R21") => embedded!("../schemas/v2_7/OUL_R21.json"),
+        ("v2_7", "OML_O21") => embedded!("../schemas/v2_7/OML_O21.json"),
 
         _ => None,
     }
@@ -0,0 +1,203 @@
+//! Runtime registry of message schemas
+//!
+//! A [`SchemaRegistry`] holds site-specific [`MessageSchema`]s loaded at
+//! runtime, so custom message definitions can be validated without
+//! recompiling the crate. Lookups fall back to the embedded schemas from
+//! [`load_schema`] when a message type is not registered.
+//!
+//! # File Format
+//!
+//! [`SchemaRegistry::from_dir`] reads every `*.json`, `*.yaml` and `*.yml`
+//! file in a directory. Each file holds one serialized [`MessageSchema`], the
+//! same format as the embedded schemas, and is keyed by its `version`,
+//! `message_type` and `trigger_event`:
+//!
+//! ```yaml
+//! message_type: ZMI
+//! trigger_event: Z01
+//! version: "2.5"
+//! description: Site-specific inventory update
+//! segments:
+//!   MSH:
+//!     name: Message Header
+//!     required: true
+//!     repeating: false
+//!     fields: {}
+//!   ZIN:
+//!     name: Inventory Item
+//!     required: true
+//!     repeating: true
+//!     fields:
+//!       1:
+//!         name: Item Code
+//!         data_type: ST
+//!         required: true
+//!         repeating: false
+//!         max_length: 20
+//! ```
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use rs7_core::Version;
+//! use rs7_validator::{SchemaRegistry, Validator};
+//!
+//! let registry = SchemaRegistry::from_dir("/etc/hl7/schemas")?;
+//! let validator = Validator::new(Version::V2_5).with_schema_registry(registry);
+//! # Ok::<(), rs7_core::Error>(())
+//! ```
+
+use crate::schema_loader::embedded_schema;
+use crate::{load_schema, MessageSchema};
+use rs7_core::{
+    error::{Error, Result},
+    Version,
+};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Message schemas registered at runtime, keyed by (version, type, event)
+#[derive(Debug, Clone, Default)]
+pub struct SchemaRegistry {
+    schemas: HashMap<(String, String, String), MessageSchema>,
+}
+
+impl SchemaRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every `*.json`, `*.yaml` and `*.yml` schema file in `dir`
+    ///
+    /// Other files and subdirectories are ignored. Fails on the first file
+    /// that cannot be read or does not deserialize into a [`MessageSchema`].
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let mut paths = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_file() {
+                paths.push(path);
+            }
+        }
+        // Sort so that duplicate keys resolve the same way on every platform
+        paths.sort();
+
+        let mut registry = Self::new();
+        for path in paths {
+            let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+            let schema = match extension {
+                "json" => {
+                    let content = std::fs::read_to_string(&path)?;
+                    serde_json::from_str(&content).map_err(|e| invalid_schema(&path, e))?
+                }
+                "yaml" | "yml" => {
+                    let content = std::fs::read_to_string(&path)?;
+                    serde_yaml::from_str(&content).map_err(|e| invalid_schema(&path, e))?
+                }
+                _ => continue,
+            };
+            registry.register(schema);
+        }
+        Ok(registry)
+    }
+
+    /// Register a schema, replacing any schema with the same key
+    pub fn register(&mut self, schema: MessageSchema) {
+        let key = (
+            schema.version.clone(),
+            schema.message_type.clone(),
+            schema.trigger_event.clone(),
+        );
+        self.schemas.insert(key, schema);
+    }
+
+    /// Get a registered schema, without falling back to the embedded set
+    pub fn get(&self, version: Version, message_type: &str, trigger_event: &str) -> Option<&MessageSchema> {
+        self.schemas.get(&(
+            version.as_str().to_string(),
+            message_type.to_string(),
+            trigger_event.to_string(),
+        ))
+    }
+
+    /// Get a schema, falling back to the embedded schemas if not registered
+    pub fn load(&self, version: Version, message_type: &str, trigger_event: &str) -> Result<MessageSchema> {
+        match self.get(version, message_type, trigger_event) {
+            Some(schema) => Ok(schema.clone()),
+            None => load_schema(version, message_type, trigger_event),
+        }
+    }
+
+    /// Borrow a schema, falling back to the embedded schemas if not registered
+    ///
+    /// Like [`SchemaRegistry::load`] without cloning; embedded schemas are
+    /// parsed on first use only.
+    pub fn resolve(&self, version: Version, message_type: &str, trigger_event: &str) -> Option<&MessageSchema> {
+        self.get(version, message_type, trigger_event)
+            .or_else(|| embedded_schema(version, message_type, trigger_event))
+    }
+
+    /// Number of registered schemas
+    pub fn len(&self) -> usize {
+        self.schemas.len()
+    }
+
+    /// Check if no schemas are registered
+    pub fn is_empty(&self) -> bool {
+        self.schemas.is_empty()
+    }
+}
+
+fn invalid_schema(path: &Path, error: impl std::fmt::Display) -> Error {
+    Error::Validation(format!("Invalid schema file {}: {}", path.display(), error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_dir_with_fallback() {
+        let dir = std::env::temp_dir().join(format!("rs7-schema-registry-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("ZMI_Z01.yaml"),
+            "message_type: ZMI\ntrigger_event: Z01\nversion: \"2.5\"\nsegments:\n  ZIN:\n    name: Item\n    required: true\n    repeating: false\n    fields:\n      1: {name: Code, data_type: ST, required: true, repeating: false, max_length: 20}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("ZMI_Z02.json"),
+            r#"{"message_type":"ZMI","trigger_event":"Z02","version":"2.5","description":"Custom","segments":{}}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("README.txt"), "not a schema").unwrap();
+
+        let registry = SchemaRegistry::from_dir(&dir).unwrap();
+        assert_eq!(registry.len(), 2);
+        let schema = registry.get(Version::V2_5, "ZMI", "Z01").unwrap();
+        assert_eq!(schema.segments["ZIN"].fields[&1].max_length, Some(20));
+        assert!(registry.get(Version::V2_4, "ZMI", "Z01").is_none());
+        assert_eq!(registry.load(Version::V2_5, "ZMI", "Z02").unwrap().trigger_event, "Z02");
+
+        // Unregistered types come from the embedded set
+        assert!(registry.get(Version::V2_5, "ADT", "A01").is_none());
+        assert_eq!(registry.load(Version::V2_5, "ADT", "A01").unwrap().message_type, "ADT");
+        assert!(registry.load(Version::V2_5, "ZMI", "Z99").is_err());
+
+        // Embedded schemas are parsed once and then shared
+        let embedded = registry.resolve(Version::V2_5, "ADT", "A01").unwrap();
+        assert!(std::ptr::eq(embedded, registry.resolve(Version::V2_5, "ADT", "A01").unwrap()));
+        assert!(std::ptr::eq(
+            registry.resolve(Version::V2_5, "ZMI", "Z01").unwrap(),
+            registry.get(Version::V2_5, "ZMI", "Z01").unwrap()
+        ));
+        assert!(registry.resolve(Version::V2_5, "ZMI", "Z99").is_none());
+
+        std::fs::write(dir.join("broken.json"), "{").unwrap();
+        let err = SchemaRegistry::from_dir(&dir).unwrap_err();
+        assert!(err.to_string().contains("broken.json"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -4,8 +4,10 @@
 //! HL7 defines numerous tables for coded values like gender, admission type,
 //! patient class, etc.
 
+use rs7_core::error::{Error, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
 
 /// Result of vocabulary validation
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -35,6 +37,9 @@ pub struct Hl7Table {
     pub name: String,
     pub description: String,
     pub values: HashMap<String, TableValue>,
+    /// Report codes missing from this table as warnings instead of errors
+    #[serde(default)]
+    pub suggest_only: bool,
 }
 
 /// A value within an HL7 table
@@ -53,9 +58,16 @@ impl Hl7Table {
             name: name.to_string(),
             description: description.to_string(),
             values: HashMap::new(),
+            suggest_only: false,
         }
     }
 
+    /// Mark the table as "suggest only": the validator reports codes that
+    /// fail validation against it as warnings rather than errors
+    pub fn set_suggest_only(&mut self, suggest_only: bool) {
+        self.suggest_only = suggest_only;
+    }
+
     /// Add a value to the table
     pub fn add_value(&mut self, code: &str, description: &str, deprecated: bool) {
         self.values.insert(
@@ -136,11 +148,106 @@ impl TableRegistry {
         self.tables.get(table_id)
     }
 
-    /// Add a custom table
+    /// Add a custom table, replacing any table with the same ID
     pub fn add_table(&mut self, table: Hl7Table) {
         self.tables.insert(table.table_id.clone(), table);
     }
 
+    /// Load a table from CSV with `code,description` rows
+    ///
+    /// Blank lines are skipped, as is a leading `code,description` header.
+    /// Values may be wrapped in double quotes; only the first comma separates
+    /// the code from its description. An existing table with the same ID,
+    /// including a built-in one, is replaced.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rs7_validator::TableRegistry;
+    ///
+    /// let csv = "code,description\nICU,Intensive Care Unit\nER,\"Emergency, main\"\n";
+    /// let mut registry = TableRegistry::new();
+    /// registry.load_from_csv("L001", csv.as_bytes()).unwrap();
+    /// assert!(registry.validate("L001", "ICU").is_valid());
+    /// ```
+    pub fn load_from_csv<R: Read>(&mut self, table_id: &str, reader: R) -> Result<()> {
+        let mut table = Hl7Table::new(table_id, table_id, "Loaded from CSV");
+
+        for (line_idx, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (code, description) = line.split_once(',').ok_or_else(|| {
+                Error::Validation(format!(
+                    "Invalid row {} for table {}: expected 'code,description'",
+                    line_idx + 1,
+                    table_id
+                ))
+            })?;
+            let code = unquote_csv(code);
+            let description = unquote_csv(description);
+
+            if line_idx == 0 && code.eq_ignore_ascii_case("code") {
+                continue;
+            }
+            table.add_value(code, description, false);
+        }
+
+        self.add_table(table);
+        Ok(())
+    }
+
+    /// Load a table from a JSON object mapping codes to descriptions
+    ///
+    /// An existing table with the same ID, including a built-in one, is
+    /// replaced.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rs7_validator::TableRegistry;
+    ///
+    /// let json = r#"{"GLU": "Glucose", "NA": "Sodium"}"#;
+    /// let mut registry = TableRegistry::new();
+    /// registry.load_from_json("L002", json.as_bytes()).unwrap();
+    /// assert!(registry.validate("L002", "NA").is_valid());
+    /// assert!(!registry.validate("L002", "K").is_valid());
+    /// ```
+    pub fn load_from_json<R: Read>(&mut self, table_id: &str, reader: R) -> Result<()> {
+        let values: HashMap<String, String> = serde_json::from_reader(reader).map_err(|e| {
+            Error::Validation(format!("Invalid JSON for table {}: {}", table_id, e))
+        })?;
+
+        let mut table = Hl7Table::new(table_id, table_id, "Loaded from JSON");
+        for (code, description) in &values {
+            table.add_value(code, description, false);
+        }
+
+        self.add_table(table);
+        Ok(())
+    }
+
+    /// Mark a loaded table as "suggest only"
+    ///
+    /// Returns `false` if no table with that ID is registered.
+    pub fn set_suggest_only(&mut self, table_id: &str, suggest_only: bool) -> bool {
+        match self.tables.get_mut(table_id) {
+            Some(table) => {
+                table.set_suggest_only(suggest_only);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check whether a table reports misses as warnings
+    pub fn is_suggest_only(&self, table_id: &str) -> bool {
+        self.tables.get(table_id).is_some_and(|t| t.suggest_only)
+    }
+
     /// Validate a code against a specific table
     pub fn validate(&self, table_id: &str, code: &str) -> VocabularyValidation {
         match self.get_table(table_id) {
@@ -376,6 +483,15 @@ impl TableRegistry {
     }
 }
 
+/// Trim a CSV value and strip surrounding double quotes
+fn unquote_csv(value: &str) -> &str {
+    let value = value.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
 impl Default for TableRegistry {
     fn default() -> Self {
         Self::new()
@@ -458,4 +574,45 @@ mod tests {
         assert!(registry.validate("9000", "B").is_valid());
         assert!(!registry.validate("9000", "C").is_valid());
     }
+
+    #[test]
+    fn test_load_from_csv() {
+        let mut registry = TableRegistry::new();
+        let csv = "code,description\n\n3WEST,\"Ward 3, West\"\nICU,Intensive care\n";
+        registry.load_from_csv("L001", csv.as_bytes()).unwrap();
+
+        let table = registry.get_table("L001").unwrap();
+        assert_eq!(table.values.len(), 2);
+        assert_eq!(table.values["3WEST"].description, "Ward 3, West");
+        assert!(registry.validate("L001", "ICU").is_valid());
+        assert!(!registry.validate("L001", "code").is_valid());
+
+        // Reloading replaces the table
+        registry.load_from_csv("L001", "ER,Emergency".as_bytes()).unwrap();
+        assert!(registry.validate("L001", "ER").is_valid());
+        assert!(!registry.validate("L001", "ICU").is_valid());
+
+        assert!(registry.load_from_csv("L002", "no separator".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_load_from_json_replaces_builtin() {
+        let mut registry = TableRegistry::new();
+        registry.load_from_json("0001", r#"{"X": "Site-specific"}"#.as_bytes()).unwrap();
+
+        assert!(registry.validate("0001", "X").is_valid());
+        assert!(!registry.validate("0001", "M").is_valid());
+        assert!(registry.load_from_json("0002", "[1, 2]".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_suggest_only() {
+        let mut registry = TableRegistry::new();
+        assert!(!registry.set_suggest_only("L001", true));
+
+        registry.load_from_csv("L001", "ICU,Intensive care".as_bytes()).unwrap();
+        assert!(registry.set_suggest_only("L001", true));
+        assert!(registry.is_suggest_only("L001"));
+        assert!(!registry.is_suggest_only("0001"));
+    }
 }
@@ -138,6 +138,36 @@ export function getTerserValue(message: WasmMessage, path: string): string | und
  */
 export function setTerserValue(message: WasmMessage, path: string, value: string): void;
 
+/**
+ * Set a field value and return the re-encoded message
+ *
+ * @param message - The message to modify
+ * @param path - The Terser path (e.g., "PID-5-1")
+ * @param value - The value to set
+ * @returns The updated message as an HL7 string
+ * @throws Error if the path is invalid
+ *
+ * @example
+ * ```typescript
+ * const hl7 = setTerserValueAndEncode(message, "PID-5-1", "SMITH");
+ * ```
+ */
+export function setTerserValueAndEncode(message: WasmMessage, path: string, value: string): string;
+
+/**
+ * Get the value at a Terser path from every repetition
+ *
+ * @param message - The parsed message
+ * @param path - The Terser path (e.g., "PID-3-1")
+ * @returns One value per repetition (empty string when a repetition lacks it)
+ *
+ * @example
+ * ```typescript
+ * getAllTerserValues(message, "PID-3-1"); // ["111", "", "333"]
+ * ```
+ */
+export function getAllTerserValues(message: WasmMessage, path: string): string[];
+
 /**
  * Get multiple values at once using Terser paths
  *
@@ -158,6 +188,36 @@ export function setTerserValue(message: WasmMessage, path: string, value: string
  */
 export function getTerserValues(message: WasmMessage, paths: string[]): Record<string, string | null>;
 
+/**
+ * Get a summary of every segment in a message
+ *
+ * @param message - The message to inspect
+ * @returns One entry per segment, in message order
+ *
+ * @example
+ * ```typescript
+ * getSegmentSummary(message);
+ * // [{ id: "MSH", index: 0, fieldCount: 12 }, { id: "PID", index: 1, fieldCount: 8 }]
+ * ```
+ */
+export function getSegmentSummary(message: WasmMessage): SegmentSummary[];
+
+/**
+ * Get the fields of one segment as encoded strings
+ *
+ * @param message - The message to inspect
+ * @param index - 0-based segment position
+ * @returns One string per field, starting at field 1
+ * @throws Error if the index is out of range
+ *
+ * @example
+ * ```typescript
+ * getSegment(message, 1);
+ * // ["1", "", "MRN123", "", "DOE^JOHN", "", "19800101", "M"]
+ * ```
+ */
+export function getSegment(message: WasmMessage, index: number): string[];
+
 /**
  * Validate a message against HL7 standards
  *
@@ -242,6 +302,13 @@ export interface SegmentJson {
   fields: string[];
 }
 
+export interface SegmentSummary {
+  id: string;
+  /** 0-based segment position, as accepted by getSegment */
+  index: number;
+  fieldCount: number;
+}
+
 export interface ValidationResultJson {
   is_valid: boolean;
   errors: ValidationError[];
@@ -147,6 +147,60 @@ pub fn set_terser_value(message: &mut WasmMessage, path: &str, value: &str) -> R
         .map_err(|e| JsValue::from_str(&e.to_string()))
 }
 
+/// Get the value at a Terser path from every repetition
+///
+/// Named `getAllTerserValues` because `getTerserValues` already fetches
+/// several paths at once.
+///
+/// # Arguments
+///
+/// * `message` - The parsed message
+/// * `path` - The Terser path (e.g., "PID-3-1")
+///
+/// # Returns
+///
+/// One value per repetition, with empty strings for repetitions that lack it
+///
+/// # Example
+///
+/// ```javascript
+/// const ids = getAllTerserValues(message, "PID-3-1"); // ["111", "", "333"]
+/// ```
+#[wasm_bindgen(js_name = getAllTerserValues)]
+pub fn get_all_terser_values(message: &WasmMessage, path: &str) -> Result<Vec<String>, JsValue> {
+    let terser = Terser::new(message.inner());
+    terser.get_all(path)
+        .map(|values| values.into_iter().map(String::from).collect())
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Set a field value and return the re-encoded message
+///
+/// # Arguments
+///
+/// * `message` - The parsed message (will be modified)
+/// * `path` - The Terser path (e.g., "PID-5-1")
+/// * `value` - The value to set
+///
+/// # Returns
+///
+/// The HL7 string of the updated message
+///
+/// # Example
+///
+/// ```javascript
+/// const hl7 = setTerserValueAndEncode(message, "PID-5-1", "SMITH");
+/// ```
+#[wasm_bindgen(js_name = setTerserValueAndEncode)]
+pub fn set_terser_value_and_encode(
+    message: &mut WasmMessage,
+    path: &str,
+    value: &str,
+) -> Result<String, JsValue> {
+    set_terser_value(message, path, value)?;
+    Ok(message.encode())
+}
+
 /// Validate a message against HL7 standards
 ///
 /// # Arguments
@@ -226,4 +280,29 @@ mod tests {
         let value = get_terser_value(&msg, "PID-5").unwrap();
         assert_eq!(value, Some("DOE^JOHN".to_string()));
     }
+
+    #[wasm_bindgen_test]
+    fn test_terser_get_all_and_set_encode() {
+        let hl7 = "MSH|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315||ADT^A01|12345|P|2.5\r\
+                   PID|1||111^^^MRN~~333^^^SSN||DOE^JOHN||19800101|M";
+
+        let mut msg = parse_message(hl7).unwrap();
+        let ids = get_all_terser_values(&msg, "PID-3-1").unwrap();
+        assert_eq!(ids, vec!["111", "", "333"]);
+
+        let encoded = set_terser_value_and_encode(&mut msg, "PID-5-1", "SMITH").unwrap();
+        assert!(encoded.contains("|SMITH^JOHN|"));
+        assert!(set_terser_value_and_encode(&mut msg, "PID", "X").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_segment_structure() {
+        let hl7 = "MSH|^~\\&|SendApp|SendFac|RecApp|RecFac|20240315||ADT^A01|12345|P|2.5\r\
+                   PID|1||MRN123||DOE^JOHN||19800101|M";
+
+        let msg = parse_message(hl7).unwrap();
+        assert!(get_segment_summary(&msg).unwrap().is_array());
+        assert!(get_segment(&msg, 1).unwrap().is_array());
+        assert!(get_segment(&msg, 2).is_err());
+    }
 }
@@ -139,3 +139,72 @@ impl WasmMessage {
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 }
+
+/// Summary of one segment, as returned by [`get_segment_summary`]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SegmentSummary<'a> {
+    id: &'a str,
+    index: usize,
+    field_count: usize,
+}
+
+/// List every segment of a message with its position and field count
+///
+/// # Arguments
+///
+/// * `message` - The message to inspect
+///
+/// # Returns
+///
+/// An array of `{ id, index, fieldCount }` objects in message order; `index`
+/// is the 0-based segment position accepted by `getSegment`
+#[wasm_bindgen(js_name = getSegmentSummary)]
+pub fn get_segment_summary(message: &WasmMessage) -> Result<JsValue, JsValue> {
+    let summary: Vec<SegmentSummary> = message
+        .inner()
+        .segments
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| SegmentSummary {
+            id: &segment.id,
+            index,
+            field_count: segment.fields.len(),
+        })
+        .collect();
+
+    serde_wasm_bindgen::to_value(&summary)
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Get the fields of one segment as encoded strings
+///
+/// # Arguments
+///
+/// * `message` - The message to inspect
+/// * `index` - 0-based segment position (see `getSegmentSummary`)
+///
+/// # Returns
+///
+/// An array with one encoded string per field, starting at field 1, or an
+/// error if `index` is out of range
+#[wasm_bindgen(js_name = getSegment)]
+pub fn get_segment(message: &WasmMessage, index: usize) -> Result<JsValue, JsValue> {
+    let inner = message.inner();
+    let segment = inner.get_segment(index).ok_or_else(|| {
+        JsValue::from_str(&format!(
+            "Segment index {} out of range ({} segments)",
+            index,
+            inner.segments.len()
+        ))
+    })?;
+
+    let fields: Vec<String> = segment
+        .fields
+        .iter()
+        .map(|f| f.encode(&inner.delimiters))
+        .collect();
+
+    serde_wasm_bindgen::to_value(&fields)
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
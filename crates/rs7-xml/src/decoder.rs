@@ -3,13 +3,21 @@
 //! Decodes HL7 XML format into rs7_core::Message structures.
 
 use crate::error::{XmlError, XmlResult};
-use quick_xml::escape::unescape;
-use quick_xml::events::Event;
+use quick_xml::escape::{resolve_predefined_entity, unescape};
+use quick_xml::events::{BytesStart, Event};
 use quick_xml::Reader;
-use rs7_core::field::{Component, Field, Repetition};
+use rs7_core::field::{Component, Field, Repetition, SubComponent};
 use rs7_core::message::Message;
 use rs7_core::segment::Segment;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+
+/// Batch envelope segments that are not part of any message
+const BATCH_SEGMENTS: [&str; 4] = ["FHS", "BHS", "BTS", "FTS"];
+
+/// Element representing an escape sequence inside text, e.g. `<escape V="H"/>`
+const ESCAPE_ELEMENT: &str = "escape";
 
 /// Configuration for XML decoding
 #[derive(Debug, Clone)]
@@ -54,151 +62,238 @@ impl XmlDecoder {
 
     /// Decode XML into a Message
     pub fn decode(&self, xml: &str) -> XmlResult<Message> {
-        let mut reader = Reader::from_str(xml);
-        reader.config_mut().trim_text(self.config.strip_whitespace);
-
-        let mut message = Message::new();
-        let mut current_segment: Option<Segment> = None;
-        let mut current_field_num: Option<usize> = None;
-        let mut current_component_num: Option<usize> = None;
-        let mut _current_subcomponent_num: Option<usize> = None;
-        let mut element_stack: Vec<String> = Vec::new();
-        let mut pending_fields: HashMap<usize, Field> = HashMap::new();
-        let mut pending_components: HashMap<(usize, usize), String> = HashMap::new();
-        let mut text_buffer = String::new();
-        let mut depth = 0;
+        self.decode_reader(xml.as_bytes())
+    }
 
-        loop {
-            match reader.read_event() {
-                Ok(Event::Start(e)) => {
-                    depth += 1;
-                    if depth > self.config.max_depth {
-                        return Err(XmlError::InvalidStructure(format!(
-                            "XML nesting depth exceeds maximum of {}",
-                            self.config.max_depth
-                        )));
-                    }
+    /// Decode XML from a reader into a Message
+    ///
+    /// The input is consumed with a pull parser, so only the message being
+    /// built is held in memory rather than the whole document.
+    pub fn decode_reader<R: Read>(&self, reader: R) -> XmlResult<Message> {
+        let mut stream = XmlMessageStream::new(self, BufReader::new(reader), false);
+        stream.next().unwrap_or_else(|| Ok(Message::new()))
+    }
 
-                    let name =
-                        String::from_utf8_lossy(e.name().as_ref()).to_string();
-                    element_stack.push(name.clone());
-
-                    // Check if this is a segment (3 uppercase letters)
-                    if self.is_segment_name(&name) && depth <= 2 {
-                        // Save previous segment if any
-                        if let Some(mut seg) = current_segment.take() {
-                            self.apply_pending_fields(&mut seg, &pending_fields);
-                            message.add_segment(seg);
-                            pending_fields.clear();
-                            pending_components.clear();
+    /// Decode a batch XML document from a reader, one message at a time
+    ///
+    /// A new message starts at each `MSH` segment, and batch envelope segments
+    /// (`FHS`, `BHS`, `BTS`, `FTS`) are skipped. Memory use stays proportional
+    /// to a single message.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use rs7_xml::XmlDecoder;
+    /// use std::fs::File;
+    ///
+    /// let decoder = XmlDecoder::new();
+    /// for message in decoder.decode_reader_stream(File::open("batch.xml")?) {
+    ///     let message = message?;
+    ///     println!("{} segments", message.segments.len());
+    /// }
+    /// ```
+    pub fn decode_reader_stream<R: Read>(&self, reader: R) -> XmlMessageStream<'_, BufReader<R>> {
+        XmlMessageStream::new(self, BufReader::new(reader), true)
+    }
+
+    /// Apply a single XML event to the decoding state
+    fn handle_event(&self, state: &mut DecodeState, event: Event<'_>) -> XmlResult<()> {
+        match event {
+            Event::Start(e) => {
+                state.depth += 1;
+                if state.depth > self.config.max_depth {
+                    return Err(XmlError::InvalidStructure(format!(
+                        "XML nesting depth exceeds maximum of {}",
+                        self.config.max_depth
+                    )));
+                }
+
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                if name == ESCAPE_ELEMENT {
+                    return self.push_escape(state, &e);
+                }
+
+                if let Some(field_depth) = state.field_depth {
+                    // Children of a field are components and their children
+                    // subcomponents, whatever their names. Numbered names such
+                    // as `XPN.2` or `PID.5.2` give the position; other names
+                    // (e.g. inside `varies` fields like OBX-5) count up in
+                    // document order.
+                    match state.depth - field_depth {
+                        1 => {
+                            let position = element_position(&name).unwrap_or(state.next_component);
+                            state.current_component_num = Some(position);
+                            state.next_component = position + 1;
+                            state.current_subcomponent_num = None;
+                            state.next_subcomponent = 1;
                         }
-                        current_segment = Some(Segment::new(&name));
-                        current_field_num = None;
-                        current_component_num = None;
-                    } else if let Some((seg_id, field_num)) = self.parse_field_name(&name) {
-                        // This is a field element like MSH.3 or PID.5
-                        if let Some(ref seg) = current_segment {
-                            if seg.id == seg_id {
-                                current_field_num = Some(field_num);
-                                current_component_num = None;
-                            }
+                        2 => {
+                            let position =
+                                element_position(&name).unwrap_or(state.next_subcomponent);
+                            state.current_subcomponent_num = Some(position);
+                            state.next_subcomponent = position + 1;
+                        }
+                        // Deeper elements have no place in the HL7 structure,
+                        // so their text joins the enclosing subcomponent
+                        _ => return Ok(()),
+                    }
+                } else if self.is_segment_name(&name) {
+                    // Segments don't nest, so an open element with a segment-like
+                    // name (e.g. an `<ACK>` message root) was only a wrapper
+                    if let Some(seg) = state.current_segment.take() {
+                        if !seg.fields.is_empty() || !state.pending_fields.is_empty() {
+                            self.finish_segment(state, seg);
+                        }
+                        state.pending_fields.clear();
+                        state.pending_components.clear();
+                    }
+                    state.current_segment = Some(Segment::new(&name));
+                } else if let Some((seg_id, field_num)) = self.parse_field_name(&name) {
+                    // This is a field element like MSH.3 or PID.5
+                    if let Some(ref seg) = state.current_segment {
+                        if seg.id == seg_id {
+                            state.current_field_num = Some(field_num);
+                            state.field_depth = Some(state.depth);
+                            state.current_component_num = None;
+                            state.current_subcomponent_num = None;
+                            state.next_component = 1;
                         }
-                    } else if let Some((_, comp_num)) =
-                        self.parse_component_name(&name, current_field_num)
-                    {
-                        // This is a component element
-                        current_component_num = Some(comp_num);
-                        _current_subcomponent_num = None;
-                    } else if let Some(sub_num) =
-                        self.parse_subcomponent_name(&name, current_component_num)
-                    {
-                        // This is a subcomponent element
-                        _current_subcomponent_num = Some(sub_num);
                     }
+                }
 
-                    text_buffer.clear();
+                state.text_buffer.clear();
+            }
+            Event::Empty(e) if e.local_name().as_ref() == ESCAPE_ELEMENT.as_bytes() => {
+                self.push_escape(state, &e)?;
+            }
+            Event::End(e) => {
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                let level = state
+                    .field_depth
+                    .map(|field_depth| state.depth - field_depth);
+                state.depth -= 1;
+                if name == ESCAPE_ELEMENT || level.is_some_and(|level| level > 2) {
+                    return Ok(());
                 }
-                Ok(Event::End(e)) => {
-                    let name =
-                        String::from_utf8_lossy(e.name().as_ref()).to_string();
-
-                    // Process text content if any
-                    if !text_buffer.is_empty() {
-                        if let Some(field_num) = current_field_num {
-                            if let Some(comp_num) = current_component_num {
-                                // Store component value
-                                pending_components
-                                    .insert((field_num, comp_num), text_buffer.clone());
-                            } else {
-                                // Store simple field value
-                                let field = Field::from_value(&text_buffer);
-                                pending_fields.insert(field_num, field);
-                            }
-                        }
+
+                if self.config.strip_whitespace {
+                    let trimmed = state.text_buffer.trim();
+                    if trimmed.len() != state.text_buffer.len() {
+                        state.text_buffer = trimmed.to_string();
                     }
+                }
 
-                    // Handle segment closure
-                    if self.is_segment_name(&name) {
-                        if let Some(mut seg) = current_segment.take() {
-                            self.apply_pending_fields(&mut seg, &pending_fields);
-                            self.apply_pending_components(&mut seg, &pending_components);
-                            message.add_segment(seg);
-                            pending_fields.clear();
-                            pending_components.clear();
+                // Process text content if any
+                if !state.text_buffer.is_empty() {
+                    if let Some(field_num) = state.current_field_num {
+                        if let Some(comp_num) = state.current_component_num {
+                            // Store component or subcomponent value
+                            let sub_num = state.current_subcomponent_num.unwrap_or(1);
+                            state
+                                .pending_components
+                                .insert((field_num, comp_num, sub_num), state.text_buffer.clone());
+                        } else {
+                            // Store simple field value
+                            let field = Field::from_value(&state.text_buffer);
+                            state.pending_fields.insert(field_num, field);
                         }
-                    } else if self.parse_field_name(&name).is_some() {
+                    }
+                }
+
+                match level {
+                    Some(0) => {
                         // Field closed - apply any pending components to this field
-                        if let Some(field_num) = current_field_num {
-                            if !pending_components.is_empty() {
+                        if let Some(field_num) = state.current_field_num {
+                            if state
+                                .pending_components
+                                .keys()
+                                .any(|(f, _, _)| *f == field_num)
+                            {
                                 // Build field from components
-                                let field =
-                                    self.build_field_from_components(&pending_components, field_num);
-                                pending_fields.insert(field_num, field);
+                                let field = self.build_field_from_components(
+                                    &state.pending_components,
+                                    field_num,
+                                );
+                                state.pending_fields.insert(field_num, field);
                                 // Clear only components for this field
-                                pending_components
-                                    .retain(|(f, _), _| *f != field_num);
+                                state
+                                    .pending_components
+                                    .retain(|(f, _, _), _| *f != field_num);
                             }
                         }
-                        current_field_num = None;
-                        current_component_num = None;
-                    } else if self.parse_component_name(&name, current_field_num).is_some() {
-                        current_component_num = None;
+                        state.current_field_num = None;
+                        state.field_depth = None;
+                        state.current_component_num = None;
+                        state.current_subcomponent_num = None;
                     }
-
-                    element_stack.pop();
-                    text_buffer.clear();
-                    depth -= 1;
-                }
-                Ok(Event::Text(e)) => {
-                    let raw = String::from_utf8_lossy(&e);
-                    let text = unescape(&raw).map_err(|e| XmlError::XmlParse(e.to_string()))?;
-                    let trimmed = if self.config.strip_whitespace {
-                        text.trim()
-                    } else {
-                        &text
-                    };
-                    if !trimmed.is_empty() {
-                        text_buffer.push_str(trimmed);
+                    Some(1) => {
+                        state.current_component_num = None;
+                        state.current_subcomponent_num = None;
+                    }
+                    Some(_) => state.current_subcomponent_num = None,
+                    None => {
+                        // Handle segment closure
+                        if self.is_segment_name(&name) {
+                            if let Some(seg) = state.current_segment.take() {
+                                self.finish_segment(state, seg);
+                            }
+                        }
                     }
                 }
-                Ok(Event::CData(e)) => {
-                    let text = String::from_utf8_lossy(&e);
-                    text_buffer.push_str(&text);
+
+                state.text_buffer.clear();
+            }
+            Event::Text(e) => {
+                let raw = String::from_utf8_lossy(&e);
+                let text = unescape(&raw).map_err(|e| XmlError::XmlParse(e.to_string()))?;
+                state.text_buffer.push_str(&decode_control_escapes(&text));
+            }
+            Event::GeneralRef(e) => {
+                // Entity and character references such as `&amp;` or `&#xD;`
+                if let Some(c) = e
+                    .resolve_char_ref()
+                    .map_err(|e| XmlError::XmlParse(e.to_string()))?
+                {
+                    state.text_buffer.push(c);
+                } else {
+                    let name = String::from_utf8_lossy(&e);
+                    match resolve_predefined_entity(&name) {
+                        Some(value) => state.text_buffer.push_str(value),
+                        None if self.config.lenient => {
+                            state.text_buffer.push('&');
+                            state.text_buffer.push_str(&name);
+                            state.text_buffer.push(';');
+                        }
+                        None => {
+                            return Err(XmlError::XmlParse(format!(
+                                "Unknown entity reference: &{};",
+                                name
+                            )))
+                        }
+                    }
                 }
-                Ok(Event::Eof) => break,
-                Ok(_) => {} // Ignore other events
-                Err(e) => return Err(XmlError::XmlParse(e.to_string())),
             }
+            Event::CData(e) => {
+                let text = String::from_utf8_lossy(&e);
+                state.text_buffer.push_str(&text);
+            }
+            _ => {} // Ignore other events
         }
 
-        // Handle any remaining segment
-        if let Some(mut seg) = current_segment.take() {
-            self.apply_pending_fields(&mut seg, &pending_fields);
-            self.apply_pending_components(&mut seg, &pending_components);
-            message.add_segment(seg);
-        }
+        Ok(())
+    }
+
+    /// Apply pending values to a closed segment and add it to the message
+    fn finish_segment(&self, state: &mut DecodeState, mut segment: Segment) {
+        self.apply_pending_fields(&mut segment, &state.pending_fields);
+        self.apply_pending_components(&mut segment, &state.pending_components);
+        state.pending_fields.clear();
+        state.pending_components.clear();
 
-        Ok(message)
+        if state.skip_batch_segments && BATCH_SEGMENTS.contains(&segment.id.as_str()) {
+            return;
+        }
+        state.message.add_segment(segment);
     }
 
     /// Check if a name looks like a segment ID (3 uppercase letters)
@@ -217,39 +312,32 @@ impl XmlDecoder {
         None
     }
 
-    /// Parse a component element name
-    fn parse_component_name(&self, name: &str, field_num: Option<usize>) -> Option<(usize, usize)> {
-        let parts: Vec<&str> = name.split('.').collect();
-
-        // Format: SEG.FIELD.COMPONENT (e.g., PID.5.1)
-        if parts.len() == 3 {
-            if let (Ok(f), Ok(c)) = (parts[1].parse::<usize>(), parts[2].parse::<usize>()) {
-                return Some((f, c));
+    /// Append the escape sequence for an `<escape V="..."/>` element
+    ///
+    /// HL7 XML represents formatting escapes such as `\H\` or `\.br\` as
+    /// elements, which become the ER7 escape sequence in the decoded value.
+    fn push_escape(&self, state: &mut DecodeState, element: &BytesStart<'_>) -> XmlResult<()> {
+        let value = element
+            .try_get_attribute("V")
+            .map_err(|e| XmlError::XmlParse(e.to_string()))?
+            .map(|attr| attr.unescape_value().map(|v| v.into_owned()))
+            .transpose()
+            .map_err(|e| XmlError::XmlParse(e.to_string()))?;
+
+        match value {
+            Some(value) => {
+                state.text_buffer.push('\\');
+                state.text_buffer.push_str(&value);
+                state.text_buffer.push('\\');
             }
-        }
-
-        // Format: DATATYPE.COMPONENT (e.g., XPN.1, MSG.1)
-        if parts.len() == 2 {
-            if let Ok(c) = parts[1].parse::<usize>() {
-                if let Some(fn_num) = field_num {
-                    return Some((fn_num, c));
-                }
+            None if self.config.lenient => {}
+            None => {
+                return Err(XmlError::InvalidStructure(
+                    "escape element without V attribute".to_string(),
+                ))
             }
         }
-
-        None
-    }
-
-    /// Parse a subcomponent element name
-    fn parse_subcomponent_name(&self, name: &str, _comp_num: Option<usize>) -> Option<usize> {
-        let parts: Vec<&str> = name.split('.').collect();
-        // Subcomponents would be SEG.FIELD.COMPONENT.SUBCOMPONENT
-        if parts.len() == 4 {
-            if let Ok(s) = parts[3].parse::<usize>() {
-                return Some(s);
-            }
-        }
-        None
+        Ok(())
     }
 
     /// Apply pending field values to a segment
@@ -263,15 +351,15 @@ impl XmlDecoder {
     fn apply_pending_components(
         &self,
         segment: &mut Segment,
-        components: &HashMap<(usize, usize), String>,
+        components: &HashMap<(usize, usize, usize), String>,
     ) {
         // Group by field number
-        let mut fields: HashMap<usize, Vec<(usize, String)>> = HashMap::new();
-        for (&(field_num, comp_num), value) in components {
+        let mut fields: HashMap<usize, Vec<(usize, usize, String)>> = HashMap::new();
+        for (&(field_num, comp_num, sub_num), value) in components {
             fields
                 .entry(field_num)
                 .or_default()
-                .push((comp_num, value.clone()));
+                .push((comp_num, sub_num, value.clone()));
         }
 
         // Apply each field's components
@@ -281,9 +369,9 @@ impl XmlDecoder {
         }
     }
 
-    /// Build a field from a list of components
-    fn build_field_from_component_list(&self, components: &[(usize, String)]) -> Field {
-        let max_comp = components.iter().map(|(c, _)| *c).max().unwrap_or(0);
+    /// Build a field from a list of (component, subcomponent, value) entries
+    fn build_field_from_component_list(&self, components: &[(usize, usize, String)]) -> Field {
+        let max_comp = components.iter().map(|(c, _, _)| *c).max().unwrap_or(0);
 
         let mut repetition = Repetition::new();
 
@@ -292,11 +380,16 @@ impl XmlDecoder {
             repetition.add_component(Component::new());
         }
 
-        // Set component values
-        for (comp_num, value) in components {
-            if *comp_num > 0 && *comp_num <= repetition.components.len() {
-                repetition.components[*comp_num - 1] = Component::from_value(value);
+        // Set subcomponent values, padding with empty subcomponents
+        for (comp_num, sub_num, value) in components {
+            if *comp_num == 0 || *sub_num == 0 {
+                continue;
             }
+            let component = &mut repetition.components[*comp_num - 1];
+            while component.subcomponents.len() < *sub_num {
+                component.add_subcomponent(SubComponent::new(""));
+            }
+            component.subcomponents[*sub_num - 1] = SubComponent::new(value.as_str());
         }
 
         let mut field = Field::new();
@@ -307,25 +400,174 @@ impl XmlDecoder {
     /// Build a field from pending components
     fn build_field_from_components(
         &self,
-        components: &HashMap<(usize, usize), String>,
+        components: &HashMap<(usize, usize, usize), String>,
         field_num: usize,
     ) -> Field {
-        let field_comps: Vec<(usize, String)> = components
+        let field_comps: Vec<(usize, usize, String)> = components
             .iter()
-            .filter(|((f, _), _)| *f == field_num)
-            .map(|((_, c), v)| (*c, v.clone()))
+            .filter(|((f, _, _), _)| *f == field_num)
+            .map(|((_, c, s), v)| (*c, *s, v.clone()))
             .collect();
 
         self.build_field_from_component_list(&field_comps)
     }
 }
 
+/// Turn HL7 hex escapes for C0 control characters back into the characters
+///
+/// The encoder writes controls that XML 1.0 cannot carry, even as character
+/// references, as `\X01\` and the like. Tab, CR and LF travel as character
+/// references instead, so their hex escapes are left alone, as are longer or
+/// non-control hex escapes.
+fn decode_control_escapes(text: &str) -> Cow<'_, str> {
+    if !text.contains("\\X") {
+        return Cow::Borrowed(text);
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("\\X") {
+        result.push_str(&rest[..start]);
+        let escape = &rest[start..];
+        let control = escape
+            .get(2..4)
+            .filter(|_| escape.as_bytes().get(4) == Some(&b'\\'))
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            .filter(|&b| b < b' ' && !matches!(b, b'\t' | b'\n' | b'\r'));
+        match control {
+            Some(b) => {
+                result.push(char::from(b));
+                rest = &escape[5..];
+            }
+            None => {
+                result.push('\\');
+                rest = &escape[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    Cow::Owned(result)
+}
+
+/// Position given by a numbered element name such as `XPN.2` or `PID.5.2`
+fn element_position(name: &str) -> Option<usize> {
+    let (_, suffix) = name.rsplit_once('.')?;
+    suffix.parse().ok().filter(|&position| position > 0)
+}
+
 impl Default for XmlDecoder {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// In-progress state while decoding a message from XML events
+#[derive(Default)]
+struct DecodeState {
+    message: Message,
+    current_segment: Option<Segment>,
+    current_field_num: Option<usize>,
+    field_depth: Option<usize>,
+    current_component_num: Option<usize>,
+    current_subcomponent_num: Option<usize>,
+    next_component: usize,
+    next_subcomponent: usize,
+    pending_fields: HashMap<usize, Field>,
+    pending_components: HashMap<(usize, usize, usize), String>,
+    text_buffer: String,
+    depth: usize,
+    skip_batch_segments: bool,
+}
+
+/// Iterator over the messages of an XML document, decoded incrementally
+///
+/// Created by [`XmlDecoder::decode_reader_stream`].
+pub struct XmlMessageStream<'a, R: BufRead> {
+    decoder: &'a XmlDecoder,
+    reader: Reader<R>,
+    buf: Vec<u8>,
+    state: DecodeState,
+    split_messages: bool,
+    finished: bool,
+}
+
+impl<'a, R: BufRead> XmlMessageStream<'a, R> {
+    fn new(decoder: &'a XmlDecoder, reader: R, split_messages: bool) -> Self {
+        // Text is trimmed once the element closes rather than per event, since
+        // entity references split text into several events and trimming each
+        // would drop whitespace around escaped characters.
+        let reader = Reader::from_reader(reader);
+        Self {
+            decoder,
+            reader,
+            buf: Vec::new(),
+            state: DecodeState {
+                skip_batch_segments: split_messages,
+                ..Default::default()
+            },
+            split_messages,
+            finished: false,
+        }
+    }
+
+    /// Read events until the next message is complete
+    fn next_message(&mut self) -> XmlResult<Option<Message>> {
+        loop {
+            self.buf.clear();
+            let event = self
+                .reader
+                .read_event_into(&mut self.buf)
+                .map_err(|e| XmlError::XmlParse(e.to_string()))?;
+
+            match event {
+                Event::Eof => {
+                    self.finished = true;
+
+                    // Handle any remaining segment
+                    if let Some(seg) = self.state.current_segment.take() {
+                        self.decoder.finish_segment(&mut self.state, seg);
+                    }
+
+                    let message = std::mem::take(&mut self.state.message);
+                    if self.split_messages && message.segments.is_empty() {
+                        return Ok(None);
+                    }
+                    return Ok(Some(message));
+                }
+                Event::Start(ref e)
+                    if self.split_messages
+                        && e.local_name().as_ref() == b"MSH"
+                        && !self.state.message.segments.is_empty() =>
+                {
+                    // A new MSH completes the previous message
+                    let message = std::mem::take(&mut self.state.message);
+                    self.decoder.handle_event(&mut self.state, event)?;
+                    return Ok(Some(message));
+                }
+                event => self.decoder.handle_event(&mut self.state, event)?,
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for XmlMessageStream<'_, R> {
+    type Item = XmlResult<Message>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        match self.next_message() {
+            Ok(message) => message.map(Ok),
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -412,8 +654,194 @@ mod tests {
         let pid = &message.segments[0];
         assert_eq!(pid.id, "PID");
 
-        // Check field 5 has components
-        let field5 = pid.get_field(5);
-        assert!(field5.is_some());
+        // Data type element names give the component positions
+        let field5 = pid.get_field(5).unwrap();
+        assert_eq!(
+            field5.get_component(0, 0).and_then(|c| c.value()),
+            Some("Smith")
+        );
+        assert_eq!(
+            field5.get_component(0, 1).and_then(|c| c.value()),
+            Some("John")
+        );
+    }
+
+    #[test]
+    fn test_decode_escape_elements() {
+        let xml = r#"
+            <OBX>
+                <OBX.5>Result <escape V="H"/>high<escape V="N"/> <escape V=".br"></escape>end</OBX.5>
+            </OBX>
+        "#;
+
+        let message = XmlDecoder::new().decode(xml).unwrap();
+        assert_eq!(
+            message.segments[0].get_field_value(5),
+            Some("Result \\H\\high\\N\\ \\.br\\end")
+        );
+
+        let strict = XmlDecoder::with_config(XmlDecoderConfig {
+            lenient: false,
+            ..Default::default()
+        });
+        assert!(strict
+            .decode("<OBX><OBX.5>A<escape/></OBX.5></OBX>")
+            .is_err());
+    }
+
+    #[test]
+    fn test_decode_varies_and_subcomponents() {
+        let xml = r#"
+            <ORU_R01>
+                <OBX>
+                    <OBX.2>CE</OBX.2>
+                    <OBX.5><varies>GLU</varies><varies>Glucose</varies><varies>LN</varies></OBX.5>
+                </OBX>
+                <OBX>
+                    <OBX.2>CX</OBX.2>
+                    <OBX.5>
+                        <CX.1>123</CX.1>
+                        <CX.4><HD.1>HOSP</HD.1><HD.2>1.2.3</HD.2></CX.4>
+                    </OBX.5>
+                </OBX>
+            </ORU_R01>
+        "#;
+
+        let message = XmlDecoder::new().decode(xml).unwrap();
+        assert_eq!(message.segments.len(), 2);
+        assert_eq!(
+            message.segments[0].encode(&Default::default()),
+            "OBX||CE|||GLU^Glucose^LN"
+        );
+        assert_eq!(
+            message.segments[1].encode(&Default::default()),
+            "OBX||CX|||123^^^HOSP&1.2.3"
+        );
+    }
+
+    #[test]
+    fn test_decode_prefixed_elements() {
+        let xml = r#"
+            <hl7:ADT_A01 xmlns:hl7="urn:hl7-org:v2xml">
+                <hl7:MSH><hl7:MSH.3>APP</hl7:MSH.3></hl7:MSH>
+                <hl7:PID>
+                    <hl7:PID.5>
+                        <hl7:PID.5.1>Smith</hl7:PID.5.1>
+                        <hl7:PID.5.2>John</hl7:PID.5.2>
+                    </hl7:PID.5>
+                </hl7:PID>
+            </hl7:ADT_A01>
+        "#;
+
+        let message = XmlDecoder::new().decode(xml).unwrap();
+
+        assert_eq!(message.segments.len(), 2);
+        assert_eq!(message.segments[0].id, "MSH");
+        assert_eq!(message.segments[0].get_field_value(3), Some("APP"));
+        assert_eq!(message.segments[1].id, "PID");
+        assert_eq!(
+            message.segments[1]
+                .get_field(5)
+                .and_then(|f| f.get_component(0, 1))
+                .and_then(|c| c.value()),
+            Some("John")
+        );
+    }
+
+    #[test]
+    fn test_decode_reader_matches_decode() {
+        let xml = r#"
+            <ADT_A01>
+                <MSH><MSH.3>APP</MSH.3><MSH.9><MSH.9.1>ADT</MSH.9.1><MSH.9.2>A01</MSH.9.2></MSH.9></MSH>
+                <PID><PID.3>12345</PID.3></PID>
+            </ADT_A01>
+        "#;
+
+        let decoder = XmlDecoder::new();
+        let from_str = decoder.decode(xml).unwrap();
+        let from_reader = decoder.decode_reader(xml.as_bytes()).unwrap();
+
+        assert_eq!(from_str.encode(), from_reader.encode());
+        assert_eq!(from_reader.segments.len(), 2);
+    }
+
+    #[test]
+    fn test_decode_reader_stream_batch() {
+        let xml = r#"
+            <BATCH>
+                <BHS><BHS.3>APP</BHS.3></BHS>
+                <ACK>
+                    <MSH><MSH.10>MSG1</MSH.10></MSH>
+                    <MSA><MSA.1>AA</MSA.1></MSA>
+                </ACK>
+                <ACK>
+                    <MSH><MSH.10>MSG2</MSH.10></MSH>
+                    <MSA><MSA.1>AE</MSA.1></MSA>
+                </ACK>
+                <BTS><BTS.1>2</BTS.1></BTS>
+            </BATCH>
+        "#;
+
+        let decoder = XmlDecoder::new();
+        let messages: Vec<Message> = decoder
+            .decode_reader_stream(xml.as_bytes())
+            .collect::<XmlResult<_>>()
+            .unwrap();
+
+        assert_eq!(messages.len(), 2);
+        for (message, (control_id, ack_code)) in
+            messages.iter().zip([("MSG1", "AA"), ("MSG2", "AE")])
+        {
+            let ids: Vec<&str> = message.segments.iter().map(|s| s.id.as_str()).collect();
+            assert_eq!(ids, vec!["MSH", "MSA"]);
+            assert_eq!(message.segments[0].get_field_value(10), Some(control_id));
+            assert_eq!(message.segments[1].get_field_value(1), Some(ack_code));
+        }
+    }
+
+    #[test]
+    fn test_decode_reader_stream_error() {
+        let xml = "<BATCH><MSH><MSH.3>APP</MSH.3></MSH><MSH><MSH.3>APP</WRONG>";
+
+        let decoder = XmlDecoder::new();
+        let results: Vec<XmlResult<Message>> =
+            decoder.decode_reader_stream(xml.as_bytes()).collect();
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+    }
+
+    #[test]
+    fn test_decode_entity_references() {
+        let xml = r#"<OBX><OBX.5>A &lt; B &amp; &quot;quoted&quot; &#x41;</OBX.5></OBX>"#;
+        let message = XmlDecoder::new().decode(xml).unwrap();
+
+        assert_eq!(
+            message.segments[0].get_field_value(5),
+            Some(r#"A < B & "quoted" A"#)
+        );
+    }
+
+    #[test]
+    fn test_decode_control_escapes() {
+        assert_eq!(decode_control_escapes("plain"), "plain");
+        assert_eq!(decode_control_escapes("A\\X01\\B\\X1F\\"), "A\u{1}B\u{1F}");
+        // Not control escapes: left for the application to interpret
+        assert_eq!(decode_control_escapes("\\X41\\ \\X0D\\ \\X0D0A\\ \\X1"), "\\X41\\ \\X0D\\ \\X0D0A\\ \\X1");
+    }
+
+    #[test]
+    fn test_decode_unknown_entity() {
+        let xml = r#"<OBX><OBX.5>&nbsp;</OBX.5></OBX>"#;
+
+        let message = XmlDecoder::new().decode(xml).unwrap();
+        assert_eq!(message.segments[0].get_field_value(5), Some("&nbsp;"));
+
+        let strict = XmlDecoder::with_config(XmlDecoderConfig {
+            lenient: false,
+            ..Default::default()
+        });
+        assert!(strict.decode(xml).is_err());
     }
 }
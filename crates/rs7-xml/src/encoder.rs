@@ -4,27 +4,40 @@
 
 use crate::error::{XmlError, XmlResult};
 use crate::{HL7_V2_XML_NAMESPACE, XML_DECLARATION};
+use quick_xml::escape::escape;
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Writer;
 use rs7_core::field::{Component, Field, Repetition};
 use rs7_core::message::Message;
 use rs7_core::segment::Segment;
+use std::borrow::Cow;
 use std::io::Cursor;
 
 /// Configuration for XML encoding
 #[derive(Debug, Clone)]
 pub struct XmlEncoderConfig {
     /// Include XML declaration at the start
+    ///
+    /// Turn off to embed the output as a fragment in another document.
     pub include_declaration: bool,
 
     /// Include the HL7 v2 XML namespace
     pub include_namespace: bool,
 
+    /// Namespace prefix for element names (e.g. `hl7` for `<hl7:MSH>`)
+    ///
+    /// When set, every element carries the prefix and the root element
+    /// declares `xmlns:<prefix>` instead of the default namespace.
+    pub namespace_prefix: Option<String>,
+
     /// Pretty print with indentation
     pub pretty_print: bool,
 
-    /// Indentation string (default: "  ")
-    pub indent: String,
+    /// Spaces per nesting level when pretty printing (default: 2)
+    pub indent: usize,
+
+    /// Line ending used when pretty printing (default: LF)
+    pub newline: Newline,
 
     /// Include empty fields as empty elements
     pub include_empty_fields: bool,
@@ -38,14 +51,36 @@ impl Default for XmlEncoderConfig {
         Self {
             include_declaration: true,
             include_namespace: false,
+            namespace_prefix: None,
             pretty_print: false,
-            indent: "  ".to_string(),
+            indent: 2,
+            newline: Newline::Lf,
             include_empty_fields: false,
             escape_text: true,
         }
     }
 }
 
+/// Line ending for pretty-printed XML
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Newline {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`, for consumers that expect Windows line endings
+    CrLf,
+}
+
+impl Newline {
+    /// Get the line ending characters
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Newline::Lf => "\n",
+            Newline::CrLf => "\r\n",
+        }
+    }
+}
+
 /// XML encoder for HL7 v2.x messages
 pub struct XmlEncoder {
     config: XmlEncoderConfig,
@@ -72,25 +107,26 @@ impl XmlEncoder {
         if self.config.include_declaration {
             result.push_str(XML_DECLARATION);
             if self.config.pretty_print {
-                result.push('\n');
+                result.push_str(self.config.newline.as_str());
             }
         }
 
         // Encode the message body
         let mut buffer = Vec::new();
         {
-            let mut writer = if self.config.pretty_print {
-                Writer::new_with_indent(Cursor::new(&mut buffer), b' ', 2)
-            } else {
-                Writer::new(Cursor::new(&mut buffer))
-            };
+            // Indentation is written by hand rather than by quick-xml, which
+            // only supports LF line endings
+            let mut writer = Writer::new(Cursor::new(&mut buffer));
 
             // Determine message type for root element
             let root_name = self.get_message_type_name(message);
 
             // Write root element
-            let mut root = BytesStart::new(&root_name);
-            if self.config.include_namespace {
+            let mut root = BytesStart::new(self.element_name(&root_name));
+            if let Some(ref prefix) = self.config.namespace_prefix {
+                let attr = format!("xmlns:{}", prefix);
+                root.push_attribute((attr.as_str(), HL7_V2_XML_NAMESPACE));
+            } else if self.config.include_namespace {
                 root.push_attribute(("xmlns", HL7_V2_XML_NAMESPACE));
             }
             writer.write_event(Event::Start(root))?;
@@ -101,7 +137,8 @@ impl XmlEncoder {
             }
 
             // Close root element
-            writer.write_event(Event::End(BytesEnd::new(&root_name)))?;
+            self.write_indent(&mut writer, 0)?;
+            writer.write_event(Event::End(BytesEnd::new(self.element_name(&root_name))))?;
         }
 
         // Append the XML body to result
@@ -138,7 +175,10 @@ impl XmlEncoder {
         segment: &Segment,
     ) -> XmlResult<()> {
         let segment_name = &segment.id;
-        writer.write_event(Event::Start(BytesStart::new(segment_name)))?;
+        self.write_indent(writer, 1)?;
+        writer.write_event(Event::Start(BytesStart::new(
+            self.element_name(segment_name),
+        )))?;
 
         // Write each field
         for (idx, field) in segment.fields.iter().enumerate() {
@@ -152,7 +192,8 @@ impl XmlEncoder {
             self.write_field(writer, segment_name, field_num, field)?;
         }
 
-        writer.write_event(Event::End(BytesEnd::new(segment_name)))?;
+        self.write_indent(writer, 1)?;
+        writer.write_event(Event::End(BytesEnd::new(self.element_name(segment_name))))?;
         Ok(())
     }
 
@@ -196,14 +237,13 @@ impl XmlEncoder {
         if repetition.components.len() == 1 {
             if let Some(value) = repetition.components[0].value() {
                 if !value.is_empty() {
-                    writer.write_event(Event::Start(BytesStart::new(field_name)))?;
-                    writer.write_event(Event::Text(BytesText::new(value)))?;
-                    writer.write_event(Event::End(BytesEnd::new(field_name)))?;
+                    self.write_text_element(writer, field_name, value, 2)?;
                 }
             }
         } else if repetition.components.len() > 1 {
             // Multiple components - wrap in field element with component children
-            writer.write_event(Event::Start(BytesStart::new(field_name)))?;
+            self.write_indent(writer, 2)?;
+            writer.write_event(Event::Start(BytesStart::new(self.element_name(field_name))))?;
 
             for (comp_idx, component) in repetition.components.iter().enumerate() {
                 if component.is_empty() && !self.config.include_empty_fields {
@@ -213,7 +253,8 @@ impl XmlEncoder {
                 self.write_component(writer, field_name, comp_idx + 1, component)?;
             }
 
-            writer.write_event(Event::End(BytesEnd::new(field_name)))?;
+            self.write_indent(writer, 2)?;
+            writer.write_event(Event::End(BytesEnd::new(self.element_name(field_name))))?;
         }
 
         Ok(())
@@ -235,14 +276,13 @@ impl XmlEncoder {
             // Simple component with value
             if let Some(value) = component.value() {
                 if !value.is_empty() {
-                    writer.write_event(Event::Start(BytesStart::new(&comp_name)))?;
-                    writer.write_event(Event::Text(BytesText::new(value)))?;
-                    writer.write_event(Event::End(BytesEnd::new(&comp_name)))?;
+                    self.write_text_element(writer, &comp_name, value, 3)?;
                 }
             }
         } else if component.subcomponents.len() > 1 {
             // Component with subcomponents
-            writer.write_event(Event::Start(BytesStart::new(&comp_name)))?;
+            self.write_indent(writer, 3)?;
+            writer.write_event(Event::Start(BytesStart::new(self.element_name(&comp_name))))?;
 
             for (sub_idx, subcomp) in component.subcomponents.iter().enumerate() {
                 if subcomp.is_empty() && !self.config.include_empty_fields {
@@ -250,17 +290,60 @@ impl XmlEncoder {
                 }
 
                 let sub_name = format!("{}.{}", comp_name, sub_idx + 1);
-                writer.write_event(Event::Start(BytesStart::new(&sub_name)))?;
-                writer.write_event(Event::Text(BytesText::new(&subcomp.value)))?;
-                writer.write_event(Event::End(BytesEnd::new(&sub_name)))?;
+                self.write_text_element(writer, &sub_name, &subcomp.value, 4)?;
             }
 
-            writer.write_event(Event::End(BytesEnd::new(&comp_name)))?;
+            self.write_indent(writer, 3)?;
+            writer.write_event(Event::End(BytesEnd::new(self.element_name(&comp_name))))?;
         }
 
         Ok(())
     }
 
+    /// Write an element containing only a text value
+    fn write_text_element<W: std::io::Write>(
+        &self,
+        writer: &mut Writer<W>,
+        name: &str,
+        value: &str,
+        depth: usize,
+    ) -> XmlResult<()> {
+        let text = if self.config.escape_text {
+            BytesText::from_escaped(escape_text(value))
+        } else {
+            BytesText::from_escaped(value)
+        };
+
+        self.write_indent(writer, depth)?;
+        writer.write_event(Event::Start(BytesStart::new(self.element_name(name))))?;
+        writer.write_event(Event::Text(text))?;
+        writer.write_event(Event::End(BytesEnd::new(self.element_name(name))))?;
+        Ok(())
+    }
+
+    /// Start a new line indented to `depth` when pretty printing
+    fn write_indent<W: std::io::Write>(
+        &self,
+        writer: &mut Writer<W>,
+        depth: usize,
+    ) -> XmlResult<()> {
+        if self.config.pretty_print {
+            let indent = " ".repeat(self.config.indent * depth);
+            writer
+                .get_mut()
+                .write_all(format!("{}{}", self.config.newline.as_str(), indent).as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Apply the configured namespace prefix to an element name
+    fn element_name<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        match self.config.namespace_prefix {
+            Some(ref prefix) => Cow::Owned(format!("{}:{}", prefix, name)),
+            None => Cow::Borrowed(name),
+        }
+    }
+
     /// Get the element name for a component
     fn get_component_element_name(&self, field_name: &str, comp_num: usize) -> String {
         // Standard HL7 XML uses data type names for components
@@ -269,6 +352,32 @@ impl XmlEncoder {
     }
 }
 
+/// Escape a value for use as XML text content
+///
+/// Markup characters (`&`, `<`, `>`, `"`, `'`) become predefined entities.
+/// Tab, CR, LF and the other control characters XML 1.0 allows become
+/// numeric character references so they survive parsing and whitespace
+/// normalization. The remaining C0 controls are not allowed in XML 1.0, even
+/// as references, so they become HL7 hex escapes such as `\X01\`.
+fn escape_text(value: &str) -> Cow<'_, str> {
+    let escaped = escape(value);
+    if !escaped.chars().any(|c| c.is_control()) {
+        return escaped;
+    }
+
+    let mut result = String::with_capacity(escaped.len() + 8);
+    for c in escaped.chars() {
+        if c < ' ' && !matches!(c, '\t' | '\n' | '\r') {
+            result.push_str(&format!("\\X{:02X}\\", c as u32));
+        } else if c.is_control() {
+            result.push_str(&format!("&#x{:X};", c as u32));
+        } else {
+            result.push(c);
+        }
+    }
+    Cow::Owned(result)
+}
+
 impl Default for XmlEncoder {
     fn default() -> Self {
         Self::new()
@@ -284,7 +393,49 @@ mod tests {
         let config = XmlEncoderConfig::default();
         assert!(config.include_declaration);
         assert!(!config.include_namespace);
+        assert!(config.namespace_prefix.is_none());
         assert!(!config.pretty_print);
+        assert_eq!(config.indent, 2);
+        assert_eq!(config.newline, Newline::Lf);
+    }
+
+    #[test]
+    fn test_pretty_print_format() {
+        let mut message = Message::new();
+        let mut pid = Segment::new("PID");
+        let _ = pid.set_field_value(1, "1");
+        let _ = pid.set_component(5, 0, 0, "DOE");
+        let _ = pid.set_component(5, 0, 1, "JOHN");
+        message.add_segment(pid);
+
+        let xml = XmlEncoder::with_config(XmlEncoderConfig {
+            pretty_print: true,
+            ..Default::default()
+        })
+        .encode(&message)
+        .unwrap();
+        assert_eq!(
+            xml,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<HL7Message>\n  <PID>\n    <PID.1>1</PID.1>\n    <PID.5>\n      <PID.5.1>DOE</PID.5.1>\n      <PID.5.2>JOHN</PID.5.2>\n    </PID.5>\n  </PID>\n</HL7Message>"
+        );
+
+        let fragment = XmlEncoder::with_config(XmlEncoderConfig {
+            include_declaration: false,
+            pretty_print: true,
+            indent: 4,
+            newline: Newline::CrLf,
+            ..Default::default()
+        })
+        .encode(&message)
+        .unwrap();
+        assert_eq!(
+            fragment,
+            xml.split_once('\n')
+                .unwrap()
+                .1
+                .replace('\n', "\r\n")
+                .replace("  ", "    ")
+        );
     }
 
     #[test]
@@ -313,4 +464,45 @@ mod tests {
         assert!(xml.contains("APP"));
         assert!(xml.contains("</MSH>"));
     }
+
+    #[test]
+    fn test_encode_with_namespace_prefix() {
+        let mut message = Message::new();
+        let mut msh = Segment::new("MSH");
+        let _ = msh.set_field_value(3, "APP");
+        let _ = msh.set_field_value(9, "ADT^A01");
+        message.add_segment(msh);
+
+        let encoder = XmlEncoder::with_config(XmlEncoderConfig {
+            namespace_prefix: Some("hl7".to_string()),
+            ..Default::default()
+        });
+        let xml = encoder.encode(&message).unwrap();
+
+        assert!(xml.contains(r#"<hl7:ADT_A01 xmlns:hl7="urn:hl7-org:v2xml">"#));
+        assert!(xml.contains("<hl7:MSH><hl7:MSH.3>APP</hl7:MSH.3>"));
+        assert!(xml.contains("</hl7:MSH></hl7:ADT_A01>"));
+    }
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("plain"), "plain");
+        assert_eq!(
+            escape_text(r#"A < B & "quoted" 'x' >"#),
+            "A &lt; B &amp; &quot;quoted&quot; &apos;x&apos; &gt;"
+        );
+        assert_eq!(escape_text("line1\rline2\t"), "line1&#xD;line2&#x9;");
+        assert_eq!(escape_text("A\u{1}B\u{1F}\u{7F}"), "A\\X01\\B\\X1F\\&#x7F;");
+    }
+
+    #[test]
+    fn test_encode_escapes_field_values() {
+        let mut message = Message::new();
+        let mut obx = Segment::new("OBX");
+        let _ = obx.set_field_value(5, "A < B & C");
+        message.add_segment(obx);
+
+        let xml = XmlEncoder::new().encode(&message).unwrap();
+        assert!(xml.contains("<OBX.5>A &lt; B &amp; C</OBX.5>"));
+    }
 }
@@ -40,13 +40,25 @@
 //! let decoder = XmlDecoder::new();
 //! let message = decoder.decode(xml)?;
 //! ```
+//!
+//! ## Streaming a Batch of Messages
+//!
+//! ```ignore
+//! use rs7_xml::XmlDecoder;
+//! use std::fs::File;
+//!
+//! let decoder = XmlDecoder::new();
+//! for message in decoder.decode_reader_stream(File::open("batch.xml")?) {
+//!     let message = message?;
+//! }
+//! ```
 
 mod decoder;
 mod encoder;
 mod error;
 
-pub use decoder::{XmlDecoder, XmlDecoderConfig};
-pub use encoder::{XmlEncoder, XmlEncoderConfig};
+pub use decoder::{XmlDecoder, XmlDecoderConfig, XmlMessageStream};
+pub use encoder::{Newline, XmlEncoder, XmlEncoderConfig};
 pub use error::{XmlError, XmlResult};
 
 /// The HL7 v2 XML namespace
@@ -218,4 +230,51 @@ mod tests {
             assert_eq!(orig.id, dec.id);
         }
     }
+
+    #[test]
+    fn test_roundtrip_special_characters() {
+        let mut message = Message::new();
+        let mut obx = Segment::new("OBX");
+        let _ = obx.set_field_value(5, r#"A < B & "quoted" 'text' > C"#);
+        let _ = obx.set_component(3, 0, 1, "x&y");
+        let _ = obx.set_field_value(7, "line1\rline2");
+        message.add_segment(obx);
+
+        for pretty_print in [false, true] {
+            let encoder = XmlEncoder::with_config(XmlEncoderConfig {
+                pretty_print,
+                ..Default::default()
+            });
+            let xml = encoder.encode(&message).unwrap();
+            assert!(xml.contains("A &lt; B &amp; &quot;quoted&quot;"));
+
+            let decoded = XmlDecoder::new().decode(&xml).unwrap();
+            let obx = &decoded.segments[0];
+            assert_eq!(
+                obx.get_field_value(5),
+                Some(r#"A < B & "quoted" 'text' > C"#)
+            );
+            assert_eq!(
+                obx.get_field(3)
+                    .and_then(|f| f.get_component(0, 1))
+                    .and_then(|c| c.value()),
+                Some("x&y")
+            );
+            assert_eq!(obx.get_field_value(7), Some("line1\rline2"));
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_control_characters() {
+        let mut message = Message::new();
+        let mut obx = Segment::new("OBX");
+        let _ = obx.set_field_value(5, "A\u{1}B\u{1F}\tC\u{7F}");
+        message.add_segment(obx);
+
+        let xml = XmlEncoder::new().encode(&message).unwrap();
+        assert!(xml.contains("A\\X01\\B"));
+
+        let decoded = XmlDecoder::new().decode(&xml).unwrap();
+        assert_eq!(decoded, message);
+    }
 }